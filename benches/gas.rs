@@ -0,0 +1,243 @@
+//! Gas used by the ERC20 entrypoints of the Stylus `MyToken` and of an OpenZeppelin Solidity
+//! token with the same ABI (`benches/solidity/BenchToken.sol`), both deployed on the devnet of
+//! the integration tests and called with the same arguments.
+//!
+//! `STYLUS_FIXTURE=dev-node cargo bench --features gas-bench --bench gas`
+//!
+//! | variable | |
+//! |---|---|
+//! | `GAS_BENCH_SOLIDITY_TOKEN` | deployed `BenchToken`, compiled with `solc` and deployed by alice when unset |
+//! | `OZ_CONTRACTS_PATH` | directory holding `@openzeppelin/contracts`, default `node_modules` |
+//! | `GAS_REPORT` | file the markdown report is also written to |
+//! | `STYLUS_PROGRAM_ADDRESS_PAUSABLE` | deployed `examples/compose_pausable.rs`, its `transfer` is measured against the one of `MyToken` |
+//!
+//! Gas is the `gasUsed` of each receipt, so it includes the 21000 base cost and the calldata,
+//! equal on both sides. Every scenario is measured once: gas does not depend on timing, only on
+//! whether the touched slots were written before, which each scenario fixes.
+use ethers::{
+    middleware::SignerMiddleware,
+    prelude::abigen,
+    providers::{Http, Middleware, Provider},
+    signers::LocalWallet,
+    types::{Address, Bytes, TransactionReceipt, TransactionRequest, U256},
+};
+use eyre::{eyre, Report};
+use std::path::Path;
+use std::process::Command;
+use std::str::FromStr;
+
+#[path = "../tests/util/mod.rs"]
+mod util;
+use util::fixture_init::{self, Devnet};
+
+/// Deployed Solidity token variable.
+const GAS_BENCH_SOLIDITY_TOKEN: &str = "GAS_BENCH_SOLIDITY_TOKEN";
+
+/// OpenZeppelin contracts variable.
+const OZ_CONTRACTS_PATH: &str = "OZ_CONTRACTS_PATH";
+
+/// Report file variable.
+const GAS_REPORT: &str = "GAS_REPORT";
+
+/// Preset of the pausable token measuring the pause check of a transfer.
+const PAUSABLE_PRESET: &str = "pausable";
+
+const AMOUNT: u64 = 1_000;
+
+abigen!(
+    BenchToken,
+    r#"[
+        function balanceOf(address account) external view returns (uint256)
+        function transfer(address recipient, uint256 amount) external returns (bool)
+        function approve(address spender, uint256 amount) external returns (bool)
+        function transferFrom(address sender, address recipient, uint256 amount) external returns (bool)
+        function mint(address account, uint256 amount) external
+        function burn(address account, uint256 amount) external
+    ]"#
+);
+
+abigen!(
+    PausableToken,
+    r#"[
+        function init() external
+        function owner() external view returns (address)
+        function mint(address account, uint256 value) external
+        function transfer(address to, uint256 value) external returns (bool)
+    ]"#
+);
+
+type Token = BenchToken<SignerMiddleware<Provider<Http>, LocalWallet>>;
+
+/// Gas of one scenario on both tokens.
+struct Measurement {
+    scenario: &'static str,
+    solidity: U256,
+    stylus: U256,
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Report> {
+    let devnet = fixture_init::devnet().await?;
+    let stylus = devnet.my_token().await?;
+    let solidity = solidity_token(devnet).await?;
+
+    let solidity_gas = scenarios(devnet, solidity).await?;
+    let stylus_gas = scenarios(devnet, stylus).await?;
+    let measurements: Vec<Measurement> = solidity_gas
+        .into_iter()
+        .zip(stylus_gas)
+        .map(|((scenario, solidity), (_, stylus))| Measurement {
+            scenario,
+            solidity,
+            stylus,
+        })
+        .collect();
+
+    let mut report = report(&measurements);
+    match devnet.program(PAUSABLE_PRESET).await {
+        Ok(pausable) => {
+            let (without_check, with_check) = pause_check(devnet, stylus, pausable).await?;
+            report.push_str(&pause_check_report(without_check, with_check));
+        }
+        Err(e) => eprintln!("pause check not measured: {}", e),
+    }
+    println!("{}", report);
+    if let Ok(path) = std::env::var(GAS_REPORT) {
+        std::fs::write(&path, &report).map_err(|e| eyre!("{} `{}` can not be written: {}", GAS_REPORT, path, e))?;
+    }
+    Ok(())
+}
+
+/// Gas of every scenario on `token`. Fresh accounts make the "new" scenarios write zero slots
+/// on every run, the others repeat a call first so they always update written slots.
+async fn scenarios(devnet: &Devnet, token: Address) -> Result<Vec<(&'static str, U256)>, Report> {
+    let alice_token = Token::new(token, devnet.alice_client.clone());
+    let bob_token = Token::new(token, devnet.bob_client.clone());
+    let alice = devnet.alice_client.address();
+    let bob = devnet.bob_client.address();
+    let amount = U256::from(AMOUNT);
+    let mut gas = Vec::new();
+
+    gas.push(("mint (new holder)", gas_used(alice_token.mint(fresh_account(), amount).send().await?.await?)?));
+    gas_used(alice_token.mint(alice, amount * 10).send().await?.await?)?;
+    gas.push(("mint", gas_used(alice_token.mint(alice, amount).send().await?.await?)?));
+
+    gas.push(("transfer (new recipient)", gas_used(alice_token.transfer(fresh_account(), amount).send().await?.await?)?));
+    gas_used(alice_token.transfer(bob, amount).send().await?.await?)?;
+    gas.push(("transfer", gas_used(alice_token.transfer(bob, amount).send().await?.await?)?));
+
+    gas_used(alice_token.approve(bob, amount).send().await?.await?)?;
+    gas.push(("approve", gas_used(alice_token.approve(bob, amount * 2).send().await?.await?)?));
+
+    gas_used(bob_token.transfer_from(alice, bob, amount).send().await?.await?)?;
+    gas.push(("transferFrom", gas_used(bob_token.transfer_from(alice, bob, amount).send().await?.await?)?));
+
+    gas.push(("burn", gas_used(alice_token.burn(alice, amount).send().await?.await?)?));
+    Ok(gas)
+}
+
+/// Gas of the same `transfer` (written slots) on `MyToken` and on the pausable token, which only
+/// adds the `when_not_paused` read of the `Flags` byte of `Erc20Pausable`.
+async fn pause_check(devnet: &Devnet, stylus: Address, pausable: Address) -> Result<(U256, U256), Report> {
+    let alice_token = Token::new(stylus, devnet.alice_client.clone());
+    let alice_pausable = PausableToken::new(pausable, devnet.alice_client.clone());
+    let alice = devnet.alice_client.address();
+    let bob = devnet.bob_client.address();
+    let amount = U256::from(AMOUNT);
+
+    if alice_pausable.owner().call().await? == Address::zero() {
+        gas_used(alice_pausable.init().send().await?.await?)?;
+    }
+    gas_used(alice_token.mint(alice, amount * 10).send().await?.await?)?;
+    gas_used(alice_pausable.mint(alice, amount * 10).send().await?.await?)?;
+
+    gas_used(alice_token.transfer(bob, amount).send().await?.await?)?;
+    let without_check = gas_used(alice_token.transfer(bob, amount).send().await?.await?)?;
+    gas_used(alice_pausable.transfer(bob, amount).send().await?.await?)?;
+    let with_check = gas_used(alice_pausable.transfer(bob, amount).send().await?.await?)?;
+    Ok((without_check, with_check))
+}
+
+/// Gas of a successful transaction.
+fn gas_used(receipt: Option<TransactionReceipt>) -> Result<U256, Report> {
+    let receipt = receipt.ok_or_else(|| eyre!("transaction returned no receipt"))?;
+    if receipt.status != Some(1.into()) {
+        return Err(eyre!("transaction {:?} reverted", receipt.transaction_hash));
+    }
+    receipt.gas_used.ok_or_else(|| eyre!("no gas used in receipt {:?}", receipt.transaction_hash))
+}
+
+/// Address no token has seen, its balance slot is zero.
+fn fresh_account() -> Address {
+    Address::from(rand::random::<[u8; 20]>())
+}
+
+/// The configured `BenchToken`, or one compiled and deployed by alice.
+async fn solidity_token(devnet: &Devnet) -> Result<Address, Report> {
+    if let Ok(value) = std::env::var(GAS_BENCH_SOLIDITY_TOKEN) {
+        return Address::from_str(value.trim())
+            .map_err(|e| eyre!("{} is not a valid address (`{}`): {}", GAS_BENCH_SOLIDITY_TOKEN, value, e));
+    }
+    let bytecode = compile_bench_token()?;
+    let tx = TransactionRequest::new().data(bytecode);
+    let receipt = devnet
+        .alice_client
+        .send_transaction(tx, None)
+        .await?
+        .await?
+        .ok_or_else(|| eyre!("BenchToken deployment returned no receipt"))?;
+    receipt
+        .contract_address
+        .ok_or_else(|| eyre!("BenchToken deployment {:?} created no contract", receipt.transaction_hash))
+}
+
+/// Creation code of `BenchToken`, built with the optimizer of `solc`.
+fn compile_bench_token() -> Result<Bytes, Report> {
+    let sources = Path::new(env!("CARGO_MANIFEST_DIR")).join("benches/solidity");
+    let oz_contracts = std::env::var(OZ_CONTRACTS_PATH).unwrap_or_else(|_| "node_modules".to_string());
+    let output = Command::new("solc")
+        .current_dir(&sources)
+        .args(["--optimize", "--optimize-runs", "200", "--bin", "--base-path", "."])
+        .args(["--include-path", &oz_contracts, "BenchToken.sol"])
+        .output()
+        .map_err(|e| eyre!("`solc` is required to deploy BenchToken, or set {}: {}", GAS_BENCH_SOLIDITY_TOKEN, e))?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if !output.status.success() {
+        return Err(eyre!(
+            "solc failed, {} must hold @openzeppelin/contracts:\n{}",
+            OZ_CONTRACTS_PATH,
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+    let binary = stdout
+        .lines()
+        .skip_while(|line| !line.contains(":BenchToken ="))
+        .skip_while(|line| !line.starts_with("Binary"))
+        .nth(1)
+        .ok_or_else(|| eyre!("no BenchToken binary in solc output:\n{}", stdout))?;
+    Ok(Bytes::from_str(binary.trim())?)
+}
+
+/// Markdown table of the transfer without and with the pause check, the difference in gas.
+fn pause_check_report(without_check: U256, with_check: U256) -> String {
+    let difference = with_check.as_u64() as i64 - without_check.as_u64() as i64;
+    format!(
+        "\n| scenario | MyToken | PausableToken | pause check |\n|---|---:|---:|---:|\n| transfer | {} | {} | {:+} |\n",
+        without_check, with_check, difference
+    )
+}
+
+/// Markdown table of the measurements, the difference relative to Solidity.
+fn report(measurements: &[Measurement]) -> String {
+    let mut report = String::from("| scenario | Solidity (OZ) | Stylus | difference |\n|---|---:|---:|---:|\n");
+    for measurement in measurements {
+        let solidity = measurement.solidity.as_u64() as i64;
+        let stylus = measurement.stylus.as_u64() as i64;
+        let difference = (stylus - solidity) as f64 * 100.0 / solidity as f64;
+        report.push_str(&format!(
+            "| {} | {} | {} | {:+.1}% |\n",
+            measurement.scenario, solidity, stylus, difference
+        ));
+    }
+    report
+}
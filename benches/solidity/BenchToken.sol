@@ -0,0 +1,18 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+import {ERC20} from "@openzeppelin/contracts/token/ERC20/ERC20.sol";
+
+/// OpenZeppelin ERC20 with the unrestricted `mint` and `burn` of `MyToken`, the Solidity side of
+/// `benches/gas.rs`.
+contract BenchToken is ERC20 {
+    constructor() ERC20("Bench token", "BENCH") {}
+
+    function mint(address account, uint256 value) external {
+        _mint(account, value);
+    }
+
+    function burn(address account, uint256 value) external {
+        _burn(account, value);
+    }
+}
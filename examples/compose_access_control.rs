@@ -0,0 +1,73 @@
+//! Composing `AccessControl`: minting goes through `Erc20Mintable` and needs `MINTER_ROLE`,
+//! at most `maxMintPerTx` tokens per mint, burning needs `BURNER_ROLE`; both roles are
+//! administered by the `DEFAULT_ADMIN_ROLE` granted to the account calling `init`, which
+//! `Initializable` lets run once.
+//!
+//! `cargo build --example compose_access_control --features export-abi` (prints the ABI when run).
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use alloc::vec::Vec;
+use oz_stylus_erc::access::access_control::{role_id, AccessControl, DEFAULT_ADMIN_ROLE};
+use oz_stylus_erc::extensions::erc20_mintable::Erc20Mintable;
+use oz_stylus_erc::tokens::erc20::{Erc20, Erc20Params, NoTransferHook};
+use oz_stylus_erc::utils::initializable::Initializable;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    msg,
+    prelude::*,
+};
+
+const BURNER_ROLE: &str = "BURNER_ROLE";
+
+pub struct RoleTokenParams;
+
+impl Erc20Params for RoleTokenParams {
+    const NAME: &'static str = "Role token";
+    const SYMBOL: &'static str = "ROLE";
+    const DECIMALS: u8 = 18;
+    type Hook = NoTransferHook;
+}
+
+sol_storage! {
+    #[entrypoint]
+    pub struct RoleToken {
+        #[borrow]
+        Erc20<RoleTokenParams> erc20;
+        #[borrow]
+        AccessControl access;
+        #[borrow]
+        Initializable initializable;
+        #[borrow]
+        Erc20Mintable mintable;
+    }
+}
+
+#[external]
+#[inherit(Erc20<RoleTokenParams>, AccessControl, Initializable, Erc20Mintable)]
+impl RoleToken {
+    pub fn init(&mut self) -> Result<(), Vec<u8>> {
+        self.initializable.initializer()?;
+        self.access.grant_role_internal(DEFAULT_ADMIN_ROLE, msg::sender());
+        Ok(())
+    }
+
+    pub fn mint(&mut self, account: Address, value: U256) -> Result<(), Vec<u8>> {
+        self.mintable.mint(&self.access, &mut self.erc20, account, value)
+    }
+
+    #[selector(name = "setMaxMintPerTx")]
+    pub fn set_max_mint_per_tx(&mut self, max_per_tx: U256) -> Result<(), Vec<u8>> {
+        self.mintable.set_max_per_tx(&self.access, max_per_tx)
+    }
+
+    pub fn burn(&mut self, account: Address, value: U256) -> Result<(), Vec<u8>> {
+        self.access.only_role(role_id(BURNER_ROLE))?;
+        Ok(self.erc20.burn(account, value)?)
+    }
+}
+
+#[cfg(feature = "export-abi")]
+fn main() {
+    print_abi("MIT OR Apache-2.0", "pragma solidity ^0.8.23;");
+}
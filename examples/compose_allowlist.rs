@@ -0,0 +1,92 @@
+//! A permissioned security token composing `Erc20Allowlist` with `AccessControl`: only investors
+//! allowlisted by a holder of `ALLOWLIST_ROLE` hold and move tokens, the issuer (`ISSUER_ROLE`)
+//! mints to them. Every external moving balances checks the allowlist first.
+//!
+//! `cargo build --example compose_allowlist --features export-abi` (prints the ABI when run).
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use alloc::vec::Vec;
+use oz_stylus_erc::access::access_control::{role_id, AccessControl, DEFAULT_ADMIN_ROLE};
+use oz_stylus_erc::extensions::erc20_allowlist::Erc20Allowlist;
+use oz_stylus_erc::tokens::erc20::{Erc20, Erc20Params, NoTransferHook};
+use oz_stylus_erc::utils::initializable::Initializable;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    msg,
+    prelude::*,
+};
+
+const ISSUER_ROLE: &str = "ISSUER_ROLE";
+
+pub struct SecurityTokenParams;
+
+impl Erc20Params for SecurityTokenParams {
+    const NAME: &'static str = "Security token";
+    const SYMBOL: &'static str = "SEC";
+    const DECIMALS: u8 = 0;
+    type Hook = NoTransferHook;
+}
+
+sol_storage! {
+    #[entrypoint]
+    pub struct SecurityToken {
+        #[borrow]
+        Erc20<SecurityTokenParams> erc20;
+        #[borrow]
+        Erc20Allowlist allowlist;
+        #[borrow]
+        AccessControl access;
+        #[borrow]
+        Initializable initializable;
+    }
+}
+
+#[external]
+#[inherit(Erc20<SecurityTokenParams>, Erc20Allowlist, AccessControl, Initializable)]
+impl SecurityToken {
+    pub fn init(&mut self) -> Result<(), Vec<u8>> {
+        self.initializable.initializer()?;
+        self.access.grant_role_internal(DEFAULT_ADMIN_ROLE, msg::sender());
+        Ok(())
+    }
+
+    pub fn allow_accounts(&mut self, accounts: Vec<Address>) -> Result<u32, Vec<u8>> {
+        self.allowlist.allow_accounts(&self.access, &accounts)
+    }
+
+    pub fn disallow_accounts(&mut self, accounts: Vec<Address>) -> Result<u32, Vec<u8>> {
+        self.allowlist.disallow_accounts(&self.access, &accounts)
+    }
+
+    pub fn mint(&mut self, account: Address, value: U256) -> Result<(), Vec<u8>> {
+        self.access.only_role(role_id(ISSUER_ROLE))?;
+        self.allowlist.when_allowed(Address::ZERO, account)?;
+        Ok(self.erc20.mint(account, value)?)
+    }
+
+    pub fn burn(&mut self, value: U256) -> Result<(), Vec<u8>> {
+        let account = msg::sender();
+        self.allowlist.when_allowed(account, Address::ZERO)?;
+        Ok(self.erc20.burn(account, value)?)
+    }
+
+    pub fn transfer(&mut self, to: Address, value: U256) -> Result<bool, Vec<u8>> {
+        let from = msg::sender();
+        self.allowlist.when_allowed(from, to)?;
+        self.erc20.transfer_internal(from, to, value)?;
+        Ok(true)
+    }
+
+    pub fn transfer_from(&mut self, from: Address, to: Address, value: U256) -> Result<bool, Vec<u8>> {
+        self.allowlist.when_allowed(from, to)?;
+        self.erc20.spend_allowance(from, msg::sender(), value)?;
+        self.erc20.transfer_internal(from, to, value)?;
+        Ok(true)
+    }
+}
+
+#[cfg(feature = "export-abi")]
+fn main() {
+    print_abi("MIT OR Apache-2.0", "pragma solidity ^0.8.23;");
+}
@@ -0,0 +1,74 @@
+//! Composing `erc20_approve_by_sig`: the extension verifies the ECDSA or EIP-1271 signature and
+//! consumes the unordered nonce in the token's `ReplayGuard`, the token hands over its `Erc20`
+//! to set the allowance.
+//!
+//! `cargo build --example compose_approve_by_sig --features export-abi` (prints the ABI when run).
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use alloc::vec::Vec;
+use oz_stylus_erc::extensions::erc20_approve_by_sig;
+use oz_stylus_erc::tokens::erc20::{Erc20, Erc20Params, NoTransferHook};
+use oz_stylus_erc::utils::eip712::{self, Eip712Domain};
+use oz_stylus_erc::utils::replay_guard::ReplayGuard;
+use stylus_sdk::{
+    abi::Bytes,
+    alloy_primitives::{Address, U256},
+    prelude::*,
+};
+
+pub struct ApproveBySigTokenParams;
+
+impl Erc20Params for ApproveBySigTokenParams {
+    const NAME: &'static str = "Approve by signature token";
+    const SYMBOL: &'static str = "ABS";
+    const DECIMALS: u8 = 18;
+    type Hook = NoTransferHook;
+}
+
+sol_storage! {
+    #[entrypoint]
+    pub struct ApproveBySigToken {
+        #[borrow]
+        Erc20<ApproveBySigTokenParams> erc20;
+        #[borrow]
+        ReplayGuard replay_guard;
+    }
+}
+
+#[external]
+#[inherit(Erc20<ApproveBySigTokenParams>, ReplayGuard)]
+impl ApproveBySigToken {
+    #[allow(clippy::too_many_arguments)]
+    pub fn approve_by_sig(
+        &mut self,
+        owner: Address,
+        spender: Address,
+        value: U256,
+        nonce: U256,
+        deadline: U256,
+        signature: Bytes,
+    ) -> Result<(), Vec<u8>> {
+        erc20_approve_by_sig::approve_by_sig(
+            &mut self.erc20,
+            &mut self.replay_guard,
+            owner,
+            spender,
+            value,
+            nonce,
+            deadline,
+            &signature,
+        )
+    }
+
+    /// ERC-5267 domain of the signatures accepted by the token, for wallets.
+    #[selector(name = "eip712Domain")]
+    pub fn eip712_domain(&self) -> Result<Eip712Domain, Vec<u8>> {
+        Ok(eip712::erc20_domain::<ApproveBySigTokenParams>())
+    }
+}
+
+#[cfg(feature = "export-abi")]
+fn main() {
+    print_abi("MIT OR Apache-2.0", "pragma solidity ^0.8.23;");
+}
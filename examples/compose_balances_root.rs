@@ -0,0 +1,65 @@
+//! Composing `Erc20BalancesRoot` with `Ownable`: the owner commits the balances merkle root
+//! computed off-chain at a past block, the views are inherited.
+//!
+//! `cargo build --example compose_balances_root --features export-abi` (prints the ABI when run).
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use alloc::vec::Vec;
+use oz_stylus_erc::access::ownable::Ownable;
+use oz_stylus_erc::extensions::erc20_balances_root::Erc20BalancesRoot;
+use oz_stylus_erc::tokens::erc20::{Erc20, Erc20Params, NoTransferHook};
+use oz_stylus_erc::utils::initializable::Initializable;
+use stylus_sdk::{
+    alloy_primitives::{Address, B256, U256},
+    msg,
+    prelude::*,
+};
+
+pub struct AttestedTokenParams;
+
+impl Erc20Params for AttestedTokenParams {
+    const NAME: &'static str = "Attested token";
+    const SYMBOL: &'static str = "ATT";
+    const DECIMALS: u8 = 18;
+    type Hook = NoTransferHook;
+}
+
+sol_storage! {
+    #[entrypoint]
+    pub struct AttestedToken {
+        #[borrow]
+        Erc20<AttestedTokenParams> erc20;
+        #[borrow]
+        Erc20BalancesRoot balances_root;
+        #[borrow]
+        Ownable ownable;
+        #[borrow]
+        Initializable initializable;
+    }
+}
+
+#[external]
+#[inherit(Erc20<AttestedTokenParams>, Erc20BalancesRoot, Ownable, Initializable)]
+impl AttestedToken {
+    pub fn init(&mut self) -> Result<(), Vec<u8>> {
+        self.initializable.initializer()?;
+        self.ownable.transfer_ownership_internal(msg::sender());
+        Ok(())
+    }
+
+    pub fn mint(&mut self, account: Address, value: U256) -> Result<(), Vec<u8>> {
+        self.ownable.only_owner()?;
+        Ok(self.erc20.mint(account, value)?)
+    }
+
+    pub fn commit_balances_root(&mut self, root: B256, block_number: U256) -> Result<(), Vec<u8>> {
+        self.ownable.only_owner()?;
+        Ok(self.balances_root.commit_balances_root(root, block_number)?)
+    }
+}
+
+#[cfg(feature = "export-abi")]
+fn main() {
+    print_abi("MIT OR Apache-2.0", "pragma solidity ^0.8.23;");
+}
@@ -0,0 +1,69 @@
+//! Airdrop token composing `extensions::erc20_batch_transfer`: the owner mints to a list of
+//! recipients with `airdrop`, choosing per call between standard {Transfer} events and one
+//! aggregated {BatchTransfer}; holders pay out to many accounts with `batchTransfer`, always
+//! with standard events so wallets see the tokens arrive.
+//!
+//! `cargo build --example compose_batch_transfer --features export-abi` (prints the ABI when run).
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use alloc::vec::Vec;
+use oz_stylus_erc::access::ownable::Ownable;
+use oz_stylus_erc::extensions::erc20_batch_transfer::{self, BatchEvents};
+use oz_stylus_erc::tokens::erc20::{Erc20, Erc20Params, NoTransferHook};
+use oz_stylus_erc::utils::initializable::Initializable;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    msg,
+    prelude::*,
+};
+
+pub struct AirdropTokenParams;
+
+impl Erc20Params for AirdropTokenParams {
+    const NAME: &'static str = "Airdrop token";
+    const SYMBOL: &'static str = "DROP";
+    const DECIMALS: u8 = 18;
+    type Hook = NoTransferHook;
+}
+
+sol_storage! {
+    #[entrypoint]
+    pub struct AirdropToken {
+        #[borrow]
+        Erc20<AirdropTokenParams> erc20;
+        #[borrow]
+        Ownable ownable;
+        #[borrow]
+        Initializable initializable;
+    }
+}
+
+#[external]
+#[inherit(Erc20<AirdropTokenParams>, Ownable, Initializable)]
+impl AirdropToken {
+    pub fn init(&mut self) -> Result<(), Vec<u8>> {
+        self.initializable.initializer()?;
+        self.ownable.transfer_ownership_internal(msg::sender());
+        Ok(())
+    }
+
+    /// Mints `values[i]` to `to[i]`, `aggregated` emits one {BatchTransfer} instead of a
+    /// {Transfer} per recipient. Returns the total minted.
+    pub fn airdrop(&mut self, to: Vec<Address>, values: Vec<U256>, aggregated: bool) -> Result<U256, Vec<u8>> {
+        self.ownable.only_owner()?;
+        let events = if aggregated { BatchEvents::Aggregated } else { BatchEvents::PerTransfer };
+        erc20_batch_transfer::batch_mint(&mut self.erc20, &to, &values, events)
+    }
+
+    /// Moves `values[i]` of the caller's tokens to `to[i]`. Returns the total moved.
+    #[selector(name = "batchTransfer")]
+    pub fn batch_transfer(&mut self, to: Vec<Address>, values: Vec<U256>) -> Result<U256, Vec<u8>> {
+        erc20_batch_transfer::batch_transfer(&mut self.erc20, msg::sender(), &to, &values, BatchEvents::PerTransfer)
+    }
+}
+
+#[cfg(feature = "export-abi")]
+fn main() {
+    print_abi("MIT OR Apache-2.0", "pragma solidity ^0.8.23;");
+}
@@ -0,0 +1,101 @@
+//! A regulated stablecoin composing `Erc20Blocklist` with `AccessControl`: holders of
+//! `BLOCKLIST_ROLE` block sanctioned accounts, which can then neither send, receive nor approve;
+//! minting needs `MINTER_ROLE`. Every external moving balances checks the blocklist first.
+//!
+//! `cargo build --example compose_blocklist --features export-abi` (prints the ABI when run).
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use alloc::vec::Vec;
+use oz_stylus_erc::access::access_control::{role_id, AccessControl, DEFAULT_ADMIN_ROLE};
+use oz_stylus_erc::extensions::erc20_blocklist::Erc20Blocklist;
+use oz_stylus_erc::tokens::erc20::{Erc20, Erc20Params, NoTransferHook};
+use oz_stylus_erc::utils::initializable::Initializable;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    msg,
+    prelude::*,
+};
+
+const MINTER_ROLE: &str = "MINTER_ROLE";
+
+pub struct RegulatedUsdParams;
+
+impl Erc20Params for RegulatedUsdParams {
+    const NAME: &'static str = "Regulated USD";
+    const SYMBOL: &'static str = "rUSD";
+    const DECIMALS: u8 = 6;
+    type Hook = NoTransferHook;
+}
+
+sol_storage! {
+    #[entrypoint]
+    pub struct RegulatedUsd {
+        #[borrow]
+        Erc20<RegulatedUsdParams> erc20;
+        #[borrow]
+        Erc20Blocklist blocklist;
+        #[borrow]
+        AccessControl access;
+        #[borrow]
+        Initializable initializable;
+    }
+}
+
+#[external]
+#[inherit(Erc20<RegulatedUsdParams>, Erc20Blocklist, AccessControl, Initializable)]
+impl RegulatedUsd {
+    pub fn init(&mut self) -> Result<(), Vec<u8>> {
+        self.initializable.initializer()?;
+        self.access.grant_role_internal(DEFAULT_ADMIN_ROLE, msg::sender());
+        Ok(())
+    }
+
+    pub fn block_account(&mut self, account: Address) -> Result<bool, Vec<u8>> {
+        self.blocklist.block_account(&self.access, account)
+    }
+
+    pub fn unblock_account(&mut self, account: Address) -> Result<bool, Vec<u8>> {
+        self.blocklist.unblock_account(&self.access, account)
+    }
+
+    pub fn mint(&mut self, account: Address, value: U256) -> Result<(), Vec<u8>> {
+        self.access.only_role(role_id(MINTER_ROLE))?;
+        self.blocklist.when_not_blocked(Address::ZERO, account)?;
+        Ok(self.erc20.mint(account, value)?)
+    }
+
+    pub fn burn(&mut self, value: U256) -> Result<(), Vec<u8>> {
+        let account = msg::sender();
+        self.blocklist.when_not_blocked(account, Address::ZERO)?;
+        Ok(self.erc20.burn(account, value)?)
+    }
+
+    pub fn transfer(&mut self, to: Address, value: U256) -> Result<bool, Vec<u8>> {
+        let from = msg::sender();
+        self.blocklist.when_not_blocked(from, to)?;
+        self.erc20.transfer_internal(from, to, value)?;
+        Ok(true)
+    }
+
+    pub fn transfer_from(&mut self, from: Address, to: Address, value: U256) -> Result<bool, Vec<u8>> {
+        let spender = msg::sender();
+        self.blocklist.when_not_blocked(from, to)?;
+        self.blocklist.check_not_blocked(spender)?;
+        self.erc20.spend_allowance(from, spender, value)?;
+        self.erc20.transfer_internal(from, to, value)?;
+        Ok(true)
+    }
+
+    pub fn approve(&mut self, spender: Address, value: U256) -> Result<bool, Vec<u8>> {
+        let owner = msg::sender();
+        self.blocklist.when_not_blocked(owner, spender)?;
+        self.erc20.approve_internal(owner, spender, value)?;
+        Ok(true)
+    }
+}
+
+#[cfg(feature = "export-abi")]
+fn main() {
+    print_abi("MIT OR Apache-2.0", "pragma solidity ^0.8.23;");
+}
@@ -0,0 +1,74 @@
+//! Composing `Erc20FixedCap` with `Ownable`: the cap of one million tokens is compiled in with
+//! `Erc20CapParams`, nobody can change it, and the owner mints through `checked_mint`, which
+//! rejects a mint over the cap before touching balances. Tokens choosing their cap at
+//! deployment compose `Erc20Cap` instead and call `set_cap` once from `init`.
+//!
+//! `cargo build --example compose_cap --features export-abi` (prints the ABI when run).
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use oz_stylus_erc::access::ownable::Ownable;
+use oz_stylus_erc::errors::OzError;
+use oz_stylus_erc::extensions::erc20_cap::{Erc20CapParams, Erc20FixedCap};
+use oz_stylus_erc::tokens::erc20::{Erc20, Erc20Params, NoTransferHook};
+use oz_stylus_erc::utils::initializable::Initializable;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    msg,
+    prelude::*,
+};
+
+pub struct CappedTokenParams;
+
+impl Erc20Params for CappedTokenParams {
+    const NAME: &'static str = "Capped token";
+    const SYMBOL: &'static str = "CAP";
+    const DECIMALS: u8 = 18;
+    type Hook = NoTransferHook;
+}
+
+impl Erc20CapParams for CappedTokenParams {
+    /// 1_000_000 * 10^18
+    const CAP: U256 = U256::from_limbs([0x1bcecceda1000000, 0xd3c2, 0, 0]);
+}
+
+sol_storage! {
+    #[entrypoint]
+    pub struct CappedToken {
+        #[borrow]
+        Erc20<CappedTokenParams> erc20;
+        #[borrow]
+        Erc20FixedCap<CappedTokenParams> cap;
+        #[borrow]
+        Ownable ownable;
+        #[borrow]
+        Initializable initializable;
+    }
+}
+
+#[external]
+#[inherit(Erc20<CappedTokenParams>, Erc20FixedCap<CappedTokenParams>, Ownable, Initializable)]
+impl CappedToken {
+    pub fn init(&mut self) -> Result<(), OzError> {
+        self.initializable.initializer()?;
+        self.ownable.transfer_ownership_internal(msg::sender());
+        Ok(())
+    }
+
+    pub fn mint(&mut self, account: Address, value: U256) -> Result<(), OzError> {
+        self.ownable.only_owner()?;
+        self.cap.checked_mint(&mut self.erc20, account, value)?;
+        Ok(())
+    }
+
+    // burns free room under the cap for later mints
+    pub fn burn(&mut self, value: U256) -> Result<(), OzError> {
+        self.erc20.burn(msg::sender(), value)?;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "export-abi")]
+fn main() {
+    print_abi("MIT OR Apache-2.0", "pragma solidity ^0.8.23;");
+}
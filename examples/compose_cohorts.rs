@@ -0,0 +1,82 @@
+//! Composing `Erc20Cohorts` with `Ownable`: every external moving balances is overridden to call
+//! `move_balance` after `Erc20`, the owner tags accounts into cohorts.
+//!
+//! `cargo build --example compose_cohorts --features export-abi` (prints the ABI when run).
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use alloc::vec::Vec;
+use oz_stylus_erc::access::ownable::Ownable;
+use oz_stylus_erc::extensions::erc20_cohorts::Erc20Cohorts;
+use oz_stylus_erc::tokens::erc20::{Erc20, Erc20Params, NoTransferHook};
+use oz_stylus_erc::utils::initializable::Initializable;
+use stylus_sdk::{
+    alloy_primitives::{Address, B256, U256},
+    msg,
+    prelude::*,
+};
+
+pub struct CohortTokenParams;
+
+impl Erc20Params for CohortTokenParams {
+    const NAME: &'static str = "Cohort token";
+    const SYMBOL: &'static str = "COH";
+    const DECIMALS: u8 = 18;
+    type Hook = NoTransferHook;
+}
+
+sol_storage! {
+    #[entrypoint]
+    pub struct CohortToken {
+        #[borrow]
+        Erc20<CohortTokenParams> erc20;
+        #[borrow]
+        Erc20Cohorts cohorts;
+        #[borrow]
+        Ownable ownable;
+        #[borrow]
+        Initializable initializable;
+    }
+}
+
+#[external]
+#[inherit(Erc20<CohortTokenParams>, Erc20Cohorts, Ownable, Initializable)]
+impl CohortToken {
+    pub fn init(&mut self) -> Result<(), Vec<u8>> {
+        self.initializable.initializer()?;
+        self.ownable.transfer_ownership_internal(msg::sender());
+        Ok(())
+    }
+
+    pub fn mint(&mut self, account: Address, value: U256) -> Result<(), Vec<u8>> {
+        self.ownable.only_owner()?;
+        self.erc20.mint(account, value)?;
+        self.cohorts.move_balance(Address::ZERO, account, value);
+        Ok(())
+    }
+
+    pub fn transfer(&mut self, to: Address, value: U256) -> Result<bool, Vec<u8>> {
+        let from = msg::sender();
+        self.erc20.transfer_internal(from, to, value)?;
+        self.cohorts.move_balance(from, to, value);
+        Ok(true)
+    }
+
+    pub fn transfer_from(&mut self, from: Address, to: Address, value: U256) -> Result<bool, Vec<u8>> {
+        self.erc20.spend_allowance(from, msg::sender(), value)?;
+        self.erc20.transfer_internal(from, to, value)?;
+        self.cohorts.move_balance(from, to, value);
+        Ok(true)
+    }
+
+    /// Tags `account` into `cohort`, e.g. `keccak256("TEAM")`.
+    pub fn set_cohort(&mut self, account: Address, cohort: B256) -> Result<(), Vec<u8>> {
+        self.ownable.only_owner()?;
+        self.cohorts.set_cohort(&self.erc20, account, cohort)
+    }
+}
+
+#[cfg(feature = "export-abi")]
+fn main() {
+    print_abi("MIT OR Apache-2.0", "pragma solidity ^0.8.23;");
+}
@@ -0,0 +1,85 @@
+//! Composing `CommitRevealSale` with `Ownable`: the owner configures the sale and withdraws the
+//! proceeds, buyers commit, reveal and claim tokens minted by the sale.
+//!
+//! `cargo build --example compose_commit_reveal_sale --features export-abi` (prints the ABI when run).
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use alloc::vec::Vec;
+use oz_stylus_erc::access::ownable::Ownable;
+use oz_stylus_erc::finance::commit_reveal_sale::CommitRevealSale;
+use oz_stylus_erc::tokens::erc20::{Erc20, Erc20Params, NoTransferHook};
+use oz_stylus_erc::utils::initializable::Initializable;
+use stylus_sdk::{
+    alloy_primitives::{Address, B256, U256},
+    msg,
+    prelude::*,
+};
+
+pub struct LaunchTokenParams;
+
+impl Erc20Params for LaunchTokenParams {
+    const NAME: &'static str = "Launch token";
+    const SYMBOL: &'static str = "LCH";
+    const DECIMALS: u8 = 18;
+    type Hook = NoTransferHook;
+}
+
+sol_storage! {
+    #[entrypoint]
+    pub struct LaunchToken {
+        #[borrow]
+        Erc20<LaunchTokenParams> erc20;
+        #[borrow]
+        CommitRevealSale sale;
+        #[borrow]
+        Ownable ownable;
+        #[borrow]
+        Initializable initializable;
+    }
+}
+
+#[external]
+#[inherit(Erc20<LaunchTokenParams>, CommitRevealSale, Ownable, Initializable)]
+impl LaunchToken {
+    pub fn init(&mut self) -> Result<(), Vec<u8>> {
+        self.initializable.initializer()?;
+        self.ownable.transfer_ownership_internal(msg::sender());
+        Ok(())
+    }
+
+    pub fn configure_sale(
+        &mut self,
+        payment_token: Address,
+        price: U256,
+        sale_supply: U256,
+        commit_start: U256,
+        commit_end: U256,
+        reveal_end: U256,
+    ) -> Result<(), Vec<u8>> {
+        self.ownable.only_owner()?;
+        Ok(self.sale.configure(payment_token, price, sale_supply, commit_start, commit_end, reveal_end)?)
+    }
+
+    pub fn commit(&mut self, commitment: B256, deposit: U256) -> Result<(), Vec<u8>> {
+        self.sale.commit(commitment, deposit)
+    }
+
+    pub fn reveal(&mut self, amount: U256, salt: B256) -> Result<(), Vec<u8>> {
+        Ok(self.sale.reveal(amount, salt)?)
+    }
+
+    pub fn claim(&mut self) -> Result<U256, Vec<u8>> {
+        self.sale.claim(&mut self.erc20)
+    }
+
+    pub fn withdraw_proceeds(&mut self, to: Address) -> Result<U256, Vec<u8>> {
+        self.ownable.only_owner()?;
+        self.sale.withdraw_proceeds(to)
+    }
+}
+
+#[cfg(feature = "export-abi")]
+fn main() {
+    print_abi("MIT OR Apache-2.0", "pragma solidity ^0.8.23;");
+}
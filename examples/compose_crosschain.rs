@@ -0,0 +1,68 @@
+//! Composing `Erc20Crosschain`: the token exposes the ERC-7802 entry points and passes its
+//! `Erc20` to the extension, which checks the caller is the bridge set at `init`, and reports
+//! ERC-7802 through `supportsInterface`.
+//!
+//! `cargo build --example compose_crosschain --features export-abi` (prints the ABI when run).
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use alloc::vec::Vec;
+use oz_stylus_erc::extensions::erc20_crosschain::Erc20Crosschain;
+use oz_stylus_erc::tokens::erc20::{Erc20, Erc20Params, NoTransferHook};
+use oz_stylus_erc::utils::erc165::Erc165;
+use oz_stylus_erc::utils::initializable::Initializable;
+use stylus_sdk::{
+    alloy_primitives::{Address, FixedBytes, U256},
+    prelude::*,
+};
+
+pub struct BridgedTokenParams;
+
+impl Erc20Params for BridgedTokenParams {
+    const NAME: &'static str = "Bridged token";
+    const SYMBOL: &'static str = "BRT";
+    const DECIMALS: u8 = 18;
+    type Hook = NoTransferHook;
+}
+
+sol_storage! {
+    #[entrypoint]
+    pub struct BridgedToken {
+        #[borrow]
+        Erc20<BridgedTokenParams> erc20;
+        #[borrow]
+        Erc20Crosschain crosschain;
+        #[borrow]
+        Initializable initializable;
+    }
+}
+
+#[external]
+#[inherit(Erc20<BridgedTokenParams>, Erc20Crosschain, Initializable)]
+impl BridgedToken {
+    pub fn init(&mut self, bridge: Address) -> Result<(), Vec<u8>> {
+        self.initializable.initializer()?;
+        self.crosschain.set_bridge(bridge);
+        Ok(())
+    }
+
+    /// ERC-20 and ERC-7802, the inherited `supportsInterface` only knows ERC-20.
+    #[selector(name = "supportsInterface")]
+    pub fn supports_interface(&self, interface_id: FixedBytes<4>) -> Result<bool, Vec<u8>> {
+        Ok(<Erc20<BridgedTokenParams> as Erc165>::supports_interface(interface_id)
+            || <Erc20Crosschain as Erc165>::supports_interface(interface_id))
+    }
+
+    pub fn crosschain_mint(&mut self, to: Address, amount: U256) -> Result<(), Vec<u8>> {
+        self.crosschain.crosschain_mint(&mut self.erc20, to, amount)
+    }
+
+    pub fn crosschain_burn(&mut self, from: Address, amount: U256) -> Result<(), Vec<u8>> {
+        self.crosschain.crosschain_burn(&mut self.erc20, from, amount)
+    }
+}
+
+#[cfg(feature = "export-abi")]
+fn main() {
+    print_abi("MIT OR Apache-2.0", "pragma solidity ^0.8.23;");
+}
@@ -0,0 +1,101 @@
+//! Game items built on `Erc1155` with `Erc2981` royalties and `Ownable`: the owner mints items and
+//! sets a default royalty and the royalty of each id, holders (or their operators) burn them.
+//!
+//! `cargo build --example compose_erc1155 --features export-abi` (prints the ABI when run).
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use alloc::vec::Vec;
+use oz_stylus_erc::access::ownable::Ownable;
+use oz_stylus_erc::extensions::erc2981::Erc2981;
+use oz_stylus_erc::tokens::erc1155::{Erc1155, Erc1155Params};
+use oz_stylus_erc::utils::erc165::Erc165;
+use oz_stylus_erc::utils::initializable::Initializable;
+use stylus_sdk::{
+    abi::Bytes,
+    alloy_primitives::{Address, FixedBytes, U256},
+    msg,
+    prelude::*,
+};
+
+pub struct GameItemsParams;
+
+impl Erc1155Params for GameItemsParams {
+    const URI: &'static str = "https://game.example/api/item/{id}.json";
+}
+
+sol_storage! {
+    #[entrypoint]
+    pub struct GameItems {
+        #[borrow]
+        Erc1155<GameItemsParams> erc1155;
+        #[borrow]
+        Erc2981 royalties;
+        #[borrow]
+        Ownable ownable;
+        #[borrow]
+        Initializable initializable;
+    }
+}
+
+#[external]
+#[inherit(Erc1155<GameItemsParams>, Erc2981, Ownable, Initializable)]
+impl GameItems {
+    pub fn init(&mut self) -> Result<(), Vec<u8>> {
+        self.initializable.initializer()?;
+        self.ownable.transfer_ownership_internal(msg::sender());
+        Ok(())
+    }
+
+    /// ERC-1155 and ERC-2981, the inherited `supportsInterface` only knows ERC-1155.
+    #[selector(name = "supportsInterface")]
+    pub fn supports_interface(&self, interface_id: FixedBytes<4>) -> Result<bool, Vec<u8>> {
+        Ok(<Erc1155<GameItemsParams> as Erc165>::supports_interface(interface_id)
+            || <Erc2981 as Erc165>::supports_interface(interface_id))
+    }
+
+    pub fn mint(&mut self, to: Address, id: U256, value: U256, data: Bytes) -> Result<(), Vec<u8>> {
+        self.ownable.only_owner()?;
+        self.erc1155.mint(to, id, value, &data)
+    }
+
+    pub fn mint_batch(&mut self, to: Address, ids: Vec<U256>, values: Vec<U256>, data: Bytes) -> Result<(), Vec<u8>> {
+        self.ownable.only_owner()?;
+        self.erc1155.mint_batch(to, ids, values, &data)
+    }
+
+    pub fn burn(&mut self, account: Address, id: U256, value: U256) -> Result<(), Vec<u8>> {
+        self.erc1155.check_approved(account, msg::sender())?;
+        Ok(self.erc1155.burn(account, id, value)?)
+    }
+
+    /// Royalty of `fee_numerator` basis points paid to `receiver` on sales of `id`.
+    pub fn set_token_royalty(&mut self, id: U256, receiver: Address, fee_numerator: U256) -> Result<(), Vec<u8>> {
+        self.ownable.only_owner()?;
+        Ok(self.royalties.set_token_royalty(id, receiver, fee_numerator)?)
+    }
+
+    /// Removes the royalty of `id`, which falls back to the default royalty.
+    pub fn reset_token_royalty(&mut self, id: U256) -> Result<(), Vec<u8>> {
+        self.ownable.only_owner()?;
+        self.royalties.reset_token_royalty(id);
+        Ok(())
+    }
+
+    /// Royalty of `fee_numerator` basis points paid to `receiver` on sales of ids without their own.
+    pub fn set_default_royalty(&mut self, receiver: Address, fee_numerator: U256) -> Result<(), Vec<u8>> {
+        self.ownable.only_owner()?;
+        Ok(self.royalties.set_default_royalty(receiver, fee_numerator)?)
+    }
+
+    pub fn delete_default_royalty(&mut self) -> Result<(), Vec<u8>> {
+        self.ownable.only_owner()?;
+        self.royalties.delete_default_royalty();
+        Ok(())
+    }
+}
+
+#[cfg(feature = "export-abi")]
+fn main() {
+    print_abi("MIT OR Apache-2.0", "pragma solidity ^0.8.23;");
+}
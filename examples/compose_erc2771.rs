@@ -0,0 +1,92 @@
+//! Composing `Erc2771Context` with `Ownable`: `transfer`, `transferFrom` and `approve` act for
+//! the user appended by the trusted forwarder, so a relayer can pay the gas of its users.
+//!
+//! `cargo build --example compose_erc2771 --features export-abi` (prints the ABI when run).
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use alloc::vec::Vec;
+use oz_stylus_erc::access::ownable::Ownable;
+use oz_stylus_erc::tokens::erc20::{Erc20, Erc20Params, NoTransferHook};
+use oz_stylus_erc::utils::erc2771_context::Erc2771Context;
+use oz_stylus_erc::utils::initializable::Initializable;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    msg,
+    prelude::*,
+};
+
+/// Calldata lengths of the forwarder aware externals: selector and one word per argument.
+const TRANSFER_ARGS_LEN: usize = 4 + 2 * 32;
+const TRANSFER_FROM_ARGS_LEN: usize = 4 + 3 * 32;
+const APPROVE_ARGS_LEN: usize = 4 + 2 * 32;
+
+pub struct GaslessTokenParams;
+
+impl Erc20Params for GaslessTokenParams {
+    const NAME: &'static str = "Gasless token";
+    const SYMBOL: &'static str = "GAS";
+    const DECIMALS: u8 = 18;
+    type Hook = NoTransferHook;
+}
+
+sol_storage! {
+    #[entrypoint]
+    pub struct GaslessToken {
+        #[borrow]
+        Erc20<GaslessTokenParams> erc20;
+        #[borrow]
+        Erc2771Context context;
+        #[borrow]
+        Ownable ownable;
+        #[borrow]
+        Initializable initializable;
+    }
+}
+
+#[external]
+#[inherit(Erc20<GaslessTokenParams>, Erc2771Context, Ownable, Initializable)]
+impl GaslessToken {
+    pub fn init(&mut self, forwarder: Address) -> Result<(), Vec<u8>> {
+        self.initializable.initializer()?;
+        self.ownable.transfer_ownership_internal(msg::sender());
+        self.context.set_trusted_forwarder(forwarder)?;
+        Ok(())
+    }
+
+    pub fn set_trusted_forwarder(&mut self, forwarder: Address) -> Result<(), Vec<u8>> {
+        self.ownable.only_owner()?;
+        self.context.set_trusted_forwarder(forwarder)?;
+        Ok(())
+    }
+
+    pub fn mint(&mut self, account: Address, value: U256) -> Result<(), Vec<u8>> {
+        self.ownable.only_owner()?;
+        self.erc20.mint(account, value)?;
+        Ok(())
+    }
+
+    pub fn transfer(&mut self, to: Address, value: U256) -> Result<bool, Vec<u8>> {
+        let from = self.context.msg_sender(TRANSFER_ARGS_LEN);
+        self.erc20.transfer_internal(from, to, value)?;
+        Ok(true)
+    }
+
+    pub fn transfer_from(&mut self, from: Address, to: Address, value: U256) -> Result<bool, Vec<u8>> {
+        let spender = self.context.msg_sender(TRANSFER_FROM_ARGS_LEN);
+        self.erc20.spend_allowance(from, spender, value)?;
+        self.erc20.transfer_internal(from, to, value)?;
+        Ok(true)
+    }
+
+    pub fn approve(&mut self, spender: Address, value: U256) -> Result<bool, Vec<u8>> {
+        let owner = self.context.msg_sender(APPROVE_ARGS_LEN);
+        self.erc20.approve_internal(owner, spender, value)?;
+        Ok(true)
+    }
+}
+
+#[cfg(feature = "export-abi")]
+fn main() {
+    print_abi("MIT OR Apache-2.0", "pragma solidity ^0.8.23;");
+}
@@ -0,0 +1,158 @@
+//! A fee charging vault: `Erc4626` with a `VaultConfig` taking 0.5% on deposits and 1% on
+//! withdrawals, paid to a recipient set by the owner, and capping the assets under management.
+//! The ERC-4626 functions needing the shares are exposed passing the `Erc20`.
+//!
+//! `cargo build --example compose_erc4626 --features export-abi` (prints the ABI when run).
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use alloc::vec::Vec;
+use oz_stylus_erc::access::ownable::Ownable;
+use oz_stylus_erc::extensions::erc4626::{Erc4626, VaultConfig};
+use oz_stylus_erc::tokens::erc20::{Erc20, Erc20Params, NoTransferHook};
+use oz_stylus_erc::utils::initializable::Initializable;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    msg,
+    prelude::*,
+};
+
+/// Assets the vault accepts in total.
+const MAX_TOTAL_ASSETS: u128 = 10_000_000_000_000_000_000_000_000;
+
+pub struct FeeVaultParams;
+
+impl Erc20Params for FeeVaultParams {
+    const NAME: &'static str = "Fee vault share";
+    const SYMBOL: &'static str = "fvUSD";
+    const DECIMALS: u8 = 18;
+    type Hook = NoTransferHook;
+}
+
+pub struct FeeVaultConfig;
+
+impl VaultConfig for FeeVaultConfig {
+    fn entry_fee_bps() -> u64 {
+        50
+    }
+
+    fn exit_fee_bps() -> u64 {
+        100
+    }
+
+    fn max_deposit_override(_receiver: Address, total_assets: U256) -> Option<U256> {
+        Some(U256::from(MAX_TOTAL_ASSETS).saturating_sub(total_assets))
+    }
+}
+
+sol_storage! {
+    #[entrypoint]
+    pub struct FeeVault {
+        #[borrow]
+        Erc20<FeeVaultParams> erc20;
+        #[borrow]
+        Erc4626<FeeVaultConfig> vault;
+        #[borrow]
+        Ownable ownable;
+        #[borrow]
+        Initializable initializable;
+    }
+}
+
+/// ERC-4626 names are pinned, aggregators call them by selector.
+#[external]
+#[inherit(Erc20<FeeVaultParams>, Erc4626<FeeVaultConfig>, Ownable, Initializable)]
+impl FeeVault {
+    pub fn init(&mut self, asset: Address, fee_recipient: Address) -> Result<(), Vec<u8>> {
+        self.initializable.initializer()?;
+        self.ownable.transfer_ownership_internal(msg::sender());
+        Ok(self.vault.set_asset(asset, fee_recipient)?)
+    }
+
+    #[selector(name = "setFeeRecipient")]
+    pub fn set_fee_recipient(&mut self, fee_recipient: Address) -> Result<(), Vec<u8>> {
+        self.ownable.only_owner()?;
+        self.vault.set_fee_recipient(fee_recipient);
+        Ok(())
+    }
+
+    #[selector(name = "totalAssets")]
+    pub fn total_assets(&self) -> Result<U256, Vec<u8>> {
+        self.vault.total_assets()
+    }
+
+    #[selector(name = "convertToShares")]
+    pub fn convert_to_shares(&self, assets: U256) -> Result<U256, Vec<u8>> {
+        self.vault.convert_to_shares(&self.erc20, assets)
+    }
+
+    #[selector(name = "convertToAssets")]
+    pub fn convert_to_assets(&self, shares: U256) -> Result<U256, Vec<u8>> {
+        self.vault.convert_to_assets(&self.erc20, shares)
+    }
+
+    #[selector(name = "previewDeposit")]
+    pub fn preview_deposit(&self, assets: U256) -> Result<U256, Vec<u8>> {
+        self.vault.preview_deposit(&self.erc20, assets)
+    }
+
+    #[selector(name = "previewMint")]
+    pub fn preview_mint(&self, shares: U256) -> Result<U256, Vec<u8>> {
+        self.vault.preview_mint(&self.erc20, shares)
+    }
+
+    #[selector(name = "previewWithdraw")]
+    pub fn preview_withdraw(&self, assets: U256) -> Result<U256, Vec<u8>> {
+        self.vault.preview_withdraw(&self.erc20, assets)
+    }
+
+    #[selector(name = "previewRedeem")]
+    pub fn preview_redeem(&self, shares: U256) -> Result<U256, Vec<u8>> {
+        self.vault.preview_redeem(&self.erc20, shares)
+    }
+
+    #[selector(name = "maxDeposit")]
+    pub fn max_deposit(&self, receiver: Address) -> Result<U256, Vec<u8>> {
+        self.vault.max_deposit(receiver)
+    }
+
+    #[selector(name = "maxMint")]
+    pub fn max_mint(&self, receiver: Address) -> Result<U256, Vec<u8>> {
+        self.vault.max_mint(&self.erc20, receiver)
+    }
+
+    #[selector(name = "maxWithdraw")]
+    pub fn max_withdraw(&self, owner: Address) -> Result<U256, Vec<u8>> {
+        self.vault.max_withdraw(&self.erc20, owner)
+    }
+
+    #[selector(name = "maxRedeem")]
+    pub fn max_redeem(&self, owner: Address) -> Result<U256, Vec<u8>> {
+        self.vault.max_redeem(&self.erc20, owner)
+    }
+
+    #[selector(name = "deposit")]
+    pub fn deposit(&mut self, assets: U256, receiver: Address) -> Result<U256, Vec<u8>> {
+        self.vault.deposit(&mut self.erc20, assets, receiver)
+    }
+
+    #[selector(name = "mint")]
+    pub fn mint(&mut self, shares: U256, receiver: Address) -> Result<U256, Vec<u8>> {
+        self.vault.mint(&mut self.erc20, shares, receiver)
+    }
+
+    #[selector(name = "withdraw")]
+    pub fn withdraw(&mut self, assets: U256, receiver: Address, owner: Address) -> Result<U256, Vec<u8>> {
+        self.vault.withdraw(&mut self.erc20, assets, receiver, owner)
+    }
+
+    #[selector(name = "redeem")]
+    pub fn redeem(&mut self, shares: U256, receiver: Address, owner: Address) -> Result<U256, Vec<u8>> {
+        self.vault.redeem(&mut self.erc20, shares, receiver, owner)
+    }
+}
+
+#[cfg(feature = "export-abi")]
+fn main() {
+    print_abi("MIT OR Apache-2.0", "pragma solidity ^0.8.23;");
+}
@@ -0,0 +1,65 @@
+//! A plain token listed by vault aggregators: `Erc4626ZeroYield` is inherited for the ERC-4626
+//! views and `totalAssets` is exposed passing the `Erc20`.
+//!
+//! `cargo build --example compose_erc4626_zero_yield --features export-abi,erc4626-adapter` (prints the ABI when run).
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use alloc::vec::Vec;
+use oz_stylus_erc::access::ownable::Ownable;
+use oz_stylus_erc::extensions::erc4626_zero_yield::Erc4626ZeroYield;
+use oz_stylus_erc::tokens::erc20::{Erc20, Erc20Params, NoTransferHook};
+use oz_stylus_erc::utils::initializable::Initializable;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    msg,
+    prelude::*,
+};
+
+pub struct ListedTokenParams;
+
+impl Erc20Params for ListedTokenParams {
+    const NAME: &'static str = "Listed token";
+    const SYMBOL: &'static str = "LST";
+    const DECIMALS: u8 = 18;
+    type Hook = NoTransferHook;
+}
+
+sol_storage! {
+    #[entrypoint]
+    pub struct ListedToken {
+        #[borrow]
+        Erc20<ListedTokenParams> erc20;
+        #[borrow]
+        Erc4626ZeroYield vault_views;
+        #[borrow]
+        Ownable ownable;
+        #[borrow]
+        Initializable initializable;
+    }
+}
+
+#[external]
+#[inherit(Erc20<ListedTokenParams>, Erc4626ZeroYield, Ownable, Initializable)]
+impl ListedToken {
+    pub fn init(&mut self) -> Result<(), Vec<u8>> {
+        self.initializable.initializer()?;
+        self.ownable.transfer_ownership_internal(msg::sender());
+        Ok(())
+    }
+
+    pub fn mint(&mut self, account: Address, value: U256) -> Result<(), Vec<u8>> {
+        self.ownable.only_owner()?;
+        Ok(self.erc20.mint(account, value)?)
+    }
+
+    #[selector(name = "totalAssets")]
+    pub fn total_assets(&self) -> Result<U256, Vec<u8>> {
+        Erc4626ZeroYield::total_assets(&self.erc20)
+    }
+}
+
+#[cfg(feature = "export-abi")]
+fn main() {
+    print_abi("MIT OR Apache-2.0", "pragma solidity ^0.8.23;");
+}
@@ -0,0 +1,93 @@
+//! A collection built on `Erc721` with `Ownable`, `Erc721Metadata` and `ContractUri`: the owner
+//! mints and sets the base URI of the tokens and the collection metadata URI, the base externals
+//! (including the marketplace batch views `ownersOf`/`approvalsOf`) are inherited as is.
+//!
+//! `cargo build --example compose_erc721 --features export-abi` (prints the ABI when run).
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use alloc::{string::String, vec::Vec};
+use oz_stylus_erc::access::ownable::Ownable;
+use oz_stylus_erc::extensions::contract_uri::ContractUri;
+use oz_stylus_erc::extensions::erc721_metadata::Erc721Metadata;
+use oz_stylus_erc::tokens::erc721::{Erc721, Erc721Params};
+use oz_stylus_erc::utils::erc165::Erc165;
+use oz_stylus_erc::utils::initializable::Initializable;
+use stylus_sdk::{
+    alloy_primitives::{Address, FixedBytes, U256},
+    msg,
+    prelude::*,
+};
+
+pub struct CollectionParams;
+
+impl Erc721Params for CollectionParams {
+    const NAME: &'static str = "Stylus collection";
+    const SYMBOL: &'static str = "SCOL";
+}
+
+sol_storage! {
+    #[entrypoint]
+    pub struct Collection {
+        #[borrow]
+        Erc721<CollectionParams> erc721;
+        #[borrow]
+        Ownable ownable;
+        #[borrow]
+        Erc721Metadata metadata;
+        #[borrow]
+        ContractUri contract_uri;
+        #[borrow]
+        Initializable initializable;
+    }
+}
+
+#[external]
+#[inherit(Erc721<CollectionParams>, Ownable, Erc721Metadata, ContractUri, Initializable)]
+impl Collection {
+    pub fn init(&mut self) -> Result<(), Vec<u8>> {
+        self.initializable.initializer()?;
+        self.ownable.transfer_ownership_internal(msg::sender());
+        Ok(())
+    }
+
+    pub fn safe_mint(&mut self, to: Address, token_id: U256) -> Result<(), Vec<u8>> {
+        self.ownable.only_owner()?;
+        self.erc721.safe_mint(to, token_id, &[])
+    }
+
+    pub fn burn(&mut self, token_id: U256) -> Result<(), Vec<u8>> {
+        let owner = self.erc721.require_owned(token_id)?;
+        self.erc721.check_authorized(owner, msg::sender(), token_id)?;
+        Ok(self.erc721.burn(token_id)?)
+    }
+
+    #[selector(name = "tokenURI")]
+    pub fn token_uri(&self, token_id: U256) -> Result<String, Vec<u8>> {
+        Ok(self.metadata.token_uri(&self.erc721, token_id)?)
+    }
+
+    #[selector(name = "setBaseURI")]
+    pub fn set_base_uri(&mut self, base_uri: String) -> Result<(), Vec<u8>> {
+        self.ownable.only_owner()?;
+        Ok(self.metadata.set_base_uri(&base_uri)?)
+    }
+
+    /// ERC-721 and its metadata, the inherited `supportsInterface` only knows ERC-721.
+    #[selector(name = "supportsInterface")]
+    pub fn supports_interface(&self, interface_id: FixedBytes<4>) -> Result<bool, Vec<u8>> {
+        Ok(<Erc721<CollectionParams> as Erc165>::supports_interface(interface_id)
+            || <Erc721Metadata as Erc165>::supports_interface(interface_id))
+    }
+
+    #[selector(name = "setContractURI")]
+    pub fn set_contract_uri(&mut self, uri: String) -> Result<(), Vec<u8>> {
+        self.ownable.only_owner()?;
+        Ok(self.contract_uri.set_contract_uri(&uri)?)
+    }
+}
+
+#[cfg(feature = "export-abi")]
+fn main() {
+    print_abi("MIT OR Apache-2.0", "pragma solidity ^0.8.23;");
+}
@@ -0,0 +1,109 @@
+//! A collection with ERC-4494 permits: `Erc721Permit` composed with `Erc721` and `Ownable`.
+//! Every transfer external is overridden to increment the nonce of the token, `permit`,
+//! `DOMAIN_SEPARATOR` and `eip712Domain` expose the collection signing domain.
+//!
+//! `cargo build --example compose_erc721_permit --features export-abi` (prints the ABI when run).
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use alloc::vec::Vec;
+use oz_stylus_erc::access::ownable::Ownable;
+use oz_stylus_erc::extensions::erc721_permit::Erc721Permit;
+use oz_stylus_erc::tokens::erc721::{Erc721, Erc721Params};
+use oz_stylus_erc::utils::eip712::{self, Eip712Domain};
+use oz_stylus_erc::utils::erc165::Erc165;
+use oz_stylus_erc::utils::initializable::Initializable;
+use stylus_sdk::{
+    abi::Bytes,
+    alloy_primitives::{Address, FixedBytes, B256, U256},
+    msg,
+    prelude::*,
+};
+
+pub struct PermitCollectionParams;
+
+impl Erc721Params for PermitCollectionParams {
+    const NAME: &'static str = "Permit collection";
+    const SYMBOL: &'static str = "PRMT";
+}
+
+sol_storage! {
+    #[entrypoint]
+    pub struct PermitCollection {
+        #[borrow]
+        Erc721<PermitCollectionParams> erc721;
+        #[borrow]
+        Erc721Permit permits;
+        #[borrow]
+        Ownable ownable;
+        #[borrow]
+        Initializable initializable;
+    }
+}
+
+#[external]
+#[inherit(Erc721<PermitCollectionParams>, Erc721Permit, Ownable, Initializable)]
+impl PermitCollection {
+    pub fn init(&mut self) -> Result<(), Vec<u8>> {
+        self.initializable.initializer()?;
+        self.ownable.transfer_ownership_internal(msg::sender());
+        Ok(())
+    }
+
+    pub fn safe_mint(&mut self, to: Address, token_id: U256) -> Result<(), Vec<u8>> {
+        self.ownable.only_owner()?;
+        self.erc721.safe_mint(to, token_id, &[])
+    }
+
+    /// Approves `spender` for `token_id` with the signature of its owner.
+    #[selector(name = "permit")]
+    pub fn permit(&mut self, spender: Address, token_id: U256, deadline: U256, signature: Bytes) -> Result<(), Vec<u8>> {
+        self.permits.permit(&mut self.erc721, spender, token_id, deadline, &signature)
+    }
+
+    #[selector(name = "DOMAIN_SEPARATOR")]
+    pub fn domain_separator(&self) -> Result<B256, Vec<u8>> {
+        Ok(eip712::erc721_domain_separator::<PermitCollectionParams>())
+    }
+
+    #[selector(name = "eip712Domain")]
+    pub fn eip712_domain(&self) -> Result<Eip712Domain, Vec<u8>> {
+        Ok(eip712::erc721_domain::<PermitCollectionParams>())
+    }
+
+    /// ERC-721 and ERC-4494, the inherited `supportsInterface` only knows ERC-721.
+    #[selector(name = "supportsInterface")]
+    pub fn supports_interface(&self, interface_id: FixedBytes<4>) -> Result<bool, Vec<u8>> {
+        Ok(<Erc721<PermitCollectionParams> as Erc165>::supports_interface(interface_id)
+            || <Erc721Permit as Erc165>::supports_interface(interface_id))
+    }
+
+    #[selector(name = "transferFrom")]
+    pub fn transfer_from(&mut self, from: Address, to: Address, token_id: U256) -> Result<(), Vec<u8>> {
+        self.permits.increment_nonce(token_id);
+        Ok(self.erc721.transfer_from(from, to, token_id)?)
+    }
+
+    #[selector(name = "safeTransferFrom")]
+    pub fn safe_transfer_from(&mut self, from: Address, to: Address, token_id: U256) -> Result<(), Vec<u8>> {
+        self.permits.increment_nonce(token_id);
+        self.erc721.safe_transfer_from(from, to, token_id)
+    }
+
+    #[selector(name = "safeTransferFrom")]
+    pub fn safe_transfer_from_with_data(
+        &mut self,
+        from: Address,
+        to: Address,
+        token_id: U256,
+        data: Bytes,
+    ) -> Result<(), Vec<u8>> {
+        self.permits.increment_nonce(token_id);
+        self.erc721.safe_transfer_from_with_data(from, to, token_id, data)
+    }
+}
+
+#[cfg(feature = "export-abi")]
+fn main() {
+    print_abi("MIT OR Apache-2.0", "pragma solidity ^0.8.23;");
+}
@@ -0,0 +1,102 @@
+//! A rentable collection with royalties: `Erc721Rental` (ERC-4907) and `Erc2981` composed with
+//! `Erc721` and `Ownable`. Every transfer external is overridden to clear the user of the token.
+//!
+//! `cargo build --example compose_erc721_rental --features export-abi` (prints the ABI when run).
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use alloc::vec::Vec;
+use oz_stylus_erc::access::ownable::Ownable;
+use oz_stylus_erc::extensions::erc2981::Erc2981;
+use oz_stylus_erc::extensions::erc721_rental::Erc721Rental;
+use oz_stylus_erc::tokens::erc721::{Erc721, Erc721Params};
+use oz_stylus_erc::utils::erc165::Erc165;
+use oz_stylus_erc::utils::initializable::Initializable;
+use stylus_sdk::{
+    abi::Bytes,
+    alloy_primitives::{Address, FixedBytes, U256},
+    msg,
+    prelude::*,
+};
+
+pub struct RentalCollectionParams;
+
+impl Erc721Params for RentalCollectionParams {
+    const NAME: &'static str = "Rental collection";
+    const SYMBOL: &'static str = "RENT";
+}
+
+sol_storage! {
+    #[entrypoint]
+    pub struct RentalCollection {
+        #[borrow]
+        Erc721<RentalCollectionParams> erc721;
+        #[borrow]
+        Erc721Rental rental;
+        #[borrow]
+        Erc2981 royalties;
+        #[borrow]
+        Ownable ownable;
+        #[borrow]
+        Initializable initializable;
+    }
+}
+
+#[external]
+#[inherit(Erc721<RentalCollectionParams>, Erc721Rental, Erc2981, Ownable, Initializable)]
+impl RentalCollection {
+    pub fn init(&mut self) -> Result<(), Vec<u8>> {
+        self.initializable.initializer()?;
+        self.ownable.transfer_ownership_internal(msg::sender());
+        Ok(())
+    }
+
+    /// Mints `token_id` with a royalty of `fee_numerator` basis points paid to the owner.
+    pub fn mint(&mut self, to: Address, token_id: U256, fee_numerator: U256) -> Result<(), Vec<u8>> {
+        self.ownable.only_owner()?;
+        self.erc721.safe_mint(to, token_id, &[])?;
+        Ok(self.royalties.set_token_royalty(token_id, self.ownable.owner()?, fee_numerator)?)
+    }
+
+    /// ERC-721, ERC-4907 and ERC-2981, the inherited `supportsInterface` only knows ERC-721.
+    #[selector(name = "supportsInterface")]
+    pub fn supports_interface(&self, interface_id: FixedBytes<4>) -> Result<bool, Vec<u8>> {
+        Ok(<Erc721<RentalCollectionParams> as Erc165>::supports_interface(interface_id)
+            || <Erc721Rental as Erc165>::supports_interface(interface_id)
+            || <Erc2981 as Erc165>::supports_interface(interface_id))
+    }
+
+    #[selector(name = "setUser")]
+    pub fn set_user(&mut self, token_id: U256, user: Address, expires: u64) -> Result<(), Vec<u8>> {
+        self.rental.set_user(&self.erc721, token_id, user, expires)
+    }
+
+    #[selector(name = "transferFrom")]
+    pub fn transfer_from(&mut self, from: Address, to: Address, token_id: U256) -> Result<(), Vec<u8>> {
+        self.rental.clear_user(token_id);
+        Ok(self.erc721.transfer_from(from, to, token_id)?)
+    }
+
+    #[selector(name = "safeTransferFrom")]
+    pub fn safe_transfer_from(&mut self, from: Address, to: Address, token_id: U256) -> Result<(), Vec<u8>> {
+        self.rental.clear_user(token_id);
+        self.erc721.safe_transfer_from(from, to, token_id)
+    }
+
+    #[selector(name = "safeTransferFrom")]
+    pub fn safe_transfer_from_with_data(
+        &mut self,
+        from: Address,
+        to: Address,
+        token_id: U256,
+        data: Bytes,
+    ) -> Result<(), Vec<u8>> {
+        self.rental.clear_user(token_id);
+        self.erc721.safe_transfer_from_with_data(from, to, token_id, data)
+    }
+}
+
+#[cfg(feature = "export-abi")]
+fn main() {
+    print_abi("MIT OR Apache-2.0", "pragma solidity ^0.8.23;");
+}
@@ -0,0 +1,69 @@
+//! Disabling inherited externals: the token inherits `Erc20` and `Ownable` but overrides
+//! `revokeAllAllowances` (allowances of integrations must not be revoked in bulk) and
+//! `renounceOwnership` (the token must keep an owner) to revert with `FeatureDisabled`.
+//!
+//! `cargo build --example compose_feature_gate --features export-abi` (prints the ABI when run).
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use alloc::vec::Vec;
+use oz_stylus_erc::access::ownable::Ownable;
+use oz_stylus_erc::tokens::erc20::{Erc20, Erc20Params, NoTransferHook};
+use oz_stylus_erc::utils::feature_gate;
+use oz_stylus_erc::utils::initializable::Initializable;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    msg,
+    prelude::*,
+};
+
+pub struct GatedTokenParams;
+
+impl Erc20Params for GatedTokenParams {
+    const NAME: &'static str = "Gated token";
+    const SYMBOL: &'static str = "GATE";
+    const DECIMALS: u8 = 18;
+    type Hook = NoTransferHook;
+}
+
+sol_storage! {
+    #[entrypoint]
+    pub struct GatedToken {
+        #[borrow]
+        Erc20<GatedTokenParams> erc20;
+        #[borrow]
+        Ownable ownable;
+        #[borrow]
+        Initializable initializable;
+    }
+}
+
+#[external]
+#[inherit(Erc20<GatedTokenParams>, Ownable, Initializable)]
+impl GatedToken {
+    pub fn init(&mut self) -> Result<(), Vec<u8>> {
+        self.initializable.initializer()?;
+        self.ownable.transfer_ownership_internal(msg::sender());
+        Ok(())
+    }
+
+    pub fn mint(&mut self, account: Address, value: U256) -> Result<(), Vec<u8>> {
+        self.ownable.only_owner()?;
+        Ok(self.erc20.mint(account, value)?)
+    }
+
+    #[selector(name = "revokeAllAllowances")]
+    pub fn revoke_all_allowances(&mut self) -> Result<(), Vec<u8>> {
+        Err(feature_gate::disabled("revokeAllAllowances()"))
+    }
+
+    #[selector(name = "renounceOwnership")]
+    pub fn renounce_ownership(&mut self) -> Result<(), Vec<u8>> {
+        Err(feature_gate::disabled("renounceOwnership()"))
+    }
+}
+
+#[cfg(feature = "export-abi")]
+fn main() {
+    print_abi("MIT OR Apache-2.0", "pragma solidity ^0.8.23;");
+}
@@ -0,0 +1,101 @@
+//! Composing `Erc20Fee`: `transfer` and `transfer_from` move tokens with `transfer_with_fee`,
+//! which routes the fee to the collector. The admin (`DEFAULT_ADMIN_ROLE`) sets the fee and the
+//! collector, `FEE_EXEMPTION_MANAGER_ROLE` manages the shared `FeeExemptions` list (e.g. to exempt
+//! a liquidity pool or the treasury); `init` grants both roles to the caller.
+//!
+//! `cargo build --example compose_fee --features export-abi` (prints the ABI when run).
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use alloc::vec::Vec;
+use oz_stylus_erc::access::access_control::{role_id, AccessControl, DEFAULT_ADMIN_ROLE};
+use oz_stylus_erc::extensions::erc20_fee::Erc20Fee;
+use oz_stylus_erc::extensions::fee_exemptions::{FeeExemptions, FEE_EXEMPTION_MANAGER_ROLE};
+use oz_stylus_erc::tokens::erc20::{Erc20, Erc20Params, NoTransferHook};
+use oz_stylus_erc::utils::initializable::Initializable;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    msg,
+    prelude::*,
+};
+
+pub struct FeeTokenParams;
+
+impl Erc20Params for FeeTokenParams {
+    const NAME: &'static str = "Fee token";
+    const SYMBOL: &'static str = "FEE";
+    const DECIMALS: u8 = 18;
+    type Hook = NoTransferHook;
+}
+
+sol_storage! {
+    #[entrypoint]
+    pub struct FeeToken {
+        #[borrow]
+        Erc20<FeeTokenParams> erc20;
+        #[borrow]
+        Erc20Fee fee;
+        #[borrow]
+        FeeExemptions fee_exemptions;
+        #[borrow]
+        AccessControl access;
+        #[borrow]
+        Initializable initializable;
+    }
+}
+
+#[external]
+#[inherit(Erc20<FeeTokenParams>, Erc20Fee, FeeExemptions, AccessControl, Initializable)]
+impl FeeToken {
+    /// The caller becomes admin, exemptions manager and fee collector, the fee starts at 0.
+    pub fn init(&mut self) -> Result<(), Vec<u8>> {
+        self.initializable.initializer()?;
+        self.access.grant_role_internal(DEFAULT_ADMIN_ROLE, msg::sender());
+        self.access.grant_role_internal(role_id(FEE_EXEMPTION_MANAGER_ROLE), msg::sender());
+        self.fee.set_fee_collector(msg::sender())?;
+        Ok(())
+    }
+
+    pub fn mint(&mut self, account: Address, value: U256) -> Result<(), Vec<u8>> {
+        self.access.only_role(DEFAULT_ADMIN_ROLE)?;
+        self.erc20.mint(account, value)?;
+        Ok(())
+    }
+
+    // the receiver gets `value` minus the fee, the allowance is spent for the full `value`
+    pub fn transfer(&mut self, to: Address, value: U256) -> Result<bool, Vec<u8>> {
+        self.fee.transfer_with_fee(&mut self.erc20, &self.fee_exemptions, msg::sender(), to, value)?;
+        Ok(true)
+    }
+
+    pub fn transfer_from(&mut self, from: Address, to: Address, value: U256) -> Result<bool, Vec<u8>> {
+        self.erc20.spend_allowance(from, msg::sender(), value)?;
+        self.fee.transfer_with_fee(&mut self.erc20, &self.fee_exemptions, from, to, value)?;
+        Ok(true)
+    }
+
+    pub fn set_fee(&mut self, fee_bps: U256) -> Result<(), Vec<u8>> {
+        self.access.only_role(DEFAULT_ADMIN_ROLE)?;
+        self.fee.set_fee(fee_bps)?;
+        Ok(())
+    }
+
+    pub fn set_fee_collector(&mut self, collector: Address) -> Result<(), Vec<u8>> {
+        self.access.only_role(DEFAULT_ADMIN_ROLE)?;
+        self.fee.set_fee_collector(collector)?;
+        Ok(())
+    }
+
+    pub fn add_fee_exemption(&mut self, account: Address) -> Result<(), Vec<u8>> {
+        self.fee_exemptions.add_fee_exemption(&self.access, account)
+    }
+
+    pub fn remove_fee_exemption(&mut self, account: Address) -> Result<(), Vec<u8>> {
+        self.fee_exemptions.remove_fee_exemption(&self.access, account)
+    }
+}
+
+#[cfg(feature = "export-abi")]
+fn main() {
+    print_abi("MIT OR Apache-2.0", "pragma solidity ^0.8.23;");
+}
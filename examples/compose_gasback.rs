@@ -0,0 +1,96 @@
+//! Composing `Erc20Gasback` with `AccessControl`: `transfer` and `transfer_from` are metered,
+//! partner contracts calling them accrue credits claimable as tokens, `GASBACK_MANAGER_ROLE`
+//! manages partners and rate, `MINTER_ROLE` mints.
+//!
+//! `cargo build --example compose_gasback --features export-abi` (prints the ABI when run).
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use alloc::vec::Vec;
+use oz_stylus_erc::access::access_control::{role_id, AccessControl, DEFAULT_ADMIN_ROLE};
+use oz_stylus_erc::extensions::erc20_gasback::Erc20Gasback;
+use oz_stylus_erc::tokens::erc20::{Erc20, Erc20Params, NoTransferHook};
+use oz_stylus_erc::utils::initializable::Initializable;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    evm, msg,
+    prelude::*,
+};
+
+const MINTER_ROLE: &str = "MINTER_ROLE";
+const GASBACK_MANAGER_ROLE: &str = "GASBACK_MANAGER_ROLE";
+
+pub struct GasbackTokenParams;
+
+impl Erc20Params for GasbackTokenParams {
+    const NAME: &'static str = "Gasback token";
+    const SYMBOL: &'static str = "GBT";
+    const DECIMALS: u8 = 18;
+    type Hook = NoTransferHook;
+}
+
+sol_storage! {
+    #[entrypoint]
+    pub struct GasbackToken {
+        #[borrow]
+        Erc20<GasbackTokenParams> erc20;
+        #[borrow]
+        Erc20Gasback gasback;
+        #[borrow]
+        AccessControl access;
+        #[borrow]
+        Initializable initializable;
+    }
+}
+
+#[external]
+#[inherit(Erc20<GasbackTokenParams>, Erc20Gasback, AccessControl, Initializable)]
+impl GasbackToken {
+    pub fn init(&mut self, credit_per_gas: U256, max_credit_per_call: U256) -> Result<(), Vec<u8>> {
+        self.initializable.initializer()?;
+        self.access.grant_role_internal(DEFAULT_ADMIN_ROLE, msg::sender());
+        self.gasback.set_rate(credit_per_gas, max_credit_per_call);
+        Ok(())
+    }
+
+    pub fn mint(&mut self, account: Address, value: U256) -> Result<(), Vec<u8>> {
+        self.access.only_role(role_id(MINTER_ROLE))?;
+        Ok(self.erc20.mint(account, value)?)
+    }
+
+    pub fn transfer(&mut self, to: Address, value: U256) -> Result<bool, Vec<u8>> {
+        let gas_start = evm::gas_left();
+        self.erc20.transfer_internal(msg::sender(), to, value)?;
+        self.gasback.accrue(gas_start);
+        Ok(true)
+    }
+
+    pub fn transfer_from(&mut self, from: Address, to: Address, value: U256) -> Result<bool, Vec<u8>> {
+        let gas_start = evm::gas_left();
+        self.erc20.spend_allowance(from, msg::sender(), value)?;
+        self.erc20.transfer_internal(from, to, value)?;
+        self.gasback.accrue(gas_start);
+        Ok(true)
+    }
+
+    pub fn set_gasback_partner(&mut self, partner: Address, registered: bool) -> Result<(), Vec<u8>> {
+        self.access.only_role(role_id(GASBACK_MANAGER_ROLE))?;
+        self.gasback.set_partner(partner, registered);
+        Ok(())
+    }
+
+    pub fn set_gasback_rate(&mut self, credit_per_gas: U256, max_credit_per_call: U256) -> Result<(), Vec<u8>> {
+        self.access.only_role(role_id(GASBACK_MANAGER_ROLE))?;
+        self.gasback.set_rate(credit_per_gas, max_credit_per_call);
+        Ok(())
+    }
+
+    pub fn claim_gasback(&mut self) -> Result<U256, Vec<u8>> {
+        self.gasback.claim_gasback(&mut self.erc20)
+    }
+}
+
+#[cfg(feature = "export-abi")]
+fn main() {
+    print_abi("MIT OR Apache-2.0", "pragma solidity ^0.8.23;");
+}
@@ -0,0 +1,85 @@
+//! Composing `Erc20GlobalSettlement` with `Ownable`: every path moving or minting tokens is
+//! guarded by `when_not_settled`, the owner configures the reserve asset and triggers the
+//! settlement, then holders `redeem` their tokens for the reserve.
+//!
+//! `cargo build --example compose_global_settlement --features export-abi` (prints the ABI when run).
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use alloc::vec::Vec;
+use oz_stylus_erc::access::ownable::Ownable;
+use oz_stylus_erc::extensions::erc20_global_settlement::Erc20GlobalSettlement;
+use oz_stylus_erc::tokens::erc20::{Erc20, Erc20Params, NoTransferHook};
+use oz_stylus_erc::utils::initializable::Initializable;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    msg,
+    prelude::*,
+};
+
+pub struct SettledTokenParams;
+
+impl Erc20Params for SettledTokenParams {
+    const NAME: &'static str = "Settlement token";
+    const SYMBOL: &'static str = "SET";
+    const DECIMALS: u8 = 18;
+    type Hook = NoTransferHook;
+}
+
+sol_storage! {
+    #[entrypoint]
+    pub struct SettledToken {
+        #[borrow]
+        Erc20<SettledTokenParams> erc20;
+        #[borrow]
+        Erc20GlobalSettlement settlement;
+        #[borrow]
+        Ownable ownable;
+        #[borrow]
+        Initializable initializable;
+    }
+}
+
+#[external]
+#[inherit(Erc20<SettledTokenParams>, Erc20GlobalSettlement, Ownable, Initializable)]
+impl SettledToken {
+    pub fn init(&mut self, reserve_asset: Address) -> Result<(), Vec<u8>> {
+        self.initializable.initializer()?;
+        self.ownable.transfer_ownership_internal(msg::sender());
+        self.settlement.set_reserve_asset(reserve_asset)?;
+        Ok(())
+    }
+
+    pub fn mint(&mut self, account: Address, value: U256) -> Result<(), Vec<u8>> {
+        self.ownable.only_owner()?;
+        self.settlement.when_not_settled()?;
+        Ok(self.erc20.mint(account, value)?)
+    }
+
+    pub fn transfer(&mut self, to: Address, value: U256) -> Result<bool, Vec<u8>> {
+        self.settlement.when_not_settled()?;
+        self.erc20.transfer_internal(msg::sender(), to, value)?;
+        Ok(true)
+    }
+
+    pub fn transfer_from(&mut self, from: Address, to: Address, value: U256) -> Result<bool, Vec<u8>> {
+        self.settlement.when_not_settled()?;
+        self.erc20.spend_allowance(from, msg::sender(), value)?;
+        self.erc20.transfer_internal(from, to, value)?;
+        Ok(true)
+    }
+
+    pub fn trigger_settlement(&mut self) -> Result<(), Vec<u8>> {
+        self.ownable.only_owner()?;
+        self.settlement.trigger_settlement(&self.erc20)
+    }
+
+    pub fn redeem(&mut self, amount: U256) -> Result<U256, Vec<u8>> {
+        self.settlement.redeem(&mut self.erc20, amount)
+    }
+}
+
+#[cfg(feature = "export-abi")]
+fn main() {
+    print_abi("MIT OR Apache-2.0", "pragma solidity ^0.8.23;");
+}
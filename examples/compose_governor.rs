@@ -0,0 +1,51 @@
+//! Composing `Governor`: the program only fixes the votes token, the timelock and the voting
+//! settings once in `init`, proposals, votes, queueing and execution are inherited. With a zero
+//! timelock succeeded proposals are executed by the governor itself.
+//!
+//! `cargo build --example compose_governor --features export-abi` (prints the ABI when run).
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use alloc::vec::Vec;
+use oz_stylus_erc::governance::governor::Governor;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    prelude::*,
+};
+
+/// Blocks between a proposal and the start of its vote.
+const VOTING_DELAY: u64 = 1;
+/// Blocks a vote lasts.
+const VOTING_PERIOD: u64 = 50;
+/// Percentage of the past total supply needed to reach the quorum.
+const QUORUM_PERCENT: u64 = 4;
+
+sol_storage! {
+    #[entrypoint]
+    pub struct TokenGovernor {
+        #[borrow]
+        Governor governor;
+    }
+}
+
+#[external]
+#[inherit(Governor)]
+impl TokenGovernor {
+    /// Fails when already initialized.
+    pub fn init(&mut self, token: Address, timelock: Address) -> Result<(), Vec<u8>> {
+        self.governor.configure(
+            token,
+            timelock,
+            U256::from(VOTING_DELAY),
+            U256::from(VOTING_PERIOD),
+            U256::ZERO,
+            U256::from(QUORUM_PERCENT),
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "export-abi")]
+fn main() {
+    print_abi("MIT OR Apache-2.0", "pragma solidity ^0.8.23;");
+}
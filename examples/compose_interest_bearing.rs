@@ -0,0 +1,71 @@
+//! Composing `Erc20InterestBearing`: it wraps `Erc20` itself (scaled balances), so the token
+//! borrows and inherits the extension instead of `Erc20`. The account calling `init` becomes
+//! the interest controller, typically the lending pool.
+//!
+//! `cargo build --example compose_interest_bearing --features export-abi` (prints the ABI when run).
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use alloc::vec::Vec;
+use oz_stylus_erc::extensions::erc20_interest_bearing::Erc20InterestBearing;
+use oz_stylus_erc::tokens::erc20::{Erc20Params, NoTransferHook};
+use oz_stylus_erc::utils::initializable::Initializable;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    msg,
+    prelude::*,
+};
+
+pub struct InterestTokenParams;
+
+impl Erc20Params for InterestTokenParams {
+    const NAME: &'static str = "Interest bearing token";
+    const SYMBOL: &'static str = "aIBT";
+    const DECIMALS: u8 = 18;
+    type Hook = NoTransferHook;
+}
+
+sol_storage! {
+    #[entrypoint]
+    pub struct InterestToken {
+        #[borrow]
+        Erc20InterestBearing<InterestTokenParams> token;
+        #[borrow]
+        Initializable initializable;
+    }
+}
+
+#[external]
+#[inherit(Erc20InterestBearing<InterestTokenParams>, Initializable)]
+impl InterestToken {
+    pub fn init(&mut self) -> Result<(), Vec<u8>> {
+        self.initializable.initializer()?;
+        self.token.set_controller(msg::sender());
+        Ok(())
+    }
+
+    // deposits and withdrawals of the pool mint and burn in unscaled units
+    pub fn mint(&mut self, account: Address, amount: U256) -> Result<(), Vec<u8>> {
+        self.only_controller()?;
+        self.token.mint(account, amount)
+    }
+
+    pub fn burn(&mut self, account: Address, amount: U256) -> Result<(), Vec<u8>> {
+        self.only_controller()?;
+        self.token.burn(account, amount)
+    }
+}
+
+impl InterestToken {
+    fn only_controller(&self) -> Result<(), Vec<u8>> {
+        if msg::sender() != self.token.interest_controller()? {
+            return Err("caller is not the controller".into());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "export-abi")]
+fn main() {
+    print_abi("MIT OR Apache-2.0", "pragma solidity ^0.8.23;");
+}
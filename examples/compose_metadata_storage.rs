@@ -0,0 +1,61 @@
+//! Composing `Erc20MetadataStorage`: name, symbol and decimals are given to `init` instead of
+//! being compiled in, so every deployment (or clone) of the same program is its own token.
+//! `init` also mints the initial supply to `holder`, nothing can mint afterwards.
+//!
+//! `cargo build --example compose_metadata_storage --features export-abi` (prints the ABI when run).
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use alloc::string::String;
+use oz_stylus_erc::errors::OzError;
+use oz_stylus_erc::extensions::erc20_metadata_storage::Erc20MetadataStorage;
+use oz_stylus_erc::tokens::erc20::{Erc20, Erc20Params, NoTransferHook};
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    prelude::*,
+};
+
+/// Placeholders, the `Erc20MetadataStorage` externals answer `name`/`symbol`/`decimals`.
+pub struct StoredMetadataParams;
+
+impl Erc20Params for StoredMetadataParams {
+    const NAME: &'static str = "";
+    const SYMBOL: &'static str = "";
+    const DECIMALS: u8 = 18;
+    type Hook = NoTransferHook;
+}
+
+sol_storage! {
+    #[entrypoint]
+    pub struct StoredMetadataToken {
+        #[borrow]
+        Erc20MetadataStorage metadata;
+        #[borrow]
+        Erc20<StoredMetadataParams> erc20;
+    }
+}
+
+#[external]
+#[inherit(Erc20MetadataStorage, Erc20<StoredMetadataParams>)]
+impl StoredMetadataToken {
+    pub fn init(
+        &mut self,
+        name: String,
+        symbol: String,
+        decimals: u8,
+        holder: Address,
+        supply: U256,
+    ) -> Result<(), OzError> {
+        // reverts with Erc20MetadataAlreadySet on a second call
+        self.metadata.set_metadata(&name, &symbol, decimals)?;
+        if supply != U256::ZERO {
+            self.erc20.mint(holder, supply)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "export-abi")]
+fn main() {
+    print_abi("MIT OR Apache-2.0", "pragma solidity ^0.8.23;");
+}
@@ -0,0 +1,92 @@
+//! Composing `Erc20MintAuthorization` with `Ownable`: the owner configures the backend signer,
+//! users claim the mints it authorized off-chain. Nonces live in the token's `ReplayGuard`.
+//!
+//! `cargo build --example compose_mint_authorization --features export-abi` (prints the ABI when run).
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use alloc::vec::Vec;
+use oz_stylus_erc::access::ownable::Ownable;
+use oz_stylus_erc::extensions::erc20_mint_authorization::Erc20MintAuthorization;
+use oz_stylus_erc::tokens::erc20::{Erc20, Erc20Params, NoTransferHook};
+use oz_stylus_erc::utils::eip712::{self, Eip712Domain};
+use oz_stylus_erc::utils::initializable::Initializable;
+use oz_stylus_erc::utils::replay_guard::ReplayGuard;
+use stylus_sdk::{
+    abi::Bytes,
+    alloy_primitives::{Address, U256},
+    msg,
+    prelude::*,
+};
+
+pub struct RewardTokenParams;
+
+impl Erc20Params for RewardTokenParams {
+    const NAME: &'static str = "Reward token";
+    const SYMBOL: &'static str = "RWD";
+    const DECIMALS: u8 = 18;
+    type Hook = NoTransferHook;
+}
+
+sol_storage! {
+    #[entrypoint]
+    pub struct RewardToken {
+        #[borrow]
+        Erc20<RewardTokenParams> erc20;
+        #[borrow]
+        Erc20MintAuthorization mint_authorization;
+        #[borrow]
+        ReplayGuard replay_guard;
+        #[borrow]
+        Ownable ownable;
+        #[borrow]
+        Initializable initializable;
+    }
+}
+
+#[external]
+#[inherit(Erc20<RewardTokenParams>, Erc20MintAuthorization, ReplayGuard, Ownable, Initializable)]
+impl RewardToken {
+    pub fn init(&mut self, signer: Address) -> Result<(), Vec<u8>> {
+        self.initializable.initializer()?;
+        self.ownable.transfer_ownership_internal(msg::sender());
+        self.mint_authorization.set_mint_signer(signer);
+        Ok(())
+    }
+
+    pub fn set_mint_signer(&mut self, signer: Address) -> Result<(), Vec<u8>> {
+        self.ownable.only_owner()?;
+        self.mint_authorization.set_mint_signer(signer);
+        Ok(())
+    }
+
+    pub fn mint_with_authorization(
+        &mut self,
+        to: Address,
+        amount: U256,
+        nonce: U256,
+        expiry: U256,
+        signature: Bytes,
+    ) -> Result<(), Vec<u8>> {
+        self.mint_authorization.mint_with_authorization(
+            &mut self.erc20,
+            &mut self.replay_guard,
+            to,
+            amount,
+            nonce,
+            expiry,
+            &signature,
+        )
+    }
+
+    /// ERC-5267 domain of the signatures accepted by the token, for wallets.
+    #[selector(name = "eip712Domain")]
+    pub fn eip712_domain(&self) -> Result<Eip712Domain, Vec<u8>> {
+        Ok(eip712::erc20_domain::<RewardTokenParams>())
+    }
+}
+
+#[cfg(feature = "export-abi")]
+fn main() {
+    print_abi("MIT OR Apache-2.0", "pragma solidity ^0.8.23;");
+}
@@ -0,0 +1,65 @@
+//! Composing `utils::multicall`: `multicall(bytes[])` runs several externals of the token in one
+//! transaction as the caller, e.g. `approve` + `transfer`, or a batch of owner mints.
+//!
+//! `cargo build --example compose_multicall --features export-abi` (prints the ABI when run).
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use alloc::vec::Vec;
+use oz_stylus_erc::access::ownable::Ownable;
+use oz_stylus_erc::tokens::erc20::{Erc20, Erc20Params, NoTransferHook};
+use oz_stylus_erc::utils::initializable::Initializable;
+use oz_stylus_erc::utils::multicall;
+use stylus_sdk::{
+    abi::Bytes,
+    alloy_primitives::{Address, U256},
+    msg,
+    prelude::*,
+};
+
+pub struct MulticallTokenParams;
+
+impl Erc20Params for MulticallTokenParams {
+    const NAME: &'static str = "Multicall token";
+    const SYMBOL: &'static str = "MULTI";
+    const DECIMALS: u8 = 18;
+    type Hook = NoTransferHook;
+}
+
+sol_storage! {
+    #[entrypoint]
+    pub struct MulticallToken {
+        #[borrow]
+        Erc20<MulticallTokenParams> erc20;
+        #[borrow]
+        Ownable ownable;
+        #[borrow]
+        Initializable initializable;
+    }
+}
+
+#[external]
+#[inherit(Erc20<MulticallTokenParams>, Ownable, Initializable)]
+impl MulticallToken {
+    pub fn init(&mut self) -> Result<(), Vec<u8>> {
+        self.initializable.initializer()?;
+        self.ownable.transfer_ownership_internal(msg::sender());
+        Ok(())
+    }
+
+    pub fn mint(&mut self, account: Address, value: U256) -> Result<(), Vec<u8>> {
+        self.ownable.only_owner()?;
+        Ok(self.erc20.mint(account, value)?)
+    }
+
+    /// Runs every payload as the caller, returns their return data.
+    #[selector(name = "multicall")]
+    pub fn multicall(&mut self, data: Vec<Bytes>) -> Result<Vec<Bytes>, Vec<u8>> {
+        multicall::multicall(self, data)
+    }
+}
+
+#[cfg(feature = "export-abi")]
+fn main() {
+    print_abi("MIT OR Apache-2.0", "pragma solidity ^0.8.23;");
+}
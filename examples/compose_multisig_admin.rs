@@ -0,0 +1,60 @@
+//! Composing `MultisigAdmin` as the authorization backend of a token: `mint` is an admin call
+//! executed by anyone once the owners confirmed `keccak256` of its calldata with enough weight.
+//!
+//! `cargo build --example compose_multisig_admin --features export-abi` (prints the ABI when run).
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use alloc::vec::Vec;
+use oz_stylus_erc::access::multisig_admin::{action_hash, MultisigAdmin};
+use oz_stylus_erc::tokens::erc20::{Erc20, Erc20Params, NoTransferHook};
+use oz_stylus_erc::utils::eip712::{address_word, u256_word};
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    prelude::*,
+};
+
+pub struct MultisigTokenParams;
+
+impl Erc20Params for MultisigTokenParams {
+    const NAME: &'static str = "Multisig token";
+    const SYMBOL: &'static str = "MST";
+    const DECIMALS: u8 = 18;
+    type Hook = NoTransferHook;
+}
+
+sol_storage! {
+    #[entrypoint]
+    pub struct MultisigToken {
+        #[borrow]
+        Erc20<MultisigTokenParams> erc20;
+        #[borrow]
+        MultisigAdmin multisig;
+    }
+}
+
+#[external]
+#[inherit(Erc20<MultisigTokenParams>, MultisigAdmin)]
+impl MultisigToken {
+    pub fn init(&mut self, owners: Vec<Address>, weights: Vec<U256>, threshold: U256) -> Result<(), Vec<u8>> {
+        if owners.len() != weights.len() {
+            return Err("owners and weights length mismatch".into());
+        }
+        let owners: Vec<(Address, U256)> = owners.into_iter().zip(weights).collect();
+        Ok(self.multisig.init_owners(&owners, threshold)?)
+    }
+
+    /// Admin call, owners confirm `keccak256(abi.encodeWithSignature("mint(address,uint256)", account, value))`.
+    pub fn mint(&mut self, account: Address, value: U256) -> Result<(), Vec<u8>> {
+        self.multisig.consume_action(action_hash(
+            "mint(address,uint256)",
+            &[address_word(account), u256_word(value)],
+        ))?;
+        Ok(self.erc20.mint(account, value)?)
+    }
+}
+
+#[cfg(feature = "export-abi")]
+fn main() {
+    print_abi("MIT OR Apache-2.0", "pragma solidity ^0.8.23;");
+}
@@ -0,0 +1,66 @@
+//! Composing `NftReceiver` with `Ownable`: the token accepts NFTs sent to it by mistake and the
+//! owner forwards them back to their sender.
+//!
+//! `cargo build --example compose_nft_receiver --features export-abi` (prints the ABI when run).
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use alloc::vec::Vec;
+use oz_stylus_erc::access::ownable::Ownable;
+use oz_stylus_erc::extensions::nft_receiver::NftReceiver;
+use oz_stylus_erc::tokens::erc20::{Erc20, Erc20Params, NoTransferHook};
+use oz_stylus_erc::utils::initializable::Initializable;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    msg,
+    prelude::*,
+};
+
+pub struct RescuableTokenParams;
+
+impl Erc20Params for RescuableTokenParams {
+    const NAME: &'static str = "Rescuable token";
+    const SYMBOL: &'static str = "RSC";
+    const DECIMALS: u8 = 18;
+    type Hook = NoTransferHook;
+}
+
+sol_storage! {
+    #[entrypoint]
+    pub struct RescuableToken {
+        #[borrow]
+        Erc20<RescuableTokenParams> erc20;
+        #[borrow]
+        NftReceiver nft_receiver;
+        #[borrow]
+        Ownable ownable;
+        #[borrow]
+        Initializable initializable;
+    }
+}
+
+#[external]
+#[inherit(Erc20<RescuableTokenParams>, NftReceiver, Ownable, Initializable)]
+impl RescuableToken {
+    pub fn init(&mut self) -> Result<(), Vec<u8>> {
+        self.initializable.initializer()?;
+        self.ownable.transfer_ownership_internal(msg::sender());
+        self.nft_receiver.set_accept_nfts(true);
+        Ok(())
+    }
+
+    pub fn rescue_erc721(&mut self, collection: Address, token_id: U256, to: Address) -> Result<(), Vec<u8>> {
+        self.ownable.only_owner()?;
+        Ok(self.nft_receiver.rescue_erc721(collection, token_id, to)?)
+    }
+
+    pub fn rescue_erc1155(&mut self, collection: Address, id: U256, amount: U256, to: Address) -> Result<(), Vec<u8>> {
+        self.ownable.only_owner()?;
+        Ok(self.nft_receiver.rescue_erc1155(collection, id, amount, to)?)
+    }
+}
+
+#[cfg(feature = "export-abi")]
+fn main() {
+    print_abi("MIT OR Apache-2.0", "pragma solidity ^0.8.23;");
+}
@@ -0,0 +1,93 @@
+//! Composing `Erc20Pausable` with `Ownable`: every external moving balances checks
+//! `when_not_paused` first, only the owner pauses and unpauses.
+//!
+//! `cargo build --example compose_pausable --features export-abi` (prints the ABI when run).
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use oz_stylus_erc::access::ownable::Ownable;
+use oz_stylus_erc::errors::OzError;
+use oz_stylus_erc::extensions::erc20_pausable::Erc20Pausable;
+use oz_stylus_erc::tokens::erc20::{Erc20, Erc20Params, NoTransferHook};
+use oz_stylus_erc::utils::initializable::Initializable;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    msg,
+    prelude::*,
+};
+
+pub struct PausableTokenParams;
+
+impl Erc20Params for PausableTokenParams {
+    const NAME: &'static str = "Pausable token";
+    const SYMBOL: &'static str = "PAU";
+    const DECIMALS: u8 = 18;
+    type Hook = NoTransferHook;
+}
+
+sol_storage! {
+    #[entrypoint]
+    pub struct PausableToken {
+        #[borrow]
+        Erc20<PausableTokenParams> erc20;
+        #[borrow]
+        Erc20Pausable pausable;
+        #[borrow]
+        Ownable ownable;
+        #[borrow]
+        Initializable initializable;
+    }
+}
+
+#[external]
+#[inherit(Erc20<PausableTokenParams>, Erc20Pausable, Ownable, Initializable)]
+impl PausableToken {
+    pub fn init(&mut self) -> Result<(), OzError> {
+        self.initializable.initializer()?;
+        self.ownable.transfer_ownership_internal(msg::sender());
+        Ok(())
+    }
+
+    pub fn pause(&mut self) -> Result<(), OzError> {
+        self.ownable.only_owner()?;
+        self.pausable.pause()?;
+        Ok(())
+    }
+
+    pub fn unpause(&mut self) -> Result<(), OzError> {
+        self.ownable.only_owner()?;
+        self.pausable.unpause()?;
+        Ok(())
+    }
+
+    pub fn mint(&mut self, account: Address, value: U256) -> Result<(), OzError> {
+        self.ownable.only_owner()?;
+        self.pausable.when_not_paused()?;
+        self.erc20.mint(account, value)?;
+        Ok(())
+    }
+
+    pub fn burn(&mut self, value: U256) -> Result<(), OzError> {
+        self.pausable.when_not_paused()?;
+        self.erc20.burn(msg::sender(), value)?;
+        Ok(())
+    }
+
+    pub fn transfer(&mut self, to: Address, value: U256) -> Result<bool, OzError> {
+        self.pausable.when_not_paused()?;
+        self.erc20.transfer_internal(msg::sender(), to, value)?;
+        Ok(true)
+    }
+
+    pub fn transfer_from(&mut self, from: Address, to: Address, value: U256) -> Result<bool, OzError> {
+        self.pausable.when_not_paused()?;
+        self.erc20.spend_allowance(from, msg::sender(), value)?;
+        self.erc20.transfer_internal(from, to, value)?;
+        Ok(true)
+    }
+}
+
+#[cfg(feature = "export-abi")]
+fn main() {
+    print_abi("MIT OR Apache-2.0", "pragma solidity ^0.8.23;");
+}
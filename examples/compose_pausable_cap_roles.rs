@@ -0,0 +1,100 @@
+//! Composing `Erc20Pausable`, `Erc20Cap` and `AccessControl`: `init` sets the cap once and
+//! makes the caller admin, `PAUSER_ROLE` pauses and unpauses, `MINTER_ROLE` mints under the cap
+//! and `BURNER_ROLE` burns; while paused no balance moves. The target of the state machine test
+//! in `tests/erc20_fuzz.rs`, replicated on the host by `tests/erc20_state_machine.rs`.
+//!
+//! `cargo build --example compose_pausable_cap_roles --features export-abi` (prints the ABI when run).
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use alloc::vec::Vec;
+use oz_stylus_erc::access::access_control::{role_id, AccessControl, DEFAULT_ADMIN_ROLE};
+use oz_stylus_erc::extensions::erc20_cap::Erc20Cap;
+use oz_stylus_erc::extensions::erc20_pausable::Erc20Pausable;
+use oz_stylus_erc::tokens::erc20::{Erc20, Erc20Params, NoTransferHook};
+use oz_stylus_erc::utils::initializable::Initializable;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    msg,
+    prelude::*,
+};
+
+const PAUSER_ROLE: &str = "PAUSER_ROLE";
+const MINTER_ROLE: &str = "MINTER_ROLE";
+const BURNER_ROLE: &str = "BURNER_ROLE";
+
+pub struct ManagedTokenParams;
+
+impl Erc20Params for ManagedTokenParams {
+    const NAME: &'static str = "Managed token";
+    const SYMBOL: &'static str = "MGD";
+    const DECIMALS: u8 = 18;
+    type Hook = NoTransferHook;
+}
+
+sol_storage! {
+    #[entrypoint]
+    pub struct ManagedToken {
+        #[borrow]
+        Erc20<ManagedTokenParams> erc20;
+        #[borrow]
+        Erc20Pausable pausable;
+        #[borrow]
+        Erc20Cap cap;
+        #[borrow]
+        AccessControl access;
+        #[borrow]
+        Initializable initializable;
+    }
+}
+
+#[external]
+#[inherit(Erc20<ManagedTokenParams>, Erc20Pausable, Erc20Cap, AccessControl, Initializable)]
+impl ManagedToken {
+    pub fn init(&mut self, cap: U256) -> Result<(), Vec<u8>> {
+        self.initializable.initializer()?;
+        self.cap.set_cap(cap)?;
+        self.access.grant_role_internal(DEFAULT_ADMIN_ROLE, msg::sender());
+        Ok(())
+    }
+
+    pub fn pause(&mut self) -> Result<(), Vec<u8>> {
+        self.access.only_role(role_id(PAUSER_ROLE))?;
+        Ok(self.pausable.pause()?)
+    }
+
+    pub fn unpause(&mut self) -> Result<(), Vec<u8>> {
+        self.access.only_role(role_id(PAUSER_ROLE))?;
+        Ok(self.pausable.unpause()?)
+    }
+
+    pub fn mint(&mut self, account: Address, value: U256) -> Result<(), Vec<u8>> {
+        self.access.only_role(role_id(MINTER_ROLE))?;
+        self.pausable.when_not_paused()?;
+        self.cap.checked_mint(&mut self.erc20, account, value)
+    }
+
+    pub fn burn(&mut self, account: Address, value: U256) -> Result<(), Vec<u8>> {
+        self.access.only_role(role_id(BURNER_ROLE))?;
+        self.pausable.when_not_paused()?;
+        Ok(self.erc20.burn(account, value)?)
+    }
+
+    pub fn transfer(&mut self, to: Address, value: U256) -> Result<bool, Vec<u8>> {
+        self.pausable.when_not_paused()?;
+        self.erc20.transfer_internal(msg::sender(), to, value)?;
+        Ok(true)
+    }
+
+    pub fn transfer_from(&mut self, from: Address, to: Address, value: U256) -> Result<bool, Vec<u8>> {
+        self.pausable.when_not_paused()?;
+        self.erc20.spend_allowance(from, msg::sender(), value)?;
+        self.erc20.transfer_internal(from, to, value)?;
+        Ok(true)
+    }
+}
+
+#[cfg(feature = "export-abi")]
+fn main() {
+    print_abi("MIT OR Apache-2.0", "pragma solidity ^0.8.23;");
+}
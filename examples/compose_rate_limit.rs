@@ -0,0 +1,101 @@
+//! Composing `Erc20RateLimit`: the token overrides every external moving tokens out of an
+//! account and records the outbound amount before the balance update. Limits are configured
+//! by the admin (`DEFAULT_ADMIN_ROLE`), `FEE_EXEMPTION_MANAGER_ROLE` manages the shared
+//! `FeeExemptions` list; `init` grants both roles to the caller.
+//!
+//! `cargo build --example compose_rate_limit --features export-abi` (prints the ABI when run).
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use alloc::vec::Vec;
+use oz_stylus_erc::access::access_control::{role_id, AccessControl, DEFAULT_ADMIN_ROLE};
+use oz_stylus_erc::extensions::erc20_rate_limit::Erc20RateLimit;
+use oz_stylus_erc::extensions::fee_exemptions::{FeeExemptions, FEE_EXEMPTION_MANAGER_ROLE};
+use oz_stylus_erc::tokens::erc20::{Erc20, Erc20Params, NoTransferHook};
+use oz_stylus_erc::utils::hostio::{self, TRANSFER_HOOK_BUDGET};
+use oz_stylus_erc::utils::initializable::Initializable;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    msg,
+    prelude::*,
+};
+
+pub struct RateLimitedTokenParams;
+
+impl Erc20Params for RateLimitedTokenParams {
+    const NAME: &'static str = "Rate limited token";
+    const SYMBOL: &'static str = "RLT";
+    const DECIMALS: u8 = 18;
+    type Hook = NoTransferHook;
+}
+
+sol_storage! {
+    #[entrypoint]
+    pub struct RateLimitedToken {
+        #[borrow]
+        Erc20<RateLimitedTokenParams> erc20;
+        #[borrow]
+        Erc20RateLimit rate_limit;
+        #[borrow]
+        FeeExemptions fee_exemptions;
+        #[borrow]
+        AccessControl access;
+        #[borrow]
+        Initializable initializable;
+    }
+}
+
+#[external]
+#[inherit(Erc20<RateLimitedTokenParams>, Erc20RateLimit, FeeExemptions, AccessControl, Initializable)]
+impl RateLimitedToken {
+    pub fn init(&mut self) -> Result<(), Vec<u8>> {
+        self.initializable.initializer()?;
+        self.access.grant_role_internal(DEFAULT_ADMIN_ROLE, msg::sender());
+        self.access.grant_role_internal(role_id(FEE_EXEMPTION_MANAGER_ROLE), msg::sender());
+        Ok(())
+    }
+
+    pub fn mint(&mut self, account: Address, value: U256) -> Result<(), Vec<u8>> {
+        self.access.only_role(DEFAULT_ADMIN_ROLE)?;
+        self.erc20.mint(account, value)?;
+        Ok(())
+    }
+
+    // outbound externals of Erc20 are overridden, `record_outbound` goes before the update.
+    // With the `debug` feature the console reports when the hook exceeds the transfer hook budget.
+    pub fn transfer(&mut self, to: Address, value: U256) -> Result<bool, Vec<u8>> {
+        let from = msg::sender();
+        hostio::with_budget("rate_limit::record_outbound", TRANSFER_HOOK_BUDGET, || {
+            self.rate_limit.record_outbound(&self.fee_exemptions, from, to, value)
+        })?;
+        self.erc20.transfer_internal(from, to, value)?;
+        Ok(true)
+    }
+
+    pub fn transfer_from(&mut self, from: Address, to: Address, value: U256) -> Result<bool, Vec<u8>> {
+        self.erc20.spend_allowance(from, msg::sender(), value)?;
+        self.rate_limit.record_outbound(&self.fee_exemptions, from, to, value)?;
+        self.erc20.transfer_internal(from, to, value)?;
+        Ok(true)
+    }
+
+    // configuration setters of the extension are internal, the token decides who may call them
+    pub fn set_rate_limit(&mut self, max_amount: U256, max_transfers: U256, window: U256) -> Result<(), Vec<u8>> {
+        self.access.only_role(DEFAULT_ADMIN_ROLE)?;
+        self.rate_limit.set_rate_limit(max_amount, max_transfers, window)?;
+        Ok(())
+    }
+
+    pub fn add_fee_exemption(&mut self, account: Address) -> Result<(), Vec<u8>> {
+        self.fee_exemptions.add_fee_exemption(&self.access, account)
+    }
+
+    pub fn remove_fee_exemption(&mut self, account: Address) -> Result<(), Vec<u8>> {
+        self.fee_exemptions.remove_fee_exemption(&self.access, account)
+    }
+}
+
+#[cfg(feature = "export-abi")]
+fn main() {
+    print_abi("MIT OR Apache-2.0", "pragma solidity ^0.8.23;");
+}
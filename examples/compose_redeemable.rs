@@ -0,0 +1,74 @@
+//! Composing `Erc20Redeemable` with `AccessControl`: the admin picks the reserve token and grants
+//! `RATE_SETTER_ROLE` at `init`, `MINTER_ROLE` mints the backed token, holders burn through
+//! `redeem` for reserve tokens at the rate set by the rate setters.
+//!
+//! `cargo build --example compose_redeemable --features export-abi` (prints the ABI when run).
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use alloc::vec::Vec;
+use oz_stylus_erc::access::access_control::{role_id, AccessControl, DEFAULT_ADMIN_ROLE};
+use oz_stylus_erc::extensions::erc20_redeemable::{Erc20Redeemable, RATE_SETTER_ROLE};
+use oz_stylus_erc::tokens::erc20::{Erc20, Erc20Params, NoTransferHook};
+use oz_stylus_erc::utils::initializable::Initializable;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    msg,
+    prelude::*,
+};
+
+const MINTER_ROLE: &str = "MINTER_ROLE";
+
+pub struct BackedTokenParams;
+
+impl Erc20Params for BackedTokenParams {
+    const NAME: &'static str = "Backed token";
+    const SYMBOL: &'static str = "BKD";
+    const DECIMALS: u8 = 18;
+    type Hook = NoTransferHook;
+}
+
+sol_storage! {
+    #[entrypoint]
+    pub struct BackedToken {
+        #[borrow]
+        Erc20<BackedTokenParams> erc20;
+        #[borrow]
+        Erc20Redeemable redeemable;
+        #[borrow]
+        AccessControl access;
+        #[borrow]
+        Initializable initializable;
+    }
+}
+
+#[external]
+#[inherit(Erc20<BackedTokenParams>, Erc20Redeemable, AccessControl, Initializable)]
+impl BackedToken {
+    pub fn init(&mut self, reserve_token: Address, rate_setter: Address) -> Result<(), Vec<u8>> {
+        self.initializable.initializer()?;
+        self.access.grant_role_internal(DEFAULT_ADMIN_ROLE, msg::sender());
+        self.access.grant_role_internal(role_id(RATE_SETTER_ROLE), rate_setter);
+        self.redeemable.set_reserve_token(reserve_token);
+        Ok(())
+    }
+
+    pub fn mint(&mut self, account: Address, value: U256) -> Result<(), Vec<u8>> {
+        self.access.only_role(role_id(MINTER_ROLE))?;
+        Ok(self.erc20.mint(account, value)?)
+    }
+
+    // more rate setters are granted `RATE_SETTER_ROLE` by the admin with `grantRole`
+    pub fn set_redemption_rate(&mut self, new_rate: U256) -> Result<(), Vec<u8>> {
+        self.redeemable.set_redemption_rate(&self.access, new_rate)
+    }
+
+    pub fn redeem(&mut self, amount: U256) -> Result<U256, Vec<u8>> {
+        self.redeemable.redeem(&mut self.erc20, amount)
+    }
+}
+
+#[cfg(feature = "export-abi")]
+fn main() {
+    print_abi("MIT OR Apache-2.0", "pragma solidity ^0.8.23;");
+}
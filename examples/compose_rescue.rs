@@ -0,0 +1,72 @@
+//! Composing `utils::rescue` with `Ownable`: the owner recovers ERC20 tokens, ether and the
+//! token's own tokens sent to the token contract by mistake.
+//!
+//! `cargo build --example compose_rescue --features export-abi` (prints the ABI when run).
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use oz_stylus_erc::access::ownable::Ownable;
+use oz_stylus_erc::errors::OzError;
+use oz_stylus_erc::tokens::erc20::{Erc20, Erc20Params, NoTransferHook};
+use oz_stylus_erc::utils::initializable::Initializable;
+use oz_stylus_erc::utils::rescue;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    contract, msg,
+    prelude::*,
+};
+
+pub struct RescueTokenParams;
+
+impl Erc20Params for RescueTokenParams {
+    const NAME: &'static str = "Rescue token";
+    const SYMBOL: &'static str = "RSQ";
+    const DECIMALS: u8 = 18;
+    type Hook = NoTransferHook;
+}
+
+sol_storage! {
+    #[entrypoint]
+    pub struct RescueToken {
+        #[borrow]
+        Erc20<RescueTokenParams> erc20;
+        #[borrow]
+        Ownable ownable;
+        #[borrow]
+        Initializable initializable;
+    }
+}
+
+#[external]
+#[inherit(Erc20<RescueTokenParams>, Ownable, Initializable)]
+impl RescueToken {
+    pub fn init(&mut self) -> Result<(), OzError> {
+        self.initializable.initializer()?;
+        self.ownable.transfer_ownership_internal(msg::sender());
+        Ok(())
+    }
+
+    /// Sends `amount` of `token` held by the contract to `to`, the token itself included.
+    #[selector(name = "rescueErc20")]
+    pub fn rescue_erc20(&mut self, token: Address, to: Address, amount: U256) -> Result<(), OzError> {
+        self.ownable.only_owner()?;
+        if token == contract::address() {
+            rescue::rescue_own_tokens(&mut self.erc20, to, amount)?;
+        } else {
+            rescue::rescue_erc20(token, to, amount)?;
+        }
+        Ok(())
+    }
+
+    #[selector(name = "rescueEth")]
+    pub fn rescue_eth(&mut self, to: Address, amount: U256) -> Result<(), OzError> {
+        self.ownable.only_owner()?;
+        rescue::rescue_eth(to, amount)?;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "export-abi")]
+fn main() {
+    print_abi("MIT OR Apache-2.0", "pragma solidity ^0.8.23;");
+}
@@ -0,0 +1,91 @@
+//! Composing `RevertStats` for a testnet build: with the `revert-stats` feature failed
+//! `transfer`/`transferFrom` return `false` and are counted per error instead of reverting.
+//! `transferFrom` checks the balance before spending the allowance, so a counted failure never
+//! leaves a spent allowance behind.
+//!
+//! `cargo build --example compose_revert_stats --features export-abi,revert-stats` (prints the ABI when run).
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use alloc::vec::Vec;
+use oz_stylus_erc::access::ownable::Ownable;
+use oz_stylus_erc::tokens::erc20::{
+    Erc20, Erc20Error, Erc20InsufficientBalance, Erc20Params, NoTransferHook,
+};
+use oz_stylus_erc::utils::initializable::Initializable;
+use oz_stylus_erc::utils::revert_stats::RevertStats;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    msg,
+    prelude::*,
+};
+
+pub struct TestnetTokenParams;
+
+impl Erc20Params for TestnetTokenParams {
+    const NAME: &'static str = "Testnet token";
+    const SYMBOL: &'static str = "TST";
+    const DECIMALS: u8 = 18;
+    type Hook = NoTransferHook;
+}
+
+sol_storage! {
+    #[entrypoint]
+    pub struct TestnetToken {
+        #[borrow]
+        Erc20<TestnetTokenParams> erc20;
+        #[borrow]
+        RevertStats revert_stats;
+        #[borrow]
+        Ownable ownable;
+        #[borrow]
+        Initializable initializable;
+    }
+}
+
+impl TestnetToken {
+    fn try_transfer_from(&mut self, from: Address, to: Address, value: U256) -> Result<bool, Vec<u8>> {
+        let balance = self.erc20.balance_of_internal(from);
+        if balance < value {
+            return Err(Erc20Error::Erc20InsufficientBalance(Erc20InsufficientBalance {
+                sender: from,
+                balance,
+                needed: value,
+            })
+            .into());
+        }
+        self.erc20.spend_allowance(from, msg::sender(), value)?;
+        self.erc20.transfer_internal(from, to, value)?;
+        Ok(true)
+    }
+}
+
+#[external]
+#[inherit(Erc20<TestnetTokenParams>, RevertStats, Ownable, Initializable)]
+impl TestnetToken {
+    pub fn init(&mut self) -> Result<(), Vec<u8>> {
+        self.initializable.initializer()?;
+        self.ownable.transfer_ownership_internal(msg::sender());
+        Ok(())
+    }
+
+    pub fn mint(&mut self, account: Address, value: U256) -> Result<(), Vec<u8>> {
+        self.ownable.only_owner()?;
+        Ok(self.erc20.mint(account, value)?)
+    }
+
+    pub fn transfer(&mut self, to: Address, value: U256) -> Result<bool, Vec<u8>> {
+        let result = self.erc20.transfer(to, value).map_err(Vec::from);
+        self.revert_stats.soft_fail(result)
+    }
+
+    pub fn transfer_from(&mut self, from: Address, to: Address, value: U256) -> Result<bool, Vec<u8>> {
+        let result = self.try_transfer_from(from, to, value);
+        self.revert_stats.soft_fail(result)
+    }
+}
+
+#[cfg(feature = "export-abi")]
+fn main() {
+    print_abi("MIT OR Apache-2.0", "pragma solidity ^0.8.23;");
+}
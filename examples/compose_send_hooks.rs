@@ -0,0 +1,94 @@
+//! ERC777 style token on the ERC20 base: `send`/`operatorSend` call the `tokensToSend` and
+//! `tokensReceived` hooks of both sides and refuse contracts without receiving hook,
+//! `transfer`/`transferFrom` call the hooks too but accept any receiver, as ERC777 tokens do for
+//! their ERC20 functions. Holders authorize operators with `authorizeOperator` and register hook
+//! implementers with `setTokensSender`/`setTokensRecipient`.
+//!
+//! `cargo build --example compose_send_hooks --features export-abi` (prints the ABI when run).
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use alloc::vec::Vec;
+use oz_stylus_erc::access::ownable::Ownable;
+use oz_stylus_erc::extensions::erc20_send_hooks::Erc20SendHooks;
+use oz_stylus_erc::tokens::erc20::{Erc20, Erc20Params, NoTransferHook};
+use oz_stylus_erc::utils::initializable::Initializable;
+use stylus_sdk::{
+    abi::Bytes,
+    alloy_primitives::{Address, U256},
+    msg,
+    prelude::*,
+};
+
+pub struct HookedTokenParams;
+
+impl Erc20Params for HookedTokenParams {
+    const NAME: &'static str = "Hooked token";
+    const SYMBOL: &'static str = "HOOK";
+    const DECIMALS: u8 = 18;
+    type Hook = NoTransferHook;
+}
+
+sol_storage! {
+    #[entrypoint]
+    pub struct HookedToken {
+        #[borrow]
+        Erc20<HookedTokenParams> erc20;
+        #[borrow]
+        Erc20SendHooks hooks;
+        #[borrow]
+        Ownable ownable;
+        #[borrow]
+        Initializable initializable;
+    }
+}
+
+#[external]
+#[inherit(Erc20<HookedTokenParams>, Erc20SendHooks, Ownable, Initializable)]
+impl HookedToken {
+    pub fn init(&mut self) -> Result<(), Vec<u8>> {
+        self.initializable.initializer()?;
+        self.ownable.transfer_ownership_internal(msg::sender());
+        Ok(())
+    }
+
+    pub fn mint(&mut self, account: Address, value: U256) -> Result<(), Vec<u8>> {
+        self.ownable.only_owner()?;
+        Ok(self.erc20.mint(account, value)?)
+    }
+
+    pub fn send(&mut self, to: Address, amount: U256, data: Bytes) -> Result<(), Vec<u8>> {
+        let from = msg::sender();
+        self.hooks.send(&mut self.erc20, from, from, to, amount, &data, &[], true)
+    }
+
+    #[selector(name = "operatorSend")]
+    pub fn operator_send(
+        &mut self,
+        from: Address,
+        to: Address,
+        amount: U256,
+        data: Bytes,
+        operator_data: Bytes,
+    ) -> Result<(), Vec<u8>> {
+        self.hooks.operator_send(&mut self.erc20, msg::sender(), from, to, amount, &data, &operator_data)
+    }
+
+    pub fn transfer(&mut self, to: Address, value: U256) -> Result<bool, Vec<u8>> {
+        let from = msg::sender();
+        self.hooks.send(&mut self.erc20, from, from, to, value, &[], &[], false)?;
+        Ok(true)
+    }
+
+    pub fn transfer_from(&mut self, from: Address, to: Address, value: U256) -> Result<bool, Vec<u8>> {
+        let spender = msg::sender();
+        self.erc20.spend_allowance(from, spender, value)?;
+        self.hooks.send(&mut self.erc20, spender, from, to, value, &[], &[], false)?;
+        Ok(true)
+    }
+}
+
+#[cfg(feature = "export-abi")]
+fn main() {
+    print_abi("MIT OR Apache-2.0", "pragma solidity ^0.8.23;");
+}
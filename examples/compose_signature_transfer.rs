@@ -0,0 +1,101 @@
+//! Composing `erc20_signature_transfer`: the extension verifies the signature and consumes the
+//! nonce in the token's `ReplayGuard`, the token exposes the transfer functions and hands over
+//! its `Erc20` to move tokens. Inheriting `ReplayGuard` exposes `nonce_bitmap` and
+//! `invalidate_unordered_nonces`.
+//!
+//! `cargo build --example compose_signature_transfer --features export-abi` (prints the ABI when run).
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use alloc::{string::String, vec::Vec};
+use oz_stylus_erc::extensions::erc20_signature_transfer::{
+    self, PermitTransferFrom, TransferDetails,
+};
+use oz_stylus_erc::tokens::erc20::{Erc20, Erc20Params, NoTransferHook};
+use oz_stylus_erc::utils::eip712::{self, Eip712Domain};
+use oz_stylus_erc::utils::replay_guard::ReplayGuard;
+use stylus_sdk::{
+    abi::Bytes,
+    alloy_primitives::{Address, B256, U256},
+    prelude::*,
+};
+
+pub struct SignatureTokenParams;
+
+impl Erc20Params for SignatureTokenParams {
+    const NAME: &'static str = "Signature transfer token";
+    const SYMBOL: &'static str = "STT";
+    const DECIMALS: u8 = 18;
+    type Hook = NoTransferHook;
+}
+
+sol_storage! {
+    #[entrypoint]
+    pub struct SignatureToken {
+        #[borrow]
+        Erc20<SignatureTokenParams> erc20;
+        #[borrow]
+        ReplayGuard replay_guard;
+    }
+}
+
+#[external]
+#[inherit(Erc20<SignatureTokenParams>, ReplayGuard)]
+impl SignatureToken {
+    #[allow(clippy::too_many_arguments)]
+    pub fn permit_transfer_from(
+        &mut self,
+        amount: U256,
+        nonce: U256,
+        deadline: U256,
+        to: Address,
+        requested_amount: U256,
+        owner: Address,
+        signature: Bytes,
+    ) -> Result<(), Vec<u8>> {
+        erc20_signature_transfer::permit_transfer_from(
+            &mut self.erc20,
+            &mut self.replay_guard,
+            PermitTransferFrom { amount, nonce, deadline },
+            TransferDetails { to, requested_amount },
+            owner,
+            &signature,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn permit_witness_transfer_from(
+        &mut self,
+        amount: U256,
+        nonce: U256,
+        deadline: U256,
+        to: Address,
+        requested_amount: U256,
+        owner: Address,
+        witness: B256,
+        witness_type_string: String,
+        signature: Bytes,
+    ) -> Result<(), Vec<u8>> {
+        erc20_signature_transfer::permit_witness_transfer_from(
+            &mut self.erc20,
+            &mut self.replay_guard,
+            PermitTransferFrom { amount, nonce, deadline },
+            TransferDetails { to, requested_amount },
+            owner,
+            witness,
+            &witness_type_string,
+            &signature,
+        )
+    }
+
+    /// ERC-5267 domain of the signatures accepted by the token, for wallets.
+    #[selector(name = "eip712Domain")]
+    pub fn eip712_domain(&self) -> Result<Eip712Domain, Vec<u8>> {
+        Ok(eip712::erc20_domain::<SignatureTokenParams>())
+    }
+}
+
+#[cfg(feature = "export-abi")]
+fn main() {
+    print_abi("MIT OR Apache-2.0", "pragma solidity ^0.8.23;");
+}
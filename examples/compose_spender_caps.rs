@@ -0,0 +1,68 @@
+//! Composing `Erc20SpenderCaps`: `transfer_from` is overridden to spend the allowance through the
+//! extension, recording the spending of the caller against the cap set by the owner. It is the
+//! only external of this token spending an allowance. The owner mints.
+//!
+//! `cargo build --example compose_spender_caps --features export-abi` (prints the ABI when run).
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use alloc::vec::Vec;
+use oz_stylus_erc::access::ownable::Ownable;
+use oz_stylus_erc::extensions::erc20_spender_caps::Erc20SpenderCaps;
+use oz_stylus_erc::tokens::erc20::{Erc20, Erc20Params, NoTransferHook};
+use oz_stylus_erc::utils::initializable::Initializable;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    msg,
+    prelude::*,
+};
+
+pub struct CappedSpendingTokenParams;
+
+impl Erc20Params for CappedSpendingTokenParams {
+    const NAME: &'static str = "Capped spending token";
+    const SYMBOL: &'static str = "CST";
+    const DECIMALS: u8 = 18;
+    type Hook = NoTransferHook;
+}
+
+sol_storage! {
+    #[entrypoint]
+    pub struct CappedSpendingToken {
+        #[borrow]
+        Erc20<CappedSpendingTokenParams> erc20;
+        #[borrow]
+        Erc20SpenderCaps spender_caps;
+        #[borrow]
+        Ownable ownable;
+        #[borrow]
+        Initializable initializable;
+    }
+}
+
+#[external]
+#[inherit(Erc20<CappedSpendingTokenParams>, Erc20SpenderCaps, Ownable, Initializable)]
+impl CappedSpendingToken {
+    pub fn init(&mut self) -> Result<(), Vec<u8>> {
+        self.initializable.initializer()?;
+        self.ownable.transfer_ownership_internal(msg::sender());
+        Ok(())
+    }
+
+    pub fn mint(&mut self, account: Address, value: U256) -> Result<(), Vec<u8>> {
+        self.ownable.only_owner()?;
+        Ok(self.erc20.mint(account, value)?)
+    }
+
+    pub fn transfer_from(&mut self, from: Address, to: Address, value: U256) -> Result<bool, Vec<u8>> {
+        self.spender_caps
+            .spend_allowance(&mut self.erc20, from, msg::sender(), value)?;
+        self.erc20.transfer_internal(from, to, value)?;
+        Ok(true)
+    }
+}
+
+#[cfg(feature = "export-abi")]
+fn main() {
+    print_abi("MIT OR Apache-2.0", "pragma solidity ^0.8.23;");
+}
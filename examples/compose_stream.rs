@@ -0,0 +1,75 @@
+//! Composing `Erc20Stream`: holders stream their tokens to a recipient over time, the owner
+//! mints. The stream escrow is the token address, nothing else moves its balance.
+//!
+//! `cargo build --example compose_stream --features export-abi` (prints the ABI when run).
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use alloc::vec::Vec;
+use oz_stylus_erc::access::ownable::Ownable;
+use oz_stylus_erc::extensions::erc20_stream::Erc20Stream;
+use oz_stylus_erc::tokens::erc20::{Erc20, Erc20Params, NoTransferHook};
+use oz_stylus_erc::utils::initializable::Initializable;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    msg,
+    prelude::*,
+};
+
+pub struct StreamTokenParams;
+
+impl Erc20Params for StreamTokenParams {
+    const NAME: &'static str = "Stream token";
+    const SYMBOL: &'static str = "FLOW";
+    const DECIMALS: u8 = 18;
+    type Hook = NoTransferHook;
+}
+
+sol_storage! {
+    #[entrypoint]
+    pub struct StreamToken {
+        #[borrow]
+        Erc20<StreamTokenParams> erc20;
+        #[borrow]
+        Erc20Stream streams;
+        #[borrow]
+        Ownable ownable;
+        #[borrow]
+        Initializable initializable;
+    }
+}
+
+#[external]
+#[inherit(Erc20<StreamTokenParams>, Erc20Stream, Ownable, Initializable)]
+impl StreamToken {
+    pub fn init(&mut self) -> Result<(), Vec<u8>> {
+        self.initializable.initializer()?;
+        self.ownable.transfer_ownership_internal(msg::sender());
+        Ok(())
+    }
+
+    pub fn mint(&mut self, account: Address, value: U256) -> Result<(), Vec<u8>> {
+        self.ownable.only_owner()?;
+        Ok(self.erc20.mint(account, value)?)
+    }
+
+    #[selector(name = "createStream")]
+    pub fn create_stream(&mut self, recipient: Address, deposit: U256, start: U256, stop: U256) -> Result<U256, Vec<u8>> {
+        self.streams.create_stream(&mut self.erc20, recipient, deposit, start, stop)
+    }
+
+    #[selector(name = "withdrawFromStream")]
+    pub fn withdraw_from_stream(&mut self, stream_id: U256, amount: U256) -> Result<(), Vec<u8>> {
+        self.streams.withdraw_from_stream(&mut self.erc20, stream_id, amount)
+    }
+
+    #[selector(name = "cancelStream")]
+    pub fn cancel_stream(&mut self, stream_id: U256) -> Result<(), Vec<u8>> {
+        self.streams.cancel_stream(&mut self.erc20, stream_id)
+    }
+}
+
+#[cfg(feature = "export-abi")]
+fn main() {
+    print_abi("MIT OR Apache-2.0", "pragma solidity ^0.8.23;");
+}
@@ -0,0 +1,67 @@
+//! Composing `Erc20TemporaryApproval`: `approveWithExpiry` gives allowances a deadline,
+//! `transfer_from` is overridden to refuse expired allowances, `approve` to clear the deadline and
+//! `allowance` to report expired allowances as 0.
+//!
+//! `cargo build --example compose_temporary_approval --features export-abi` (prints the ABI when run).
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use alloc::vec::Vec;
+use oz_stylus_erc::extensions::erc20_temporary_approval::Erc20TemporaryApproval;
+use oz_stylus_erc::tokens::erc20::{Erc20, Erc20Params, NoTransferHook};
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    msg,
+    prelude::*,
+};
+
+pub struct ExpiringApprovalTokenParams;
+
+impl Erc20Params for ExpiringApprovalTokenParams {
+    const NAME: &'static str = "Expiring approval token";
+    const SYMBOL: &'static str = "EAT";
+    const DECIMALS: u8 = 18;
+    type Hook = NoTransferHook;
+}
+
+sol_storage! {
+    #[entrypoint]
+    pub struct ExpiringApprovalToken {
+        #[borrow]
+        Erc20<ExpiringApprovalTokenParams> erc20;
+        #[borrow]
+        Erc20TemporaryApproval temporary_approval;
+    }
+}
+
+#[external]
+#[inherit(Erc20<ExpiringApprovalTokenParams>, Erc20TemporaryApproval)]
+impl ExpiringApprovalToken {
+    #[selector(name = "approveWithExpiry")]
+    pub fn approve_with_expiry(&mut self, spender: Address, value: U256, deadline: U256) -> Result<bool, Vec<u8>> {
+        self.temporary_approval
+            .approve_with_expiry(&mut self.erc20, msg::sender(), spender, value, deadline)?;
+        Ok(true)
+    }
+
+    pub fn approve(&mut self, spender: Address, value: U256) -> Result<bool, Vec<u8>> {
+        self.temporary_approval.approve(&mut self.erc20, msg::sender(), spender, value)?;
+        Ok(true)
+    }
+
+    pub fn allowance(&self, owner: Address, spender: Address) -> Result<U256, Vec<u8>> {
+        Ok(self.temporary_approval.allowance(&self.erc20, owner, spender))
+    }
+
+    pub fn transfer_from(&mut self, from: Address, to: Address, value: U256) -> Result<bool, Vec<u8>> {
+        self.temporary_approval
+            .spend_allowance(&mut self.erc20, from, msg::sender(), value)?;
+        self.erc20.transfer_internal(from, to, value)?;
+        Ok(true)
+    }
+}
+
+#[cfg(feature = "export-abi")]
+fn main() {
+    print_abi("MIT OR Apache-2.0", "pragma solidity ^0.8.23;");
+}
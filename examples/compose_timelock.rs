@@ -0,0 +1,40 @@
+//! Composing `TimelockController`: `init` sets the minimum delay, the proposers (also
+//! cancellers) and the executors once, everything else is inherited. For a governor stack pass
+//! the governor as proposer and executor, then transfer the ownership of the governed contracts
+//! to the timelock.
+//!
+//! `cargo build --example compose_timelock --features export-abi` (prints the ABI when run).
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use alloc::vec::Vec;
+use oz_stylus_erc::governance::timelock::TimelockController;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    msg,
+    prelude::*,
+};
+
+sol_storage! {
+    #[entrypoint]
+    pub struct Timelock {
+        #[borrow]
+        TimelockController timelock;
+    }
+}
+
+#[external]
+#[inherit(TimelockController)]
+impl Timelock {
+    /// The caller is admin until it renounces `DEFAULT_ADMIN_ROLE`. Fails when already
+    /// initialized.
+    pub fn init(&mut self, min_delay: U256, proposers: Vec<Address>, executors: Vec<Address>) -> Result<(), Vec<u8>> {
+        self.timelock.configure(min_delay, &proposers, &executors, msg::sender())?;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "export-abi")]
+fn main() {
+    print_abi("MIT OR Apache-2.0", "pragma solidity ^0.8.23;");
+}
@@ -0,0 +1,86 @@
+//! Composing `TokenMigrator` with `Ownable` and `Pausable`: the new token is its own migrator,
+//! holders approve it on the old token and call `migrate(amount)` to swap at the ratio set by
+//! `init`, until the deadline. The owner pauses migrations, e.g. while investigating an incident
+//! on the old token.
+//!
+//! `cargo build --example compose_token_migrator --features export-abi` (prints the ABI when run).
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use alloc::vec::Vec;
+use oz_stylus_erc::access::ownable::Ownable;
+use oz_stylus_erc::finance::token_migrator::TokenMigrator;
+use oz_stylus_erc::tokens::erc20::{Erc20, Erc20Params, NoTransferHook};
+use oz_stylus_erc::utils::initializable::Initializable;
+use oz_stylus_erc::utils::pausable::Pausable;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    msg,
+    prelude::*,
+};
+
+pub struct MigratedTokenParams;
+
+impl Erc20Params for MigratedTokenParams {
+    const NAME: &'static str = "Migrated token";
+    const SYMBOL: &'static str = "MIG";
+    const DECIMALS: u8 = 18;
+    type Hook = NoTransferHook;
+}
+
+sol_storage! {
+    #[entrypoint]
+    pub struct MigratedToken {
+        #[borrow]
+        Erc20<MigratedTokenParams> erc20;
+        #[borrow]
+        TokenMigrator migrator;
+        #[borrow]
+        Pausable pausable;
+        #[borrow]
+        Ownable ownable;
+        #[borrow]
+        Initializable initializable;
+    }
+}
+
+#[external]
+#[inherit(Erc20<MigratedTokenParams>, TokenMigrator, Pausable, Ownable, Initializable)]
+impl MigratedToken {
+    /// Migrates from `old_token` at `ratio_numerator / ratio_denominator` until `deadline`,
+    /// burning the old tokens with `burn_old` and escrowing them otherwise.
+    pub fn init(
+        &mut self,
+        old_token: Address,
+        ratio_numerator: U256,
+        ratio_denominator: U256,
+        deadline: U256,
+        burn_old: bool,
+    ) -> Result<(), Vec<u8>> {
+        self.initializable.initializer()?;
+        self.ownable.transfer_ownership_internal(msg::sender());
+        Ok(self
+            .migrator
+            .configure(old_token, ratio_numerator, ratio_denominator, deadline, burn_old)?)
+    }
+
+    pub fn migrate(&mut self, amount: U256) -> Result<U256, Vec<u8>> {
+        self.migrator
+            .migrate(&self.pausable, &mut self.erc20, msg::sender(), amount)
+    }
+
+    pub fn pause(&mut self) -> Result<(), Vec<u8>> {
+        self.ownable.only_owner()?;
+        Ok(self.pausable.pause()?)
+    }
+
+    pub fn unpause(&mut self) -> Result<(), Vec<u8>> {
+        self.ownable.only_owner()?;
+        Ok(self.pausable.unpause()?)
+    }
+}
+
+#[cfg(feature = "export-abi")]
+fn main() {
+    print_abi("MIT OR Apache-2.0", "pragma solidity ^0.8.23;");
+}
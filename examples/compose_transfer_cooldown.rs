@@ -0,0 +1,94 @@
+//! Composing `Erc20TransferCooldown` with `AccessControl`: outbound externals record the transfer
+//! before the balance update, the admin sets the cooldown and `FEE_EXEMPTION_MANAGER_ROLE` manages
+//! the shared `FeeExemptions`; `init` grants both roles to the caller.
+//!
+//! `cargo build --example compose_transfer_cooldown --features export-abi` (prints the ABI when run).
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use alloc::vec::Vec;
+use oz_stylus_erc::access::access_control::{role_id, AccessControl, DEFAULT_ADMIN_ROLE};
+use oz_stylus_erc::extensions::erc20_transfer_cooldown::Erc20TransferCooldown;
+use oz_stylus_erc::extensions::fee_exemptions::{FeeExemptions, FEE_EXEMPTION_MANAGER_ROLE};
+use oz_stylus_erc::tokens::erc20::{Erc20, Erc20Params, NoTransferHook};
+use oz_stylus_erc::utils::initializable::Initializable;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    msg,
+    prelude::*,
+};
+
+pub struct LaunchTokenParams;
+
+impl Erc20Params for LaunchTokenParams {
+    const NAME: &'static str = "Launch token";
+    const SYMBOL: &'static str = "LCH";
+    const DECIMALS: u8 = 18;
+    type Hook = NoTransferHook;
+}
+
+sol_storage! {
+    #[entrypoint]
+    pub struct LaunchToken {
+        #[borrow]
+        Erc20<LaunchTokenParams> erc20;
+        #[borrow]
+        Erc20TransferCooldown cooldown;
+        #[borrow]
+        FeeExemptions fee_exemptions;
+        #[borrow]
+        AccessControl access;
+        #[borrow]
+        Initializable initializable;
+    }
+}
+
+#[external]
+#[inherit(Erc20<LaunchTokenParams>, Erc20TransferCooldown, FeeExemptions, AccessControl, Initializable)]
+impl LaunchToken {
+    pub fn init(&mut self, cooldown: U256) -> Result<(), Vec<u8>> {
+        self.initializable.initializer()?;
+        self.access.grant_role_internal(DEFAULT_ADMIN_ROLE, msg::sender());
+        self.access.grant_role_internal(role_id(FEE_EXEMPTION_MANAGER_ROLE), msg::sender());
+        self.cooldown.set_cooldown(cooldown);
+        Ok(())
+    }
+
+    pub fn mint(&mut self, account: Address, value: U256) -> Result<(), Vec<u8>> {
+        self.access.only_role(DEFAULT_ADMIN_ROLE)?;
+        Ok(self.erc20.mint(account, value)?)
+    }
+
+    pub fn transfer(&mut self, to: Address, value: U256) -> Result<bool, Vec<u8>> {
+        let from = msg::sender();
+        self.cooldown.record_outbound(&self.fee_exemptions, from, to, value)?;
+        self.erc20.transfer_internal(from, to, value)?;
+        Ok(true)
+    }
+
+    pub fn transfer_from(&mut self, from: Address, to: Address, value: U256) -> Result<bool, Vec<u8>> {
+        self.erc20.spend_allowance(from, msg::sender(), value)?;
+        self.cooldown.record_outbound(&self.fee_exemptions, from, to, value)?;
+        self.erc20.transfer_internal(from, to, value)?;
+        Ok(true)
+    }
+
+    pub fn set_transfer_cooldown(&mut self, cooldown: U256) -> Result<(), Vec<u8>> {
+        self.access.only_role(DEFAULT_ADMIN_ROLE)?;
+        self.cooldown.set_cooldown(cooldown);
+        Ok(())
+    }
+
+    pub fn add_fee_exemption(&mut self, account: Address) -> Result<(), Vec<u8>> {
+        self.fee_exemptions.add_fee_exemption(&self.access, account)
+    }
+
+    pub fn remove_fee_exemption(&mut self, account: Address) -> Result<(), Vec<u8>> {
+        self.fee_exemptions.remove_fee_exemption(&self.access, account)
+    }
+}
+
+#[cfg(feature = "export-abi")]
+fn main() {
+    print_abi("MIT OR Apache-2.0", "pragma solidity ^0.8.23;");
+}
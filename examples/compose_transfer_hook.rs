@@ -0,0 +1,88 @@
+//! Plugging a `TransferHook` into `Erc20Params`: an anti-whale limit checked after every transfer,
+//! no external is overridden, `transfer` and `transferFrom` are inherited from `Erc20`. Mints by
+//! the owner are not limited.
+//!
+//! `cargo build --example compose_transfer_hook --features export-abi` (prints the ABI when run).
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use alloc::vec::Vec;
+use oz_stylus_erc::access::ownable::Ownable;
+use oz_stylus_erc::tokens::erc20::{Erc20, Erc20Params, TransferHook};
+use oz_stylus_erc::utils::initializable::Initializable;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    alloy_sol_types::{sol, SolError},
+    msg,
+    prelude::*,
+};
+
+sol! {
+    /// Indicates that a transfer would leave `account` holding more than `max_balance`.
+    error MaxWalletExceeded(address account, uint256 balance, uint256 max_balance);
+}
+
+/// Share of the total supply a single account may receive through transfers, in basis points.
+const MAX_WALLET_BPS: u64 = 100;
+
+pub struct MaxWalletHook;
+
+impl TransferHook for MaxWalletHook {
+    fn after_update<T: Erc20Params>(erc20: &Erc20<T>, from: Address, to: Address, _value: U256) -> Result<(), Vec<u8>> {
+        if from == Address::ZERO || to == Address::ZERO {
+            return Ok(());
+        }
+        let balance = erc20.balance_of_internal(to);
+        let max_balance = erc20.total_supply_internal() * U256::from(MAX_WALLET_BPS) / U256::from(10_000);
+        if balance > max_balance {
+            return Err(MaxWalletExceeded {
+                account: to,
+                balance,
+                max_balance,
+            }
+            .encode());
+        }
+        Ok(())
+    }
+}
+
+pub struct FairLaunchTokenParams;
+
+impl Erc20Params for FairLaunchTokenParams {
+    const NAME: &'static str = "Fair launch token";
+    const SYMBOL: &'static str = "FAIR";
+    const DECIMALS: u8 = 18;
+    type Hook = MaxWalletHook;
+}
+
+sol_storage! {
+    #[entrypoint]
+    pub struct FairLaunchToken {
+        #[borrow]
+        Erc20<FairLaunchTokenParams> erc20;
+        #[borrow]
+        Ownable ownable;
+        #[borrow]
+        Initializable initializable;
+    }
+}
+
+#[external]
+#[inherit(Erc20<FairLaunchTokenParams>, Ownable, Initializable)]
+impl FairLaunchToken {
+    pub fn init(&mut self) -> Result<(), Vec<u8>> {
+        self.initializable.initializer()?;
+        self.ownable.transfer_ownership_internal(msg::sender());
+        Ok(())
+    }
+
+    pub fn mint(&mut self, account: Address, value: U256) -> Result<(), Vec<u8>> {
+        self.ownable.only_owner()?;
+        Ok(self.erc20.mint(account, value)?)
+    }
+}
+
+#[cfg(feature = "export-abi")]
+fn main() {
+    print_abi("MIT OR Apache-2.0", "pragma solidity ^0.8.23;");
+}
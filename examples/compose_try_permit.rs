@@ -0,0 +1,54 @@
+//! An integrator contract accepting deposits of any EIP-2612 token with a permit in the same
+//! transaction, using `safe_erc20::try_permit` so a frontrun permit does not block the deposit.
+//!
+//! `cargo build --example compose_try_permit --features export-abi` (prints the ABI when run).
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use alloc::vec::Vec;
+use oz_stylus_erc::utils::safe_erc20;
+use stylus_sdk::{
+    alloy_primitives::{Address, B256, U256},
+    contract, msg,
+    prelude::*,
+};
+
+sol_storage! {
+    #[entrypoint]
+    pub struct DepositBox {
+        /// token => depositor => deposited amount
+        mapping(address => mapping(address => uint256)) deposits;
+    }
+}
+
+#[external]
+impl DepositBox {
+    #[allow(clippy::too_many_arguments)]
+    pub fn deposit_with_permit(
+        &mut self,
+        token: Address,
+        amount: U256,
+        deadline: U256,
+        v: u8,
+        r: B256,
+        s: B256,
+    ) -> Result<(), Vec<u8>> {
+        let owner = msg::sender();
+        safe_erc20::try_permit(token, owner, contract::address(), amount, deadline, v, r, s)?;
+        safe_erc20::safe_transfer_from(token, owner, contract::address(), amount)?;
+
+        let mut deposit_ref = self.deposits.setter(token).setter(owner);
+        let deposit = deposit_ref.get();
+        deposit_ref.set(deposit + amount);
+        Ok(())
+    }
+
+    pub fn deposit_of(&self, token: Address, account: Address) -> Result<U256, Vec<u8>> {
+        Ok(self.deposits.get(token).get(account))
+    }
+}
+
+#[cfg(feature = "export-abi")]
+fn main() {
+    print_abi("MIT OR Apache-2.0", "pragma solidity ^0.8.23;");
+}
@@ -0,0 +1,123 @@
+//! Composing `Erc20Votes`: every external moving balances is overridden to call
+//! `move_voting_units` after `Erc20`, and `delegate` is exposed passing the `Erc20`. The owner
+//! mints and sets the ERC-7572 metadata URI. `delegateBySig` accepts OpenZeppelin compatible
+//! signed delegations, signed under the domain returned by `eip712Domain`.
+//!
+//! `cargo build --example compose_votes --features export-abi` (prints the ABI when run).
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use alloc::{string::String, vec::Vec};
+use oz_stylus_erc::access::ownable::Ownable;
+use oz_stylus_erc::extensions::contract_uri::ContractUri;
+use oz_stylus_erc::extensions::erc20_votes::Erc20Votes;
+use oz_stylus_erc::tokens::erc20::{Erc20, Erc20Params, NoTransferHook};
+use oz_stylus_erc::utils::eip712::{self, Eip712Domain};
+use oz_stylus_erc::utils::initializable::Initializable;
+use oz_stylus_erc::utils::replay_guard::ReplayGuard;
+use stylus_sdk::{
+    alloy_primitives::{Address, B256, U256},
+    msg,
+    prelude::*,
+};
+
+pub struct GovernanceTokenParams;
+
+impl Erc20Params for GovernanceTokenParams {
+    const NAME: &'static str = "Governance token";
+    const SYMBOL: &'static str = "GOV";
+    const DECIMALS: u8 = 18;
+    type Hook = NoTransferHook;
+}
+
+sol_storage! {
+    #[entrypoint]
+    pub struct GovernanceToken {
+        #[borrow]
+        Erc20<GovernanceTokenParams> erc20;
+        #[borrow]
+        Erc20Votes<GovernanceTokenParams> votes;
+        #[borrow]
+        Ownable ownable;
+        #[borrow]
+        ContractUri contract_uri;
+        #[borrow]
+        ReplayGuard replay_guard;
+        #[borrow]
+        Initializable initializable;
+    }
+}
+
+#[external]
+#[inherit(Erc20<GovernanceTokenParams>, Erc20Votes<GovernanceTokenParams>, Ownable, ContractUri, ReplayGuard, Initializable)]
+impl GovernanceToken {
+    pub fn init(&mut self) -> Result<(), Vec<u8>> {
+        self.initializable.initializer()?;
+        self.ownable.transfer_ownership_internal(msg::sender());
+        Ok(())
+    }
+
+    pub fn mint(&mut self, account: Address, value: U256) -> Result<(), Vec<u8>> {
+        self.ownable.only_owner()?;
+        self.erc20.mint(account, value)?;
+        self.votes.move_voting_units(Address::ZERO, account, value);
+        Ok(())
+    }
+
+    pub fn burn(&mut self, value: U256) -> Result<(), Vec<u8>> {
+        let account = msg::sender();
+        self.erc20.burn(account, value)?;
+        self.votes.move_voting_units(account, Address::ZERO, value);
+        Ok(())
+    }
+
+    pub fn transfer(&mut self, to: Address, value: U256) -> Result<bool, Vec<u8>> {
+        let from = msg::sender();
+        self.erc20.transfer_internal(from, to, value)?;
+        self.votes.move_voting_units(from, to, value);
+        Ok(true)
+    }
+
+    pub fn transfer_from(&mut self, from: Address, to: Address, value: U256) -> Result<bool, Vec<u8>> {
+        self.erc20.spend_allowance(from, msg::sender(), value)?;
+        self.erc20.transfer_internal(from, to, value)?;
+        self.votes.move_voting_units(from, to, value);
+        Ok(true)
+    }
+
+    #[selector(name = "delegate")]
+    pub fn delegate(&mut self, delegatee: Address) -> Result<(), Vec<u8>> {
+        self.votes.delegate(&self.erc20, msg::sender(), delegatee)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    #[selector(name = "delegateBySig")]
+    pub fn delegate_by_sig(
+        &mut self,
+        delegatee: Address,
+        nonce: U256,
+        expiry: U256,
+        v: u8,
+        r: B256,
+        s: B256,
+    ) -> Result<(), Vec<u8>> {
+        self.votes
+            .delegate_by_sig(&self.erc20, &mut self.replay_guard, delegatee, nonce, expiry, v, r, s)
+    }
+
+    #[selector(name = "eip712Domain")]
+    pub fn eip712_domain(&self) -> Result<Eip712Domain, Vec<u8>> {
+        Ok(eip712::erc20_domain::<GovernanceTokenParams>())
+    }
+
+    #[selector(name = "setContractURI")]
+    pub fn set_contract_uri(&mut self, uri: String) -> Result<(), Vec<u8>> {
+        self.ownable.only_owner()?;
+        Ok(self.contract_uri.set_contract_uri(&uri)?)
+    }
+}
+
+#[cfg(feature = "export-abi")]
+fn main() {
+    print_abi("MIT OR Apache-2.0", "pragma solidity ^0.8.23;");
+}
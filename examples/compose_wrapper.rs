@@ -0,0 +1,55 @@
+//! Composing `Erc20Wrapper`: an 18 decimals token wrapping a 6 decimals bridged stablecoin,
+//! amounts are scaled by 10^12 and the previews are inherited. `init` can only succeed once as
+//! the underlying can not be replaced.
+//!
+//! `cargo build --example compose_wrapper --features export-abi` (prints the ABI when run).
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use alloc::vec::Vec;
+use oz_stylus_erc::extensions::erc20_wrapper::Erc20Wrapper;
+use oz_stylus_erc::tokens::erc20::{Erc20, Erc20Params, NoTransferHook};
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    prelude::*,
+};
+
+pub struct WrappedUsdParams;
+
+impl Erc20Params for WrappedUsdParams {
+    const NAME: &'static str = "Wrapped USD";
+    const SYMBOL: &'static str = "wUSD";
+    const DECIMALS: u8 = 18;
+    type Hook = NoTransferHook;
+}
+
+sol_storage! {
+    #[entrypoint]
+    pub struct WrappedUsd {
+        #[borrow]
+        Erc20<WrappedUsdParams> erc20;
+        #[borrow]
+        Erc20Wrapper wrapper;
+    }
+}
+
+#[external]
+#[inherit(Erc20<WrappedUsdParams>, Erc20Wrapper)]
+impl WrappedUsd {
+    pub fn init(&mut self, underlying: Address, underlying_decimals: u8) -> Result<(), Vec<u8>> {
+        Ok(self.wrapper.set_underlying::<WrappedUsdParams>(underlying, underlying_decimals)?)
+    }
+
+    pub fn deposit_for(&mut self, account: Address, amount: U256) -> Result<U256, Vec<u8>> {
+        self.wrapper.deposit_for(&mut self.erc20, account, amount)
+    }
+
+    pub fn withdraw_to(&mut self, account: Address, value: U256) -> Result<U256, Vec<u8>> {
+        self.wrapper.withdraw_to(&mut self.erc20, account, value)
+    }
+}
+
+#[cfg(feature = "export-abi")]
+fn main() {
+    print_abi("MIT OR Apache-2.0", "pragma solidity ^0.8.23;");
+}
@@ -0,0 +1,61 @@
+//! Deterministic factory built on `utils::create2`: anyone deploys init code at an address
+//! derived from their own account and salt, so a wallet or a vault gets the same address on
+//! every chain the factory is deployed at the same address, and nobody can squat it.
+//!
+//! `cargo build --example create2_factory --features export-abi` (prints the ABI when run).
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use alloc::vec::Vec;
+use oz_stylus_erc::utils::create2;
+use stylus_sdk::{
+    abi::Bytes,
+    alloy_primitives::{Address, B256},
+    alloy_sol_types::sol,
+    crypto::keccak,
+    evm, msg,
+    prelude::*,
+};
+
+sol! {
+    event ContractDeployed(address indexed deployer, address indexed deployed, bytes32 salt);
+}
+
+sol_storage! {
+    #[entrypoint]
+    pub struct Create2Factory {}
+}
+
+#[external]
+impl Create2Factory {
+    /// Deploys `init_code` with the caller salt, forwarding the sent value to the new contract.
+    #[payable]
+    #[selector(name = "deploy")]
+    pub fn deploy(&mut self, salt: B256, init_code: Bytes) -> Result<Address, Vec<u8>> {
+        let deployer = msg::sender();
+        let deployed = create2::deploy(msg::value(), create2::account_salt(deployer, salt), &init_code)?;
+        evm::log(ContractDeployed {
+            deployer,
+            deployed,
+            salt,
+        });
+        Ok(deployed)
+    }
+
+    /// Address `deploy` returns for `deployer`, `salt` and init code hashing to `init_code_hash`.
+    #[selector(name = "computeAddress")]
+    pub fn compute_address(&self, deployer: Address, salt: B256, init_code_hash: B256) -> Result<Address, Vec<u8>> {
+        Ok(create2::compute_address(create2::account_salt(deployer, salt), init_code_hash))
+    }
+
+    /// Same as `computeAddress` with the init code itself.
+    #[selector(name = "computeAddressOfCode")]
+    pub fn compute_address_of_code(&self, deployer: Address, salt: B256, init_code: Bytes) -> Result<Address, Vec<u8>> {
+        Ok(create2::compute_address(create2::account_salt(deployer, salt), keccak(init_code.as_slice())))
+    }
+}
+
+#[cfg(feature = "export-abi")]
+fn main() {
+    print_abi("MIT OR Apache-2.0", "pragma solidity ^0.8.23;");
+}
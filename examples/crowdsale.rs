@@ -0,0 +1,245 @@
+//! Refundable crowdsale: buyers send ether to `buyTokens()` and reserve `msg.value * rate` tokens
+//! until `closingTime`, each purchase at most `maxPurchase` wei and the reserved plus minted
+//! tokens at most the cap. After the closing time anyone finalizes the sale: if `goal` wei were
+//! raised the escrow is closed, the beneficiary receives the ether and buyers claim their tokens,
+//! otherwise buyers claim refunds and their reservations are dropped. Built from `Erc20`,
+//! `Ownable`, `Erc20Cap` and `RefundEscrow`.
+//!
+//! `cargo build --example crowdsale --features export-abi` (prints the ABI when run).
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use oz_stylus_erc::access::ownable::Ownable;
+use oz_stylus_erc::errors::OzError;
+use oz_stylus_erc::extensions::erc20_cap::Erc20Cap;
+use oz_stylus_erc::finance::refund_escrow::RefundEscrow;
+use oz_stylus_erc::tokens::erc20::{Erc20, Erc20Params, NoTransferHook};
+use oz_stylus_erc::utils::initializable::Initializable;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    alloy_sol_types::{sol, SolError},
+    block, evm, msg,
+    prelude::*,
+};
+
+pub struct CrowdsaleTokenParams;
+
+impl Erc20Params for CrowdsaleTokenParams {
+    const NAME: &'static str = "Crowdsale token";
+    const SYMBOL: &'static str = "CRWD";
+    const DECIMALS: u8 = 18;
+    type Hook = NoTransferHook;
+}
+
+sol! {
+    event TokensPurchased(address indexed buyer, uint256 value, uint256 amount);
+    event CrowdsaleFinalized(uint256 wei_raised, bool goal_reached);
+    event TokensClaimed(address indexed buyer, uint256 amount);
+
+    /// Indicates a purchase of zero wei or above `max_purchase`.
+    error CrowdsaleInvalidPurchase(uint256 value, uint256 max_purchase);
+
+    /// Indicates a purchase after `closing_time`, or a finalization before it.
+    error CrowdsaleNotOpen(uint256 closing_time);
+
+    /// Indicates a purchase or finalization once finalized, or a claim before.
+    error CrowdsaleFinalizedState(bool finalized);
+
+    /// Indicates that the goal was not reached, tokens are not claimable.
+    error CrowdsaleGoalNotReached(uint256 wei_raised, uint256 goal);
+
+    /// Indicates that `buyer` reserved no tokens.
+    error CrowdsaleNothingToClaim(address buyer);
+}
+
+sol_storage! {
+    #[entrypoint]
+    pub struct CrowdsaleToken {
+        #[borrow]
+        Erc20<CrowdsaleTokenParams> erc20;
+        #[borrow]
+        Ownable ownable;
+        #[borrow]
+        Erc20Cap cap;
+        #[borrow]
+        RefundEscrow escrow;
+        /// token units per wei
+        uint256 rate;
+        /// wei to raise for the sale to succeed
+        uint256 goal;
+        /// wei accepted by a single purchase
+        uint256 max_purchase;
+        uint256 closing_time;
+        uint256 wei_raised;
+        bool finalized;
+        /// tokens reserved by each buyer, minted on claim
+        mapping(address => uint256) reserved;
+        uint256 total_reserved;
+        #[borrow]
+        Initializable initializable;
+    }
+}
+
+#[external]
+#[inherit(Erc20<CrowdsaleTokenParams>, Ownable, Erc20Cap, RefundEscrow, Initializable)]
+impl CrowdsaleToken {
+    pub fn init(
+        &mut self,
+        beneficiary: Address,
+        rate: U256,
+        goal: U256,
+        max_purchase: U256,
+        cap: U256,
+        closing_time: U256,
+    ) -> Result<(), OzError> {
+        self.initializable.initializer()?;
+        if rate == U256::ZERO || max_purchase == U256::ZERO {
+            return Err("invalid crowdsale terms".into());
+        }
+        self.ownable.transfer_ownership_internal(msg::sender());
+        self.cap.set_cap(cap)?;
+        self.escrow.set_beneficiary(beneficiary)?;
+        self.rate.set(rate);
+        self.goal.set(goal);
+        self.max_purchase.set(max_purchase);
+        self.closing_time.set(closing_time);
+        Ok(())
+    }
+
+    /// Reserves `msg.value * rate` tokens for the caller, the ether is held in escrow.
+    #[payable]
+    pub fn buy_tokens(&mut self) -> Result<U256, OzError> {
+        self.when_open()?;
+        let buyer = msg::sender();
+        let value = msg::value();
+        let max_purchase = self.max_purchase.get();
+        if value == U256::ZERO || value > max_purchase {
+            return Err(CrowdsaleInvalidPurchase { value, max_purchase }.encode().into());
+        }
+        let amount = value.saturating_mul(self.rate.get());
+        // reserved tokens count against the cap as if already minted
+        let committed = self.erc20.total_supply_internal() + self.total_reserved.get();
+        self.cap.check_mint(committed, amount)?;
+
+        self.escrow.deposit(buyer, value)?;
+        self.reserved.insert(buyer, self.reserved.get(buyer) + amount);
+        self.total_reserved.set(self.total_reserved.get() + amount);
+        self.wei_raised.set(self.wei_raised.get() + value);
+
+        evm::log(TokensPurchased { buyer, value, amount });
+        Ok(amount)
+    }
+
+    /// Ends the sale after the closing time: pays the beneficiary if the goal was reached,
+    /// opens refunds otherwise.
+    pub fn finalize(&mut self) -> Result<(), OzError> {
+        let closing_time = self.closing_time.get();
+        if U256::from(block::timestamp()) < closing_time {
+            return Err(CrowdsaleNotOpen { closing_time }.encode().into());
+        }
+        self.require_finalized(false)?;
+        self.finalized.set(true);
+
+        let wei_raised = self.wei_raised.get();
+        let goal_reached = self.goal_reached();
+        if goal_reached {
+            self.escrow.close()?;
+            self.escrow.beneficiary_withdraw()?;
+        } else {
+            self.escrow.enable_refunds()?;
+        }
+
+        evm::log(CrowdsaleFinalized {
+            wei_raised,
+            goal_reached,
+        });
+        Ok(())
+    }
+
+    /// Mints the tokens reserved by the caller once the sale succeeded.
+    pub fn claim_tokens(&mut self) -> Result<U256, OzError> {
+        self.require_finalized(true)?;
+        if !self.goal_reached() {
+            return Err(CrowdsaleGoalNotReached {
+                wei_raised: self.wei_raised.get(),
+                goal: self.goal.get(),
+            }
+            .encode()
+            .into());
+        }
+        let buyer = msg::sender();
+        let amount = self.take_reservation(buyer)?;
+        self.cap.checked_mint(&mut self.erc20, buyer, amount)?;
+
+        evm::log(TokensClaimed { buyer, amount });
+        Ok(amount)
+    }
+
+    /// Refunds the ether of the caller once the sale failed, dropping its reservation.
+    pub fn claim_refund(&mut self) -> Result<U256, OzError> {
+        let buyer = msg::sender();
+        self.take_reservation(buyer)?;
+        Ok(self.escrow.withdraw(buyer)?)
+    }
+
+    /// Returns `(rate, goal, max_purchase, closing_time)`.
+    pub fn crowdsale_terms(&self) -> Result<(U256, U256, U256, U256), OzError> {
+        Ok((
+            self.rate.get(),
+            self.goal.get(),
+            self.max_purchase.get(),
+            self.closing_time.get(),
+        ))
+    }
+
+    /// Returns `(wei_raised, total_reserved, finalized)`.
+    pub fn crowdsale_progress(&self) -> Result<(U256, U256, bool), OzError> {
+        Ok((self.wei_raised.get(), self.total_reserved.get(), self.finalized.get()))
+    }
+
+    pub fn reserved_of(&self, buyer: Address) -> Result<U256, OzError> {
+        Ok(self.reserved.get(buyer))
+    }
+}
+
+impl CrowdsaleToken {
+    fn when_open(&self) -> Result<(), OzError> {
+        self.require_finalized(false)?;
+        let closing_time = self.closing_time.get();
+        if U256::from(block::timestamp()) >= closing_time {
+            return Err(CrowdsaleNotOpen { closing_time }.encode().into());
+        }
+        Ok(())
+    }
+
+    fn require_finalized(&self, finalized: bool) -> Result<(), OzError> {
+        if self.finalized.get() != finalized {
+            return Err(CrowdsaleFinalizedState {
+                finalized: self.finalized.get(),
+            }
+            .encode()
+            .into());
+        }
+        Ok(())
+    }
+
+    fn goal_reached(&self) -> bool {
+        self.wei_raised.get() >= self.goal.get()
+    }
+
+    /// Clears the reservation of `buyer` and returns it.
+    fn take_reservation(&mut self, buyer: Address) -> Result<U256, OzError> {
+        let amount = self.reserved.get(buyer);
+        if amount == U256::ZERO {
+            return Err(CrowdsaleNothingToClaim { buyer }.encode().into());
+        }
+        self.reserved.insert(buyer, U256::ZERO);
+        self.total_reserved.set(self.total_reserved.get() - amount);
+        Ok(amount)
+    }
+}
+
+#[cfg(feature = "export-abi")]
+fn main() {
+    print_abi("MIT OR Apache-2.0", "pragma solidity ^0.8.23;");
+}
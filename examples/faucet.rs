@@ -0,0 +1,138 @@
+//! Testnet faucet token: `drip()` mints `dripAmount` tokens to the caller at most once per
+//! `dripInterval` seconds, tracked with the timestamp of the last drip of each account. The
+//! owner sets the amount and the interval and pauses drips (transfers stay open), built from
+//! `Erc20`, `Ownable` and `Erc20Pausable`.
+//!
+//! `cargo build --example faucet --features export-abi` (prints the ABI when run).
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use alloc::vec::Vec;
+use oz_stylus_erc::access::ownable::Ownable;
+use oz_stylus_erc::extensions::erc20_pausable::Erc20Pausable;
+use oz_stylus_erc::tokens::erc20::{Erc20, Erc20Params, NoTransferHook};
+use oz_stylus_erc::utils::initializable::Initializable;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    alloy_sol_types::{sol, SolError},
+    block, evm, msg,
+    prelude::*,
+};
+
+/// Tokens minted by a drip until the owner changes it.
+const DEFAULT_DRIP_AMOUNT: u128 = 100_000_000_000_000_000_000;
+/// Seconds between two drips of an account until the owner changes it.
+const DEFAULT_DRIP_INTERVAL: u64 = 24 * 60 * 60;
+
+pub struct FaucetTokenParams;
+
+impl Erc20Params for FaucetTokenParams {
+    const NAME: &'static str = "Faucet token";
+    const SYMBOL: &'static str = "DRIP";
+    const DECIMALS: u8 = 18;
+    type Hook = NoTransferHook;
+}
+
+sol! {
+    event Dripped(address indexed account, uint256 value);
+    event DripConfigured(uint256 amount, uint256 interval);
+
+    /// Indicates a drip before `next_drip` (timestamp) for `account`.
+    error FaucetCooldown(address account, uint256 next_drip);
+}
+
+sol_storage! {
+    #[entrypoint]
+    pub struct FaucetToken {
+        #[borrow]
+        Erc20<FaucetTokenParams> erc20;
+        #[borrow]
+        Ownable ownable;
+        #[borrow]
+        Erc20Pausable pausable;
+        uint256 drip_amount;
+        uint256 drip_interval;
+        /// timestamp of the last drip of each account
+        mapping(address => uint256) last_drip;
+        #[borrow]
+        Initializable initializable;
+    }
+}
+
+#[external]
+#[inherit(Erc20<FaucetTokenParams>, Ownable, Erc20Pausable, Initializable)]
+impl FaucetToken {
+    pub fn init(&mut self) -> Result<(), Vec<u8>> {
+        self.initializable.initializer()?;
+        self.ownable.transfer_ownership_internal(msg::sender());
+        self.configure(U256::from(DEFAULT_DRIP_AMOUNT), U256::from(DEFAULT_DRIP_INTERVAL));
+        Ok(())
+    }
+
+    /// Mints `dripAmount` tokens to the caller, at most once per `dripInterval`.
+    pub fn drip(&mut self) -> Result<U256, Vec<u8>> {
+        self.pausable.when_not_paused()?;
+        let account = msg::sender();
+        let now = U256::from(block::timestamp());
+        let last = self.last_drip.get(account);
+        if last != U256::ZERO {
+            let next_drip = last.saturating_add(self.drip_interval.get());
+            if now < next_drip {
+                return Err(FaucetCooldown { account, next_drip }.encode());
+            }
+        }
+        self.last_drip.insert(account, now);
+
+        let value = self.drip_amount.get();
+        self.erc20.mint(account, value)?;
+        evm::log(Dripped { account, value });
+        Ok(value)
+    }
+
+    /// Timestamp from which `account` can drip again, 0 if it can drip now.
+    pub fn next_drip(&self, account: Address) -> Result<U256, Vec<u8>> {
+        let last = self.last_drip.get(account);
+        let next_drip = last.saturating_add(self.drip_interval.get());
+        if last == U256::ZERO || U256::from(block::timestamp()) >= next_drip {
+            return Ok(U256::ZERO);
+        }
+        Ok(next_drip)
+    }
+
+    pub fn drip_amount(&self) -> Result<U256, Vec<u8>> {
+        Ok(self.drip_amount.get())
+    }
+
+    pub fn drip_interval(&self) -> Result<U256, Vec<u8>> {
+        Ok(self.drip_interval.get())
+    }
+
+    pub fn set_drip(&mut self, amount: U256, interval: U256) -> Result<(), Vec<u8>> {
+        self.ownable.only_owner()?;
+        self.configure(amount, interval);
+        Ok(())
+    }
+
+    pub fn pause(&mut self) -> Result<(), Vec<u8>> {
+        self.ownable.only_owner()?;
+        Ok(self.pausable.pause()?)
+    }
+
+    pub fn unpause(&mut self) -> Result<(), Vec<u8>> {
+        self.ownable.only_owner()?;
+        Ok(self.pausable.unpause()?)
+    }
+}
+
+impl FaucetToken {
+    fn configure(&mut self, amount: U256, interval: U256) {
+        self.drip_amount.set(amount);
+        self.drip_interval.set(interval);
+        evm::log(DripConfigured { amount, interval });
+    }
+}
+
+#[cfg(feature = "export-abi")]
+fn main() {
+    print_abi("MIT OR Apache-2.0", "pragma solidity ^0.8.23;");
+}
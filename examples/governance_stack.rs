@@ -0,0 +1,308 @@
+//! End to end governance walkthrough on a devnet: a proposal minting governance tokens goes
+//! through propose, vote, queue and execute. Every step is checked, so the script also works as
+//! a system test of the stack.
+//!
+//! Programs are deployed beforehand with `cargo stylus deploy` and configured like the
+//! integration tests:
+//! * `STYLUS_PROGRAM_ADDRESS_VOTES` - governance token (`examples/compose_votes.rs`), owned by alice,
+//! * `STYLUS_PROGRAM_ADDRESS_TIMELOCK` - timelock (`examples/compose_timelock.rs`), with the
+//!   governor as proposer and executor,
+//! * `STYLUS_PROGRAM_ADDRESS_GOVERNOR` - governor counting votes of the token
+//!   (`examples/compose_governor.rs`).
+//!
+//! The governor and timelock programs follow the OpenZeppelin `Governor` / `TimelockController`
+//! ABI used below.
+//!
+//! `cargo run --example governance_stack`
+use ethers::{
+    abi::AbiEncode,
+    middleware::SignerMiddleware,
+    prelude::abigen,
+    providers::{Http, Middleware, Provider},
+    signers::{LocalWallet, Signer},
+    types::{Address, Bytes, TransactionReceipt, TransactionRequest, U256},
+    utils::keccak256,
+};
+use eyre::ensure;
+use std::sync::Arc;
+use std::time::Duration;
+
+// same environment handling as the integration tests
+#[path = "../tests/util/config.rs"]
+#[allow(dead_code)]
+mod config;
+use config::TestConfig;
+
+abigen!(
+    GovernanceToken,
+    r#"[
+        function balanceOf(address account) external view returns (uint256)
+        function mint(address account, uint256 value) external
+        function delegate(address delegatee) external
+        function getVotes(address account) external view returns (uint256)
+        function owner() external view returns (address)
+        function transferOwnership(address new_owner) external
+    ]"#
+);
+
+abigen!(
+    Timelock,
+    r#"[
+        function getMinDelay() external view returns (uint256)
+    ]"#
+);
+
+abigen!(
+    Governor,
+    r#"[
+        function propose(address[] targets, uint256[] values, bytes[] calldatas, string description) external returns (uint256)
+        function hashProposal(address[] targets, uint256[] values, bytes[] calldatas, bytes32 description_hash) external view returns (uint256)
+        function castVote(uint256 proposal_id, uint8 support) external returns (uint256)
+        function queue(address[] targets, uint256[] values, bytes[] calldatas, bytes32 description_hash) external returns (uint256)
+        function execute(address[] targets, uint256[] values, bytes[] calldatas, bytes32 description_hash) external payable returns (uint256)
+        function state(uint256 proposal_id) external view returns (uint8)
+        function timelock() external view returns (address)
+    ]"#
+);
+
+type SignerClient = Arc<SignerMiddleware<Provider<Http>, LocalWallet>>;
+
+/// `ProposalState` values of the governor.
+const STATE_PENDING: u8 = 0;
+const STATE_ACTIVE: u8 = 1;
+const STATE_SUCCEEDED: u8 = 4;
+const STATE_QUEUED: u8 = 5;
+const STATE_EXECUTED: u8 = 7;
+
+/// `support` value of a vote in favor.
+const VOTE_FOR: u8 = 1;
+
+/// Transactions sent at most while waiting for a proposal state.
+const MAX_WAIT_BLOCKS: usize = 200;
+
+struct Fixtures {
+    alice_wallet: LocalWallet,
+    bob_wallet: LocalWallet,
+    alice_client: SignerClient,
+    bob_client: SignerClient,
+    token_address: Address,
+    timelock_address: Address,
+    governor_address: Address,
+}
+
+/// Proposal submitted to the governor, kept together as `queue` and `execute` take it again.
+struct Proposal {
+    targets: Vec<Address>,
+    values: Vec<U256>,
+    calldatas: Vec<Bytes>,
+    description: String,
+}
+
+impl Proposal {
+    fn description_hash(&self) -> [u8; 32] {
+        keccak256(self.description.as_bytes())
+    }
+}
+
+#[tokio::main]
+async fn main() -> eyre::Result<()> {
+    let fixtures = init_fixtures().await?;
+    let alice = fixtures.alice_wallet.address();
+    let bob = fixtures.bob_wallet.address();
+
+    let token_alice = GovernanceToken::new(fixtures.token_address, fixtures.alice_client.clone());
+    let token_bob = GovernanceToken::new(fixtures.token_address, fixtures.bob_client.clone());
+    let governor_alice = Governor::new(fixtures.governor_address, fixtures.alice_client.clone());
+    let governor_bob = Governor::new(fixtures.governor_address, fixtures.bob_client.clone());
+    let timelock = Timelock::new(fixtures.timelock_address, fixtures.alice_client.clone());
+
+    let governor_timelock = governor_alice.timelock().call().await?;
+    ensure!(
+        governor_timelock == fixtures.timelock_address,
+        "governor executes through {:?}, not the configured timelock {:?}",
+        governor_timelock,
+        fixtures.timelock_address
+    );
+
+    println!("--- Distribute and delegate");
+    // minting is only possible while alice still owns the token
+    if token_alice.owner().call().await? == alice {
+        send(token_alice.mint(alice, 1000.into()), "mint alice").await?;
+        send(token_alice.mint(bob, 1000.into()), "mint bob").await?;
+        send(token_alice.transfer_ownership(fixtures.timelock_address), "hand token to timelock").await?;
+    }
+    let owner = token_alice.owner().call().await?;
+    ensure!(owner == fixtures.timelock_address, "token is owned by {:?}, not the timelock", owner);
+
+    send(token_alice.delegate(alice), "alice self-delegates").await?;
+    send(token_bob.delegate(bob), "bob self-delegates").await?;
+    println!("alice votes: {}", token_alice.get_votes(alice).call().await?);
+    println!("bob votes: {}", token_alice.get_votes(bob).call().await?);
+
+    println!("--- Propose");
+    let mint_amount = U256::from(500);
+    let mint_call = MintCall { account: bob, value: mint_amount };
+    let proposal = Proposal {
+        targets: vec![fixtures.token_address],
+        values: vec![U256::zero()],
+        calldatas: vec![mint_call.encode().into()],
+        // unique per run, a description can only be proposed once
+        description: format!("Mint {} GOV to bob #{}", mint_amount, timestamp()),
+    };
+    let proposal_id = governor_alice
+        .hash_proposal(
+            proposal.targets.clone(),
+            proposal.values.clone(),
+            proposal.calldatas.clone(),
+            proposal.description_hash(),
+        )
+        .call()
+        .await?;
+    send(
+        governor_alice.propose(
+            proposal.targets.clone(),
+            proposal.values.clone(),
+            proposal.calldatas.clone(),
+            proposal.description.clone(),
+        ),
+        "propose",
+    )
+    .await?;
+    println!("proposal id: {}", proposal_id);
+    expect_state(&governor_alice, proposal_id, STATE_PENDING).await?;
+
+    println!("--- Vote");
+    wait_for_state(&fixtures.alice_client, &governor_alice, proposal_id, STATE_ACTIVE).await?;
+    send(governor_alice.cast_vote(proposal_id, VOTE_FOR), "alice votes for").await?;
+    send(governor_bob.cast_vote(proposal_id, VOTE_FOR), "bob votes for").await?;
+    wait_for_state(&fixtures.alice_client, &governor_alice, proposal_id, STATE_SUCCEEDED).await?;
+
+    println!("--- Queue");
+    send(
+        governor_alice.queue(
+            proposal.targets.clone(),
+            proposal.values.clone(),
+            proposal.calldatas.clone(),
+            proposal.description_hash(),
+        ),
+        "queue",
+    )
+    .await?;
+    expect_state(&governor_alice, proposal_id, STATE_QUEUED).await?;
+
+    let min_delay = timelock.get_min_delay().call().await?;
+    println!("waiting timelock delay: {}s", min_delay);
+    tokio::time::sleep(Duration::from_secs(min_delay.as_u64() + 1)).await;
+    // the timelock reads the block timestamp, make sure a block is produced after the delay
+    advance_block(&fixtures.alice_client).await?;
+
+    println!("--- Execute");
+    let bob_balance_before = token_alice.balance_of(bob).call().await?;
+    send(
+        governor_alice.execute(
+            proposal.targets.clone(),
+            proposal.values.clone(),
+            proposal.calldatas.clone(),
+            proposal.description_hash(),
+        ),
+        "execute",
+    )
+    .await?;
+    expect_state(&governor_alice, proposal_id, STATE_EXECUTED).await?;
+
+    let bob_balance_after = token_alice.balance_of(bob).call().await?;
+    println!("bob balance before: {}, after: {}", bob_balance_before, bob_balance_after);
+    ensure!(
+        bob_balance_after == bob_balance_before + mint_amount,
+        "proposal executed but bob balance moved from {} to {}",
+        bob_balance_before,
+        bob_balance_after
+    );
+
+    println!("governance stack OK");
+    Ok(())
+}
+
+/// Sends `call` and waits for its receipt, failing on a reverted transaction.
+async fn send<D: ethers::abi::Detokenize>(
+    call: ethers::contract::ContractCall<SignerMiddleware<Provider<Http>, LocalWallet>, D>,
+    label: &str,
+) -> eyre::Result<TransactionReceipt> {
+    let receipt = call
+        .send()
+        .await?
+        .await?
+        .ok_or_else(|| eyre::eyre!("{} tx returned no receipt", label))?;
+    ensure!(receipt.status == Some(1.into()), "{} tx {:?} reverted", label, receipt.transaction_hash);
+    println!("{} tx: {:?}", label, receipt.transaction_hash);
+    Ok(receipt)
+}
+
+async fn expect_state(
+    governor: &Governor<SignerMiddleware<Provider<Http>, LocalWallet>>,
+    proposal_id: U256,
+    expected: u8,
+) -> eyre::Result<()> {
+    let state = governor.state(proposal_id).call().await?;
+    ensure!(state == expected, "proposal in state {}, expected {}", state, expected);
+    Ok(())
+}
+
+/// Produces blocks until the proposal reaches `expected`, as voting delay and period are counted
+/// in blocks and a devnet only produces blocks on transactions.
+async fn wait_for_state(
+    client: &SignerClient,
+    governor: &Governor<SignerMiddleware<Provider<Http>, LocalWallet>>,
+    proposal_id: U256,
+    expected: u8,
+) -> eyre::Result<()> {
+    for _ in 0..MAX_WAIT_BLOCKS {
+        if governor.state(proposal_id).call().await? == expected {
+            return Ok(());
+        }
+        advance_block(client).await?;
+    }
+    expect_state(governor, proposal_id, expected).await
+}
+
+/// Empty self transfer, to produce a block.
+async fn advance_block(client: &SignerClient) -> eyre::Result<()> {
+    let tx = TransactionRequest::new().to(client.address()).value(0);
+    client.send_transaction(tx, None).await?.await?;
+    Ok(())
+}
+
+fn timestamp() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+async fn init_fixtures() -> eyre::Result<Fixtures> {
+    let config = TestConfig::load()?;
+    let provider = config.provider()?;
+    let chain_id = provider.get_chainid().await?.as_u64();
+
+    let alice_wallet = config.alice_wallet()?;
+    let alice_client = Arc::new(SignerMiddleware::new(
+        provider.clone(),
+        alice_wallet.clone().with_chain_id(chain_id),
+    ));
+
+    let bob_wallet = config.bob_wallet()?;
+    let bob_client = Arc::new(SignerMiddleware::new(
+        provider.clone(),
+        bob_wallet.clone().with_chain_id(chain_id),
+    ));
+
+    Ok(Fixtures {
+        alice_wallet,
+        bob_wallet,
+        alice_client,
+        bob_client,
+        token_address: config.program("votes")?,
+        timelock_address: config.program("timelock")?,
+        governor_address: config.program("governor")?,
+    })
+}
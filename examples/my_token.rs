@@ -1,4 +1,3 @@
-use dotenv::dotenv;
 use ethers::{
     middleware::SignerMiddleware,
     prelude::abigen,
@@ -6,22 +5,14 @@ use ethers::{
     signers::{LocalWallet, Signer},
     types::{Address, TransactionReceipt, U256},
 };
-use eyre::eyre;
 use std::io::{BufRead, BufReader};
-use std::str::FromStr;
 use std::sync::Arc;
 
-/// deployer private key file path.
-const ALICE_PRIV_KEY_PATH: &str = "ALICE_PRIV_KEY_PATH";
-
-/// deployer private key file path.
-const BOB_PRIV_KEY_PATH: &str = "BOB_PRIV_KEY_PATH";
-
-/// Stylus RPC endpoint url.
-const RPC_URL: &str = "RPC_URL";
-
-/// Deployed program address.
-const MY_TOKEN_PROGRAM_ADDRESS: &str = "STYLUS_PROGRAM_ADDRESS";
+// same environment handling as the integration tests
+#[path = "../tests/util/config.rs"]
+#[allow(dead_code)]
+mod config;
+use config::TestConfig;
 
 abigen!(
     MyToken,
@@ -192,29 +183,18 @@ async fn transfer_from(
 }
 
 async fn init_fixtures() -> eyre::Result<Fixtures>  {
-    dotenv().ok();
-
-    let program_address = std::env::var(MY_TOKEN_PROGRAM_ADDRESS)
-        .map_err(|_| eyre!("No {} env var set", MY_TOKEN_PROGRAM_ADDRESS))?;
-    let alice_key_path = std::env::var(ALICE_PRIV_KEY_PATH)
-        .map_err(|_| eyre!("No {} env var set", ALICE_PRIV_KEY_PATH))?;
-    let rpc_url = std::env::var(RPC_URL).map_err(|_| eyre!("No {} env var set", RPC_URL))?;
-    let bob_key_path = std::env::var(BOB_PRIV_KEY_PATH)
-        .map_err(|_| eyre!("No {} env var set", BOB_PRIV_KEY_PATH))?;
+    let config = TestConfig::load()?;
+    let provider = config.provider()?;
+    let my_token_address = config.my_token()?;
 
-    let provider = Provider::<Http>::try_from(rpc_url)?;
-    let my_token_address: Address = program_address.parse()?;
-
-    let alice_private_key = read_secret_from_file(&alice_key_path)?;
-    let alice_wallet = LocalWallet::from_str(&alice_private_key)?;
+    let alice_wallet = config.alice_wallet()?;
     let chain_id = provider.get_chainid().await?.as_u64();
     let alice_client = Arc::new(SignerMiddleware::new(
         provider.clone(),
         alice_wallet.clone().with_chain_id(chain_id),
     ));
 
-    let bob_private_key = read_secret_from_file(&bob_key_path)?;
-    let bob_wallet = LocalWallet::from_str(&bob_private_key)?;
+    let bob_wallet = config.bob_wallet()?;
     let bob_client = Arc::new(SignerMiddleware::new(
         provider.clone(),
         bob_wallet.clone().with_chain_id(chain_id),
@@ -228,7 +208,3 @@ async fn init_fixtures() -> eyre::Result<Fixtures>  {
         erc20_token_address: my_token_address,
     })
 }
-
-fn read_secret_from_file(fpath: &str) -> eyre::Result<String> {
-    Ok(std::fs::read_to_string(fpath)?)
-}
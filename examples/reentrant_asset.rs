@@ -0,0 +1,89 @@
+//! Test asset of the read-only reentrancy test in `tests/erc4626.rs`: a plain ERC20 which, once
+//! armed with `setCallback(true)`, reads `convertToAssets(1)` of the vault moving it during
+//! `transfer`/`transferFrom`, as an integrator's oracle would, and reverts with the vault's
+//! error when that view reverts. Anyone mints, devnets only.
+//!
+//! `cargo build --example reentrant_asset --features export-abi` (prints the ABI when run).
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use alloc::vec::Vec;
+use oz_stylus_erc::tokens::erc20::{Erc20, Erc20Params, NoTransferHook};
+use oz_stylus_erc::utils::eip712::u256_word;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    call::{self, Call},
+    msg,
+    prelude::*,
+};
+
+/// `bytes4(keccak256("convertToAssets(uint256)"))`
+const CONVERT_TO_ASSETS_SELECTOR: [u8; 4] = [0x07, 0xa2, 0xd1, 0x3a];
+
+pub struct ReentrantAssetParams;
+
+impl Erc20Params for ReentrantAssetParams {
+    const NAME: &'static str = "Reentrant asset";
+    const SYMBOL: &'static str = "REA";
+    const DECIMALS: u8 = 18;
+    type Hook = NoTransferHook;
+}
+
+sol_storage! {
+    #[entrypoint]
+    pub struct ReentrantAsset {
+        #[borrow]
+        Erc20<ReentrantAssetParams> erc20;
+        /// whether transfers call back into their caller
+        bool callback;
+    }
+}
+
+#[external]
+#[inherit(Erc20<ReentrantAssetParams>)]
+impl ReentrantAsset {
+    pub fn mint(&mut self, account: Address, value: U256) -> Result<(), Vec<u8>> {
+        self.erc20.mint(account, value)?;
+        Ok(())
+    }
+
+    #[selector(name = "setCallback")]
+    pub fn set_callback(&mut self, enabled: bool) -> Result<(), Vec<u8>> {
+        self.callback.set(enabled);
+        Ok(())
+    }
+
+    pub fn transfer(&mut self, to: Address, value: U256) -> Result<bool, Vec<u8>> {
+        self.erc20.transfer_internal(msg::sender(), to, value)?;
+        self.call_back()?;
+        Ok(true)
+    }
+
+    pub fn transfer_from(&mut self, from: Address, to: Address, value: U256) -> Result<bool, Vec<u8>> {
+        self.erc20.spend_allowance(from, msg::sender(), value)?;
+        self.erc20.transfer_internal(from, to, value)?;
+        self.call_back()?;
+        Ok(true)
+    }
+}
+
+impl ReentrantAsset {
+    /// Reads the price of one share on the caller once the tokens moved, while the vault has not
+    /// minted or paid the shares yet.
+    fn call_back(&mut self) -> Result<(), Vec<u8>> {
+        if !self.callback.get() {
+            return Ok(());
+        }
+        let calldata = [CONVERT_TO_ASSETS_SELECTOR.as_slice(), u256_word(U256::from(1)).as_slice()].concat();
+        call::static_call(Call::new(), msg::sender(), &calldata).map_err(|e| match e {
+            call::Error::Revert(reason) => reason,
+            _ => Vec::new(),
+        })?;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "export-abi")]
+fn main() {
+    print_abi("MIT OR Apache-2.0", "pragma solidity ^0.8.23;");
+}
@@ -0,0 +1,139 @@
+//! One program, many tokens: `TokenFactory` launches ERC20s as EIP-1167 minimal proxies of a
+//! single deployed `examples/compose_metadata_storage.rs` program. Each token costs a 45 bytes
+//! proxy instead of a WASM deployment and activation, and keeps its own storage (balances,
+//! name, symbol) while running the shared code.
+//!
+//! `createToken` deploys the clone at an address derived from the caller and a salt, then
+//! initializes it in the same transaction so nobody can initialize it first. The caller gets
+//! the whole initial supply.
+//!
+//! `cargo build --example token_factory --features export-abi` (prints the ABI when run).
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use alloc::{string::String, vec::Vec};
+use oz_stylus_erc::utils::eip712::{address_word, u256_word};
+use oz_stylus_erc::utils::initializable::Initializable;
+use oz_stylus_erc::utils::{clones, create2};
+use stylus_sdk::{
+    alloy_primitives::{Address, B256, U256},
+    alloy_sol_types::sol,
+    call::{self, Call},
+    contract, evm, msg,
+    prelude::*,
+};
+
+/// `bytes4(keccak256("init(string,string,uint8,address,uint256)"))` of the token program.
+const TOKEN_INIT_SELECTOR: [u8; 4] = [0x8d, 0xf2, 0x8e, 0x55];
+
+sol! {
+    event TokenCreated(address indexed creator, address indexed token, string name, string symbol);
+}
+
+sol_storage! {
+    #[entrypoint]
+    pub struct TokenFactory {
+        /// deployed `compose_metadata_storage` program every token delegates to
+        address implementation;
+        /// tokens created, in order
+        address[] tokens;
+        #[borrow]
+        Initializable initializable;
+    }
+}
+
+#[external]
+#[inherit(Initializable)]
+impl TokenFactory {
+    pub fn init(&mut self, implementation: Address) -> Result<(), Vec<u8>> {
+        self.initializable.initializer()?;
+        if implementation == Address::ZERO {
+            return Err("zero implementation".into());
+        }
+        self.implementation.set(implementation);
+        Ok(())
+    }
+
+    /// Deploys and initializes a token, mints `supply` to the caller and returns the token.
+    #[selector(name = "createToken")]
+    pub fn create_token(
+        &mut self,
+        name: String,
+        symbol: String,
+        decimals: u8,
+        supply: U256,
+        salt: B256,
+    ) -> Result<Address, Vec<u8>> {
+        let creator = msg::sender();
+        let token = clones::clone_deterministic(self.implementation.get(), create2::account_salt(creator, salt))?;
+        self.tokens.push(token);
+
+        let calldata = token_init_calldata(&name, &symbol, decimals, creator, supply);
+        call::call(Call::new(), token, &calldata).map_err(|e| match e {
+            call::Error::Revert(reason) => reason,
+            _ => Vec::new(),
+        })?;
+
+        evm::log(TokenCreated {
+            creator,
+            token,
+            name,
+            symbol,
+        });
+        Ok(token)
+    }
+
+    /// Address `createToken` returns for `creator` and `salt`.
+    #[selector(name = "predictToken")]
+    pub fn predict_token(&self, creator: Address, salt: B256) -> Result<Address, Vec<u8>> {
+        Ok(clones::predict_deterministic_address(
+            self.implementation.get(),
+            create2::account_salt(creator, salt),
+            contract::address(),
+        ))
+    }
+
+    #[selector(name = "implementation")]
+    pub fn implementation(&self) -> Result<Address, Vec<u8>> {
+        Ok(self.implementation.get())
+    }
+
+    #[selector(name = "tokenCount")]
+    pub fn token_count(&self) -> Result<U256, Vec<u8>> {
+        Ok(U256::from(self.tokens.len()))
+    }
+
+    #[selector(name = "tokenAt")]
+    pub fn token_at(&self, index: U256) -> Result<Address, Vec<u8>> {
+        self.tokens.get(index).ok_or_else(|| "index out of bounds".into())
+    }
+}
+
+/// Calldata of `init(name, symbol, decimals, holder, supply)`.
+fn token_init_calldata(name: &str, symbol: &str, decimals: u8, holder: Address, supply: U256) -> Vec<u8> {
+    // strings follow the 5 head words, each as a length word and the padded bytes
+    let name_offset = 5 * 32;
+    let symbol_offset = name_offset + 32 + padded_len(name.len());
+
+    let mut calldata = TOKEN_INIT_SELECTOR.to_vec();
+    calldata.extend_from_slice(u256_word(U256::from(name_offset)).as_slice());
+    calldata.extend_from_slice(u256_word(U256::from(symbol_offset)).as_slice());
+    calldata.extend_from_slice(u256_word(U256::from(decimals)).as_slice());
+    calldata.extend_from_slice(address_word(holder).as_slice());
+    calldata.extend_from_slice(u256_word(supply).as_slice());
+    for value in [name, symbol] {
+        calldata.extend_from_slice(u256_word(U256::from(value.len())).as_slice());
+        calldata.extend_from_slice(value.as_bytes());
+        calldata.resize(calldata.len() + padded_len(value.len()) - value.len(), 0);
+    }
+    calldata
+}
+
+fn padded_len(len: usize) -> usize {
+    len.div_ceil(32) * 32
+}
+
+#[cfg(feature = "export-abi")]
+fn main() {
+    print_abi("MIT OR Apache-2.0", "pragma solidity ^0.8.23;");
+}
@@ -0,0 +1,80 @@
+//! Version 1 of an upgradeable token, deployed behind an EIP-1967 proxy (e.g. OpenZeppelin
+//! `ERC1967Proxy` constructed with this program and `initialize(owner)` calldata). The owner
+//! mints and upgrades the proxy with `upgradeToAndCall`, see `examples/upgradeable_token_v2.rs`
+//! for the next version. After deploying the program itself call `disableInitializers()` on it,
+//! so its own storage can not be initialized by anyone.
+//!
+//! `cargo build --example upgradeable_token --features export-abi` (prints the ABI when run).
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use alloc::vec::Vec;
+use oz_stylus_erc::access::ownable::Ownable;
+use oz_stylus_erc::proxy::uups::UupsUpgradeable;
+use oz_stylus_erc::tokens::erc20::{Erc20, Erc20Params, NoTransferHook};
+use oz_stylus_erc::utils::initializable::Initializable;
+use stylus_sdk::{
+    abi::Bytes,
+    alloy_primitives::{Address, U256},
+    prelude::*,
+};
+
+pub struct UpgradeableTokenParams;
+
+impl Erc20Params for UpgradeableTokenParams {
+    const NAME: &'static str = "Upgradeable token";
+    const SYMBOL: &'static str = "UPG";
+    const DECIMALS: u8 = 18;
+    type Hook = NoTransferHook;
+}
+
+sol_storage! {
+    /// The layout of every later version starts with these fields, in this order.
+    #[entrypoint]
+    pub struct UpgradeableToken {
+        #[borrow]
+        Erc20<UpgradeableTokenParams> erc20;
+        #[borrow]
+        Ownable ownable;
+        #[borrow]
+        Initializable initializable;
+        #[borrow]
+        UupsUpgradeable upgrades;
+    }
+}
+
+#[external]
+#[inherit(Erc20<UpgradeableTokenParams>, Ownable, Initializable, UupsUpgradeable)]
+impl UpgradeableToken {
+    pub fn initialize(&mut self, owner: Address) -> Result<(), Vec<u8>> {
+        self.initializable.initializer()?;
+        self.ownable.transfer_ownership_internal(owner);
+        Ok(())
+    }
+
+    /// Locks the program's own storage, callable on the program only, not through the proxy.
+    #[selector(name = "disableInitializers")]
+    pub fn disable_initializers(&mut self) -> Result<(), Vec<u8>> {
+        self.upgrades.not_delegated()?;
+        self.initializable.disable_initializers();
+        Ok(())
+    }
+
+    #[selector(name = "upgradeToAndCall")]
+    pub fn upgrade_to_and_call(&mut self, new_implementation: Address, data: Bytes) -> Result<(), Vec<u8>> {
+        self.ownable.only_owner()?;
+        self.upgrades.upgrade_to_and_call(new_implementation, &data)?;
+        Ok(())
+    }
+
+    pub fn mint(&mut self, account: Address, value: U256) -> Result<(), Vec<u8>> {
+        self.ownable.only_owner()?;
+        self.erc20.mint(account, value)?;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "export-abi")]
+fn main() {
+    print_abi("MIT OR Apache-2.0", "pragma solidity ^0.8.23;");
+}
@@ -0,0 +1,117 @@
+//! Version 2 of `examples/upgradeable_token.rs`: balances, ownership and the initialized
+//! version are kept in the proxy's storage, the new `Pausable` is appended after the fields of
+//! version 1. Upgrade with `upgradeToAndCall(v2, initializeV2())` from the owner: the migration
+//! runs once, guarded by `reinitializer(2)`.
+//!
+//! `cargo build --example upgradeable_token_v2 --features export-abi` (prints the ABI when run).
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use alloc::vec::Vec;
+use oz_stylus_erc::access::ownable::Ownable;
+use oz_stylus_erc::proxy::uups::UupsUpgradeable;
+use oz_stylus_erc::tokens::erc20::{Erc20, Erc20Params, NoTransferHook};
+use oz_stylus_erc::utils::initializable::Initializable;
+use oz_stylus_erc::utils::pausable::Pausable;
+use stylus_sdk::{
+    abi::Bytes,
+    alloy_primitives::{Address, U256},
+    msg,
+    prelude::*,
+};
+
+pub struct UpgradeableTokenParams;
+
+impl Erc20Params for UpgradeableTokenParams {
+    const NAME: &'static str = "Upgradeable token";
+    const SYMBOL: &'static str = "UPG";
+    const DECIMALS: u8 = 18;
+    type Hook = NoTransferHook;
+}
+
+sol_storage! {
+    #[entrypoint]
+    pub struct UpgradeableTokenV2 {
+        // version 1 layout, unchanged
+        #[borrow]
+        Erc20<UpgradeableTokenParams> erc20;
+        #[borrow]
+        Ownable ownable;
+        #[borrow]
+        Initializable initializable;
+        #[borrow]
+        UupsUpgradeable upgrades;
+        // added by version 2
+        #[borrow]
+        Pausable pausable;
+    }
+}
+
+#[external]
+#[inherit(Erc20<UpgradeableTokenParams>, Ownable, Initializable, UupsUpgradeable, Pausable)]
+impl UpgradeableTokenV2 {
+    /// Kept for fresh deployments of version 2, initializes both versions at once.
+    pub fn initialize(&mut self, owner: Address) -> Result<(), Vec<u8>> {
+        self.initializable.reinitializer(2)?;
+        self.ownable.transfer_ownership_internal(owner);
+        Ok(())
+    }
+
+    /// Migration from version 1, run by `upgradeToAndCall` (the owner is the original caller).
+    #[selector(name = "initializeV2")]
+    pub fn initialize_v2(&mut self) -> Result<(), Vec<u8>> {
+        self.initializable.reinitializer(2)?;
+        Ok(())
+    }
+
+    #[selector(name = "disableInitializers")]
+    pub fn disable_initializers(&mut self) -> Result<(), Vec<u8>> {
+        self.upgrades.not_delegated()?;
+        self.initializable.disable_initializers();
+        Ok(())
+    }
+
+    #[selector(name = "upgradeToAndCall")]
+    pub fn upgrade_to_and_call(&mut self, new_implementation: Address, data: Bytes) -> Result<(), Vec<u8>> {
+        self.ownable.only_owner()?;
+        self.upgrades.upgrade_to_and_call(new_implementation, &data)?;
+        Ok(())
+    }
+
+    pub fn pause(&mut self) -> Result<(), Vec<u8>> {
+        self.ownable.only_owner()?;
+        self.pausable.pause()?;
+        Ok(())
+    }
+
+    pub fn unpause(&mut self) -> Result<(), Vec<u8>> {
+        self.ownable.only_owner()?;
+        self.pausable.unpause()?;
+        Ok(())
+    }
+
+    pub fn mint(&mut self, account: Address, value: U256) -> Result<(), Vec<u8>> {
+        self.ownable.only_owner()?;
+        self.pausable.when_not_paused()?;
+        self.erc20.mint(account, value)?;
+        Ok(())
+    }
+
+    pub fn transfer(&mut self, to: Address, value: U256) -> Result<bool, Vec<u8>> {
+        self.pausable.when_not_paused()?;
+        self.erc20.transfer_internal(msg::sender(), to, value)?;
+        Ok(true)
+    }
+
+    pub fn transfer_from(&mut self, from: Address, to: Address, value: U256) -> Result<bool, Vec<u8>> {
+        self.pausable.when_not_paused()?;
+        self.erc20.spend_allowance(from, msg::sender(), value)?;
+        self.erc20.transfer_internal(from, to, value)?;
+        Ok(true)
+    }
+}
+
+#[cfg(feature = "export-abi")]
+fn main() {
+    print_abi("MIT OR Apache-2.0", "pragma solidity ^0.8.23;");
+}
@@ -0,0 +1,76 @@
+//! Wrapped ETH on `utils::payable`: ETH sent without calldata, or to the payable `deposit`, mints
+//! the same amount of tokens to the sender, `withdraw` burns them and sends the ETH back.
+//! `payable_entrypoint!` replaces `#[entrypoint]` so plain transfers reach `Receive::receive`.
+//!
+//! `cargo build --example weth --features export-abi` (prints the ABI when run).
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use alloc::vec::Vec;
+use oz_stylus_erc::tokens::erc20::{Erc20, Erc20Params, NoTransferHook};
+use oz_stylus_erc::utils::payable::Receive;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    alloy_sol_types::sol,
+    call, evm, msg,
+    prelude::*,
+};
+
+pub struct WethParams;
+
+impl Erc20Params for WethParams {
+    const NAME: &'static str = "Wrapped Ether";
+    const SYMBOL: &'static str = "WETH";
+    const DECIMALS: u8 = 18;
+    type Hook = NoTransferHook;
+}
+
+sol_storage! {
+    pub struct Weth {
+        #[borrow]
+        Erc20<WethParams> erc20;
+    }
+}
+
+oz_stylus_erc::payable_entrypoint!(Weth);
+
+sol! {
+    event Deposit(address indexed dst, uint256 wad);
+    event Withdrawal(address indexed src, uint256 wad);
+}
+
+impl Weth {
+    fn deposit_from(&mut self, account: Address, value: U256) -> Result<(), Vec<u8>> {
+        self.erc20.mint(account, value)?;
+        evm::log(Deposit { dst: account, wad: value });
+        Ok(())
+    }
+}
+
+impl Receive for Weth {
+    fn receive(&mut self) -> Result<(), Vec<u8>> {
+        self.deposit_from(msg::sender(), msg::value())
+    }
+}
+
+#[external]
+#[inherit(Erc20<WethParams>)]
+impl Weth {
+    #[payable]
+    pub fn deposit(&mut self) -> Result<(), Vec<u8>> {
+        self.deposit_from(msg::sender(), msg::value())
+    }
+
+    pub fn withdraw(&mut self, wad: U256) -> Result<(), Vec<u8>> {
+        let account = msg::sender();
+        // burnt before the transfer, a reentrant withdraw finds the balance already debited
+        self.erc20.burn(account, wad)?;
+        evm::log(Withdrawal { src: account, wad });
+        call::transfer_eth(account, wad)
+    }
+}
+
+#[cfg(feature = "export-abi")]
+fn main() {
+    print_abi("MIT OR Apache-2.0", "pragma solidity ^0.8.23;");
+}
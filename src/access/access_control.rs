@@ -0,0 +1,193 @@
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{Address, B256},
+    alloy_sol_types::{sol, SolError},
+    crypto::keccak,
+    evm, msg,
+    prelude::*,
+};
+
+/// Admin of every role by default, accounts holding it can grant and revoke any role whose admin
+/// was not changed. Grant it with care.
+pub const DEFAULT_ADMIN_ROLE: B256 = B256::ZERO;
+
+/// Identifier of a role, `keccak256(name)` as in Solidity (e.g. `role_id("MINTER_ROLE")`).
+pub fn role_id(name: &str) -> B256 {
+    keccak(name.as_bytes())
+}
+
+sol_storage! {
+    /// Role based access control, port of OpenZeppelin's `AccessControl`. Roles are `bytes32`
+    /// identifiers (see `role_id`), each role has an admin role whose members grant and revoke it.
+    ///
+    /// Compose with `#[borrow] AccessControl access;`, inherit it to expose the role management
+    /// externals and call `only_role(role)` at the start of restricted methods. Stylus programs
+    /// have no constructor, the composing token grants `DEFAULT_ADMIN_ROLE` in its `init` with
+    /// `grant_role_internal`. See `examples/compose_access_control.rs` for a complete composition.
+    pub struct AccessControl {
+        mapping(bytes32 => RoleData) roles;
+    }
+
+    pub struct RoleData {
+        mapping(address => bool) members;
+        bytes32 admin_role;
+    }
+}
+
+sol! {
+    event RoleAdminChanged(bytes32 indexed role, bytes32 indexed previous_admin_role, bytes32 indexed new_admin_role);
+    event RoleGranted(bytes32 indexed role, address indexed account, address indexed sender);
+    event RoleRevoked(bytes32 indexed role, address indexed account, address indexed sender);
+
+    /// The `account` is missing a role.
+    /// * `account` - caller of the restricted operation.
+    /// * `needed_role` - role required by the operation.
+    error AccessControlUnauthorizedAccount(address account, bytes32 needed_role);
+
+    /// The caller of `renounceRole` is not the account renouncing, renouncing is only possible
+    /// for the caller's own roles.
+    error AccessControlBadConfirmation();
+}
+
+pub enum AccessControlError {
+    AccessControlUnauthorizedAccount(AccessControlUnauthorizedAccount),
+    AccessControlBadConfirmation(AccessControlBadConfirmation),
+}
+
+impl From<AccessControlError> for Vec<u8> {
+    fn from(e: AccessControlError) -> Vec<u8> {
+        match e {
+            AccessControlError::AccessControlUnauthorizedAccount(e) => e.encode(),
+            AccessControlError::AccessControlBadConfirmation(e) => e.encode(),
+        }
+    }
+}
+
+crate::errors::error_helpers!(AccessControlError {
+    AccessControlUnauthorizedAccount,
+    AccessControlBadConfirmation,
+});
+
+impl AccessControl {
+    /// Reverts unless the caller has `role`.
+    pub fn only_role(&self, role: B256) -> Result<(), AccessControlError> {
+        self.check_role(role, msg::sender())
+    }
+
+    /// Reverts unless `account` has `role`.
+    pub fn check_role(&self, role: B256, account: Address) -> Result<(), AccessControlError> {
+        check_membership(self.has_role(role, account)?, role, account)
+    }
+
+    /// Sets `admin_role` as the admin of `role`.
+    ///
+    /// Emits a {RoleAdminChanged} event.
+    pub fn set_role_admin(&mut self, role: B256, admin_role: B256) {
+        let mut role_data = self.roles.setter(role);
+        let previous_admin_role = role_data.admin_role.get();
+        role_data.admin_role.set(admin_role);
+        evm::log(RoleAdminChanged {
+            role,
+            previous_admin_role,
+            new_admin_role: admin_role,
+        });
+    }
+
+    /// Grants `role` to `account` without access restriction, returns whether it was granted.
+    ///
+    /// Emits a {RoleGranted} event if `account` did not have the role.
+    pub fn grant_role_internal(&mut self, role: B256, account: Address) -> bool {
+        let mut role_data = self.roles.setter(role);
+        if role_data.members.get(account) {
+            return false;
+        }
+        role_data.members.insert(account, true);
+        evm::log(RoleGranted {
+            role,
+            account,
+            sender: msg::sender(),
+        });
+        true
+    }
+
+    /// Revokes `role` from `account` without access restriction, returns whether it was revoked.
+    ///
+    /// Emits a {RoleRevoked} event if `account` had the role.
+    pub fn revoke_role_internal(&mut self, role: B256, account: Address) -> bool {
+        let mut role_data = self.roles.setter(role);
+        if !role_data.members.get(account) {
+            return false;
+        }
+        role_data.members.insert(account, false);
+        evm::log(RoleRevoked {
+            role,
+            account,
+            sender: msg::sender(),
+        });
+        true
+    }
+}
+
+/// `IAccessControl` names are pinned, as for the token externals.
+#[external]
+impl AccessControl {
+    #[selector(name = "hasRole")]
+    pub fn has_role(&self, role: B256, account: Address) -> Result<bool, AccessControlError> {
+        Ok(self.roles.get(role).members.get(account))
+    }
+
+    /// Returns the admin role of `role`, `DEFAULT_ADMIN_ROLE` unless changed.
+    #[selector(name = "getRoleAdmin")]
+    pub fn get_role_admin(&self, role: B256) -> Result<B256, AccessControlError> {
+        Ok(self.roles.get(role).admin_role.get())
+    }
+
+    /// Grants `role` to `account`, the caller must have the admin role of `role`.
+    ///
+    /// Emits a {RoleGranted} event if `account` did not have the role.
+    #[selector(name = "grantRole")]
+    pub fn grant_role(&mut self, role: B256, account: Address) -> Result<(), AccessControlError> {
+        self.only_role(self.get_role_admin(role)?)?;
+        self.grant_role_internal(role, account);
+        Ok(())
+    }
+
+    /// Revokes `role` from `account`, the caller must have the admin role of `role`.
+    ///
+    /// Emits a {RoleRevoked} event if `account` had the role.
+    #[selector(name = "revokeRole")]
+    pub fn revoke_role(&mut self, role: B256, account: Address) -> Result<(), AccessControlError> {
+        self.only_role(self.get_role_admin(role)?)?;
+        self.revoke_role_internal(role, account);
+        Ok(())
+    }
+
+    /// Revokes `role` from the caller, e.g. when its key is compromised. `caller_confirmation`
+    /// must be the caller, which guards against renouncing by mistake.
+    ///
+    /// Emits a {RoleRevoked} event if the caller had the role.
+    #[selector(name = "renounceRole")]
+    pub fn renounce_role(&mut self, role: B256, caller_confirmation: Address) -> Result<(), AccessControlError> {
+        if caller_confirmation != msg::sender() {
+            return Err(AccessControlError::AccessControlBadConfirmation(
+                AccessControlBadConfirmation {},
+            ));
+        }
+        self.revoke_role_internal(role, caller_confirmation);
+        Ok(())
+    }
+}
+
+/// Check of {AccessControl::check_role} without storage: `AccessControlUnauthorizedAccount`
+/// unless `account` holds `role`, `has_role` telling whether it does.
+pub fn check_membership(has_role: bool, role: B256, account: Address) -> Result<(), AccessControlError> {
+    if !has_role {
+        return Err(AccessControlError::AccessControlUnauthorizedAccount(
+            AccessControlUnauthorizedAccount {
+                account,
+                needed_role: role,
+            },
+        ));
+    }
+    Ok(())
+}
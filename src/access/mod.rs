@@ -0,0 +1,3 @@
+pub mod ownable;
+pub mod multisig_admin;
+pub mod access_control;
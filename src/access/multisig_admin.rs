@@ -0,0 +1,380 @@
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{Address, B256, U256},
+    alloy_sol_types::{sol, SolError},
+    crypto::keccak,
+    evm, msg,
+    prelude::*,
+};
+
+use crate::utils::eip712::{address_word, u256_word};
+
+sol_storage! {
+    /// Weighted multi-signature authorization, a drop-in replacement of `Ownable::only_owner`
+    /// for teams not relying on an external Safe.
+    ///
+    /// An admin call is identified by its action hash, `keccak256` of the exact calldata of the
+    /// restricted method (see {action_hash}). Owners `submit_action` and `confirm` it, once the
+    /// confirmed weight reaches the threshold anyone can send the restricted call, which starts with
+    /// `self.multisig.consume_action(action_hash(...))?` instead of `self.ownable.only_owner()?`.
+    ///
+    /// The confirmed weight of a proposal is the sum of the current weights of its confirmers,
+    /// recomputed when the action is consumed: a confirmation follows later weight changes of its
+    /// owner and a removed owner's confirmation no longer counts.
+    /// See `examples/compose_multisig_admin.rs` for a complete composition.
+    pub struct MultisigAdmin {
+        /// weight of each owner, 0 for non owners
+        mapping(address => uint256) weights;
+        uint256 total_weight;
+        /// confirmed weight needed to execute an action
+        uint256 threshold;
+        /// number of submitted proposals, ids start at 1
+        uint256 proposal_count;
+        mapping(uint256 => bytes32) proposal_actions;
+        /// number of owners confirming each proposal
+        mapping(uint256 => uint256) confirmer_counts;
+        mapping(uint256 => bool) proposal_executed;
+        /// proposal => 1 based position of the owner in `confirmers`, 0 if not confirming
+        mapping(uint256 => mapping(address => uint256)) confirmations;
+        /// proposal => position => owner confirming it
+        mapping(uint256 => mapping(uint256 => address)) confirmers;
+        /// latest proposal submitted for an action
+        mapping(bytes32 => uint256) action_proposals;
+    }
+}
+
+sol! {
+    event ProposalSubmitted(uint256 indexed id, bytes32 indexed action, address indexed proposer);
+    event ProposalConfirmed(uint256 indexed id, address indexed owner, uint256 weight);
+    event ConfirmationRevoked(uint256 indexed id, address indexed owner, uint256 weight);
+    event ProposalExecuted(uint256 indexed id, bytes32 indexed action);
+    event OwnerWeightUpdated(address indexed owner, uint256 weight);
+    event ThresholdUpdated(uint256 threshold);
+
+    /// The caller is not a multisig owner.
+    error MultisigUnauthorizedOwner(address account);
+
+    /// The proposal does not exist.
+    error MultisigUnknownProposal(uint256 id);
+
+    /// The owner already confirmed the proposal.
+    error MultisigAlreadyConfirmed(uint256 id, address owner);
+
+    /// The owner did not confirm the proposal.
+    error MultisigNotConfirmed(uint256 id, address owner);
+
+    /// The proposal was already executed.
+    error MultisigAlreadyExecuted(uint256 id);
+
+    /// The action has no proposal confirmed by enough weight.
+    /// * `action` - hash of the restricted call.
+    /// * `confirmed` - weight confirmed for the latest proposal of the action.
+    /// * `threshold` - weight needed.
+    error MultisigInsufficientWeight(bytes32 action, uint256 confirmed, uint256 threshold);
+
+    /// The threshold is zero or higher than the total weight of the owners.
+    error MultisigInvalidThreshold(uint256 threshold, uint256 total_weight);
+
+    /// The owners were already initialized.
+    error MultisigAlreadyInitialized();
+
+    /// Giving `weight` to `owner` would push the total weight of the owners past `uint256`.
+    error MultisigWeightOverflow(address owner, uint256 weight);
+}
+
+pub enum MultisigAdminError {
+    MultisigUnauthorizedOwner(MultisigUnauthorizedOwner),
+    MultisigUnknownProposal(MultisigUnknownProposal),
+    MultisigAlreadyConfirmed(MultisigAlreadyConfirmed),
+    MultisigNotConfirmed(MultisigNotConfirmed),
+    MultisigAlreadyExecuted(MultisigAlreadyExecuted),
+    MultisigInsufficientWeight(MultisigInsufficientWeight),
+    MultisigInvalidThreshold(MultisigInvalidThreshold),
+    MultisigAlreadyInitialized(MultisigAlreadyInitialized),
+    MultisigWeightOverflow(MultisigWeightOverflow),
+}
+
+impl From<MultisigAdminError> for Vec<u8> {
+    fn from(e: MultisigAdminError) -> Vec<u8> {
+        match e {
+            MultisigAdminError::MultisigUnauthorizedOwner(e) => e.encode(),
+            MultisigAdminError::MultisigUnknownProposal(e) => e.encode(),
+            MultisigAdminError::MultisigAlreadyConfirmed(e) => e.encode(),
+            MultisigAdminError::MultisigNotConfirmed(e) => e.encode(),
+            MultisigAdminError::MultisigAlreadyExecuted(e) => e.encode(),
+            MultisigAdminError::MultisigInsufficientWeight(e) => e.encode(),
+            MultisigAdminError::MultisigInvalidThreshold(e) => e.encode(),
+            MultisigAdminError::MultisigAlreadyInitialized(e) => e.encode(),
+            MultisigAdminError::MultisigWeightOverflow(e) => e.encode(),
+        }
+    }
+}
+
+crate::errors::error_helpers!(MultisigAdminError {
+    MultisigUnauthorizedOwner,
+    MultisigUnknownProposal,
+    MultisigAlreadyConfirmed,
+    MultisigNotConfirmed,
+    MultisigAlreadyExecuted,
+    MultisigInsufficientWeight,
+    MultisigInvalidThreshold,
+    MultisigAlreadyInitialized,
+    MultisigWeightOverflow,
+});
+
+/// Hash identifying an admin call: `keccak256(selector ‖ abi.encode(args))` for a method with
+/// static arguments only, i.e. the keccak of the calldata the owners will send.
+/// * `signature` - Solidity signature of the restricted method, e.g. `"mint(address,uint256)"`.
+/// * `args` - ABI words of the arguments (see `utils::eip712::{address_word, u256_word}`).
+pub fn action_hash(signature: &str, args: &[B256]) -> B256 {
+    let mut data = Vec::with_capacity(4 + args.len() * 32);
+    data.extend_from_slice(&keccak(signature.as_bytes())[..4]);
+    for arg in args {
+        data.extend_from_slice(arg.as_slice());
+    }
+    keccak(data)
+}
+
+/// Total weight of the owners once the weight of `owner` goes from `previous_weight` to `weight`.
+pub fn updated_total_weight(
+    total_weight: U256,
+    owner: Address,
+    previous_weight: U256,
+    weight: U256,
+) -> Result<U256, MultisigAdminError> {
+    // `previous_weight` is part of `total_weight`
+    (total_weight - previous_weight)
+        .checked_add(weight)
+        .ok_or(MultisigAdminError::MultisigWeightOverflow(MultisigWeightOverflow { owner, weight }))
+}
+
+impl MultisigAdmin {
+    /// Sets the initial owners and threshold, can only be called once.
+    ///
+    /// Emits an {OwnerWeightUpdated} event per owner and a {ThresholdUpdated} event.
+    pub fn init_owners(&mut self, owners: &[(Address, U256)], threshold: U256) -> Result<(), MultisigAdminError> {
+        if self.threshold.get() != U256::ZERO {
+            return Err(MultisigAdminError::MultisigAlreadyInitialized(
+                MultisigAlreadyInitialized {},
+            ));
+        }
+        for (owner, weight) in owners {
+            self.set_weight(*owner, *weight)?;
+        }
+        self.set_threshold(threshold)
+    }
+
+    /// Reverts unless the latest proposal of `action` reached the threshold and was not executed
+    /// yet, then marks it executed. Call at the start of every restricted method.
+    ///
+    /// Emits a {ProposalExecuted} event.
+    pub fn consume_action(&mut self, action: B256) -> Result<(), MultisigAdminError> {
+        let id = self.action_proposals.get(action);
+        let confirmed = self.confirmed_weight(id);
+        let threshold = self.threshold.get();
+        if id == U256::ZERO || confirmed < threshold {
+            return Err(MultisigAdminError::MultisigInsufficientWeight(
+                MultisigInsufficientWeight {
+                    action,
+                    confirmed,
+                    threshold,
+                },
+            ));
+        }
+        if self.proposal_executed.get(id) {
+            return Err(MultisigAdminError::MultisigAlreadyExecuted(
+                MultisigAlreadyExecuted { id },
+            ));
+        }
+        self.proposal_executed.insert(id, true);
+        evm::log(ProposalExecuted { id, action });
+        Ok(())
+    }
+
+    fn only_multisig_owner(&self) -> Result<(Address, U256), MultisigAdminError> {
+        let account = msg::sender();
+        let weight = self.weights.get(account);
+        if weight == U256::ZERO {
+            return Err(MultisigAdminError::MultisigUnauthorizedOwner(
+                MultisigUnauthorizedOwner { account },
+            ));
+        }
+        Ok((account, weight))
+    }
+
+    /// Sum of the current weights of the owners confirming proposal `id`.
+    pub fn confirmed_weight(&self, id: U256) -> U256 {
+        let confirmers = self.confirmers.get(id);
+        let mut weight = U256::ZERO;
+        let mut position = U256::from(1);
+        while position <= self.confirmer_counts.get(id) {
+            weight += self.weights.get(confirmers.get(position));
+            position += U256::from(1);
+        }
+        weight
+    }
+
+    fn existing_proposal(&self, id: U256) -> Result<(), MultisigAdminError> {
+        if id == U256::ZERO || id > self.proposal_count.get() {
+            return Err(MultisigAdminError::MultisigUnknownProposal(
+                MultisigUnknownProposal { id },
+            ));
+        }
+        if self.proposal_executed.get(id) {
+            return Err(MultisigAdminError::MultisigAlreadyExecuted(
+                MultisigAlreadyExecuted { id },
+            ));
+        }
+        Ok(())
+    }
+
+    fn set_weight(&mut self, owner: Address, weight: U256) -> Result<(), MultisigAdminError> {
+        let previous_weight = self.weights.get(owner);
+        let total_weight = updated_total_weight(self.total_weight.get(), owner, previous_weight, weight)?;
+        self.total_weight.set(total_weight);
+        self.weights.insert(owner, weight);
+        evm::log(OwnerWeightUpdated { owner, weight });
+        Ok(())
+    }
+
+    fn set_threshold(&mut self, threshold: U256) -> Result<(), MultisigAdminError> {
+        let total_weight = self.total_weight.get();
+        if threshold == U256::ZERO || threshold > total_weight {
+            return Err(MultisigAdminError::MultisigInvalidThreshold(
+                MultisigInvalidThreshold {
+                    threshold,
+                    total_weight,
+                },
+            ));
+        }
+        self.threshold.set(threshold);
+        evm::log(ThresholdUpdated { threshold });
+        Ok(())
+    }
+}
+
+#[external]
+impl MultisigAdmin {
+    #[selector(name = "ownerWeight")]
+    pub fn owner_weight(&self, owner: Address) -> Result<U256, MultisigAdminError> {
+        Ok(self.weights.get(owner))
+    }
+
+    /// Returns `(threshold, total_weight)`.
+    #[selector(name = "multisigThreshold")]
+    pub fn multisig_threshold(&self) -> Result<(U256, U256), MultisigAdminError> {
+        Ok((self.threshold.get(), self.total_weight.get()))
+    }
+
+    /// Returns `(action, confirmed_weight, executed)` of proposal `id`, the weight at the current
+    /// owner weights.
+    #[selector(name = "proposal")]
+    pub fn proposal(&self, id: U256) -> Result<(B256, U256, bool), MultisigAdminError> {
+        Ok((
+            self.proposal_actions.get(id),
+            self.confirmed_weight(id),
+            self.proposal_executed.get(id),
+        ))
+    }
+
+    #[selector(name = "isConfirmedBy")]
+    pub fn is_confirmed_by(&self, id: U256, owner: Address) -> Result<bool, MultisigAdminError> {
+        Ok(self.confirmations.get(id).get(owner) != U256::ZERO)
+    }
+
+    /// Submits a proposal for `action` and confirms it with the caller's weight, returns its id.
+    ///
+    /// Emits a {ProposalSubmitted} and a {ProposalConfirmed} event.
+    #[selector(name = "submitAction")]
+    pub fn submit_action(&mut self, action: B256) -> Result<U256, MultisigAdminError> {
+        let (proposer, _) = self.only_multisig_owner()?;
+        let id = self.proposal_count.get() + U256::from(1);
+        self.proposal_count.set(id);
+        self.proposal_actions.insert(id, action);
+        self.action_proposals.insert(action, id);
+        evm::log(ProposalSubmitted {
+            id,
+            action,
+            proposer,
+        });
+
+        self.confirm(id)?;
+        Ok(id)
+    }
+
+    /// Adds the caller's weight to proposal `id`.
+    ///
+    /// Emits a {ProposalConfirmed} event.
+    #[selector(name = "confirm")]
+    pub fn confirm(&mut self, id: U256) -> Result<(), MultisigAdminError> {
+        let (owner, weight) = self.only_multisig_owner()?;
+        self.existing_proposal(id)?;
+        if self.confirmations.get(id).get(owner) != U256::ZERO {
+            return Err(MultisigAdminError::MultisigAlreadyConfirmed(
+                MultisigAlreadyConfirmed { id, owner },
+            ));
+        }
+        let position = self.confirmer_counts.get(id) + U256::from(1);
+        self.confirmer_counts.insert(id, position);
+        self.confirmers.setter(id).insert(position, owner);
+        self.confirmations.setter(id).insert(owner, position);
+
+        evm::log(ProposalConfirmed { id, owner, weight });
+        Ok(())
+    }
+
+    /// Removes the caller's confirmation of a not yet executed proposal. Owners removed since
+    /// they confirmed can still revoke, their confirmation counts for nothing anyway.
+    ///
+    /// Emits a {ConfirmationRevoked} event with the current weight of the caller.
+    #[selector(name = "revokeConfirmation")]
+    pub fn revoke_confirmation(&mut self, id: U256) -> Result<(), MultisigAdminError> {
+        let owner = msg::sender();
+        self.existing_proposal(id)?;
+        let position = self.confirmations.get(id).get(owner);
+        if position == U256::ZERO {
+            return Err(MultisigAdminError::MultisigNotConfirmed(MultisigNotConfirmed {
+                id,
+                owner,
+            }));
+        }
+        // the last confirmer takes the place of the removed one
+        let last = self.confirmer_counts.get(id);
+        let last_owner = self.confirmers.get(id).get(last);
+        self.confirmers.setter(id).insert(position, last_owner);
+        self.confirmations.setter(id).insert(last_owner, position);
+        self.confirmers.setter(id).insert(last, Address::ZERO);
+        self.confirmations.setter(id).insert(owner, U256::ZERO);
+        self.confirmer_counts.insert(id, last - U256::from(1));
+        let weight = self.weights.get(owner);
+
+        evm::log(ConfirmationRevoked { id, owner, weight });
+        Ok(())
+    }
+
+    /// Sets the weight of `owner` (0 removes it), itself a multisig action.
+    ///
+    /// Emits a {ProposalExecuted} and an {OwnerWeightUpdated} event.
+    #[selector(name = "setOwnerWeight")]
+    pub fn set_owner_weight(&mut self, owner: Address, weight: U256) -> Result<(), MultisigAdminError> {
+        self.consume_action(action_hash(
+            "setOwnerWeight(address,uint256)",
+            &[address_word(owner), u256_word(weight)],
+        ))?;
+        self.set_weight(owner, weight)?;
+        // the threshold must stay reachable
+        self.set_threshold(self.threshold.get())
+    }
+
+    /// Sets the confirmed weight needed to execute actions, itself a multisig action.
+    ///
+    /// Emits a {ProposalExecuted} and a {ThresholdUpdated} event.
+    #[selector(name = "changeThreshold")]
+    pub fn change_threshold(&mut self, threshold: U256) -> Result<(), MultisigAdminError> {
+        self.consume_action(action_hash(
+            "changeThreshold(uint256)",
+            &[u256_word(threshold)],
+        ))?;
+        self.set_threshold(threshold)
+    }
+}
@@ -0,0 +1,106 @@
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::Address,
+    alloy_sol_types::{sol, SolError},
+    evm, msg,
+    prelude::*,
+};
+
+sol_storage! {
+    /// Single account access control. Compose with `#[borrow] Ownable ownable;`, inherit it to
+    /// expose `owner`/`transfer_ownership`/`renounce_ownership` and call `only_owner()` at the
+    /// start of restricted methods. Stylus programs have no constructor, the composing token sets
+    /// the first owner in its `init` with `transfer_ownership_internal`.
+    pub struct Ownable {
+        address owner;
+    }
+}
+
+sol! {
+    event OwnershipTransferred(address indexed previous_owner, address indexed new_owner);
+
+    /// The caller account is not authorized to perform an operation.
+    /// * `account` - caller of the restricted operation.
+    error OwnableUnauthorizedAccount(address account);
+
+    /// The owner is not a valid owner account (e.g. `address(0)`).
+    /// * `owner` - rejected owner.
+    error OwnableInvalidOwner(address owner);
+}
+
+pub enum OwnableError {
+    OwnableUnauthorizedAccount(OwnableUnauthorizedAccount),
+    OwnableInvalidOwner(OwnableInvalidOwner),
+}
+
+impl From<OwnableError> for Vec<u8> {
+    fn from(e: OwnableError) -> Vec<u8> {
+        match e {
+            OwnableError::OwnableUnauthorizedAccount(e) => e.encode(),
+            OwnableError::OwnableInvalidOwner(e) => e.encode(),
+        }
+    }
+}
+
+crate::errors::error_helpers!(OwnableError {
+    OwnableUnauthorizedAccount,
+    OwnableInvalidOwner,
+});
+
+impl Ownable {
+    /// Reverts if called by any account other than the owner.
+    pub fn only_owner(&self) -> Result<(), OwnableError> {
+        let account = msg::sender();
+        if account != self.owner.get() {
+            return Err(OwnableError::OwnableUnauthorizedAccount(
+                OwnableUnauthorizedAccount { account },
+            ));
+        }
+        Ok(())
+    }
+
+    /// Transfers ownership of the contract to `new_owner` without access restriction.
+    ///
+    /// Emits an {OwnershipTransferred} event.
+    pub fn transfer_ownership_internal(&mut self, new_owner: Address) {
+        let previous_owner = self.owner.get();
+        self.owner.set(new_owner);
+        evm::log(OwnershipTransferred {
+            previous_owner,
+            new_owner,
+        });
+    }
+}
+
+#[external]
+impl Ownable {
+    #[selector(name = "owner")]
+    pub fn owner(&self) -> Result<Address, OwnableError> {
+        Ok(self.owner.get())
+    }
+
+    /// Transfers ownership of the contract to `new_owner`, can only be called by the current owner.
+    ///
+    /// Emits an {OwnershipTransferred} event.
+    #[selector(name = "transferOwnership")]
+    pub fn transfer_ownership(&mut self, new_owner: Address) -> Result<(), OwnableError> {
+        self.only_owner()?;
+        if new_owner == Address::ZERO {
+            return Err(OwnableError::OwnableInvalidOwner(OwnableInvalidOwner {
+                owner: Address::ZERO,
+            }));
+        }
+        self.transfer_ownership_internal(new_owner);
+        Ok(())
+    }
+
+    /// Leaves the contract without owner, restricted methods can never be called again.
+    ///
+    /// Emits an {OwnershipTransferred} event.
+    #[selector(name = "renounceOwnership")]
+    pub fn renounce_ownership(&mut self) -> Result<(), OwnableError> {
+        self.only_owner()?;
+        self.transfer_ownership_internal(Address::ZERO);
+        Ok(())
+    }
+}
@@ -0,0 +1,32 @@
+//! Prints the JSON ABI of the entrypoint token: the functions exported by `export-abi` plus the
+//! events and custom errors of its `AbiItems`, so ethers/viem bindings decode logs and reverts
+//! without hand maintained selector tables.
+//!
+//! `cargo run --features abi-json --bin abi_json > my_token.abi.json`
+use core::fmt;
+use core::marker::PhantomData;
+use oz_stylus_erc::tokens::my_token::MyToken;
+use oz_stylus_erc::utils::abi_export::{check_abi_items, human_readable_abi, AbiItems};
+use stylus_sdk::abi::export::GenerateAbi;
+
+/// Solidity interface generated by `export-abi` for `T`.
+struct Interface<T>(PhantomData<T>);
+
+impl<T: GenerateAbi> fmt::Display for Interface<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        T::fmt_abi(f)
+    }
+}
+
+fn main() {
+    let items = MyToken::abi_items();
+    if let Err(mismatch) = check_abi_items(&items) {
+        panic!("stale ABI declaration: {}", mismatch);
+    }
+    let interface = Interface::<MyToken>(PhantomData).to_string();
+    let lines = human_readable_abi(&interface, &items);
+    let lines: Vec<&str> = lines.iter().map(String::as_str).collect();
+
+    let abi = ethers::abi::parse_abi(&lines).expect("human readable ABI");
+    println!("{}", serde_json::to_string_pretty(&abi).unwrap());
+}
@@ -0,0 +1,144 @@
+//! Prints JSON test vectors of the ABI surface of crate-built tokens: call encodings, event
+//! topics, error selectors and EIP-712 permit digests signed by fixed dev keys. JS/Python clients
+//! check their encoders against them.
+//!
+//! `cargo run --features test-vectors --bin test_vectors > vectors.json`
+use ethers::signers::LocalWallet;
+use ethers::types::H256;
+use oz_stylus_erc::access::ownable::{OwnableInvalidOwner, OwnableUnauthorizedAccount, OwnershipTransferred};
+use oz_stylus_erc::extensions::erc20_signature_transfer::{
+    self, Erc20InvalidRequestedAmount, Erc20InvalidSigner, Erc20SignatureExpired, PermitTransferFrom,
+    SIGNATURE_TRANSFER_VERSION,
+};
+use oz_stylus_erc::tokens::erc20::{
+    AllowancesRevoked, Approval, Erc20AllowanceOverflow, Erc20ArithmeticOverflow, Erc20FailedDecreaseAllowance,
+    Erc20InsufficientAllowance, Erc20InsufficientBalance, Erc20InvalidApprover, Erc20InvalidReceiver, Erc20InvalidSpender,
+    Transfer,
+};
+use oz_stylus_erc::tokens::my_token::MyTokenParams;
+use oz_stylus_erc::utils::ecdsa::{ECDSAInvalidSignature, ECDSAInvalidSignatureLength, ECDSAInvalidSignatureS};
+use oz_stylus_erc::utils::eip712::{self, address_word, u256_word};
+use oz_stylus_erc::utils::replay_guard::{InvalidAccountNonce, InvalidUnorderedNonce, UnorderedNonceInvalidation};
+use oz_stylus_erc::Erc20Params;
+use serde_json::{json, Value};
+use stylus_sdk::{
+    alloy_primitives::{Address, B256, U256},
+    alloy_sol_types::{SolError, SolEvent},
+    crypto::keccak,
+};
+
+/// Well known dev node keys, never use them on a public network.
+const SIGNER_KEYS: [&str; 2] = [
+    "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+    "59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d",
+];
+
+/// Chain id of the local Nitro dev node.
+const CHAIN_ID: u64 = 412346;
+
+fn main() {
+    let token: Address = "0x5fbdb2315678afecb367f032d93f642f64180aa3".parse().unwrap();
+    let alice: Address = "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266".parse().unwrap();
+    let bob: Address = "0x70997970c51812dc3a010c7d01b50e0d17dc79c8".parse().unwrap();
+    let value = U256::from(1_000_000_000_000_000_000u128);
+
+    let vectors = json!({
+        "token": { "name": MyTokenParams::NAME, "symbol": MyTokenParams::SYMBOL, "decimals": MyTokenParams::DECIMALS },
+        "calls": [
+            call("transfer(address,uint256)", &[address_word(bob), u256_word(value)]),
+            call("approve(address,uint256)", &[address_word(bob), u256_word(U256::MAX)]),
+            call("transferFrom(address,address,uint256)", &[address_word(alice), address_word(bob), u256_word(value)]),
+            call("balanceOf(address)", &[address_word(alice)]),
+            call("allowance(address,address)", &[address_word(alice), address_word(bob)]),
+            call("revokeAllAllowances()", &[]),
+        ],
+        "events": [
+            event(Transfer::SIGNATURE, Transfer::SIGNATURE_HASH, &[address_word(alice), address_word(bob)], &[u256_word(value)]),
+            event(Approval::SIGNATURE, Approval::SIGNATURE_HASH, &[address_word(alice), address_word(bob)], &[u256_word(value)]),
+            event(AllowancesRevoked::SIGNATURE, AllowancesRevoked::SIGNATURE_HASH, &[address_word(alice)], &[u256_word(U256::from(1))]),
+            event(OwnershipTransferred::SIGNATURE, OwnershipTransferred::SIGNATURE_HASH, &[address_word(Address::ZERO), address_word(alice)], &[]),
+            event(UnorderedNonceInvalidation::SIGNATURE, UnorderedNonceInvalidation::SIGNATURE_HASH, &[address_word(alice)], &[u256_word(U256::ZERO), u256_word(U256::from(1))]),
+        ],
+        "errors": [
+            error(Erc20InsufficientBalance::SIGNATURE, Erc20InsufficientBalance::SELECTOR),
+            error(Erc20InvalidReceiver::SIGNATURE, Erc20InvalidReceiver::SELECTOR),
+            error(Erc20InsufficientAllowance::SIGNATURE, Erc20InsufficientAllowance::SELECTOR),
+            error(Erc20InvalidApprover::SIGNATURE, Erc20InvalidApprover::SELECTOR),
+            error(Erc20InvalidSpender::SIGNATURE, Erc20InvalidSpender::SELECTOR),
+            error(Erc20FailedDecreaseAllowance::SIGNATURE, Erc20FailedDecreaseAllowance::SELECTOR),
+            error(Erc20AllowanceOverflow::SIGNATURE, Erc20AllowanceOverflow::SELECTOR),
+            error(Erc20ArithmeticOverflow::SIGNATURE, Erc20ArithmeticOverflow::SELECTOR),
+            error(Erc20SignatureExpired::SIGNATURE, Erc20SignatureExpired::SELECTOR),
+            error(Erc20InvalidSigner::SIGNATURE, Erc20InvalidSigner::SELECTOR),
+            error(Erc20InvalidRequestedAmount::SIGNATURE, Erc20InvalidRequestedAmount::SELECTOR),
+            error(InvalidAccountNonce::SIGNATURE, InvalidAccountNonce::SELECTOR),
+            error(InvalidUnorderedNonce::SIGNATURE, InvalidUnorderedNonce::SELECTOR),
+            error(ECDSAInvalidSignature::SIGNATURE, ECDSAInvalidSignature::SELECTOR),
+            error(ECDSAInvalidSignatureLength::SIGNATURE, ECDSAInvalidSignatureLength::SELECTOR),
+            error(ECDSAInvalidSignatureS::SIGNATURE, ECDSAInvalidSignatureS::SELECTOR),
+            error(OwnableUnauthorizedAccount::SIGNATURE, OwnableUnauthorizedAccount::SELECTOR),
+            error(OwnableInvalidOwner::SIGNATURE, OwnableInvalidOwner::SELECTOR),
+        ],
+        "permit_transfer_from": SIGNER_KEYS
+            .iter()
+            .map(|key| permit_vector(key, token, bob))
+            .collect::<Vec<Value>>(),
+    });
+    println!("{}", serde_json::to_string_pretty(&vectors).unwrap());
+}
+
+fn call(signature: &str, args: &[B256]) -> Value {
+    let mut calldata = keccak(signature.as_bytes())[..4].to_vec();
+    for arg in args {
+        calldata.extend_from_slice(arg.as_slice());
+    }
+    json!({ "signature": signature, "calldata": hex_string(&calldata) })
+}
+
+fn event(signature: &str, topic0: B256, indexed: &[B256], data: &[B256]) -> Value {
+    let mut topics = vec![hex_string(topic0.as_slice())];
+    topics.extend(indexed.iter().map(|topic| hex_string(topic.as_slice())));
+    let data: Vec<u8> = data.iter().flat_map(|word| word.as_slice().to_vec()).collect();
+    json!({ "signature": signature, "topics": topics, "data": hex_string(&data) })
+}
+
+fn error(signature: &str, selector: [u8; 4]) -> Value {
+    json!({ "signature": signature, "selector": hex_string(&selector) })
+}
+
+/// `PermitTransferFrom` of 1 token to `spender` with nonce 0, signed by `key`.
+fn permit_vector(key: &str, token: Address, spender: Address) -> Value {
+    let wallet: LocalWallet = key.parse().unwrap();
+    let permit = PermitTransferFrom {
+        amount: U256::from(1_000_000_000_000_000_000u128),
+        nonce: U256::ZERO,
+        deadline: U256::from(u64::MAX),
+    };
+    let domain_separator = eip712::domain_separator(
+        MyTokenParams::NAME,
+        SIGNATURE_TRANSFER_VERSION,
+        CHAIN_ID,
+        token,
+    );
+    let struct_hash = erc20_signature_transfer::permit_transfer_from_hash(token, spender, &permit);
+    let digest = eip712::to_typed_data_hash(domain_separator, struct_hash);
+    let signature = wallet.sign_hash(H256::from_slice(digest.as_slice())).unwrap();
+
+    json!({
+        "signer": format!("{:?}", ethers::signers::Signer::address(&wallet)),
+        "chain_id": CHAIN_ID,
+        "verifying_contract": format!("{:?}", token),
+        "spender": format!("{:?}", spender),
+        "amount": permit.amount.to_string(),
+        "nonce": permit.nonce.to_string(),
+        "deadline": permit.deadline.to_string(),
+        "domain_separator": hex_string(domain_separator.as_slice()),
+        "struct_hash": hex_string(struct_hash.as_slice()),
+        "digest": hex_string(digest.as_slice()),
+        "signature": format!("0x{}", signature),
+    })
+}
+
+fn hex_string(bytes: &[u8]) -> String {
+    format!("0x{}", hex::encode(bytes))
+}
@@ -0,0 +1,903 @@
+//! Helpers shared by the error enums of every module. Each enum keeps its `sol!` errors as
+//! variants and its `From<_> for Vec<u8>` revert encoding, `error_helpers!` adds:
+//!
+//! - `selector()`: the 4 bytes selector of the revert data, to match reverts off-chain,
+//! - `name()`: the name of the variant,
+//! - `Debug` (name and full revert data) and `Display` (name and selector), so results can be
+//!   unwrapped and printed in tests and tools.
+//!
+//! Variants passing revert data of another contract through (e.g.
+//! `Erc20Error::TransferHookRejected`) are listed after `passthrough`, their selector is the
+//! one of the data.
+//!
+//! `OzError` wraps the error enums of every module for composed tokens, see its doc.
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+
+use crate::tokens::erc1155::Erc1155Error;
+use crate::tokens::erc20::Erc20Error;
+use crate::tokens::erc721::Erc721Error;
+use crate::access::access_control::AccessControlError;
+use crate::access::multisig_admin::MultisigAdminError;
+use crate::access::ownable::OwnableError;
+use crate::extensions::erc20_allowlist::Erc20AllowlistError;
+use crate::extensions::erc20_batch_transfer::Erc20BatchTransferError;
+use crate::extensions::erc20_send_hooks::Erc20SendHooksError;
+use crate::extensions::erc20_mintable::Erc20MintableError;
+use crate::extensions::erc20_temporary_approval::Erc20TemporaryApprovalError;
+use crate::extensions::erc20_approve_by_sig::Erc20ApproveBySigError;
+use crate::extensions::erc20_balances_root::Erc20BalancesRootError;
+use crate::extensions::erc20_blocklist::Erc20BlocklistError;
+use crate::extensions::erc20_cap::Erc20CapError;
+use crate::extensions::erc20_crosschain::Erc20CrosschainError;
+use crate::extensions::erc20_fee::Erc20FeeError;
+use crate::extensions::erc20_gasback::Erc20GasbackError;
+use crate::extensions::erc20_global_settlement::Erc20GlobalSettlementError;
+use crate::extensions::erc20_interest_bearing::Erc20InterestBearingError;
+use crate::extensions::erc20_metadata_storage::Erc20MetadataStorageError;
+use crate::extensions::erc20_mint_authorization::Erc20MintAuthorizationError;
+use crate::extensions::erc20_rate_limit::Erc20RateLimitError;
+use crate::extensions::erc20_redeemable::Erc20RedeemableError;
+use crate::extensions::erc20_signature_transfer::Erc20SignatureTransferError;
+use crate::extensions::erc20_spender_caps::Erc20SpenderCapsError;
+use crate::extensions::erc20_stream::Erc20StreamError;
+use crate::extensions::erc20_transfer_cooldown::Erc20TransferCooldownError;
+use crate::extensions::erc20_votes::Erc20VotesError;
+use crate::extensions::erc20_wrapper::Erc20WrapperError;
+use crate::extensions::erc2981::Erc2981Error;
+use crate::extensions::erc4626::Erc4626Error;
+use crate::extensions::erc721_permit::Erc721PermitError;
+use crate::extensions::nft_receiver::NftReceiverError;
+use crate::finance::commit_reveal_sale::CommitRevealSaleError;
+use crate::finance::refund_escrow::RefundEscrowError;
+use crate::finance::token_migrator::TokenMigratorError;
+use crate::governance::governor::GovernorError;
+use crate::governance::timelock::TimelockError;
+use crate::proxy::erc1967::Erc1967Error;
+use crate::proxy::uups::UupsUpgradeableError;
+use crate::utils::bounded_string::BoundedStringError;
+use crate::utils::checkpoints::CheckpointsError;
+use crate::utils::create2::Create2Error;
+use crate::utils::ecdsa::EcdsaError;
+use crate::utils::erc2771_context::Erc2771ContextError;
+use crate::utils::initializable::InitializableError;
+use crate::utils::math::MathError;
+use crate::utils::merkle_proof::MerkleProofError;
+use crate::utils::nonces::NoncesError;
+use crate::utils::pausable::PausableError;
+use crate::utils::reentrancy_guard::ReentrancyGuardError;
+use crate::utils::replay_guard::ReplayGuardError;
+use crate::utils::rescue::RescueError;
+use crate::utils::safe_erc20::SafeErc20Error;
+
+/// Implements `selector()`, `name()`, `Debug` and `Display` for an error enum whose variants
+/// wrap the `sol!` error of the same name.
+macro_rules! error_helpers {
+    ($error:ident { $($variant:ident),* $(,)? } $(passthrough { $($raw:ident),* $(,)? })?) => {
+        impl $error {
+            /// Selector of the revert data of the error.
+            pub fn selector(&self) -> [u8; 4] {
+                match self {
+                    $(Self::$variant(_) => <$variant as ::stylus_sdk::alloy_sol_types::SolError>::SELECTOR,)*
+                    $($(Self::$raw(data) => $crate::errors::data_selector(data),)*)?
+                }
+            }
+
+            /// Name of the error variant.
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(Self::$variant(_) => stringify!($variant),)*
+                    $($(Self::$raw(_) => stringify!($raw),)*)?
+                }
+            }
+        }
+
+        impl core::fmt::Debug for $error {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                let data = match self {
+                    $(Self::$variant(e) => ::stylus_sdk::alloy_sol_types::SolError::encode(e),)*
+                    $($(Self::$raw(data) => data.clone(),)*)?
+                };
+                f.debug_tuple(self.name()).field(&$crate::errors::Hex(&data)).finish()
+            }
+        }
+
+        impl core::fmt::Display for $error {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                write!(f, "{} ({})", self.name(), $crate::errors::Hex(&self.selector()))
+            }
+        }
+    };
+}
+
+pub(crate) use error_helpers;
+
+/// Selector of passed through revert data, zero padded when shorter than 4 bytes.
+pub fn data_selector(data: &[u8]) -> [u8; 4] {
+    let mut selector = [0; 4];
+    let len = data.len().min(4);
+    selector[..len].copy_from_slice(&data[..len]);
+    selector
+}
+
+/// Bytes formatted as `0x` prefixed lowercase hex, the way RPC errors report revert data.
+pub struct Hex<'a>(pub &'a [u8]);
+
+impl fmt::Display for Hex<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("0x")?;
+        for byte in self.0 {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Hex<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// `0x` prefixed hex of a selector, as found in the revert messages of ethers and viem.
+pub fn selector_hex(selector: [u8; 4]) -> String {
+    alloc::format!("{}", Hex(&selector))
+}
+
+/// Errors of every module of the crate in one type, for composed tokens mixing extensions:
+/// externals return `Result<_, OzError>` and `?` converts the error of each component,
+/// instead of erasing them all into `Vec<u8>` revert data. Reverts encode the wrapped error
+/// unchanged, callers see the same custom errors as with the component alone.
+///
+/// Helpers still returning raw revert data (e.g. `safe_erc20`, passed through reverts of other
+/// contracts) convert into `Revert`, as do string reasons.
+pub enum OzError {
+    Erc1155(Erc1155Error),
+    Erc20(Erc20Error),
+    Erc721(Erc721Error),
+    AccessControl(AccessControlError),
+    MultisigAdmin(MultisigAdminError),
+    Ownable(OwnableError),
+    Erc20Allowlist(Erc20AllowlistError),
+    Erc20BatchTransfer(Erc20BatchTransferError),
+    Erc20SendHooks(Erc20SendHooksError),
+    Erc20Mintable(Erc20MintableError),
+    Erc20TemporaryApproval(Erc20TemporaryApprovalError),
+    Erc20ApproveBySig(Erc20ApproveBySigError),
+    Erc20BalancesRoot(Erc20BalancesRootError),
+    Erc20Blocklist(Erc20BlocklistError),
+    Erc20Cap(Erc20CapError),
+    Erc20Crosschain(Erc20CrosschainError),
+    Erc20Fee(Erc20FeeError),
+    Erc20Gasback(Erc20GasbackError),
+    Erc20GlobalSettlement(Erc20GlobalSettlementError),
+    Erc20InterestBearing(Erc20InterestBearingError),
+    Erc20MetadataStorage(Erc20MetadataStorageError),
+    Erc20MintAuthorization(Erc20MintAuthorizationError),
+    Erc20RateLimit(Erc20RateLimitError),
+    Erc20Redeemable(Erc20RedeemableError),
+    Erc20SignatureTransfer(Erc20SignatureTransferError),
+    Erc20SpenderCaps(Erc20SpenderCapsError),
+    Erc20Stream(Erc20StreamError),
+    Erc20TransferCooldown(Erc20TransferCooldownError),
+    Erc20Votes(Erc20VotesError),
+    Erc20Wrapper(Erc20WrapperError),
+    Erc2981(Erc2981Error),
+    Erc4626(Erc4626Error),
+    Erc721Permit(Erc721PermitError),
+    NftReceiver(NftReceiverError),
+    CommitRevealSale(CommitRevealSaleError),
+    RefundEscrow(RefundEscrowError),
+    TokenMigrator(TokenMigratorError),
+    Governor(GovernorError),
+    Timelock(TimelockError),
+    Erc1967(Erc1967Error),
+    UupsUpgradeable(UupsUpgradeableError),
+    BoundedString(BoundedStringError),
+    Checkpoints(CheckpointsError),
+    Create2(Create2Error),
+    Ecdsa(EcdsaError),
+    Erc2771Context(Erc2771ContextError),
+    Initializable(InitializableError),
+    Math(MathError),
+    MerkleProof(MerkleProofError),
+    Nonces(NoncesError),
+    Pausable(PausableError),
+    ReentrancyGuard(ReentrancyGuardError),
+    ReplayGuard(ReplayGuardError),
+    Rescue(RescueError),
+    SafeErc20(SafeErc20Error),
+    /// raw revert data, passed through as is
+    Revert(Vec<u8>),
+}
+
+impl From<Erc1155Error> for OzError {
+    fn from(e: Erc1155Error) -> OzError {
+        OzError::Erc1155(e)
+    }
+}
+
+impl From<Erc20Error> for OzError {
+    fn from(e: Erc20Error) -> OzError {
+        OzError::Erc20(e)
+    }
+}
+
+impl From<Erc721Error> for OzError {
+    fn from(e: Erc721Error) -> OzError {
+        OzError::Erc721(e)
+    }
+}
+
+impl From<AccessControlError> for OzError {
+    fn from(e: AccessControlError) -> OzError {
+        OzError::AccessControl(e)
+    }
+}
+
+impl From<MultisigAdminError> for OzError {
+    fn from(e: MultisigAdminError) -> OzError {
+        OzError::MultisigAdmin(e)
+    }
+}
+
+impl From<OwnableError> for OzError {
+    fn from(e: OwnableError) -> OzError {
+        OzError::Ownable(e)
+    }
+}
+
+impl From<Erc20AllowlistError> for OzError {
+    fn from(e: Erc20AllowlistError) -> OzError {
+        OzError::Erc20Allowlist(e)
+    }
+}
+
+impl From<Erc20BatchTransferError> for OzError {
+    fn from(e: Erc20BatchTransferError) -> OzError {
+        OzError::Erc20BatchTransfer(e)
+    }
+}
+
+impl From<Erc20SendHooksError> for OzError {
+    fn from(e: Erc20SendHooksError) -> OzError {
+        OzError::Erc20SendHooks(e)
+    }
+}
+
+impl From<Erc20MintableError> for OzError {
+    fn from(e: Erc20MintableError) -> OzError {
+        OzError::Erc20Mintable(e)
+    }
+}
+
+impl From<Erc20TemporaryApprovalError> for OzError {
+    fn from(e: Erc20TemporaryApprovalError) -> OzError {
+        OzError::Erc20TemporaryApproval(e)
+    }
+}
+
+impl From<Erc20ApproveBySigError> for OzError {
+    fn from(e: Erc20ApproveBySigError) -> OzError {
+        OzError::Erc20ApproveBySig(e)
+    }
+}
+
+impl From<Erc20BalancesRootError> for OzError {
+    fn from(e: Erc20BalancesRootError) -> OzError {
+        OzError::Erc20BalancesRoot(e)
+    }
+}
+
+impl From<Erc20BlocklistError> for OzError {
+    fn from(e: Erc20BlocklistError) -> OzError {
+        OzError::Erc20Blocklist(e)
+    }
+}
+
+impl From<Erc20CapError> for OzError {
+    fn from(e: Erc20CapError) -> OzError {
+        OzError::Erc20Cap(e)
+    }
+}
+
+impl From<Erc20CrosschainError> for OzError {
+    fn from(e: Erc20CrosschainError) -> OzError {
+        OzError::Erc20Crosschain(e)
+    }
+}
+
+impl From<Erc20FeeError> for OzError {
+    fn from(e: Erc20FeeError) -> OzError {
+        OzError::Erc20Fee(e)
+    }
+}
+
+impl From<Erc20GasbackError> for OzError {
+    fn from(e: Erc20GasbackError) -> OzError {
+        OzError::Erc20Gasback(e)
+    }
+}
+
+impl From<Erc20GlobalSettlementError> for OzError {
+    fn from(e: Erc20GlobalSettlementError) -> OzError {
+        OzError::Erc20GlobalSettlement(e)
+    }
+}
+
+impl From<Erc20InterestBearingError> for OzError {
+    fn from(e: Erc20InterestBearingError) -> OzError {
+        OzError::Erc20InterestBearing(e)
+    }
+}
+
+impl From<Erc20MetadataStorageError> for OzError {
+    fn from(e: Erc20MetadataStorageError) -> OzError {
+        OzError::Erc20MetadataStorage(e)
+    }
+}
+
+impl From<Erc20MintAuthorizationError> for OzError {
+    fn from(e: Erc20MintAuthorizationError) -> OzError {
+        OzError::Erc20MintAuthorization(e)
+    }
+}
+
+impl From<Erc20RateLimitError> for OzError {
+    fn from(e: Erc20RateLimitError) -> OzError {
+        OzError::Erc20RateLimit(e)
+    }
+}
+
+impl From<Erc20RedeemableError> for OzError {
+    fn from(e: Erc20RedeemableError) -> OzError {
+        OzError::Erc20Redeemable(e)
+    }
+}
+
+impl From<Erc20SignatureTransferError> for OzError {
+    fn from(e: Erc20SignatureTransferError) -> OzError {
+        OzError::Erc20SignatureTransfer(e)
+    }
+}
+
+impl From<Erc20SpenderCapsError> for OzError {
+    fn from(e: Erc20SpenderCapsError) -> OzError {
+        OzError::Erc20SpenderCaps(e)
+    }
+}
+
+impl From<Erc20StreamError> for OzError {
+    fn from(e: Erc20StreamError) -> OzError {
+        OzError::Erc20Stream(e)
+    }
+}
+
+impl From<Erc20TransferCooldownError> for OzError {
+    fn from(e: Erc20TransferCooldownError) -> OzError {
+        OzError::Erc20TransferCooldown(e)
+    }
+}
+
+impl From<Erc20VotesError> for OzError {
+    fn from(e: Erc20VotesError) -> OzError {
+        OzError::Erc20Votes(e)
+    }
+}
+
+impl From<Erc20WrapperError> for OzError {
+    fn from(e: Erc20WrapperError) -> OzError {
+        OzError::Erc20Wrapper(e)
+    }
+}
+
+impl From<Erc2981Error> for OzError {
+    fn from(e: Erc2981Error) -> OzError {
+        OzError::Erc2981(e)
+    }
+}
+
+impl From<Erc4626Error> for OzError {
+    fn from(e: Erc4626Error) -> OzError {
+        OzError::Erc4626(e)
+    }
+}
+
+impl From<Erc721PermitError> for OzError {
+    fn from(e: Erc721PermitError) -> OzError {
+        OzError::Erc721Permit(e)
+    }
+}
+
+impl From<NftReceiverError> for OzError {
+    fn from(e: NftReceiverError) -> OzError {
+        OzError::NftReceiver(e)
+    }
+}
+
+impl From<CommitRevealSaleError> for OzError {
+    fn from(e: CommitRevealSaleError) -> OzError {
+        OzError::CommitRevealSale(e)
+    }
+}
+
+impl From<RefundEscrowError> for OzError {
+    fn from(e: RefundEscrowError) -> OzError {
+        OzError::RefundEscrow(e)
+    }
+}
+
+impl From<TokenMigratorError> for OzError {
+    fn from(e: TokenMigratorError) -> OzError {
+        OzError::TokenMigrator(e)
+    }
+}
+
+impl From<GovernorError> for OzError {
+    fn from(e: GovernorError) -> OzError {
+        OzError::Governor(e)
+    }
+}
+
+impl From<TimelockError> for OzError {
+    fn from(e: TimelockError) -> OzError {
+        OzError::Timelock(e)
+    }
+}
+
+impl From<Erc1967Error> for OzError {
+    fn from(e: Erc1967Error) -> OzError {
+        OzError::Erc1967(e)
+    }
+}
+
+impl From<UupsUpgradeableError> for OzError {
+    fn from(e: UupsUpgradeableError) -> OzError {
+        OzError::UupsUpgradeable(e)
+    }
+}
+
+impl From<BoundedStringError> for OzError {
+    fn from(e: BoundedStringError) -> OzError {
+        OzError::BoundedString(e)
+    }
+}
+
+impl From<CheckpointsError> for OzError {
+    fn from(e: CheckpointsError) -> OzError {
+        OzError::Checkpoints(e)
+    }
+}
+
+impl From<Create2Error> for OzError {
+    fn from(e: Create2Error) -> OzError {
+        OzError::Create2(e)
+    }
+}
+
+impl From<EcdsaError> for OzError {
+    fn from(e: EcdsaError) -> OzError {
+        OzError::Ecdsa(e)
+    }
+}
+
+impl From<Erc2771ContextError> for OzError {
+    fn from(e: Erc2771ContextError) -> OzError {
+        OzError::Erc2771Context(e)
+    }
+}
+
+impl From<InitializableError> for OzError {
+    fn from(e: InitializableError) -> OzError {
+        OzError::Initializable(e)
+    }
+}
+
+impl From<MathError> for OzError {
+    fn from(e: MathError) -> OzError {
+        OzError::Math(e)
+    }
+}
+
+impl From<MerkleProofError> for OzError {
+    fn from(e: MerkleProofError) -> OzError {
+        OzError::MerkleProof(e)
+    }
+}
+
+impl From<NoncesError> for OzError {
+    fn from(e: NoncesError) -> OzError {
+        OzError::Nonces(e)
+    }
+}
+
+impl From<PausableError> for OzError {
+    fn from(e: PausableError) -> OzError {
+        OzError::Pausable(e)
+    }
+}
+
+impl From<ReentrancyGuardError> for OzError {
+    fn from(e: ReentrancyGuardError) -> OzError {
+        OzError::ReentrancyGuard(e)
+    }
+}
+
+impl From<ReplayGuardError> for OzError {
+    fn from(e: ReplayGuardError) -> OzError {
+        OzError::ReplayGuard(e)
+    }
+}
+
+impl From<RescueError> for OzError {
+    fn from(e: RescueError) -> OzError {
+        OzError::Rescue(e)
+    }
+}
+
+impl From<SafeErc20Error> for OzError {
+    fn from(e: SafeErc20Error) -> OzError {
+        OzError::SafeErc20(e)
+    }
+}
+
+impl From<Vec<u8>> for OzError {
+    fn from(data: Vec<u8>) -> OzError {
+        OzError::Revert(data)
+    }
+}
+
+impl From<&str> for OzError {
+    fn from(reason: &str) -> OzError {
+        OzError::Revert(reason.into())
+    }
+}
+
+impl From<OzError> for Vec<u8> {
+    fn from(e: OzError) -> Vec<u8> {
+        match e {
+            OzError::Erc1155(e) => e.into(),
+            OzError::Erc20(e) => e.into(),
+            OzError::Erc721(e) => e.into(),
+            OzError::AccessControl(e) => e.into(),
+            OzError::MultisigAdmin(e) => e.into(),
+            OzError::Ownable(e) => e.into(),
+            OzError::Erc20Allowlist(e) => e.into(),
+            OzError::Erc20BatchTransfer(e) => e.into(),
+            OzError::Erc20SendHooks(e) => e.into(),
+            OzError::Erc20Mintable(e) => e.into(),
+            OzError::Erc20TemporaryApproval(e) => e.into(),
+            OzError::Erc20ApproveBySig(e) => e.into(),
+            OzError::Erc20BalancesRoot(e) => e.into(),
+            OzError::Erc20Blocklist(e) => e.into(),
+            OzError::Erc20Cap(e) => e.into(),
+            OzError::Erc20Crosschain(e) => e.into(),
+            OzError::Erc20Fee(e) => e.into(),
+            OzError::Erc20Gasback(e) => e.into(),
+            OzError::Erc20GlobalSettlement(e) => e.into(),
+            OzError::Erc20InterestBearing(e) => e.into(),
+            OzError::Erc20MetadataStorage(e) => e.into(),
+            OzError::Erc20MintAuthorization(e) => e.into(),
+            OzError::Erc20RateLimit(e) => e.into(),
+            OzError::Erc20Redeemable(e) => e.into(),
+            OzError::Erc20SignatureTransfer(e) => e.into(),
+            OzError::Erc20SpenderCaps(e) => e.into(),
+            OzError::Erc20Stream(e) => e.into(),
+            OzError::Erc20TransferCooldown(e) => e.into(),
+            OzError::Erc20Votes(e) => e.into(),
+            OzError::Erc20Wrapper(e) => e.into(),
+            OzError::Erc2981(e) => e.into(),
+            OzError::Erc4626(e) => e.into(),
+            OzError::Erc721Permit(e) => e.into(),
+            OzError::NftReceiver(e) => e.into(),
+            OzError::CommitRevealSale(e) => e.into(),
+            OzError::RefundEscrow(e) => e.into(),
+            OzError::TokenMigrator(e) => e.into(),
+            OzError::Governor(e) => e.into(),
+            OzError::Timelock(e) => e.into(),
+            OzError::Erc1967(e) => e.into(),
+            OzError::UupsUpgradeable(e) => e.into(),
+            OzError::BoundedString(e) => e.into(),
+            OzError::Checkpoints(e) => e.into(),
+            OzError::Create2(e) => e.into(),
+            OzError::Ecdsa(e) => e.into(),
+            OzError::Erc2771Context(e) => e.into(),
+            OzError::Initializable(e) => e.into(),
+            OzError::Math(e) => e.into(),
+            OzError::MerkleProof(e) => e.into(),
+            OzError::Nonces(e) => e.into(),
+            OzError::Pausable(e) => e.into(),
+            OzError::ReentrancyGuard(e) => e.into(),
+            OzError::ReplayGuard(e) => e.into(),
+            OzError::Rescue(e) => e.into(),
+            OzError::SafeErc20(e) => e.into(),
+            OzError::Revert(data) => data,
+        }
+    }
+}
+
+impl OzError {
+    /// Selector of the revert data of the wrapped error.
+    pub fn selector(&self) -> [u8; 4] {
+        match self {
+            OzError::Erc1155(e) => e.selector(),
+            OzError::Erc20(e) => e.selector(),
+            OzError::Erc721(e) => e.selector(),
+            OzError::AccessControl(e) => e.selector(),
+            OzError::MultisigAdmin(e) => e.selector(),
+            OzError::Ownable(e) => e.selector(),
+            OzError::Erc20Allowlist(e) => e.selector(),
+            OzError::Erc20BatchTransfer(e) => e.selector(),
+            OzError::Erc20SendHooks(e) => e.selector(),
+            OzError::Erc20Mintable(e) => e.selector(),
+            OzError::Erc20TemporaryApproval(e) => e.selector(),
+            OzError::Erc20ApproveBySig(e) => e.selector(),
+            OzError::Erc20BalancesRoot(e) => e.selector(),
+            OzError::Erc20Blocklist(e) => e.selector(),
+            OzError::Erc20Cap(e) => e.selector(),
+            OzError::Erc20Crosschain(e) => e.selector(),
+            OzError::Erc20Fee(e) => e.selector(),
+            OzError::Erc20Gasback(e) => e.selector(),
+            OzError::Erc20GlobalSettlement(e) => e.selector(),
+            OzError::Erc20InterestBearing(e) => e.selector(),
+            OzError::Erc20MetadataStorage(e) => e.selector(),
+            OzError::Erc20MintAuthorization(e) => e.selector(),
+            OzError::Erc20RateLimit(e) => e.selector(),
+            OzError::Erc20Redeemable(e) => e.selector(),
+            OzError::Erc20SignatureTransfer(e) => e.selector(),
+            OzError::Erc20SpenderCaps(e) => e.selector(),
+            OzError::Erc20Stream(e) => e.selector(),
+            OzError::Erc20TransferCooldown(e) => e.selector(),
+            OzError::Erc20Votes(e) => e.selector(),
+            OzError::Erc20Wrapper(e) => e.selector(),
+            OzError::Erc2981(e) => e.selector(),
+            OzError::Erc4626(e) => e.selector(),
+            OzError::Erc721Permit(e) => e.selector(),
+            OzError::NftReceiver(e) => e.selector(),
+            OzError::CommitRevealSale(e) => e.selector(),
+            OzError::RefundEscrow(e) => e.selector(),
+            OzError::TokenMigrator(e) => e.selector(),
+            OzError::Governor(e) => e.selector(),
+            OzError::Timelock(e) => e.selector(),
+            OzError::Erc1967(e) => e.selector(),
+            OzError::UupsUpgradeable(e) => e.selector(),
+            OzError::BoundedString(e) => e.selector(),
+            OzError::Checkpoints(e) => e.selector(),
+            OzError::Create2(e) => e.selector(),
+            OzError::Ecdsa(e) => e.selector(),
+            OzError::Erc2771Context(e) => e.selector(),
+            OzError::Initializable(e) => e.selector(),
+            OzError::Math(e) => e.selector(),
+            OzError::MerkleProof(e) => e.selector(),
+            OzError::Nonces(e) => e.selector(),
+            OzError::Pausable(e) => e.selector(),
+            OzError::ReentrancyGuard(e) => e.selector(),
+            OzError::ReplayGuard(e) => e.selector(),
+            OzError::Rescue(e) => e.selector(),
+            OzError::SafeErc20(e) => e.selector(),
+            OzError::Revert(data) => data_selector(data),
+        }
+    }
+
+    /// Name of the wrapped error variant.
+    pub fn name(&self) -> &'static str {
+        match self {
+            OzError::Erc1155(e) => e.name(),
+            OzError::Erc20(e) => e.name(),
+            OzError::Erc721(e) => e.name(),
+            OzError::AccessControl(e) => e.name(),
+            OzError::MultisigAdmin(e) => e.name(),
+            OzError::Ownable(e) => e.name(),
+            OzError::Erc20Allowlist(e) => e.name(),
+            OzError::Erc20BatchTransfer(e) => e.name(),
+            OzError::Erc20SendHooks(e) => e.name(),
+            OzError::Erc20Mintable(e) => e.name(),
+            OzError::Erc20TemporaryApproval(e) => e.name(),
+            OzError::Erc20ApproveBySig(e) => e.name(),
+            OzError::Erc20BalancesRoot(e) => e.name(),
+            OzError::Erc20Blocklist(e) => e.name(),
+            OzError::Erc20Cap(e) => e.name(),
+            OzError::Erc20Crosschain(e) => e.name(),
+            OzError::Erc20Fee(e) => e.name(),
+            OzError::Erc20Gasback(e) => e.name(),
+            OzError::Erc20GlobalSettlement(e) => e.name(),
+            OzError::Erc20InterestBearing(e) => e.name(),
+            OzError::Erc20MetadataStorage(e) => e.name(),
+            OzError::Erc20MintAuthorization(e) => e.name(),
+            OzError::Erc20RateLimit(e) => e.name(),
+            OzError::Erc20Redeemable(e) => e.name(),
+            OzError::Erc20SignatureTransfer(e) => e.name(),
+            OzError::Erc20SpenderCaps(e) => e.name(),
+            OzError::Erc20Stream(e) => e.name(),
+            OzError::Erc20TransferCooldown(e) => e.name(),
+            OzError::Erc20Votes(e) => e.name(),
+            OzError::Erc20Wrapper(e) => e.name(),
+            OzError::Erc2981(e) => e.name(),
+            OzError::Erc4626(e) => e.name(),
+            OzError::Erc721Permit(e) => e.name(),
+            OzError::NftReceiver(e) => e.name(),
+            OzError::CommitRevealSale(e) => e.name(),
+            OzError::RefundEscrow(e) => e.name(),
+            OzError::TokenMigrator(e) => e.name(),
+            OzError::Governor(e) => e.name(),
+            OzError::Timelock(e) => e.name(),
+            OzError::Erc1967(e) => e.name(),
+            OzError::UupsUpgradeable(e) => e.name(),
+            OzError::BoundedString(e) => e.name(),
+            OzError::Checkpoints(e) => e.name(),
+            OzError::Create2(e) => e.name(),
+            OzError::Ecdsa(e) => e.name(),
+            OzError::Erc2771Context(e) => e.name(),
+            OzError::Initializable(e) => e.name(),
+            OzError::Math(e) => e.name(),
+            OzError::MerkleProof(e) => e.name(),
+            OzError::Nonces(e) => e.name(),
+            OzError::Pausable(e) => e.name(),
+            OzError::ReentrancyGuard(e) => e.name(),
+            OzError::ReplayGuard(e) => e.name(),
+            OzError::Rescue(e) => e.name(),
+            OzError::SafeErc20(e) => e.name(),
+            OzError::Revert(_) => "Revert",
+        }
+    }
+}
+
+impl fmt::Debug for OzError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OzError::Erc1155(e) => fmt::Debug::fmt(e, f),
+            OzError::Erc20(e) => fmt::Debug::fmt(e, f),
+            OzError::Erc721(e) => fmt::Debug::fmt(e, f),
+            OzError::AccessControl(e) => fmt::Debug::fmt(e, f),
+            OzError::MultisigAdmin(e) => fmt::Debug::fmt(e, f),
+            OzError::Ownable(e) => fmt::Debug::fmt(e, f),
+            OzError::Erc20Allowlist(e) => fmt::Debug::fmt(e, f),
+            OzError::Erc20BatchTransfer(e) => fmt::Debug::fmt(e, f),
+            OzError::Erc20SendHooks(e) => fmt::Debug::fmt(e, f),
+            OzError::Erc20Mintable(e) => fmt::Debug::fmt(e, f),
+            OzError::Erc20TemporaryApproval(e) => fmt::Debug::fmt(e, f),
+            OzError::Erc20ApproveBySig(e) => fmt::Debug::fmt(e, f),
+            OzError::Erc20BalancesRoot(e) => fmt::Debug::fmt(e, f),
+            OzError::Erc20Blocklist(e) => fmt::Debug::fmt(e, f),
+            OzError::Erc20Cap(e) => fmt::Debug::fmt(e, f),
+            OzError::Erc20Crosschain(e) => fmt::Debug::fmt(e, f),
+            OzError::Erc20Fee(e) => fmt::Debug::fmt(e, f),
+            OzError::Erc20Gasback(e) => fmt::Debug::fmt(e, f),
+            OzError::Erc20GlobalSettlement(e) => fmt::Debug::fmt(e, f),
+            OzError::Erc20InterestBearing(e) => fmt::Debug::fmt(e, f),
+            OzError::Erc20MetadataStorage(e) => fmt::Debug::fmt(e, f),
+            OzError::Erc20MintAuthorization(e) => fmt::Debug::fmt(e, f),
+            OzError::Erc20RateLimit(e) => fmt::Debug::fmt(e, f),
+            OzError::Erc20Redeemable(e) => fmt::Debug::fmt(e, f),
+            OzError::Erc20SignatureTransfer(e) => fmt::Debug::fmt(e, f),
+            OzError::Erc20SpenderCaps(e) => fmt::Debug::fmt(e, f),
+            OzError::Erc20Stream(e) => fmt::Debug::fmt(e, f),
+            OzError::Erc20TransferCooldown(e) => fmt::Debug::fmt(e, f),
+            OzError::Erc20Votes(e) => fmt::Debug::fmt(e, f),
+            OzError::Erc20Wrapper(e) => fmt::Debug::fmt(e, f),
+            OzError::Erc2981(e) => fmt::Debug::fmt(e, f),
+            OzError::Erc4626(e) => fmt::Debug::fmt(e, f),
+            OzError::Erc721Permit(e) => fmt::Debug::fmt(e, f),
+            OzError::NftReceiver(e) => fmt::Debug::fmt(e, f),
+            OzError::CommitRevealSale(e) => fmt::Debug::fmt(e, f),
+            OzError::RefundEscrow(e) => fmt::Debug::fmt(e, f),
+            OzError::TokenMigrator(e) => fmt::Debug::fmt(e, f),
+            OzError::Governor(e) => fmt::Debug::fmt(e, f),
+            OzError::Timelock(e) => fmt::Debug::fmt(e, f),
+            OzError::Erc1967(e) => fmt::Debug::fmt(e, f),
+            OzError::UupsUpgradeable(e) => fmt::Debug::fmt(e, f),
+            OzError::BoundedString(e) => fmt::Debug::fmt(e, f),
+            OzError::Checkpoints(e) => fmt::Debug::fmt(e, f),
+            OzError::Create2(e) => fmt::Debug::fmt(e, f),
+            OzError::Ecdsa(e) => fmt::Debug::fmt(e, f),
+            OzError::Erc2771Context(e) => fmt::Debug::fmt(e, f),
+            OzError::Initializable(e) => fmt::Debug::fmt(e, f),
+            OzError::Math(e) => fmt::Debug::fmt(e, f),
+            OzError::MerkleProof(e) => fmt::Debug::fmt(e, f),
+            OzError::Nonces(e) => fmt::Debug::fmt(e, f),
+            OzError::Pausable(e) => fmt::Debug::fmt(e, f),
+            OzError::ReentrancyGuard(e) => fmt::Debug::fmt(e, f),
+            OzError::ReplayGuard(e) => fmt::Debug::fmt(e, f),
+            OzError::Rescue(e) => fmt::Debug::fmt(e, f),
+            OzError::SafeErc20(e) => fmt::Debug::fmt(e, f),
+            OzError::Revert(data) => f.debug_tuple("Revert").field(&Hex(data)).finish(),
+        }
+    }
+}
+
+impl fmt::Display for OzError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OzError::Erc1155(e) => fmt::Display::fmt(e, f),
+            OzError::Erc20(e) => fmt::Display::fmt(e, f),
+            OzError::Erc721(e) => fmt::Display::fmt(e, f),
+            OzError::AccessControl(e) => fmt::Display::fmt(e, f),
+            OzError::MultisigAdmin(e) => fmt::Display::fmt(e, f),
+            OzError::Ownable(e) => fmt::Display::fmt(e, f),
+            OzError::Erc20Allowlist(e) => fmt::Display::fmt(e, f),
+            OzError::Erc20BatchTransfer(e) => fmt::Display::fmt(e, f),
+            OzError::Erc20SendHooks(e) => fmt::Display::fmt(e, f),
+            OzError::Erc20Mintable(e) => fmt::Display::fmt(e, f),
+            OzError::Erc20TemporaryApproval(e) => fmt::Display::fmt(e, f),
+            OzError::Erc20ApproveBySig(e) => fmt::Display::fmt(e, f),
+            OzError::Erc20BalancesRoot(e) => fmt::Display::fmt(e, f),
+            OzError::Erc20Blocklist(e) => fmt::Display::fmt(e, f),
+            OzError::Erc20Cap(e) => fmt::Display::fmt(e, f),
+            OzError::Erc20Crosschain(e) => fmt::Display::fmt(e, f),
+            OzError::Erc20Fee(e) => fmt::Display::fmt(e, f),
+            OzError::Erc20Gasback(e) => fmt::Display::fmt(e, f),
+            OzError::Erc20GlobalSettlement(e) => fmt::Display::fmt(e, f),
+            OzError::Erc20InterestBearing(e) => fmt::Display::fmt(e, f),
+            OzError::Erc20MetadataStorage(e) => fmt::Display::fmt(e, f),
+            OzError::Erc20MintAuthorization(e) => fmt::Display::fmt(e, f),
+            OzError::Erc20RateLimit(e) => fmt::Display::fmt(e, f),
+            OzError::Erc20Redeemable(e) => fmt::Display::fmt(e, f),
+            OzError::Erc20SignatureTransfer(e) => fmt::Display::fmt(e, f),
+            OzError::Erc20SpenderCaps(e) => fmt::Display::fmt(e, f),
+            OzError::Erc20Stream(e) => fmt::Display::fmt(e, f),
+            OzError::Erc20TransferCooldown(e) => fmt::Display::fmt(e, f),
+            OzError::Erc20Votes(e) => fmt::Display::fmt(e, f),
+            OzError::Erc20Wrapper(e) => fmt::Display::fmt(e, f),
+            OzError::Erc2981(e) => fmt::Display::fmt(e, f),
+            OzError::Erc4626(e) => fmt::Display::fmt(e, f),
+            OzError::Erc721Permit(e) => fmt::Display::fmt(e, f),
+            OzError::NftReceiver(e) => fmt::Display::fmt(e, f),
+            OzError::CommitRevealSale(e) => fmt::Display::fmt(e, f),
+            OzError::RefundEscrow(e) => fmt::Display::fmt(e, f),
+            OzError::TokenMigrator(e) => fmt::Display::fmt(e, f),
+            OzError::Governor(e) => fmt::Display::fmt(e, f),
+            OzError::Timelock(e) => fmt::Display::fmt(e, f),
+            OzError::Erc1967(e) => fmt::Display::fmt(e, f),
+            OzError::UupsUpgradeable(e) => fmt::Display::fmt(e, f),
+            OzError::BoundedString(e) => fmt::Display::fmt(e, f),
+            OzError::Checkpoints(e) => fmt::Display::fmt(e, f),
+            OzError::Create2(e) => fmt::Display::fmt(e, f),
+            OzError::Ecdsa(e) => fmt::Display::fmt(e, f),
+            OzError::Erc2771Context(e) => fmt::Display::fmt(e, f),
+            OzError::Initializable(e) => fmt::Display::fmt(e, f),
+            OzError::Math(e) => fmt::Display::fmt(e, f),
+            OzError::MerkleProof(e) => fmt::Display::fmt(e, f),
+            OzError::Nonces(e) => fmt::Display::fmt(e, f),
+            OzError::Pausable(e) => fmt::Display::fmt(e, f),
+            OzError::ReentrancyGuard(e) => fmt::Display::fmt(e, f),
+            OzError::ReplayGuard(e) => fmt::Display::fmt(e, f),
+            OzError::Rescue(e) => fmt::Display::fmt(e, f),
+            OzError::SafeErc20(e) => fmt::Display::fmt(e, f),
+            OzError::Revert(data) => write!(f, "Revert ({})", Hex(&data_selector(data))),
+        }
+    }
+}
+
+/// Selectors of the errors reverted by the token bases, for tests and off-chain tools matching
+/// revert data instead of hardcoding hex strings.
+pub mod selectors {
+    use stylus_sdk::alloy_sol_types::SolError;
+
+    use crate::access::ownable::{OwnableInvalidOwner, OwnableUnauthorizedAccount};
+    use crate::tokens::erc1155::{Erc1155InsufficientBalance, Erc1155InvalidReceiver, Erc1155MissingApprovalForAll};
+    use crate::tokens::erc20::{
+        Erc20AllowanceOverflow, Erc20ArithmeticOverflow, Erc20FailedDecreaseAllowance, Erc20InsufficientAllowance,
+        Erc20InsufficientBalance, Erc20InvalidApprover, Erc20InvalidReceiver, Erc20InvalidSpender,
+    };
+    use crate::tokens::erc721::{Erc721InsufficientApproval, Erc721InvalidReceiver, Erc721NonexistentToken};
+
+    pub const ERC20_INSUFFICIENT_BALANCE: [u8; 4] = Erc20InsufficientBalance::SELECTOR;
+    pub const ERC20_INSUFFICIENT_ALLOWANCE: [u8; 4] = Erc20InsufficientAllowance::SELECTOR;
+    pub const ERC20_INVALID_APPROVER: [u8; 4] = Erc20InvalidApprover::SELECTOR;
+    pub const ERC20_INVALID_SPENDER: [u8; 4] = Erc20InvalidSpender::SELECTOR;
+    pub const ERC20_INVALID_RECEIVER: [u8; 4] = Erc20InvalidReceiver::SELECTOR;
+    pub const ERC20_FAILED_DECREASE_ALLOWANCE: [u8; 4] = Erc20FailedDecreaseAllowance::SELECTOR;
+    pub const ERC20_ALLOWANCE_OVERFLOW: [u8; 4] = Erc20AllowanceOverflow::SELECTOR;
+    pub const ERC20_ARITHMETIC_OVERFLOW: [u8; 4] = Erc20ArithmeticOverflow::SELECTOR;
+
+    pub const ERC721_NONEXISTENT_TOKEN: [u8; 4] = Erc721NonexistentToken::SELECTOR;
+    pub const ERC721_INSUFFICIENT_APPROVAL: [u8; 4] = Erc721InsufficientApproval::SELECTOR;
+    pub const ERC721_INVALID_RECEIVER: [u8; 4] = Erc721InvalidReceiver::SELECTOR;
+
+    pub const ERC1155_INSUFFICIENT_BALANCE: [u8; 4] = Erc1155InsufficientBalance::SELECTOR;
+    pub const ERC1155_MISSING_APPROVAL_FOR_ALL: [u8; 4] = Erc1155MissingApprovalForAll::SELECTOR;
+    pub const ERC1155_INVALID_RECEIVER: [u8; 4] = Erc1155InvalidReceiver::SELECTOR;
+
+    pub const OWNABLE_UNAUTHORIZED_ACCOUNT: [u8; 4] = OwnableUnauthorizedAccount::SELECTOR;
+    pub const OWNABLE_INVALID_OWNER: [u8; 4] = OwnableInvalidOwner::SELECTOR;
+}
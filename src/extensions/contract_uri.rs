@@ -0,0 +1,43 @@
+use alloc::{string::String, vec::Vec};
+use stylus_sdk::{alloy_sol_types::sol, evm, prelude::*};
+
+use crate::utils::bounded_string::{BoundedString, BoundedStringError};
+
+/// Max length of the contract URI, enough for an IPFS/Arweave URI or a data URI of small JSON.
+pub const MAX_CONTRACT_URI_LENGTH: usize = 512;
+
+sol_storage! {
+    /// ERC-7572 `contractURI()`: a URI to JSON metadata describing the contract (name, image,
+    /// description...) read by marketplaces and token lists, for ERC20 tokens and collections alike.
+    ///
+    /// The setter is internal, the composing contract exposes it behind its own authorization
+    /// (e.g. `Ownable`). See `examples/compose_erc721.rs` and `examples/compose_votes.rs`.
+    pub struct ContractUri {
+        BoundedString<MAX_CONTRACT_URI_LENGTH> uri;
+    }
+}
+
+sol! {
+    /// Signals offchain readers to fetch the contract metadata again.
+    event ContractURIUpdated();
+}
+
+impl ContractUri {
+    /// Sets the contract metadata URI, at most `MAX_CONTRACT_URI_LENGTH` bytes.
+    ///
+    /// Emits a {ContractURIUpdated} event.
+    pub fn set_contract_uri(&mut self, uri: &str) -> Result<(), BoundedStringError> {
+        self.uri.set_str(uri)?;
+        evm::log(ContractURIUpdated {});
+        Ok(())
+    }
+}
+
+#[external]
+impl ContractUri {
+    /// Returns the contract metadata URI, empty if not set.
+    #[selector(name = "contractURI")]
+    pub fn contract_uri(&self) -> Result<String, Vec<u8>> {
+        Ok(self.uri.get_string())
+    }
+}
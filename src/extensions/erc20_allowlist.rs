@@ -0,0 +1,126 @@
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::Address,
+    alloy_sol_types::{sol, SolError},
+    evm,
+    prelude::*,
+};
+
+use crate::access::access_control::{role_id, AccessControl};
+
+/// Name of the role adding and removing allowlisted accounts, see `role_id`.
+pub const ALLOWLIST_ROLE: &str = "ALLOWLIST_ROLE";
+
+sol_storage! {
+    /// Restricted transfers of a permissioned token (securities style): only allowlisted
+    /// accounts send and receive tokens. The composing token calls `when_allowed(from, to)` at the
+    /// start of every external moving balances, before `Erc20::update`. The zero address is
+    /// always allowed, so mints and burns only check the holder. Allowances are left alone, a
+    /// spender needs no allowlisting since tokens only move between allowlisted holders.
+    ///
+    /// `allow_accounts`/`disallow_accounts` take batches (onboarding an investor list in one
+    /// transaction) and require `ALLOWLIST_ROLE` of the token's `AccessControl`; the `_internal`
+    /// variants skip the check for the composing token.
+    /// See `examples/compose_allowlist.rs` for a complete composition.
+    pub struct Erc20Allowlist {
+        mapping(address => bool) allowed;
+    }
+}
+
+sol! {
+    event AccountAllowed(address indexed account);
+    event AccountDisallowed(address indexed account);
+
+    /// Indicates an operation involving `account`, which is not allowlisted.
+    error Erc20AccountNotAllowed(address account);
+}
+
+pub enum Erc20AllowlistError {
+    Erc20AccountNotAllowed(Erc20AccountNotAllowed),
+}
+
+impl From<Erc20AllowlistError> for Vec<u8> {
+    fn from(e: Erc20AllowlistError) -> Vec<u8> {
+        match e {
+            Erc20AllowlistError::Erc20AccountNotAllowed(e) => e.encode(),
+        }
+    }
+}
+
+crate::errors::error_helpers!(Erc20AllowlistError {
+    Erc20AccountNotAllowed,
+});
+
+impl Erc20Allowlist {
+    /// Reverts with `Erc20AccountNotAllowed` unless both `from` and `to` are allowed.
+    pub fn when_allowed(&self, from: Address, to: Address) -> Result<(), Erc20AllowlistError> {
+        self.check_allowed(from)?;
+        self.check_allowed(to)
+    }
+
+    /// Reverts with `Erc20AccountNotAllowed` unless `account` is allowlisted or the zero address.
+    pub fn check_allowed(&self, account: Address) -> Result<(), Erc20AllowlistError> {
+        if account != Address::ZERO && !self.allowed.get(account) {
+            return Err(Erc20AllowlistError::Erc20AccountNotAllowed(Erc20AccountNotAllowed { account }));
+        }
+        Ok(())
+    }
+
+    /// Allowlists `accounts`, the caller needs `ALLOWLIST_ROLE`. Returns how many were added.
+    ///
+    /// Emits an {AccountAllowed} event per account not allowlisted yet.
+    pub fn allow_accounts(&mut self, access: &AccessControl, accounts: &[Address]) -> Result<u32, Vec<u8>> {
+        access.only_role(role_id(ALLOWLIST_ROLE))?;
+        Ok(self.allow_accounts_internal(accounts))
+    }
+
+    /// Removes `accounts` from the allowlist, the caller needs `ALLOWLIST_ROLE`. Returns how
+    /// many were removed. Their tokens stay frozen until they are allowlisted again.
+    ///
+    /// Emits an {AccountDisallowed} event per account allowlisted.
+    pub fn disallow_accounts(&mut self, access: &AccessControl, accounts: &[Address]) -> Result<u32, Vec<u8>> {
+        access.only_role(role_id(ALLOWLIST_ROLE))?;
+        Ok(self.disallow_accounts_internal(accounts))
+    }
+
+    /// Allowlists `accounts` without authorization, returns how many were added.
+    ///
+    /// Emits an {AccountAllowed} event per account not allowlisted yet.
+    pub fn allow_accounts_internal(&mut self, accounts: &[Address]) -> u32 {
+        let mut added = 0;
+        for &account in accounts {
+            if account == Address::ZERO || self.allowed.get(account) {
+                continue;
+            }
+            self.allowed.insert(account, true);
+            evm::log(AccountAllowed { account });
+            added += 1;
+        }
+        added
+    }
+
+    /// Removes `accounts` from the allowlist without authorization, returns how many were removed.
+    ///
+    /// Emits an {AccountDisallowed} event per account allowlisted.
+    pub fn disallow_accounts_internal(&mut self, accounts: &[Address]) -> u32 {
+        let mut removed = 0;
+        for &account in accounts {
+            if !self.allowed.get(account) {
+                continue;
+            }
+            self.allowed.insert(account, false);
+            evm::log(AccountDisallowed { account });
+            removed += 1;
+        }
+        removed
+    }
+}
+
+#[external]
+impl Erc20Allowlist {
+    /// Returns true if `account` is allowlisted.
+    #[selector(name = "isAllowed")]
+    pub fn is_allowed(&self, account: Address) -> Result<bool, Erc20AllowlistError> {
+        Ok(self.allowed.get(account))
+    }
+}
@@ -0,0 +1,109 @@
+//! Approvals on behalf of an owner with its signature, for smart wallet batching flows. Unlike
+//! an EIP-2612 permit the nonce is unordered, signatures can be submitted in any order, and
+//! contract owners sign through EIP-1271 `isValidSignature`.
+//!
+//! The function takes the token's `Erc20` and shared `ReplayGuard`, expose it from the composing
+//! token e.g. `erc20_approve_by_sig::approve_by_sig(&mut self.erc20, &mut self.replay_guard, ...)`.
+//! See `examples/compose_approve_by_sig.rs` for a complete composition.
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{Address, B256, U256},
+    alloy_sol_types::{sol, SolError},
+    block,
+    crypto::keccak,
+};
+
+use crate::tokens::erc20::{Erc20, Erc20Params};
+use crate::utils::{
+    eip712::{self, address_word, hash_words, u256_word},
+    replay_guard::{NonceMode, ReplayGuard},
+    signature_checker,
+};
+
+/// EIP-712 version of the approve by signature domain, the shared token domain of `utils::eip712`.
+pub const APPROVE_BY_SIG_VERSION: &str = eip712::DOMAIN_VERSION;
+
+pub const APPROVE_BY_SIG_TYPE: &str =
+    "ApproveBySig(address owner,address spender,uint256 value,uint256 nonce,uint256 deadline)";
+
+/// Approvals by signature use unordered nonces, any unused nonce is valid.
+pub const NONCE_MODE: NonceMode = NonceMode::Unordered;
+
+sol! {
+    /// Indicates that the approval signature deadline has passed.
+    error Erc20ApproveBySigExpired(uint256 deadline);
+
+    /// Indicates that the signature is not valid for `owner`.
+    error Erc20ApproveBySigInvalidSignature(address owner);
+}
+
+pub enum Erc20ApproveBySigError {
+    Erc20ApproveBySigExpired(Erc20ApproveBySigExpired),
+    Erc20ApproveBySigInvalidSignature(Erc20ApproveBySigInvalidSignature),
+}
+
+impl From<Erc20ApproveBySigError> for Vec<u8> {
+    fn from(e: Erc20ApproveBySigError) -> Vec<u8> {
+        match e {
+            Erc20ApproveBySigError::Erc20ApproveBySigExpired(e) => e.encode(),
+            Erc20ApproveBySigError::Erc20ApproveBySigInvalidSignature(e) => e.encode(),
+        }
+    }
+}
+
+crate::errors::error_helpers!(Erc20ApproveBySigError {
+    Erc20ApproveBySigExpired,
+    Erc20ApproveBySigInvalidSignature,
+});
+
+/// Sets `value` as the allowance of `spender` over `owner`'s tokens, signed by `owner`.
+///
+/// Emits an {Approval} event.
+#[allow(clippy::too_many_arguments)]
+pub fn approve_by_sig<T: Erc20Params>(
+    erc20: &mut Erc20<T>,
+    replay_guard: &mut ReplayGuard,
+    owner: Address,
+    spender: Address,
+    value: U256,
+    nonce: U256,
+    deadline: U256,
+    signature: &[u8],
+) -> Result<(), Vec<u8>> {
+    if U256::from(block::timestamp()) > deadline {
+        return Err(Erc20ApproveBySigError::Erc20ApproveBySigExpired(
+            Erc20ApproveBySigExpired { deadline },
+        )
+        .into());
+    }
+    replay_guard.consume(NONCE_MODE, owner, nonce)?;
+
+    let struct_hash = approve_by_sig_hash(owner, spender, value, nonce, deadline);
+    let hash = eip712::to_typed_data_hash(domain_separator::<T>(), struct_hash);
+    if !signature_checker::is_valid_signature_now(owner, hash, signature) {
+        return Err(Erc20ApproveBySigError::Erc20ApproveBySigInvalidSignature(
+            Erc20ApproveBySigInvalidSignature { owner },
+        )
+        .into());
+    }
+
+    erc20.approve_internal(owner, spender, value)?;
+    Ok(())
+}
+
+/// EIP-712 struct hash of an `ApproveBySig`.
+pub fn approve_by_sig_hash(owner: Address, spender: Address, value: U256, nonce: U256, deadline: U256) -> B256 {
+    hash_words(&[
+        keccak(APPROVE_BY_SIG_TYPE.as_bytes()),
+        address_word(owner),
+        address_word(spender),
+        u256_word(value),
+        u256_word(nonce),
+        u256_word(deadline),
+    ])
+}
+
+/// Domain separator of the token, see `utils::eip712` for the shared domain.
+pub fn domain_separator<T: Erc20Params>() -> B256 {
+    eip712::erc20_domain_separator::<T>()
+}
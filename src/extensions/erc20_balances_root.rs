@@ -0,0 +1,129 @@
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{Address, B256, U256},
+    alloy_sol_types::{sol, SolError},
+    block,
+    crypto::keccak,
+    evm, msg,
+    prelude::*,
+};
+
+use crate::utils::eip712::{address_word, hash_words, u256_word};
+use crate::utils::merkle_proof;
+
+sol_storage! {
+    /// Proof-of-reserves style attestations: an admin commits the merkle root of all balances at a
+    /// past block, computed off-chain from chain state (see `tests/util/balances_root.rs`). Auditors
+    /// recompute the root at the same block, holders prove their balance against it.
+    ///
+    /// Leaves use the OpenZeppelin `StandardMerkleTree` leaf encoding (see {balance_leaf}), sorted
+    /// ascending, paired with sorted pair hashing and an odd node carried up a level, so proofs
+    /// verify with the usual `MerkleProof.verify` (`utils::merkle_proof::verify`, or `verifyBalance`). `commit_balances_root` is internal, the composing
+    /// token exposes it behind its own authorization.
+    /// See `examples/compose_balances_root.rs` for a complete composition.
+    pub struct Erc20BalancesRoot {
+        /// block number => committed root
+        mapping(uint256 => bytes32) roots;
+        /// block number of the latest committed root
+        uint256 latest_block;
+    }
+}
+
+sol! {
+    event BalancesRootCommitted(bytes32 indexed root, uint256 indexed block_number, address committer);
+
+    /// Indicates a root committed for a block that is not past or not after the latest committed one.
+    /// * `block_number` - block of the root.
+    /// * `latest_block` - block of the latest committed root.
+    error Erc20InvalidBalancesRootBlock(uint256 block_number, uint256 latest_block);
+
+    /// Indicates a zero root.
+    error Erc20InvalidBalancesRoot();
+}
+
+pub enum Erc20BalancesRootError {
+    Erc20InvalidBalancesRootBlock(Erc20InvalidBalancesRootBlock),
+    Erc20InvalidBalancesRoot(Erc20InvalidBalancesRoot),
+}
+
+impl From<Erc20BalancesRootError> for Vec<u8> {
+    fn from(e: Erc20BalancesRootError) -> Vec<u8> {
+        match e {
+            Erc20BalancesRootError::Erc20InvalidBalancesRootBlock(e) => e.encode(),
+            Erc20BalancesRootError::Erc20InvalidBalancesRoot(e) => e.encode(),
+        }
+    }
+}
+
+crate::errors::error_helpers!(Erc20BalancesRootError {
+    Erc20InvalidBalancesRootBlock,
+    Erc20InvalidBalancesRoot,
+});
+
+/// Leaf of `account` holding `balance`: `keccak256(bytes.concat(keccak256(abi.encode(account, balance))))`.
+pub fn balance_leaf(account: Address, balance: U256) -> B256 {
+    keccak(hash_words(&[address_word(account), u256_word(balance)]))
+}
+
+impl Erc20BalancesRoot {
+    /// Commits the balances `root` at `block_number`, which must be a past block after the block
+    /// of the latest committed root.
+    ///
+    /// Emits a {BalancesRootCommitted} event.
+    pub fn commit_balances_root(&mut self, root: B256, block_number: U256) -> Result<(), Erc20BalancesRootError> {
+        if root == B256::ZERO {
+            return Err(Erc20BalancesRootError::Erc20InvalidBalancesRoot(
+                Erc20InvalidBalancesRoot {},
+            ));
+        }
+        let latest_block = self.latest_block.get();
+        let has_root = self.roots.get(latest_block) != B256::ZERO;
+        if block_number >= U256::from(block::number()) || (has_root && block_number <= latest_block) {
+            return Err(Erc20BalancesRootError::Erc20InvalidBalancesRootBlock(
+                Erc20InvalidBalancesRootBlock {
+                    block_number,
+                    latest_block,
+                },
+            ));
+        }
+        self.roots.insert(block_number, root);
+        self.latest_block.set(block_number);
+
+        evm::log(BalancesRootCommitted {
+            root,
+            block_number,
+            committer: msg::sender(),
+        });
+        Ok(())
+    }
+}
+
+#[external]
+impl Erc20BalancesRoot {
+    /// Returns `(root, block_number)` of the latest commitment, zero if none.
+    #[selector(name = "latestBalancesRoot")]
+    pub fn latest_balances_root(&self) -> Result<(B256, U256), Erc20BalancesRootError> {
+        let latest_block = self.latest_block.get();
+        Ok((self.roots.get(latest_block), latest_block))
+    }
+
+    /// Returns the root committed at `block_number`, zero if none.
+    #[selector(name = "balancesRootAt")]
+    pub fn balances_root_at(&self, block_number: U256) -> Result<B256, Erc20BalancesRootError> {
+        Ok(self.roots.get(block_number))
+    }
+
+    /// Returns whether `proof` proves that `account` held `balance` in the root committed at
+    /// `block_number`, false if none was committed.
+    #[selector(name = "verifyBalance")]
+    pub fn verify_balance(
+        &self,
+        block_number: U256,
+        account: Address,
+        balance: U256,
+        proof: Vec<B256>,
+    ) -> Result<bool, Erc20BalancesRootError> {
+        let root = self.roots.get(block_number);
+        Ok(root != B256::ZERO && merkle_proof::verify(&proof, root, balance_leaf(account, balance)))
+    }
+}
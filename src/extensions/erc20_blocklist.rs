@@ -0,0 +1,117 @@
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::Address,
+    alloy_sol_types::{sol, SolError},
+    evm,
+    prelude::*,
+};
+
+use crate::access::access_control::{role_id, AccessControl};
+
+/// Name of the role blocking and unblocking accounts, see `role_id`.
+pub const BLOCKLIST_ROLE: &str = "BLOCKLIST_ROLE";
+
+sol_storage! {
+    /// Sanctions list of a regulated token (stablecoin style): blocked accounts can neither send
+    /// nor receive tokens, nor approve spenders. The composing token calls
+    /// `when_not_blocked(from, to)` at the start of every external moving balances, before
+    /// `Erc20::update` (and `check_not_blocked` on the spender of `transferFrom` and `approve`).
+    /// Zero addresses are never blocked, so mints and burns only check the holder.
+    ///
+    /// `block_account`/`unblock_account` require `BLOCKLIST_ROLE` of the token's `AccessControl`;
+    /// the `_internal` variants skip the check for the composing token.
+    /// See `examples/compose_blocklist.rs` for a complete composition.
+    pub struct Erc20Blocklist {
+        mapping(address => bool) blocked;
+    }
+}
+
+sol! {
+    event AccountBlocked(address indexed account);
+    event AccountUnblocked(address indexed account);
+
+    /// Indicates an operation involving the blocked `account`.
+    error Erc20AccountBlocked(address account);
+}
+
+pub enum Erc20BlocklistError {
+    Erc20AccountBlocked(Erc20AccountBlocked),
+}
+
+impl From<Erc20BlocklistError> for Vec<u8> {
+    fn from(e: Erc20BlocklistError) -> Vec<u8> {
+        match e {
+            Erc20BlocklistError::Erc20AccountBlocked(e) => e.encode(),
+        }
+    }
+}
+
+crate::errors::error_helpers!(Erc20BlocklistError {
+    Erc20AccountBlocked,
+});
+
+impl Erc20Blocklist {
+    /// Reverts with `Erc20AccountBlocked` if `from` or `to` is blocked.
+    pub fn when_not_blocked(&self, from: Address, to: Address) -> Result<(), Erc20BlocklistError> {
+        self.check_not_blocked(from)?;
+        self.check_not_blocked(to)
+    }
+
+    /// Reverts with `Erc20AccountBlocked` if `account` is blocked.
+    pub fn check_not_blocked(&self, account: Address) -> Result<(), Erc20BlocklistError> {
+        if self.blocked.get(account) {
+            return Err(Erc20BlocklistError::Erc20AccountBlocked(Erc20AccountBlocked { account }));
+        }
+        Ok(())
+    }
+
+    /// Blocks `account`, the caller needs `BLOCKLIST_ROLE`.
+    ///
+    /// Emits an {AccountBlocked} event if `account` was not blocked.
+    pub fn block_account(&mut self, access: &AccessControl, account: Address) -> Result<bool, Vec<u8>> {
+        access.only_role(role_id(BLOCKLIST_ROLE))?;
+        Ok(self.block_account_internal(account))
+    }
+
+    /// Unblocks `account`, the caller needs `BLOCKLIST_ROLE`.
+    ///
+    /// Emits an {AccountUnblocked} event if `account` was blocked.
+    pub fn unblock_account(&mut self, access: &AccessControl, account: Address) -> Result<bool, Vec<u8>> {
+        access.only_role(role_id(BLOCKLIST_ROLE))?;
+        Ok(self.unblock_account_internal(account))
+    }
+
+    /// Blocks `account` without authorization, returns whether it changed. The zero address is
+    /// never blocked.
+    ///
+    /// Emits an {AccountBlocked} event if `account` was not blocked.
+    pub fn block_account_internal(&mut self, account: Address) -> bool {
+        if account == Address::ZERO || self.blocked.get(account) {
+            return false;
+        }
+        self.blocked.insert(account, true);
+        evm::log(AccountBlocked { account });
+        true
+    }
+
+    /// Unblocks `account` without authorization, returns whether it changed.
+    ///
+    /// Emits an {AccountUnblocked} event if `account` was blocked.
+    pub fn unblock_account_internal(&mut self, account: Address) -> bool {
+        if !self.blocked.get(account) {
+            return false;
+        }
+        self.blocked.insert(account, false);
+        evm::log(AccountUnblocked { account });
+        true
+    }
+}
+
+#[external]
+impl Erc20Blocklist {
+    /// Returns true if `account` is blocked.
+    #[selector(name = "isBlocked")]
+    pub fn is_blocked(&self, account: Address) -> Result<bool, Erc20BlocklistError> {
+        Ok(self.blocked.get(account))
+    }
+}
@@ -0,0 +1,157 @@
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    alloy_sol_types::{sol, SolError},
+    prelude::*,
+};
+
+use crate::tokens::erc20::{Erc20, Erc20Params};
+
+sol_storage! {
+    /// Cap on the total supply. Mints are validated before any state change with `check_mint`,
+    /// so a mint over the cap reverts without a balance write or a {Transfer} event.
+    ///
+    /// The composing token sets the cap once in `init` with `set_cap` and mints through
+    /// `checked_mint` instead of `Erc20::mint`. `set_cap` is internal and fails once a cap is
+    /// set, never expose it as an external; a cap known at build time is better compiled in with
+    /// `Erc20FixedCap`, which has no setter at all. The cap is only read by mints, never by
+    /// transfers, so it keeps a full `uint256` slot: packing it would save nothing on the hot path.
+    /// See `examples/compose_cap.rs` for a complete composition.
+    pub struct Erc20Cap {
+        /// maximum total supply, 0 until set
+        uint256 cap;
+    }
+}
+
+sol! {
+    /// Indicates that the total supply would exceed the cap.
+    /// * `increased_supply` - total supply after the mint, `type(uint256).max` on overflow.
+    /// * `cap` - maximum total supply.
+    error Erc20ExceededCap(uint256 increased_supply, uint256 cap);
+
+    /// Indicates a zero cap or a cap set twice.
+    error Erc20InvalidCap(uint256 cap);
+}
+
+pub enum Erc20CapError {
+    Erc20ExceededCap(Erc20ExceededCap),
+    Erc20InvalidCap(Erc20InvalidCap),
+}
+
+impl From<Erc20CapError> for Vec<u8> {
+    fn from(e: Erc20CapError) -> Vec<u8> {
+        match e {
+            Erc20CapError::Erc20ExceededCap(e) => e.encode(),
+            Erc20CapError::Erc20InvalidCap(e) => e.encode(),
+        }
+    }
+}
+
+crate::errors::error_helpers!(Erc20CapError {
+    Erc20ExceededCap,
+    Erc20InvalidCap,
+});
+
+impl Erc20Cap {
+    /// Sets the cap, only once and non zero.
+    pub fn set_cap(&mut self, cap: U256) -> Result<(), Erc20CapError> {
+        check_new_cap(self.cap.get(), cap)?;
+        self.cap.set(cap);
+        Ok(())
+    }
+
+    /// Validates that minting `value` on top of `total_supply` stays within the cap.
+    pub fn check_mint(&self, total_supply: U256, value: U256) -> Result<(), Erc20CapError> {
+        check_cap(self.cap.get(), total_supply, value)
+    }
+
+    /// Mints `value` tokens to `account` after checking the cap.
+    ///
+    /// Emits a {Transfer} event.
+    pub fn checked_mint<T: Erc20Params>(
+        &self,
+        erc20: &mut Erc20<T>,
+        account: Address,
+        value: U256,
+    ) -> Result<(), Vec<u8>> {
+        self.check_mint(erc20.total_supply_internal(), value)?;
+        erc20.mint(account, value)?;
+        Ok(())
+    }
+}
+
+#[external]
+impl Erc20Cap {
+    /// Returns the maximum total supply.
+    #[selector(name = "cap")]
+    pub fn cap(&self) -> Result<U256, Erc20CapError> {
+        Ok(self.cap.get())
+    }
+}
+
+/// Compile time cap of an `Erc20FixedCap`, in the smallest unit of the token.
+pub trait Erc20CapParams {
+    /// maximum total supply, non zero
+    const CAP: U256;
+}
+
+sol_storage! {
+    /// Cap on the total supply fixed at compile time by `P::CAP`: no storage, no setter and no
+    /// `init` step to forget or front-run. Same `check_mint`/`checked_mint` and `cap()` view as
+    /// `Erc20Cap`, compose one or the other.
+    /// See `examples/compose_cap.rs` for a complete composition.
+    pub struct Erc20FixedCap<P> {
+        PhantomData<P> phantom;
+    }
+}
+
+impl<P: Erc20CapParams> Erc20FixedCap<P> {
+    /// Validates that minting `value` on top of `total_supply` stays within `P::CAP`.
+    pub fn check_mint(&self, total_supply: U256, value: U256) -> Result<(), Erc20CapError> {
+        check_cap(P::CAP, total_supply, value)
+    }
+
+    /// Mints `value` tokens to `account` after checking the cap.
+    ///
+    /// Emits a {Transfer} event.
+    pub fn checked_mint<T: Erc20Params>(
+        &self,
+        erc20: &mut Erc20<T>,
+        account: Address,
+        value: U256,
+    ) -> Result<(), Vec<u8>> {
+        self.check_mint(erc20.total_supply_internal(), value)?;
+        erc20.mint(account, value)?;
+        Ok(())
+    }
+}
+
+#[external]
+impl<P: Erc20CapParams> Erc20FixedCap<P> {
+    /// Returns the maximum total supply.
+    #[selector(name = "cap")]
+    pub fn cap(&self) -> Result<U256, Erc20CapError> {
+        Ok(P::CAP)
+    }
+}
+
+/// Check of {Erc20Cap::set_cap} without storage: `Erc20InvalidCap` for a zero `cap`, or once
+/// `current` is set.
+pub fn check_new_cap(current: U256, cap: U256) -> Result<(), Erc20CapError> {
+    if cap == U256::ZERO || current != U256::ZERO {
+        return Err(Erc20CapError::Erc20InvalidCap(Erc20InvalidCap { cap }));
+    }
+    Ok(())
+}
+
+/// Cap check without storage: `total_supply + value` must not overflow nor exceed `cap`.
+pub fn check_cap(cap: U256, total_supply: U256, value: U256) -> Result<(), Erc20CapError> {
+    match total_supply.checked_add(value) {
+        Some(increased_supply) if increased_supply <= cap => Ok(()),
+        increased_supply => Err(Erc20CapError::Erc20ExceededCap(Erc20ExceededCap {
+            increased_supply: increased_supply.unwrap_or(U256::MAX),
+            cap,
+        })),
+    }
+}
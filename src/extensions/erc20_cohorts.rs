@@ -0,0 +1,123 @@
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{Address, B256, U256},
+    alloy_sol_types::sol,
+    crypto::keccak,
+    evm,
+    prelude::*,
+};
+
+use crate::tokens::erc20::{Erc20, Erc20Params};
+
+/// Cohort of untagged accounts, its balance is not tracked (it is the total supply minus the
+/// balances of every cohort).
+pub const NO_COHORT: B256 = B256::ZERO;
+
+/// Identifier of a cohort, `keccak256(name)` (e.g. `cohort_id("TEAM")`), as for roles.
+pub fn cohort_id(name: &str) -> B256 {
+    keccak(name.as_bytes())
+}
+
+sol_storage! {
+    /// Balances aggregated by cohort (team, investors, community...) for tokenomics reporting:
+    /// an admin tags accounts into cohorts and the total balance and member count of each cohort
+    /// are kept up to date on every transfer, readable on-chain at any block.
+    ///
+    /// Opt-in, the composing token calls `move_balance(from, to, value)` after every transfer,
+    /// mint and burn (the same `from`/`to` as `Erc20::update`) and exposes `set_cohort` behind
+    /// its own authorization. See `examples/compose_cohorts.rs` for a complete composition.
+    pub struct Erc20Cohorts {
+        mapping(address => bytes32) cohorts;
+        mapping(bytes32 => uint256) cohort_balances;
+        mapping(bytes32 => uint256) member_counts;
+    }
+}
+
+sol! {
+    event CohortAssigned(address indexed account, bytes32 indexed previous_cohort, bytes32 indexed cohort);
+}
+
+impl Erc20Cohorts {
+    /// Tags `account` into `cohort` (`NO_COHORT` to untag it), moving its balance along.
+    ///
+    /// Emits a {CohortAssigned} event.
+    pub fn set_cohort<T: Erc20Params>(&mut self, erc20: &Erc20<T>, account: Address, cohort: B256) -> Result<(), Vec<u8>> {
+        let previous_cohort = self.cohorts.get(account);
+        if previous_cohort == cohort {
+            return Ok(());
+        }
+        let balance = erc20.balance_of_internal(account);
+        if previous_cohort != NO_COHORT {
+            self.sub_balance(previous_cohort, balance);
+            let count = self.member_counts.get(previous_cohort);
+            self.member_counts.insert(previous_cohort, count - U256::from(1));
+        }
+        if cohort != NO_COHORT {
+            self.add_balance(cohort, balance);
+            let count = self.member_counts.get(cohort);
+            self.member_counts.insert(cohort, count + U256::from(1));
+        }
+        self.cohorts.insert(account, cohort);
+
+        evm::log(CohortAssigned {
+            account,
+            previous_cohort,
+            cohort,
+        });
+        Ok(())
+    }
+
+    /// Hook for every balance change, moves `value` between the cohorts of `from` and `to`.
+    /// Transfers inside a cohort cost no write.
+    pub fn move_balance(&mut self, from: Address, to: Address, value: U256) {
+        let from_cohort = self.cohorts.get(from);
+        let to_cohort = self.cohorts.get(to);
+        if from_cohort == to_cohort || value == U256::ZERO {
+            return;
+        }
+        // the zero address is never tagged, mints and burns only touch one cohort
+        if from_cohort != NO_COHORT {
+            self.sub_balance(from_cohort, value);
+        }
+        if to_cohort != NO_COHORT {
+            self.add_balance(to_cohort, value);
+        }
+    }
+
+    fn add_balance(&mut self, cohort: B256, value: U256) {
+        let mut balance_ref = self.cohort_balances.setter(cohort);
+        let balance = balance_ref.get();
+        // cohort balances are sums of account balances, bounded by the total supply
+        balance_ref.set(balance + value);
+    }
+
+    fn sub_balance(&mut self, cohort: B256, value: U256) {
+        let mut balance_ref = self.cohort_balances.setter(cohort);
+        let balance = balance_ref.get();
+        balance_ref.set(balance - value);
+    }
+}
+
+#[external]
+impl Erc20Cohorts {
+    /// Returns the cohort of `account`, `NO_COHORT` (zero) if untagged.
+    #[selector(name = "cohortOf")]
+    pub fn cohort_of(&self, account: Address) -> Result<B256, Vec<u8>> {
+        Ok(self.cohorts.get(account))
+    }
+
+    /// Returns `(balance, members)` of `cohort`: the sum of the balances of its accounts and
+    /// their number.
+    #[selector(name = "cohortTotals")]
+    pub fn cohort_totals(&self, cohort: B256) -> Result<(U256, U256), Vec<u8>> {
+        Ok((self.cohort_balances.get(cohort), self.member_counts.get(cohort)))
+    }
+
+    /// Same as {cohort_totals} for several cohorts, balances and member counts in `cohorts` order.
+    #[selector(name = "cohortsTotals")]
+    pub fn cohorts_totals(&self, cohorts: Vec<B256>) -> Result<(Vec<U256>, Vec<U256>), Vec<u8>> {
+        let balances = cohorts.iter().map(|cohort| self.cohort_balances.get(*cohort)).collect();
+        let members = cohorts.iter().map(|cohort| self.member_counts.get(*cohort)).collect();
+        Ok((balances, members))
+    }
+}
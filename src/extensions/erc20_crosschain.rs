@@ -0,0 +1,128 @@
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{Address, FixedBytes, U256},
+    alloy_sol_types::{sol, SolError},
+    evm, msg,
+    prelude::*,
+};
+
+use crate::tokens::erc20::{Erc20, Erc20Params};
+use crate::utils::erc165::{self, Erc165};
+
+sol_storage! {
+    /// ERC-7802 style crosschain mint/burn, callable only by the configured bridge (Superchain
+    /// token bridge, LayerZero OFT adapter, native bridge gateway...).
+    ///
+    /// Expose `crosschain_mint`/`crosschain_burn` from the composing token, passing its `Erc20`:
+    /// `self.crosschain.crosschain_mint(&mut self.erc20, to, amount)`, and override
+    /// `supportsInterface` to report ERC-7802 along with the `Erc20` interfaces.
+    /// See `examples/compose_crosschain.rs` for a complete composition.
+    pub struct Erc20Crosschain {
+        /// the only account allowed to mint and burn crosschain
+        address bridge;
+    }
+}
+
+sol! {
+    event CrosschainMint(address indexed to, uint256 amount, address indexed sender);
+    event CrosschainBurn(address indexed from, uint256 amount, address indexed sender);
+    event CrosschainBridgeUpdated(address indexed previous_bridge, address indexed new_bridge);
+
+    /// Indicates that `caller` is not the configured bridge.
+    error Erc20UnauthorizedBridge(address caller);
+}
+
+pub enum Erc20CrosschainError {
+    Erc20UnauthorizedBridge(Erc20UnauthorizedBridge),
+}
+
+impl From<Erc20CrosschainError> for Vec<u8> {
+    fn from(e: Erc20CrosschainError) -> Vec<u8> {
+        match e {
+            Erc20CrosschainError::Erc20UnauthorizedBridge(e) => e.encode(),
+        }
+    }
+}
+
+crate::errors::error_helpers!(Erc20CrosschainError {
+    Erc20UnauthorizedBridge,
+});
+
+impl Erc20Crosschain {
+    /// Sets the bridge allowed to mint and burn, the zero address disables crosschain transfers.
+    ///
+    /// Emits a {CrosschainBridgeUpdated} event.
+    pub fn set_bridge(&mut self, new_bridge: Address) {
+        let previous_bridge = self.bridge.get();
+        self.bridge.set(new_bridge);
+        evm::log(CrosschainBridgeUpdated {
+            previous_bridge,
+            new_bridge,
+        });
+    }
+
+    /// Reverts unless the caller is the configured bridge.
+    pub fn only_bridge(&self) -> Result<(), Erc20CrosschainError> {
+        let caller = msg::sender();
+        if caller != self.bridge.get() || caller == Address::ZERO {
+            return Err(Erc20CrosschainError::Erc20UnauthorizedBridge(
+                Erc20UnauthorizedBridge { caller },
+            ));
+        }
+        Ok(())
+    }
+
+    /// Mints `amount` tokens to `to` for a transfer coming from another chain.
+    ///
+    /// Emits a {Transfer} and a {CrosschainMint} event.
+    pub fn crosschain_mint<T: Erc20Params>(
+        &mut self,
+        erc20: &mut Erc20<T>,
+        to: Address,
+        amount: U256,
+    ) -> Result<(), Vec<u8>> {
+        self.only_bridge()?;
+        erc20.mint(to, amount)?;
+        evm::log(CrosschainMint {
+            to,
+            amount,
+            sender: msg::sender(),
+        });
+        Ok(())
+    }
+
+    /// Burns `amount` tokens of `from` for a transfer leaving to another chain. The bridge is
+    /// trusted, no allowance of `from` is required.
+    ///
+    /// Emits a {Transfer} and a {CrosschainBurn} event.
+    pub fn crosschain_burn<T: Erc20Params>(
+        &mut self,
+        erc20: &mut Erc20<T>,
+        from: Address,
+        amount: U256,
+    ) -> Result<(), Vec<u8>> {
+        self.only_bridge()?;
+        erc20.burn(from, amount)?;
+        evm::log(CrosschainBurn {
+            from,
+            amount,
+            sender: msg::sender(),
+        });
+        Ok(())
+    }
+}
+
+/// No `supportsInterface` external, the composing token combines this impl with its base.
+impl Erc165 for Erc20Crosschain {
+    fn supports_interface(interface_id: FixedBytes<4>) -> bool {
+        erc165::supports(interface_id, &[erc165::IERC7802])
+    }
+}
+
+#[external]
+impl Erc20Crosschain {
+    #[selector(name = "crosschainBridge")]
+    pub fn crosschain_bridge(&self) -> Result<Address, Erc20CrosschainError> {
+        Ok(self.bridge.get())
+    }
+}
@@ -0,0 +1,153 @@
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    alloy_sol_types::{sol, SolError},
+    evm,
+    prelude::*,
+};
+
+use crate::extensions::fee_exemptions::FeeExemptions;
+use crate::tokens::erc20::{Erc20, Erc20Params};
+use crate::utils::math::{self, Rounding};
+
+/// Denominator of the fee, fees are in basis points.
+pub const FEE_DENOMINATOR: u64 = 10_000;
+/// Highest fee accepted by `set_fee`, 10%.
+pub const MAX_FEE_BPS: u64 = 1_000;
+
+sol_storage! {
+    /// Fee on transfer: a share of every transfer, in basis points, is routed to the fee
+    /// collector. Mints, burns, zero value and self transfers and transfers from or to an
+    /// account exempt in the shared `FeeExemptions` are not charged.
+    ///
+    /// The composing token moves tokens with `transfer_with_fee(&mut self.erc20,
+    /// &self.fee_exemptions, from, to, value)` instead of `transfer_internal` in `transfer` and
+    /// `transfer_from`; the allowance covers the full `value`. Setters are internal, the token
+    /// exposes them behind its own authorization (e.g. `Ownable`).
+    /// See `examples/compose_fee.rs` for a complete composition.
+    pub struct Erc20Fee {
+        /// fee in basis points of the transferred value, 0 disables the fee
+        uint256 fee_bps;
+        /// receiver of the fees
+        address fee_collector;
+    }
+}
+
+sol! {
+    event FeeUpdated(uint256 fee_bps);
+    event FeeCollectorUpdated(address indexed previous_collector, address indexed new_collector);
+    /// `fee` out of a transfer from `from` to `to` was paid to the fee collector.
+    event FeeCharged(address indexed from, address indexed to, uint256 fee);
+
+    /// Indicates a fee above `max_fee_bps`.
+    error Erc20FeeTooHigh(uint256 fee_bps, uint256 max_fee_bps);
+
+    /// Indicates the zero address as fee collector.
+    error Erc20InvalidFeeCollector(address collector);
+}
+
+pub enum Erc20FeeError {
+    Erc20FeeTooHigh(Erc20FeeTooHigh),
+    Erc20InvalidFeeCollector(Erc20InvalidFeeCollector),
+}
+
+impl From<Erc20FeeError> for Vec<u8> {
+    fn from(e: Erc20FeeError) -> Vec<u8> {
+        match e {
+            Erc20FeeError::Erc20FeeTooHigh(e) => e.encode(),
+            Erc20FeeError::Erc20InvalidFeeCollector(e) => e.encode(),
+        }
+    }
+}
+
+crate::errors::error_helpers!(Erc20FeeError {
+    Erc20FeeTooHigh,
+    Erc20InvalidFeeCollector,
+});
+
+impl Erc20Fee {
+    /// Sets the fee in basis points, at most `MAX_FEE_BPS`.
+    ///
+    /// Emits a {FeeUpdated} event.
+    pub fn set_fee(&mut self, fee_bps: U256) -> Result<(), Erc20FeeError> {
+        if fee_bps > U256::from(MAX_FEE_BPS) {
+            return Err(Erc20FeeError::Erc20FeeTooHigh(Erc20FeeTooHigh {
+                fee_bps,
+                max_fee_bps: U256::from(MAX_FEE_BPS),
+            }));
+        }
+        self.fee_bps.set(fee_bps);
+        evm::log(FeeUpdated { fee_bps });
+        Ok(())
+    }
+
+    /// Emits a {FeeCollectorUpdated} event.
+    pub fn set_fee_collector(&mut self, new_collector: Address) -> Result<(), Erc20FeeError> {
+        if new_collector == Address::ZERO {
+            return Err(Erc20FeeError::Erc20InvalidFeeCollector(Erc20InvalidFeeCollector {
+                collector: new_collector,
+            }));
+        }
+        let previous_collector = self.fee_collector.get();
+        self.fee_collector.set(new_collector);
+        evm::log(FeeCollectorUpdated {
+            previous_collector,
+            new_collector,
+        });
+        Ok(())
+    }
+
+    /// Moves `value` tokens out of `from`: `value - fee` to `to` and the fee to the collector,
+    /// returns the fee.
+    ///
+    /// Emits a {Transfer} event per receiver and a {FeeCharged} event when a fee is paid.
+    pub fn transfer_with_fee<T: Erc20Params>(
+        &self,
+        erc20: &mut Erc20<T>,
+        exemptions: &FeeExemptions,
+        from: Address,
+        to: Address,
+        value: U256,
+    ) -> Result<U256, Vec<u8>> {
+        let fee = if from == to || exemptions.is_exempt(from) || exemptions.is_exempt(to) {
+            U256::ZERO
+        } else {
+            self.fee_for(value)
+        };
+        erc20.transfer_internal(from, to, value - fee)?;
+        if fee != U256::ZERO {
+            erc20.transfer_internal(from, self.fee_collector.get(), fee)?;
+            evm::log(FeeCharged { from, to, fee });
+        }
+        Ok(fee)
+    }
+
+    /// Fee charged on a transfer of `value`, rounded down; 0 while no collector is set.
+    fn fee_for(&self, value: U256) -> U256 {
+        if self.fee_collector.get() == Address::ZERO {
+            return U256::ZERO;
+        }
+        fee_on(value, self.fee_bps.get())
+    }
+}
+
+#[external]
+impl Erc20Fee {
+    /// Returns `(fee_bps, fee_collector)`.
+    #[selector(name = "transferFee")]
+    pub fn transfer_fee(&self) -> Result<(U256, Address), Erc20FeeError> {
+        Ok((self.fee_bps.get(), self.fee_collector.get()))
+    }
+
+    /// Returns the fee charged on a non exempt transfer of `value`.
+    #[selector(name = "previewFee")]
+    pub fn preview_fee(&self, value: U256) -> Result<U256, Erc20FeeError> {
+        Ok(self.fee_for(value))
+    }
+}
+
+/// Fee of `fee_bps` basis points on `value`, rounded down: nothing on a zero value transfer.
+pub fn fee_on(value: U256, fee_bps: U256) -> U256 {
+    // fee_bps <= MAX_FEE_BPS < FEE_DENOMINATOR, the fee is below `value` and can not overflow
+    math::mul_div(value, fee_bps, U256::from(FEE_DENOMINATOR), Rounding::Down).unwrap_or(U256::ZERO)
+}
@@ -0,0 +1,160 @@
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    alloy_sol_types::{sol, SolError},
+    evm, msg,
+    prelude::*,
+};
+
+use crate::tokens::erc20::{Erc20, Erc20Params};
+
+sol_storage! {
+    /// Gasback accounting: calls made by registered partner contracts are metered and accrue
+    /// credits, `gas_used * credit_per_gas` token units capped by `max_credit_per_call`, which the
+    /// partner claims later as freshly minted tokens. Lets chains or apps offering gas rebates
+    /// reimburse integrators at the token layer.
+    ///
+    /// The composing token reads `evm::gas_left()` at the start of each metered external and calls
+    /// `accrue(gas_start)` at the end, exposes `claim_gasback` to everyone and the configuration
+    /// setters behind its own authorization (e.g. `Ownable`).
+    /// See `examples/compose_gasback.rs` for a complete composition.
+    pub struct Erc20Gasback {
+        mapping(address => bool) partners;
+        /// token units credited per unit of gas used
+        uint256 credit_per_gas;
+        /// upper bound of the credit accrued by a single call, 0 for no bound
+        uint256 max_credit_per_call;
+        /// credits accrued and not claimed yet
+        mapping(address => uint256) credits;
+        uint256 total_credits;
+    }
+}
+
+sol! {
+    event GasbackPartnerUpdated(address indexed partner, bool registered);
+    event GasbackRateUpdated(uint256 credit_per_gas, uint256 max_credit_per_call);
+    event GasbackAccrued(address indexed partner, uint256 gas_used, uint256 credit);
+    event GasbackClaimed(address indexed partner, uint256 credit);
+
+    /// Indicates a claim by an account without credits.
+    error Erc20GasbackNothingToClaim(address account);
+}
+
+pub enum Erc20GasbackError {
+    Erc20GasbackNothingToClaim(Erc20GasbackNothingToClaim),
+}
+
+impl From<Erc20GasbackError> for Vec<u8> {
+    fn from(e: Erc20GasbackError) -> Vec<u8> {
+        match e {
+            Erc20GasbackError::Erc20GasbackNothingToClaim(e) => e.encode(),
+        }
+    }
+}
+
+crate::errors::error_helpers!(Erc20GasbackError {
+    Erc20GasbackNothingToClaim,
+});
+
+impl Erc20Gasback {
+    /// Registers or removes `partner`, removed partners keep the credits already accrued.
+    ///
+    /// Emits a {GasbackPartnerUpdated} event.
+    pub fn set_partner(&mut self, partner: Address, registered: bool) {
+        self.partners.insert(partner, registered);
+        evm::log(GasbackPartnerUpdated { partner, registered });
+    }
+
+    /// Sets the credit rate and the bound of a single call credit, applied to calls accrued from now on.
+    ///
+    /// Emits a {GasbackRateUpdated} event.
+    pub fn set_rate(&mut self, credit_per_gas: U256, max_credit_per_call: U256) {
+        self.credit_per_gas.set(credit_per_gas);
+        self.max_credit_per_call.set(max_credit_per_call);
+        evm::log(GasbackRateUpdated {
+            credit_per_gas,
+            max_credit_per_call,
+        });
+    }
+
+    /// Accrues the credit of the current call if the caller is a registered partner, `gas_start`
+    /// being `evm::gas_left()` read at the start of the call. Returns the credit, 0 for other callers.
+    ///
+    /// Only the gas consumed between the two reads is metered, the intrinsic cost of the
+    /// transaction and the gas used by the partner itself are not.
+    ///
+    /// Emits a {GasbackAccrued} event.
+    pub fn accrue(&mut self, gas_start: u64) -> U256 {
+        let partner = msg::sender();
+        if !self.partners.get(partner) {
+            return U256::ZERO;
+        }
+        let gas_used = U256::from(gas_start.saturating_sub(evm::gas_left()));
+        let mut credit = gas_used.saturating_mul(self.credit_per_gas.get());
+        let max_credit = self.max_credit_per_call.get();
+        if max_credit != U256::ZERO && credit > max_credit {
+            credit = max_credit;
+        }
+        if credit == U256::ZERO {
+            return credit;
+        }
+
+        let mut credits_ref = self.credits.setter(partner);
+        let credits = credits_ref.get();
+        credits_ref.set(credits.saturating_add(credit));
+        self.total_credits.set(self.total_credits.get().saturating_add(credit));
+
+        evm::log(GasbackAccrued {
+            partner,
+            gas_used,
+            credit,
+        });
+        credit
+    }
+
+    /// Mints the credits of the caller to it and resets them, returns the amount claimed.
+    ///
+    /// Emits a {Transfer} and a {GasbackClaimed} event.
+    pub fn claim_gasback<T: Erc20Params>(&mut self, erc20: &mut Erc20<T>) -> Result<U256, Vec<u8>> {
+        let partner = msg::sender();
+        let credit = self.credits.get(partner);
+        if credit == U256::ZERO {
+            return Err(Erc20GasbackError::Erc20GasbackNothingToClaim(
+                Erc20GasbackNothingToClaim { account: partner },
+            )
+            .into());
+        }
+        self.credits.insert(partner, U256::ZERO);
+        self.total_credits.set(self.total_credits.get() - credit);
+        erc20.mint(partner, credit)?;
+
+        evm::log(GasbackClaimed { partner, credit });
+        Ok(credit)
+    }
+}
+
+#[external]
+impl Erc20Gasback {
+    #[selector(name = "isGasbackPartner")]
+    pub fn is_gasback_partner(&self, account: Address) -> Result<bool, Erc20GasbackError> {
+        Ok(self.partners.get(account))
+    }
+
+    /// Returns `(credit_per_gas, max_credit_per_call)`.
+    #[selector(name = "gasbackRate")]
+    pub fn gasback_rate(&self) -> Result<(U256, U256), Erc20GasbackError> {
+        Ok((self.credit_per_gas.get(), self.max_credit_per_call.get()))
+    }
+
+    /// Returns the credits of `account` not claimed yet.
+    #[selector(name = "gasbackCredits")]
+    pub fn gasback_credits(&self, account: Address) -> Result<U256, Erc20GasbackError> {
+        Ok(self.credits.get(account))
+    }
+
+    /// Returns the credits of all partners not claimed yet, the supply still to be minted.
+    #[selector(name = "totalGasbackCredits")]
+    pub fn total_gasback_credits(&self) -> Result<U256, Erc20GasbackError> {
+        Ok(self.total_credits.get())
+    }
+}
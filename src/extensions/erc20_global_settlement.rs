@@ -0,0 +1,189 @@
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    alloy_sol_types::{sol, SolError},
+    evm, msg,
+    prelude::*,
+};
+
+use crate::tokens::erc20::{Erc20, Erc20Params};
+use crate::utils::math::{self, Rounding};
+use crate::utils::safe_erc20;
+
+sol_storage! {
+    /// Emergency exit: once settlement is triggered the token is frozen for good, the only
+    /// remaining way out is `redeem`, burning tokens for a pro-rata share of the reserve asset
+    /// (an ERC20) held by the contract. The share is fixed at settlement time, so every holder
+    /// gets the same rate whatever the order of redemptions.
+    ///
+    /// The composing token calls `when_not_settled()` in every transfer/mint path, exposes
+    /// `trigger_settlement` behind its own authorization (e.g. `Ownable`) and `redeem` to everyone.
+    /// See `examples/compose_global_settlement.rs` for a complete composition.
+    pub struct Erc20GlobalSettlement {
+        /// terminal state, can not be reverted
+        bool settled;
+        /// ERC20 token paid out on redeem
+        address reserve_asset;
+        /// reserve balance of the contract when settlement was triggered
+        uint256 settlement_reserve;
+        /// token total supply when settlement was triggered
+        uint256 settlement_supply;
+    }
+}
+
+sol! {
+    event GlobalSettlementTriggered(address indexed reserve_asset, uint256 reserve, uint256 supply);
+    event ReserveAssetUpdated(address indexed reserve_asset);
+    event Redeemed(address indexed account, uint256 amount, uint256 claim);
+
+    /// Indicates an operation not allowed once the token is settled.
+    error Erc20SettlementActive();
+
+    /// Indicates a redeem before the token is settled.
+    error Erc20SettlementNotActive();
+
+    /// Indicates that settlement was triggered without a reserve asset.
+    error Erc20InvalidReserveAsset(address reserve_asset);
+
+    /// Indicates that the claim of `amount` tokens exceeds `uint256`.
+    error Erc20SettlementClaimOverflow(uint256 amount);
+}
+
+pub enum Erc20GlobalSettlementError {
+    Erc20SettlementActive(Erc20SettlementActive),
+    Erc20SettlementNotActive(Erc20SettlementNotActive),
+    Erc20InvalidReserveAsset(Erc20InvalidReserveAsset),
+    Erc20SettlementClaimOverflow(Erc20SettlementClaimOverflow),
+}
+
+impl From<Erc20GlobalSettlementError> for Vec<u8> {
+    fn from(e: Erc20GlobalSettlementError) -> Vec<u8> {
+        match e {
+            Erc20GlobalSettlementError::Erc20SettlementActive(e) => e.encode(),
+            Erc20GlobalSettlementError::Erc20SettlementNotActive(e) => e.encode(),
+            Erc20GlobalSettlementError::Erc20InvalidReserveAsset(e) => e.encode(),
+            Erc20GlobalSettlementError::Erc20SettlementClaimOverflow(e) => e.encode(),
+        }
+    }
+}
+
+crate::errors::error_helpers!(Erc20GlobalSettlementError {
+    Erc20SettlementActive,
+    Erc20SettlementNotActive,
+    Erc20InvalidReserveAsset,
+    Erc20SettlementClaimOverflow,
+});
+
+impl Erc20GlobalSettlement {
+    /// Guard for every path moving or minting tokens.
+    pub fn when_not_settled(&self) -> Result<(), Erc20GlobalSettlementError> {
+        if self.settled.get() {
+            return Err(Erc20GlobalSettlementError::Erc20SettlementActive(
+                Erc20SettlementActive {},
+            ));
+        }
+        Ok(())
+    }
+
+    /// Sets the ERC20 paid out on redeem, can only be changed before settlement.
+    ///
+    /// Emits a {ReserveAssetUpdated} event.
+    pub fn set_reserve_asset(&mut self, reserve_asset: Address) -> Result<(), Erc20GlobalSettlementError> {
+        self.when_not_settled()?;
+        self.reserve_asset.set(reserve_asset);
+        evm::log(ReserveAssetUpdated { reserve_asset });
+        Ok(())
+    }
+
+    /// Enters the terminal settlement state, snapshotting the reserve held by the contract and
+    /// the token total supply which fix the redeem rate.
+    ///
+    /// Emits a {GlobalSettlementTriggered} event.
+    pub fn trigger_settlement<T: Erc20Params>(&mut self, erc20: &Erc20<T>) -> Result<(), Vec<u8>> {
+        self.when_not_settled()?;
+        let reserve_asset = self.reserve_asset.get();
+        if reserve_asset == Address::ZERO {
+            return Err(Erc20GlobalSettlementError::Erc20InvalidReserveAsset(
+                Erc20InvalidReserveAsset { reserve_asset },
+            )
+            .into());
+        }
+
+        let reserve = safe_erc20::self_balance(reserve_asset)?;
+        let supply = erc20.total_supply_internal();
+        self.settled.set(true);
+        self.settlement_reserve.set(reserve);
+        self.settlement_supply.set(supply);
+
+        evm::log(GlobalSettlementTriggered {
+            reserve_asset,
+            reserve,
+            supply,
+        });
+        Ok(())
+    }
+
+    /// Burns `amount` tokens of the caller and pays out its share of the reserve, returns the claim.
+    ///
+    /// Emits a {Transfer} and a {Redeemed} event.
+    pub fn redeem<T: Erc20Params>(&mut self, erc20: &mut Erc20<T>, amount: U256) -> Result<U256, Vec<u8>> {
+        if !self.settled.get() {
+            return Err(Erc20GlobalSettlementError::Erc20SettlementNotActive(
+                Erc20SettlementNotActive {},
+            )
+            .into());
+        }
+        let account = msg::sender();
+        let claim = self.claim_for(amount)?;
+        erc20.burn(account, amount)?;
+        safe_erc20::safe_transfer(self.reserve_asset.get(), account, claim)?;
+
+        evm::log(Redeemed {
+            account,
+            amount,
+            claim,
+        });
+        Ok(claim)
+    }
+
+    /// Reserve paid out for `amount` tokens at the rate snapshotted at settlement.
+    fn claim_for(&self, amount: U256) -> Result<U256, Erc20GlobalSettlementError> {
+        settlement_claim(amount, self.settlement_reserve.get(), self.settlement_supply.get())
+    }
+}
+
+/// Reserve paid out for `amount` tokens: `amount * reserve / supply`, rounded down so the claims
+/// never add up to more than `reserve`. Nothing is owed without a supply.
+pub fn settlement_claim(amount: U256, reserve: U256, supply: U256) -> Result<U256, Erc20GlobalSettlementError> {
+    if supply == U256::ZERO {
+        return Ok(U256::ZERO);
+    }
+    math::mul_div(amount, reserve, supply, Rounding::Down).map_err(|_| {
+        Erc20GlobalSettlementError::Erc20SettlementClaimOverflow(Erc20SettlementClaimOverflow { amount })
+    })
+}
+
+#[external]
+impl Erc20GlobalSettlement {
+    #[selector(name = "isSettled")]
+    pub fn is_settled(&self) -> Result<bool, Erc20GlobalSettlementError> {
+        Ok(self.settled.get())
+    }
+
+    #[selector(name = "reserveAsset")]
+    pub fn reserve_asset(&self) -> Result<Address, Erc20GlobalSettlementError> {
+        Ok(self.reserve_asset.get())
+    }
+
+    /// Returns `(reserve, supply)` snapshotted at settlement, both 0 before.
+    #[selector(name = "settlementRate")]
+    pub fn settlement_rate(&self) -> Result<(U256, U256), Erc20GlobalSettlementError> {
+        Ok((self.settlement_reserve.get(), self.settlement_supply.get()))
+    }
+
+    /// Returns the reserve that would be paid out for redeeming `amount` tokens.
+    #[selector(name = "redeemable")]
+    pub fn redeemable(&self, amount: U256) -> Result<U256, Erc20GlobalSettlementError> {
+        self.claim_for(amount)
+    }
+}
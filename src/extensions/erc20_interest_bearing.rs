@@ -0,0 +1,305 @@
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    alloy_sol_types::{sol, SolError},
+    evm, msg,
+    prelude::*,
+};
+
+use crate::tokens::erc20::{
+    Erc20, Erc20Error, Erc20InsufficientBalance, Erc20InvalidReceiver, Erc20InvalidSpender, Erc20Params,
+};
+
+/// 1e27, precision of the liquidity index (same "ray" unit as Aave).
+pub const RAY: U256 = U256::from_limbs([0x9fd0803ce8000000, 0x33b2e3c, 0, 0]);
+
+sol_storage! {
+    /// aToken style interest bearing ERC20. The wrapped `Erc20` stores scaled balances, the
+    /// balances reported to users are `scaled * liquidity_index / RAY` and grow every time the
+    /// controller accrues a new index. Allowances are kept in (unscaled) token units.
+    ///
+    /// Balances move with `Erc20::update_balances`, so the `TransferHook` of `T` does NOT run on
+    /// mints, burns and transfers of this token: its `value` and the balances it would read are
+    /// scaled units. Checks on transfers go in the overriding externals of the composing token.
+    ///
+    /// Compose with `#[borrow] Erc20InterestBearing<P> token;` and `#[inherit(Erc20InterestBearing<P>)]`,
+    /// the balance related externals of the inner `Erc20` are overridden here.
+    /// See `examples/compose_interest_bearing.rs` for a complete composition.
+    pub struct Erc20InterestBearing<T> {
+        #[borrow]
+        Erc20<T> erc20;
+        /// current liquidity index in ray, 0 until `set_controller` is called for the first time
+        uint256 liquidity_index;
+        /// the only account allowed to accrue interest (e.g. the lending pool)
+        address controller;
+    }
+}
+
+sol! {
+    event LiquidityIndexUpdated(uint256 previous_index, uint256 new_index);
+    event InterestControllerUpdated(address indexed previous_controller, address indexed new_controller);
+
+    /// Indicates that `caller` is not the interest controller.
+    error Erc20InterestUnauthorizedController(address caller);
+
+    /// Indicates an attempt to move the liquidity index backwards, which would burn user balances.
+    /// * `current_index` - current liquidity index.
+    /// * `new_index` - rejected index.
+    error Erc20InterestInvalidIndex(uint256 current_index, uint256 new_index);
+
+    /// Indicates that `amount` can not be converted to or from scaled units without overflowing.
+    error Erc20InterestAmountOverflow(uint256 amount);
+
+    /// Indicates an index at which the scaled total supply can not be reported in token units.
+    /// * `new_index` - rejected index.
+    /// * `scaled_total_supply` - scaled total supply overflowing at `new_index`.
+    error Erc20InterestIndexOverflow(uint256 new_index, uint256 scaled_total_supply);
+}
+
+pub enum Erc20InterestBearingError {
+    Erc20InterestUnauthorizedController(Erc20InterestUnauthorizedController),
+    Erc20InterestInvalidIndex(Erc20InterestInvalidIndex),
+    Erc20InterestAmountOverflow(Erc20InterestAmountOverflow),
+    Erc20InterestIndexOverflow(Erc20InterestIndexOverflow),
+}
+
+impl From<Erc20InterestBearingError> for Vec<u8> {
+    fn from(e: Erc20InterestBearingError) -> Vec<u8> {
+        match e {
+            Erc20InterestBearingError::Erc20InterestUnauthorizedController(e) => e.encode(),
+            Erc20InterestBearingError::Erc20InterestInvalidIndex(e) => e.encode(),
+            Erc20InterestBearingError::Erc20InterestAmountOverflow(e) => e.encode(),
+            Erc20InterestBearingError::Erc20InterestIndexOverflow(e) => e.encode(),
+        }
+    }
+}
+
+crate::errors::error_helpers!(Erc20InterestBearingError {
+    Erc20InterestUnauthorizedController,
+    Erc20InterestInvalidIndex,
+    Erc20InterestAmountOverflow,
+    Erc20InterestIndexOverflow,
+});
+
+impl<T: Erc20Params> Erc20InterestBearing<T> {
+    /// Sets the account allowed to call `accrue`, initializes the index to `RAY` on first call.
+    ///
+    /// Emits an {InterestControllerUpdated} event.
+    pub fn set_controller(&mut self, new_controller: Address) {
+        if self.liquidity_index.get() == U256::ZERO {
+            self.liquidity_index.set(RAY);
+        }
+        let previous_controller = self.controller.get();
+        self.controller.set(new_controller);
+        evm::log(InterestControllerUpdated {
+            previous_controller,
+            new_controller,
+        });
+    }
+
+    /// Mints `amount` (unscaled) tokens to `account`, rounding the scaled amount down. Reverts
+    /// when the total supply could no longer be reported in token units at the current index.
+    ///
+    /// Emits a {Transfer} event with `from` set to the zero address.
+    pub fn mint(&mut self, account: Address, amount: U256) -> Result<(), Vec<u8>> {
+        if account == Address::ZERO {
+            return Err(Erc20Error::Erc20InvalidReceiver(Erc20InvalidReceiver {
+                receiver: Address::ZERO,
+            })
+            .into());
+        }
+        let index = self.index();
+        let scaled = to_scaled(amount, index, false)?;
+        self.erc20.update_balances(Address::ZERO, account, scaled)?;
+        // every balance is at most the total supply, `balanceOf` can not overflow either
+        from_scaled(self.erc20.total_supply_internal(), index)?;
+        Erc20::<T>::emit_transfer(Address::ZERO, account, amount);
+        Ok(())
+    }
+
+    /// Burns `amount` (unscaled) tokens from `account`, rounding the scaled amount up, see
+    /// {scaled_debit}.
+    ///
+    /// Emits a {Transfer} event with `to` set to the zero address.
+    pub fn burn(&mut self, account: Address, amount: U256) -> Result<(), Vec<u8>> {
+        if account == Address::ZERO {
+            return Err(Erc20Error::Erc20InvalidSpender(Erc20InvalidSpender {
+                spender: Address::ZERO,
+            })
+            .into());
+        }
+        let scaled = scaled_debit(account, amount, self.erc20.balance_of_internal(account), self.index())?;
+        self.erc20.update_balances(account, Address::ZERO, scaled)?;
+        Erc20::<T>::emit_transfer(account, Address::ZERO, amount);
+        Ok(())
+    }
+
+    /// Moves `amount` (unscaled) tokens, the sender is debited the scaled amount rounded up so
+    /// rounding never creates tokens, see {scaled_debit}.
+    ///
+    /// Emits a {Transfer} event.
+    fn transfer_scaled(&mut self, from: Address, to: Address, amount: U256) -> Result<(), Vec<u8>> {
+        if from == Address::ZERO {
+            return Err(Erc20Error::Erc20InvalidSpender(Erc20InvalidSpender {
+                spender: Address::ZERO,
+            })
+            .into());
+        }
+        if to == Address::ZERO {
+            return Err(Erc20Error::Erc20InvalidReceiver(Erc20InvalidReceiver {
+                receiver: Address::ZERO,
+            })
+            .into());
+        }
+        let scaled = scaled_debit(from, amount, self.erc20.balance_of_internal(from), self.index())?;
+        self.erc20.update_balances(from, to, scaled)?;
+        Erc20::<T>::emit_transfer(from, to, amount);
+        Ok(())
+    }
+
+    fn index(&self) -> U256 {
+        let index = self.liquidity_index.get();
+        if index == U256::ZERO {
+            RAY
+        } else {
+            index
+        }
+    }
+}
+
+#[external]
+#[inherit(Erc20<T>)]
+impl<T: Erc20Params> Erc20InterestBearing<T> {
+    #[selector(name = "balanceOf")]
+    pub fn balance_of(&self, account: Address) -> Result<U256, Vec<u8>> {
+        Ok(from_scaled(self.erc20.balance_of_internal(account), self.index())?)
+    }
+
+    #[selector(name = "totalSupply")]
+    pub fn total_supply(&self) -> Result<U256, Vec<u8>> {
+        Ok(from_scaled(self.erc20.total_supply_internal(), self.index())?)
+    }
+
+    #[selector(name = "scaledBalanceOf")]
+    pub fn scaled_balance_of(&self, account: Address) -> Result<U256, Vec<u8>> {
+        Ok(self.erc20.balance_of_internal(account))
+    }
+
+    #[selector(name = "scaledTotalSupply")]
+    pub fn scaled_total_supply(&self) -> Result<U256, Vec<u8>> {
+        Ok(self.erc20.total_supply_internal())
+    }
+
+    #[selector(name = "liquidityIndex")]
+    pub fn liquidity_index(&self) -> Result<U256, Vec<u8>> {
+        Ok(self.index())
+    }
+
+    #[selector(name = "interestController")]
+    pub fn interest_controller(&self) -> Result<Address, Vec<u8>> {
+        Ok(self.controller.get())
+    }
+
+    /// Moves the liquidity index to `new_index`, growing every balance by `new_index / current_index`.
+    /// Callable only by the controller, the index can never decrease nor grow past the point where
+    /// the total supply overflows in token units, see {check_accrued_index}.
+    ///
+    /// Emits a {LiquidityIndexUpdated} event.
+    #[selector(name = "accrue")]
+    pub fn accrue(&mut self, new_index: U256) -> Result<(), Vec<u8>> {
+        let caller = msg::sender();
+        if caller != self.controller.get() {
+            return Err(Erc20InterestBearingError::Erc20InterestUnauthorizedController(
+                Erc20InterestUnauthorizedController { caller },
+            )
+            .into());
+        }
+        let previous_index = self.index();
+        check_accrued_index(previous_index, new_index, self.erc20.total_supply_internal())?;
+        self.liquidity_index.set(new_index);
+        evm::log(LiquidityIndexUpdated {
+            previous_index,
+            new_index,
+        });
+        Ok(())
+    }
+
+    #[selector(name = "transfer")]
+    pub fn transfer(&mut self, to: Address, value: U256) -> Result<bool, Vec<u8>> {
+        self.transfer_scaled(msg::sender(), to, value)?;
+        Ok(true)
+    }
+
+    #[selector(name = "transferFrom")]
+    pub fn transfer_from(&mut self, from: Address, to: Address, value: U256) -> Result<bool, Vec<u8>> {
+        self.erc20.spend_allowance(from, msg::sender(), value)?;
+        self.transfer_scaled(from, to, value)?;
+        Ok(true)
+    }
+}
+
+/// Reverts unless `new_index` can replace `current_index`: it never decreases, which would burn
+/// user balances, and `scaled_total_supply` (so every balance) stays convertible to token units,
+/// or `balanceOf` and `totalSupply` would revert for every holder.
+pub fn check_accrued_index(
+    current_index: U256,
+    new_index: U256,
+    scaled_total_supply: U256,
+) -> Result<(), Erc20InterestBearingError> {
+    if new_index < current_index {
+        return Err(Erc20InterestBearingError::Erc20InterestInvalidIndex(
+            Erc20InterestInvalidIndex {
+                current_index,
+                new_index,
+            },
+        ));
+    }
+    if scaled_total_supply.checked_mul(new_index).is_none() {
+        return Err(Erc20InterestBearingError::Erc20InterestIndexOverflow(
+            Erc20InterestIndexOverflow {
+                new_index,
+                scaled_total_supply,
+            },
+        ));
+    }
+    Ok(())
+}
+
+/// Scaled units worth `amount` token units at `index`, rounded down or up.
+pub fn to_scaled(amount: U256, index: U256, round_up: bool) -> Result<U256, Erc20InterestBearingError> {
+    let numerator = amount.checked_mul(RAY).ok_or_else(|| overflow(amount))?;
+    let scaled = numerator / index;
+    if round_up && numerator % index != U256::ZERO {
+        Ok(scaled + U256::from(1))
+    } else {
+        Ok(scaled)
+    }
+}
+
+/// Token units worth `scaled` units at `index`, rounded down.
+pub fn from_scaled(scaled: U256, index: U256) -> Result<U256, Erc20InterestBearingError> {
+    Ok(scaled.checked_mul(index).ok_or_else(|| overflow(scaled))? / RAY)
+}
+
+/// Scaled units debited from `account` holding `scaled_balance` to move `amount` token units:
+/// rounded up, capped at `scaled_balance` when `amount` is the whole reported balance so a full
+/// transfer or burn always succeeds and leaves no scaled dust. Errors report token units.
+pub fn scaled_debit(account: Address, amount: U256, scaled_balance: U256, index: U256) -> Result<U256, Vec<u8>> {
+    let balance = from_scaled(scaled_balance, index)?;
+    if amount > balance {
+        return Err(Erc20Error::Erc20InsufficientBalance(Erc20InsufficientBalance {
+            sender: account,
+            balance,
+            needed: amount,
+        })
+        .into());
+    }
+    if amount == balance {
+        return Ok(scaled_balance);
+    }
+    Ok(to_scaled(amount, index, true)?)
+}
+
+fn overflow(amount: U256) -> Erc20InterestBearingError {
+    Erc20InterestBearingError::Erc20InterestAmountOverflow(Erc20InterestAmountOverflow { amount })
+}
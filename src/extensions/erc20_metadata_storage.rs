@@ -0,0 +1,104 @@
+use alloc::{string::String, vec::Vec};
+use stylus_sdk::{
+    alloy_primitives::U8,
+    alloy_sol_types::{sol, SolError},
+    prelude::*,
+};
+
+use crate::utils::bounded_string::{BoundedString, BoundedStringError, BoundedStringTooLong};
+
+/// Longest token name in bytes.
+pub const MAX_NAME_LENGTH: usize = 64;
+/// Longest token symbol in bytes.
+pub const MAX_SYMBOL_LENGTH: usize = 32;
+
+sol_storage! {
+    /// Token name, symbol and decimals kept in storage instead of `Erc20Params`, so one deployed
+    /// program serves tokens with different metadata (e.g. clones deployed by a factory).
+    ///
+    /// The composing token sets the metadata once in `init` with `set_metadata` and lists
+    /// `Erc20MetadataStorage` before `Erc20<T>` in `#[inherit]`: its `name`, `symbol` and
+    /// `decimals` externals then answer instead of the `Erc20Params` constants. Code reading
+    /// `T::NAME` directly (e.g. EIP-712 domains of permits) still sees the constant.
+    /// See `examples/compose_metadata_storage.rs` for a complete composition.
+    pub struct Erc20MetadataStorage {
+        /// at most `MAX_NAME_LENGTH` bytes
+        BoundedString<64> name;
+        /// at most `MAX_SYMBOL_LENGTH` bytes
+        BoundedString<32> symbol;
+        uint8 decimals;
+        /// set by `set_metadata`, an empty name or symbol is valid
+        bool initialized;
+    }
+}
+
+sol! {
+    /// Indicates metadata set twice.
+    error Erc20MetadataAlreadySet();
+}
+
+pub enum Erc20MetadataStorageError {
+    Erc20MetadataAlreadySet(Erc20MetadataAlreadySet),
+    BoundedStringTooLong(BoundedStringTooLong),
+}
+
+impl From<Erc20MetadataStorageError> for Vec<u8> {
+    fn from(e: Erc20MetadataStorageError) -> Vec<u8> {
+        match e {
+            Erc20MetadataStorageError::Erc20MetadataAlreadySet(e) => e.encode(),
+            Erc20MetadataStorageError::BoundedStringTooLong(e) => e.encode(),
+        }
+    }
+}
+
+crate::errors::error_helpers!(Erc20MetadataStorageError {
+    Erc20MetadataAlreadySet,
+    BoundedStringTooLong,
+});
+
+impl From<BoundedStringError> for Erc20MetadataStorageError {
+    fn from(e: BoundedStringError) -> Self {
+        match e {
+            BoundedStringError::BoundedStringTooLong(e) => Erc20MetadataStorageError::BoundedStringTooLong(e),
+        }
+    }
+}
+
+impl Erc20MetadataStorage {
+    /// Sets name, symbol and decimals, only once. Reverts with `BoundedStringTooLong` for a
+    /// name over `MAX_NAME_LENGTH` or a symbol over `MAX_SYMBOL_LENGTH` bytes.
+    pub fn set_metadata(&mut self, name: &str, symbol: &str, decimals: u8) -> Result<(), Erc20MetadataStorageError> {
+        if self.initialized.get() {
+            return Err(Erc20MetadataStorageError::Erc20MetadataAlreadySet(Erc20MetadataAlreadySet {}));
+        }
+        self.name.set_str(name)?;
+        self.symbol.set_str(symbol)?;
+        self.decimals.set(U8::from(decimals));
+        self.initialized.set(true);
+        Ok(())
+    }
+
+    /// Whether `set_metadata` was called.
+    pub fn is_initialized(&self) -> bool {
+        self.initialized.get()
+    }
+}
+
+/// Metadata names are pinned, as for the token externals.
+#[external]
+impl Erc20MetadataStorage {
+    #[selector(name = "name")]
+    pub fn name(&self) -> Result<String, Erc20MetadataStorageError> {
+        Ok(self.name.get_string())
+    }
+
+    #[selector(name = "symbol")]
+    pub fn symbol(&self) -> Result<String, Erc20MetadataStorageError> {
+        Ok(self.symbol.get_string())
+    }
+
+    #[selector(name = "decimals")]
+    pub fn decimals(&self) -> Result<u8, Erc20MetadataStorageError> {
+        Ok(self.decimals.get().to::<u8>())
+    }
+}
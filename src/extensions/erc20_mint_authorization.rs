@@ -0,0 +1,154 @@
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{Address, B256, U256},
+    alloy_sol_types::{sol, SolError},
+    block,
+    crypto::keccak,
+    evm,
+    prelude::*,
+};
+
+use crate::tokens::erc20::{Erc20, Erc20Params};
+use crate::utils::{
+    eip712::{self, address_word, hash_words, u256_word},
+    replay_guard::{NonceMode, ReplayGuard},
+    signature_checker,
+};
+
+/// EIP-712 version of the mint authorization domain, the shared token domain of `utils::eip712`.
+pub const MINT_AUTHORIZATION_VERSION: &str = eip712::DOMAIN_VERSION;
+
+pub const MINT_AUTHORIZATION_TYPE: &str =
+    "MintAuthorization(address to,uint256 amount,uint256 nonce,uint256 expiry)";
+
+/// Authorizations use unordered nonces of the signer, the backend picks any unused nonce per
+/// claim and claims can be submitted in any order.
+pub const NONCE_MODE: NonceMode = NonceMode::Unordered;
+
+sol_storage! {
+    /// Mints authorized off-chain: a configured signer (an EOA or an EIP-1271 contract) signs
+    /// `MintAuthorization(to, amount, nonce, expiry)` and the recipient submits the signature to
+    /// claim, e.g. game rewards or points conversions, without the backend paying gas per claim.
+    ///
+    /// Nonces are the unordered nonces of the signer in the token's shared `ReplayGuard`, the
+    /// signer cancels an unclaimed authorization with `invalidateUnorderedNonces`. The composing
+    /// token exposes `mint_with_authorization` to everyone and `set_mint_signer` behind its own
+    /// authorization. See `examples/compose_mint_authorization.rs` for a complete composition.
+    pub struct Erc20MintAuthorization {
+        address signer;
+    }
+}
+
+sol! {
+    event MintSignerUpdated(address indexed previous_signer, address indexed new_signer);
+    event MintAuthorizationUsed(address indexed signer, address indexed to, uint256 amount, uint256 nonce);
+
+    /// Indicates that the mint authorization expired.
+    error Erc20MintAuthorizationExpired(uint256 expiry);
+
+    /// Indicates that the signature is not a valid authorization of the configured `signer`.
+    error Erc20MintAuthorizationInvalidSignature(address signer);
+}
+
+pub enum Erc20MintAuthorizationError {
+    Erc20MintAuthorizationExpired(Erc20MintAuthorizationExpired),
+    Erc20MintAuthorizationInvalidSignature(Erc20MintAuthorizationInvalidSignature),
+}
+
+impl From<Erc20MintAuthorizationError> for Vec<u8> {
+    fn from(e: Erc20MintAuthorizationError) -> Vec<u8> {
+        match e {
+            Erc20MintAuthorizationError::Erc20MintAuthorizationExpired(e) => e.encode(),
+            Erc20MintAuthorizationError::Erc20MintAuthorizationInvalidSignature(e) => e.encode(),
+        }
+    }
+}
+
+crate::errors::error_helpers!(Erc20MintAuthorizationError {
+    Erc20MintAuthorizationExpired,
+    Erc20MintAuthorizationInvalidSignature,
+});
+
+impl Erc20MintAuthorization {
+    /// Sets the account signing authorizations, the zero address disables minting with
+    /// authorization. Unclaimed authorizations of the previous signer become invalid.
+    ///
+    /// Emits a {MintSignerUpdated} event.
+    pub fn set_mint_signer(&mut self, new_signer: Address) {
+        let previous_signer = self.signer.get();
+        self.signer.set(new_signer);
+        evm::log(MintSignerUpdated {
+            previous_signer,
+            new_signer,
+        });
+    }
+
+    /// Mints `amount` tokens to `to`, authorized by the signature of the configured signer.
+    /// Anyone can submit the authorization, the tokens always go to `to`.
+    ///
+    /// Emits a {Transfer} and a {MintAuthorizationUsed} event.
+    #[allow(clippy::too_many_arguments)]
+    pub fn mint_with_authorization<T: Erc20Params>(
+        &self,
+        erc20: &mut Erc20<T>,
+        replay_guard: &mut ReplayGuard,
+        to: Address,
+        amount: U256,
+        nonce: U256,
+        expiry: U256,
+        signature: &[u8],
+    ) -> Result<(), Vec<u8>> {
+        if U256::from(block::timestamp()) > expiry {
+            return Err(Erc20MintAuthorizationError::Erc20MintAuthorizationExpired(
+                Erc20MintAuthorizationExpired { expiry },
+            )
+            .into());
+        }
+        let signer = self.signer.get();
+        let hash = eip712::to_typed_data_hash(
+            domain_separator::<T>(),
+            mint_authorization_hash(to, amount, nonce, expiry),
+        );
+        if signer == Address::ZERO || !signature_checker::is_valid_signature_now(signer, hash, signature) {
+            return Err(Erc20MintAuthorizationError::Erc20MintAuthorizationInvalidSignature(
+                Erc20MintAuthorizationInvalidSignature { signer },
+            )
+            .into());
+        }
+        replay_guard.consume(NONCE_MODE, signer, nonce)?;
+
+        erc20.mint(to, amount)?;
+        evm::log(MintAuthorizationUsed {
+            signer,
+            to,
+            amount,
+            nonce,
+        });
+        Ok(())
+    }
+}
+
+#[external]
+impl Erc20MintAuthorization {
+    /// Returns the account signing mint authorizations, zero if disabled.
+    #[selector(name = "mintSigner")]
+    pub fn mint_signer(&self) -> Result<Address, Erc20MintAuthorizationError> {
+        Ok(self.signer.get())
+    }
+}
+
+/// EIP-712 struct hash of a `MintAuthorization`.
+pub fn mint_authorization_hash(to: Address, amount: U256, nonce: U256, expiry: U256) -> B256 {
+    hash_words(&[
+        keccak(MINT_AUTHORIZATION_TYPE.as_bytes()),
+        address_word(to),
+        u256_word(amount),
+        u256_word(nonce),
+        u256_word(expiry),
+    ])
+}
+
+/// Domain separator of the token, see `utils::eip712` for the shared domain.
+pub fn domain_separator<T: Erc20Params>() -> B256 {
+    eip712::erc20_domain_separator::<T>()
+}
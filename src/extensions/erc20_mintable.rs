@@ -0,0 +1,109 @@
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    alloy_sol_types::{sol, SolError},
+    evm,
+    prelude::*,
+};
+
+use crate::access::access_control::{role_id, AccessControl, DEFAULT_ADMIN_ROLE};
+use crate::tokens::erc20::{Erc20, Erc20Params};
+
+/// Name of the role minting tokens, see `role_id`.
+pub const MINTER_ROLE: &str = "MINTER_ROLE";
+
+sol_storage! {
+    /// Minting restricted to holders of `MINTER_ROLE` in the token's `AccessControl`, the safe
+    /// default to copy instead of a mint open to anyone. The composing token exposes
+    /// `mint(to, amount)` calling `mint(&self.access, &mut self.erc20, to, amount)`.
+    ///
+    /// `max_per_tx` optionally bounds the amount of a single mint call (0, the default, for no
+    /// bound), limiting what a leaked minter key mints per transaction it sends; calls batched
+    /// in one transaction (e.g. `multicall`) are bounded one by one. `DEFAULT_ADMIN_ROLE` sets it
+    /// with `set_max_per_tx`.
+    /// See `examples/compose_access_control.rs` for a complete composition.
+    pub struct Erc20Mintable {
+        /// largest amount of one mint, 0 for no bound
+        uint256 max_per_tx;
+    }
+}
+
+sol! {
+    event MaxMintPerTxUpdated(uint256 max_per_tx);
+
+    /// Indicates a mint of `value` above the `max_per_tx` bound.
+    error Erc20MintAboveMaxPerTx(uint256 value, uint256 max_per_tx);
+}
+
+pub enum Erc20MintableError {
+    Erc20MintAboveMaxPerTx(Erc20MintAboveMaxPerTx),
+}
+
+impl From<Erc20MintableError> for Vec<u8> {
+    fn from(e: Erc20MintableError) -> Vec<u8> {
+        match e {
+            Erc20MintableError::Erc20MintAboveMaxPerTx(e) => e.encode(),
+        }
+    }
+}
+
+crate::errors::error_helpers!(Erc20MintableError {
+    Erc20MintAboveMaxPerTx,
+});
+
+impl Erc20Mintable {
+    /// Mints `value` tokens to `to`, the caller needs `MINTER_ROLE`.
+    ///
+    /// Emits a {Transfer} event.
+    pub fn mint<T: Erc20Params>(
+        &self,
+        access: &AccessControl,
+        erc20: &mut Erc20<T>,
+        to: Address,
+        value: U256,
+    ) -> Result<(), Vec<u8>> {
+        access.only_role(role_id(MINTER_ROLE))?;
+        self.check_max_per_tx(value)?;
+        erc20.mint(to, value)?;
+        Ok(())
+    }
+
+    /// Reverts with `Erc20MintAboveMaxPerTx` if `value` exceeds a non zero `max_per_tx`.
+    pub fn check_max_per_tx(&self, value: U256) -> Result<(), Erc20MintableError> {
+        check_max_per_tx(self.max_per_tx.get(), value)
+    }
+
+    /// Sets the bound of a single mint, 0 for none; the caller needs `DEFAULT_ADMIN_ROLE`.
+    ///
+    /// Emits a {MaxMintPerTxUpdated} event.
+    pub fn set_max_per_tx(&mut self, access: &AccessControl, max_per_tx: U256) -> Result<(), Vec<u8>> {
+        access.only_role(DEFAULT_ADMIN_ROLE)?;
+        self.set_max_per_tx_internal(max_per_tx);
+        Ok(())
+    }
+
+    /// Sets the bound of a single mint without authorization, e.g. from `init`.
+    ///
+    /// Emits a {MaxMintPerTxUpdated} event.
+    pub fn set_max_per_tx_internal(&mut self, max_per_tx: U256) {
+        self.max_per_tx.set(max_per_tx);
+        evm::log(MaxMintPerTxUpdated { max_per_tx });
+    }
+}
+
+#[external]
+impl Erc20Mintable {
+    /// Returns the largest amount of one mint, 0 for no bound.
+    #[selector(name = "maxMintPerTx")]
+    pub fn max_mint_per_tx(&self) -> Result<U256, Erc20MintableError> {
+        Ok(self.max_per_tx.get())
+    }
+}
+
+/// Bound check without storage: `value` must not exceed `max_per_tx` unless it is 0.
+pub fn check_max_per_tx(max_per_tx: U256, value: U256) -> Result<(), Erc20MintableError> {
+    if max_per_tx != U256::ZERO && value > max_per_tx {
+        return Err(Erc20MintableError::Erc20MintAboveMaxPerTx(Erc20MintAboveMaxPerTx { value, max_per_tx }));
+    }
+    Ok(())
+}
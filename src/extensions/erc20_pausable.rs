@@ -0,0 +1,6 @@
+//! `Pausable` under the name ERC20 tokens have composed since before it became a generic utility,
+//! same storage, events and errors.
+pub use crate::utils::pausable::{EnforcedPause, ExpectedPause, Paused, Unpaused, PAUSED_FLAG};
+
+pub type Erc20Pausable = crate::utils::pausable::Pausable;
+pub type Erc20PausableError = crate::utils::pausable::PausableError;
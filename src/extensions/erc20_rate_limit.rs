@@ -0,0 +1,230 @@
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    alloy_sol_types::{sol, SolError},
+    block, evm,
+    prelude::*,
+};
+
+use crate::extensions::fee_exemptions::FeeExemptions;
+use crate::tokens::erc20::moves_out;
+use crate::utils::hostio;
+
+sol_storage! {
+    /// Per account limits of outbound transfers within a time window.
+    ///
+    /// Compose next to `Erc20` and `FeeExemptions`, and call
+    /// `record_outbound(&self.fee_exemptions, from, to, value)` before every `update` moving tokens
+    /// out of `from` (transfer, transfer_from, burn). Configuration setters are internal, the
+    /// composing token exposes them behind its own authorization.
+    /// See `examples/compose_rate_limit.rs` for a complete composition.
+    pub struct Erc20RateLimit {
+        /// max amount an account can send within one window, 0 disables the amount limit
+        uint256 max_amount_per_window;
+        /// max number of outbound transfers of an account within one window, 0 disables the count limit
+        uint256 max_transfers_per_window;
+        /// window length in seconds
+        uint256 window;
+        /// timestamp at which the current window of an account started
+        mapping(address => uint256) window_start;
+        /// amount already sent by an account in its current window
+        mapping(address => uint256) window_amount;
+        /// number of transfers already done by an account in its current window
+        mapping(address => uint256) window_count;
+    }
+}
+
+sol! {
+    event RateLimitUpdated(uint256 max_amount_per_window, uint256 max_transfers_per_window, uint256 window);
+
+    /// Indicates that `account` would send more than `limit` tokens within the current window.
+    /// * `account` - address whose tokens are being transferred.
+    /// * `sent` - amount the account would have sent in the window including this transfer.
+    /// * `limit` - max amount per window.
+    error Erc20RateLimitAmountExceeded(address account, uint256 sent, uint256 limit);
+
+    /// Indicates that `account` already made `limit` transfers within the current window.
+    /// * `account` - address whose tokens are being transferred.
+    /// * `limit` - max number of transfers per window.
+    error Erc20RateLimitCountExceeded(address account, uint256 limit);
+
+    /// Indicates that limits were configured with a zero length window.
+    error Erc20RateLimitInvalidWindow();
+}
+
+pub enum Erc20RateLimitError {
+    Erc20RateLimitAmountExceeded(Erc20RateLimitAmountExceeded),
+    Erc20RateLimitCountExceeded(Erc20RateLimitCountExceeded),
+    Erc20RateLimitInvalidWindow(Erc20RateLimitInvalidWindow),
+}
+
+impl From<Erc20RateLimitError> for Vec<u8> {
+    fn from(e: Erc20RateLimitError) -> Vec<u8> {
+        match e {
+            Erc20RateLimitError::Erc20RateLimitAmountExceeded(e) => e.encode(),
+            Erc20RateLimitError::Erc20RateLimitCountExceeded(e) => e.encode(),
+            Erc20RateLimitError::Erc20RateLimitInvalidWindow(e) => e.encode(),
+        }
+    }
+}
+
+crate::errors::error_helpers!(Erc20RateLimitError {
+    Erc20RateLimitAmountExceeded,
+    Erc20RateLimitCountExceeded,
+    Erc20RateLimitInvalidWindow,
+});
+
+impl Erc20RateLimit {
+    /// Sets the limits applied to every non exempt account. A zero limit disables that limit.
+    /// Window length must be non zero.
+    ///
+    /// Emits a {RateLimitUpdated} event.
+    pub fn set_rate_limit(
+        &mut self,
+        max_amount_per_window: U256,
+        max_transfers_per_window: U256,
+        window: U256,
+    ) -> Result<(), Erc20RateLimitError> {
+        if window == U256::ZERO {
+            return Err(Erc20RateLimitError::Erc20RateLimitInvalidWindow(
+                Erc20RateLimitInvalidWindow {},
+            ));
+        }
+        self.max_amount_per_window.set(max_amount_per_window);
+        self.max_transfers_per_window.set(max_transfers_per_window);
+        self.window.set(window);
+
+        evm::log(RateLimitUpdated {
+            max_amount_per_window,
+            max_transfers_per_window,
+            window,
+        });
+        Ok(())
+    }
+
+    /// Accounts an outbound transfer of `value` tokens from `from` to `to` (zero for burns),
+    /// reverting if it would exceed the limits of the current window. Starts a new window for
+    /// `from` when the previous one elapsed. Accounts exempt in `exemptions` are not limited.
+    ///
+    /// Zero value transfers and self transfers move nothing out of `from`: they are neither
+    /// limited nor counted, so they always succeed as ERC20 requires and a `transferFrom` of 0
+    /// by a third party can not use up the transfer count of `from`.
+    pub fn record_outbound(
+        &mut self,
+        exemptions: &FeeExemptions,
+        from: Address,
+        to: Address,
+        value: U256,
+    ) -> Result<(), Erc20RateLimitError> {
+        if !moves_out(from, to, value) {
+            return Ok(());
+        }
+        let window = hostio::read(|| self.window.get());
+        // not configured yet
+        if window == U256::ZERO || hostio::read(|| exemptions.is_exempt(from)) {
+            return Ok(());
+        }
+
+        let now = U256::from(block::timestamp());
+        let mut sent = hostio::read(|| self.window_amount.get(from));
+        let mut count = hostio::read(|| self.window_count.get(from));
+        if window_elapsed(now, hostio::read(|| self.window_start.get(from)), window) {
+            hostio::write(|| self.window_start.insert(from, now));
+            sent = U256::ZERO;
+            count = U256::ZERO;
+        }
+
+        let (sent, count) = check_outbound(
+            from,
+            sent,
+            count,
+            value,
+            hostio::read(|| self.max_amount_per_window.get()),
+            hostio::read(|| self.max_transfers_per_window.get()),
+        )?;
+        hostio::write(|| self.window_amount.insert(from, sent));
+        hostio::write(|| self.window_count.insert(from, count));
+        Ok(())
+    }
+}
+
+#[external]
+impl Erc20RateLimit {
+    /// Returns `(max_amount_per_window, max_transfers_per_window, window)`.
+    #[selector(name = "rateLimit")]
+    pub fn rate_limit(&self) -> Result<(U256, U256, U256), Erc20RateLimitError> {
+        Ok((
+            self.max_amount_per_window.get(),
+            self.max_transfers_per_window.get(),
+            self.window.get(),
+        ))
+    }
+
+    /// Returns `(amount, transfers)` that `account` can still send in its current window.
+    /// A disabled limit is reported as the maximum `uint256`, exemptions are not taken into
+    /// account (see `isFeeExempt`).
+    #[selector(name = "remainingOutbound")]
+    pub fn remaining_outbound(&self, account: Address) -> Result<(U256, U256), Erc20RateLimitError> {
+        let window = self.window.get();
+        if window == U256::ZERO {
+            return Ok((U256::MAX, U256::MAX));
+        }
+
+        let now = U256::from(block::timestamp());
+        let (sent, count) = if window_elapsed(now, self.window_start.get(account), window) {
+            (U256::ZERO, U256::ZERO)
+        } else {
+            (self.window_amount.get(account), self.window_count.get(account))
+        };
+
+        let remaining = |limit: U256, used: U256| {
+            if limit == U256::ZERO {
+                U256::MAX
+            } else {
+                limit.saturating_sub(used)
+            }
+        };
+        Ok((
+            remaining(self.max_amount_per_window.get(), sent),
+            remaining(self.max_transfers_per_window.get(), count),
+        ))
+    }
+}
+
+/// Whether the window of `window` seconds started at `window_start` elapsed at `now`. A window
+/// ending past the largest `uint256` timestamp never elapses.
+pub fn window_elapsed(now: U256, window_start: U256, window: U256) -> bool {
+    now >= window_start.saturating_add(window)
+}
+
+/// Amount and count used by `account` in its window once it sends `value` more, `sent` and `count`
+/// being used so far; reverts above `max_amount` or `max_transfers`, a zero limit being disabled.
+/// Usage saturates at the largest `uint256`, above any limit.
+pub fn check_outbound(
+    account: Address,
+    sent: U256,
+    count: U256,
+    value: U256,
+    max_amount: U256,
+    max_transfers: U256,
+) -> Result<(U256, U256), Erc20RateLimitError> {
+    let sent = sent.saturating_add(value);
+    if max_amount != U256::ZERO && sent > max_amount {
+        return Err(Erc20RateLimitError::Erc20RateLimitAmountExceeded(
+            Erc20RateLimitAmountExceeded {
+                account,
+                sent,
+                limit: max_amount,
+            },
+        ));
+    }
+    if max_transfers != U256::ZERO && count >= max_transfers {
+        return Err(Erc20RateLimitError::Erc20RateLimitCountExceeded(
+            Erc20RateLimitCountExceeded {
+                account,
+                limit: max_transfers,
+            },
+        ));
+    }
+    Ok((sent, count.saturating_add(U256::from(1))))
+}
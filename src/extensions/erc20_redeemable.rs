@@ -0,0 +1,146 @@
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    alloy_sol_types::{sol, SolError},
+    evm, msg,
+    prelude::*,
+};
+
+use crate::access::access_control::{role_id, AccessControl};
+use crate::tokens::erc20::{Erc20, Erc20Params};
+use crate::utils::math::{self, Rounding};
+use crate::utils::safe_erc20;
+
+/// 1e18, precision of the redemption rate.
+pub const RATE_PRECISION: U256 = U256::from_limbs([1_000_000_000_000_000_000, 0, 0, 0]);
+
+/// Name of the role updating the redemption rate, see `role_id`.
+pub const RATE_SETTER_ROLE: &str = "RATE_SETTER_ROLE";
+
+sol_storage! {
+    /// Burn-to-redeem against an external reserve ERC20 held by the contract, at a stored
+    /// exchange rate maintained by the holders of `RATE_SETTER_ROLE` (oracle, treasury
+    /// multisig...) in the token's `AccessControl`. Building block for wrapped and backed assets.
+    ///
+    /// The composing token exposes `redeem` passing its `Erc20` and `set_redemption_rate` passing
+    /// its `AccessControl`; the `_internal` variant skips the role check for the composing token.
+    /// See `examples/compose_redeemable.rs` for a complete composition.
+    pub struct Erc20Redeemable {
+        /// ERC20 paid out on redeem
+        address reserve_token;
+        /// reserve units paid per 1e18 token units, 0 disables redemptions
+        uint256 redemption_rate;
+    }
+}
+
+sol! {
+    event RedemptionRateUpdated(uint256 previous_rate, uint256 new_rate);
+    event ReserveTokenUpdated(address indexed reserve_token);
+    event RedeemedForReserve(address indexed account, uint256 amount, uint256 reserve_amount);
+
+    /// Indicates a redeem while no rate or reserve token is configured.
+    error Erc20RedemptionDisabled();
+
+    /// Indicates that the reserve amount for `amount` tokens overflows.
+    error Erc20RedemptionOverflow(uint256 amount);
+}
+
+pub enum Erc20RedeemableError {
+    Erc20RedemptionDisabled(Erc20RedemptionDisabled),
+    Erc20RedemptionOverflow(Erc20RedemptionOverflow),
+}
+
+impl From<Erc20RedeemableError> for Vec<u8> {
+    fn from(e: Erc20RedeemableError) -> Vec<u8> {
+        match e {
+            Erc20RedeemableError::Erc20RedemptionDisabled(e) => e.encode(),
+            Erc20RedeemableError::Erc20RedemptionOverflow(e) => e.encode(),
+        }
+    }
+}
+
+crate::errors::error_helpers!(Erc20RedeemableError {
+    Erc20RedemptionDisabled,
+    Erc20RedemptionOverflow,
+});
+
+/// Reserve amount paid for redeeming `amount` tokens at `rate` reserve units per 1e18 tokens,
+/// rounded down in favour of the reserve.
+pub fn reserve_amount_at(amount: U256, rate: U256) -> Result<U256, Erc20RedeemableError> {
+    math::mul_div(amount, rate, RATE_PRECISION, Rounding::Down)
+        .map_err(|_| Erc20RedeemableError::Erc20RedemptionOverflow(Erc20RedemptionOverflow { amount }))
+}
+
+impl Erc20Redeemable {
+    /// Updates the redemption rate, the caller needs `RATE_SETTER_ROLE`.
+    ///
+    /// Emits a {RedemptionRateUpdated} event.
+    pub fn set_redemption_rate(&mut self, access: &AccessControl, new_rate: U256) -> Result<(), Vec<u8>> {
+        access.only_role(role_id(RATE_SETTER_ROLE))?;
+        self.set_redemption_rate_internal(new_rate);
+        Ok(())
+    }
+
+    /// Updates the redemption rate without authorization.
+    ///
+    /// Emits a {RedemptionRateUpdated} event.
+    pub fn set_redemption_rate_internal(&mut self, new_rate: U256) {
+        let previous_rate = self.redemption_rate.get();
+        self.redemption_rate.set(new_rate);
+        evm::log(RedemptionRateUpdated {
+            previous_rate,
+            new_rate,
+        });
+    }
+
+    /// Emits a {ReserveTokenUpdated} event.
+    pub fn set_reserve_token(&mut self, reserve_token: Address) {
+        self.reserve_token.set(reserve_token);
+        evm::log(ReserveTokenUpdated { reserve_token });
+    }
+
+    /// Burns `amount` tokens of the caller and transfers `amount * rate / 1e18` reserve tokens
+    /// to the caller, returns the reserve amount.
+    ///
+    /// Emits a {Transfer} and a {RedeemedForReserve} event.
+    pub fn redeem<T: Erc20Params>(&mut self, erc20: &mut Erc20<T>, amount: U256) -> Result<U256, Vec<u8>> {
+        let reserve_token = self.reserve_token.get();
+        if reserve_token == Address::ZERO || self.redemption_rate.get() == U256::ZERO {
+            return Err(Erc20RedeemableError::Erc20RedemptionDisabled(Erc20RedemptionDisabled {}).into());
+        }
+        let account = msg::sender();
+        let reserve_amount = self.reserve_amount(amount)?;
+        erc20.burn(account, amount)?;
+        safe_erc20::safe_transfer(reserve_token, account, reserve_amount)?;
+
+        evm::log(RedeemedForReserve {
+            account,
+            amount,
+            reserve_amount,
+        });
+        Ok(reserve_amount)
+    }
+
+    fn reserve_amount(&self, amount: U256) -> Result<U256, Erc20RedeemableError> {
+        reserve_amount_at(amount, self.redemption_rate.get())
+    }
+}
+
+#[external]
+impl Erc20Redeemable {
+    #[selector(name = "reserveToken")]
+    pub fn reserve_token(&self) -> Result<Address, Erc20RedeemableError> {
+        Ok(self.reserve_token.get())
+    }
+
+    #[selector(name = "redemptionRate")]
+    pub fn redemption_rate(&self) -> Result<U256, Erc20RedeemableError> {
+        Ok(self.redemption_rate.get())
+    }
+
+    /// Returns the reserve amount paid for redeeming `amount` tokens at the current rate.
+    #[selector(name = "previewRedeem")]
+    pub fn preview_redeem(&self, amount: U256) -> Result<U256, Erc20RedeemableError> {
+        self.reserve_amount(amount)
+    }
+}
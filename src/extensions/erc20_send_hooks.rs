@@ -0,0 +1,305 @@
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    alloy_sol_types::{sol, SolError},
+    call::{self, Call},
+    evm, msg,
+    prelude::*,
+    types::AddressVM,
+};
+
+use crate::tokens::erc20::{Erc20, Erc20Error, Erc20InvalidReceiver, Erc20InvalidSpender, Erc20Params};
+use crate::utils::eip712::{address_word, u256_word};
+use crate::utils::erc165::{self, IERC777_RECIPIENT, IERC777_SENDER};
+use crate::utils::transfer_checks::encode_bytes;
+
+sol_storage! {
+    /// ERC777 style send hooks and operators for projects migrating ERC777 systems to an ERC20
+    /// base. Before tokens leave `from` its `tokensToSend` hook is called, once they arrived the
+    /// `tokensReceived` hook of `to`, with the ERC777 arguments (operator, from, to, amount,
+    /// data, operatorData).
+    ///
+    /// There is no ERC-1820 registry to rely on, hooks are found per token: the implementer an
+    /// account registered here with `setTokensSender`/`setTokensRecipient` (EOAs delegating to a
+    /// contract, like `setInterfaceImplementer`), else the account itself when it reports
+    /// `IERC777Sender`/`IERC777Recipient` through ERC-165. Hooks are called from the token, a hook
+    /// calling back into it reverts as every reentrant call of a Stylus program.
+    ///
+    /// The composing token moves tokens with `send` from its externals: `send`/`operatorSend`
+    /// with `require_receiver` (a contract receiving without a hook reverts, as ERC777 `send`),
+    /// `transfer`/`transferFrom` without. See `examples/compose_send_hooks.rs` for a complete
+    /// composition.
+    pub struct Erc20SendHooks {
+        /// holder => operator => authorized
+        mapping(address => mapping(address => bool)) operators;
+        /// account => implementer of its `tokensToSend`
+        mapping(address => address) senders;
+        /// account => implementer of its `tokensReceived`
+        mapping(address => address) recipients;
+    }
+}
+
+sol! {
+    event Sent(address indexed operator, address indexed from, address indexed to, uint256 amount, bytes data, bytes operatorData);
+    event AuthorizedOperator(address indexed operator, address indexed holder);
+    event RevokedOperator(address indexed operator, address indexed holder);
+    event TokensSenderSet(address indexed account, address implementer);
+    event TokensRecipientSet(address indexed account, address implementer);
+
+    /// Indicates `operator` moving tokens of `holder` without being its operator.
+    error Erc20SendHooksUnauthorizedOperator(address operator, address holder);
+
+    /// Indicates a holder authorizing or revoking itself as operator.
+    error Erc20SendHooksInvalidOperator(address operator);
+
+    /// Indicates a `send` to a contract without `tokensReceived` hook.
+    error Erc20SendHooksMissingRecipientHook(address recipient);
+
+    /// Indicates a hook of `implementer` reverting without a reason.
+    error Erc20SendHooksRejected(address implementer);
+}
+
+pub enum Erc20SendHooksError {
+    Erc20SendHooksUnauthorizedOperator(Erc20SendHooksUnauthorizedOperator),
+    Erc20SendHooksInvalidOperator(Erc20SendHooksInvalidOperator),
+    Erc20SendHooksMissingRecipientHook(Erc20SendHooksMissingRecipientHook),
+    Erc20SendHooksRejected(Erc20SendHooksRejected),
+    /// revert data of a hook, passed through
+    Reverted(Vec<u8>),
+}
+
+impl From<Erc20SendHooksError> for Vec<u8> {
+    fn from(e: Erc20SendHooksError) -> Vec<u8> {
+        match e {
+            Erc20SendHooksError::Erc20SendHooksUnauthorizedOperator(e) => e.encode(),
+            Erc20SendHooksError::Erc20SendHooksInvalidOperator(e) => e.encode(),
+            Erc20SendHooksError::Erc20SendHooksMissingRecipientHook(e) => e.encode(),
+            Erc20SendHooksError::Erc20SendHooksRejected(e) => e.encode(),
+            Erc20SendHooksError::Reverted(data) => data,
+        }
+    }
+}
+
+crate::errors::error_helpers!(Erc20SendHooksError {
+    Erc20SendHooksUnauthorizedOperator,
+    Erc20SendHooksInvalidOperator,
+    Erc20SendHooksMissingRecipientHook,
+    Erc20SendHooksRejected,
+} passthrough { Reverted });
+
+/// `bytes4(keccak256("tokensToSend(address,address,address,uint256,bytes,bytes)"))`
+pub const TOKENS_TO_SEND_SELECTOR: [u8; 4] = IERC777_SENDER;
+/// `bytes4(keccak256("tokensReceived(address,address,address,uint256,bytes,bytes)"))`
+pub const TOKENS_RECEIVED_SELECTOR: [u8; 4] = IERC777_RECIPIENT;
+
+impl Erc20SendHooks {
+    /// Moves `amount` tokens from `from` to `to` on behalf of `operator` (`from` itself for a
+    /// plain send) through `Erc20::update`, calling the hooks of both sides around it.
+    /// `require_receiver` reverts a send to a contract without `tokensReceived` hook.
+    ///
+    /// Emits a {Transfer} and a {Sent} event.
+    #[allow(clippy::too_many_arguments)]
+    pub fn send<T: Erc20Params>(
+        &self,
+        erc20: &mut Erc20<T>,
+        operator: Address,
+        from: Address,
+        to: Address,
+        amount: U256,
+        data: &[u8],
+        operator_data: &[u8],
+        require_receiver: bool,
+    ) -> Result<(), Vec<u8>> {
+        if from == Address::ZERO {
+            return Err(Erc20Error::Erc20InvalidSpender(Erc20InvalidSpender {
+                spender: Address::ZERO,
+            })
+            .into());
+        }
+        if to == Address::ZERO {
+            return Err(Erc20Error::Erc20InvalidReceiver(Erc20InvalidReceiver {
+                receiver: Address::ZERO,
+            })
+            .into());
+        }
+        if let Some(implementer) = self.tokens_sender(from) {
+            let calldata = hook_calldata(TOKENS_TO_SEND_SELECTOR, operator, from, to, amount, data, operator_data);
+            call_hook(implementer, &calldata)?;
+        }
+        erc20.update(from, to, amount)?;
+        evm::log(Sent {
+            operator,
+            from,
+            to,
+            amount,
+            data: data.to_vec(),
+            operatorData: operator_data.to_vec(),
+        });
+        match self.tokens_recipient(to) {
+            Some(implementer) => {
+                let calldata = hook_calldata(TOKENS_RECEIVED_SELECTOR, operator, from, to, amount, data, operator_data);
+                call_hook(implementer, &calldata)?;
+            }
+            None if require_receiver && to.has_code() => {
+                return Err(Erc20SendHooksError::Erc20SendHooksMissingRecipientHook(
+                    Erc20SendHooksMissingRecipientHook { recipient: to },
+                )
+                .into());
+            }
+            None => {}
+        }
+        Ok(())
+    }
+
+    /// `send` by `operator` of `from`'s tokens, reverting unless it is an operator of `from`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn operator_send<T: Erc20Params>(
+        &self,
+        erc20: &mut Erc20<T>,
+        operator: Address,
+        from: Address,
+        to: Address,
+        amount: U256,
+        data: &[u8],
+        operator_data: &[u8],
+    ) -> Result<(), Vec<u8>> {
+        if !self.is_operator_for_internal(operator, from) {
+            return Err(Erc20SendHooksError::Erc20SendHooksUnauthorizedOperator(
+                Erc20SendHooksUnauthorizedOperator { operator, holder: from },
+            )
+            .into());
+        }
+        self.send(erc20, operator, from, to, amount, data, operator_data, true)
+    }
+
+    /// Whether `operator` moves `holder`'s tokens, every holder is its own operator.
+    pub fn is_operator_for_internal(&self, operator: Address, holder: Address) -> bool {
+        operator == holder || self.operators.getter(holder).get(operator)
+    }
+
+    /// Lets `operator` send any amount of `holder`'s tokens.
+    ///
+    /// Emits an {AuthorizedOperator} event.
+    pub fn authorize_operator_internal(&mut self, holder: Address, operator: Address) -> Result<(), Erc20SendHooksError> {
+        if operator == holder {
+            return Err(Erc20SendHooksError::Erc20SendHooksInvalidOperator(
+                Erc20SendHooksInvalidOperator { operator },
+            ));
+        }
+        self.operators.setter(holder).insert(operator, true);
+        evm::log(AuthorizedOperator { operator, holder });
+        Ok(())
+    }
+
+    /// Emits a {RevokedOperator} event.
+    pub fn revoke_operator_internal(&mut self, holder: Address, operator: Address) -> Result<(), Erc20SendHooksError> {
+        if operator == holder {
+            return Err(Erc20SendHooksError::Erc20SendHooksInvalidOperator(
+                Erc20SendHooksInvalidOperator { operator },
+            ));
+        }
+        self.operators.setter(holder).insert(operator, false);
+        evm::log(RevokedOperator { operator, holder });
+        Ok(())
+    }
+
+    /// Implementer of the `tokensToSend` hook of `account`, `None` when it has none.
+    pub fn tokens_sender(&self, account: Address) -> Option<Address> {
+        hook_implementer(self.senders.get(account), account, IERC777_SENDER)
+    }
+
+    /// Implementer of the `tokensReceived` hook of `account`, `None` when it has none.
+    pub fn tokens_recipient(&self, account: Address) -> Option<Address> {
+        hook_implementer(self.recipients.get(account), account, IERC777_RECIPIENT)
+    }
+}
+
+/// ERC777 names are pinned, composers expose `send`/`operatorSend` themselves.
+#[external]
+impl Erc20SendHooks {
+    #[selector(name = "authorizeOperator")]
+    pub fn authorize_operator(&mut self, operator: Address) -> Result<(), Erc20SendHooksError> {
+        self.authorize_operator_internal(msg::sender(), operator)
+    }
+
+    #[selector(name = "revokeOperator")]
+    pub fn revoke_operator(&mut self, operator: Address) -> Result<(), Erc20SendHooksError> {
+        self.revoke_operator_internal(msg::sender(), operator)
+    }
+
+    #[selector(name = "isOperatorFor")]
+    pub fn is_operator_for(&self, operator: Address, holder: Address) -> Result<bool, Erc20SendHooksError> {
+        Ok(self.is_operator_for_internal(operator, holder))
+    }
+
+    /// Registers `implementer` (zero to unregister) as the `tokensToSend` hook of the caller.
+    #[selector(name = "setTokensSender")]
+    pub fn set_tokens_sender(&mut self, implementer: Address) -> Result<(), Erc20SendHooksError> {
+        let account = msg::sender();
+        self.senders.insert(account, implementer);
+        evm::log(TokensSenderSet { account, implementer });
+        Ok(())
+    }
+
+    /// Registers `implementer` (zero to unregister) as the `tokensReceived` hook of the caller.
+    #[selector(name = "setTokensRecipient")]
+    pub fn set_tokens_recipient(&mut self, implementer: Address) -> Result<(), Erc20SendHooksError> {
+        let account = msg::sender();
+        self.recipients.insert(account, implementer);
+        evm::log(TokensRecipientSet { account, implementer });
+        Ok(())
+    }
+
+    #[selector(name = "tokensSenderOf")]
+    pub fn tokens_sender_of(&self, account: Address) -> Result<Address, Erc20SendHooksError> {
+        Ok(self.tokens_sender(account).unwrap_or(Address::ZERO))
+    }
+
+    #[selector(name = "tokensRecipientOf")]
+    pub fn tokens_recipient_of(&self, account: Address) -> Result<Address, Erc20SendHooksError> {
+        Ok(self.tokens_recipient(account).unwrap_or(Address::ZERO))
+    }
+}
+
+/// Calldata of `tokensToSend`/`tokensReceived(operator, from, to, amount, data, operatorData)`.
+pub fn hook_calldata(
+    selector: [u8; 4],
+    operator: Address,
+    from: Address,
+    to: Address,
+    amount: U256,
+    data: &[u8],
+    operator_data: &[u8],
+) -> Vec<u8> {
+    // tails follow the 6 head words: data, then operatorData (length word plus padded bytes each)
+    let data_offset = 6 * 32;
+    let operator_data_offset = data_offset + 32 + data.len().div_ceil(32) * 32;
+
+    let mut calldata = selector.to_vec();
+    for account in [operator, from, to] {
+        calldata.extend_from_slice(address_word(account).as_slice());
+    }
+    calldata.extend_from_slice(u256_word(amount).as_slice());
+    for offset in [data_offset, operator_data_offset] {
+        calldata.extend_from_slice(u256_word(U256::from(offset)).as_slice());
+    }
+    encode_bytes(&mut calldata, data);
+    encode_bytes(&mut calldata, operator_data);
+    calldata
+}
+
+/// The registered implementer, else `account` when it reports `interface_id` through ERC-165.
+fn hook_implementer(registered: Address, account: Address, interface_id: [u8; 4]) -> Option<Address> {
+    if registered != Address::ZERO {
+        return Some(registered);
+    }
+    erc165::supports_interface_of(account, interface_id).then_some(account)
+}
+
+fn call_hook(implementer: Address, calldata: &[u8]) -> Result<(), Erc20SendHooksError> {
+    match call::call(Call::new(), implementer, calldata) {
+        Ok(_) => Ok(()),
+        Err(call::Error::Revert(reason)) if !reason.is_empty() => Err(Erc20SendHooksError::Reverted(reason)),
+        Err(_) => Err(Erc20SendHooksError::Erc20SendHooksRejected(Erc20SendHooksRejected { implementer })),
+    }
+}
@@ -0,0 +1,201 @@
+//! Permit2 style signature transfers of the token itself: the owner signs a one time
+//! `PermitTransferFrom` (optionally bound to witness data) and the signed spender moves the
+//! tokens without a prior `approve`.
+//!
+//! The functions take the token's `Erc20` and shared `ReplayGuard`, expose them from the composing
+//! token e.g. `erc20_signature_transfer::permit_witness_transfer_from(&mut self.erc20, &mut self.replay_guard, ...)`.
+//! See `examples/compose_signature_transfer.rs` for a complete composition.
+use alloc::{string::String, vec::Vec};
+use stylus_sdk::{
+    alloy_primitives::{Address, B256, U256},
+    alloy_sol_types::{sol, SolError},
+    block, contract,
+    crypto::keccak,
+    msg,
+};
+
+use crate::tokens::erc20::{Erc20, Erc20Params};
+use crate::utils::{
+    ecdsa,
+    eip712::{self, address_word, hash_words, u256_word},
+    replay_guard::{NonceMode, ReplayGuard},
+};
+
+/// EIP-712 version of the signature transfer domain, the shared token domain of `utils::eip712`.
+pub const SIGNATURE_TRANSFER_VERSION: &str = eip712::DOMAIN_VERSION;
+
+pub const TOKEN_PERMISSIONS_TYPE: &str = "TokenPermissions(address token,uint256 amount)";
+
+pub const PERMIT_TRANSFER_FROM_TYPE: &str = "PermitTransferFrom(TokenPermissions permitted,address spender,uint256 nonce,uint256 deadline)TokenPermissions(address token,uint256 amount)";
+
+/// Prefix of the witness typed permit, completed by the witness type string given by the
+/// integrator, e.g. `"Order witness)Order(address user,uint256 price)TokenPermissions(address token,uint256 amount)"`.
+pub const PERMIT_WITNESS_TRANSFER_FROM_TYPE_STUB: &str = "PermitWitnessTransferFrom(TokenPermissions permitted,address spender,uint256 nonce,uint256 deadline,";
+
+/// Signature transfers use unordered nonces, any unused nonce is valid.
+pub const NONCE_MODE: NonceMode = NonceMode::Unordered;
+
+sol! {
+    /// Indicates that the signature deadline has passed.
+    /// * `deadline` - signed deadline.
+    error Erc20SignatureExpired(uint256 deadline);
+
+    /// Indicates that the signature was not signed by `owner`.
+    /// * `signer` - recovered signer.
+    /// * `owner` - owner of the transferred tokens.
+    error Erc20InvalidSigner(address signer, address owner);
+
+    /// Indicates that the spender requested more than the signed amount.
+    /// * `max_amount` - signed amount.
+    error Erc20InvalidRequestedAmount(uint256 max_amount);
+}
+
+pub enum Erc20SignatureTransferError {
+    Erc20SignatureExpired(Erc20SignatureExpired),
+    Erc20InvalidSigner(Erc20InvalidSigner),
+    Erc20InvalidRequestedAmount(Erc20InvalidRequestedAmount),
+}
+
+impl From<Erc20SignatureTransferError> for Vec<u8> {
+    fn from(e: Erc20SignatureTransferError) -> Vec<u8> {
+        match e {
+            Erc20SignatureTransferError::Erc20SignatureExpired(e) => e.encode(),
+            Erc20SignatureTransferError::Erc20InvalidSigner(e) => e.encode(),
+            Erc20SignatureTransferError::Erc20InvalidRequestedAmount(e) => e.encode(),
+        }
+    }
+}
+
+crate::errors::error_helpers!(Erc20SignatureTransferError {
+    Erc20SignatureExpired,
+    Erc20InvalidSigner,
+    Erc20InvalidRequestedAmount,
+});
+
+/// Signed part of a permit transfer, the spender is always `msg::sender()`.
+pub struct PermitTransferFrom {
+    /// max amount the spender can transfer
+    pub amount: U256,
+    pub nonce: U256,
+    pub deadline: U256,
+}
+
+/// Requested part of a permit transfer, chosen by the spender at execution.
+pub struct TransferDetails {
+    pub to: Address,
+    /// must be at most the permitted amount
+    pub requested_amount: U256,
+}
+
+/// Transfers tokens of `owner` using a signed `PermitTransferFrom`.
+///
+/// Emits a {Transfer} event.
+pub fn permit_transfer_from<T: Erc20Params>(
+    erc20: &mut Erc20<T>,
+    replay_guard: &mut ReplayGuard,
+    permit: PermitTransferFrom,
+    details: TransferDetails,
+    owner: Address,
+    signature: &[u8],
+) -> Result<(), Vec<u8>> {
+    let struct_hash = permit_transfer_from_hash(contract::address(), msg::sender(), &permit);
+    transfer_with_struct_hash(erc20, replay_guard, struct_hash, permit, details, owner, signature)
+}
+
+/// Transfers tokens of `owner` using a signed `PermitWitnessTransferFrom`, binding extra
+/// data of the integrator (order, intent...) to the transfer signature.
+/// * `witness` - EIP-712 hash of the witness struct.
+/// * `witness_type_string` - EIP-712 type string of the witness, starting with the witness
+///   member declaration, e.g. `"Order witness)Order(...)TokenPermissions(address token,uint256 amount)"`.
+///
+/// Emits a {Transfer} event.
+#[allow(clippy::too_many_arguments)]
+pub fn permit_witness_transfer_from<T: Erc20Params>(
+    erc20: &mut Erc20<T>,
+    replay_guard: &mut ReplayGuard,
+    permit: PermitTransferFrom,
+    details: TransferDetails,
+    owner: Address,
+    witness: B256,
+    witness_type_string: &str,
+    signature: &[u8],
+) -> Result<(), Vec<u8>> {
+    let mut type_string = String::from(PERMIT_WITNESS_TRANSFER_FROM_TYPE_STUB);
+    type_string.push_str(witness_type_string);
+
+    let struct_hash = hash_words(&[
+        keccak(type_string.as_bytes()),
+        token_permissions_hash(contract::address(), permit.amount),
+        address_word(msg::sender()),
+        u256_word(permit.nonce),
+        u256_word(permit.deadline),
+        witness,
+    ]);
+    transfer_with_struct_hash(erc20, replay_guard, struct_hash, permit, details, owner, signature)
+}
+
+/// EIP-712 struct hash of a `PermitTransferFrom` of `token` signed for `spender`, without host
+/// calls so off-chain tooling (see the `test_vectors` binary) computes the same digests.
+pub fn permit_transfer_from_hash(token: Address, spender: Address, permit: &PermitTransferFrom) -> B256 {
+    hash_words(&[
+        keccak(PERMIT_TRANSFER_FROM_TYPE.as_bytes()),
+        token_permissions_hash(token, permit.amount),
+        address_word(spender),
+        u256_word(permit.nonce),
+        u256_word(permit.deadline),
+    ])
+}
+
+/// Domain separator of the token, see `utils::eip712` for the shared domain.
+pub fn domain_separator<T: Erc20Params>() -> B256 {
+    eip712::erc20_domain_separator::<T>()
+}
+
+fn transfer_with_struct_hash<T: Erc20Params>(
+    erc20: &mut Erc20<T>,
+    replay_guard: &mut ReplayGuard,
+    struct_hash: B256,
+    permit: PermitTransferFrom,
+    details: TransferDetails,
+    owner: Address,
+    signature: &[u8],
+) -> Result<(), Vec<u8>> {
+    if U256::from(block::timestamp()) > permit.deadline {
+        return Err(Erc20SignatureTransferError::Erc20SignatureExpired(
+            Erc20SignatureExpired {
+                deadline: permit.deadline,
+            },
+        )
+        .into());
+    }
+    if details.requested_amount > permit.amount {
+        return Err(Erc20SignatureTransferError::Erc20InvalidRequestedAmount(
+            Erc20InvalidRequestedAmount {
+                max_amount: permit.amount,
+            },
+        )
+        .into());
+    }
+    replay_guard.consume(NONCE_MODE, owner, permit.nonce)?;
+
+    let hash = eip712::to_typed_data_hash(domain_separator::<T>(), struct_hash);
+    let signer = ecdsa::recover_bytes(hash, signature)?;
+    if signer != owner {
+        return Err(Erc20SignatureTransferError::Erc20InvalidSigner(Erc20InvalidSigner {
+            signer,
+            owner,
+        })
+        .into());
+    }
+
+    erc20.transfer_internal(owner, details.to, details.requested_amount)?;
+    Ok(())
+}
+
+fn token_permissions_hash(token: Address, amount: U256) -> B256 {
+    hash_words(&[
+        keccak(TOKEN_PERMISSIONS_TYPE.as_bytes()),
+        address_word(token),
+        u256_word(amount),
+    ])
+}
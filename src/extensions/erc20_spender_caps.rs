@@ -0,0 +1,128 @@
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    alloy_sol_types::{sol, SolError},
+    evm, msg,
+    prelude::*,
+};
+
+use crate::tokens::erc20::{Erc20, Erc20Params};
+
+sol_storage! {
+    /// Hard cap, set by the owner, on the cumulative amount a spender can move with its allowance,
+    /// whatever the allowances given to it. Topping up an allowance (or an infinite approval) does
+    /// not raise the cap, so a buggy or compromised protocol can only drain up to it.
+    ///
+    /// Compose next to `Erc20` and override `transfer_from` to call `spend_allowance` of this
+    /// extension instead of the `Erc20` one. `Erc20` can not reach this storage, so the cap only
+    /// covers the paths going through this `spend_allowance`: every other external spending an
+    /// allowance must be overridden the same way, or the spender bypasses its cap there. With
+    /// `Erc4626` that is `withdraw` and `redeem` by a spender, which spend share allowances with
+    /// `Erc20::spend_allowance`; `Erc20TemporaryApproval::spend_allowance` and `burnFrom` style
+    /// externals need `record_spend` before them.
+    /// See `examples/compose_spender_caps.rs` for a complete composition.
+    pub struct Erc20SpenderCaps {
+        /// owner => spender => max cumulative amount spent, 0 for no cap
+        mapping(address => mapping(address => uint256)) caps;
+        /// owner => spender => cumulative amount spent while capped
+        mapping(address => mapping(address => uint256)) spent;
+    }
+}
+
+sol! {
+    event SpenderCapSet(address indexed owner, address indexed spender, uint256 cap);
+
+    /// Indicates that `spender` would spend more than the cap set by `owner`.
+    /// * `spent` - cumulative amount the spender would have spent including this transfer.
+    /// * `cap` - max cumulative amount.
+    error Erc20SpenderCapExceeded(address owner, address spender, uint256 spent, uint256 cap);
+}
+
+pub enum Erc20SpenderCapsError {
+    Erc20SpenderCapExceeded(Erc20SpenderCapExceeded),
+}
+
+impl From<Erc20SpenderCapsError> for Vec<u8> {
+    fn from(e: Erc20SpenderCapsError) -> Vec<u8> {
+        match e {
+            Erc20SpenderCapsError::Erc20SpenderCapExceeded(e) => e.encode(),
+        }
+    }
+}
+
+crate::errors::error_helpers!(Erc20SpenderCapsError {
+    Erc20SpenderCapExceeded,
+});
+
+impl Erc20SpenderCaps {
+    /// Accounts `value` tokens of `owner` moved by `spender`, reverting if it would exceed the cap.
+    /// Spending of uncapped spenders is not tracked.
+    pub fn record_spend(&mut self, owner: Address, spender: Address, value: U256) -> Result<(), Erc20SpenderCapsError> {
+        let cap = self.caps.get(owner).get(spender);
+        if cap == U256::ZERO {
+            return Ok(());
+        }
+        let mut spent_ref = self.spent.setter(owner).setter(spender);
+        let spent = capped_spend(owner, spender, spent_ref.get(), value, cap)?;
+        spent_ref.set(spent);
+        Ok(())
+    }
+
+    /// Spends `value` of the allowance of `spender` like `Erc20::spend_allowance`, after
+    /// recording it against the cap set by `owner`.
+    pub fn spend_allowance<T: Erc20Params>(
+        &mut self,
+        erc20: &mut Erc20<T>,
+        owner: Address,
+        spender: Address,
+        value: U256,
+    ) -> Result<(), Vec<u8>> {
+        self.record_spend(owner, spender, value)?;
+        erc20.spend_allowance(owner, spender, value)?;
+        Ok(())
+    }
+}
+
+/// Cumulative amount spent by `spender` once `value` is added to `spent`, reverting above the
+/// (nonzero) `cap`. Saturates, a cap can not be passed by overflowing.
+pub fn capped_spend(
+    owner: Address,
+    spender: Address,
+    spent: U256,
+    value: U256,
+    cap: U256,
+) -> Result<U256, Erc20SpenderCapsError> {
+    let spent = spent.saturating_add(value);
+    if spent > cap {
+        return Err(Erc20SpenderCapsError::Erc20SpenderCapExceeded(
+            Erc20SpenderCapExceeded {
+                owner,
+                spender,
+                spent,
+                cap,
+            },
+        ));
+    }
+    Ok(spent)
+}
+
+#[external]
+impl Erc20SpenderCaps {
+    /// Returns `(cap, spent)` of `spender` over `owner`'s tokens, a zero cap means no cap.
+    #[selector(name = "spenderCap")]
+    pub fn spender_cap(&self, owner: Address, spender: Address) -> Result<(U256, U256), Erc20SpenderCapsError> {
+        Ok((self.caps.get(owner).get(spender), self.spent.get(owner).get(spender)))
+    }
+
+    /// Sets the max cumulative amount `spender` can move from the caller's account, 0 removes the
+    /// cap. The amount already spent is kept, a new cap lower than it blocks the spender.
+    ///
+    /// Emits a {SpenderCapSet} event.
+    #[selector(name = "setSpenderCap")]
+    pub fn set_spender_cap(&mut self, spender: Address, cap: U256) -> Result<(), Erc20SpenderCapsError> {
+        let owner = msg::sender();
+        self.caps.setter(owner).insert(spender, cap);
+        evm::log(SpenderCapSet { owner, spender, cap });
+        Ok(())
+    }
+}
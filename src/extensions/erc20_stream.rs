@@ -0,0 +1,304 @@
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    alloy_sol_types::{sol, SolError},
+    block, contract, evm, msg,
+    prelude::*,
+};
+
+use crate::tokens::erc20::{Erc20, Erc20Params};
+use crate::utils::math::{self, Rounding};
+
+sol_storage! {
+    /// Sablier style continuous payments in the token itself: `create_stream` escrows a deposit
+    /// of the sender on the contract address, the recipient earns it linearly every second
+    /// between `start` and `stop` and withdraws what it earned at any time. Either party cancels
+    /// a stream, the recipient is then paid what it earned and the sender refunded the rest.
+    ///
+    /// Earned amounts are `deposit * elapsed / duration` rounded down, so any deposit works
+    /// (Sablier v1 requires a multiple of the duration) and the last second releases the
+    /// rounding dust. Escrowed tokens are held by the contract address, the composing token must
+    /// not let anyone else move its balance.
+    ///
+    /// The composing token exposes `createStream`, `withdrawFromStream` and `cancelStream`
+    /// passing its `Erc20`. See `examples/compose_stream.rs` for a complete composition.
+    pub struct Erc20Stream {
+        /// id of the next stream, ids start at 1
+        uint256 next_stream_id;
+        mapping(uint256 => Stream) streams;
+    }
+
+    pub struct Stream {
+        address sender;
+        address recipient;
+        uint256 deposit;
+        uint256 start;
+        uint256 stop;
+        /// part of the deposit already paid to the recipient
+        uint256 withdrawn;
+    }
+}
+
+sol! {
+    event CreateStream(uint256 indexed stream_id, address indexed sender, address indexed recipient, uint256 deposit, uint256 start, uint256 stop);
+    event WithdrawFromStream(uint256 indexed stream_id, address indexed recipient, uint256 amount);
+    event CancelStream(uint256 indexed stream_id, address indexed sender, address indexed recipient, uint256 sender_balance, uint256 recipient_balance);
+
+    /// Indicates a recipient that can not receive a stream: zero, the sender or the contract.
+    error StreamInvalidRecipient(address recipient);
+
+    /// Indicates a zero deposit.
+    error StreamInvalidDeposit();
+
+    /// Indicates a start in the past or a stop not after the start.
+    error StreamInvalidTimeframe(uint256 start, uint256 stop);
+
+    /// Indicates an operation on a stream that does not exist or was canceled.
+    error StreamNotFound(uint256 stream_id);
+
+    /// Indicates a caller that is neither the sender nor the recipient of the stream.
+    error StreamUnauthorized(address caller, uint256 stream_id);
+
+    /// Indicates a withdrawal of more than the recipient earned.
+    error StreamInsufficientBalance(uint256 stream_id, uint256 available, uint256 requested);
+}
+
+pub enum Erc20StreamError {
+    StreamInvalidRecipient(StreamInvalidRecipient),
+    StreamInvalidDeposit(StreamInvalidDeposit),
+    StreamInvalidTimeframe(StreamInvalidTimeframe),
+    StreamNotFound(StreamNotFound),
+    StreamUnauthorized(StreamUnauthorized),
+    StreamInsufficientBalance(StreamInsufficientBalance),
+}
+
+impl From<Erc20StreamError> for Vec<u8> {
+    fn from(e: Erc20StreamError) -> Vec<u8> {
+        match e {
+            Erc20StreamError::StreamInvalidRecipient(e) => e.encode(),
+            Erc20StreamError::StreamInvalidDeposit(e) => e.encode(),
+            Erc20StreamError::StreamInvalidTimeframe(e) => e.encode(),
+            Erc20StreamError::StreamNotFound(e) => e.encode(),
+            Erc20StreamError::StreamUnauthorized(e) => e.encode(),
+            Erc20StreamError::StreamInsufficientBalance(e) => e.encode(),
+        }
+    }
+}
+
+crate::errors::error_helpers!(Erc20StreamError {
+    StreamInvalidRecipient,
+    StreamInvalidDeposit,
+    StreamInvalidTimeframe,
+    StreamNotFound,
+    StreamUnauthorized,
+    StreamInsufficientBalance,
+});
+
+impl Erc20Stream {
+    /// Streams `deposit` tokens of the caller to `recipient` from `start` to `stop`
+    /// (timestamps), returns the stream id.
+    ///
+    /// Emits a {Transfer} and a {CreateStream} event.
+    pub fn create_stream<T: Erc20Params>(
+        &mut self,
+        erc20: &mut Erc20<T>,
+        recipient: Address,
+        deposit: U256,
+        start: U256,
+        stop: U256,
+    ) -> Result<U256, Vec<u8>> {
+        let sender = msg::sender();
+        let escrow = contract::address();
+        if recipient == Address::ZERO || recipient == sender || recipient == escrow {
+            return Err(Erc20StreamError::StreamInvalidRecipient(StreamInvalidRecipient { recipient }).into());
+        }
+        if deposit == U256::ZERO {
+            return Err(Erc20StreamError::StreamInvalidDeposit(StreamInvalidDeposit {}).into());
+        }
+        if start < U256::from(block::timestamp()) || stop <= start {
+            return Err(Erc20StreamError::StreamInvalidTimeframe(StreamInvalidTimeframe { start, stop }).into());
+        }
+        erc20.transfer_internal(sender, escrow, deposit)?;
+
+        let stream_id = self.next_stream_id.get().max(U256::from(1));
+        self.next_stream_id.set(stream_id + U256::from(1));
+        let mut stream = self.streams.setter(stream_id);
+        stream.sender.set(sender);
+        stream.recipient.set(recipient);
+        stream.deposit.set(deposit);
+        stream.start.set(start);
+        stream.stop.set(stop);
+
+        evm::log(CreateStream {
+            stream_id,
+            sender,
+            recipient,
+            deposit,
+            start,
+            stop,
+        });
+        Ok(stream_id)
+    }
+
+    /// Pays `amount` earned tokens to the recipient of the stream, called by either party.
+    ///
+    /// Emits a {Transfer} and a {WithdrawFromStream} event.
+    pub fn withdraw_from_stream<T: Erc20Params>(
+        &mut self,
+        erc20: &mut Erc20<T>,
+        stream_id: U256,
+        amount: U256,
+    ) -> Result<(), Vec<u8>> {
+        let (_, recipient) = self.only_party(stream_id)?;
+        let available = self.recipient_balance(stream_id);
+        if amount > available {
+            return Err(Erc20StreamError::StreamInsufficientBalance(StreamInsufficientBalance {
+                stream_id,
+                available,
+                requested: amount,
+            })
+            .into());
+        }
+        let mut stream = self.streams.setter(stream_id);
+        let withdrawn = stream.withdrawn.get() + amount;
+        stream.withdrawn.set(withdrawn);
+        erc20.transfer_internal(contract::address(), recipient, amount)?;
+
+        evm::log(WithdrawFromStream {
+            stream_id,
+            recipient,
+            amount,
+        });
+        Ok(())
+    }
+
+    /// Ends the stream, called by either party: the recipient is paid what it earned and not
+    /// withdrawn yet, the sender is refunded the rest.
+    ///
+    /// Emits {Transfer} events and a {CancelStream} event.
+    pub fn cancel_stream<T: Erc20Params>(&mut self, erc20: &mut Erc20<T>, stream_id: U256) -> Result<(), Vec<u8>> {
+        let (sender, recipient) = self.only_party(stream_id)?;
+        let recipient_balance = self.recipient_balance(stream_id);
+        let sender_balance = {
+            let stream = self.streams.get(stream_id);
+            stream.deposit.get() - stream.withdrawn.get() - recipient_balance
+        };
+
+        let mut stream = self.streams.setter(stream_id);
+        stream.sender.set(Address::ZERO);
+        stream.recipient.set(Address::ZERO);
+        stream.deposit.set(U256::ZERO);
+        stream.start.set(U256::ZERO);
+        stream.stop.set(U256::ZERO);
+        stream.withdrawn.set(U256::ZERO);
+
+        let escrow = contract::address();
+        if recipient_balance > U256::ZERO {
+            erc20.transfer_internal(escrow, recipient, recipient_balance)?;
+        }
+        if sender_balance > U256::ZERO {
+            erc20.transfer_internal(escrow, sender, sender_balance)?;
+        }
+        evm::log(CancelStream {
+            stream_id,
+            sender,
+            recipient,
+            sender_balance,
+            recipient_balance,
+        });
+        Ok(())
+    }
+
+    /// Reverts unless the stream exists and the caller is one of its parties, returns
+    /// `(sender, recipient)`.
+    fn only_party(&self, stream_id: U256) -> Result<(Address, Address), Erc20StreamError> {
+        let stream = self.streams.get(stream_id);
+        let (sender, recipient) = (stream.sender.get(), stream.recipient.get());
+        if sender == Address::ZERO {
+            return Err(Erc20StreamError::StreamNotFound(StreamNotFound { stream_id }));
+        }
+        let caller = msg::sender();
+        if caller != sender && caller != recipient {
+            return Err(Erc20StreamError::StreamUnauthorized(StreamUnauthorized { caller, stream_id }));
+        }
+        Ok((sender, recipient))
+    }
+
+    /// Earned and not withdrawn tokens of the recipient.
+    fn recipient_balance(&self, stream_id: U256) -> U256 {
+        let stream = self.streams.get(stream_id);
+        let earned = streamed_amount(
+            stream.deposit.get(),
+            stream.start.get(),
+            stream.stop.get(),
+            U256::from(block::timestamp()),
+        );
+        earned - stream.withdrawn.get()
+    }
+}
+
+/// Sablier names are pinned, except `balanceOf(uint256,address)` renamed `streamBalanceOf` to
+/// keep a single `balanceOf` in the token ABI.
+#[external]
+impl Erc20Stream {
+    /// Returns `(sender, recipient, deposit, start, stop, withdrawn)` of the stream.
+    #[selector(name = "getStream")]
+    pub fn get_stream(&self, stream_id: U256) -> Result<(Address, Address, U256, U256, U256, U256), Erc20StreamError> {
+        let stream = self.streams.get(stream_id);
+        if stream.sender.get() == Address::ZERO {
+            return Err(Erc20StreamError::StreamNotFound(StreamNotFound { stream_id }));
+        }
+        Ok((
+            stream.sender.get(),
+            stream.recipient.get(),
+            stream.deposit.get(),
+            stream.start.get(),
+            stream.stop.get(),
+            stream.withdrawn.get(),
+        ))
+    }
+
+    /// Returns the tokens of `account` in the stream: earned and not withdrawn for the
+    /// recipient, not earned yet for the sender, 0 for anyone else.
+    #[selector(name = "streamBalanceOf")]
+    pub fn stream_balance_of(&self, stream_id: U256, account: Address) -> Result<U256, Erc20StreamError> {
+        let stream = self.streams.get(stream_id);
+        if stream.sender.get() == Address::ZERO {
+            return Err(Erc20StreamError::StreamNotFound(StreamNotFound { stream_id }));
+        }
+        let recipient_balance = self.recipient_balance(stream_id);
+        if account == stream.recipient.get() {
+            return Ok(recipient_balance);
+        }
+        if account == stream.sender.get() {
+            return Ok(stream.deposit.get() - stream.withdrawn.get() - recipient_balance);
+        }
+        Ok(U256::ZERO)
+    }
+
+    /// Returns the seconds of the stream elapsed so far, 0 before the start.
+    #[selector(name = "deltaOf")]
+    pub fn delta_of(&self, stream_id: U256) -> Result<U256, Erc20StreamError> {
+        let stream = self.streams.get(stream_id);
+        if stream.sender.get() == Address::ZERO {
+            return Err(Erc20StreamError::StreamNotFound(StreamNotFound { stream_id }));
+        }
+        Ok(elapsed(stream.start.get(), stream.stop.get(), U256::from(block::timestamp())))
+    }
+}
+
+/// Part of `deposit` earned at `now` by a stream from `start` to `stop`, rounded down.
+pub fn streamed_amount(deposit: U256, start: U256, stop: U256, now: U256) -> U256 {
+    let elapsed = elapsed(start, stop, now);
+    let duration = stop - start;
+    if elapsed == duration {
+        return deposit;
+    }
+    // elapsed < duration, below `deposit` so it can not overflow
+    math::mul_div(deposit, elapsed, duration, Rounding::Down).unwrap_or(deposit)
+}
+
+/// Seconds of `start..stop` elapsed at `now`.
+fn elapsed(start: U256, stop: U256, now: U256) -> U256 {
+    now.clamp(start, stop) - start
+}
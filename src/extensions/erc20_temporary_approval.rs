@@ -0,0 +1,153 @@
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    alloy_sol_types::{sol, SolError},
+    block, evm,
+    prelude::*,
+};
+
+use crate::tokens::erc20::{Erc20, Erc20Params};
+
+sol_storage! {
+    /// Approvals carrying a deadline: once `block.timestamp` passes it the allowance can no longer
+    /// be spent, so a forgotten (or infinite) approval stops being a liability on its own.
+    ///
+    /// The composing token exposes `approveWithExpiry(spender, value, deadline)` calling
+    /// `approve_with_expiry`, and overrides `transfer_from` to call `spend_allowance` of this
+    /// extension instead of the `Erc20` one. The deadline stays attached to the owner => spender
+    /// pair, `increaseAllowance` and `decreaseAllowance` keep it; overriding `approve` with
+    /// `approve` of this extension makes a plain approval unbounded again. Overriding `allowance`
+    /// with `allowance` of this extension reports expired allowances as 0.
+    /// See `examples/compose_temporary_approval.rs` for a complete composition.
+    pub struct Erc20TemporaryApproval {
+        /// owner => spender => last timestamp the allowance can be spent at, 0 for no deadline
+        mapping(address => mapping(address => uint256)) expiries;
+    }
+}
+
+sol! {
+    event ApprovalExpiry(address indexed owner, address indexed spender, uint256 deadline);
+
+    /// Indicates a spend of the allowance of `spender` over `owner`'s tokens after its `deadline`.
+    error Erc20ExpiredAllowance(address owner, address spender, uint256 deadline);
+
+    /// Indicates an approval with a `deadline` already in the past.
+    error Erc20InvalidAllowanceExpiry(uint256 deadline);
+}
+
+pub enum Erc20TemporaryApprovalError {
+    Erc20ExpiredAllowance(Erc20ExpiredAllowance),
+    Erc20InvalidAllowanceExpiry(Erc20InvalidAllowanceExpiry),
+}
+
+impl From<Erc20TemporaryApprovalError> for Vec<u8> {
+    fn from(e: Erc20TemporaryApprovalError) -> Vec<u8> {
+        match e {
+            Erc20TemporaryApprovalError::Erc20ExpiredAllowance(e) => e.encode(),
+            Erc20TemporaryApprovalError::Erc20InvalidAllowanceExpiry(e) => e.encode(),
+        }
+    }
+}
+
+crate::errors::error_helpers!(Erc20TemporaryApprovalError {
+    Erc20ExpiredAllowance,
+    Erc20InvalidAllowanceExpiry,
+});
+
+impl Erc20TemporaryApproval {
+    /// Sets `value` as the allowance of `spender` over `owner`'s tokens, spendable until `deadline`
+    /// included. Reverts if `deadline` is already past.
+    ///
+    /// Emits an {Approval} and an {ApprovalExpiry} event.
+    pub fn approve_with_expiry<T: Erc20Params>(
+        &mut self,
+        erc20: &mut Erc20<T>,
+        owner: Address,
+        spender: Address,
+        value: U256,
+        deadline: U256,
+    ) -> Result<(), Vec<u8>> {
+        if deadline < U256::from(block::timestamp()) {
+            return Err(Erc20TemporaryApprovalError::Erc20InvalidAllowanceExpiry(
+                Erc20InvalidAllowanceExpiry { deadline },
+            )
+            .into());
+        }
+        erc20.approve_internal(owner, spender, value)?;
+        self.set_expiry(owner, spender, deadline);
+        Ok(())
+    }
+
+    /// Sets `value` as an allowance without deadline, clearing the one of a previous
+    /// `approve_with_expiry`.
+    ///
+    /// Emits an {Approval} event, and an {ApprovalExpiry} event with a 0 deadline if one is cleared.
+    pub fn approve<T: Erc20Params>(
+        &mut self,
+        erc20: &mut Erc20<T>,
+        owner: Address,
+        spender: Address,
+        value: U256,
+    ) -> Result<(), Vec<u8>> {
+        erc20.approve_internal(owner, spender, value)?;
+        if self.expiries.get(owner).get(spender) != U256::ZERO {
+            self.set_expiry(owner, spender, U256::ZERO);
+        }
+        Ok(())
+    }
+
+    /// Spends `value` of the allowance of `spender` like `Erc20::spend_allowance`, reverting with
+    /// `Erc20ExpiredAllowance` past its deadline, infinite allowances included.
+    pub fn spend_allowance<T: Erc20Params>(
+        &self,
+        erc20: &mut Erc20<T>,
+        owner: Address,
+        spender: Address,
+        value: U256,
+    ) -> Result<(), Vec<u8>> {
+        self.check_expiry(owner, spender)?;
+        erc20.spend_allowance(owner, spender, value)?;
+        Ok(())
+    }
+
+    /// Reverts with `Erc20ExpiredAllowance` if the allowance of `spender` over `owner`'s tokens is expired.
+    pub fn check_expiry(&self, owner: Address, spender: Address) -> Result<(), Erc20TemporaryApprovalError> {
+        let deadline = self.expiries.get(owner).get(spender);
+        if is_expired(deadline, U256::from(block::timestamp())) {
+            return Err(Erc20TemporaryApprovalError::Erc20ExpiredAllowance(Erc20ExpiredAllowance {
+                owner,
+                spender,
+                deadline,
+            }));
+        }
+        Ok(())
+    }
+
+    /// Returns the spendable allowance of `spender` over `owner`'s tokens, 0 once expired.
+    pub fn allowance<T: Erc20Params>(&self, erc20: &Erc20<T>, owner: Address, spender: Address) -> U256 {
+        if self.check_expiry(owner, spender).is_err() {
+            return U256::ZERO;
+        }
+        erc20.allowance_internal(owner, spender)
+    }
+
+    fn set_expiry(&mut self, owner: Address, spender: Address, deadline: U256) {
+        self.expiries.setter(owner).insert(spender, deadline);
+        evm::log(ApprovalExpiry { owner, spender, deadline });
+    }
+}
+
+#[external]
+impl Erc20TemporaryApproval {
+    /// Returns the last timestamp the allowance of `spender` over `owner`'s tokens can be spent
+    /// at, 0 for no deadline.
+    #[selector(name = "allowanceExpiry")]
+    pub fn allowance_expiry(&self, owner: Address, spender: Address) -> Result<U256, Erc20TemporaryApprovalError> {
+        Ok(self.expiries.get(owner).get(spender))
+    }
+}
+
+/// Whether an allowance with `deadline` (0 for none) is expired at timestamp `now`.
+pub fn is_expired(deadline: U256, now: U256) -> bool {
+    deadline != U256::ZERO && now > deadline
+}
@@ -0,0 +1,121 @@
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    alloy_sol_types::{sol, SolError},
+    block, evm,
+    prelude::*,
+};
+
+use crate::extensions::fee_exemptions::FeeExemptions;
+use crate::tokens::erc20::moves_out;
+
+sol_storage! {
+    /// Minimum time between two outbound transfers of an account, an anti-bot control for token
+    /// launches. The revert carries the remaining wait so frontends can display it.
+    ///
+    /// Compose next to `Erc20` and `FeeExemptions` (pools and the treasury are usually exempt),
+    /// and call `record_outbound(&self.fee_exemptions, from, to, value)` before every `update` moving tokens
+    /// out of `from`. The setter is internal, the composing token exposes it behind its own
+    /// authorization, typically to shorten then disable the cooldown after launch.
+    /// See `examples/compose_transfer_cooldown.rs` for a complete composition.
+    pub struct Erc20TransferCooldown {
+        /// seconds between two outbound transfers, 0 disables the cooldown
+        uint256 cooldown;
+        /// timestamp of the last outbound transfer of an account
+        mapping(address => uint256) last_transfer;
+    }
+}
+
+sol! {
+    event TransferCooldownUpdated(uint256 cooldown);
+
+    /// Indicates that `account` transferred less than the cooldown ago.
+    /// * `account` - address whose tokens are being transferred.
+    /// * `remaining` - seconds to wait before the next transfer.
+    /// * `available_at` - timestamp from which the account can transfer again.
+    error Erc20TransferCooldownActive(address account, uint256 remaining, uint256 available_at);
+}
+
+pub enum Erc20TransferCooldownError {
+    Erc20TransferCooldownActive(Erc20TransferCooldownActive),
+}
+
+impl From<Erc20TransferCooldownError> for Vec<u8> {
+    fn from(e: Erc20TransferCooldownError) -> Vec<u8> {
+        match e {
+            Erc20TransferCooldownError::Erc20TransferCooldownActive(e) => e.encode(),
+        }
+    }
+}
+
+crate::errors::error_helpers!(Erc20TransferCooldownError {
+    Erc20TransferCooldownActive,
+});
+
+impl Erc20TransferCooldown {
+    /// Sets the cooldown in seconds, 0 disables it.
+    ///
+    /// Emits a {TransferCooldownUpdated} event.
+    pub fn set_cooldown(&mut self, cooldown: U256) {
+        self.cooldown.set(cooldown);
+        evm::log(TransferCooldownUpdated { cooldown });
+    }
+
+    /// Accounts an outbound transfer of `value` from `from` to `to`, reverting if the previous
+    /// one of `from` is more recent than the cooldown. Mints (`from` zero) and accounts exempt in
+    /// `exemptions` are not limited.
+    ///
+    /// Zero value and self transfers neither wait for nor start a cooldown: anyone can
+    /// `transferFrom` 0 tokens of any account, they must not lock its transfers.
+    pub fn record_outbound(
+        &mut self,
+        exemptions: &FeeExemptions,
+        from: Address,
+        to: Address,
+        value: U256,
+    ) -> Result<(), Erc20TransferCooldownError> {
+        if !moves_out(from, to, value) {
+            return Ok(());
+        }
+        let cooldown = self.cooldown.get();
+        if cooldown == U256::ZERO || from == Address::ZERO || exemptions.is_exempt(from) {
+            return Ok(());
+        }
+
+        let now = U256::from(block::timestamp());
+        let last_transfer = self.last_transfer.get(from);
+        // accounts which never transferred have a zero timestamp, far enough in the past
+        let available_at = last_transfer.saturating_add(cooldown);
+        if last_transfer != U256::ZERO && now < available_at {
+            return Err(Erc20TransferCooldownError::Erc20TransferCooldownActive(
+                Erc20TransferCooldownActive {
+                    account: from,
+                    remaining: available_at - now,
+                    available_at,
+                },
+            ));
+        }
+        self.last_transfer.insert(from, now);
+        Ok(())
+    }
+}
+
+#[external]
+impl Erc20TransferCooldown {
+    #[selector(name = "transferCooldown")]
+    pub fn transfer_cooldown(&self) -> Result<U256, Erc20TransferCooldownError> {
+        Ok(self.cooldown.get())
+    }
+
+    /// Returns the seconds `account` must wait before its next outbound transfer, 0 if it can
+    /// transfer now. Exemptions are not taken into account (see `isFeeExempt`).
+    #[selector(name = "cooldownRemaining")]
+    pub fn cooldown_remaining(&self, account: Address) -> Result<U256, Erc20TransferCooldownError> {
+        let last_transfer = self.last_transfer.get(account);
+        if last_transfer == U256::ZERO {
+            return Ok(U256::ZERO);
+        }
+        let available_at = last_transfer.saturating_add(self.cooldown.get());
+        Ok(available_at.saturating_sub(U256::from(block::timestamp())))
+    }
+}
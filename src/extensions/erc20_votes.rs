@@ -0,0 +1,251 @@
+use alloc::{string::String, vec::Vec};
+use core::marker::PhantomData;
+use stylus_sdk::{
+    alloy_primitives::{Address, B256, U256},
+    alloy_sol_types::{sol, SolError},
+    block, evm,
+    prelude::*,
+};
+
+use crate::tokens::erc20::{Erc20, Erc20Params};
+use crate::utils::{
+    checkpoints::{ClockMode, Trace},
+    eip712::{self, address_word, u256_word},
+    replay_guard::ReplayGuard,
+};
+
+/// EIP-712 type of a signed delegation, same as OpenZeppelin `Votes`.
+pub const DELEGATION_TYPE: &str = "Delegation(address delegatee,uint256 nonce,uint256 expiry)";
+
+sol_storage! {
+    /// Voting power with historical checkpoints (ERC-5805): every holder delegates its balance to
+    /// a delegatee (itself to vote directly), the votes of each delegatee and the total supply are
+    /// checkpointed so governors can read them at the proposal snapshot. Timepoints are block
+    /// numbers, or timestamps with `Erc20Params::CLOCK_MODE`, as reported by the ERC-6372
+    /// `clock()` and `CLOCK_MODE()`; the mode can not change once checkpoints are written.
+    ///
+    /// Balances are only counted once delegated, an account that never called `delegate` has no
+    /// votes. The composing token calls `move_voting_units(from, to, value)` after every transfer,
+    /// mint and burn (the same `from`/`to` as `Erc20::update`) and exposes
+    /// `#[selector(name = "delegate")]` passing its `Erc20`. Signed delegations are exposed with
+    /// `#[selector(name = "delegateBySig")]` passing the shared `ReplayGuard` as well, whose
+    /// sequential `nonces` are the ones signed.
+    /// See `examples/compose_votes.rs` for a complete composition.
+    pub struct Erc20Votes<T> {
+        mapping(address => address) delegatees;
+        mapping(address => Trace) delegate_checkpoints;
+        Trace total_checkpoints;
+        PhantomData<T> phantom;
+    }
+}
+
+sol! {
+    event DelegateChanged(address indexed delegator, address indexed from_delegate, address indexed to_delegate);
+    event DelegateVotesChanged(address indexed delegate, uint256 previous_votes, uint256 new_votes);
+
+    /// Indicates a lookup of votes at a timepoint not reached yet.
+    /// * `timepoint` - requested timepoint.
+    /// * `clock` - current timepoint.
+    error Erc5805FutureLookup(uint256 timepoint, uint48 clock);
+
+    /// Indicates a delegation signature used after its `expiry`.
+    error VotesExpiredSignature(uint256 expiry);
+}
+
+pub enum Erc20VotesError {
+    Erc5805FutureLookup(Erc5805FutureLookup),
+    VotesExpiredSignature(VotesExpiredSignature),
+}
+
+impl From<Erc20VotesError> for Vec<u8> {
+    fn from(e: Erc20VotesError) -> Vec<u8> {
+        match e {
+            Erc20VotesError::Erc5805FutureLookup(e) => e.encode(),
+            Erc20VotesError::VotesExpiredSignature(e) => e.encode(),
+        }
+    }
+}
+
+crate::errors::error_helpers!(Erc20VotesError {
+    Erc5805FutureLookup,
+    VotesExpiredSignature,
+});
+
+impl<T: Erc20Params> Erc20Votes<T> {
+    /// Delegates the votes of `account` to `delegatee`, moving its whole balance.
+    ///
+    /// Emits a {DelegateChanged} event and {DelegateVotesChanged} events for both delegatees.
+    pub fn delegate(
+        &mut self,
+        erc20: &Erc20<T>,
+        account: Address,
+        delegatee: Address,
+    ) -> Result<(), Vec<u8>> {
+        let old_delegate = self.delegatees.get(account);
+        self.delegatees.insert(account, delegatee);
+
+        evm::log(DelegateChanged {
+            delegator: account,
+            from_delegate: old_delegate,
+            to_delegate: delegatee,
+        });
+        self.move_delegate_votes(old_delegate, delegatee, erc20.balance_of_internal(account));
+        Ok(())
+    }
+
+    /// Delegates the votes of the signer of a `Delegation(delegatee, nonce, expiry)` under the
+    /// token domain (`utils::eip712`), consuming its sequential `nonce` in `replay_guard`. Only
+    /// EOA signatures are accepted, as in OpenZeppelin `Votes`.
+    ///
+    /// Emits a {DelegateChanged} event and {DelegateVotesChanged} events for both delegatees.
+    #[allow(clippy::too_many_arguments)]
+    pub fn delegate_by_sig(
+        &mut self,
+        erc20: &Erc20<T>,
+        replay_guard: &mut ReplayGuard,
+        delegatee: Address,
+        nonce: U256,
+        expiry: U256,
+        v: u8,
+        r: B256,
+        s: B256,
+    ) -> Result<(), Vec<u8>> {
+        if U256::from(block::timestamp()) > expiry {
+            return Err(Erc20VotesError::VotesExpiredSignature(VotesExpiredSignature { expiry }).into());
+        }
+        let signer = eip712::recover_typed_signature(
+            eip712::erc20_domain_separator::<T>(),
+            delegation_hash(delegatee, nonce, expiry),
+            v,
+            r,
+            s,
+        )?;
+        replay_guard.use_checked_nonce(signer, nonce)?;
+        self.delegate(erc20, signer, delegatee)
+    }
+
+    /// Hook for every balance change: checkpoints the total supply on mint/burn and moves the
+    /// votes between the delegatees of `from` and `to`. Zero value changes write nothing.
+    pub fn move_voting_units(&mut self, from: Address, to: Address, value: U256) {
+        if value == U256::ZERO {
+            return;
+        }
+        if from == Address::ZERO {
+            let total = self.total_checkpoints.latest();
+            push_now(T::CLOCK_MODE, &mut self.total_checkpoints, total + value);
+        }
+        if to == Address::ZERO {
+            let total = self.total_checkpoints.latest();
+            push_now(T::CLOCK_MODE, &mut self.total_checkpoints, total - value);
+        }
+        let from_delegate = self.delegatees.get(from);
+        let to_delegate = self.delegatees.get(to);
+        self.move_delegate_votes(from_delegate, to_delegate, value);
+    }
+
+    /// Emits a {DelegateVotesChanged} event for each non zero delegatee.
+    fn move_delegate_votes(&mut self, from: Address, to: Address, value: U256) {
+        if from == to || value == U256::ZERO {
+            return;
+        }
+        if from != Address::ZERO {
+            let mut checkpoints = self.delegate_checkpoints.setter(from);
+            let previous_votes = checkpoints.latest();
+            // votes of a delegatee are the sum of the balances delegated to it
+            let new_votes = previous_votes - value;
+            push_now(T::CLOCK_MODE, &mut checkpoints, new_votes);
+            evm::log(DelegateVotesChanged {
+                delegate: from,
+                previous_votes,
+                new_votes,
+            });
+        }
+        if to != Address::ZERO {
+            let mut checkpoints = self.delegate_checkpoints.setter(to);
+            let previous_votes = checkpoints.latest();
+            let new_votes = previous_votes + value;
+            push_now(T::CLOCK_MODE, &mut checkpoints, new_votes);
+            evm::log(DelegateVotesChanged {
+                delegate: to,
+                previous_votes,
+                new_votes,
+            });
+        }
+    }
+
+    /// Reverts unless `timepoint` is in the past of the clock.
+    fn check_past(timepoint: U256) -> Result<(), Erc20VotesError> {
+        let clock = T::CLOCK_MODE.now();
+        if timepoint >= U256::from(clock) {
+            return Err(Erc20VotesError::Erc5805FutureLookup(Erc5805FutureLookup {
+                timepoint,
+                clock,
+            }));
+        }
+        Ok(())
+    }
+}
+
+/// ERC-5805 names are pinned, governors call them by selector.
+#[external]
+impl<T: Erc20Params> Erc20Votes<T> {
+    /// Returns the current timepoint of the checkpoints (ERC-6372).
+    #[selector(name = "clock")]
+    pub fn clock(&self) -> Result<u64, Erc20VotesError> {
+        Ok(T::CLOCK_MODE.now())
+    }
+
+    /// Returns the description of the clock (ERC-6372), `mode=blocknumber&from=default` or
+    /// `mode=timestamp`.
+    #[selector(name = "CLOCK_MODE")]
+    pub fn clock_mode(&self) -> Result<String, Erc20VotesError> {
+        Ok(T::CLOCK_MODE.description().into())
+    }
+
+    /// Returns the delegatee of `account`, the zero address if it never delegated.
+    #[selector(name = "delegates")]
+    pub fn delegates(&self, account: Address) -> Result<Address, Erc20VotesError> {
+        Ok(self.delegatees.get(account))
+    }
+
+    /// Returns the current votes of `account`.
+    #[selector(name = "getVotes")]
+    pub fn get_votes(&self, account: Address) -> Result<U256, Erc20VotesError> {
+        Ok(self.delegate_checkpoints.get(account).latest())
+    }
+
+    /// Returns the votes of `account` at the end of `timepoint`, which must be in the past.
+    #[selector(name = "getPastVotes")]
+    pub fn get_past_votes(&self, account: Address, timepoint: U256) -> Result<U256, Erc20VotesError> {
+        Self::check_past(timepoint)?;
+        Ok(self.delegate_checkpoints.get(account).upper_lookup(timepoint))
+    }
+
+    /// Returns the total supply at the end of `timepoint`, which must be in the past.
+    /// Unlike votes, it counts undelegated balances.
+    #[selector(name = "getPastTotalSupply")]
+    pub fn get_past_total_supply(&self, timepoint: U256) -> Result<U256, Erc20VotesError> {
+        Self::check_past(timepoint)?;
+        Ok(self.total_checkpoints.upper_lookup(timepoint))
+    }
+
+    /// Returns the number of checkpoints of `account`.
+    #[selector(name = "numCheckpoints")]
+    pub fn num_checkpoints(&self, account: Address) -> Result<U256, Erc20VotesError> {
+        Ok(self.delegate_checkpoints.get(account).length())
+    }
+}
+
+/// Struct hash of a `Delegation` to sign for `delegate_by_sig`.
+pub fn delegation_hash(delegatee: Address, nonce: U256, expiry: U256) -> B256 {
+    eip712::hash_struct(
+        DELEGATION_TYPE,
+        &[address_word(delegatee), u256_word(nonce), u256_word(expiry)],
+    )
+}
+
+/// Checkpoints `value` at the current timepoint of `clock`.
+fn push_now(clock: ClockMode, trace: &mut Trace, value: U256) {
+    // block numbers and timestamps never decrease, the push can not be unordered
+    let _ = trace.push(U256::from(clock.now()), value);
+}
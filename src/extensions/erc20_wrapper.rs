@@ -0,0 +1,253 @@
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256, U8},
+    alloy_sol_types::{sol, SolError},
+    contract, evm, msg,
+    prelude::*,
+};
+
+use crate::tokens::erc20::{Erc20, Erc20Params};
+use crate::utils::reentrancy_guard::ReentrancyGuard;
+use crate::utils::safe_erc20;
+
+/// Largest decimals difference supported between the wrapper and its underlying, `10^36`
+/// leaves room for amounts up to `2^136` when scaling up.
+pub const MAX_DECIMALS_DIFFERENCE: u8 = 36;
+
+/// Rounding direction of a conversion between underlying and wrapped units.
+pub use crate::utils::math::Rounding;
+
+sol_storage! {
+    /// Wraps an ERC20 underlying held by the contract: `deposit_for` pulls underlying and mints
+    /// wrapped tokens, `withdraw_to` burns them and pays the underlying back.
+    ///
+    /// When the underlying has other decimals than the wrapper (e.g. a 6 decimals bridged
+    /// stablecoin wrapped into an 18 decimals token) amounts are scaled by `10^difference`
+    /// instead of 1:1. Conversions round down in favor of the contract and only the converted
+    /// part moves: the remainder (dust) of a deposit is not pulled and the remainder of a
+    /// withdrawal is not burned, `preview_deposit`/`preview_withdraw` report it beforehand.
+    ///
+    /// Underlying transfers run with the `ReentrancyGuard` engaged and the previews revert
+    /// meanwhile, so an underlying calling back does not read them mid-deposit or mid-withdrawal.
+    ///
+    /// The composing token sets the underlying once in `init` with `set_underlying` and exposes
+    /// `deposit_for`/`withdraw_to` passing its `Erc20`.
+    /// See `examples/compose_wrapper.rs` for a complete composition.
+    pub struct Erc20Wrapper {
+        /// wrapped ERC20
+        address underlying;
+        /// decimals of the underlying, fixed with the underlying
+        uint8 underlying_decimals;
+        /// decimals of the wrapper token, copied from its params
+        uint8 wrapped_decimals;
+        /// engaged during underlying transfers
+        ReentrancyGuard guard;
+    }
+}
+
+sol! {
+    event Deposited(address indexed caller, address indexed account, uint256 underlying_amount, uint256 wrapped_amount);
+    event Withdrawn(address indexed caller, address indexed account, uint256 underlying_amount, uint256 wrapped_amount);
+
+    /// Indicates an invalid underlying: zero address, the wrapper itself or set twice.
+    error Erc20InvalidUnderlying(address underlying);
+
+    /// Indicates underlying decimals too far from the wrapper decimals.
+    error Erc20WrapperInvalidDecimals(uint8 underlying_decimals);
+
+    /// Indicates that `amount` converts to 0 units, nothing would move.
+    error Erc20WrapperAmountTooSmall(uint256 amount);
+
+    /// Indicates that scaling `amount` up overflows.
+    error Erc20WrapperConversionOverflow(uint256 amount);
+}
+
+pub enum Erc20WrapperError {
+    Erc20InvalidUnderlying(Erc20InvalidUnderlying),
+    Erc20WrapperInvalidDecimals(Erc20WrapperInvalidDecimals),
+    Erc20WrapperAmountTooSmall(Erc20WrapperAmountTooSmall),
+    Erc20WrapperConversionOverflow(Erc20WrapperConversionOverflow),
+}
+
+impl From<Erc20WrapperError> for Vec<u8> {
+    fn from(e: Erc20WrapperError) -> Vec<u8> {
+        match e {
+            Erc20WrapperError::Erc20InvalidUnderlying(e) => e.encode(),
+            Erc20WrapperError::Erc20WrapperInvalidDecimals(e) => e.encode(),
+            Erc20WrapperError::Erc20WrapperAmountTooSmall(e) => e.encode(),
+            Erc20WrapperError::Erc20WrapperConversionOverflow(e) => e.encode(),
+        }
+    }
+}
+
+crate::errors::error_helpers!(Erc20WrapperError {
+    Erc20InvalidUnderlying,
+    Erc20WrapperInvalidDecimals,
+    Erc20WrapperAmountTooSmall,
+    Erc20WrapperConversionOverflow,
+});
+
+impl Erc20Wrapper {
+    /// Sets the underlying and its decimals, only once. `T` is the wrapper token, its
+    /// `DECIMALS` are stored for the conversions.
+    pub fn set_underlying<T: Erc20Params>(
+        &mut self,
+        underlying: Address,
+        underlying_decimals: u8,
+    ) -> Result<(), Erc20WrapperError> {
+        if underlying == Address::ZERO
+            || underlying == contract::address()
+            || self.underlying.get() != Address::ZERO
+        {
+            return Err(Erc20WrapperError::Erc20InvalidUnderlying(Erc20InvalidUnderlying {
+                underlying,
+            }));
+        }
+        if T::DECIMALS.abs_diff(underlying_decimals) > MAX_DECIMALS_DIFFERENCE {
+            return Err(Erc20WrapperError::Erc20WrapperInvalidDecimals(
+                Erc20WrapperInvalidDecimals { underlying_decimals },
+            ));
+        }
+        self.underlying.set(underlying);
+        self.underlying_decimals.set(U8::from(underlying_decimals));
+        self.wrapped_decimals.set(U8::from(T::DECIMALS));
+        Ok(())
+    }
+
+    /// Pulls underlying from the caller and mints the wrapped amount to `account`, returns it.
+    /// Only the converted part of `amount` is pulled, see `preview_deposit`.
+    ///
+    /// Emits a {Transfer} and a {Deposited} event.
+    pub fn deposit_for<T: Erc20Params>(
+        &mut self,
+        erc20: &mut Erc20<T>,
+        account: Address,
+        amount: U256,
+    ) -> Result<U256, Vec<u8>> {
+        let (wrapped_amount, dust) = self.preview_deposit(amount)?;
+        if wrapped_amount == U256::ZERO {
+            return Err(Erc20WrapperError::Erc20WrapperAmountTooSmall(Erc20WrapperAmountTooSmall { amount }).into());
+        }
+        let caller = msg::sender();
+        let underlying_amount = amount - dust;
+        self.guard.enter()?;
+        safe_erc20::safe_transfer_from(self.underlying.get(), caller, contract::address(), underlying_amount)?;
+        self.guard.exit();
+        erc20.mint(account, wrapped_amount)?;
+
+        evm::log(Deposited {
+            caller,
+            account,
+            underlying_amount,
+            wrapped_amount,
+        });
+        Ok(wrapped_amount)
+    }
+
+    /// Burns wrapped tokens of the caller and pays the underlying to `account`, returns the
+    /// underlying amount. Only the converted part of `value` is burned, see `preview_withdraw`.
+    ///
+    /// Emits a {Transfer} and a {Withdrawn} event.
+    pub fn withdraw_to<T: Erc20Params>(
+        &mut self,
+        erc20: &mut Erc20<T>,
+        account: Address,
+        value: U256,
+    ) -> Result<U256, Vec<u8>> {
+        let (underlying_amount, dust) = self.preview_withdraw(value)?;
+        if underlying_amount == U256::ZERO {
+            return Err(Erc20WrapperError::Erc20WrapperAmountTooSmall(Erc20WrapperAmountTooSmall { amount: value }).into());
+        }
+        let caller = msg::sender();
+        let wrapped_amount = value - dust;
+        erc20.burn(caller, wrapped_amount)?;
+        self.guard.enter()?;
+        safe_erc20::safe_transfer(self.underlying.get(), account, underlying_amount)?;
+        self.guard.exit();
+
+        evm::log(Withdrawn {
+            caller,
+            account,
+            underlying_amount,
+            wrapped_amount,
+        });
+        Ok(underlying_amount)
+    }
+
+    /// Wrapped units worth `amount` underlying units.
+    pub fn to_wrapped(&self, amount: U256, rounding: Rounding) -> Result<U256, Erc20WrapperError> {
+        let (underlying_decimals, wrapped_decimals) = self.decimals();
+        if wrapped_decimals >= underlying_decimals {
+            scale_up(amount, wrapped_decimals - underlying_decimals)
+        } else {
+            Ok(scale_down(amount, underlying_decimals - wrapped_decimals, rounding))
+        }
+    }
+
+    /// Underlying units worth `value` wrapped units.
+    pub fn to_underlying(&self, value: U256, rounding: Rounding) -> Result<U256, Erc20WrapperError> {
+        let (underlying_decimals, wrapped_decimals) = self.decimals();
+        if wrapped_decimals >= underlying_decimals {
+            Ok(scale_down(value, wrapped_decimals - underlying_decimals, rounding))
+        } else {
+            scale_up(value, underlying_decimals - wrapped_decimals)
+        }
+    }
+
+    /// `(underlying decimals, wrapped decimals)`
+    fn decimals(&self) -> (u8, u8) {
+        (
+            self.underlying_decimals.get().to::<u8>(),
+            self.wrapped_decimals.get().to::<u8>(),
+        )
+    }
+}
+
+#[external]
+impl Erc20Wrapper {
+    #[selector(name = "underlying")]
+    pub fn underlying(&self) -> Result<Address, Erc20WrapperError> {
+        Ok(self.underlying.get())
+    }
+
+    #[selector(name = "underlyingDecimals")]
+    pub fn underlying_decimals(&self) -> Result<u8, Erc20WrapperError> {
+        Ok(self.decimals().0)
+    }
+
+    /// Returns `(wrapped minted, underlying dust not pulled)` for a deposit of `amount`
+    /// underlying units, the dust stays with the caller.
+    #[selector(name = "previewDeposit")]
+    pub fn preview_deposit(&self, amount: U256) -> Result<(U256, U256), Vec<u8>> {
+        self.guard.non_reentrant_view()?;
+        let wrapped = self.to_wrapped(amount, Rounding::Down)?;
+        let used = self.to_underlying(wrapped, Rounding::Up)?;
+        Ok((wrapped, amount - used))
+    }
+
+    /// Returns `(underlying paid, wrapped dust not burned)` for a withdrawal of `value` wrapped
+    /// units, the dust stays with the caller.
+    #[selector(name = "previewWithdraw")]
+    pub fn preview_withdraw(&self, value: U256) -> Result<(U256, U256), Vec<u8>> {
+        self.guard.non_reentrant_view()?;
+        let underlying = self.to_underlying(value, Rounding::Down)?;
+        let used = self.to_wrapped(underlying, Rounding::Up)?;
+        Ok((underlying, value - used))
+    }
+}
+
+fn scale_up(amount: U256, decimals: u8) -> Result<U256, Erc20WrapperError> {
+    amount
+        .checked_mul(U256::from(10).pow(U256::from(decimals)))
+        .ok_or(Erc20WrapperError::Erc20WrapperConversionOverflow(Erc20WrapperConversionOverflow { amount }))
+}
+
+fn scale_down(amount: U256, decimals: u8, rounding: Rounding) -> U256 {
+    let factor = U256::from(10).pow(U256::from(decimals));
+    let scaled = amount / factor;
+    if rounding == Rounding::Up && amount % factor != U256::ZERO {
+        scaled + U256::from(1)
+    } else {
+        scaled
+    }
+}
@@ -0,0 +1,193 @@
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{Address, FixedBytes, U256},
+    alloy_sol_types::{sol, SolError},
+    evm,
+    prelude::*,
+};
+
+use crate::utils::erc165::{self, Erc165};
+
+/// Denominator of the royalty fractions, fractions are in basis points.
+pub const FEE_DENOMINATOR: u64 = 10_000;
+
+sol_storage! {
+    /// ERC-2981 royalties per token id, for ERC721 token ids and ERC1155 ids alike: marketplaces
+    /// call `royaltyInfo(id, sale_price)` and pay `sale_price * fraction / 10_000` to the receiver.
+    /// Ids without a royalty of their own use the default royalty, if any.
+    ///
+    /// Setters are internal, the composing collection exposes them behind its own authorization.
+    /// See `examples/compose_erc721_rental.rs` and `examples/compose_erc1155.rs` for compositions.
+    pub struct Erc2981 {
+        mapping(uint256 => address) token_receivers;
+        /// royalty fraction of each id in basis points
+        mapping(uint256 => uint256) token_fractions;
+        address default_receiver;
+        /// default royalty fraction in basis points
+        uint256 default_fraction;
+    }
+}
+
+sol! {
+    event TokenRoyaltySet(uint256 indexed token_id, address indexed receiver, uint256 fee_numerator);
+    event DefaultRoyaltySet(address indexed receiver, uint256 fee_numerator);
+
+    /// Indicates that the default royalty is above 100%.
+    /// * `numerator` - royalty fraction.
+    /// * `denominator` - fraction denominator.
+    error Erc2981InvalidDefaultRoyalty(uint256 numerator, uint256 denominator);
+
+    /// Indicates a zero address default royalty receiver.
+    error Erc2981InvalidDefaultRoyaltyReceiver(address receiver);
+
+    /// Indicates that the royalty of `token_id` is above 100%.
+    /// * `numerator` - royalty fraction.
+    /// * `denominator` - fraction denominator.
+    error Erc2981InvalidTokenRoyalty(uint256 token_id, uint256 numerator, uint256 denominator);
+
+    /// Indicates a zero address royalty receiver for `token_id`.
+    error Erc2981InvalidTokenRoyaltyReceiver(uint256 token_id, address receiver);
+}
+
+pub enum Erc2981Error {
+    Erc2981InvalidDefaultRoyalty(Erc2981InvalidDefaultRoyalty),
+    Erc2981InvalidDefaultRoyaltyReceiver(Erc2981InvalidDefaultRoyaltyReceiver),
+    Erc2981InvalidTokenRoyalty(Erc2981InvalidTokenRoyalty),
+    Erc2981InvalidTokenRoyaltyReceiver(Erc2981InvalidTokenRoyaltyReceiver),
+}
+
+impl From<Erc2981Error> for Vec<u8> {
+    fn from(e: Erc2981Error) -> Vec<u8> {
+        match e {
+            Erc2981Error::Erc2981InvalidDefaultRoyalty(e) => e.encode(),
+            Erc2981Error::Erc2981InvalidDefaultRoyaltyReceiver(e) => e.encode(),
+            Erc2981Error::Erc2981InvalidTokenRoyalty(e) => e.encode(),
+            Erc2981Error::Erc2981InvalidTokenRoyaltyReceiver(e) => e.encode(),
+        }
+    }
+}
+
+crate::errors::error_helpers!(Erc2981Error {
+    Erc2981InvalidDefaultRoyalty,
+    Erc2981InvalidDefaultRoyaltyReceiver,
+    Erc2981InvalidTokenRoyalty,
+    Erc2981InvalidTokenRoyaltyReceiver,
+});
+
+impl Erc2981 {
+    /// Sets the royalty of every id without a royalty of its own, `fee_numerator` is in basis
+    /// points and at most 10_000.
+    ///
+    /// Emits a {DefaultRoyaltySet} event.
+    pub fn set_default_royalty(&mut self, receiver: Address, fee_numerator: U256) -> Result<(), Erc2981Error> {
+        let denominator = U256::from(FEE_DENOMINATOR);
+        if fee_numerator > denominator {
+            return Err(Erc2981Error::Erc2981InvalidDefaultRoyalty(
+                Erc2981InvalidDefaultRoyalty {
+                    numerator: fee_numerator,
+                    denominator,
+                },
+            ));
+        }
+        if receiver == Address::ZERO {
+            return Err(Erc2981Error::Erc2981InvalidDefaultRoyaltyReceiver(
+                Erc2981InvalidDefaultRoyaltyReceiver { receiver },
+            ));
+        }
+        self.default_receiver.set(receiver);
+        self.default_fraction.set(fee_numerator);
+
+        evm::log(DefaultRoyaltySet { receiver, fee_numerator });
+        Ok(())
+    }
+
+    /// Removes the default royalty, ids without a royalty of their own pay none.
+    ///
+    /// Emits a {DefaultRoyaltySet} event with the zero address and fraction.
+    pub fn delete_default_royalty(&mut self) {
+        self.default_receiver.set(Address::ZERO);
+        self.default_fraction.set(U256::ZERO);
+
+        evm::log(DefaultRoyaltySet {
+            receiver: Address::ZERO,
+            fee_numerator: U256::ZERO,
+        });
+    }
+
+    /// Sets the royalty of `token_id`, `fee_numerator` is in basis points and at most 10_000.
+    ///
+    /// Emits a {TokenRoyaltySet} event.
+    pub fn set_token_royalty(
+        &mut self,
+        token_id: U256,
+        receiver: Address,
+        fee_numerator: U256,
+    ) -> Result<(), Erc2981Error> {
+        let denominator = U256::from(FEE_DENOMINATOR);
+        if fee_numerator > denominator {
+            return Err(Erc2981Error::Erc2981InvalidTokenRoyalty(
+                Erc2981InvalidTokenRoyalty {
+                    token_id,
+                    numerator: fee_numerator,
+                    denominator,
+                },
+            ));
+        }
+        if receiver == Address::ZERO {
+            return Err(Erc2981Error::Erc2981InvalidTokenRoyaltyReceiver(
+                Erc2981InvalidTokenRoyaltyReceiver { token_id, receiver },
+            ));
+        }
+        self.token_receivers.insert(token_id, receiver);
+        self.token_fractions.insert(token_id, fee_numerator);
+
+        evm::log(TokenRoyaltySet {
+            token_id,
+            receiver,
+            fee_numerator,
+        });
+        Ok(())
+    }
+
+    /// Removes the royalty of `token_id`, which falls back to the default royalty.
+    ///
+    /// Emits a {TokenRoyaltySet} event with the zero address and fraction.
+    pub fn reset_token_royalty(&mut self, token_id: U256) {
+        self.token_receivers.insert(token_id, Address::ZERO);
+        self.token_fractions.insert(token_id, U256::ZERO);
+
+        evm::log(TokenRoyaltySet {
+            token_id,
+            receiver: Address::ZERO,
+            fee_numerator: U256::ZERO,
+        });
+    }
+}
+
+/// No `supportsInterface` external, the composing token combines this impl with its base.
+impl Erc165 for Erc2981 {
+    fn supports_interface(interface_id: FixedBytes<4>) -> bool {
+        erc165::supports(interface_id, &[erc165::IERC2981])
+    }
+}
+
+#[external]
+impl Erc2981 {
+    /// Returns the royalty receiver of `token_id` and the royalty owed for a sale at `sale_price`,
+    /// from the default royalty if the id has none, `(0, 0)` if neither is set.
+    #[selector(name = "royaltyInfo")]
+    pub fn royalty_info(&self, token_id: U256, sale_price: U256) -> Result<(Address, U256), Erc2981Error> {
+        let receiver = self.token_receivers.get(token_id);
+        if receiver != Address::ZERO {
+            return Ok((receiver, royalty_amount(sale_price, self.token_fractions.get(token_id))));
+        }
+        let royalty = royalty_amount(sale_price, self.default_fraction.get());
+        Ok((self.default_receiver.get(), royalty))
+    }
+}
+
+/// Royalty owed for a sale at `sale_price` with a fraction of `fee_numerator` basis points,
+/// rounded down; saturates instead of overflowing for prices near `type(uint256).max`.
+pub fn royalty_amount(sale_price: U256, fee_numerator: U256) -> U256 {
+    sale_price.saturating_mul(fee_numerator) / U256::from(FEE_DENOMINATOR)
+}
@@ -0,0 +1,433 @@
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    alloy_sol_types::{sol, SolError},
+    contract, evm, msg,
+    prelude::*,
+};
+
+use crate::tokens::erc20::{Erc20, Erc20Params};
+use crate::utils::math::{self, Rounding};
+use crate::utils::reentrancy_guard::ReentrancyGuard;
+use crate::utils::safe_erc20;
+
+/// Denominator of the entry and exit fees, fees are in basis points.
+pub const FEE_DENOMINATOR: u64 = 10_000;
+
+/// Fees and limits of an `Erc4626` vault, plugged in as its type parameter so a fee charging or
+/// capped vault does not fork the deposit and withdrawal logic. Every hook defaults to the plain
+/// ERC-4626 behavior: no fees, no limits besides the balance of the owner.
+///
+/// Hooks are associated functions: state they need (a TVL cap, an allowlist...) is passed in,
+/// vaults with runtime settings keep them in the composing contract and check them before
+/// calling the vault.
+pub trait VaultConfig {
+    /// Fee on deposits and mints in basis points, taken on top of the assets converted to shares.
+    fn entry_fee_bps() -> u64 {
+        0
+    }
+
+    /// Fee on withdrawals and redemptions in basis points, taken on top of the assets paid out.
+    fn exit_fee_bps() -> u64 {
+        0
+    }
+
+    /// Assets `receiver` may still deposit when the vault holds `total_assets`, `None` for no
+    /// limit. Also bounds `maxMint`.
+    fn max_deposit_override(_receiver: Address, _total_assets: U256) -> Option<U256> {
+        None
+    }
+
+    /// Assets `owner` may still withdraw when the vault holds `total_assets`, `None` for its whole
+    /// position. Also bounds `maxRedeem`.
+    fn max_withdraw_override(_owner: Address, _total_assets: U256) -> Option<U256> {
+        None
+    }
+}
+
+/// `VaultConfig` of vaults without fees or limits.
+pub struct NoVaultConfig;
+
+impl VaultConfig for NoVaultConfig {}
+
+sol_storage! {
+    /// ERC-4626 tokenized vault over an ERC20 asset held by the contract, the shares being the
+    /// `Erc20` of the composing token. Conversions use a virtual share and asset (OpenZeppelin
+    /// `ERC4626` with a zero decimals offset) so the first depositor can not inflate the share
+    /// price against the next ones, and round in favor of the vault.
+    ///
+    /// Entry and exit fees of the `VaultConfig` go to the fee recipient, or stay in the vault for
+    /// the share holders when it is the vault itself, as in OpenZeppelin `ERC4626Fees`.
+    ///
+    /// Asset transfers run with the `ReentrancyGuard` engaged, the views pricing shares (total
+    /// assets, conversions, previews, limits) all read `total_assets`, which reverts meanwhile: an
+    /// asset calling back during a deposit or withdrawal can not read a price where the assets
+    /// moved but the shares did not yet.
+    ///
+    /// The composing token sets the asset once in `init` with `set_asset` and exposes the
+    /// ERC-4626 functions needing the shares (`totalAssets`, conversions, previews, limits,
+    /// `deposit`, `mint`, `withdraw`, `redeem`) passing its `Erc20`.
+    /// See `examples/compose_erc4626.rs` for a complete composition.
+    pub struct Erc4626<C> {
+        /// ERC20 deposited in the vault
+        address asset;
+        /// receiver of the entry and exit fees
+        address fee_recipient;
+        PhantomData<C> phantom;
+        /// engaged during asset transfers
+        ReentrancyGuard guard;
+    }
+}
+
+sol! {
+    event Deposit(address indexed sender, address indexed owner, uint256 assets, uint256 shares);
+    event Withdraw(address indexed sender, address indexed receiver, address indexed owner, uint256 assets, uint256 shares);
+
+    /// Indicates an attempt to deposit more assets than the max amount for `receiver`.
+    error Erc4626ExceededMaxDeposit(address receiver, uint256 assets, uint256 max);
+
+    /// Indicates an attempt to mint more shares than the max amount for `receiver`.
+    error Erc4626ExceededMaxMint(address receiver, uint256 shares, uint256 max);
+
+    /// Indicates an attempt to withdraw more assets than the max amount for `owner`.
+    error Erc4626ExceededMaxWithdraw(address owner, uint256 assets, uint256 max);
+
+    /// Indicates an attempt to redeem more shares than the max amount for `owner`.
+    error Erc4626ExceededMaxRedeem(address owner, uint256 shares, uint256 max);
+
+    /// Indicates an invalid asset: zero address, the vault itself or set twice.
+    error Erc4626InvalidAsset(address asset);
+
+    /// Indicates a `VaultConfig` fee above 100%.
+    error Erc4626InvalidFee(uint256 fee_bps);
+
+    /// Indicates that converting `amount` overflows.
+    error Erc4626ConversionOverflow(uint256 amount);
+}
+
+pub enum Erc4626Error {
+    Erc4626ExceededMaxDeposit(Erc4626ExceededMaxDeposit),
+    Erc4626ExceededMaxMint(Erc4626ExceededMaxMint),
+    Erc4626ExceededMaxWithdraw(Erc4626ExceededMaxWithdraw),
+    Erc4626ExceededMaxRedeem(Erc4626ExceededMaxRedeem),
+    Erc4626InvalidAsset(Erc4626InvalidAsset),
+    Erc4626InvalidFee(Erc4626InvalidFee),
+    Erc4626ConversionOverflow(Erc4626ConversionOverflow),
+}
+
+impl From<Erc4626Error> for Vec<u8> {
+    fn from(e: Erc4626Error) -> Vec<u8> {
+        match e {
+            Erc4626Error::Erc4626ExceededMaxDeposit(e) => e.encode(),
+            Erc4626Error::Erc4626ExceededMaxMint(e) => e.encode(),
+            Erc4626Error::Erc4626ExceededMaxWithdraw(e) => e.encode(),
+            Erc4626Error::Erc4626ExceededMaxRedeem(e) => e.encode(),
+            Erc4626Error::Erc4626InvalidAsset(e) => e.encode(),
+            Erc4626Error::Erc4626InvalidFee(e) => e.encode(),
+            Erc4626Error::Erc4626ConversionOverflow(e) => e.encode(),
+        }
+    }
+}
+
+crate::errors::error_helpers!(Erc4626Error {
+    Erc4626ExceededMaxDeposit,
+    Erc4626ExceededMaxMint,
+    Erc4626ExceededMaxWithdraw,
+    Erc4626ExceededMaxRedeem,
+    Erc4626InvalidAsset,
+    Erc4626InvalidFee,
+    Erc4626ConversionOverflow,
+});
+
+impl<C: VaultConfig> Erc4626<C> {
+    /// Sets the asset and the fee recipient, only once. Checks the fees of the `VaultConfig`.
+    pub fn set_asset(&mut self, asset: Address, fee_recipient: Address) -> Result<(), Erc4626Error> {
+        if asset == Address::ZERO || asset == contract::address() || self.asset.get() != Address::ZERO {
+            return Err(Erc4626Error::Erc4626InvalidAsset(Erc4626InvalidAsset { asset }));
+        }
+        for fee_bps in [C::entry_fee_bps(), C::exit_fee_bps()] {
+            if fee_bps > FEE_DENOMINATOR {
+                return Err(Erc4626Error::Erc4626InvalidFee(Erc4626InvalidFee {
+                    fee_bps: U256::from(fee_bps),
+                }));
+            }
+        }
+        self.asset.set(asset);
+        self.fee_recipient.set(fee_recipient);
+        Ok(())
+    }
+
+    /// Sets the receiver of the fees, to be called by the composing token behind its own
+    /// authorization. The vault itself (or the zero address) keeps the fees for the share holders.
+    pub fn set_fee_recipient(&mut self, fee_recipient: Address) {
+        self.fee_recipient.set(fee_recipient);
+    }
+
+    /// `totalAssets`: the asset balance of the vault, reverts with `ReentrancyGuardReentrantCall`
+    /// during an asset transfer of the vault.
+    pub fn total_assets(&self) -> Result<U256, Vec<u8>> {
+        self.guard.non_reentrant_view()?;
+        Ok(safe_erc20::self_balance(self.asset.get())?)
+    }
+
+    pub fn convert_to_shares<T: Erc20Params>(&self, erc20: &Erc20<T>, assets: U256) -> Result<U256, Vec<u8>> {
+        self.to_shares(erc20, assets, Rounding::Down)
+    }
+
+    pub fn convert_to_assets<T: Erc20Params>(&self, erc20: &Erc20<T>, shares: U256) -> Result<U256, Vec<u8>> {
+        self.to_assets(erc20, shares, Rounding::Down)
+    }
+
+    /// Shares minted for `assets`, after the entry fee.
+    pub fn preview_deposit<T: Erc20Params>(&self, erc20: &Erc20<T>, assets: U256) -> Result<U256, Vec<u8>> {
+        let fee = fee_on_total(assets, C::entry_fee_bps());
+        self.to_shares(erc20, assets - fee, Rounding::Down)
+    }
+
+    /// Assets pulled to mint `shares`, entry fee included.
+    pub fn preview_mint<T: Erc20Params>(&self, erc20: &Erc20<T>, shares: U256) -> Result<U256, Vec<u8>> {
+        let assets = self.to_assets(erc20, shares, Rounding::Up)?;
+        Ok(assets.saturating_add(fee_on_raw(assets, C::entry_fee_bps())))
+    }
+
+    /// Shares burned to pay out `assets`, exit fee included.
+    pub fn preview_withdraw<T: Erc20Params>(&self, erc20: &Erc20<T>, assets: U256) -> Result<U256, Vec<u8>> {
+        let fee = fee_on_raw(assets, C::exit_fee_bps());
+        self.to_shares(erc20, assets.saturating_add(fee), Rounding::Up)
+    }
+
+    /// Assets paid out for `shares`, after the exit fee.
+    pub fn preview_redeem<T: Erc20Params>(&self, erc20: &Erc20<T>, shares: U256) -> Result<U256, Vec<u8>> {
+        let assets = self.to_assets(erc20, shares, Rounding::Down)?;
+        Ok(assets - fee_on_total(assets, C::exit_fee_bps()))
+    }
+
+    pub fn max_deposit(&self, receiver: Address) -> Result<U256, Vec<u8>> {
+        Ok(C::max_deposit_override(receiver, self.total_assets()?).unwrap_or(U256::MAX))
+    }
+
+    pub fn max_mint<T: Erc20Params>(&self, erc20: &Erc20<T>, receiver: Address) -> Result<U256, Vec<u8>> {
+        match C::max_deposit_override(receiver, self.total_assets()?) {
+            Some(max_assets) => self.preview_deposit(erc20, max_assets),
+            None => Ok(U256::MAX),
+        }
+    }
+
+    /// Assets `owner` can withdraw: its position after the exit fee, bounded by the config.
+    pub fn max_withdraw<T: Erc20Params>(&self, erc20: &Erc20<T>, owner: Address) -> Result<U256, Vec<u8>> {
+        let position = self.preview_redeem(erc20, erc20.balance_of_internal(owner))?;
+        match C::max_withdraw_override(owner, self.total_assets()?) {
+            Some(max_assets) => Ok(position.min(max_assets)),
+            None => Ok(position),
+        }
+    }
+
+    /// Shares `owner` can redeem: its balance, bounded by the config.
+    pub fn max_redeem<T: Erc20Params>(&self, erc20: &Erc20<T>, owner: Address) -> Result<U256, Vec<u8>> {
+        let balance = erc20.balance_of_internal(owner);
+        match C::max_withdraw_override(owner, self.total_assets()?) {
+            Some(max_assets) => {
+                let fee = fee_on_raw(max_assets, C::exit_fee_bps());
+                Ok(balance.min(self.to_shares(erc20, max_assets.saturating_add(fee), Rounding::Down)?))
+            }
+            None => Ok(balance),
+        }
+    }
+
+    /// Pulls `assets` from the caller and mints the shares to `receiver`, returns the shares.
+    ///
+    /// Emits a {Transfer} and a {Deposit} event.
+    pub fn deposit<T: Erc20Params>(
+        &mut self,
+        erc20: &mut Erc20<T>,
+        assets: U256,
+        receiver: Address,
+    ) -> Result<U256, Vec<u8>> {
+        let max = self.max_deposit(receiver)?;
+        if assets > max {
+            return Err(Erc4626Error::Erc4626ExceededMaxDeposit(Erc4626ExceededMaxDeposit { receiver, assets, max }).into());
+        }
+        let shares = self.preview_deposit(erc20, assets)?;
+        self.deposit_internal(erc20, receiver, assets, shares)?;
+        Ok(shares)
+    }
+
+    /// Mints `shares` to `receiver` pulling their assets from the caller, returns the assets.
+    ///
+    /// Emits a {Transfer} and a {Deposit} event.
+    pub fn mint<T: Erc20Params>(&mut self, erc20: &mut Erc20<T>, shares: U256, receiver: Address) -> Result<U256, Vec<u8>> {
+        let max = self.max_mint(erc20, receiver)?;
+        if shares > max {
+            return Err(Erc4626Error::Erc4626ExceededMaxMint(Erc4626ExceededMaxMint { receiver, shares, max }).into());
+        }
+        let assets = self.preview_mint(erc20, shares)?;
+        self.deposit_internal(erc20, receiver, assets, shares)?;
+        Ok(assets)
+    }
+
+    /// Burns the shares of `owner` worth `assets` and pays them to `receiver`, returns the
+    /// shares. The caller spends its allowance over the shares of `owner` unless it is `owner`.
+    ///
+    /// Emits a {Transfer} and a {Withdraw} event.
+    pub fn withdraw<T: Erc20Params>(
+        &mut self,
+        erc20: &mut Erc20<T>,
+        assets: U256,
+        receiver: Address,
+        owner: Address,
+    ) -> Result<U256, Vec<u8>> {
+        let max = self.max_withdraw(erc20, owner)?;
+        if assets > max {
+            return Err(Erc4626Error::Erc4626ExceededMaxWithdraw(Erc4626ExceededMaxWithdraw { owner, assets, max }).into());
+        }
+        let shares = self.preview_withdraw(erc20, assets)?;
+        self.withdraw_internal(erc20, receiver, owner, assets, shares)?;
+        Ok(shares)
+    }
+
+    /// Burns `shares` of `owner` and pays their assets to `receiver`, returns the assets.
+    ///
+    /// Emits a {Transfer} and a {Withdraw} event.
+    pub fn redeem<T: Erc20Params>(
+        &mut self,
+        erc20: &mut Erc20<T>,
+        shares: U256,
+        receiver: Address,
+        owner: Address,
+    ) -> Result<U256, Vec<u8>> {
+        let max = self.max_redeem(erc20, owner)?;
+        if shares > max {
+            return Err(Erc4626Error::Erc4626ExceededMaxRedeem(Erc4626ExceededMaxRedeem { owner, shares, max }).into());
+        }
+        let assets = self.preview_redeem(erc20, shares)?;
+        self.withdraw_internal(erc20, receiver, owner, assets, shares)?;
+        Ok(assets)
+    }
+
+    /// `assets` include the entry fee, sent on to the fee recipient once the shares are minted.
+    fn deposit_internal<T: Erc20Params>(
+        &mut self,
+        erc20: &mut Erc20<T>,
+        receiver: Address,
+        assets: U256,
+        shares: U256,
+    ) -> Result<(), Vec<u8>> {
+        let sender = msg::sender();
+        let asset = self.asset.get();
+        self.guard.enter()?;
+        safe_erc20::safe_transfer_from(asset, sender, contract::address(), assets)?;
+        self.guard.exit();
+        erc20.mint(receiver, shares)?;
+
+        evm::log(Deposit {
+            sender,
+            owner: receiver,
+            assets,
+            shares,
+        });
+        self.pay_fee(fee_on_total(assets, C::entry_fee_bps()))
+    }
+
+    /// `assets` exclude the exit fee, paid to the fee recipient after `receiver`.
+    fn withdraw_internal<T: Erc20Params>(
+        &mut self,
+        erc20: &mut Erc20<T>,
+        receiver: Address,
+        owner: Address,
+        assets: U256,
+        shares: U256,
+    ) -> Result<(), Vec<u8>> {
+        let sender = msg::sender();
+        if sender != owner {
+            erc20.spend_allowance(owner, sender, shares)?;
+        }
+        erc20.burn(owner, shares)?;
+        self.guard.enter()?;
+        safe_erc20::safe_transfer(self.asset.get(), receiver, assets)?;
+        self.guard.exit();
+
+        evm::log(Withdraw {
+            sender,
+            receiver,
+            owner,
+            assets,
+            shares,
+        });
+        self.pay_fee(fee_on_raw(assets, C::exit_fee_bps()))
+    }
+
+    fn pay_fee(&mut self, fee: U256) -> Result<(), Vec<u8>> {
+        let fee_recipient = self.fee_recipient.get();
+        if fee == U256::ZERO || fee_recipient == Address::ZERO || fee_recipient == contract::address() {
+            return Ok(());
+        }
+        self.guard.enter()?;
+        safe_erc20::safe_transfer(self.asset.get(), fee_recipient, fee)?;
+        self.guard.exit();
+        Ok(())
+    }
+
+    fn to_shares<T: Erc20Params>(&self, erc20: &Erc20<T>, assets: U256, rounding: Rounding) -> Result<U256, Vec<u8>> {
+        Ok(assets_to_shares(assets, erc20.total_supply_internal(), self.total_assets()?, rounding)?)
+    }
+
+    fn to_assets<T: Erc20Params>(&self, erc20: &Erc20<T>, shares: U256, rounding: Rounding) -> Result<U256, Vec<u8>> {
+        Ok(shares_to_assets(shares, erc20.total_supply_internal(), self.total_assets()?, rounding)?)
+    }
+}
+
+/// ERC-4626 names are pinned, aggregators call them by selector.
+#[external]
+impl<C: VaultConfig> Erc4626<C> {
+    #[selector(name = "asset")]
+    pub fn asset(&self) -> Result<Address, Erc4626Error> {
+        Ok(self.asset.get())
+    }
+
+    #[selector(name = "feeRecipient")]
+    pub fn fee_recipient(&self) -> Result<Address, Erc4626Error> {
+        Ok(self.fee_recipient.get())
+    }
+
+    /// Returns `(entry fee, exit fee)` in basis points.
+    #[selector(name = "vaultFees")]
+    pub fn vault_fees(&self) -> Result<(U256, U256), Erc4626Error> {
+        Ok((U256::from(C::entry_fee_bps()), U256::from(C::exit_fee_bps())))
+    }
+}
+
+/// Shares worth `assets` with `total_supply` shares backed by `total_assets`, counting one
+/// virtual share and asset.
+pub fn assets_to_shares(
+    assets: U256,
+    total_supply: U256,
+    total_assets: U256,
+    rounding: Rounding,
+) -> Result<U256, Erc4626Error> {
+    math::mul_div(assets, total_supply.saturating_add(U256::from(1)), total_assets.saturating_add(U256::from(1)), rounding)
+        .map_err(|_| Erc4626Error::Erc4626ConversionOverflow(Erc4626ConversionOverflow { amount: assets }))
+}
+
+/// Assets worth `shares` with `total_supply` shares backed by `total_assets`, counting one
+/// virtual share and asset.
+pub fn shares_to_assets(
+    shares: U256,
+    total_supply: U256,
+    total_assets: U256,
+    rounding: Rounding,
+) -> Result<U256, Erc4626Error> {
+    math::mul_div(shares, total_assets.saturating_add(U256::from(1)), total_supply.saturating_add(U256::from(1)), rounding)
+        .map_err(|_| Erc4626Error::Erc4626ConversionOverflow(Erc4626ConversionOverflow { amount: shares }))
+}
+
+/// Fee of `fee_bps` on top of `assets`, rounded up, saturating for fees above 100%.
+pub fn fee_on_raw(assets: U256, fee_bps: u64) -> U256 {
+    math::mul_div(assets, U256::from(fee_bps), U256::from(FEE_DENOMINATOR), Rounding::Up).unwrap_or(U256::MAX)
+}
+
+/// Part of `assets` that is the fee of `fee_bps` on the rest, rounded up.
+pub fn fee_on_total(assets: U256, fee_bps: u64) -> U256 {
+    // below `assets`, the denominator is above `fee_bps`
+    math::mul_div(assets, U256::from(fee_bps), U256::from(fee_bps + FEE_DENOMINATOR), Rounding::Up).unwrap_or(assets)
+}
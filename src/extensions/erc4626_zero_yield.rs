@@ -0,0 +1,91 @@
+//! ERC-4626 shaped views over a plain token, for aggregators and tooling only speaking vaults:
+//! the token is its own asset, one share is one asset and nothing accrues. Deposits and
+//! redemptions are not supported, the `max*` views return 0 so integrators never try them.
+//!
+//! Only built with the `erc4626-adapter` feature. Inherit `Erc4626ZeroYield` and expose
+//! `totalAssets` from the composing token with {total_assets}, it needs the `Erc20`.
+//! See `examples/compose_erc4626_zero_yield.rs` for a complete composition.
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    contract,
+    prelude::*,
+};
+
+use crate::tokens::erc20::{Erc20, Erc20Params};
+
+sol_storage! {
+    /// Stateless, every view derives from the token itself.
+    pub struct Erc4626ZeroYield {}
+}
+
+impl Erc4626ZeroYield {
+    /// `totalAssets`: the whole supply, as every token is a share of itself.
+    pub fn total_assets<T: Erc20Params>(erc20: &Erc20<T>) -> Result<U256, Vec<u8>> {
+        Ok(erc20.total_supply_internal())
+    }
+}
+
+/// ERC-4626 names are pinned, aggregators call them by selector.
+#[external]
+impl Erc4626ZeroYield {
+    /// Returns the token itself.
+    #[selector(name = "asset")]
+    pub fn asset(&self) -> Result<Address, Vec<u8>> {
+        Ok(contract::address())
+    }
+
+    #[selector(name = "convertToShares")]
+    pub fn convert_to_shares(&self, assets: U256) -> Result<U256, Vec<u8>> {
+        Ok(assets)
+    }
+
+    #[selector(name = "convertToAssets")]
+    pub fn convert_to_assets(&self, shares: U256) -> Result<U256, Vec<u8>> {
+        Ok(shares)
+    }
+
+    #[selector(name = "previewDeposit")]
+    pub fn preview_deposit(&self, assets: U256) -> Result<U256, Vec<u8>> {
+        Ok(assets)
+    }
+
+    #[selector(name = "previewMint")]
+    pub fn preview_mint(&self, shares: U256) -> Result<U256, Vec<u8>> {
+        Ok(shares)
+    }
+
+    #[selector(name = "previewWithdraw")]
+    pub fn preview_withdraw(&self, assets: U256) -> Result<U256, Vec<u8>> {
+        Ok(assets)
+    }
+
+    #[selector(name = "previewRedeem")]
+    pub fn preview_redeem(&self, shares: U256) -> Result<U256, Vec<u8>> {
+        Ok(shares)
+    }
+
+    /// Returns 0, deposits are not supported.
+    #[selector(name = "maxDeposit")]
+    pub fn max_deposit(&self, _receiver: Address) -> Result<U256, Vec<u8>> {
+        Ok(U256::ZERO)
+    }
+
+    /// Returns 0, mints of shares are not supported.
+    #[selector(name = "maxMint")]
+    pub fn max_mint(&self, _receiver: Address) -> Result<U256, Vec<u8>> {
+        Ok(U256::ZERO)
+    }
+
+    /// Returns 0, withdrawals are not supported.
+    #[selector(name = "maxWithdraw")]
+    pub fn max_withdraw(&self, _owner: Address) -> Result<U256, Vec<u8>> {
+        Ok(U256::ZERO)
+    }
+
+    /// Returns 0, redemptions are not supported.
+    #[selector(name = "maxRedeem")]
+    pub fn max_redeem(&self, _owner: Address) -> Result<U256, Vec<u8>> {
+        Ok(U256::ZERO)
+    }
+}
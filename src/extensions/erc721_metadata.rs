@@ -0,0 +1,79 @@
+use alloc::{string::String, vec::Vec};
+use stylus_sdk::{
+    alloy_primitives::{FixedBytes, U256},
+    alloy_sol_types::sol,
+    evm,
+    prelude::*,
+};
+
+use crate::tokens::erc721::{Erc721, Erc721Error, Erc721Params};
+use crate::utils::bounded_string::{BoundedString, BoundedStringError};
+use crate::utils::erc165::{self, Erc165};
+use crate::utils::strings;
+
+/// Max length of the base URI, enough for an IPFS/Arweave folder or an API endpoint.
+pub const MAX_BASE_URI_LENGTH: usize = 256;
+
+sol_storage! {
+    /// ERC-721 metadata `tokenURI`: the base URI followed by the decimal token id (e.g.
+    /// `ipfs://<cid>/` gives `ipfs://<cid>/42`), empty while no base URI is set, as in
+    /// OpenZeppelin `ERC721`. Collection level metadata is `ContractUri`.
+    ///
+    /// `set_base_uri` is internal, the composing collection exposes it behind its own
+    /// authorization (e.g. `Ownable`). `tokenURI` needs the `Erc721` to reject nonexistent
+    /// tokens, the collection exposes it with `token_uri` and combines `IERC721_METADATA` in
+    /// `supportsInterface`. See `examples/compose_erc721.rs` for a complete composition.
+    pub struct Erc721Metadata {
+        BoundedString<MAX_BASE_URI_LENGTH> base_uri;
+    }
+}
+
+sol! {
+    /// ERC-4906: signals marketplaces to refresh the metadata of tokens `from_token_id` to
+    /// `to_token_id`, emitted for all tokens when the base URI changes.
+    event BatchMetadataUpdate(uint256 from_token_id, uint256 to_token_id);
+}
+
+impl Erc721Metadata {
+    /// Sets the base URI of every token, at most `MAX_BASE_URI_LENGTH` bytes.
+    ///
+    /// Emits a {BatchMetadataUpdate} event covering all token ids.
+    pub fn set_base_uri(&mut self, base_uri: &str) -> Result<(), BoundedStringError> {
+        self.base_uri.set_str(base_uri)?;
+        evm::log(BatchMetadataUpdate {
+            from_token_id: U256::ZERO,
+            to_token_id: U256::MAX,
+        });
+        Ok(())
+    }
+
+    /// `tokenURI` of `token_id`, reverting with `Erc721NonexistentToken` for a token not minted.
+    pub fn token_uri<T: Erc721Params>(&self, erc721: &Erc721<T>, token_id: U256) -> Result<String, Erc721Error> {
+        erc721.require_owned(token_id)?;
+        Ok(token_uri(&self.base_uri.get_string(), token_id))
+    }
+}
+
+/// ERC-721 metadata, with the `name`/`symbol` of the `Erc721`.
+impl Erc165 for Erc721Metadata {
+    fn supports_interface(interface_id: FixedBytes<4>) -> bool {
+        erc165::supports(interface_id, &[erc165::IERC721_METADATA])
+    }
+}
+
+#[external]
+impl Erc721Metadata {
+    /// Returns the base URI, empty if not set.
+    #[selector(name = "baseURI")]
+    pub fn base_uri(&self) -> Result<String, Vec<u8>> {
+        Ok(self.base_uri.get_string())
+    }
+}
+
+/// `base_uri` followed by the decimal `token_id`, empty for an empty base URI.
+pub fn token_uri(base_uri: &str, token_id: U256) -> String {
+    if base_uri.is_empty() {
+        return String::new();
+    }
+    strings::concat(&[base_uri, &strings::to_decimal(token_id)])
+}
@@ -0,0 +1,127 @@
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{Address, FixedBytes, B256, U256},
+    alloy_sol_types::{sol, SolError},
+    block,
+    prelude::*,
+};
+
+use crate::tokens::erc721::{Erc721, Erc721Params};
+use crate::utils::{
+    eip712::{self, address_word, u256_word},
+    erc165::{self, Erc165},
+    signature_checker,
+};
+
+/// EIP-712 type of an ERC-4494 permit.
+pub const PERMIT_TYPE: &str = "Permit(address spender,uint256 tokenId,uint256 nonce,uint256 deadline)";
+
+sol_storage! {
+    /// ERC-4494 permits: the owner of a token signs `Permit(spender, tokenId, nonce, deadline)`
+    /// and anyone submits it to approve `spender` for the token, so marketplaces list NFTs
+    /// without an approval transaction from the owner. Signatures are checked under the
+    /// collection domain of `utils::eip712` (`erc721_domain_separator`), contract owners sign
+    /// through EIP-1271.
+    ///
+    /// Nonces are per token and, as required by the ERC, must change on every transfer: the
+    /// composing collection calls `increment_nonce(token_id)` from its transfer externals, so a
+    /// permit signed by a previous owner can never be replayed. It exposes `permit` passing its
+    /// `Erc721` and combines `IERC4494` in `supportsInterface`.
+    /// See `examples/compose_erc721_permit.rs` for a complete composition.
+    pub struct Erc721Permit {
+        /// token id => nonce of the next permit
+        mapping(uint256 => uint256) token_nonces;
+    }
+}
+
+sol! {
+    /// Indicates a permit submitted after its `deadline`.
+    error Erc721PermitExpired(uint256 deadline);
+
+    /// Indicates a signature not made by the owner of the token.
+    error Erc721PermitInvalidSignature(uint256 token_id);
+}
+
+pub enum Erc721PermitError {
+    Erc721PermitExpired(Erc721PermitExpired),
+    Erc721PermitInvalidSignature(Erc721PermitInvalidSignature),
+}
+
+impl From<Erc721PermitError> for Vec<u8> {
+    fn from(e: Erc721PermitError) -> Vec<u8> {
+        match e {
+            Erc721PermitError::Erc721PermitExpired(e) => e.encode(),
+            Erc721PermitError::Erc721PermitInvalidSignature(e) => e.encode(),
+        }
+    }
+}
+
+crate::errors::error_helpers!(Erc721PermitError {
+    Erc721PermitExpired,
+    Erc721PermitInvalidSignature,
+});
+
+impl Erc721Permit {
+    /// Approves `spender` for `token_id` with the signature of its owner, consuming the nonce of
+    /// the token.
+    ///
+    /// Emits an {Approval} event.
+    pub fn permit<T: Erc721Params>(
+        &mut self,
+        erc721: &mut Erc721<T>,
+        spender: Address,
+        token_id: U256,
+        deadline: U256,
+        signature: &[u8],
+    ) -> Result<(), Vec<u8>> {
+        if U256::from(block::timestamp()) > deadline {
+            return Err(Erc721PermitError::Erc721PermitExpired(Erc721PermitExpired { deadline }).into());
+        }
+        let owner = erc721.require_owned(token_id)?;
+        let nonce = self.token_nonces.get(token_id);
+        let hash = eip712::to_typed_data_hash(
+            eip712::erc721_domain_separator::<T>(),
+            permit_hash(spender, token_id, nonce, deadline),
+        );
+        if !signature_checker::is_valid_signature_now(owner, hash, signature) {
+            return Err(Erc721PermitError::Erc721PermitInvalidSignature(Erc721PermitInvalidSignature {
+                token_id,
+            })
+            .into());
+        }
+        self.token_nonces.insert(token_id, nonce + U256::from(1));
+        erc721.approve_internal(spender, token_id, owner)?;
+        Ok(())
+    }
+
+    /// Invalidates the pending permits of `token_id`, call it on every transfer of the token.
+    pub fn increment_nonce(&mut self, token_id: U256) {
+        let nonce = self.token_nonces.get(token_id);
+        self.token_nonces.insert(token_id, nonce + U256::from(1));
+    }
+}
+
+/// ERC-4494, assuming the composing collection exposes `permit`.
+impl Erc165 for Erc721Permit {
+    fn supports_interface(interface_id: FixedBytes<4>) -> bool {
+        erc165::supports(interface_id, &[erc165::IERC4494])
+    }
+}
+
+/// ERC-4494 names are pinned, as for the token externals.
+#[external]
+impl Erc721Permit {
+    /// Returns the nonce of the next permit of `token_id`.
+    #[selector(name = "nonces")]
+    pub fn nonces(&self, token_id: U256) -> Result<U256, Erc721PermitError> {
+        Ok(self.token_nonces.get(token_id))
+    }
+}
+
+/// EIP-712 struct hash of a `Permit`.
+pub fn permit_hash(spender: Address, token_id: U256, nonce: U256, deadline: U256) -> B256 {
+    eip712::hash_struct(
+        PERMIT_TYPE,
+        &[address_word(spender), u256_word(token_id), u256_word(nonce), u256_word(deadline)],
+    )
+}
@@ -0,0 +1,98 @@
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{Address, FixedBytes, U256},
+    alloy_sol_types::sol,
+    block, evm, msg,
+    prelude::*,
+};
+
+use crate::tokens::erc721::{Erc721, Erc721Params};
+use crate::utils::erc165::{self, Erc165};
+
+sol_storage! {
+    /// ERC-4907 rentals: the owner (or an approved account) grants a `user` role on a token until
+    /// `expires`, the user can use the token in games/apps but not transfer it.
+    ///
+    /// Expose `set_user` from the composing collection with `#[selector(name = "setUser")]`,
+    /// passing its `Erc721`, and call `clear_user(token_id)` in every transfer external, a
+    /// transferred token is not rented any more. See `examples/compose_erc721_rental.rs` for a
+    /// complete composition.
+    pub struct Erc721Rental {
+        mapping(uint256 => address) users;
+        /// unix timestamp at which the user role ends
+        mapping(uint256 => uint256) expires;
+    }
+}
+
+sol! {
+    event UpdateUser(uint256 indexed token_id, address indexed user, uint64 expires);
+}
+
+impl Erc721Rental {
+    /// Sets `user` of `token_id` until `expires`, the caller must be the owner or approved.
+    /// The zero address removes the user.
+    ///
+    /// Emits an {UpdateUser} event.
+    pub fn set_user<T: Erc721Params>(
+        &mut self,
+        erc721: &Erc721<T>,
+        token_id: U256,
+        user: Address,
+        expires: u64,
+    ) -> Result<(), Vec<u8>> {
+        let owner = erc721.require_owned(token_id)?;
+        erc721.check_authorized(owner, msg::sender(), token_id)?;
+        self.users.insert(token_id, user);
+        self.expires.insert(token_id, U256::from(expires));
+
+        evm::log(UpdateUser {
+            token_id,
+            user,
+            expires,
+        });
+        Ok(())
+    }
+
+    /// Removes the user of `token_id`, to be called when the token is transferred or burned.
+    ///
+    /// Emits an {UpdateUser} event if the token had a user.
+    pub fn clear_user(&mut self, token_id: U256) {
+        if self.users.get(token_id) == Address::ZERO {
+            return;
+        }
+        self.users.insert(token_id, Address::ZERO);
+        self.expires.insert(token_id, U256::ZERO);
+
+        evm::log(UpdateUser {
+            token_id,
+            user: Address::ZERO,
+            expires: 0,
+        });
+    }
+}
+
+/// ERC-4907, assuming the composing collection exposes `setUser`.
+impl Erc165 for Erc721Rental {
+    fn supports_interface(interface_id: FixedBytes<4>) -> bool {
+        erc165::supports(interface_id, &[erc165::IERC4907])
+    }
+}
+
+/// ERC-4907 names are pinned, as for the token externals.
+#[external]
+impl Erc721Rental {
+    /// Returns the user of `token_id`, the zero address if there is none or the rental expired.
+    #[selector(name = "userOf")]
+    pub fn user_of(&self, token_id: U256) -> Result<Address, Vec<u8>> {
+        if self.expires.get(token_id) < U256::from(block::timestamp()) {
+            return Ok(Address::ZERO);
+        }
+        Ok(self.users.get(token_id))
+    }
+
+    /// Returns the end of the rental of `token_id`, 0 if there is no user.
+    #[selector(name = "userExpires")]
+    pub fn user_expires(&self, token_id: U256) -> Result<U256, Vec<u8>> {
+        Ok(self.expires.get(token_id))
+    }
+}
@@ -0,0 +1,72 @@
+use alloc::vec::Vec;
+use stylus_sdk::{alloy_primitives::Address, alloy_sol_types::sol, evm, prelude::*};
+
+use crate::access::access_control::{role_id, AccessControl};
+
+/// Name of the role adding and removing exemptions, see `role_id`.
+pub const FEE_EXEMPTION_MANAGER_ROLE: &str = "FEE_EXEMPTION_MANAGER_ROLE";
+
+sol_storage! {
+    /// Single list of accounts exempt from transfer fees and limits (custodian hot wallets,
+    /// liquidity pools, the treasury...), shared by the fee-type extensions (fee on transfer,
+    /// auto-burn, max transfer/rate limits) so every one of them has the same list and admin API.
+    ///
+    /// Compose once in the token with `#[borrow] FeeExemptions fee_exemptions;` and inherit it, then
+    /// pass `&self.fee_exemptions` to the extensions checking exemptions (e.g.
+    /// `Erc20Fee::transfer_with_fee`, `Erc20RateLimit::record_outbound`, `Erc20TransferCooldown::record_outbound`).
+    ///
+    /// `add_fee_exemption`/`remove_fee_exemption` require `FEE_EXEMPTION_MANAGER_ROLE` of the
+    /// token's `AccessControl`, the token exposes them passing it; `set_exempt` skips the check
+    /// for the composing token.
+    pub struct FeeExemptions {
+        mapping(address => bool) exempt;
+    }
+}
+
+sol! {
+    event FeeExemptionUpdated(address indexed account, bool exempt);
+}
+
+impl FeeExemptions {
+    /// Whether `account` is exempt, checked by the consuming extensions.
+    pub fn is_exempt(&self, account: Address) -> bool {
+        self.exempt.get(account)
+    }
+
+    /// Exempts `account` from the fees and limits of every consuming extension, the caller needs
+    /// `FEE_EXEMPTION_MANAGER_ROLE`.
+    ///
+    /// Emits a {FeeExemptionUpdated} event.
+    pub fn add_fee_exemption(&mut self, access: &AccessControl, account: Address) -> Result<(), Vec<u8>> {
+        access.only_role(role_id(FEE_EXEMPTION_MANAGER_ROLE))?;
+        self.set_exempt(account, true);
+        Ok(())
+    }
+
+    /// Subjects `account` back to the fees and limits of every consuming extension, the caller
+    /// needs `FEE_EXEMPTION_MANAGER_ROLE`.
+    ///
+    /// Emits a {FeeExemptionUpdated} event.
+    pub fn remove_fee_exemption(&mut self, access: &AccessControl, account: Address) -> Result<(), Vec<u8>> {
+        access.only_role(role_id(FEE_EXEMPTION_MANAGER_ROLE))?;
+        self.set_exempt(account, false);
+        Ok(())
+    }
+
+    /// Sets the exemption of `account` without authorization, for the composing token (e.g. to
+    /// exempt itself or a pool at init).
+    ///
+    /// Emits a {FeeExemptionUpdated} event.
+    pub fn set_exempt(&mut self, account: Address, exempt: bool) {
+        self.exempt.insert(account, exempt);
+        evm::log(FeeExemptionUpdated { account, exempt });
+    }
+}
+
+#[external]
+impl FeeExemptions {
+    #[selector(name = "isFeeExempt")]
+    pub fn is_fee_exempt(&self, account: Address) -> Result<bool, Vec<u8>> {
+        Ok(self.exempt.get(account))
+    }
+}
@@ -0,0 +1,36 @@
+pub mod erc20_rate_limit;
+pub mod erc20_interest_bearing;
+pub mod erc20_signature_transfer;
+pub mod erc20_crosschain;
+pub mod erc20_global_settlement;
+pub mod erc20_redeemable;
+pub mod fee_exemptions;
+pub mod erc20_spender_caps;
+pub mod erc20_balances_root;
+pub mod nft_receiver;
+pub mod erc20_approve_by_sig;
+pub mod erc721_rental;
+pub mod erc2981;
+pub mod erc20_gasback;
+pub mod erc20_votes;
+pub mod contract_uri;
+pub mod erc20_cohorts;
+pub mod erc20_mint_authorization;
+pub mod erc20_transfer_cooldown;
+pub mod erc20_wrapper;
+pub mod erc20_fee;
+pub mod erc20_pausable;
+pub mod erc20_cap;
+pub mod erc721_permit;
+pub mod erc20_stream;
+pub mod erc20_metadata_storage;
+pub mod erc721_metadata;
+pub mod erc4626;
+pub mod erc20_blocklist;
+pub mod erc20_allowlist;
+pub mod erc20_batch_transfer;
+pub mod erc20_send_hooks;
+pub mod erc20_mintable;
+pub mod erc20_temporary_approval;
+#[cfg(feature = "erc4626-adapter")]
+pub mod erc4626_zero_yield;
@@ -0,0 +1,177 @@
+use alloc::vec::Vec;
+use stylus_sdk::{
+    abi::Bytes,
+    alloy_primitives::{Address, FixedBytes, U256},
+    alloy_sol_types::{sol, SolError},
+    call::{self, Call},
+    contract, evm,
+    prelude::*,
+};
+
+use crate::utils::eip712::{address_word, u256_word};
+
+pub use crate::utils::transfer_checks::{
+    ON_ERC1155_BATCH_RECEIVED_SELECTOR, ON_ERC1155_RECEIVED_SELECTOR, ON_ERC721_RECEIVED_SELECTOR,
+};
+
+/// `bytes4(keccak256("safeTransferFrom(address,address,uint256)"))`
+const ERC721_SAFE_TRANSFER_FROM_SELECTOR: [u8; 4] = [0x42, 0x84, 0x2e, 0x0e];
+/// `bytes4(keccak256("safeTransferFrom(address,address,uint256,uint256,bytes)"))`
+const ERC1155_SAFE_TRANSFER_FROM_SELECTOR: [u8; 4] = [0xf2, 0x42, 0x43, 0x2a];
+
+sol_storage! {
+    /// ERC721/ERC1155 receiver hooks for ERC20 tokens, so NFTs mistakenly sent with
+    /// `safeTransferFrom` to the token contract are accepted and can be forwarded out with
+    /// `rescue_erc721`/`rescue_erc1155` instead of being locked forever.
+    ///
+    /// Opt-in: until the composing token calls `set_accept_nfts(true)` the hooks reject every
+    /// transfer, as a contract without them would. The rescue methods are internal, the composing
+    /// token exposes them behind its own authorization (e.g. `Ownable`).
+    /// See `examples/compose_nft_receiver.rs` for a complete composition.
+    pub struct NftReceiver {
+        bool accept_nfts;
+    }
+}
+
+sol! {
+    event NftsAcceptanceUpdated(bool accept);
+    event Erc721Rescued(address indexed collection, uint256 indexed token_id, address indexed to);
+    event Erc1155Rescued(address indexed collection, uint256 indexed id, uint256 amount, address indexed to);
+
+    /// Indicates an NFT received while the receiver hooks are disabled.
+    error NftReceiverDisabled();
+
+    /// Indicates that forwarding NFTs out of `collection` failed.
+    error NftRescueFailed(address collection);
+}
+
+pub enum NftReceiverError {
+    NftReceiverDisabled(NftReceiverDisabled),
+    NftRescueFailed(NftRescueFailed),
+}
+
+impl From<NftReceiverError> for Vec<u8> {
+    fn from(e: NftReceiverError) -> Vec<u8> {
+        match e {
+            NftReceiverError::NftReceiverDisabled(e) => e.encode(),
+            NftReceiverError::NftRescueFailed(e) => e.encode(),
+        }
+    }
+}
+
+crate::errors::error_helpers!(NftReceiverError {
+    NftReceiverDisabled,
+    NftRescueFailed,
+});
+
+impl NftReceiver {
+    /// Enables or disables the receiver hooks.
+    ///
+    /// Emits a {NftsAcceptanceUpdated} event.
+    pub fn set_accept_nfts(&mut self, accept: bool) {
+        self.accept_nfts.set(accept);
+        evm::log(NftsAcceptanceUpdated { accept });
+    }
+
+    /// Sends `token_id` of `collection` held by this contract to `to`.
+    ///
+    /// Emits an {Erc721Rescued} event.
+    pub fn rescue_erc721(&mut self, collection: Address, token_id: U256, to: Address) -> Result<(), NftReceiverError> {
+        let mut data = ERC721_SAFE_TRANSFER_FROM_SELECTOR.to_vec();
+        data.extend_from_slice(address_word(contract::address()).as_slice());
+        data.extend_from_slice(address_word(to).as_slice());
+        data.extend_from_slice(u256_word(token_id).as_slice());
+        call::call(Call::new(), collection, &data).map_err(|_| rescue_failed(collection))?;
+
+        evm::log(Erc721Rescued {
+            collection,
+            token_id,
+            to,
+        });
+        Ok(())
+    }
+
+    /// Sends `amount` of `id` of `collection` held by this contract to `to`, with empty data.
+    ///
+    /// Emits an {Erc1155Rescued} event.
+    pub fn rescue_erc1155(
+        &mut self,
+        collection: Address,
+        id: U256,
+        amount: U256,
+        to: Address,
+    ) -> Result<(), NftReceiverError> {
+        let mut data = ERC1155_SAFE_TRANSFER_FROM_SELECTOR.to_vec();
+        data.extend_from_slice(address_word(contract::address()).as_slice());
+        data.extend_from_slice(address_word(to).as_slice());
+        data.extend_from_slice(u256_word(id).as_slice());
+        data.extend_from_slice(u256_word(amount).as_slice());
+        // offset of the empty `bytes data` tail, after the 5 head words, then its zero length
+        data.extend_from_slice(u256_word(U256::from(5 * 32)).as_slice());
+        data.extend_from_slice(u256_word(U256::ZERO).as_slice());
+        call::call(Call::new(), collection, &data).map_err(|_| rescue_failed(collection))?;
+
+        evm::log(Erc1155Rescued {
+            collection,
+            id,
+            amount,
+            to,
+        });
+        Ok(())
+    }
+
+    fn accepting(&self, selector: [u8; 4]) -> Result<FixedBytes<4>, NftReceiverError> {
+        if !self.accept_nfts.get() {
+            return Err(NftReceiverError::NftReceiverDisabled(NftReceiverDisabled {}));
+        }
+        Ok(FixedBytes::from(selector))
+    }
+}
+
+/// Hook names are pinned, the automatic conversion would export `onErc721Received`.
+#[external]
+impl NftReceiver {
+    #[selector(name = "acceptsNfts")]
+    pub fn accepts_nfts(&self) -> Result<bool, NftReceiverError> {
+        Ok(self.accept_nfts.get())
+    }
+
+    #[selector(name = "onERC721Received")]
+    pub fn on_erc721_received(
+        &mut self,
+        _operator: Address,
+        _from: Address,
+        _token_id: U256,
+        _data: Bytes,
+    ) -> Result<FixedBytes<4>, NftReceiverError> {
+        self.accepting(ON_ERC721_RECEIVED_SELECTOR)
+    }
+
+    #[selector(name = "onERC1155Received")]
+    pub fn on_erc1155_received(
+        &mut self,
+        _operator: Address,
+        _from: Address,
+        _id: U256,
+        _value: U256,
+        _data: Bytes,
+    ) -> Result<FixedBytes<4>, NftReceiverError> {
+        self.accepting(ON_ERC1155_RECEIVED_SELECTOR)
+    }
+
+    #[selector(name = "onERC1155BatchReceived")]
+    pub fn on_erc1155_batch_received(
+        &mut self,
+        _operator: Address,
+        _from: Address,
+        _ids: Vec<U256>,
+        _values: Vec<U256>,
+        _data: Bytes,
+    ) -> Result<FixedBytes<4>, NftReceiverError> {
+        self.accepting(ON_ERC1155_BATCH_RECEIVED_SELECTOR)
+    }
+}
+
+fn rescue_failed(collection: Address) -> NftReceiverError {
+    NftReceiverError::NftRescueFailed(NftRescueFailed { collection })
+}
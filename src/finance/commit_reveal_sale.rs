@@ -0,0 +1,341 @@
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{Address, B256, U256},
+    alloy_sol_types::{sol, SolError},
+    block, contract, evm, msg,
+    prelude::*,
+};
+
+use crate::tokens::erc20::{Erc20, Erc20Params};
+use crate::utils::eip712::{address_word, hash_words, u256_word};
+use crate::utils::math::{self, Rounding};
+use crate::utils::safe_erc20;
+
+/// Precision of `price`, the payment owed for `PRICE_PRECISION` sale token units.
+pub const PRICE_PRECISION: u128 = 1_000_000_000_000_000_000;
+
+sol_storage! {
+    /// Fair launch sale: during the commit window buyers deposit payment tokens (an ERC20) together
+    /// with `keccak256(abi.encode(buyer, amount, salt))`, hiding the amount of sale tokens they want.
+    /// During the reveal window they disclose `(amount, salt)`, the deposit must cover
+    /// `amount * price`. After the reveal window every revealed buyer claims its allocation, scaled
+    /// down pro-rata if the revealed demand exceeds `sale_supply`, minted from the composing token,
+    /// and is refunded the unspent deposit. Unrevealed commitments get no allocation but are refunded.
+    ///
+    /// Expose `commit`, `reveal` and `claim` from the composing token, passing its `Erc20` to `claim`.
+    /// See `examples/compose_commit_reveal_sale.rs` for a complete composition.
+    pub struct CommitRevealSale {
+        /// ERC20 used to pay for the sale tokens
+        address payment_token;
+        /// payment owed for `PRICE_PRECISION` sale tokens
+        uint256 price;
+        /// maximum amount of sale tokens minted by the sale
+        uint256 sale_supply;
+        uint256 commit_start;
+        /// end of the commit window, start of the reveal window
+        uint256 commit_end;
+        /// end of the reveal window, start of the claims
+        uint256 reveal_end;
+        mapping(address => bytes32) commitments;
+        mapping(address => uint256) deposits;
+        /// revealed amount of sale tokens requested by a buyer
+        mapping(address => uint256) requested;
+        mapping(address => bool) claimed;
+        /// sum of the revealed requests
+        uint256 total_requested;
+        /// payments of the claimed allocations, withdrawable by the sale admin
+        uint256 proceeds;
+    }
+}
+
+sol! {
+    event SaleConfigured(address indexed payment_token, uint256 price, uint256 sale_supply, uint256 commit_start, uint256 commit_end, uint256 reveal_end);
+    event Committed(address indexed buyer, bytes32 commitment, uint256 deposit);
+    event Revealed(address indexed buyer, uint256 amount);
+    event AllocationClaimed(address indexed buyer, uint256 allocation, uint256 cost, uint256 refund);
+    event ProceedsWithdrawn(address indexed to, uint256 amount);
+
+    /// Indicates a call outside of the window of the sale phase it belongs to.
+    /// * `start` - start of the window, inclusive.
+    /// * `end` - end of the window, exclusive.
+    error SaleOutsideWindow(uint256 start, uint256 end);
+
+    /// Indicates invalid windows (`commit_start <= commit_end <= reveal_end` and non empty) or a zero price.
+    error SaleInvalidConfig();
+
+    /// Indicates that the sale was already configured, its terms can not change once set.
+    error SaleAlreadyConfigured();
+
+    /// Indicates a zero commitment, which would not mark the buyer as committed.
+    error SaleInvalidCommitment(address buyer);
+
+    /// Indicates that the buyer already committed.
+    error SaleAlreadyCommitted(address buyer);
+
+    /// Indicates that `(amount, salt)` does not match the commitment of the buyer, or that there is none.
+    error SaleInvalidReveal(address buyer);
+
+    /// Indicates that the deposit of the buyer does not cover the revealed amount.
+    error SaleInsufficientDeposit(address buyer, uint256 deposit, uint256 cost);
+
+    /// Indicates that the buyer has nothing left to claim.
+    error SaleNothingToClaim(address buyer);
+}
+
+pub enum CommitRevealSaleError {
+    SaleOutsideWindow(SaleOutsideWindow),
+    SaleInvalidConfig(SaleInvalidConfig),
+    SaleAlreadyConfigured(SaleAlreadyConfigured),
+    SaleInvalidCommitment(SaleInvalidCommitment),
+    SaleAlreadyCommitted(SaleAlreadyCommitted),
+    SaleInvalidReveal(SaleInvalidReveal),
+    SaleInsufficientDeposit(SaleInsufficientDeposit),
+    SaleNothingToClaim(SaleNothingToClaim),
+}
+
+impl From<CommitRevealSaleError> for Vec<u8> {
+    fn from(e: CommitRevealSaleError) -> Vec<u8> {
+        match e {
+            CommitRevealSaleError::SaleOutsideWindow(e) => e.encode(),
+            CommitRevealSaleError::SaleInvalidConfig(e) => e.encode(),
+            CommitRevealSaleError::SaleAlreadyConfigured(e) => e.encode(),
+            CommitRevealSaleError::SaleInvalidCommitment(e) => e.encode(),
+            CommitRevealSaleError::SaleAlreadyCommitted(e) => e.encode(),
+            CommitRevealSaleError::SaleInvalidReveal(e) => e.encode(),
+            CommitRevealSaleError::SaleInsufficientDeposit(e) => e.encode(),
+            CommitRevealSaleError::SaleNothingToClaim(e) => e.encode(),
+        }
+    }
+}
+
+crate::errors::error_helpers!(CommitRevealSaleError {
+    SaleOutsideWindow,
+    SaleInvalidConfig,
+    SaleAlreadyConfigured,
+    SaleInvalidCommitment,
+    SaleAlreadyCommitted,
+    SaleInvalidReveal,
+    SaleInsufficientDeposit,
+    SaleNothingToClaim,
+});
+
+/// Commitment of `buyer` requesting `amount` sale tokens: `keccak256(abi.encode(buyer, amount, salt))`.
+pub fn commitment_hash(buyer: Address, amount: U256, salt: B256) -> B256 {
+    hash_words(&[address_word(buyer), u256_word(amount), salt])
+}
+
+/// Payment owed for `amount` sale tokens at `price`, rounded up in favour of the sale. A cost
+/// above `uint256` is reported as the maximum, more than any deposit.
+pub fn sale_cost(amount: U256, price: U256) -> U256 {
+    math::mul_div(amount, price, U256::from(PRICE_PRECISION), Rounding::Up).unwrap_or(U256::MAX)
+}
+
+/// Allocation of a buyer who revealed `requested` of the `total_requested` sale tokens, scaled
+/// down pro-rata, rounded down, when the demand exceeds `sale_supply`.
+pub fn pro_rata_allocation(requested: U256, total_requested: U256, sale_supply: U256) -> U256 {
+    if total_requested <= sale_supply {
+        return requested;
+    }
+    // sale_supply < total_requested, the result is below `requested` and can not overflow
+    math::mul_div(requested, sale_supply, total_requested, Rounding::Down).unwrap_or(U256::ZERO)
+}
+
+impl CommitRevealSale {
+    /// Configures the sale, to be called by the composing token behind its own authorization.
+    /// Only once: buyers commit under terms which can not change afterwards.
+    ///
+    /// Emits a {SaleConfigured} event.
+    pub fn configure(
+        &mut self,
+        payment_token: Address,
+        price: U256,
+        sale_supply: U256,
+        commit_start: U256,
+        commit_end: U256,
+        reveal_end: U256,
+    ) -> Result<(), CommitRevealSaleError> {
+        // a configured sale has a non zero price
+        if self.price.get() != U256::ZERO {
+            return Err(CommitRevealSaleError::SaleAlreadyConfigured(SaleAlreadyConfigured {}));
+        }
+        if price == U256::ZERO || commit_start >= commit_end || commit_end >= reveal_end {
+            return Err(CommitRevealSaleError::SaleInvalidConfig(SaleInvalidConfig {}));
+        }
+        self.payment_token.set(payment_token);
+        self.price.set(price);
+        self.sale_supply.set(sale_supply);
+        self.commit_start.set(commit_start);
+        self.commit_end.set(commit_end);
+        self.reveal_end.set(reveal_end);
+
+        evm::log(SaleConfigured {
+            payment_token,
+            price,
+            sale_supply,
+            commit_start,
+            commit_end,
+            reveal_end,
+        });
+        Ok(())
+    }
+
+    /// Commits the caller to a hidden request and pulls `deposit` payment tokens, which needs an
+    /// allowance of the caller to this contract.
+    ///
+    /// Emits a {Committed} event.
+    pub fn commit(&mut self, commitment: B256, deposit: U256) -> Result<(), Vec<u8>> {
+        self.within(self.commit_start.get(), self.commit_end.get())?;
+        let buyer = msg::sender();
+        // a zero commitment reads as no commitment, a second commit would overwrite the deposit
+        if commitment == B256::ZERO {
+            return Err(CommitRevealSaleError::SaleInvalidCommitment(SaleInvalidCommitment { buyer }).into());
+        }
+        if self.commitments.get(buyer) != B256::ZERO {
+            return Err(CommitRevealSaleError::SaleAlreadyCommitted(SaleAlreadyCommitted { buyer }).into());
+        }
+        self.commitments.insert(buyer, commitment);
+        self.deposits.insert(buyer, deposit);
+        safe_erc20::safe_transfer_from(self.payment_token.get(), buyer, contract::address(), deposit)?;
+
+        evm::log(Committed {
+            buyer,
+            commitment,
+            deposit,
+        });
+        Ok(())
+    }
+
+    /// Reveals the amount of sale tokens requested by the caller.
+    ///
+    /// Emits a {Revealed} event.
+    pub fn reveal(&mut self, amount: U256, salt: B256) -> Result<(), CommitRevealSaleError> {
+        self.within(self.commit_end.get(), self.reveal_end.get())?;
+        let buyer = msg::sender();
+        let commitment = self.commitments.get(buyer);
+        if commitment == B256::ZERO || commitment != commitment_hash(buyer, amount, salt) {
+            return Err(CommitRevealSaleError::SaleInvalidReveal(SaleInvalidReveal { buyer }));
+        }
+        let deposit = self.deposits.get(buyer);
+        let cost = self.cost_of(amount);
+        if cost > deposit {
+            return Err(CommitRevealSaleError::SaleInsufficientDeposit(
+                SaleInsufficientDeposit {
+                    buyer,
+                    deposit,
+                    cost,
+                },
+            ));
+        }
+        // a commitment can only be revealed once
+        self.commitments.insert(buyer, B256::ZERO);
+        self.requested.insert(buyer, amount);
+        self.total_requested.set(self.total_requested.get() + amount);
+
+        evm::log(Revealed { buyer, amount });
+        Ok(())
+    }
+
+    /// Mints the allocation of the caller and refunds its unspent deposit, returns the allocation.
+    ///
+    /// Emits a {Transfer} and an {AllocationClaimed} event.
+    pub fn claim<T: Erc20Params>(&mut self, erc20: &mut Erc20<T>) -> Result<U256, Vec<u8>> {
+        self.within(self.reveal_end.get(), U256::MAX)?;
+        let buyer = msg::sender();
+        let deposit = self.deposits.get(buyer);
+        if self.claimed.get(buyer) || deposit == U256::ZERO {
+            return Err(CommitRevealSaleError::SaleNothingToClaim(SaleNothingToClaim { buyer }).into());
+        }
+        self.claimed.insert(buyer, true);
+
+        let allocation = self.allocation_of(buyer);
+        let cost = self.cost_of(allocation);
+        let refund = deposit - cost;
+        self.proceeds.set(self.proceeds.get() + cost);
+        if allocation > U256::ZERO {
+            erc20.mint(buyer, allocation)?;
+        }
+        if refund > U256::ZERO {
+            safe_erc20::safe_transfer(self.payment_token.get(), buyer, refund)?;
+        }
+
+        evm::log(AllocationClaimed {
+            buyer,
+            allocation,
+            cost,
+            refund,
+        });
+        Ok(allocation)
+    }
+
+    /// Sends the payments of the claimed allocations to `to`, to be called by the composing token
+    /// behind its own authorization.
+    ///
+    /// Emits a {ProceedsWithdrawn} event.
+    pub fn withdraw_proceeds(&mut self, to: Address) -> Result<U256, Vec<u8>> {
+        let amount = self.proceeds.get();
+        self.proceeds.set(U256::ZERO);
+        safe_erc20::safe_transfer(self.payment_token.get(), to, amount)?;
+
+        evm::log(ProceedsWithdrawn { to, amount });
+        Ok(amount)
+    }
+
+    /// Revealed request of `buyer`, scaled down pro-rata when the total demand exceeds the sale supply.
+    fn allocation_of(&self, buyer: Address) -> U256 {
+        pro_rata_allocation(
+            self.requested.get(buyer),
+            self.total_requested.get(),
+            self.sale_supply.get(),
+        )
+    }
+
+    /// Payment owed for `amount` sale tokens, rounded up in favour of the sale.
+    fn cost_of(&self, amount: U256) -> U256 {
+        sale_cost(amount, self.price.get())
+    }
+
+    fn within(&self, start: U256, end: U256) -> Result<(), CommitRevealSaleError> {
+        let now = U256::from(block::timestamp());
+        if now < start || now >= end {
+            return Err(CommitRevealSaleError::SaleOutsideWindow(SaleOutsideWindow { start, end }));
+        }
+        Ok(())
+    }
+}
+
+#[external]
+impl CommitRevealSale {
+    /// Returns `(payment_token, price, sale_supply)`.
+    #[selector(name = "saleTerms")]
+    pub fn sale_terms(&self) -> Result<(Address, U256, U256), CommitRevealSaleError> {
+        Ok((self.payment_token.get(), self.price.get(), self.sale_supply.get()))
+    }
+
+    /// Returns `(commit_start, commit_end, reveal_end)`.
+    #[selector(name = "saleWindows")]
+    pub fn sale_windows(&self) -> Result<(U256, U256, U256), CommitRevealSaleError> {
+        Ok((self.commit_start.get(), self.commit_end.get(), self.reveal_end.get()))
+    }
+
+    /// Returns `(commitment, deposit, requested, claimed)` of `buyer`, the commitment is cleared once revealed.
+    #[selector(name = "salePosition")]
+    pub fn sale_position(&self, buyer: Address) -> Result<(B256, U256, U256, bool), CommitRevealSaleError> {
+        Ok((
+            self.commitments.get(buyer),
+            self.deposits.get(buyer),
+            self.requested.get(buyer),
+            self.claimed.get(buyer),
+        ))
+    }
+
+    #[selector(name = "totalRequested")]
+    pub fn total_requested(&self) -> Result<U256, CommitRevealSaleError> {
+        Ok(self.total_requested.get())
+    }
+
+    /// Returns the allocation `buyer` would claim with the current revealed demand.
+    #[selector(name = "allocation")]
+    pub fn allocation(&self, buyer: Address) -> Result<U256, CommitRevealSaleError> {
+        Ok(self.allocation_of(buyer))
+    }
+}
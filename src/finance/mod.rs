@@ -0,0 +1,3 @@
+pub mod commit_reveal_sale;
+pub mod refund_escrow;
+pub mod token_migrator;
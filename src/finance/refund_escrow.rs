@@ -0,0 +1,210 @@
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256, U8},
+    alloy_sol_types::{sol, SolError},
+    call, evm,
+    prelude::*,
+};
+
+/// Deposits are accepted, neither refunds nor the beneficiary withdrawal are open.
+pub const ESCROW_ACTIVE: u8 = 0;
+/// Depositors withdraw their deposits, no more deposits.
+pub const ESCROW_REFUNDING: u8 = 1;
+/// The beneficiary withdraws all the deposits, no more deposits.
+pub const ESCROW_CLOSED: u8 = 2;
+
+sol_storage! {
+    /// Ether escrow of a sale, as OpenZeppelin `RefundEscrow`: deposits are credited to their payee
+    /// while active, then the escrow is either closed, the beneficiary withdrawing every deposit,
+    /// or opened for refunds, each payee withdrawing its own. The ether stays in the composing
+    /// contract, the escrow only accounts for it: payable externals of the composing contract
+    /// credit `msg::value()` with `deposit`.
+    ///
+    /// `deposit`, `close` and `enable_refunds` are internal and called by the composing contract
+    /// behind its own rules (e.g. a sale goal), `withdraw` and `beneficiary_withdraw` send ether.
+    /// See `examples/crowdsale.rs` for a complete composition.
+    pub struct RefundEscrow {
+        address beneficiary;
+        /// `ESCROW_ACTIVE`, `ESCROW_REFUNDING` or `ESCROW_CLOSED`
+        uint8 state;
+        mapping(address => uint256) deposits;
+        /// sum of the deposits not withdrawn
+        uint256 total_deposits;
+    }
+}
+
+sol! {
+    event Deposited(address indexed payee, uint256 wei_amount);
+    event Withdrawn(address indexed payee, uint256 wei_amount);
+    event RefundsEnabled();
+    event RefundsClosed();
+
+    /// Indicates an operation not allowed in the current state of the escrow.
+    /// * `state` - current state.
+    /// * `expected` - state the operation requires.
+    error EscrowUnexpectedState(uint8 state, uint8 expected);
+
+    /// Indicates a zero address beneficiary or a beneficiary set twice.
+    error EscrowInvalidBeneficiary(address beneficiary);
+
+    /// Indicates that the receiver of a withdrawal reverted without a reason.
+    error EscrowFailedCall();
+}
+
+pub enum RefundEscrowError {
+    EscrowUnexpectedState(EscrowUnexpectedState),
+    EscrowInvalidBeneficiary(EscrowInvalidBeneficiary),
+    EscrowFailedCall(EscrowFailedCall),
+    /// revert data of the withdrawal receiver, passed through
+    Reverted(Vec<u8>),
+}
+
+impl From<RefundEscrowError> for Vec<u8> {
+    fn from(e: RefundEscrowError) -> Vec<u8> {
+        match e {
+            RefundEscrowError::EscrowUnexpectedState(e) => e.encode(),
+            RefundEscrowError::EscrowInvalidBeneficiary(e) => e.encode(),
+            RefundEscrowError::EscrowFailedCall(e) => e.encode(),
+            RefundEscrowError::Reverted(data) => data,
+        }
+    }
+}
+
+crate::errors::error_helpers!(RefundEscrowError {
+    EscrowUnexpectedState,
+    EscrowInvalidBeneficiary,
+    EscrowFailedCall,
+} passthrough { Reverted });
+
+impl RefundEscrow {
+    /// Sets the beneficiary, only once and non zero.
+    pub fn set_beneficiary(&mut self, beneficiary: Address) -> Result<(), RefundEscrowError> {
+        if beneficiary == Address::ZERO || self.beneficiary.get() != Address::ZERO {
+            return Err(RefundEscrowError::EscrowInvalidBeneficiary(
+                EscrowInvalidBeneficiary { beneficiary },
+            ));
+        }
+        self.beneficiary.set(beneficiary);
+        Ok(())
+    }
+
+    /// Credits `amount` wei, already received by the composing contract, to `payee`.
+    ///
+    /// Emits a {Deposited} event.
+    pub fn deposit(&mut self, payee: Address, amount: U256) -> Result<(), RefundEscrowError> {
+        self.require_state(ESCROW_ACTIVE)?;
+        self.deposits.insert(payee, self.deposits.get(payee) + amount);
+        self.total_deposits.set(self.total_deposits.get() + amount);
+
+        evm::log(Deposited {
+            payee,
+            wei_amount: amount,
+        });
+        Ok(())
+    }
+
+    /// Opens the refunds, depositors then withdraw their deposits.
+    ///
+    /// Emits a {RefundsEnabled} event.
+    pub fn enable_refunds(&mut self) -> Result<(), RefundEscrowError> {
+        self.require_state(ESCROW_ACTIVE)?;
+        self.state.set(U8::from(ESCROW_REFUNDING));
+
+        evm::log(RefundsEnabled {});
+        Ok(())
+    }
+
+    /// Closes the escrow, the beneficiary then withdraws every deposit.
+    ///
+    /// Emits a {RefundsClosed} event.
+    pub fn close(&mut self) -> Result<(), RefundEscrowError> {
+        self.require_state(ESCROW_ACTIVE)?;
+        self.state.set(U8::from(ESCROW_CLOSED));
+
+        evm::log(RefundsClosed {});
+        Ok(())
+    }
+
+    /// Sends the deposit of `payee` back to it once refunds are enabled, returns the amount sent.
+    ///
+    /// Emits a {Withdrawn} event.
+    pub fn withdraw(&mut self, payee: Address) -> Result<U256, RefundEscrowError> {
+        self.require_state(ESCROW_REFUNDING)?;
+        let amount = self.deposits.get(payee);
+        // cleared before the call, the payee runs with all the gas left
+        self.deposits.insert(payee, U256::ZERO);
+        self.total_deposits.set(self.total_deposits.get() - amount);
+        send(payee, amount)?;
+
+        evm::log(Withdrawn {
+            payee,
+            wei_amount: amount,
+        });
+        Ok(amount)
+    }
+
+    /// Sends every deposit to the beneficiary once the escrow is closed, returns the amount sent.
+    ///
+    /// Emits a {Withdrawn} event for the beneficiary.
+    pub fn beneficiary_withdraw(&mut self) -> Result<U256, RefundEscrowError> {
+        self.require_state(ESCROW_CLOSED)?;
+        let beneficiary = self.beneficiary.get();
+        let amount = self.total_deposits.get();
+        self.total_deposits.set(U256::ZERO);
+        send(beneficiary, amount)?;
+
+        evm::log(Withdrawn {
+            payee: beneficiary,
+            wei_amount: amount,
+        });
+        Ok(amount)
+    }
+
+    /// Current state, one of the `ESCROW_*` constants.
+    pub fn state_internal(&self) -> u8 {
+        self.state.get().to::<u8>()
+    }
+
+    fn require_state(&self, expected: u8) -> Result<(), RefundEscrowError> {
+        let state = self.state_internal();
+        if state != expected {
+            return Err(RefundEscrowError::EscrowUnexpectedState(
+                EscrowUnexpectedState { state, expected },
+            ));
+        }
+        Ok(())
+    }
+}
+
+fn send(to: Address, amount: U256) -> Result<(), RefundEscrowError> {
+    if amount == U256::ZERO {
+        return Ok(());
+    }
+    call::transfer_eth(to, amount).map_err(|data| {
+        if data.is_empty() {
+            RefundEscrowError::EscrowFailedCall(EscrowFailedCall {})
+        } else {
+            RefundEscrowError::Reverted(data)
+        }
+    })
+}
+
+#[external]
+impl RefundEscrow {
+    /// Returns the deposit of `payee` not withdrawn yet.
+    #[selector(name = "depositsOf")]
+    pub fn deposits_of(&self, payee: Address) -> Result<U256, RefundEscrowError> {
+        Ok(self.deposits.get(payee))
+    }
+
+    /// Returns the state: 0 active, 1 refunding, 2 closed.
+    #[selector(name = "escrowState")]
+    pub fn escrow_state(&self) -> Result<u8, RefundEscrowError> {
+        Ok(self.state_internal())
+    }
+
+    #[selector(name = "beneficiary")]
+    pub fn beneficiary(&self) -> Result<Address, RefundEscrowError> {
+        Ok(self.beneficiary.get())
+    }
+}
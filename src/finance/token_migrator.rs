@@ -0,0 +1,193 @@
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    alloy_sol_types::{sol, SolError},
+    block,
+    call::Call,
+    contract, evm,
+    prelude::*,
+};
+
+use crate::interfaces::ierc20::IERC20Burnable;
+use crate::tokens::erc20::{Erc20, Erc20Params};
+use crate::utils::math::{self, MathError, Rounding};
+use crate::utils::pausable::Pausable;
+use crate::utils::safe_erc20;
+
+sol_storage! {
+    /// Swap of an existing (e.g. Solidity) token for the composing token: holders approve the
+    /// migrator program and `migrate` pulls their old tokens with `transferFrom`, then mints
+    /// `amount * ratio_numerator / ratio_denominator` (rounded down) new tokens to them, 1:1 with
+    /// equal numerator and denominator.
+    ///
+    /// Old tokens are escrowed in the program, or burnt with `burn(uint256)` (OpenZeppelin
+    /// `ERC20Burnable`) when configured with `burn_old`. Migrations stop after `deadline` (0 for
+    /// none) and while the `Pausable` passed to `migrate` is paused. `configure` is internal and
+    /// called once by the composing token, e.g. from its `init`.
+    /// See `examples/compose_token_migrator.rs` for a complete composition.
+    pub struct TokenMigrator {
+        /// token migrated from, zero until configured
+        address old_token;
+        uint256 ratio_numerator;
+        uint256 ratio_denominator;
+        /// last timestamp of a migration, 0 for no deadline
+        uint256 deadline;
+        /// burn the old tokens instead of escrowing them
+        bool burn_old;
+        /// sum of the old tokens migrated
+        uint256 total_migrated;
+    }
+}
+
+sol! {
+    event MigratorConfigured(address indexed old_token, uint256 ratio_numerator, uint256 ratio_denominator, uint256 deadline, bool burn_old);
+    event Migrated(address indexed account, uint256 old_amount, uint256 new_amount);
+
+    /// Indicates a zero old token or ratio term, or a migrator configured twice.
+    error MigratorInvalidConfig();
+
+    /// Indicates a migration after the `deadline`.
+    error MigratorClaimPeriodEnded(uint256 deadline);
+
+    /// Indicates a migration of `amount` old tokens worth no new token.
+    error MigratorInvalidAmount(uint256 amount);
+
+    /// Indicates that the old token reverted on `burn`.
+    error MigratorFailedBurn(address old_token);
+}
+
+pub enum TokenMigratorError {
+    MigratorInvalidConfig(MigratorInvalidConfig),
+    MigratorClaimPeriodEnded(MigratorClaimPeriodEnded),
+    MigratorInvalidAmount(MigratorInvalidAmount),
+    MigratorFailedBurn(MigratorFailedBurn),
+}
+
+impl From<TokenMigratorError> for Vec<u8> {
+    fn from(e: TokenMigratorError) -> Vec<u8> {
+        match e {
+            TokenMigratorError::MigratorInvalidConfig(e) => e.encode(),
+            TokenMigratorError::MigratorClaimPeriodEnded(e) => e.encode(),
+            TokenMigratorError::MigratorInvalidAmount(e) => e.encode(),
+            TokenMigratorError::MigratorFailedBurn(e) => e.encode(),
+        }
+    }
+}
+
+crate::errors::error_helpers!(TokenMigratorError {
+    MigratorInvalidConfig,
+    MigratorClaimPeriodEnded,
+    MigratorInvalidAmount,
+    MigratorFailedBurn,
+});
+
+impl TokenMigrator {
+    /// Sets the old token, the ratio, the deadline and the fate of the old tokens, only once.
+    ///
+    /// Emits a {MigratorConfigured} event.
+    pub fn configure(
+        &mut self,
+        old_token: Address,
+        ratio_numerator: U256,
+        ratio_denominator: U256,
+        deadline: U256,
+        burn_old: bool,
+    ) -> Result<(), TokenMigratorError> {
+        if old_token == Address::ZERO
+            || ratio_numerator == U256::ZERO
+            || ratio_denominator == U256::ZERO
+            || self.old_token.get() != Address::ZERO
+        {
+            return Err(TokenMigratorError::MigratorInvalidConfig(MigratorInvalidConfig {}));
+        }
+        self.old_token.set(old_token);
+        self.ratio_numerator.set(ratio_numerator);
+        self.ratio_denominator.set(ratio_denominator);
+        self.deadline.set(deadline);
+        self.burn_old.set(burn_old);
+
+        evm::log(MigratorConfigured {
+            old_token,
+            ratio_numerator,
+            ratio_denominator,
+            deadline,
+            burn_old,
+        });
+        Ok(())
+    }
+
+    /// Pulls `amount` old tokens of `account`, which needs an allowance of `account` to this
+    /// program, escrows or burns them and mints the new tokens to `account`. Returns the amount
+    /// minted.
+    ///
+    /// Emits a {Migrated} event.
+    pub fn migrate<T: Erc20Params>(
+        &mut self,
+        pausable: &Pausable,
+        erc20: &mut Erc20<T>,
+        account: Address,
+        amount: U256,
+    ) -> Result<U256, Vec<u8>> {
+        pausable.when_not_paused()?;
+        let deadline = self.deadline.get();
+        if deadline != U256::ZERO && U256::from(block::timestamp()) > deadline {
+            return Err(TokenMigratorError::MigratorClaimPeriodEnded(MigratorClaimPeriodEnded { deadline }).into());
+        }
+        let old_token = self.old_token.get();
+        if old_token == Address::ZERO {
+            return Err(TokenMigratorError::MigratorInvalidConfig(MigratorInvalidConfig {}).into());
+        }
+        let new_amount = migrated_amount(amount, self.ratio_numerator.get(), self.ratio_denominator.get())?;
+        if new_amount == U256::ZERO {
+            return Err(TokenMigratorError::MigratorInvalidAmount(MigratorInvalidAmount { amount }).into());
+        }
+
+        safe_erc20::safe_transfer_from(old_token, account, contract::address(), amount)?;
+        if self.burn_old.get() {
+            IERC20Burnable::new(old_token)
+                .burn(Call::new(), amount)
+                .map_err(|_| TokenMigratorError::MigratorFailedBurn(MigratorFailedBurn { old_token }))?;
+        }
+        self.total_migrated.set(self.total_migrated.get() + amount);
+        erc20.mint(account, new_amount)?;
+
+        evm::log(Migrated {
+            account,
+            old_amount: amount,
+            new_amount,
+        });
+        Ok(new_amount)
+    }
+}
+
+#[external]
+impl TokenMigrator {
+    /// Returns `(old_token, ratio_numerator, ratio_denominator, deadline, burn_old)`.
+    #[selector(name = "migrationTerms")]
+    pub fn migration_terms(&self) -> Result<(Address, U256, U256, U256, bool), TokenMigratorError> {
+        Ok((
+            self.old_token.get(),
+            self.ratio_numerator.get(),
+            self.ratio_denominator.get(),
+            self.deadline.get(),
+            self.burn_old.get(),
+        ))
+    }
+
+    /// Returns the sum of the old tokens migrated.
+    #[selector(name = "totalMigrated")]
+    pub fn total_migrated(&self) -> Result<U256, TokenMigratorError> {
+        Ok(self.total_migrated.get())
+    }
+
+    /// Returns the new tokens minted for `amount` old tokens.
+    #[selector(name = "previewMigrate")]
+    pub fn preview_migrate(&self, amount: U256) -> Result<U256, Vec<u8>> {
+        Ok(migrated_amount(amount, self.ratio_numerator.get(), self.ratio_denominator.get())?)
+    }
+}
+
+/// New tokens for `amount` old tokens: `amount * ratio_numerator / ratio_denominator`, rounded down.
+pub fn migrated_amount(amount: U256, ratio_numerator: U256, ratio_denominator: U256) -> Result<U256, MathError> {
+    math::mul_div(amount, ratio_numerator, ratio_denominator, Rounding::Down)
+}
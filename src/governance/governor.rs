@@ -0,0 +1,720 @@
+use alloc::{string::String, vec, vec::Vec};
+use stylus_sdk::{
+    abi::Bytes,
+    alloy_primitives::{Address, B256, U256},
+    alloy_sol_types::{sol, SolError},
+    block,
+    call::{self, Call},
+    contract, crypto::keccak, evm, msg,
+    prelude::*,
+};
+
+use crate::governance::operations;
+use crate::utils::checkpoints::ClockMode;
+use crate::utils::eip712::{address_word, u256_word};
+use crate::utils::math::{self, Rounding};
+
+/// Denominator of `quorum_numerator`, the quorum is a percentage of the past total supply.
+pub const QUORUM_DENOMINATOR: u64 = 100;
+
+/// `support` of a vote against the proposal.
+pub const VOTE_AGAINST: u8 = 0;
+/// `support` of a vote for the proposal.
+pub const VOTE_FOR: u8 = 1;
+/// `support` of an abstention, counted for the quorum only.
+pub const VOTE_ABSTAIN: u8 = 2;
+
+/// `bytes4(keccak256("getPastVotes(address,uint256)"))`
+const GET_PAST_VOTES_SELECTOR: [u8; 4] = [0x3a, 0x46, 0xb1, 0xa8];
+/// `bytes4(keccak256("getPastTotalSupply(uint256)"))`
+const GET_PAST_TOTAL_SUPPLY_SELECTOR: [u8; 4] = [0x8e, 0x53, 0x9e, 0x8c];
+/// `bytes4(keccak256("CLOCK_MODE()"))`
+const CLOCK_MODE_SELECTOR: [u8; 4] = [0x4b, 0xf5, 0xd7, 0xe9];
+/// `bytes4(keccak256("getMinDelay()"))`
+const GET_MIN_DELAY_SELECTOR: [u8; 4] = [0xf2, 0x7a, 0x0c, 0x92];
+/// `bytes4(keccak256("scheduleBatch(address[],uint256[],bytes[],bytes32,bytes32,uint256)"))`
+const SCHEDULE_BATCH_SELECTOR: [u8; 4] = [0x8f, 0x2a, 0x0b, 0xb0];
+/// `bytes4(keccak256("executeBatch(address[],uint256[],bytes[],bytes32,bytes32)"))`
+const EXECUTE_BATCH_SELECTOR: [u8; 4] = [0xe3, 0x83, 0x35, 0xe5];
+
+/// Lifecycle of a proposal, same values as OpenZeppelin `IGovernor.ProposalState`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[repr(u8)]
+pub enum ProposalState {
+    Pending = 0,
+    Active = 1,
+    Canceled = 2,
+    Defeated = 3,
+    Succeeded = 4,
+    Queued = 5,
+    Expired = 6,
+    Executed = 7,
+}
+
+impl ProposalState {
+    /// `bytes32` bitmap with the bit of each state set, as reported by
+    /// `GovernorUnexpectedProposalState`.
+    pub fn bitmap(states: &[ProposalState]) -> B256 {
+        let bits = states
+            .iter()
+            .fold(U256::ZERO, |bits, state| bits | (U256::from(1) << (*state as u8)));
+        B256::from(bits.to_be_bytes::<32>())
+    }
+}
+
+/// Stored data of a proposal needed to derive its state.
+#[derive(Clone, Copy)]
+pub struct ProposalStatus {
+    pub vote_start: U256,
+    pub vote_end: U256,
+    pub executed: bool,
+    pub canceled: bool,
+    /// non zero once queued in the timelock
+    pub eta_seconds: U256,
+    pub quorum_reached: bool,
+    pub vote_succeeded: bool,
+}
+
+sol_storage! {
+    /// Port of OpenZeppelin `Governor` with `GovernorCountingSimple`, `GovernorVotes`,
+    /// `GovernorVotesQuorumFraction` and `GovernorTimelockControl`: proposals are voted with the
+    /// past votes of an ERC-5805 token (`Erc20Votes`, or any token exposing `getPastVotes` and
+    /// `getPastTotalSupply`) at the proposal snapshot and, once succeeded, queued in the timelock
+    /// and executed by it. Without timelock succeeded proposals are executed by the governor.
+    ///
+    /// Delay, period and snapshots follow the ERC-6372 clock of the token, read from its
+    /// `CLOCK_MODE()` by `configure` as in OpenZeppelin `GovernorVotes`: blocks, or seconds for a
+    /// timestamp token; tokens without `CLOCK_MODE()` count in blocks. Settings are fixed by
+    /// `configure` in the `init` of the composing program.
+    /// See `examples/compose_governor.rs` for a complete composition.
+    pub struct Governor {
+        /// ERC-5805 token of the voting power
+        address token;
+        /// `TimelockController` executing the proposals, zero to execute from the governor
+        address timelock;
+        /// timepoints between the proposal and the start of the vote
+        uint256 voting_delay;
+        /// timepoints the vote lasts
+        uint256 voting_period;
+        /// votes needed to propose
+        uint256 proposal_threshold;
+        /// percentage of the past total supply needed as for and abstain votes
+        uint256 quorum_numerator;
+        mapping(uint256 => ProposalCore) proposals;
+        /// clock of the token, block numbers when false
+        bool timestamp_clock;
+    }
+
+    pub struct ProposalCore {
+        address proposer;
+        /// snapshot timepoint of the votes, the vote starts after it
+        uint256 vote_start;
+        /// last timepoint of the vote
+        uint256 vote_end;
+        bool executed;
+        bool canceled;
+        /// timestamp from which the timelock executes the queued proposal
+        uint256 eta_seconds;
+        uint256 against_votes;
+        uint256 for_votes;
+        uint256 abstain_votes;
+        mapping(address => bool) has_voted;
+    }
+}
+
+sol! {
+    event ProposalCreated(uint256 proposal_id, address proposer, address[] targets, uint256[] values, string[] signatures, bytes[] calldatas, uint256 vote_start, uint256 vote_end, string description);
+    event ProposalQueued(uint256 proposal_id, uint256 eta_seconds);
+    event ProposalExecuted(uint256 proposal_id);
+    event ProposalCanceled(uint256 proposal_id);
+    event VoteCast(address indexed voter, uint256 proposal_id, uint8 support, uint256 weight, string reason);
+
+    /// Indicates empty or mismatched `targets`, `calldatas` and `values` of a proposal.
+    error GovernorInvalidProposalLength(uint256 targets, uint256 calldatas, uint256 values);
+
+    /// Indicates an operation on a proposal that was never created.
+    error GovernorNonexistentProposal(uint256 proposal_id);
+
+    /// Indicates a proposal in `current` state instead of one of the bits of `expected_states`.
+    error GovernorUnexpectedProposalState(uint256 proposal_id, uint8 current, bytes32 expected_states);
+
+    /// Indicates a proposer with fewer votes than the proposal threshold.
+    error GovernorInsufficientProposerVotes(address proposer, uint256 votes, uint256 threshold);
+
+    /// Indicates a second vote of `voter` on a proposal.
+    error GovernorAlreadyCastVote(address voter);
+
+    /// Indicates a `support` other than against, for or abstain.
+    error GovernorInvalidVoteType();
+
+    /// Indicates a cancellation by another account than the proposer.
+    error GovernorOnlyProposer(address account);
+
+    /// Indicates a `queue` on a governor without timelock.
+    error GovernorQueueNotImplemented();
+
+    /// Indicates a failed call to the votes token or to the timelock.
+    error GovernorFailedCall(address target);
+
+    /// Indicates an invalid configuration: zero token, configured twice, zero voting period,
+    /// quorum numerator above the denominator or a token clock other than ERC-6372 block numbers
+    /// and timestamps.
+    error GovernorInvalidConfiguration();
+
+    /// Indicates that the `support` votes of a proposal would exceed `uint256`.
+    error GovernorVoteOverflow(uint256 proposal_id, uint8 support);
+}
+
+pub enum GovernorError {
+    GovernorInvalidProposalLength(GovernorInvalidProposalLength),
+    GovernorNonexistentProposal(GovernorNonexistentProposal),
+    GovernorUnexpectedProposalState(GovernorUnexpectedProposalState),
+    GovernorInsufficientProposerVotes(GovernorInsufficientProposerVotes),
+    GovernorAlreadyCastVote(GovernorAlreadyCastVote),
+    GovernorInvalidVoteType(GovernorInvalidVoteType),
+    GovernorOnlyProposer(GovernorOnlyProposer),
+    GovernorQueueNotImplemented(GovernorQueueNotImplemented),
+    GovernorFailedCall(GovernorFailedCall),
+    GovernorInvalidConfiguration(GovernorInvalidConfiguration),
+    GovernorVoteOverflow(GovernorVoteOverflow),
+}
+
+impl From<GovernorError> for Vec<u8> {
+    fn from(e: GovernorError) -> Vec<u8> {
+        match e {
+            GovernorError::GovernorInvalidProposalLength(e) => e.encode(),
+            GovernorError::GovernorNonexistentProposal(e) => e.encode(),
+            GovernorError::GovernorUnexpectedProposalState(e) => e.encode(),
+            GovernorError::GovernorInsufficientProposerVotes(e) => e.encode(),
+            GovernorError::GovernorAlreadyCastVote(e) => e.encode(),
+            GovernorError::GovernorInvalidVoteType(e) => e.encode(),
+            GovernorError::GovernorOnlyProposer(e) => e.encode(),
+            GovernorError::GovernorQueueNotImplemented(e) => e.encode(),
+            GovernorError::GovernorFailedCall(e) => e.encode(),
+            GovernorError::GovernorInvalidConfiguration(e) => e.encode(),
+            GovernorError::GovernorVoteOverflow(e) => e.encode(),
+        }
+    }
+}
+
+crate::errors::error_helpers!(GovernorError {
+    GovernorInvalidProposalLength,
+    GovernorNonexistentProposal,
+    GovernorUnexpectedProposalState,
+    GovernorInsufficientProposerVotes,
+    GovernorAlreadyCastVote,
+    GovernorInvalidVoteType,
+    GovernorOnlyProposer,
+    GovernorQueueNotImplemented,
+    GovernorFailedCall,
+    GovernorInvalidConfiguration,
+    GovernorVoteOverflow,
+});
+
+/// `quorum_numerator` percent of `supply`, rounded down. Only a numerator above
+/// `QUORUM_DENOMINATOR`, rejected by `configure`, could overflow.
+pub fn quorum_of(supply: U256, quorum_numerator: U256) -> Result<U256, GovernorError> {
+    math::mul_div(supply, quorum_numerator, U256::from(QUORUM_DENOMINATOR), Rounding::Down)
+        .map_err(|_| GovernorError::GovernorInvalidConfiguration(GovernorInvalidConfiguration {}))
+}
+
+/// `votes` of a proposal once `weight` more is cast with `support`.
+pub fn add_votes(proposal_id: U256, support: u8, votes: U256, weight: U256) -> Result<U256, GovernorError> {
+    votes
+        .checked_add(weight)
+        .ok_or(GovernorError::GovernorVoteOverflow(GovernorVoteOverflow { proposal_id, support }))
+}
+
+impl Governor {
+    /// Sets the votes token, the optional timelock and the voting settings, only once. The clock
+    /// is the `CLOCK_MODE()` of the token, `voting_delay` and `voting_period` are in its units.
+    pub fn configure(
+        &mut self,
+        token: Address,
+        timelock: Address,
+        voting_delay: U256,
+        voting_period: U256,
+        proposal_threshold: U256,
+        quorum_numerator: U256,
+    ) -> Result<(), GovernorError> {
+        if token == Address::ZERO
+            || self.token.get() != Address::ZERO
+            || voting_period == U256::ZERO
+            || quorum_numerator > U256::from(QUORUM_DENOMINATOR)
+        {
+            return Err(GovernorError::GovernorInvalidConfiguration(GovernorInvalidConfiguration {}));
+        }
+        // tokens without ERC-6372 count in blocks, as `Votes` before it
+        let clock_mode = match call::static_call(Call::new(), token, &CLOCK_MODE_SELECTOR) {
+            Ok(output) => decode_clock_mode(&output)
+                .ok_or(GovernorError::GovernorInvalidConfiguration(GovernorInvalidConfiguration {}))?,
+            Err(_) => ClockMode::BlockNumber,
+        };
+        self.timestamp_clock.set(clock_mode == ClockMode::Timestamp);
+        self.token.set(token);
+        self.timelock.set(timelock);
+        self.voting_delay.set(voting_delay);
+        self.voting_period.set(voting_period);
+        self.proposal_threshold.set(proposal_threshold);
+        self.quorum_numerator.set(quorum_numerator);
+        Ok(())
+    }
+
+    fn clock_mode_of_token(&self) -> ClockMode {
+        if self.timestamp_clock.get() {
+            ClockMode::Timestamp
+        } else {
+            ClockMode::BlockNumber
+        }
+    }
+
+    /// Current timepoint of the token clock.
+    fn now(&self) -> u64 {
+        self.clock_mode_of_token().now()
+    }
+
+    fn status(&self, proposal_id: U256) -> Result<ProposalStatus, GovernorError> {
+        let proposal = self.proposals.get(proposal_id);
+        let vote_start = proposal.vote_start.get();
+        if vote_start == U256::ZERO {
+            return Err(GovernorError::GovernorNonexistentProposal(GovernorNonexistentProposal {
+                proposal_id,
+            }));
+        }
+        let (against, for_votes, abstain) = (
+            proposal.against_votes.get(),
+            proposal.for_votes.get(),
+            proposal.abstain_votes.get(),
+        );
+        // the quorum is only read once the vote is over, the snapshot is then in the past
+        let vote_end = proposal.vote_end.get();
+        let quorum_reached = vote_end < self.now() && for_votes + abstain >= self.quorum_at(vote_start)?;
+        Ok(ProposalStatus {
+            vote_start,
+            vote_end,
+            executed: proposal.executed.get(),
+            canceled: proposal.canceled.get(),
+            eta_seconds: proposal.eta_seconds.get(),
+            quorum_reached,
+            vote_succeeded: for_votes > against,
+        })
+    }
+
+    /// Reverts unless `proposal_id` is in one of `allowed`, returns its state.
+    fn validate_state(&self, proposal_id: U256, allowed: &[ProposalState]) -> Result<ProposalState, GovernorError> {
+        let current = proposal_state(&self.status(proposal_id)?, self.now());
+        if !allowed.contains(&current) {
+            return Err(GovernorError::GovernorUnexpectedProposalState(
+                GovernorUnexpectedProposalState {
+                    proposal_id,
+                    current: current as u8,
+                    expected_states: ProposalState::bitmap(allowed),
+                },
+            ));
+        }
+        Ok(current)
+    }
+
+    fn cast_vote_internal(
+        &mut self,
+        proposal_id: U256,
+        voter: Address,
+        support: u8,
+        reason: String,
+    ) -> Result<U256, GovernorError> {
+        self.validate_state(proposal_id, &[ProposalState::Active])?;
+        let snapshot = self.proposals.get(proposal_id).vote_start.get();
+        let weight = self.past_votes(voter, snapshot)?;
+
+        let mut proposal = self.proposals.setter(proposal_id);
+        if proposal.has_voted.get(voter) {
+            return Err(GovernorError::GovernorAlreadyCastVote(GovernorAlreadyCastVote { voter }));
+        }
+        proposal.has_voted.insert(voter, true);
+        match support {
+            VOTE_AGAINST => {
+                let total = add_votes(proposal_id, support, proposal.against_votes.get(), weight)?;
+                proposal.against_votes.set(total);
+            }
+            VOTE_FOR => {
+                let total = add_votes(proposal_id, support, proposal.for_votes.get(), weight)?;
+                proposal.for_votes.set(total);
+            }
+            VOTE_ABSTAIN => {
+                let total = add_votes(proposal_id, support, proposal.abstain_votes.get(), weight)?;
+                proposal.abstain_votes.set(total);
+            }
+            _ => return Err(GovernorError::GovernorInvalidVoteType(GovernorInvalidVoteType {})),
+        }
+
+        evm::log(VoteCast {
+            voter,
+            proposal_id,
+            support,
+            weight,
+            reason,
+        });
+        Ok(weight)
+    }
+
+    /// `getPastVotes(account, timepoint)` of the token.
+    fn past_votes(&self, account: Address, timepoint: U256) -> Result<U256, GovernorError> {
+        let mut data = GET_PAST_VOTES_SELECTOR.to_vec();
+        data.extend_from_slice(address_word(account).as_slice());
+        data.extend_from_slice(u256_word(timepoint).as_slice());
+        static_call_word(self.token.get(), &data)
+    }
+
+    /// Quorum at `timepoint`: `quorum_numerator` percent of the past total supply.
+    fn quorum_at(&self, timepoint: U256) -> Result<U256, GovernorError> {
+        let mut data = GET_PAST_TOTAL_SUPPLY_SELECTOR.to_vec();
+        data.extend_from_slice(u256_word(timepoint).as_slice());
+        let supply = static_call_word(self.token.get(), &data)?;
+        quorum_of(supply, self.quorum_numerator.get())
+    }
+
+    /// Salt of the timelock operation of a proposal, `bytes20(governor) ^ description_hash` as
+    /// in OpenZeppelin `GovernorTimelockControl`.
+    fn timelock_salt(description_hash: B256) -> B256 {
+        let mut salt = description_hash;
+        for (byte, governor_byte) in salt.0.iter_mut().zip(contract::address().as_slice()) {
+            *byte ^= governor_byte;
+        }
+        salt
+    }
+}
+
+#[external]
+impl Governor {
+    /// Creates a proposal, the vote starts `voting_delay` timepoints later. The proposer needs
+    /// `proposal_threshold` votes at the previous timepoint.
+    ///
+    /// Emits a {ProposalCreated} event.
+    #[selector(name = "propose")]
+    pub fn propose(
+        &mut self,
+        targets: Vec<Address>,
+        values: Vec<U256>,
+        calldatas: Vec<Bytes>,
+        description: String,
+    ) -> Result<U256, GovernorError> {
+        let proposer = msg::sender();
+        let current = U256::from(self.now());
+        let threshold = self.proposal_threshold.get();
+        if threshold > U256::ZERO {
+            let votes = self.past_votes(proposer, current - U256::from(1))?;
+            if votes < threshold {
+                return Err(GovernorError::GovernorInsufficientProposerVotes(
+                    GovernorInsufficientProposerVotes {
+                        proposer,
+                        votes,
+                        threshold,
+                    },
+                ));
+            }
+        }
+        if targets.is_empty() || targets.len() != values.len() || targets.len() != calldatas.len() {
+            return Err(GovernorError::GovernorInvalidProposalLength(GovernorInvalidProposalLength {
+                targets: U256::from(targets.len()),
+                calldatas: U256::from(calldatas.len()),
+                values: U256::from(values.len()),
+            }));
+        }
+
+        let proposal_id = hash_proposal(&targets, &values, &calldatas, keccak(description.as_bytes()));
+        if self.proposals.get(proposal_id).vote_start.get() != U256::ZERO {
+            let current_state = self.state(proposal_id)?;
+            return Err(GovernorError::GovernorUnexpectedProposalState(
+                GovernorUnexpectedProposalState {
+                    proposal_id,
+                    current: current_state,
+                    expected_states: B256::ZERO,
+                },
+            ));
+        }
+        let vote_start = current + self.voting_delay.get();
+        let vote_end = vote_start + self.voting_period.get();
+        let mut proposal = self.proposals.setter(proposal_id);
+        proposal.proposer.set(proposer);
+        proposal.vote_start.set(vote_start);
+        proposal.vote_end.set(vote_end);
+
+        evm::log(ProposalCreated {
+            proposal_id,
+            proposer,
+            signatures: vec![String::new(); targets.len()],
+            calldatas: calldatas.iter().map(|calldata| calldata.to_vec()).collect(),
+            targets,
+            values,
+            vote_start,
+            vote_end,
+            description,
+        });
+        Ok(proposal_id)
+    }
+
+    /// Casts the vote of the caller with its votes at the proposal snapshot, returns them.
+    ///
+    /// Emits a {VoteCast} event.
+    #[selector(name = "castVote")]
+    pub fn cast_vote(&mut self, proposal_id: U256, support: u8) -> Result<U256, GovernorError> {
+        self.cast_vote_internal(proposal_id, msg::sender(), support, String::new())
+    }
+
+    /// Same as `castVote` with a reason, logged in {VoteCast}.
+    #[selector(name = "castVoteWithReason")]
+    pub fn cast_vote_with_reason(&mut self, proposal_id: U256, support: u8, reason: String) -> Result<U256, GovernorError> {
+        self.cast_vote_internal(proposal_id, msg::sender(), support, reason)
+    }
+
+    /// Schedules a succeeded proposal in the timelock with its minimum delay.
+    ///
+    /// Emits a {ProposalQueued} event.
+    #[selector(name = "queue")]
+    pub fn queue(
+        &mut self,
+        targets: Vec<Address>,
+        values: Vec<U256>,
+        calldatas: Vec<Bytes>,
+        description_hash: B256,
+    ) -> Result<U256, GovernorError> {
+        let proposal_id = hash_proposal(&targets, &values, &calldatas, description_hash);
+        self.validate_state(proposal_id, &[ProposalState::Succeeded])?;
+        let timelock = self.timelock.get();
+        if timelock == Address::ZERO {
+            return Err(GovernorError::GovernorQueueNotImplemented(GovernorQueueNotImplemented {}));
+        }
+
+        let delay = static_call_word(timelock, &GET_MIN_DELAY_SELECTOR)?;
+        let calldata = operations::batch_calldata(
+            SCHEDULE_BATCH_SELECTOR,
+            &targets,
+            &values,
+            &calldatas,
+            &[B256::ZERO, Self::timelock_salt(description_hash), B256::from(delay.to_be_bytes::<32>())],
+        );
+        call::call(Call::new(), timelock, &calldata)
+            .map_err(|_| GovernorError::GovernorFailedCall(GovernorFailedCall { target: timelock }))?;
+
+        let eta_seconds = U256::from(block::timestamp()) + delay;
+        self.proposals.setter(proposal_id).eta_seconds.set(eta_seconds);
+        evm::log(ProposalQueued {
+            proposal_id,
+            eta_seconds,
+        });
+        Ok(proposal_id)
+    }
+
+    /// Executes a queued proposal through the timelock, or a succeeded one directly without
+    /// timelock. The sent value is forwarded.
+    ///
+    /// Emits a {ProposalExecuted} event.
+    #[payable]
+    #[selector(name = "execute")]
+    pub fn execute(
+        &mut self,
+        targets: Vec<Address>,
+        values: Vec<U256>,
+        calldatas: Vec<Bytes>,
+        description_hash: B256,
+    ) -> Result<U256, Vec<u8>> {
+        let proposal_id = hash_proposal(&targets, &values, &calldatas, description_hash);
+        let timelock = self.timelock.get();
+        let executable = if timelock == Address::ZERO {
+            ProposalState::Succeeded
+        } else {
+            ProposalState::Queued
+        };
+        self.validate_state(proposal_id, &[executable])?;
+        // marked before the calls, a reentrant execute sees the proposal executed
+        self.proposals.setter(proposal_id).executed.set(true);
+
+        if timelock == Address::ZERO {
+            operations::execute_batch(&targets, &values, &calldatas)?;
+        } else {
+            let calldata = operations::batch_calldata(
+                EXECUTE_BATCH_SELECTOR,
+                &targets,
+                &values,
+                &calldatas,
+                &[B256::ZERO, Self::timelock_salt(description_hash)],
+            );
+            operations::execute_call(timelock, msg::value(), &calldata)?;
+        }
+
+        evm::log(ProposalExecuted { proposal_id });
+        Ok(proposal_id)
+    }
+
+    /// Cancels a pending proposal, only by its proposer.
+    ///
+    /// Emits a {ProposalCanceled} event.
+    #[selector(name = "cancel")]
+    pub fn cancel(
+        &mut self,
+        targets: Vec<Address>,
+        values: Vec<U256>,
+        calldatas: Vec<Bytes>,
+        description_hash: B256,
+    ) -> Result<U256, GovernorError> {
+        let proposal_id = hash_proposal(&targets, &values, &calldatas, description_hash);
+        self.validate_state(proposal_id, &[ProposalState::Pending])?;
+        let account = msg::sender();
+        if account != self.proposals.get(proposal_id).proposer.get() {
+            return Err(GovernorError::GovernorOnlyProposer(GovernorOnlyProposer { account }));
+        }
+        self.proposals.setter(proposal_id).canceled.set(true);
+        evm::log(ProposalCanceled { proposal_id });
+        Ok(proposal_id)
+    }
+
+    #[selector(name = "hashProposal")]
+    pub fn hash_proposal(
+        &self,
+        targets: Vec<Address>,
+        values: Vec<U256>,
+        calldatas: Vec<Bytes>,
+        description_hash: B256,
+    ) -> Result<U256, GovernorError> {
+        Ok(hash_proposal(&targets, &values, &calldatas, description_hash))
+    }
+
+    /// Returns the `ProposalState` of `proposal_id`.
+    #[selector(name = "state")]
+    pub fn state(&self, proposal_id: U256) -> Result<u8, GovernorError> {
+        Ok(proposal_state(&self.status(proposal_id)?, self.now()) as u8)
+    }
+
+    #[selector(name = "proposalSnapshot")]
+    pub fn proposal_snapshot(&self, proposal_id: U256) -> Result<U256, GovernorError> {
+        Ok(self.proposals.get(proposal_id).vote_start.get())
+    }
+
+    #[selector(name = "proposalDeadline")]
+    pub fn proposal_deadline(&self, proposal_id: U256) -> Result<U256, GovernorError> {
+        Ok(self.proposals.get(proposal_id).vote_end.get())
+    }
+
+    #[selector(name = "proposalProposer")]
+    pub fn proposal_proposer(&self, proposal_id: U256) -> Result<Address, GovernorError> {
+        Ok(self.proposals.get(proposal_id).proposer.get())
+    }
+
+    #[selector(name = "proposalEta")]
+    pub fn proposal_eta(&self, proposal_id: U256) -> Result<U256, GovernorError> {
+        Ok(self.proposals.get(proposal_id).eta_seconds.get())
+    }
+
+    /// Returns `(against, for, abstain)` votes of `proposal_id`.
+    #[selector(name = "proposalVotes")]
+    pub fn proposal_votes(&self, proposal_id: U256) -> Result<(U256, U256, U256), GovernorError> {
+        let proposal = self.proposals.get(proposal_id);
+        Ok((
+            proposal.against_votes.get(),
+            proposal.for_votes.get(),
+            proposal.abstain_votes.get(),
+        ))
+    }
+
+    #[selector(name = "hasVoted")]
+    pub fn has_voted(&self, proposal_id: U256, account: Address) -> Result<bool, GovernorError> {
+        Ok(self.proposals.get(proposal_id).has_voted.get(account))
+    }
+
+    #[selector(name = "getVotes")]
+    pub fn get_votes(&self, account: Address, timepoint: U256) -> Result<U256, GovernorError> {
+        self.past_votes(account, timepoint)
+    }
+
+    #[selector(name = "quorum")]
+    pub fn quorum(&self, timepoint: U256) -> Result<U256, GovernorError> {
+        self.quorum_at(timepoint)
+    }
+
+    #[selector(name = "votingDelay")]
+    pub fn voting_delay(&self) -> Result<U256, GovernorError> {
+        Ok(self.voting_delay.get())
+    }
+
+    #[selector(name = "votingPeriod")]
+    pub fn voting_period(&self) -> Result<U256, GovernorError> {
+        Ok(self.voting_period.get())
+    }
+
+    #[selector(name = "proposalThreshold")]
+    pub fn proposal_threshold(&self) -> Result<U256, GovernorError> {
+        Ok(self.proposal_threshold.get())
+    }
+
+    #[selector(name = "token")]
+    pub fn token(&self) -> Result<Address, GovernorError> {
+        Ok(self.token.get())
+    }
+
+    #[selector(name = "timelock")]
+    pub fn timelock(&self) -> Result<Address, GovernorError> {
+        Ok(self.timelock.get())
+    }
+
+    /// Current timepoint of the token clock.
+    #[selector(name = "clock")]
+    pub fn clock(&self) -> Result<u64, GovernorError> {
+        Ok(self.now())
+    }
+
+    #[selector(name = "CLOCK_MODE")]
+    pub fn clock_mode(&self) -> Result<String, GovernorError> {
+        Ok(self.clock_mode_of_token().description().into())
+    }
+
+    #[selector(name = "COUNTING_MODE")]
+    pub fn counting_mode(&self) -> Result<String, GovernorError> {
+        Ok("support=bravo&quorum=for,abstain".into())
+    }
+}
+
+/// Proposal id: `uint256(keccak256(abi.encode(targets, values, calldatas, description_hash)))`.
+pub fn hash_proposal(targets: &[Address], values: &[U256], calldatas: &[Bytes], description_hash: B256) -> U256 {
+    let hash = operations::hash_batch(targets, values, calldatas, &[description_hash]);
+    U256::from_be_bytes(hash.0)
+}
+
+/// State of a proposal at timepoint `clock`. The vote is active from the timepoint after the snapshot
+/// to the deadline included, then the proposal is defeated, succeeded or queued.
+pub fn proposal_state(status: &ProposalStatus, clock: u64) -> ProposalState {
+    let clock = U256::from(clock);
+    if status.executed {
+        ProposalState::Executed
+    } else if status.canceled {
+        ProposalState::Canceled
+    } else if status.vote_start >= clock {
+        ProposalState::Pending
+    } else if status.vote_end >= clock {
+        ProposalState::Active
+    } else if !status.quorum_reached || !status.vote_succeeded {
+        ProposalState::Defeated
+    } else if status.eta_seconds == U256::ZERO {
+        ProposalState::Succeeded
+    } else {
+        ProposalState::Queued
+    }
+}
+
+/// Clock of an ABI encoded `CLOCK_MODE()` output, `None` when malformed or neither ERC-6372
+/// block numbers nor timestamps.
+pub fn decode_clock_mode(output: &[u8]) -> Option<ClockMode> {
+    let offset = usize::try_from(U256::from_be_slice(output.get(..32)?)).ok()?;
+    let len_end = offset.checked_add(32)?;
+    let len = usize::try_from(U256::from_be_slice(output.get(offset..len_end)?)).ok()?;
+    let description = output.get(len_end..len_end.checked_add(len)?)?;
+    ClockMode::from_description(core::str::from_utf8(description).ok()?)
+}
+
+fn static_call_word(target: Address, data: &[u8]) -> Result<U256, GovernorError> {
+    let output = call::static_call(Call::new(), target, data)
+        .map_err(|_| GovernorError::GovernorFailedCall(GovernorFailedCall { target }))?;
+    if output.len() < 32 {
+        return Err(GovernorError::GovernorFailedCall(GovernorFailedCall { target }));
+    }
+    Ok(U256::from_be_slice(&output[..32]))
+}
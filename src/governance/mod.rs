@@ -0,0 +1,3 @@
+pub mod governor;
+pub mod operations;
+pub mod timelock;
@@ -0,0 +1,105 @@
+//! Batches of calls shared by the `Governor` and the `TimelockController`: proposal and
+//! operation ids are hashes of `abi.encode(targets, values, payloads, ...)` and both end up
+//! calling every target with its value and payload.
+use alloc::vec::Vec;
+use stylus_sdk::{
+    abi::Bytes,
+    alloy_primitives::{Address, B256, U256},
+    call::{self, Call},
+    crypto::keccak,
+};
+
+use crate::utils::eip712::{address_word, u256_word};
+
+/// `abi.encode(targets, values, payloads, words...)` where `words` are static trailing arguments
+/// (`bytes32`, `uint256`...), already encoded.
+pub fn encode_batch(targets: &[Address], values: &[U256], payloads: &[Bytes], words: &[B256]) -> Vec<u8> {
+    let head_len = 32 * (3 + words.len());
+    let targets_len = 32 * (1 + targets.len());
+    let values_len = 32 * (1 + values.len());
+
+    let mut encoded = Vec::new();
+    encoded.extend_from_slice(u256_word(U256::from(head_len)).as_slice());
+    encoded.extend_from_slice(u256_word(U256::from(head_len + targets_len)).as_slice());
+    encoded.extend_from_slice(u256_word(U256::from(head_len + targets_len + values_len)).as_slice());
+    for word in words {
+        encoded.extend_from_slice(word.as_slice());
+    }
+
+    encoded.extend_from_slice(u256_word(U256::from(targets.len())).as_slice());
+    for target in targets {
+        encoded.extend_from_slice(address_word(*target).as_slice());
+    }
+    encoded.extend_from_slice(u256_word(U256::from(values.len())).as_slice());
+    for value in values {
+        encoded.extend_from_slice(u256_word(*value).as_slice());
+    }
+
+    // bytes[]: length, one offset per element relative to the first offset, then the elements
+    encoded.extend_from_slice(u256_word(U256::from(payloads.len())).as_slice());
+    let mut offset = 32 * payloads.len();
+    for payload in payloads {
+        encoded.extend_from_slice(u256_word(U256::from(offset)).as_slice());
+        offset += 32 + padded_len(payload.len());
+    }
+    for payload in payloads {
+        encoded.extend_from_slice(u256_word(U256::from(payload.len())).as_slice());
+        encoded.extend_from_slice(payload);
+        encoded.resize(encoded.len() + padded_len(payload.len()) - payload.len(), 0);
+    }
+    encoded
+}
+
+/// `abi.encode(target, value, payload, words...)`, the single call counterpart of `encode_batch`.
+pub fn encode_call(target: Address, value: U256, payload: &[u8], words: &[B256]) -> Vec<u8> {
+    let mut encoded = Vec::new();
+    encoded.extend_from_slice(address_word(target).as_slice());
+    encoded.extend_from_slice(u256_word(value).as_slice());
+    encoded.extend_from_slice(u256_word(U256::from(32 * (3 + words.len()))).as_slice());
+    for word in words {
+        encoded.extend_from_slice(word.as_slice());
+    }
+    encoded.extend_from_slice(u256_word(U256::from(payload.len())).as_slice());
+    encoded.extend_from_slice(payload);
+    encoded.resize(encoded.len() + padded_len(payload.len()) - payload.len(), 0);
+    encoded
+}
+
+/// `keccak256(abi.encode(targets, values, payloads, words...))`
+pub fn hash_batch(targets: &[Address], values: &[U256], payloads: &[Bytes], words: &[B256]) -> B256 {
+    keccak(encode_batch(targets, values, payloads, words))
+}
+
+/// Calldata of a function taking `(address[], uint256[], bytes[], words...)`.
+pub fn batch_calldata(
+    selector: [u8; 4],
+    targets: &[Address],
+    values: &[U256],
+    payloads: &[Bytes],
+    words: &[B256],
+) -> Vec<u8> {
+    let mut calldata = selector.to_vec();
+    calldata.extend_from_slice(&encode_batch(targets, values, payloads, words));
+    calldata
+}
+
+/// Calls every target with its value and payload in order, the first failure reverts with the
+/// revert data of the target.
+pub fn execute_batch(targets: &[Address], values: &[U256], payloads: &[Bytes]) -> Result<(), Vec<u8>> {
+    for ((target, value), payload) in targets.iter().zip(values).zip(payloads) {
+        execute_call(*target, *value, payload)?;
+    }
+    Ok(())
+}
+
+/// Calls `target` with `value` and `payload`, reverting with its revert data on failure.
+pub fn execute_call(target: Address, value: U256, payload: &[u8]) -> Result<Vec<u8>, Vec<u8>> {
+    call::call(Call::new().value(value), target, payload).map_err(|e| match e {
+        call::Error::Revert(reason) => reason,
+        _ => Vec::new(),
+    })
+}
+
+fn padded_len(len: usize) -> usize {
+    len.div_ceil(32) * 32
+}
@@ -0,0 +1,575 @@
+use alloc::vec::Vec;
+use stylus_sdk::{
+    abi::Bytes,
+    alloy_primitives::{Address, B256, U256},
+    alloy_sol_types::{sol, SolError},
+    block, contract,
+    crypto::keccak,
+    evm, msg,
+    prelude::*,
+};
+
+use crate::access::access_control::{
+    role_id, AccessControl, AccessControlBadConfirmation, AccessControlError, DEFAULT_ADMIN_ROLE,
+};
+use crate::governance::operations;
+
+/// Name of the role scheduling operations, see `role_id`.
+pub const PROPOSER_ROLE: &str = "PROPOSER_ROLE";
+/// Name of the role executing ready operations, granted to the zero address to let anyone execute.
+pub const EXECUTOR_ROLE: &str = "EXECUTOR_ROLE";
+/// Name of the role cancelling pending operations, granted to the proposers by `configure`.
+pub const CANCELLER_ROLE: &str = "CANCELLER_ROLE";
+
+/// Timestamp stored for executed operations.
+pub const DONE_TIMESTAMP: u64 = 1;
+
+/// `bytes4(keccak256("updateDelay(uint256)"))`
+pub const UPDATE_DELAY_SELECTOR: [u8; 4] = [0x64, 0xd6, 0x23, 0x53];
+/// `bytes4(keccak256("grantRole(bytes32,address)"))`
+pub const GRANT_ROLE_SELECTOR: [u8; 4] = [0x2f, 0x2f, 0xf1, 0x5d];
+/// `bytes4(keccak256("revokeRole(bytes32,address)"))`
+pub const REVOKE_ROLE_SELECTOR: [u8; 4] = [0xd5, 0x47, 0x74, 0x1f];
+/// `bytes4(keccak256("renounceRole(bytes32,address)"))`
+pub const RENOUNCE_ROLE_SELECTOR: [u8; 4] = [0x36, 0x56, 0x8a, 0xbe];
+
+/// State of an operation, same values as OpenZeppelin `TimelockController.OperationState`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[repr(u8)]
+pub enum OperationState {
+    Unset = 0,
+    Waiting = 1,
+    Ready = 2,
+    Done = 3,
+}
+
+impl OperationState {
+    /// `bytes32` bitmap with the bit of each state set, as reported by
+    /// `TimelockUnexpectedOperationState`.
+    pub fn bitmap(states: &[OperationState]) -> B256 {
+        let bits = states
+            .iter()
+            .fold(U256::ZERO, |bits, state| bits | (U256::from(1) << (*state as u8)));
+        B256::from(bits.to_be_bytes::<32>())
+    }
+}
+
+/// Call of an operation targeting the timelock itself, see {decode_self_call}.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SelfCall {
+    UpdateDelay(U256),
+    GrantRole(B256, Address),
+    RevokeRole(B256, Address),
+    RenounceRole(B256, Address),
+}
+
+sol_storage! {
+    /// Port of OpenZeppelin `TimelockController`: proposers schedule calls (or batches of calls)
+    /// executable once `min_delay` seconds passed, executors run them, cancellers drop pending
+    /// ones. Operations are identified by `hashOperation` / `hashOperationBatch`, the same ids as
+    /// in Solidity, and can wait for a predecessor operation to be done.
+    ///
+    /// Roles are managed by the embedded `AccessControl`, the timelock is its own admin so role
+    /// and delay changes go through scheduled operations. Stylus programs cannot call themselves
+    /// without the SDK `reentrant` feature, so operations targeting the timelock are applied in
+    /// place by `execute` / `executeBatch` (see {decode_self_call}) instead of being called, with
+    /// the timelock as the authorized caller. Stylus programs have no constructor,
+    /// the composing program calls `configure` in its `init`.
+    /// See `examples/compose_timelock.rs` for a complete composition.
+    pub struct TimelockController {
+        #[borrow]
+        AccessControl access;
+        /// seconds between scheduling and execution
+        uint256 min_delay;
+        /// operation id => timestamp from which it is executable, `DONE_TIMESTAMP` once executed
+        mapping(bytes32 => uint256) timestamps;
+    }
+}
+
+sol! {
+    event CallScheduled(bytes32 indexed id, uint256 indexed index, address target, uint256 value, bytes data, bytes32 predecessor, uint256 delay);
+    event CallExecuted(bytes32 indexed id, uint256 indexed index, address target, uint256 value, bytes data);
+    event CallSalt(bytes32 indexed id, bytes32 salt);
+    event Cancelled(bytes32 indexed id);
+    event MinDelayChange(uint256 old_duration, uint256 new_duration);
+
+    /// Indicates mismatched `targets`, `payloads` and `values` of a batch.
+    error TimelockInvalidOperationLength(uint256 targets, uint256 payloads, uint256 values);
+
+    /// Indicates a schedule with a delay below the minimum delay.
+    error TimelockInsufficientDelay(uint256 delay, uint256 min_delay);
+
+    /// Indicates an operation in none of the states set in the `expected_states` bitmap.
+    error TimelockUnexpectedOperationState(bytes32 operation_id, bytes32 expected_states);
+
+    /// Indicates an execution before the predecessor operation is done.
+    error TimelockUnexecutedPredecessor(bytes32 predecessor_id);
+
+    /// Indicates a call reserved to the timelock itself.
+    error TimelockUnauthorizedCaller(address caller);
+
+    /// Indicates a second `configure`.
+    error TimelockAlreadyConfigured();
+
+    /// Indicates an operation calling the timelock with a method it cannot apply in place.
+    error TimelockUnsupportedSelfCall(bytes4 selector);
+}
+
+pub enum TimelockError {
+    TimelockInvalidOperationLength(TimelockInvalidOperationLength),
+    TimelockInsufficientDelay(TimelockInsufficientDelay),
+    TimelockUnexpectedOperationState(TimelockUnexpectedOperationState),
+    TimelockUnexecutedPredecessor(TimelockUnexecutedPredecessor),
+    TimelockUnauthorizedCaller(TimelockUnauthorizedCaller),
+    TimelockAlreadyConfigured(TimelockAlreadyConfigured),
+    TimelockUnsupportedSelfCall(TimelockUnsupportedSelfCall),
+}
+
+impl From<TimelockError> for Vec<u8> {
+    fn from(e: TimelockError) -> Vec<u8> {
+        match e {
+            TimelockError::TimelockInvalidOperationLength(e) => e.encode(),
+            TimelockError::TimelockInsufficientDelay(e) => e.encode(),
+            TimelockError::TimelockUnexpectedOperationState(e) => e.encode(),
+            TimelockError::TimelockUnexecutedPredecessor(e) => e.encode(),
+            TimelockError::TimelockUnauthorizedCaller(e) => e.encode(),
+            TimelockError::TimelockAlreadyConfigured(e) => e.encode(),
+            TimelockError::TimelockUnsupportedSelfCall(e) => e.encode(),
+        }
+    }
+}
+
+crate::errors::error_helpers!(TimelockError {
+    TimelockInvalidOperationLength,
+    TimelockInsufficientDelay,
+    TimelockUnexpectedOperationState,
+    TimelockUnexecutedPredecessor,
+    TimelockUnauthorizedCaller,
+    TimelockAlreadyConfigured,
+    TimelockUnsupportedSelfCall,
+});
+
+impl TimelockController {
+    /// Sets the minimum delay and the roles, only once: the timelock and the optional `admin`
+    /// get `DEFAULT_ADMIN_ROLE`, proposers get `PROPOSER_ROLE` and `CANCELLER_ROLE`, executors
+    /// `EXECUTOR_ROLE`. `admin` should renounce once the roles are set up.
+    ///
+    /// Emits a {MinDelayChange} event.
+    pub fn configure(
+        &mut self,
+        min_delay: U256,
+        proposers: &[Address],
+        executors: &[Address],
+        admin: Address,
+    ) -> Result<(), TimelockError> {
+        let timelock = contract::address();
+        if !self.access.grant_role_internal(DEFAULT_ADMIN_ROLE, timelock) {
+            return Err(TimelockError::TimelockAlreadyConfigured(TimelockAlreadyConfigured {}));
+        }
+        if admin != Address::ZERO {
+            self.access.grant_role_internal(DEFAULT_ADMIN_ROLE, admin);
+        }
+        for proposer in proposers {
+            self.access.grant_role_internal(role_id(PROPOSER_ROLE), *proposer);
+            self.access.grant_role_internal(role_id(CANCELLER_ROLE), *proposer);
+        }
+        for executor in executors {
+            self.access.grant_role_internal(role_id(EXECUTOR_ROLE), *executor);
+        }
+        self.min_delay.set(min_delay);
+        evm::log(MinDelayChange {
+            old_duration: U256::ZERO,
+            new_duration: min_delay,
+        });
+        Ok(())
+    }
+
+    /// Reverts unless the caller has `role`, or the zero address has it (open role).
+    fn only_role_or_open_role(&self, role: &str) -> Result<(), Vec<u8>> {
+        let role = role_id(role);
+        if !self.access.has_role(role, Address::ZERO)? {
+            self.access.only_role(role)?;
+        }
+        Ok(())
+    }
+
+    fn operation_state(&self, id: B256) -> OperationState {
+        operation_state(self.timestamps.get(id), block::timestamp())
+    }
+
+    fn schedule_internal(&mut self, id: B256, delay: U256) -> Result<(), TimelockError> {
+        if self.operation_state(id) != OperationState::Unset {
+            return Err(unexpected_state(id, &[OperationState::Unset]));
+        }
+        let min_delay = self.min_delay.get();
+        if delay < min_delay {
+            return Err(TimelockError::TimelockInsufficientDelay(TimelockInsufficientDelay {
+                delay,
+                min_delay,
+            }));
+        }
+        self.timestamps.insert(id, U256::from(block::timestamp()) + delay);
+        Ok(())
+    }
+
+    /// Checks the operation is ready and its predecessor done.
+    fn before_call(&self, id: B256, predecessor: B256) -> Result<(), TimelockError> {
+        if self.operation_state(id) != OperationState::Ready {
+            return Err(unexpected_state(id, &[OperationState::Ready]));
+        }
+        if predecessor != B256::ZERO && self.operation_state(predecessor) != OperationState::Done {
+            return Err(TimelockError::TimelockUnexecutedPredecessor(TimelockUnexecutedPredecessor {
+                predecessor_id: predecessor,
+            }));
+        }
+        Ok(())
+    }
+
+    /// Calls `target`, or applies the call in place when `target` is the timelock itself.
+    fn call(&mut self, target: Address, value: U256, payload: &[u8]) -> Result<(), Vec<u8>> {
+        if target != contract::address() {
+            operations::execute_call(target, value, payload)?;
+            return Ok(());
+        }
+        match decode_self_call(payload)? {
+            SelfCall::UpdateDelay(new_delay) => self.set_min_delay(new_delay),
+            SelfCall::GrantRole(role, account) => {
+                self.access.grant_role_internal(role, account);
+            }
+            SelfCall::RevokeRole(role, account) => {
+                self.access.revoke_role_internal(role, account);
+            }
+            // `renounceRole` only lets the caller, the timelock here, renounce
+            SelfCall::RenounceRole(role, account) => {
+                if account != target {
+                    return Err(AccessControlError::AccessControlBadConfirmation(
+                        AccessControlBadConfirmation {},
+                    )
+                    .into());
+                }
+                self.access.revoke_role_internal(role, account);
+            }
+        }
+        Ok(())
+    }
+
+    fn set_min_delay(&mut self, new_delay: U256) {
+        let old_duration = self.min_delay.get();
+        self.min_delay.set(new_delay);
+        evm::log(MinDelayChange {
+            old_duration,
+            new_duration: new_delay,
+        });
+    }
+
+    /// Marks the operation done, it must still be ready after the calls: a reentrant execution
+    /// of the same operation fails here.
+    fn after_call(&mut self, id: B256) -> Result<(), TimelockError> {
+        if self.operation_state(id) != OperationState::Ready {
+            return Err(unexpected_state(id, &[OperationState::Ready]));
+        }
+        self.timestamps.insert(id, U256::from(DONE_TIMESTAMP));
+        Ok(())
+    }
+}
+
+/// `TimelockController` names are pinned, as for the token externals.
+#[external]
+#[inherit(AccessControl)]
+impl TimelockController {
+    /// Schedules a call executable after `delay` seconds, only by proposers.
+    ///
+    /// Emits a {CallScheduled} event and a {CallSalt} event for a non zero salt.
+    #[selector(name = "schedule")]
+    pub fn schedule(
+        &mut self,
+        target: Address,
+        value: U256,
+        data: Bytes,
+        predecessor: B256,
+        salt: B256,
+        delay: U256,
+    ) -> Result<(), Vec<u8>> {
+        self.access.only_role(role_id(PROPOSER_ROLE))?;
+        let id = hash_operation(target, value, &data, predecessor, salt);
+        self.schedule_internal(id, delay)?;
+        evm::log(CallScheduled {
+            id,
+            index: U256::ZERO,
+            target,
+            value,
+            data: data.to_vec(),
+            predecessor,
+            delay,
+        });
+        if salt != B256::ZERO {
+            evm::log(CallSalt { id, salt });
+        }
+        Ok(())
+    }
+
+    /// Schedules a batch of calls executable after `delay` seconds, only by proposers.
+    ///
+    /// Emits one {CallScheduled} event per call and a {CallSalt} event for a non zero salt.
+    #[selector(name = "scheduleBatch")]
+    pub fn schedule_batch(
+        &mut self,
+        targets: Vec<Address>,
+        values: Vec<U256>,
+        payloads: Vec<Bytes>,
+        predecessor: B256,
+        salt: B256,
+        delay: U256,
+    ) -> Result<(), Vec<u8>> {
+        self.access.only_role(role_id(PROPOSER_ROLE))?;
+        check_lengths(&targets, &values, &payloads)?;
+        let id = hash_operation_batch(&targets, &values, &payloads, predecessor, salt);
+        self.schedule_internal(id, delay)?;
+        for (index, ((target, value), payload)) in targets.iter().zip(&values).zip(&payloads).enumerate() {
+            evm::log(CallScheduled {
+                id,
+                index: U256::from(index),
+                target: *target,
+                value: *value,
+                data: payload.to_vec(),
+                predecessor,
+                delay,
+            });
+        }
+        if salt != B256::ZERO {
+            evm::log(CallSalt { id, salt });
+        }
+        Ok(())
+    }
+
+    /// Cancels a pending (waiting or ready) operation, only by cancellers.
+    ///
+    /// Emits a {Cancelled} event.
+    #[selector(name = "cancel")]
+    pub fn cancel(&mut self, id: B256) -> Result<(), Vec<u8>> {
+        self.access.only_role(role_id(CANCELLER_ROLE))?;
+        let pending = [OperationState::Waiting, OperationState::Ready];
+        if !pending.contains(&self.operation_state(id)) {
+            return Err(unexpected_state(id, &pending).into());
+        }
+        self.timestamps.insert(id, U256::ZERO);
+        evm::log(Cancelled { id });
+        Ok(())
+    }
+
+    /// Executes a ready call, only by executors unless the role is open. The target reverting
+    /// reverts with its revert data.
+    ///
+    /// Emits a {CallExecuted} event.
+    #[payable]
+    #[selector(name = "execute")]
+    pub fn execute(
+        &mut self,
+        target: Address,
+        value: U256,
+        payload: Bytes,
+        predecessor: B256,
+        salt: B256,
+    ) -> Result<(), Vec<u8>> {
+        self.only_role_or_open_role(EXECUTOR_ROLE)?;
+        let id = hash_operation(target, value, &payload, predecessor, salt);
+        self.before_call(id, predecessor)?;
+        self.call(target, value, &payload)?;
+        evm::log(CallExecuted {
+            id,
+            index: U256::ZERO,
+            target,
+            value,
+            data: payload.to_vec(),
+        });
+        self.after_call(id)?;
+        Ok(())
+    }
+
+    /// Executes a ready batch in order, only by executors unless the role is open.
+    ///
+    /// Emits one {CallExecuted} event per call.
+    #[payable]
+    #[selector(name = "executeBatch")]
+    pub fn execute_batch(
+        &mut self,
+        targets: Vec<Address>,
+        values: Vec<U256>,
+        payloads: Vec<Bytes>,
+        predecessor: B256,
+        salt: B256,
+    ) -> Result<(), Vec<u8>> {
+        self.only_role_or_open_role(EXECUTOR_ROLE)?;
+        check_lengths(&targets, &values, &payloads)?;
+        let id = hash_operation_batch(&targets, &values, &payloads, predecessor, salt);
+        self.before_call(id, predecessor)?;
+        for (index, ((target, value), payload)) in targets.iter().zip(&values).zip(&payloads).enumerate() {
+            self.call(*target, *value, payload)?;
+            evm::log(CallExecuted {
+                id,
+                index: U256::from(index),
+                target: *target,
+                value: *value,
+                data: payload.to_vec(),
+            });
+        }
+        self.after_call(id)?;
+        Ok(())
+    }
+
+    /// Changes the minimum delay of future operations, only by the timelock itself, i.e.
+    /// through a scheduled operation (applied in place by `execute`).
+    ///
+    /// Emits a {MinDelayChange} event.
+    #[selector(name = "updateDelay")]
+    pub fn update_delay(&mut self, new_delay: U256) -> Result<(), TimelockError> {
+        let caller = msg::sender();
+        if caller != contract::address() {
+            return Err(TimelockError::TimelockUnauthorizedCaller(TimelockUnauthorizedCaller { caller }));
+        }
+        self.set_min_delay(new_delay);
+        Ok(())
+    }
+
+    #[selector(name = "getMinDelay")]
+    pub fn get_min_delay(&self) -> Result<U256, TimelockError> {
+        Ok(self.min_delay.get())
+    }
+
+    /// Returns the timestamp from which `id` is executable, 0 when unset and `DONE_TIMESTAMP`
+    /// once executed.
+    #[selector(name = "getTimestamp")]
+    pub fn get_timestamp(&self, id: B256) -> Result<U256, TimelockError> {
+        Ok(self.timestamps.get(id))
+    }
+
+    /// Returns the `OperationState` of `id`.
+    #[selector(name = "getOperationState")]
+    pub fn get_operation_state(&self, id: B256) -> Result<u8, TimelockError> {
+        Ok(self.operation_state(id) as u8)
+    }
+
+    #[selector(name = "isOperation")]
+    pub fn is_operation(&self, id: B256) -> Result<bool, TimelockError> {
+        Ok(self.operation_state(id) != OperationState::Unset)
+    }
+
+    /// Whether `id` is waiting or ready.
+    #[selector(name = "isOperationPending")]
+    pub fn is_operation_pending(&self, id: B256) -> Result<bool, TimelockError> {
+        let state = self.operation_state(id);
+        Ok(state == OperationState::Waiting || state == OperationState::Ready)
+    }
+
+    #[selector(name = "isOperationReady")]
+    pub fn is_operation_ready(&self, id: B256) -> Result<bool, TimelockError> {
+        Ok(self.operation_state(id) == OperationState::Ready)
+    }
+
+    #[selector(name = "isOperationDone")]
+    pub fn is_operation_done(&self, id: B256) -> Result<bool, TimelockError> {
+        Ok(self.operation_state(id) == OperationState::Done)
+    }
+
+    #[selector(name = "hashOperation")]
+    pub fn hash_operation(
+        &self,
+        target: Address,
+        value: U256,
+        data: Bytes,
+        predecessor: B256,
+        salt: B256,
+    ) -> Result<B256, TimelockError> {
+        Ok(hash_operation(target, value, &data, predecessor, salt))
+    }
+
+    #[selector(name = "hashOperationBatch")]
+    pub fn hash_operation_batch(
+        &self,
+        targets: Vec<Address>,
+        values: Vec<U256>,
+        payloads: Vec<Bytes>,
+        predecessor: B256,
+        salt: B256,
+    ) -> Result<B256, TimelockError> {
+        Ok(hash_operation_batch(&targets, &values, &payloads, predecessor, salt))
+    }
+}
+
+/// `keccak256(abi.encode(target, value, data, predecessor, salt))`
+pub fn hash_operation(target: Address, value: U256, data: &[u8], predecessor: B256, salt: B256) -> B256 {
+    keccak(operations::encode_call(target, value, data, &[predecessor, salt]))
+}
+
+/// `keccak256(abi.encode(targets, values, payloads, predecessor, salt))`
+pub fn hash_operation_batch(
+    targets: &[Address],
+    values: &[U256],
+    payloads: &[Bytes],
+    predecessor: B256,
+    salt: B256,
+) -> B256 {
+    operations::hash_batch(targets, values, payloads, &[predecessor, salt])
+}
+
+/// State of an operation stored with `timestamp`, at time `now`.
+pub fn operation_state(timestamp: U256, now: u64) -> OperationState {
+    if timestamp == U256::ZERO {
+        OperationState::Unset
+    } else if timestamp == U256::from(DONE_TIMESTAMP) {
+        OperationState::Done
+    } else if timestamp > U256::from(now) {
+        OperationState::Waiting
+    } else {
+        OperationState::Ready
+    }
+}
+
+/// Decodes the payload of an operation targeting the timelock: `updateDelay`, `grantRole`,
+/// `revokeRole` and `renounceRole` are supported, other methods and malformed arguments fail
+/// with `TimelockUnsupportedSelfCall`.
+pub fn decode_self_call(payload: &[u8]) -> Result<SelfCall, TimelockError> {
+    let mut selector = [0u8; 4];
+    if payload.len() >= 4 {
+        selector.copy_from_slice(&payload[..4]);
+    }
+    let unsupported = || {
+        TimelockError::TimelockUnsupportedSelfCall(TimelockUnsupportedSelfCall {
+            selector: selector.into(),
+        })
+    };
+    let args = payload.get(4..).ok_or_else(unsupported)?;
+    let word = |index: usize| B256::from_slice(&args[32 * index..32 * (index + 1)]);
+    let address = |index: usize| {
+        let word = word(index);
+        if word[..12].iter().any(|byte| *byte != 0) {
+            return Err(unsupported());
+        }
+        Ok(Address::from_slice(&word[12..]))
+    };
+    match selector {
+        UPDATE_DELAY_SELECTOR if args.len() == 32 => Ok(SelfCall::UpdateDelay(U256::from_be_bytes(word(0).0))),
+        GRANT_ROLE_SELECTOR if args.len() == 64 => Ok(SelfCall::GrantRole(word(0), address(1)?)),
+        REVOKE_ROLE_SELECTOR if args.len() == 64 => Ok(SelfCall::RevokeRole(word(0), address(1)?)),
+        RENOUNCE_ROLE_SELECTOR if args.len() == 64 => Ok(SelfCall::RenounceRole(word(0), address(1)?)),
+        _ => Err(unsupported()),
+    }
+}
+
+fn check_lengths(targets: &[Address], values: &[U256], payloads: &[Bytes]) -> Result<(), TimelockError> {
+    if targets.len() != values.len() || targets.len() != payloads.len() {
+        return Err(TimelockError::TimelockInvalidOperationLength(TimelockInvalidOperationLength {
+            targets: U256::from(targets.len()),
+            payloads: U256::from(payloads.len()),
+            values: U256::from(values.len()),
+        }));
+    }
+    Ok(())
+}
+
+fn unexpected_state(operation_id: B256, expected: &[OperationState]) -> TimelockError {
+    TimelockError::TimelockUnexpectedOperationState(TimelockUnexpectedOperationState {
+        operation_id,
+        expected_states: OperationState::bitmap(expected),
+    })
+}
@@ -0,0 +1,17 @@
+//! Typed calls to other ERC1155 tokens.
+use stylus_sdk::prelude::*;
+
+sol_interface! {
+    interface IERC1155 {
+        function balanceOf(address account, uint256 id) external view returns (uint256);
+        function balanceOfBatch(address[] accounts, uint256[] ids) external view returns (uint256[]);
+        function setApprovalForAll(address operator, bool approved) external;
+        function isApprovedForAll(address account, address operator) external view returns (bool);
+        function safeTransferFrom(address from, address to, uint256 id, uint256 value, bytes data) external;
+        function safeBatchTransferFrom(address from, address to, uint256[] ids, uint256[] values, bytes data) external;
+    }
+
+    interface IERC1155MetadataURI {
+        function uri(uint256 id) external view returns (string);
+    }
+}
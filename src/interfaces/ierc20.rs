@@ -0,0 +1,36 @@
+//! Typed calls to other ERC20 tokens, e.g.
+//!
+//! ```ignore
+//! let balance = IERC20::new(token).balance_of(Call::new(), account)?;
+//! ```
+//!
+//! Tokens returning nothing from `transfer`, `transferFrom` or `approve` (e.g. USDT) fail to
+//! decode here, move tokens of unknown contracts with `utils::safe_erc20` instead.
+use stylus_sdk::prelude::*;
+
+sol_interface! {
+    interface IERC20 {
+        function totalSupply() external view returns (uint256);
+        function balanceOf(address account) external view returns (uint256);
+        function allowance(address owner, address spender) external view returns (uint256);
+        function transfer(address to, uint256 value) external returns (bool);
+        function transferFrom(address from, address to, uint256 value) external returns (bool);
+        function approve(address spender, uint256 value) external returns (bool);
+    }
+
+    interface IERC20Metadata {
+        function name() external view returns (string);
+        function symbol() external view returns (string);
+        function decimals() external view returns (uint8);
+    }
+
+    interface IERC20Burnable {
+        function burn(uint256 value) external;
+    }
+
+    interface IERC20Permit {
+        function permit(address owner, address spender, uint256 value, uint256 deadline, uint8 v, bytes32 r, bytes32 s) external;
+        function nonces(address owner) external view returns (uint256);
+        function DOMAIN_SEPARATOR() external view returns (bytes32);
+    }
+}
@@ -0,0 +1,22 @@
+//! Typed calls to other ERC721 tokens. `sol_interface!` binds one function per name, only the
+//! `safeTransferFrom` overload taking `data` is declared (pass empty `data` for the other one).
+use stylus_sdk::prelude::*;
+
+sol_interface! {
+    interface IERC721 {
+        function balanceOf(address owner) external view returns (uint256);
+        function ownerOf(uint256 token_id) external view returns (address);
+        function safeTransferFrom(address from, address to, uint256 token_id, bytes data) external;
+        function transferFrom(address from, address to, uint256 token_id) external;
+        function approve(address to, uint256 token_id) external;
+        function setApprovalForAll(address operator, bool approved) external;
+        function getApproved(uint256 token_id) external view returns (address);
+        function isApprovedForAll(address owner, address operator) external view returns (bool);
+    }
+
+    interface IERC721Metadata {
+        function name() external view returns (string);
+        function symbol() external view returns (string);
+        function tokenURI(uint256 token_id) external view returns (string);
+    }
+}
@@ -0,0 +1,3 @@
+pub mod ierc1155;
+pub mod ierc20;
+pub mod ierc721;
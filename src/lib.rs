@@ -6,4 +6,24 @@ extern crate alloc;
 #[global_allocator]
 static ALLOC: mini_alloc::MiniAlloc = mini_alloc::MiniAlloc::INIT;
 
-pub mod tokens;
\ No newline at end of file
+/// Token base implementations and example tokens composed from them.
+pub mod tokens;
+/// Optional building blocks composed next to a token base (e.g. `#[borrow] Erc20Pausable pausable`).
+pub mod extensions;
+/// Authorization components (ownership, roles) gating admin methods of composed tokens.
+pub mod access;
+/// Sale and distribution contracts built on top of the tokens (e.g. `CommitRevealSale`).
+pub mod finance;
+/// On-chain governance voting with the tokens (`Governor`) and delaying its execution.
+pub mod governance;
+/// UUPS upgrades of programs deployed behind EIP-1967 proxies.
+pub mod proxy;
+/// `sol_interface!` bindings for typed calls to other ERC20, ERC721 and ERC1155 tokens.
+pub mod interfaces;
+/// Token agnostic helpers shared by tokens and extensions.
+pub mod utils;
+/// Selectors and formatting of the error enums, and the selectors of the token errors for tests.
+pub mod errors;
+
+/// Canonical paths of the core ERC20 items, `oz_stylus_erc::Erc20` is the same type as `oz_stylus_erc::tokens::erc20::Erc20`.
+pub use tokens::erc20::{Erc20, Erc20Error, Erc20Params, NoTransferHook, TransferHook};
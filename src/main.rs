@@ -1,4 +1,7 @@
+#![cfg_attr(not(feature = "export-abi"), no_main)]
 
-pub fn main() {
-    
-}
\ No newline at end of file
+/// Prints the Solidity interface of the entrypoint token, `cargo run --features export-abi --bin erc20`.
+#[cfg(feature = "export-abi")]
+fn main() {
+    oz_stylus_erc::tokens::my_token::print_abi("MIT OR Apache-2.0", "pragma solidity ^0.8.23;");
+}
@@ -0,0 +1,105 @@
+//! EIP-1967 implementation slot of a proxy, read and written from the implementation running in
+//! the proxy's storage (through `delegatecall`). The slot is `keccak256(label) - 1`, out of
+//! reach of `sol_storage!` layouts, which start at slot 0, and of mappings, whose slots are
+//! hashes of known preimages.
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{Address, B256, U256},
+    alloy_sol_types::{sol, SolError},
+    call::{self, Call},
+    crypto::keccak,
+    evm,
+    storage::{StorageAddress, StorageCache, StorageType},
+    types::AddressVM,
+};
+
+/// `bytes32(uint256(keccak256("eip1967.proxy.implementation")) - 1)`
+pub const IMPLEMENTATION_SLOT: B256 = B256::new([
+    0x36, 0x08, 0x94, 0xa1, 0x3b, 0xa1, 0xa3, 0x21, 0x06, 0x67, 0xc8, 0x28, 0x49, 0x2d, 0xb9, 0x8d, 0xca, 0x3e, 0x20,
+    0x76, 0xcc, 0x37, 0x35, 0xa9, 0x20, 0xa3, 0xca, 0x50, 0x5d, 0x38, 0x2b, 0xbc,
+]);
+
+sol! {
+    /// Emitted when the implementation is upgraded.
+    event Upgraded(address indexed implementation);
+
+    /// Indicates that `implementation` has no code.
+    error Erc1967InvalidImplementation(address implementation);
+
+    /// Indicates that the initialization call to the new implementation reverted without a reason.
+    error Erc1967FailedCall();
+}
+
+pub enum Erc1967Error {
+    Erc1967InvalidImplementation(Erc1967InvalidImplementation),
+    Erc1967FailedCall(Erc1967FailedCall),
+    /// revert data of the initialization call, passed through
+    Reverted(Vec<u8>),
+}
+
+impl From<Erc1967Error> for Vec<u8> {
+    fn from(e: Erc1967Error) -> Vec<u8> {
+        match e {
+            Erc1967Error::Erc1967InvalidImplementation(e) => e.encode(),
+            Erc1967Error::Erc1967FailedCall(e) => e.encode(),
+            Erc1967Error::Reverted(data) => data,
+        }
+    }
+}
+
+crate::errors::error_helpers!(Erc1967Error {
+    Erc1967InvalidImplementation,
+    Erc1967FailedCall,
+} passthrough { Reverted });
+
+/// EIP-1967 slot of `label`: `keccak256(label) - 1`.
+pub fn erc1967_slot(label: &str) -> B256 {
+    let hash = U256::from_be_bytes(keccak(label.as_bytes()).0);
+    B256::from((hash - U256::from(1)).to_be_bytes::<32>())
+}
+
+/// Implementation stored in the current storage, the zero address outside of a proxy.
+pub fn implementation() -> Address {
+    implementation_storage().get()
+}
+
+/// Stores `new_implementation`, which must have code.
+///
+/// Emits an {Upgraded} event.
+pub fn set_implementation(new_implementation: Address) -> Result<(), Erc1967Error> {
+    if !new_implementation.has_code() {
+        return Err(Erc1967Error::Erc1967InvalidImplementation(Erc1967InvalidImplementation {
+            implementation: new_implementation,
+        }));
+    }
+    implementation_storage().set(new_implementation);
+    evm::log(Upgraded {
+        implementation: new_implementation,
+    });
+    Ok(())
+}
+
+/// Stores `new_implementation` and, unless `data` is empty, delegatecalls it with `data` (e.g.
+/// its `reinitializer` migration), returning the call output.
+///
+/// Emits an {Upgraded} event.
+pub fn upgrade_to_and_call(new_implementation: Address, data: &[u8]) -> Result<Vec<u8>, Erc1967Error> {
+    set_implementation(new_implementation)?;
+    if data.is_empty() {
+        return Ok(Vec::new());
+    }
+    // SAFETY: the new implementation runs on this storage: cached writes are flushed before the
+    // call and the cache is dropped after, so neither side reads stale slots.
+    StorageCache::flush();
+    let output = unsafe { call::delegate_call(Call::new(), new_implementation, data) };
+    StorageCache::clear();
+    output.map_err(|e| match e {
+        call::Error::Revert(data) if !data.is_empty() => Erc1967Error::Reverted(data),
+        _ => Erc1967Error::Erc1967FailedCall(Erc1967FailedCall {}),
+    })
+}
+
+fn implementation_storage() -> StorageAddress {
+    // SAFETY: the EIP-1967 slot is not used by any `sol_storage!` field, see the module doc.
+    unsafe { StorageAddress::new(U256::from_be_bytes(IMPLEMENTATION_SLOT.0), 0) }
+}
@@ -0,0 +1,2 @@
+pub mod erc1967;
+pub mod uups;
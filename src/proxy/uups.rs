@@ -0,0 +1,112 @@
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{Address, B256},
+    alloy_sol_types::{sol, SolError},
+    call::{self, Call},
+    prelude::*,
+};
+
+use super::erc1967::{self, Erc1967Error, Erc1967InvalidImplementation, IMPLEMENTATION_SLOT};
+
+/// `proxiableUUID()` selector.
+pub const PROXIABLE_UUID_SELECTOR: [u8; 4] = [0x52, 0xd1, 0x90, 0x2d];
+
+sol_storage! {
+    /// UUPS (ERC-1822) upgrades of a program deployed behind an EIP-1967 proxy, e.g. OpenZeppelin
+    /// `ERC1967Proxy` constructed with the program address and its `initialize` calldata: the
+    /// upgrade logic lives in the implementation, which stores the next implementation in the
+    /// proxy's EIP-1967 slot.
+    ///
+    /// Stylus programs have no immutables, so a call is told to come through a proxy by the
+    /// EIP-1967 slot of the current storage: set in the proxy, empty in the implementation's own
+    /// storage. The composing program exposes `upgradeToAndCall` behind its own authorization
+    /// (the `_authorizeUpgrade` of OpenZeppelin, e.g. `Ownable::only_owner`) calling
+    /// `upgrade_to_and_call`. Every version keeps the storage layout of the previous one and
+    /// only appends fields, as Solidity upgradeable contracts do.
+    /// See `examples/upgradeable_token.rs` and `examples/upgradeable_token_v2.rs`.
+    pub struct UupsUpgradeable {}
+}
+
+sol! {
+    /// Indicates a call from the wrong context: an upgrade outside of a proxy, or
+    /// `proxiableUUID` through one.
+    error UupsUnauthorizedCallContext();
+
+    /// Indicates a new implementation answering another `proxiableUUID` than the EIP-1967 slot.
+    error UupsUnsupportedProxiableUuid(bytes32 slot);
+}
+
+pub enum UupsUpgradeableError {
+    UupsUnauthorizedCallContext(UupsUnauthorizedCallContext),
+    UupsUnsupportedProxiableUuid(UupsUnsupportedProxiableUuid),
+}
+
+impl From<UupsUpgradeableError> for Vec<u8> {
+    fn from(e: UupsUpgradeableError) -> Vec<u8> {
+        match e {
+            UupsUpgradeableError::UupsUnauthorizedCallContext(e) => e.encode(),
+            UupsUpgradeableError::UupsUnsupportedProxiableUuid(e) => e.encode(),
+        }
+    }
+}
+
+crate::errors::error_helpers!(UupsUpgradeableError {
+    UupsUnauthorizedCallContext,
+    UupsUnsupportedProxiableUuid,
+});
+
+impl UupsUpgradeable {
+    /// Reverts unless called through a proxy.
+    pub fn only_proxy(&self) -> Result<(), UupsUpgradeableError> {
+        if erc1967::implementation() == Address::ZERO {
+            return Err(UupsUpgradeableError::UupsUnauthorizedCallContext(UupsUnauthorizedCallContext {}));
+        }
+        Ok(())
+    }
+
+    /// Reverts when called through a proxy.
+    pub fn not_delegated(&self) -> Result<(), UupsUpgradeableError> {
+        if erc1967::implementation() != Address::ZERO {
+            return Err(UupsUpgradeableError::UupsUnauthorizedCallContext(UupsUnauthorizedCallContext {}));
+        }
+        Ok(())
+    }
+
+    /// Upgrades the proxy to `new_implementation` and delegatecalls it with `data` unless empty.
+    /// The new implementation must answer `proxiableUUID` with the EIP-1967 slot, so a proxy is
+    /// never upgraded to a program that can not upgrade it further.
+    ///
+    /// Emits an {Upgraded} event.
+    pub fn upgrade_to_and_call(&mut self, new_implementation: Address, data: &[u8]) -> Result<(), Vec<u8>> {
+        self.only_proxy()?;
+        // a program without `proxiableUUID` (or reverting it, as a proxy does) is not UUPS
+        let output = call::static_call(Call::new(), new_implementation, &PROXIABLE_UUID_SELECTOR).map_err(|_| {
+            Erc1967Error::Erc1967InvalidImplementation(Erc1967InvalidImplementation {
+                implementation: new_implementation,
+            })
+        })?;
+        let slot = if output.len() == 32 { B256::from_slice(&output) } else { B256::ZERO };
+        if slot != IMPLEMENTATION_SLOT {
+            return Err(UupsUpgradeableError::UupsUnsupportedProxiableUuid(UupsUnsupportedProxiableUuid { slot }).into());
+        }
+        erc1967::upgrade_to_and_call(new_implementation, data)?;
+        Ok(())
+    }
+}
+
+#[external]
+impl UupsUpgradeable {
+    /// ERC-1822: returns the EIP-1967 implementation slot, reverts through a proxy so a proxy
+    /// can not be upgraded to another proxy.
+    #[selector(name = "proxiableUUID")]
+    pub fn proxiable_uuid(&self) -> Result<B256, UupsUpgradeableError> {
+        self.not_delegated()?;
+        Ok(IMPLEMENTATION_SLOT)
+    }
+
+    /// Returns the current implementation, the zero address outside of a proxy.
+    #[selector(name = "implementation")]
+    pub fn implementation(&self) -> Result<Address, UupsUpgradeableError> {
+        Ok(erc1967::implementation())
+    }
+}
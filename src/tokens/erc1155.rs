@@ -0,0 +1,401 @@
+use alloc::{string::String, vec, vec::Vec};
+use core::marker::PhantomData;
+use stylus_sdk::{
+    abi::Bytes,
+    alloy_primitives::{Address, FixedBytes, U256},
+    alloy_sol_types::{sol, SolError},
+    evm, msg,
+    prelude::*,
+};
+
+use crate::utils::abi_export::{self, AbiItem, AbiItems};
+use crate::utils::erc165::{self, Erc165};
+use crate::utils::transfer_checks::{check_on_erc1155_batch_received, check_on_erc1155_received};
+
+/// ERC1155 base params
+pub trait Erc1155Params {
+    /// metadata URI of every id, clients replace `{id}` with the lowercase hex id padded to 64
+    /// characters (e.g. `https://game.example/api/item/{id}.json`)
+    const URI: &'static str;
+}
+
+sol_storage! {
+    /// ERC1155 storage
+    pub struct Erc1155<T> {
+        /// id => account => balance
+        mapping(uint256 => mapping(address => uint256)) balances;
+        /// owner => operator => approved for all ids of the owner
+        mapping(address => mapping(address => bool)) operator_approvals;
+        /// special construct to allow having Erc1155Params
+        PhantomData<T> phantom;
+    }
+}
+
+sol! {
+    event TransferSingle(address indexed operator, address indexed from, address indexed to, uint256 id, uint256 value);
+    event TransferBatch(address indexed operator, address indexed from, address indexed to, uint256[] ids, uint256[] values);
+    event ApprovalForAll(address indexed account, address indexed operator, bool approved);
+    event URI(string value, uint256 indexed id);
+
+    /// Indicates an error related to the current `balance` of a `sender`. Used in transfers.
+    /// * `sender` - address whose tokens are being transferred.
+    /// * `balance` - current balance for the interacting account.
+    /// * `needed` - minimum amount required to perform a transfer.
+    /// * `token_id` - identifier number of a token.
+    error Erc1155InsufficientBalance(address sender, uint256 balance, uint256 needed, uint256 token_id);
+
+    /// Indicates a failure with the token `sender`. Used in transfers.
+    error Erc1155InvalidSender(address sender);
+
+    /// Indicates a failure with the token `receiver`. Used in transfers.
+    error Erc1155InvalidReceiver(address receiver);
+
+    /// Indicates a failure with the `operator`'s approval. Used in transfers.
+    /// * `operator` - address that may be allowed to operate on tokens without being their owner.
+    /// * `owner` - address of the current owner of the tokens.
+    error Erc1155MissingApprovalForAll(address operator, address owner);
+
+    /// Indicates a failure with the `approver` of a token to be approved. Used in approvals.
+    error Erc1155InvalidApprover(address approver);
+
+    /// Indicates a failure with the `operator` to be approved. Used in approvals.
+    error Erc1155InvalidOperator(address operator);
+
+    /// Indicates an array length mismatch between ids and values in a batch operation.
+    error Erc1155InvalidArrayLength(uint256 ids_length, uint256 values_length);
+}
+
+pub enum Erc1155Error {
+    Erc1155InsufficientBalance(Erc1155InsufficientBalance),
+    Erc1155InvalidSender(Erc1155InvalidSender),
+    Erc1155InvalidReceiver(Erc1155InvalidReceiver),
+    Erc1155MissingApprovalForAll(Erc1155MissingApprovalForAll),
+    Erc1155InvalidApprover(Erc1155InvalidApprover),
+    Erc1155InvalidOperator(Erc1155InvalidOperator),
+    Erc1155InvalidArrayLength(Erc1155InvalidArrayLength),
+}
+
+impl From<Erc1155Error> for Vec<u8> {
+    fn from(e: Erc1155Error) -> Vec<u8> {
+        match e {
+            Erc1155Error::Erc1155InsufficientBalance(e) => e.encode(),
+            Erc1155Error::Erc1155InvalidSender(e) => e.encode(),
+            Erc1155Error::Erc1155InvalidReceiver(e) => e.encode(),
+            Erc1155Error::Erc1155MissingApprovalForAll(e) => e.encode(),
+            Erc1155Error::Erc1155InvalidApprover(e) => e.encode(),
+            Erc1155Error::Erc1155InvalidOperator(e) => e.encode(),
+            Erc1155Error::Erc1155InvalidArrayLength(e) => e.encode(),
+        }
+    }
+}
+
+crate::errors::error_helpers!(Erc1155Error {
+    Erc1155InsufficientBalance,
+    Erc1155InvalidSender,
+    Erc1155InvalidReceiver,
+    Erc1155MissingApprovalForAll,
+    Erc1155InvalidApprover,
+    Erc1155InvalidOperator,
+    Erc1155InvalidArrayLength,
+});
+
+/// Methods not exposed to other contracts, public so the composing contract (and extensions)
+/// can call them, same as for `Erc20`.
+impl<T: Erc1155Params> Erc1155<T> {
+    /// Transfers `values` of `ids` from `from` to `to`, or alternatively mints (or burns) if `from`
+    /// (or `to`) is the zero address. Receivers are not checked, see {update_with_acceptance_check}.
+    ///
+    /// Emits a {TransferSingle} event for a single id, a {TransferBatch} event otherwise.
+    pub fn update(&mut self, from: Address, to: Address, ids: Vec<U256>, values: Vec<U256>) -> Result<(), Erc1155Error> {
+        if ids.len() != values.len() {
+            return Err(Erc1155Error::Erc1155InvalidArrayLength(Erc1155InvalidArrayLength {
+                ids_length: U256::from(ids.len()),
+                values_length: U256::from(values.len()),
+            }));
+        }
+
+        for (&id, &value) in ids.iter().zip(values.iter()) {
+            if from != Address::ZERO {
+                let mut from_balance_ref = self.balances.setter(id).setter(from);
+                let from_balance = from_balance_ref.get();
+                if from_balance < value {
+                    return Err(Erc1155Error::Erc1155InsufficientBalance(Erc1155InsufficientBalance {
+                        sender: from,
+                        balance: from_balance,
+                        needed: value,
+                        token_id: id,
+                    }));
+                }
+                from_balance_ref.set(from_balance - value);
+            }
+            if to != Address::ZERO {
+                let mut to_balance_ref = self.balances.setter(id).setter(to);
+                let to_balance = to_balance_ref.get();
+                to_balance_ref.set(to_balance + value);
+            }
+        }
+
+        let operator = msg::sender();
+        if ids.len() == 1 {
+            evm::log(TransferSingle {
+                operator,
+                from,
+                to,
+                id: ids[0],
+                value: values[0],
+            });
+        } else {
+            evm::log(TransferBatch {
+                operator,
+                from,
+                to,
+                ids,
+                values,
+            });
+        }
+        Ok(())
+    }
+
+    /// Same as {update} and, if `to` is a contract, calls `onERC1155Received` (single id) or
+    /// `onERC1155BatchReceived` on it, reverting unless it accepts the tokens.
+    pub fn update_with_acceptance_check(
+        &mut self,
+        from: Address,
+        to: Address,
+        ids: Vec<U256>,
+        values: Vec<U256>,
+        data: &[u8],
+    ) -> Result<(), Vec<u8>> {
+        self.update(from, to, ids.clone(), values.clone())?;
+        if to == Address::ZERO {
+            return Ok(());
+        }
+        let operator = msg::sender();
+        if ids.len() == 1 {
+            check_on_erc1155_received(operator, from, to, ids[0], values[0], data)
+        } else {
+            check_on_erc1155_batch_received(operator, from, to, &ids, &values, data)
+        }
+    }
+
+    /// Mints `value` of `id` to `to`, checking that a contract receiver accepts them.
+    ///
+    /// Emits a {TransferSingle} event with `from` set to the zero address.
+    pub fn mint(&mut self, to: Address, id: U256, value: U256, data: &[u8]) -> Result<(), Vec<u8>> {
+        self.mint_batch(to, vec![id], vec![value], data)
+    }
+
+    /// Batched version of {mint}.
+    ///
+    /// Emits a {TransferSingle} or {TransferBatch} event with `from` set to the zero address.
+    pub fn mint_batch(&mut self, to: Address, ids: Vec<U256>, values: Vec<U256>, data: &[u8]) -> Result<(), Vec<u8>> {
+        if to == Address::ZERO {
+            return Err(Erc1155Error::Erc1155InvalidReceiver(Erc1155InvalidReceiver {
+                receiver: Address::ZERO,
+            })
+            .into());
+        }
+        self.update_with_acceptance_check(Address::ZERO, to, ids, values, data)
+    }
+
+    /// Destroys `value` of `id` owned by `from`.
+    ///
+    /// Emits a {TransferSingle} event with `to` set to the zero address.
+    pub fn burn(&mut self, from: Address, id: U256, value: U256) -> Result<(), Erc1155Error> {
+        self.burn_batch(from, vec![id], vec![value])
+    }
+
+    /// Batched version of {burn}.
+    ///
+    /// Emits a {TransferSingle} or {TransferBatch} event with `to` set to the zero address.
+    pub fn burn_batch(&mut self, from: Address, ids: Vec<U256>, values: Vec<U256>) -> Result<(), Erc1155Error> {
+        if from == Address::ZERO {
+            return Err(Erc1155Error::Erc1155InvalidSender(Erc1155InvalidSender {
+                sender: Address::ZERO,
+            }));
+        }
+        self.update(from, Address::ZERO, ids, values)
+    }
+
+    /// Transfers `values` of `ids` from `from` to `to` without checking the caller, checking that
+    /// a contract receiver accepts them.
+    ///
+    /// Emits a {TransferSingle} or {TransferBatch} event.
+    pub fn safe_transfer_internal(
+        &mut self,
+        from: Address,
+        to: Address,
+        ids: Vec<U256>,
+        values: Vec<U256>,
+        data: &[u8],
+    ) -> Result<(), Vec<u8>> {
+        if to == Address::ZERO {
+            return Err(Erc1155Error::Erc1155InvalidReceiver(Erc1155InvalidReceiver {
+                receiver: Address::ZERO,
+            })
+            .into());
+        }
+        if from == Address::ZERO {
+            return Err(Erc1155Error::Erc1155InvalidSender(Erc1155InvalidSender {
+                sender: Address::ZERO,
+            })
+            .into());
+        }
+        self.update_with_acceptance_check(from, to, ids, values, data)
+    }
+
+    /// Reverts unless `operator` is `owner` or an operator of `owner`.
+    pub fn check_approved(&self, owner: Address, operator: Address) -> Result<(), Erc1155Error> {
+        if owner != operator && !self.operator_approvals.get(owner).get(operator) {
+            return Err(Erc1155Error::Erc1155MissingApprovalForAll(Erc1155MissingApprovalForAll {
+                operator,
+                owner,
+            }));
+        }
+        Ok(())
+    }
+
+    /// Approves or removes `operator` as an operator of all ids of `owner`.
+    ///
+    /// Emits an {ApprovalForAll} event.
+    pub fn set_approval_for_all_internal(
+        &mut self,
+        owner: Address,
+        operator: Address,
+        approved: bool,
+    ) -> Result<(), Erc1155Error> {
+        if operator == Address::ZERO {
+            return Err(Erc1155Error::Erc1155InvalidOperator(Erc1155InvalidOperator {
+                operator,
+            }));
+        }
+        self.operator_approvals.setter(owner).insert(operator, approved);
+
+        evm::log(ApprovalForAll {
+            account: owner,
+            operator,
+            approved,
+        });
+        Ok(())
+    }
+}
+
+/// ERC-1155 and its metadata URI extension.
+impl<T: Erc1155Params> Erc165 for Erc1155<T> {
+    fn supports_interface(interface_id: FixedBytes<4>) -> bool {
+        erc165::supports(interface_id, &[erc165::IERC1155, erc165::IERC1155_METADATA_URI])
+    }
+}
+
+impl<T: Erc1155Params> AbiItems for Erc1155<T> {
+    fn abi_items() -> Vec<AbiItem> {
+        Vec::from([
+            abi_export::event::<TransferSingle>(
+                "event TransferSingle(address indexed operator, address indexed from, address indexed to, uint256 id, uint256 value)",
+            ),
+            abi_export::event::<TransferBatch>(
+                "event TransferBatch(address indexed operator, address indexed from, address indexed to, uint256[] ids, uint256[] values)",
+            ),
+            abi_export::event::<ApprovalForAll>(
+                "event ApprovalForAll(address indexed account, address indexed operator, bool approved)",
+            ),
+            abi_export::event::<URI>("event URI(string value, uint256 indexed id)"),
+            abi_export::error::<Erc1155InsufficientBalance>(
+                "error Erc1155InsufficientBalance(address sender, uint256 balance, uint256 needed, uint256 token_id)",
+            ),
+            abi_export::error::<Erc1155InvalidSender>("error Erc1155InvalidSender(address sender)"),
+            abi_export::error::<Erc1155InvalidReceiver>("error Erc1155InvalidReceiver(address receiver)"),
+            abi_export::error::<Erc1155MissingApprovalForAll>(
+                "error Erc1155MissingApprovalForAll(address operator, address owner)",
+            ),
+            abi_export::error::<Erc1155InvalidApprover>("error Erc1155InvalidApprover(address approver)"),
+            abi_export::error::<Erc1155InvalidOperator>("error Erc1155InvalidOperator(address operator)"),
+            abi_export::error::<Erc1155InvalidArrayLength>(
+                "error Erc1155InvalidArrayLength(uint256 ids_length, uint256 values_length)",
+            ),
+        ])
+    }
+}
+
+/// Solidity names of the externals are pinned with `#[selector(name = ...)]`, as for `Erc20`.
+#[external]
+impl<T: Erc1155Params> Erc1155<T> {
+    /// Returns whether the contract implements `interface_id`, see `utils::erc165`.
+    #[selector(name = "supportsInterface")]
+    pub fn supports_interface(interface_id: FixedBytes<4>) -> Result<bool, Erc1155Error> {
+        Ok(<Self as Erc165>::supports_interface(interface_id))
+    }
+
+    /// Returns the metadata URI of `id`, the same template for every id (see `Erc1155Params::URI`).
+    #[selector(name = "uri")]
+    pub fn uri(&self, _id: U256) -> Result<String, Erc1155Error> {
+        Ok(T::URI.into())
+    }
+
+    #[selector(name = "balanceOf")]
+    pub fn balance_of(&self, account: Address, id: U256) -> Result<U256, Erc1155Error> {
+        Ok(self.balances.get(id).get(account))
+    }
+
+    /// Balances of `accounts[i]` in `ids[i]`, both arrays must have the same length.
+    #[selector(name = "balanceOfBatch")]
+    pub fn balance_of_batch(&self, accounts: Vec<Address>, ids: Vec<U256>) -> Result<Vec<U256>, Erc1155Error> {
+        if accounts.len() != ids.len() {
+            return Err(Erc1155Error::Erc1155InvalidArrayLength(Erc1155InvalidArrayLength {
+                ids_length: U256::from(ids.len()),
+                values_length: U256::from(accounts.len()),
+            }));
+        }
+        Ok(accounts
+            .iter()
+            .zip(ids.iter())
+            .map(|(account, id)| self.balances.get(*id).get(*account))
+            .collect())
+    }
+
+    #[selector(name = "isApprovedForAll")]
+    pub fn is_approved_for_all(&self, account: Address, operator: Address) -> Result<bool, Erc1155Error> {
+        Ok(self.operator_approvals.get(account).get(operator))
+    }
+
+    /// Approves or removes `operator` as an operator of all ids of the caller.
+    ///
+    /// Emits an {ApprovalForAll} event.
+    #[selector(name = "setApprovalForAll")]
+    pub fn set_approval_for_all(&mut self, operator: Address, approved: bool) -> Result<(), Erc1155Error> {
+        self.set_approval_for_all_internal(msg::sender(), operator, approved)
+    }
+
+    /// Transfers `value` of `id` from `from` to `to`, the caller must be `from` or its operator.
+    /// If `to` is a contract it must implement `onERC1155Received`.
+    ///
+    /// Emits a {TransferSingle} event.
+    #[selector(name = "safeTransferFrom")]
+    pub fn safe_transfer_from(
+        &mut self,
+        from: Address,
+        to: Address,
+        id: U256,
+        value: U256,
+        data: Bytes,
+    ) -> Result<(), Vec<u8>> {
+        self.check_approved(from, msg::sender())?;
+        self.safe_transfer_internal(from, to, vec![id], vec![value], &data)
+    }
+
+    /// Batched version of {safe_transfer_from}, a contract `to` must implement `onERC1155BatchReceived`.
+    ///
+    /// Emits a {TransferSingle} or {TransferBatch} event.
+    #[selector(name = "safeBatchTransferFrom")]
+    pub fn safe_batch_transfer_from(
+        &mut self,
+        from: Address,
+        to: Address,
+        ids: Vec<U256>,
+        values: Vec<U256>,
+        data: Bytes,
+    ) -> Result<(), Vec<u8>> {
+        self.check_approved(from, msg::sender())?;
+        self.safe_transfer_internal(from, to, ids, values, &data)
+    }
+}
@@ -1,12 +1,75 @@
 use alloc::{string::String, vec::Vec};
 use core::marker::PhantomData;
 use stylus_sdk::{
-    alloy_primitives::{Address, U256},
-    alloy_sol_types::{sol, SolError},
+    alloy_primitives::{Address, FixedBytes, B256, U256},
+    alloy_sol_types::{sol, SolError, SolEvent},
+    crypto::keccak,
     evm, msg,
     prelude::*,
+    types::AddressVM,
 };
 
+use crate::utils::abi_export::{self, AbiItem, AbiItems};
+use crate::utils::checkpoints::ClockMode;
+use crate::utils::eip712::{address_word, u256_word};
+use crate::utils::erc165::{self, Erc165};
+use crate::utils::hostio;
+use crate::utils::storage_slots;
+
+/// How {Transfer} and {Approval} events expose accounts.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum EventMode {
+    /// ERC20 compliant events, accounts are indexed topics.
+    Standard,
+    /// NON STANDARD: accounts are moved from the topics to the event data, the events can not be
+    /// filtered by account any more.
+    Unindexed,
+    /// NON STANDARD: account topics are `keccak256(abi.encode(account))`, filtering needs the
+    /// account. This only reduces casual linkability, anyone can hash known addresses.
+    HashedAccounts,
+}
+
+/// Customization of every balance change going through `Erc20::update` (transfers, mints and
+/// burns), plugged in with `Erc20Params::Hook` so a token does not have to override every
+/// external moving balances. Both hooks default to doing nothing, an error reverts the update
+/// with the returned bytes.
+///
+/// Hooks get the `Erc20` only: state kept by other fields of the entrypoint (pause flag,
+/// checkpoints...) is out of reach, extensions needing it still wrap the externals.
+/// {update_balances} does not run the hooks.
+pub trait TransferHook {
+    /// Runs before `value` moves from `from` to `to`, zero addresses for mints and burns.
+    fn before_update<T: Erc20Params>(
+        _erc20: &Erc20<T>,
+        _from: Address,
+        _to: Address,
+        _value: U256,
+    ) -> Result<(), Vec<u8>> {
+        Ok(())
+    }
+
+    /// Runs once balances and total supply are updated, before the {Transfer} event.
+    fn after_update<T: Erc20Params>(
+        _erc20: &Erc20<T>,
+        _from: Address,
+        _to: Address,
+        _value: U256,
+    ) -> Result<(), Vec<u8>> {
+        Ok(())
+    }
+}
+
+/// `TransferHook` of tokens without customization.
+pub struct NoTransferHook;
+
+impl TransferHook for NoTransferHook {}
+
+/// `dangerousAllowances` flag: the spender has no code, an EOA (or a not yet deployed
+/// contract) can move the tokens at will.
+pub const ALLOWANCE_TO_EOA: u8 = 1;
+/// `dangerousAllowances` flag: the allowance is infinite (`type(uint256).max`).
+pub const ALLOWANCE_UNLIMITED: u8 = 2;
+
 /// ERC20 base params
 pub trait Erc20Params {
     /// token name
@@ -15,6 +78,20 @@ pub trait Erc20Params {
     const SYMBOL: &'static str;
     /// token decimals
     const DECIMALS: u8;
+    /// events encoding, the non standard modes are meant for permissioned chains where public
+    /// linkability of accounts must be reduced, wallets and indexers will not track the token
+    const EVENT_MODE: EventMode = EventMode::Standard;
+    /// first storage slot of the `Erc20` in the entrypoint, 0 when it is its first field;
+    /// only used by `balanceSlot` to report storage keys for proofs
+    const STORAGE_BASE_SLOT: u64 = 0;
+    /// when set, every allowance spend of a (owner, spender) pair gets the next id of the pair,
+    /// emitted in an {AllowanceSpent} event, so custodians can reconcile `transferFrom`s by id
+    /// even if reorgs drop and reorder them; costs one extra storage write and log per spend
+    const ALLOWANCE_SPEND_IDS: bool = false;
+    /// clock of the checkpoints of extensions tracking history (`Erc20Votes`)
+    const CLOCK_MODE: ClockMode = ClockMode::BlockNumber;
+    /// hooks run on every `update`, `NoTransferHook` for none
+    type Hook: TransferHook;
 }
 
 sol_storage! {
@@ -22,10 +99,15 @@ sol_storage! {
     pub struct Erc20<T> {
         /// token balances
         mapping(address => uint256) balances;
-        /// token allowances
-        mapping(address => mapping(address => uint256)) allowances;
+        /// token allowances, owner => allowance epoch => spender => value
+        mapping(address => mapping(uint256 => mapping(address => uint256))) allowances;
+        /// current allowance epoch of each owner, only allowances of the current epoch are valid
+        mapping(address => uint256) allowance_epochs;
         /// total supply
         uint256 total_supply;
+        /// last spend id of each owner => spender pair, only written with `ALLOWANCE_SPEND_IDS`;
+        /// not tied to the allowance epoch so ids keep increasing across revokes
+        mapping(address => mapping(address => uint256)) allowance_spend_ids;
         /// special construct to allow having Erc20Params
         PhantomData<T> phantom;
     }
@@ -34,6 +116,11 @@ sol_storage! {
 sol! {
     event Transfer(address indexed from, address indexed to, uint256 value);
     event Approval(address indexed owner, address indexed spender, uint256 value);
+    /// All allowances given by `owner` before `epoch` are no longer valid.
+    event AllowancesRevoked(address indexed owner, uint256 epoch);
+    /// `spender` used `value` of its allowance over `owner`'s tokens, `spend_id` is the 1 based
+    /// sequence number of the spend for the pair.
+    event AllowanceSpent(address indexed owner, address indexed spender, uint256 indexed spend_id, uint256 value);
 
      /// Indicates an error related to the current `balance` of a `sender`. Used in transfers.
      /// * `sender` - address whose tokens are being transferred.
@@ -58,6 +145,26 @@ sol! {
     /// Indicates a failure with the token `receiver`. Used in transfers.
     /// * `receiver` - address to which tokens are being transferred.
     error Erc20InvalidReceiver(address receiver);
+
+    /// Indicates a decrease of the allowance of `spender` below zero. Used in `decreaseAllowance`.
+    /// * `spender` - address whose allowance is decreased.
+    /// * `current_allowance` - allowance before the decrease.
+    /// * `requested_decrease` - amount the allowance was asked to decrease by.
+    error Erc20FailedDecreaseAllowance(address spender, uint256 current_allowance, uint256 requested_decrease);
+
+    /// Indicates an increase of the allowance of `spender` above `type(uint256).max`. Used in
+    /// `increaseAllowance`.
+    /// * `spender` - address whose allowance is increased.
+    /// * `current_allowance` - allowance before the increase.
+    /// * `added_value` - amount the allowance was asked to increase by.
+    error Erc20AllowanceOverflow(address spender, uint256 current_allowance, uint256 added_value);
+
+    /// Indicates a balance or total supply update leaving `uint256`: `current + value` above
+    /// `type(uint256).max` or `current - value` below zero. Only possible when an extension broke
+    /// the invariant `balance <= totalSupply`, e.g. by writing balances directly.
+    /// * `current` - balance or total supply before the update.
+    /// * `value` - amount added or subtracted.
+    error Erc20ArithmeticOverflow(uint256 current, uint256 value);
 }
 
 pub enum Erc20Error {
@@ -66,6 +173,11 @@ pub enum Erc20Error {
     Erc20InvalidSpender(Erc20InvalidSpender),
     Erc20InvalidApprover(Erc20InvalidApprover),
     Erc20InvalidReceiver(Erc20InvalidReceiver),
+    Erc20FailedDecreaseAllowance(Erc20FailedDecreaseAllowance),
+    Erc20AllowanceOverflow(Erc20AllowanceOverflow),
+    Erc20ArithmeticOverflow(Erc20ArithmeticOverflow),
+    /// revert data returned by a `TransferHook`, passed through as is
+    TransferHookRejected(Vec<u8>),
 }
 
 impl From<Erc20Error> for Vec<u8> {
@@ -76,10 +188,25 @@ impl From<Erc20Error> for Vec<u8> {
             Erc20Error::Erc20InvalidSpender(e) => e.encode(),
             Erc20Error::Erc20InvalidApprover(e) => e.encode(),
             Erc20Error::Erc20InvalidReceiver(e) => e.encode(),
+            Erc20Error::Erc20FailedDecreaseAllowance(e) => e.encode(),
+            Erc20Error::Erc20AllowanceOverflow(e) => e.encode(),
+            Erc20Error::Erc20ArithmeticOverflow(e) => e.encode(),
+            Erc20Error::TransferHookRejected(data) => data,
         }
     }
 }
 
+crate::errors::error_helpers!(Erc20Error {
+    Erc20InsufficientBalance,
+    Erc20InsufficientAllowance,
+    Erc20InvalidSpender,
+    Erc20InvalidApprover,
+    Erc20InvalidReceiver,
+    Erc20FailedDecreaseAllowance,
+    Erc20AllowanceOverflow,
+    Erc20ArithmeticOverflow,
+} passthrough { TransferHookRejected });
+
 /// Methods in this file are not exposed to other contracts (for that they must be under #[external] macro).
 /// If you want other contracts to be able to "extend" your contract and be able to "inherit" some methods that are not external you must put them here and make 
 /// public, in this way they will be visible by Rust in other structs that want to call them.
@@ -119,61 +246,342 @@ impl<T: Erc20Params> Erc20<T> {
     /// (or `to`) is the zero address. All customizations to transfers, mints, and burns should be done by overriding
     /// this function.
     ///
+    /// Runs the `before_update` and `after_update` hooks of `T::Hook` around the change.
+    ///
     /// Emits a {Transfer} event.
     pub fn update(&mut self, from: Address, to: Address, value: U256) -> Result<(), Erc20Error> {
-        if from == Address::ZERO {  // mint
-            let total_supply = self.total_supply.get();
-            self.total_supply.set(total_supply + value);
-        } else {
-            let mut from_balance_ref = self.balances.setter(from);
-            let from_balance_value = from_balance_ref.get();
-            if from_balance_value < value {
-                return  Err(Erc20Error::Erc20InsufficientBalance(Erc20InsufficientBalance {
-                    sender: from,
-                    balance: from_balance_value,
-                    needed: value,
-                }));
-            }
-            // Overflow not possible: value <= fromBalance <= totalSupply.
-            from_balance_ref.set(from_balance_value - value);
+        self.update_without_event(from, to, value)?;
+        Self::emit_transfer(from, to, value);
+        Ok(())
+    }
+
+    /// Same as {update}, hooks included, but without the {Transfer} event, for batches emitting
+    /// one aggregated event instead (see `extensions::erc20_batch_transfer`).
+    pub fn update_without_event(&mut self, from: Address, to: Address, value: U256) -> Result<(), Erc20Error> {
+        T::Hook::before_update(self, from, to, value).map_err(Erc20Error::TransferHookRejected)?;
+        self.update_balances(from, to, value)?;
+        T::Hook::after_update(self, from, to, value).map_err(Erc20Error::TransferHookRejected)?;
+        Ok(())
+    }
+
+    /// Same as {update} but without running the hooks of `T::Hook` nor emitting the {Transfer}
+    /// event, for extensions whose balances are stored in different units than the ones reported
+    /// in events (e.g. scaled balances).
+    ///
+    /// Only reads and writes the slots {balances_after} uses: the total supply of a mint or burn,
+    /// the balances of non zero accounts.
+    pub fn update_balances(&mut self, from: Address, to: Address, value: U256) -> Result<(), Erc20Error> {
+        let mint_or_burn = from == Address::ZERO || to == Address::ZERO;
+        let total_supply = if mint_or_burn { hostio::read(|| self.total_supply.get()) } else { U256::ZERO };
+        let from_balance = if from == Address::ZERO { U256::ZERO } else { hostio::read(|| self.balances.get(from)) };
+        let to_balance = if to == Address::ZERO || to == from { U256::ZERO } else { hostio::read(|| self.balances.get(to)) };
+
+        let (from_balance, to_balance, total_supply) =
+            balances_after(from, to, value, from_balance, to_balance, total_supply)?;
+
+        if from != Address::ZERO {
+            hostio::write(|| self.balances.insert(from, from_balance));
+        }
+        if to != Address::ZERO {
+            hostio::write(|| self.balances.insert(to, to_balance));
         }
-        
-        if to == Address::ZERO {  // burn
-            // Overflow not possible: value <= totalSupply or value <= fromBalance <= totalSupply.
-            let total_supply = self.total_supply.get();
-            self.total_supply.set(total_supply - value);
-        } else {
-            let mut to_balance_ref = self.balances.setter(to);
-            let to_balance_value = to_balance_ref.get();
-            // Overflow not possible: balance + value is at most totalSupply, which we know fits into a uint256.
-            to_balance_ref.set(to_balance_value + value);
+        if mint_or_burn {
+            hostio::write(|| self.total_supply.set(total_supply));
         }
+        Ok(())
+    }
 
-        evm::log(Transfer { from, to, value });
+    /// Moves a `value` amount of tokens from `from` to `to`, both must be non zero addresses.
+    ///
+    /// Emits a {Transfer} event.
+    pub fn transfer_internal(&mut self, from: Address, to: Address, value: U256) -> Result<(), Erc20Error> {
+        if from == Address::ZERO {
+            return Err(Erc20Error::Erc20InvalidSpender(Erc20InvalidSpender {
+                spender: Address::ZERO,
+            }));    
+        }
+        if to == Address::ZERO {
+            return Err(Erc20Error::Erc20InvalidReceiver(Erc20InvalidReceiver {
+                receiver: Address::ZERO,
+            }));
+        }
+
+        self.update(from, to, value)
+    }
+
+    /// Updates `owner`'s allowance for `spender` based on spent `value`.
+    ///
+    /// Does not update the allowance value in case of infinite allowance.
+    /// Revert if not enough allowance is available.
+    ///
+    /// Does not emit an {Approval} event.
+    /// Emits an {AllowanceSpent} event with `ALLOWANCE_SPEND_IDS`, also for infinite allowances.
+    pub fn spend_allowance(&mut self, owner: Address, spender: Address, value: U256) -> Result<(), Erc20Error> {
+        let current_allowance = self.allowance_internal(owner, spender);
+        if let Some(remaining) = remaining_allowance(owner, current_allowance, value)? {
+            self.approve_internal_conditional(owner, spender, remaining, false)?;
+        }
+        if T::ALLOWANCE_SPEND_IDS {
+            self.record_spend(owner, spender, value);
+        }
         Ok(())
     }
+
+    /// Assigns the next spend id of the `owner` => `spender` pair to a spend of `value`.
+    fn record_spend(&mut self, owner: Address, spender: Address, value: U256) {
+        let mut spend_id_ref = self.allowance_spend_ids.setter(owner).setter(spender);
+        let spend_id = hostio::read(|| spend_id_ref.get()) + U256::from(1);
+        hostio::write(|| spend_id_ref.set(spend_id));
+
+        hostio::log(|| {
+            evm::log(AllowanceSpent {
+                owner,
+                spender,
+                spend_id,
+                value,
+            })
+        });
+    }
+
+    /// Sets `value` as the allowance of `spender` over `owner`'s tokens, both must be non zero.
+    ///
+    /// Emits an {Approval} event.
+    pub fn approve_internal(
+        &mut self,
+        owner: Address,
+        spender: Address,
+        value: U256,
+    ) -> Result<(), Erc20Error> {
+        self.approve_internal_conditional(owner, spender, value, true)
+    }
+
+    fn approve_internal_conditional(
+        &mut self,
+        owner: Address,
+        spender: Address,
+        value: U256,
+        emit_event: bool,
+    ) -> Result<(), Erc20Error> {
+        if owner == Address::ZERO {
+            return Err(Erc20Error::Erc20InvalidApprover(Erc20InvalidApprover {
+                approver: Address::ZERO,
+            }));
+        }
+        if spender == Address::ZERO {
+            return Err(Erc20Error::Erc20InvalidSpender(Erc20InvalidSpender {
+                spender: Address::ZERO,
+            }));
+        }
+
+        let epoch = hostio::read(|| self.allowance_epochs.get(owner));
+        hostio::write(|| self.allowances.setter(owner).setter(epoch).insert(spender, value));
+
+        if emit_event {
+            Self::emit_approval(owner, spender, value);
+        }
+        Ok(())
+    }
+
+    /// Emits a {Transfer} event encoded according to `T::EVENT_MODE`, for extensions emitting
+    /// their own transfers (e.g. after {update_balances}).
+    pub fn emit_transfer(from: Address, to: Address, value: U256) {
+        hostio::log(|| match T::EVENT_MODE {
+            EventMode::Standard => evm::log(Transfer { from, to, value }),
+            mode => emit_non_standard(mode, Transfer::SIGNATURE_HASH, from, to, value),
+        })
+    }
+
+    /// Emits an {Approval} event encoded according to `T::EVENT_MODE`.
+    pub fn emit_approval(owner: Address, spender: Address, value: U256) {
+        hostio::log(|| match T::EVENT_MODE {
+            EventMode::Standard => evm::log(Approval {
+                owner,
+                spender,
+                value,
+            }),
+            mode => emit_non_standard(mode, Approval::SIGNATURE_HASH, owner, spender, value),
+        })
+    }
+
+    /// Balance of `account`, the raw stored value (scaled units for extensions storing scaled
+    /// balances).
+    pub fn balance_of_internal(&self, account: Address) -> U256 {
+        hostio::read(|| self.balances.get(account))
+    }
+
+    /// Overwrites the balance of `account` without any check, hook or event. The total supply
+    /// is not adjusted: the caller keeps it equal to the sum of the balances, {update} and
+    /// {update_balances} should be preferred whenever tokens move.
+    pub fn set_balance_internal(&mut self, account: Address, value: U256) {
+        hostio::write(|| self.balances.insert(account, value));
+    }
+
+    /// Total supply, the raw stored value.
+    pub fn total_supply_internal(&self) -> U256 {
+        hostio::read(|| self.total_supply.get())
+    }
+
+    /// Allowance of `spender` over `owner`'s tokens in the current allowance epoch of `owner`.
+    pub fn allowance_internal(&self, owner: Address, spender: Address) -> U256 {
+        let epoch = hostio::read(|| self.allowance_epochs.get(owner));
+        hostio::read(|| self.allowances.get(owner).get(epoch).get(spender))
+    }
 }
 
+/// ERC-20 and its metadata (`name`, `symbol`, `decimals`).
+impl<T: Erc20Params> Erc165 for Erc20<T> {
+    fn supports_interface(interface_id: FixedBytes<4>) -> bool {
+        erc165::supports(interface_id, &[erc165::IERC20, erc165::IERC20_METADATA])
+    }
+}
+
+/// {Transfer} and {Approval} follow `T::EVENT_MODE`: accounts are not indexed with
+/// `Unindexed`; with `HashedAccounts` they stay declared as indexed addresses but the topics are
+/// hashes, decoded accounts are meaningless.
+impl<T: Erc20Params> AbiItems for Erc20<T> {
+    fn abi_items() -> Vec<AbiItem> {
+        let (transfer, approval) = match T::EVENT_MODE {
+            EventMode::Unindexed => (
+                "event Transfer(address from, address to, uint256 value)",
+                "event Approval(address owner, address spender, uint256 value)",
+            ),
+            _ => (
+                "event Transfer(address indexed from, address indexed to, uint256 value)",
+                "event Approval(address indexed owner, address indexed spender, uint256 value)",
+            ),
+        };
+        let mut items = Vec::from([
+            abi_export::event::<Transfer>(transfer),
+            abi_export::event::<Approval>(approval),
+            abi_export::event::<AllowancesRevoked>("event AllowancesRevoked(address indexed owner, uint256 epoch)"),
+            abi_export::error::<Erc20InsufficientBalance>(
+                "error Erc20InsufficientBalance(address sender, uint256 balance, uint256 needed)",
+            ),
+            abi_export::error::<Erc20InsufficientAllowance>(
+                "error Erc20InsufficientAllowance(address sender, uint256 allowance, uint256 needed)",
+            ),
+            abi_export::error::<Erc20InvalidApprover>("error Erc20InvalidApprover(address approver)"),
+            abi_export::error::<Erc20InvalidSpender>("error Erc20InvalidSpender(address spender)"),
+            abi_export::error::<Erc20InvalidReceiver>("error Erc20InvalidReceiver(address receiver)"),
+            abi_export::error::<Erc20FailedDecreaseAllowance>(
+                "error Erc20FailedDecreaseAllowance(address spender, uint256 current_allowance, uint256 requested_decrease)",
+            ),
+            abi_export::error::<Erc20AllowanceOverflow>(
+                "error Erc20AllowanceOverflow(address spender, uint256 current_allowance, uint256 added_value)",
+            ),
+            abi_export::error::<Erc20ArithmeticOverflow>("error Erc20ArithmeticOverflow(uint256 current, uint256 value)"),
+        ]);
+        if T::ALLOWANCE_SPEND_IDS {
+            items.push(abi_export::event::<AllowanceSpent>(
+                "event AllowanceSpent(address indexed owner, address indexed spender, uint256 indexed spend_id, uint256 value)",
+            ));
+        }
+        items
+    }
+}
+
+/// Solidity names of the externals are pinned with `#[selector(name = ...)]` instead of relying on the
+/// automatic snake_case -> camelCase conversion, renaming a method in Rust must not change the ABI.
+/// The exported selectors are checked against `tests/golden/my_token.selectors`.
 #[external]
 impl<T: Erc20Params> Erc20<T> {
+    #[selector(name = "name")]
     pub fn name() -> Result<String, Erc20Error> {
         Ok(T::NAME.into())
     }
 
+    #[selector(name = "symbol")]
     pub fn symbol() -> Result<String, Erc20Error> {
         Ok(T::SYMBOL.into())
     }
 
+    #[selector(name = "decimals")]
     pub fn decimals() -> Result<u8, Erc20Error> {
         Ok(T::DECIMALS)
     }
 
+    /// Returns whether the contract implements `interface_id`, see `utils::erc165`.
+    #[selector(name = "supportsInterface")]
+    pub fn supports_interface(interface_id: FixedBytes<4>) -> Result<bool, Erc20Error> {
+        Ok(<Self as Erc165>::supports_interface(interface_id))
+    }
+
+    #[selector(name = "totalSupply")]
+    pub fn total_supply(&self) -> Result<U256, Erc20Error> {
+        Ok(self.total_supply_internal())
+    }
+
+    #[selector(name = "balanceOf")]
     pub fn balance_of(&self, address: Address) -> Result<U256, Erc20Error> {
-        Ok(self.balances.get(address))
+        Ok(self.balance_of_internal(address))
     }
 
+    /// Returns the storage key of the balance of `account`, for light clients and cross-chain
+    /// messaging proving a balance with `eth_getProof` instead of trusting an RPC `balanceOf`.
+    /// The value stored at the key is the raw balance.
+    ///
+    /// NOTE: only valid if `STORAGE_BASE_SLOT` matches the position of the `Erc20` in the
+    /// entrypoint, see `utils::storage_slots` for the layout.
+    #[selector(name = "balanceSlot")]
+    pub fn balance_slot(&self, account: Address) -> Result<B256, Erc20Error> {
+        Ok(storage_slots::erc20_balance_slot(U256::from(T::STORAGE_BASE_SLOT), account))
+    }
+
+    #[selector(name = "allowance")]
     pub fn allowance(&self, owner: Address, spender: Address) -> Result<U256, Erc20Error> {
-        Ok(self.allowances.get(owner).get(spender))
+        Ok(self.allowance_internal(owner, spender))
+    }
+
+    /// Returns the current allowance epoch of `owner`, incremented on every `revoke_all_allowances`.
+    #[selector(name = "allowanceEpoch")]
+    pub fn allowance_epoch(&self, owner: Address) -> Result<U256, Erc20Error> {
+        Ok(self.allowance_epochs.get(owner))
+    }
+
+    /// Returns the id of the last allowance spend of `spender` over `owner`'s tokens, 0 if none
+    /// or if the token does not use `ALLOWANCE_SPEND_IDS`.
+    #[selector(name = "allowanceSpendId")]
+    pub fn allowance_spend_id(&self, owner: Address, spender: Address) -> Result<U256, Erc20Error> {
+        Ok(self.allowance_spend_ids.get(owner).get(spender))
+    }
+
+    /// Audits the allowances of `owner` to `spenders`: returns, for each spender, a bitmask of
+    /// `ALLOWANCE_TO_EOA` and `ALLOWANCE_UNLIMITED`, 0 for no allowance or a bounded allowance to
+    /// a contract. Wallets pass the spenders found in {Approval} events and `revoke` the flagged ones.
+    #[selector(name = "dangerousAllowances")]
+    pub fn dangerous_allowances(&self, owner: Address, spenders: Vec<Address>) -> Result<Vec<u8>, Erc20Error> {
+        Ok(spenders
+            .into_iter()
+            .map(|spender| {
+                let allowance = self.allowance_internal(owner, spender);
+                if allowance == U256::ZERO {
+                    return 0;
+                }
+                let mut flags = 0;
+                if !spender.has_code() {
+                    flags |= ALLOWANCE_TO_EOA;
+                }
+                if allowance == U256::MAX {
+                    flags |= ALLOWANCE_UNLIMITED;
+                }
+                flags
+            })
+            .collect())
+    }
+
+    /// Sets the allowances of `spenders` over the caller's tokens to 0, skipping the ones
+    /// already at 0. Unlike `revokeAllAllowances` other allowances are kept.
+    ///
+    /// Emits an {Approval} event per revoked allowance.
+    #[selector(name = "revoke")]
+    pub fn revoke(&mut self, spenders: Vec<Address>) -> Result<(), Erc20Error> {
+        let owner = msg::sender();
+        for spender in spenders {
+            if self.allowance_internal(owner, spender) != U256::ZERO {
+                self.approve_internal(owner, spender, U256::ZERO)?;
+            }
+        }
+        Ok(())
     }
 
     /// Sets a `value` amount of tokens as the allowance of `spender` over the
@@ -192,17 +600,82 @@ impl<T: Erc20Params> Erc20<T> {
     ///         `transferFrom`. This is semantically equivalent to an infinite approval.
     ///
     /// Emits an {Approval} event.
+    #[selector(name = "approve")]
     pub fn approve(&mut self, spender: Address, value: U256) -> Result<bool, Erc20Error> {
         let owner = msg::sender();
         self.approve_internal(owner, spender, value)?;
         Ok(true)
     }
 
+    /// Atomically increases the allowance of `spender` over the caller's tokens by `added_value`,
+    /// an alternative to `approve` without its transaction ordering race.
+    ///
+    /// Returns a boolean value indicating whether the operation succeeded.
+    ///
+    /// Emits an {Approval} event indicating the updated allowance.
+    #[selector(name = "increaseAllowance")]
+    pub fn increase_allowance(&mut self, spender: Address, added_value: U256) -> Result<bool, Erc20Error> {
+        let owner = msg::sender();
+        let current_allowance = self.allowance_internal(owner, spender);
+        let value = current_allowance.checked_add(added_value).ok_or(
+            Erc20Error::Erc20AllowanceOverflow(Erc20AllowanceOverflow {
+                spender,
+                current_allowance,
+                added_value,
+            }),
+        )?;
+        self.approve_internal(owner, spender, value)?;
+        Ok(true)
+    }
+
+    /// Atomically decreases the allowance of `spender` over the caller's tokens by
+    /// `requested_decrease`, reverting instead of going below zero.
+    ///
+    /// Returns a boolean value indicating whether the operation succeeded.
+    ///
+    /// NOTE: an infinite allowance is decreased like any other, it becomes finite.
+    ///
+    /// Emits an {Approval} event indicating the updated allowance.
+    #[selector(name = "decreaseAllowance")]
+    pub fn decrease_allowance(&mut self, spender: Address, requested_decrease: U256) -> Result<bool, Erc20Error> {
+        let owner = msg::sender();
+        let current_allowance = self.allowance_internal(owner, spender);
+        let value = current_allowance.checked_sub(requested_decrease).ok_or(
+            Erc20Error::Erc20FailedDecreaseAllowance(Erc20FailedDecreaseAllowance {
+                spender,
+                current_allowance,
+                requested_decrease,
+            }),
+        )?;
+        self.approve_internal(owner, spender, value)?;
+        Ok(true)
+    }
+
+    /// Invalidates every allowance given by the caller with a single storage write, by moving
+    /// the caller to a new allowance epoch. Allowances are stored per epoch, so approvals given
+    /// before the revoke are left in storage but never read again.
+    ///
+    /// NOTE: no {Approval} events are emitted for the revoked allowances, indexers must
+    /// reset all allowances of the owner on {AllowancesRevoked}.
+    ///
+    /// Emits an {AllowancesRevoked} event.
+    #[selector(name = "revokeAllAllowances")]
+    pub fn revoke_all_allowances(&mut self) -> Result<(), Erc20Error> {
+        let owner = msg::sender();
+        let mut epoch_ref = self.allowance_epochs.setter(owner);
+        let epoch = hostio::read(|| epoch_ref.get()) + U256::from(1);
+        hostio::write(|| epoch_ref.set(epoch));
+
+        hostio::log(|| evm::log(AllowancesRevoked { owner, epoch }));
+        Ok(())
+    }
+
     /// Moves a `value` amount of tokens from the caller's account to `to`.
     ///
     /// Returns a boolean value indicating whether the operation succeeded.
     ///
     /// Emits a {Transfer} event.
+    #[selector(name = "transfer")]
     pub fn transfer(&mut self, to: Address, value: U256) -> Result<bool, Erc20Error> {
         let owner = msg::sender();
         self.transfer_internal(owner, to, value)?;
@@ -219,6 +692,7 @@ impl<T: Erc20Params> Erc20<T> {
     ///
     /// Emits a  {Transfer} event.
     /// Emits an {Approval} event indicating the updated allowance (this is not required by the ERC)
+    #[selector(name = "transferFrom")]
     pub fn transfer_from(
         &mut self,
         from: Address,
@@ -230,75 +704,107 @@ impl<T: Erc20Params> Erc20<T> {
         self.transfer_internal(from, to, value)?;
         Ok(true)
     }
+}
 
-    fn transfer_internal(&mut self, from: Address, to: Address, value: U256) -> Result<(), Erc20Error> {
-        if from == Address::ZERO {
-            return Err(Erc20Error::Erc20InvalidSpender(Erc20InvalidSpender {
-                spender: Address::ZERO,
-            }));    
-        }
-        if to == Address::ZERO {
-            return Err(Erc20Error::Erc20InvalidReceiver(Erc20InvalidReceiver {
-                receiver: Address::ZERO,
-            }));
-        }
-
-        self.update(from, to, value)
+/// Balance of `from` once `value` is moved out of it, the error of {update} when it holds less.
+pub fn debit_balance(from: Address, balance: U256, value: U256) -> Result<U256, Erc20Error> {
+    if balance < value {
+        return Err(Erc20Error::Erc20InsufficientBalance(Erc20InsufficientBalance {
+            sender: from,
+            balance,
+            needed: value,
+        }));
     }
+    // Overflow not possible: value <= fromBalance <= totalSupply.
+    Ok(balance - value)
+}
 
-    
+/// `current + value` for a balance or the total supply, `Erc20ArithmeticOverflow` past
+/// `type(uint256).max`.
+pub fn credit(current: U256, value: U256) -> Result<U256, Erc20Error> {
+    current
+        .checked_add(value)
+        .ok_or(Erc20Error::Erc20ArithmeticOverflow(Erc20ArithmeticOverflow { current, value }))
+}
 
-    fn approve_internal(
-        &mut self,
-        owner: Address,
-        spender: Address,
-        value: U256,
-    ) -> Result<(), Erc20Error> {
-        self.approve_internal_conditional(owner, spender, value, true)
+/// Total supply once `value` is burned, `Erc20ArithmeticOverflow` below zero: value <= fromBalance
+/// <= totalSupply unless an extension broke the invariant.
+pub fn debit_supply(total_supply: U256, value: U256) -> Result<U256, Erc20Error> {
+    total_supply
+        .checked_sub(value)
+        .ok_or(Erc20Error::Erc20ArithmeticOverflow(Erc20ArithmeticOverflow {
+            current: total_supply,
+            value,
+        }))
+}
+
+/// `(from balance, to balance, total supply)` after moving `value` from `from` to `to`, the
+/// transition of {Erc20::update_balances}. The zero address mints or burns: its balance is
+/// returned as given, as is the total supply of a plain transfer. A self transfer credits the
+/// debited `from_balance` back, `to_balance` is ignored.
+pub fn balances_after(
+    from: Address,
+    to: Address,
+    value: U256,
+    from_balance: U256,
+    to_balance: U256,
+    total_supply: U256,
+) -> Result<(U256, U256, U256), Erc20Error> {
+    let (mut from_balance, mut to_balance, mut total_supply) = (from_balance, to_balance, total_supply);
+    if from == Address::ZERO {  // mint
+        total_supply = credit(total_supply, value)?;
+    } else {
+        from_balance = debit_balance(from, from_balance, value)?;
     }
 
-    fn approve_internal_conditional(
-        &mut self,
-        owner: Address,
-        spender: Address,
-        value: U256,
-        emit_event: bool,
-    ) -> Result<(), Erc20Error> {
-        if owner == Address::ZERO {
-            return Err(Erc20Error::Erc20InvalidApprover(Erc20InvalidApprover {
-                approver: Address::ZERO,
-            }));
-        }
-        if spender == Address::ZERO {
-            return Err(Erc20Error::Erc20InvalidSpender(Erc20InvalidSpender {
-                spender: Address::ZERO,
-            }));
-        }
+    if to == Address::ZERO {  // burn
+        total_supply = debit_supply(total_supply, value)?;
+    } else {
+        // balance + value <= totalSupply unless an extension broke the invariant
+        let current = if to == from { from_balance } else { to_balance };
+        to_balance = credit(current, value)?;
+    }
+    Ok((from_balance, to_balance, total_supply))
+}
 
-        self.allowances.setter(owner).insert(spender, value);
+/// Whether a transfer of `value` from `from` to `to` moves tokens out of `from`. Zero value and
+/// self transfers leave every balance as is, outbound limits neither check nor count them.
+pub fn moves_out(from: Address, to: Address, value: U256) -> bool {
+    value != U256::ZERO && from != to
+}
 
-        if emit_event {
-            evm::log(Approval {
-                owner,
-                spender,
-                value,
-            });
-        }
-        Ok(())
+/// Allowance of `owner`'s tokens left after spending `value` of `current_allowance`, `None` for
+/// an infinite allowance, which {spend_allowance} does not update.
+pub fn remaining_allowance(owner: Address, current_allowance: U256, value: U256) -> Result<Option<U256>, Erc20Error> {
+    if current_allowance == U256::MAX {
+        return Ok(None);
+    }
+    if current_allowance < value {
+        return Err(Erc20Error::Erc20InsufficientAllowance(Erc20InsufficientAllowance {
+            sender: owner,
+            allowance: current_allowance,
+            needed: value,
+        }));
     }
+    Ok(Some(current_allowance - value))
+}
 
-    fn spend_allowance(&mut self, owner: Address, spender: Address, value: U256) -> Result<(), Erc20Error> {
-        let current_allowance = self.allowances.get(owner).get(spender);
-        if current_allowance != U256::MAX {
-            if current_allowance < value {
-                return Err(Erc20Error::Erc20InsufficientAllowance(Erc20InsufficientAllowance {
-                    sender: owner,
-                    allowance: current_allowance,
-                    needed: value,
-                }));
-            }
-            self.approve_internal_conditional(owner, spender, current_allowance - value, false)?;
+/// `Transfer`/`Approval` with the same signature hash but the non standard account encoding of `mode`.
+fn emit_non_standard(mode: EventMode, signature_hash: B256, first: Address, second: Address, value: U256) {
+    match mode {
+        EventMode::Unindexed => {
+            let mut data = address_word(first).to_vec();
+            data.extend_from_slice(address_word(second).as_slice());
+            data.extend_from_slice(u256_word(value).as_slice());
+            evm::raw_log(&[signature_hash], &data).ok();
+        }
+        _ => {
+            let topics = [
+                signature_hash,
+                keccak(address_word(first)),
+                keccak(address_word(second)),
+            ];
+            evm::raw_log(&topics, u256_word(value).as_slice()).ok();
         }
-        Ok(())
     }
 }
@@ -0,0 +1,470 @@
+use alloc::{string::String, vec::Vec};
+use core::marker::PhantomData;
+use stylus_sdk::{
+    abi::Bytes,
+    alloy_primitives::{Address, FixedBytes, U256},
+    alloy_sol_types::{sol, SolError},
+    evm, msg,
+    prelude::*,
+};
+
+use crate::utils::abi_export::{self, AbiItem, AbiItems};
+use crate::utils::erc165::{self, Erc165};
+use crate::utils::transfer_checks::check_on_erc721_received;
+
+/// ERC721 base params
+pub trait Erc721Params {
+    /// collection name
+    const NAME: &'static str;
+    /// collection symbol
+    const SYMBOL: &'static str;
+}
+
+sol_storage! {
+    /// ERC721 storage
+    pub struct Erc721<T> {
+        /// owner of each token, zero for non existent tokens
+        mapping(uint256 => address) owners;
+        /// number of tokens owned by each account
+        mapping(address => uint256) balances;
+        /// approved account of each token, cleared on transfer
+        mapping(uint256 => address) token_approvals;
+        /// owner => operator => approved for all tokens of the owner
+        mapping(address => mapping(address => bool)) operator_approvals;
+        /// special construct to allow having Erc721Params
+        PhantomData<T> phantom;
+    }
+}
+
+sol! {
+    event Transfer(address indexed from, address indexed to, uint256 indexed token_id);
+    event Approval(address indexed owner, address indexed approved, uint256 indexed token_id);
+    event ApprovalForAll(address indexed owner, address indexed operator, bool approved);
+
+    /// Indicates that an address can't be an owner, e.g. `address(0)` in a balance query.
+    error Erc721InvalidOwner(address owner);
+
+    /// Indicates a `token_id` whose `owner` is the zero address.
+    error Erc721NonexistentToken(uint256 token_id);
+
+    /// Indicates an error related to the ownership over a particular token. Used in transfers.
+    /// * `sender` - address whose tokens are being transferred.
+    /// * `token_id` - identifier number of a token.
+    /// * `owner` - address of the current owner of a token.
+    error Erc721IncorrectOwner(address sender, uint256 token_id, address owner);
+
+    /// Indicates a failure with the token `sender`. Used in transfers.
+    error Erc721InvalidSender(address sender);
+
+    /// Indicates a failure with the token `receiver`. Used in transfers.
+    error Erc721InvalidReceiver(address receiver);
+
+    /// Indicates a failure with the `operator`'s approval. Used in transfers.
+    /// * `operator` - address that may be allowed to operate on tokens without being their owner.
+    /// * `token_id` - identifier number of a token.
+    error Erc721InsufficientApproval(address operator, uint256 token_id);
+
+    /// Indicates a failure with the `approver` of a token to be approved. Used in approvals.
+    error Erc721InvalidApprover(address approver);
+
+    /// Indicates a failure with the `operator` to be approved. Used in approvals.
+    error Erc721InvalidOperator(address operator);
+}
+
+pub enum Erc721Error {
+    Erc721InvalidOwner(Erc721InvalidOwner),
+    Erc721NonexistentToken(Erc721NonexistentToken),
+    Erc721IncorrectOwner(Erc721IncorrectOwner),
+    Erc721InvalidSender(Erc721InvalidSender),
+    Erc721InvalidReceiver(Erc721InvalidReceiver),
+    Erc721InsufficientApproval(Erc721InsufficientApproval),
+    Erc721InvalidApprover(Erc721InvalidApprover),
+    Erc721InvalidOperator(Erc721InvalidOperator),
+}
+
+impl From<Erc721Error> for Vec<u8> {
+    fn from(e: Erc721Error) -> Vec<u8> {
+        match e {
+            Erc721Error::Erc721InvalidOwner(e) => e.encode(),
+            Erc721Error::Erc721NonexistentToken(e) => e.encode(),
+            Erc721Error::Erc721IncorrectOwner(e) => e.encode(),
+            Erc721Error::Erc721InvalidSender(e) => e.encode(),
+            Erc721Error::Erc721InvalidReceiver(e) => e.encode(),
+            Erc721Error::Erc721InsufficientApproval(e) => e.encode(),
+            Erc721Error::Erc721InvalidApprover(e) => e.encode(),
+            Erc721Error::Erc721InvalidOperator(e) => e.encode(),
+        }
+    }
+}
+
+crate::errors::error_helpers!(Erc721Error {
+    Erc721InvalidOwner,
+    Erc721NonexistentToken,
+    Erc721IncorrectOwner,
+    Erc721InvalidSender,
+    Erc721InvalidReceiver,
+    Erc721InsufficientApproval,
+    Erc721InvalidApprover,
+    Erc721InvalidOperator,
+});
+
+/// Methods not exposed to other contracts, public so the composing collection (and extensions)
+/// can call them, same as for `Erc20`.
+impl<T: Erc721Params> Erc721<T> {
+    /// Mints `token_id` and transfers it to `to`, the token must not exist.
+    ///
+    /// Emits a {Transfer} event with `from` set to the zero address.
+    pub fn mint(&mut self, to: Address, token_id: U256) -> Result<(), Erc721Error> {
+        if to == Address::ZERO {
+            return Err(Erc721Error::Erc721InvalidReceiver(Erc721InvalidReceiver {
+                receiver: Address::ZERO,
+            }));
+        }
+        let previous_owner = self.update(to, token_id, Address::ZERO)?;
+        if previous_owner != Address::ZERO {
+            return Err(Erc721Error::Erc721InvalidSender(Erc721InvalidSender {
+                sender: Address::ZERO,
+            }));
+        }
+        Ok(())
+    }
+
+    /// Same as {mint} but checks that a contract receiver accepts the token.
+    ///
+    /// Emits a {Transfer} event with `from` set to the zero address.
+    pub fn safe_mint(&mut self, to: Address, token_id: U256, data: &[u8]) -> Result<(), Vec<u8>> {
+        self.mint(to, token_id)?;
+        check_on_erc721_received(msg::sender(), Address::ZERO, to, token_id, data)
+    }
+
+    /// Destroys `token_id`, the approval is cleared when the token is burned.
+    ///
+    /// Emits a {Transfer} event with `to` set to the zero address.
+    pub fn burn(&mut self, token_id: U256) -> Result<(), Erc721Error> {
+        let previous_owner = self.update(Address::ZERO, token_id, Address::ZERO)?;
+        if previous_owner == Address::ZERO {
+            return Err(Erc721Error::Erc721NonexistentToken(Erc721NonexistentToken {
+                token_id,
+            }));
+        }
+        Ok(())
+    }
+
+    /// Transfers `token_id` from its current owner to `to`, or alternatively mints (or burns) if the
+    /// current owner (or `to`) is the zero address. Returns the owner before the update.
+    ///
+    /// If `auth` is non zero, checks that it is the owner or approved to operate on the token.
+    ///
+    /// Emits a {Transfer} event.
+    pub fn update(&mut self, to: Address, token_id: U256, auth: Address) -> Result<Address, Erc721Error> {
+        let from = self.owners.get(token_id);
+
+        if auth != Address::ZERO {
+            self.check_authorized(from, auth, token_id)?;
+        }
+
+        if from != Address::ZERO {
+            // clear the approval, no need to emit an event
+            self.token_approvals.insert(token_id, Address::ZERO);
+            let mut from_balance_ref = self.balances.setter(from);
+            let from_balance = from_balance_ref.get();
+            from_balance_ref.set(from_balance - U256::from(1));
+        }
+        if to != Address::ZERO {
+            let mut to_balance_ref = self.balances.setter(to);
+            let to_balance = to_balance_ref.get();
+            to_balance_ref.set(to_balance + U256::from(1));
+        }
+        self.owners.insert(token_id, to);
+
+        evm::log(Transfer { from, to, token_id });
+        Ok(from)
+    }
+
+    /// Transfers `token_id` from `from` to `to` without checking the caller, `from` must own it.
+    ///
+    /// Emits a {Transfer} event.
+    pub fn transfer_internal(&mut self, from: Address, to: Address, token_id: U256) -> Result<(), Erc721Error> {
+        if to == Address::ZERO {
+            return Err(Erc721Error::Erc721InvalidReceiver(Erc721InvalidReceiver {
+                receiver: Address::ZERO,
+            }));
+        }
+        let previous_owner = self.update(to, token_id, Address::ZERO)?;
+        check_previous_owner(previous_owner, from, token_id)
+    }
+
+    /// Returns the owner of `token_id`, reverting if it does not exist.
+    pub fn require_owned(&self, token_id: U256) -> Result<Address, Erc721Error> {
+        let owner = self.owners.get(token_id);
+        if owner == Address::ZERO {
+            return Err(Erc721Error::Erc721NonexistentToken(Erc721NonexistentToken {
+                token_id,
+            }));
+        }
+        Ok(owner)
+    }
+
+    /// Whether `spender` can operate on `owner`'s `token_id`: it is the owner, is approved for
+    /// the token or is an operator of the owner. Does not check that `owner` owns the token.
+    pub fn is_authorized(&self, owner: Address, spender: Address, token_id: U256) -> bool {
+        spender != Address::ZERO
+            && (owner == spender
+                || self.operator_approvals.get(owner).get(spender)
+                || self.token_approvals.get(token_id) == spender)
+    }
+
+    /// Reverts unless `spender` is authorized for `owner`'s `token_id` (see {is_authorized}).
+    pub fn check_authorized(&self, owner: Address, spender: Address, token_id: U256) -> Result<(), Erc721Error> {
+        if self.is_authorized(owner, spender, token_id) {
+            return Ok(());
+        }
+        if owner == Address::ZERO {
+            return Err(Erc721Error::Erc721NonexistentToken(Erc721NonexistentToken {
+                token_id,
+            }));
+        }
+        Err(Erc721Error::Erc721InsufficientApproval(Erc721InsufficientApproval {
+            operator: spender,
+            token_id,
+        }))
+    }
+
+    /// Approves `to` to operate on `token_id`. If `auth` is non zero it must be the owner or an
+    /// operator of the owner.
+    ///
+    /// Emits an {Approval} event.
+    pub fn approve_internal(&mut self, to: Address, token_id: U256, auth: Address) -> Result<(), Erc721Error> {
+        let owner = self.require_owned(token_id)?;
+        if auth != Address::ZERO && owner != auth && !self.operator_approvals.get(owner).get(auth) {
+            return Err(Erc721Error::Erc721InvalidApprover(Erc721InvalidApprover {
+                approver: auth,
+            }));
+        }
+        self.token_approvals.insert(token_id, to);
+
+        evm::log(Approval {
+            owner,
+            approved: to,
+            token_id,
+        });
+        Ok(())
+    }
+
+    /// Approves or removes `operator` as an operator of all tokens of `owner`.
+    ///
+    /// Emits an {ApprovalForAll} event.
+    pub fn set_approval_for_all_internal(
+        &mut self,
+        owner: Address,
+        operator: Address,
+        approved: bool,
+    ) -> Result<(), Erc721Error> {
+        if operator == Address::ZERO {
+            return Err(Erc721Error::Erc721InvalidOperator(Erc721InvalidOperator {
+                operator,
+            }));
+        }
+        self.operator_approvals.setter(owner).insert(operator, approved);
+
+        evm::log(ApprovalForAll {
+            owner,
+            operator,
+            approved,
+        });
+        Ok(())
+    }
+
+    fn safe_transfer_internal(
+        &mut self,
+        from: Address,
+        to: Address,
+        token_id: U256,
+        data: &[u8],
+    ) -> Result<(), Vec<u8>> {
+        self.transfer_from_internal(from, to, token_id)?;
+        check_on_erc721_received(msg::sender(), from, to, token_id, data)
+    }
+
+    fn transfer_from_internal(&mut self, from: Address, to: Address, token_id: U256) -> Result<(), Erc721Error> {
+        if to == Address::ZERO {
+            return Err(Erc721Error::Erc721InvalidReceiver(Erc721InvalidReceiver {
+                receiver: Address::ZERO,
+            }));
+        }
+        let previous_owner = self.update(to, token_id, msg::sender())?;
+        check_previous_owner(previous_owner, from, token_id)
+    }
+}
+
+/// ERC-721 only, the metadata interface also requires `tokenURI` (see `Erc721Metadata`).
+impl<T: Erc721Params> Erc165 for Erc721<T> {
+    fn supports_interface(interface_id: FixedBytes<4>) -> bool {
+        erc165::supports(interface_id, &[erc165::IERC721])
+    }
+}
+
+impl<T: Erc721Params> AbiItems for Erc721<T> {
+    fn abi_items() -> Vec<AbiItem> {
+        Vec::from([
+            abi_export::event::<Transfer>(
+                "event Transfer(address indexed from, address indexed to, uint256 indexed token_id)",
+            ),
+            abi_export::event::<Approval>(
+                "event Approval(address indexed owner, address indexed approved, uint256 indexed token_id)",
+            ),
+            abi_export::event::<ApprovalForAll>(
+                "event ApprovalForAll(address indexed owner, address indexed operator, bool approved)",
+            ),
+            abi_export::error::<Erc721InvalidOwner>("error Erc721InvalidOwner(address owner)"),
+            abi_export::error::<Erc721NonexistentToken>("error Erc721NonexistentToken(uint256 token_id)"),
+            abi_export::error::<Erc721IncorrectOwner>(
+                "error Erc721IncorrectOwner(address sender, uint256 token_id, address owner)",
+            ),
+            abi_export::error::<Erc721InvalidSender>("error Erc721InvalidSender(address sender)"),
+            abi_export::error::<Erc721InvalidReceiver>("error Erc721InvalidReceiver(address receiver)"),
+            abi_export::error::<Erc721InsufficientApproval>(
+                "error Erc721InsufficientApproval(address operator, uint256 token_id)",
+            ),
+            abi_export::error::<Erc721InvalidApprover>("error Erc721InvalidApprover(address approver)"),
+            abi_export::error::<Erc721InvalidOperator>("error Erc721InvalidOperator(address operator)"),
+        ])
+    }
+}
+
+/// Solidity names of the externals are pinned with `#[selector(name = ...)]`, as for `Erc20`.
+#[external]
+impl<T: Erc721Params> Erc721<T> {
+    /// Returns whether the contract implements `interface_id`, see `utils::erc165`.
+    #[selector(name = "supportsInterface")]
+    pub fn supports_interface(interface_id: FixedBytes<4>) -> Result<bool, Erc721Error> {
+        Ok(<Self as Erc165>::supports_interface(interface_id))
+    }
+
+    #[selector(name = "name")]
+    pub fn name() -> Result<String, Erc721Error> {
+        Ok(T::NAME.into())
+    }
+
+    #[selector(name = "symbol")]
+    pub fn symbol() -> Result<String, Erc721Error> {
+        Ok(T::SYMBOL.into())
+    }
+
+    #[selector(name = "balanceOf")]
+    pub fn balance_of(&self, owner: Address) -> Result<U256, Erc721Error> {
+        if owner == Address::ZERO {
+            return Err(Erc721Error::Erc721InvalidOwner(Erc721InvalidOwner {
+                owner: Address::ZERO,
+            }));
+        }
+        Ok(self.balances.get(owner))
+    }
+
+    #[selector(name = "ownerOf")]
+    pub fn owner_of(&self, token_id: U256) -> Result<Address, Erc721Error> {
+        self.require_owned(token_id)
+    }
+
+    #[selector(name = "getApproved")]
+    pub fn get_approved(&self, token_id: U256) -> Result<Address, Erc721Error> {
+        self.require_owned(token_id)?;
+        Ok(self.token_approvals.get(token_id))
+    }
+
+    #[selector(name = "isApprovedForAll")]
+    pub fn is_approved_for_all(&self, owner: Address, operator: Address) -> Result<bool, Erc721Error> {
+        Ok(self.operator_approvals.get(owner).get(operator))
+    }
+
+    /// Whether `spender` can transfer `token_id`: it is the owner, approved for the token or an
+    /// operator of the owner. Reverts if the token does not exist.
+    #[selector(name = "isApprovedOrOwner")]
+    pub fn is_approved_or_owner(&self, spender: Address, token_id: U256) -> Result<bool, Erc721Error> {
+        let owner = self.require_owned(token_id)?;
+        Ok(self.is_authorized(owner, spender, token_id))
+    }
+
+    /// Owners of `token_ids` in one call, for marketplace indexers. Non existent tokens are
+    /// reported as the zero address instead of reverting the whole batch.
+    #[selector(name = "ownersOf")]
+    pub fn owners_of(&self, token_ids: Vec<U256>) -> Result<Vec<Address>, Erc721Error> {
+        Ok(token_ids.iter().map(|token_id| self.owners.get(*token_id)).collect())
+    }
+
+    /// Approved accounts of `token_ids` in one call, for marketplace indexers. Non existent (and
+    /// not approved) tokens are reported as the zero address instead of reverting the whole batch.
+    #[selector(name = "approvalsOf")]
+    pub fn approvals_of(&self, token_ids: Vec<U256>) -> Result<Vec<Address>, Erc721Error> {
+        Ok(token_ids
+            .iter()
+            .map(|token_id| self.token_approvals.get(*token_id))
+            .collect())
+    }
+
+    /// Gives permission to `to` to transfer `token_id`, the caller must own the token or be an
+    /// operator of its owner. The zero address clears the approval.
+    ///
+    /// Emits an {Approval} event.
+    #[selector(name = "approve")]
+    pub fn approve(&mut self, to: Address, token_id: U256) -> Result<(), Erc721Error> {
+        self.approve_internal(to, token_id, msg::sender())
+    }
+
+    /// Approves or removes `operator` as an operator of all tokens of the caller.
+    ///
+    /// Emits an {ApprovalForAll} event.
+    #[selector(name = "setApprovalForAll")]
+    pub fn set_approval_for_all(&mut self, operator: Address, approved: bool) -> Result<(), Erc721Error> {
+        self.set_approval_for_all_internal(msg::sender(), operator, approved)
+    }
+
+    /// Transfers `token_id` from `from` to `to`, the caller must be authorized for the token.
+    ///
+    /// WARNING: the receiver is not checked, tokens sent to a contract not handling them are
+    /// locked, prefer {safe_transfer_from}.
+    ///
+    /// Emits a {Transfer} event.
+    #[selector(name = "transferFrom")]
+    pub fn transfer_from(&mut self, from: Address, to: Address, token_id: U256) -> Result<(), Erc721Error> {
+        self.transfer_from_internal(from, to, token_id)
+    }
+
+    /// Same as {transfer_from} but, if `to` is a contract, it must implement `onERC721Received`.
+    ///
+    /// Emits a {Transfer} event.
+    #[selector(name = "safeTransferFrom")]
+    pub fn safe_transfer_from(&mut self, from: Address, to: Address, token_id: U256) -> Result<(), Vec<u8>> {
+        self.safe_transfer_internal(from, to, token_id, &[])
+    }
+
+    /// Same as {safe_transfer_from} with `data` forwarded to `onERC721Received`.
+    ///
+    /// Emits a {Transfer} event.
+    #[selector(name = "safeTransferFrom")]
+    pub fn safe_transfer_from_with_data(
+        &mut self,
+        from: Address,
+        to: Address,
+        token_id: U256,
+        data: Bytes,
+    ) -> Result<(), Vec<u8>> {
+        self.safe_transfer_internal(from, to, token_id, &data)
+    }
+}
+
+fn check_previous_owner(previous_owner: Address, from: Address, token_id: U256) -> Result<(), Erc721Error> {
+    if previous_owner == Address::ZERO {
+        return Err(Erc721Error::Erc721NonexistentToken(Erc721NonexistentToken {
+            token_id,
+        }));
+    }
+    if previous_owner != from {
+        return Err(Erc721Error::Erc721IncorrectOwner(Erc721IncorrectOwner {
+            sender: from,
+            token_id,
+            owner: previous_owner,
+        }));
+    }
+    Ok(())
+}
@@ -1,2 +1,4 @@
 pub mod erc20;
+pub mod erc721;
+pub mod erc1155;
 pub mod my_token;
\ No newline at end of file
@@ -1,10 +1,12 @@
 
+use alloc::vec::Vec;
 use stylus_sdk::{
     alloy_primitives::{Address, U256},
     prelude::*,
 };
 
-use super::erc20::{Erc20, Erc20Params, Erc20Error};
+use super::erc20::{Erc20, Erc20Params, Erc20Error, NoTransferHook};
+use crate::utils::abi_export::{AbiItem, AbiItems};
 
 pub struct MyTokenParams;
 
@@ -12,6 +14,7 @@ impl Erc20Params for MyTokenParams {
     const NAME: &'static str = "My test erc20 token";
     const SYMBOL: &'static str = "MT";
     const DECIMALS: u8 = 18;
+    type Hook = NoTransferHook;
 }
 
 sol_storage! {
@@ -26,14 +29,23 @@ sol_storage! {
 #[inherit(Erc20<MyTokenParams>)]
 impl MyToken {
 
-    // for testing purposes, anyone can mint
+    // for testing purposes, anyone can mint; real tokens gate it, e.g. with
+    // `extensions::erc20_mintable::Erc20Mintable` as in `examples/compose_access_control.rs`
+    #[selector(name = "mint")]
     pub fn mint(&mut self, account: Address, amount: U256) -> Result<(), Erc20Error> {
         self.erc20.mint(account, amount)
     }
 
     // for testing purposes, anyone can burn
+    #[selector(name = "burn")]
     pub fn burn(&mut self, account: Address, amount: U256) -> Result<(), Erc20Error> {
         self.erc20.burn(account, amount)
     }
 
 }
+
+impl AbiItems for MyToken {
+    fn abi_items() -> Vec<AbiItem> {
+        Erc20::<MyTokenParams>::abi_items()
+    }
+}
@@ -0,0 +1,84 @@
+//! Events and errors of a contract for ABI export. `export-abi` only prints the functions of
+//! the entrypoint, the events and custom errors declared with `sol!` are listed by implementing
+//! `AbiItems` next to the `sol!` block, as human readable declarations (the format of ethers
+//! `abigen!` and viem `parseAbi`) tied to the `sol!` type they describe.
+//!
+//! `check_abi_items` compares every declaration with the signature of its type, so a changed
+//! `sol!` event or error can not leave a stale declaration behind; the `abi_json` binary turns
+//! the functions and items of the entrypoint into a JSON ABI.
+use alloc::{format, string::String, vec::Vec};
+use stylus_sdk::alloy_sol_types::{SolError, SolEvent};
+
+/// Human readable declaration of an event or error, e.g.
+/// `event Transfer(address indexed from, address indexed to, uint256 value)`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AbiItem {
+    /// declaration with parameter names and `indexed` flags
+    pub declaration: &'static str,
+    /// canonical signature of the `sol!` type, e.g. `Transfer(address,address,uint256)`
+    pub signature: &'static str,
+}
+
+/// Declaration of the event `E`.
+pub fn event<E: SolEvent>(declaration: &'static str) -> AbiItem {
+    AbiItem {
+        declaration,
+        signature: E::SIGNATURE,
+    }
+}
+
+/// Declaration of the custom error `E`.
+pub fn error<E: SolError>(declaration: &'static str) -> AbiItem {
+    AbiItem {
+        declaration,
+        signature: E::SIGNATURE,
+    }
+}
+
+/// Events and custom errors a contract emits and reverts with, composed contracts chain the
+/// items of their components.
+pub trait AbiItems {
+    fn abi_items() -> Vec<AbiItem>;
+}
+
+/// `Name(type1,type2)` of a human readable declaration, without keyword, names, `indexed` or
+/// data locations.
+pub fn canonical_signature(declaration: &str) -> String {
+    let declaration = declaration.trim().trim_end_matches(';');
+    let declaration = declaration.split_once(' ').map_or(declaration, |(_, rest)| rest).trim();
+    let (name, rest) = declaration.split_once('(').unwrap_or((declaration, ")"));
+    let params = &rest[..rest.rfind(')').unwrap_or(0)];
+    let types: Vec<&str> = params
+        .split(',')
+        .filter_map(|param| param.split_whitespace().next())
+        .collect();
+    format!("{}({})", name.trim(), types.join(","))
+}
+
+/// Checks that every declaration matches the signature of its `sol!` type, returns the first
+/// mismatch.
+pub fn check_abi_items(items: &[AbiItem]) -> Result<(), String> {
+    for item in items {
+        let actual = canonical_signature(item.declaration);
+        if actual != item.signature {
+            return Err(format!(
+                "`{}` declares {} instead of {}",
+                item.declaration, actual, item.signature
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Human readable ABI of a contract: the functions of the Solidity interface printed by
+/// `export-abi` followed by the declarations of `items`, one per line.
+pub fn human_readable_abi(interface: &str, items: &[AbiItem]) -> Vec<String> {
+    let functions = interface
+        .lines()
+        .map(str::trim)
+        .filter(|line| line.starts_with("function "))
+        .map(|line| String::from(line.trim_end_matches(';')));
+    let mut abi: Vec<String> = functions.collect();
+    abi.extend(items.iter().map(|item| String::from(item.declaration)));
+    abi
+}
@@ -0,0 +1,87 @@
+use alloc::{string::String, vec::Vec};
+use stylus_sdk::{
+    alloy_primitives::{Address, B256, U256},
+    crypto::keccak,
+};
+
+/// Offset added by Arbitrum to the address of an L1 contract sending a retryable/message to L2.
+pub const L1_TO_L2_ALIAS_OFFSET: Address = Address::new([
+    0x11, 0x11, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x11, 0x11,
+]);
+
+/// Returns the L2 sender seen for calls made by the L1 contract `l1_address`:
+/// `l1_address + 0x1111000000000000000000000000000000001111`, wrapping at 2^160.
+pub fn apply_l1_to_l2_alias(l1_address: Address) -> Address {
+    from_u160(to_u160(l1_address).wrapping_add(to_u160(L1_TO_L2_ALIAS_OFFSET)))
+}
+
+/// Inverse of {apply_l1_to_l2_alias}, recovers the L1 contract from its aliased L2 sender.
+pub fn undo_l1_to_l2_alias(l2_address: Address) -> Address {
+    from_u160(to_u160(l2_address).wrapping_sub(to_u160(L1_TO_L2_ALIAS_OFFSET)))
+}
+
+/// Address of the contract created with CREATE by `deployer` at `nonce`:
+/// `keccak256(rlp([deployer, nonce]))[12..]`.
+pub fn create_address(deployer: Address, nonce: u64) -> Address {
+    let nonce_bytes = nonce.to_be_bytes();
+    let significant = &nonce_bytes[nonce.leading_zeros() as usize / 8..];
+
+    let mut payload = Vec::with_capacity(30);
+    payload.push(0x80 + 20);
+    payload.extend_from_slice(deployer.as_slice());
+    match significant {
+        [] => payload.push(0x80),
+        [byte] if *byte < 0x80 => payload.push(*byte),
+        bytes => {
+            payload.push(0x80 + bytes.len() as u8);
+            payload.extend_from_slice(bytes);
+        }
+    }
+
+    let mut encoded = Vec::with_capacity(31);
+    encoded.push(0xc0 + payload.len() as u8);
+    encoded.extend_from_slice(&payload);
+    hash_to_address(keccak(encoded))
+}
+
+/// Address of the contract created with CREATE2 by `deployer`:
+/// `keccak256(0xff ‖ deployer ‖ salt ‖ init_code_hash)[12..]`.
+pub fn create2_address(deployer: Address, salt: B256, init_code_hash: B256) -> Address {
+    let mut buf = Vec::with_capacity(85);
+    buf.push(0xff);
+    buf.extend_from_slice(deployer.as_slice());
+    buf.extend_from_slice(salt.as_slice());
+    buf.extend_from_slice(init_code_hash.as_slice());
+    hash_to_address(keccak(buf))
+}
+
+/// EIP-55 mixed-case checksum encoding of `address`, `0x` prefixed.
+pub fn to_checksum(address: Address) -> String {
+    let lower = hex::encode(address.as_slice());
+    let hash = keccak(lower.as_bytes());
+
+    let mut checksummed = String::with_capacity(42);
+    checksummed.push_str("0x");
+    for (i, c) in lower.chars().enumerate() {
+        let nibble = (hash[i / 2] >> (if i % 2 == 0 { 4 } else { 0 })) & 0x0f;
+        if c.is_ascii_alphabetic() && nibble >= 8 {
+            checksummed.push(c.to_ascii_uppercase());
+        } else {
+            checksummed.push(c);
+        }
+    }
+    checksummed
+}
+
+fn hash_to_address(hash: B256) -> Address {
+    Address::from_slice(&hash[12..])
+}
+
+fn to_u160(address: Address) -> U256 {
+    U256::from_be_slice(address.as_slice())
+}
+
+/// Keeps the low 160 bits, which is where wrapping arithmetic on addresses ends.
+fn from_u160(value: U256) -> Address {
+    Address::from_slice(&value.to_be_bytes::<32>()[12..])
+}
@@ -0,0 +1,76 @@
+//! Sets of `uint256` indexes packed 256 to a storage slot, port of OpenZeppelin `BitMaps`.
+//!
+//! A `mapping(uint256 => bool)` takes a slot per index: marking 256 consecutive indexes (claimed
+//! airdrop entries, used unordered nonces) costs 256 fresh slot writes. A `Bitmap` keeps index `i`
+//! as bit `i % 256` of the word at key `i / 256`, only the first index of each word pays for a
+//! fresh slot and neighbouring indexes are read from a warm one.
+use stylus_sdk::{alloy_primitives::U256, prelude::*};
+
+sol_storage! {
+    /// Boolean per `uint256` index, all false until set. Same layout as OpenZeppelin
+    /// `BitMaps.BitMap`, a `mapping(uint256 => uint256)` of 256 bit words.
+    pub struct Bitmap {
+        mapping(uint256 => uint256) data;
+    }
+}
+
+impl Bitmap {
+    /// Whether the bit at `index` is set.
+    pub fn get(&self, index: U256) -> bool {
+        is_set_in(self.word(index), index)
+    }
+
+    /// Sets the bit at `index` to `value`.
+    pub fn set_to(&mut self, index: U256, value: bool) {
+        let mut word = self.data.setter(index >> 8);
+        let bits = word.get();
+        word.set(with_bit(bits, index, value));
+    }
+
+    /// Sets the bit at `index`.
+    pub fn set(&mut self, index: U256) {
+        self.set_to(index, true);
+    }
+
+    /// Clears the bit at `index`.
+    pub fn unset(&mut self, index: U256) {
+        self.set_to(index, false);
+    }
+
+    /// Word of 256 bits holding `index`, e.g. to check many neighbouring indexes with one read.
+    pub fn word(&self, index: U256) -> U256 {
+        self.word_at(index >> 8)
+    }
+
+    /// Word at key `bucket`, the bits of indexes `bucket * 256 .. bucket * 256 + 255`.
+    pub fn word_at(&self, bucket: U256) -> U256 {
+        self.data.get(bucket)
+    }
+
+    /// Sets the bits of `mask` in the word at key `bucket`, many indexes in one write.
+    pub fn set_word_bits(&mut self, bucket: U256, mask: U256) {
+        let mut word = self.data.setter(bucket);
+        let bits = word.get();
+        word.set(bits | mask);
+    }
+}
+
+/// Mapping key of the word holding `index` and the mask of its bit in the word.
+pub fn bucket_and_mask(index: U256) -> (U256, U256) {
+    (index >> 8, U256::from(1) << (index.as_limbs()[0] & 0xff))
+}
+
+/// Whether the bit of `index` is set in `word`, the word of its bucket.
+pub fn is_set_in(word: U256, index: U256) -> bool {
+    word & bucket_and_mask(index).1 != U256::ZERO
+}
+
+/// `word`, the word of the bucket of `index`, with the bit of `index` set to `value`.
+pub fn with_bit(word: U256, index: U256, value: bool) -> U256 {
+    let (_, mask) = bucket_and_mask(index);
+    if value {
+        word | mask
+    } else {
+        word & !mask
+    }
+}
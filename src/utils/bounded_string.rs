@@ -0,0 +1,91 @@
+use alloc::{string::String, vec::Vec};
+use stylus_sdk::{
+    alloy_primitives::U256,
+    alloy_sol_types::{sol, SolError},
+    storage::{StorageGuard, StorageGuardMut, StorageString, StorageType},
+};
+
+sol! {
+    /// Indicates a string longer than the bound of its storage.
+    /// * `length` - length of the rejected string in bytes.
+    /// * `max_length` - bound of the storage in bytes.
+    error BoundedStringTooLong(uint256 length, uint256 max_length);
+}
+
+pub enum BoundedStringError {
+    BoundedStringTooLong(BoundedStringTooLong),
+}
+
+impl From<BoundedStringError> for Vec<u8> {
+    fn from(e: BoundedStringError) -> Vec<u8> {
+        match e {
+            BoundedStringError::BoundedStringTooLong(e) => e.encode(),
+        }
+    }
+}
+
+crate::errors::error_helpers!(BoundedStringError {
+    BoundedStringTooLong,
+});
+
+/// Storage string of at most `N` bytes, for names, symbols and URIs set at runtime. Writes longer
+/// than `N` revert instead of spending ink on unbounded slot writes, and strings of at most 31
+/// bytes are stored in a single slot, a single read.
+///
+/// Declared in `sol_storage!` as a Rust type, e.g. `BoundedString<256> uri;`.
+pub struct BoundedString<const N: usize> {
+    inner: StorageString,
+}
+
+impl<const N: usize> StorageType for BoundedString<N> {
+    type Wrap<'a> = StorageGuard<'a, Self> where Self: 'a;
+    type WrapMut<'a> = StorageGuardMut<'a, Self> where Self: 'a;
+
+    unsafe fn new(slot: U256, offset: u8) -> Self {
+        Self {
+            inner: StorageString::new(slot, offset),
+        }
+    }
+
+    fn load<'s>(self) -> Self::Wrap<'s> {
+        StorageGuard::new(self)
+    }
+
+    fn load_mut<'s>(self) -> Self::WrapMut<'s> {
+        StorageGuardMut::new(self)
+    }
+}
+
+impl<const N: usize> BoundedString<N> {
+    /// Max length in bytes.
+    pub const MAX_LENGTH: usize = N;
+
+    pub fn get_string(&self) -> String {
+        self.inner.get_string()
+    }
+
+    /// Stores `value`, reverting if it is longer than `N` bytes.
+    pub fn set_str(&mut self, value: &str) -> Result<(), BoundedStringError> {
+        if value.len() > N {
+            return Err(BoundedStringError::BoundedStringTooLong(BoundedStringTooLong {
+                length: U256::from(value.len()),
+                max_length: U256::from(N),
+            }));
+        }
+        self.inner.set_str(value);
+        Ok(())
+    }
+
+    /// Length in bytes, read from the first slot only.
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    pub fn erase(&mut self) {
+        self.inner.erase();
+    }
+}
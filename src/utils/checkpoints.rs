@@ -0,0 +1,186 @@
+//! History of a value keyed by increasing timepoints (block numbers or timestamps), port of
+//! OpenZeppelin `Checkpoints.Trace208`: `push` records the value at a timepoint, lookups binary
+//! search the checkpoints for the value at a past timepoint.
+//!
+//! Values are full `uint256` words in their own mapping instead of OpenZeppelin's packed
+//! `uint48 key, uint208 value` slot, the layout `Erc20Votes` used before sharing this type.
+//! `ClockMode` is the ERC-6372 clock giving the timepoints.
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::U256,
+    alloy_sol_types::{sol, SolError},
+    block,
+    prelude::*,
+};
+
+/// ERC-6372 clock of checkpointed values, reported by `clock()` and `CLOCK_MODE()` so governance
+/// tooling knows what timepoints mean.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ClockMode {
+    /// block numbers, the default of OpenZeppelin `Votes`
+    BlockNumber,
+    /// block timestamps, for chains whose block times are irregular
+    Timestamp,
+}
+
+impl ClockMode {
+    /// Current timepoint.
+    pub fn now(self) -> u64 {
+        match self {
+            ClockMode::BlockNumber => block::number(),
+            ClockMode::Timestamp => block::timestamp(),
+        }
+    }
+
+    /// Machine readable description returned by `CLOCK_MODE()`.
+    pub fn description(self) -> &'static str {
+        match self {
+            ClockMode::BlockNumber => "mode=blocknumber&from=default",
+            ClockMode::Timestamp => "mode=timestamp",
+        }
+    }
+
+    /// Mode described by a `CLOCK_MODE()` string, `None` for other clocks.
+    pub fn from_description(description: &str) -> Option<ClockMode> {
+        [ClockMode::BlockNumber, ClockMode::Timestamp]
+            .into_iter()
+            .find(|mode| mode.description() == description)
+    }
+}
+
+sol_storage! {
+    /// Checkpoints with strictly increasing keys, at most one per key: pushing again at the
+    /// last key overwrites its value.
+    pub struct Trace {
+        uint256 length;
+        mapping(uint256 => uint256) keys;
+        mapping(uint256 => uint256) values;
+    }
+}
+
+sol! {
+    /// Indicates a push at a key lower than the key of the last checkpoint.
+    error CheckpointUnorderedInsertion();
+}
+
+pub enum CheckpointsError {
+    CheckpointUnorderedInsertion(CheckpointUnorderedInsertion),
+}
+
+impl From<CheckpointsError> for Vec<u8> {
+    fn from(e: CheckpointsError) -> Vec<u8> {
+        match e {
+            CheckpointsError::CheckpointUnorderedInsertion(e) => e.encode(),
+        }
+    }
+}
+
+crate::errors::error_helpers!(CheckpointsError {
+    CheckpointUnorderedInsertion,
+});
+
+impl Trace {
+    /// Records `value` at `key`, overwriting the last checkpoint if it has the same key.
+    /// Returns `(previous value, new value)`.
+    pub fn push(&mut self, key: U256, value: U256) -> Result<(U256, U256), CheckpointsError> {
+        let length = self.length.get();
+        if length != U256::ZERO {
+            let last = length - U256::from(1);
+            let last_key = self.keys.get(last);
+            if key < last_key {
+                return Err(CheckpointsError::CheckpointUnorderedInsertion(
+                    CheckpointUnorderedInsertion {},
+                ));
+            }
+            let previous = self.values.get(last);
+            if key == last_key {
+                self.values.insert(last, value);
+                return Ok((previous, value));
+            }
+            self.push_new(length, key, value);
+            return Ok((previous, value));
+        }
+        self.push_new(length, key, value);
+        Ok((U256::ZERO, value))
+    }
+
+    /// Value of the last checkpoint, 0 if there is none.
+    pub fn latest(&self) -> U256 {
+        self.latest_checkpoint().map_or(U256::ZERO, |(_, value)| value)
+    }
+
+    /// `(key, value)` of the last checkpoint.
+    pub fn latest_checkpoint(&self) -> Option<(U256, U256)> {
+        let length = self.length.get();
+        if length == U256::ZERO {
+            return None;
+        }
+        self.at(length - U256::from(1))
+    }
+
+    /// `(key, value)` of the checkpoint at `position`.
+    pub fn at(&self, position: U256) -> Option<(U256, U256)> {
+        if position >= self.length.get() {
+            return None;
+        }
+        Some((self.keys.get(position), self.values.get(position)))
+    }
+
+    pub fn length(&self) -> U256 {
+        self.length.get()
+    }
+
+    /// Value of the last checkpoint with a key lower or equal to `key`, 0 if there is none.
+    pub fn upper_lookup(&self, key: U256) -> U256 {
+        let position = upper_bound(self.length.get(), key, |position| self.keys.get(position));
+        if position == U256::ZERO {
+            return U256::ZERO;
+        }
+        self.values.get(position - U256::from(1))
+    }
+
+    /// Value of the first checkpoint with a key greater or equal to `key`, 0 if there is none.
+    pub fn lower_lookup(&self, key: U256) -> U256 {
+        let length = self.length.get();
+        let position = lower_bound(length, key, |position| self.keys.get(position));
+        if position == length {
+            return U256::ZERO;
+        }
+        self.values.get(position)
+    }
+
+    fn push_new(&mut self, length: U256, key: U256, value: U256) {
+        self.keys.insert(length, key);
+        self.values.insert(length, value);
+        self.length.set(length + U256::from(1));
+    }
+}
+
+/// Position of the first of `length` sorted keys strictly greater than `key`, `length` if none.
+/// `key_at` reads the key at a position, one storage read per step of the binary search.
+pub fn upper_bound(length: U256, key: U256, key_at: impl Fn(U256) -> U256) -> U256 {
+    let (mut low, mut high) = (U256::ZERO, length);
+    while low < high {
+        let mid = (low + high) >> 1;
+        if key_at(mid) > key {
+            high = mid;
+        } else {
+            low = mid + U256::from(1);
+        }
+    }
+    high
+}
+
+/// Position of the first of `length` sorted keys greater or equal to `key`, `length` if none.
+pub fn lower_bound(length: U256, key: U256, key_at: impl Fn(U256) -> U256) -> U256 {
+    let (mut low, mut high) = (U256::ZERO, length);
+    while low < high {
+        let mid = (low + high) >> 1;
+        if key_at(mid) < key {
+            low = mid + U256::from(1);
+        } else {
+            high = mid;
+        }
+    }
+    high
+}
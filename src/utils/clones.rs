@@ -0,0 +1,47 @@
+//! EIP-1167 minimal proxies, port of OpenZeppelin `Clones`: a 45 bytes EVM contract
+//! delegating every call to an implementation, so many instances share one deployed program
+//! and only pay for their own storage. Instances are initialized by a call after the
+//! deployment (there is no constructor), the implementation must keep its state in storage
+//! (e.g. `Erc20MetadataStorage` instead of `Erc20Params` constants).
+//! See `examples/token_factory.rs` for a factory of ERC20 clones.
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{Address, B256, U256},
+    crypto::keccak,
+};
+
+use crate::utils::create2::{self, Create2Error};
+
+/// Init code before the implementation address: copies the 45 bytes runtime and returns it.
+const CLONE_INIT_PREFIX: [u8; 20] = [
+    0x3d, 0x60, 0x2d, 0x80, 0x60, 0x0a, 0x3d, 0x39, 0x81, 0xf3, // init
+    0x36, 0x3d, 0x3d, 0x37, 0x3d, 0x3d, 0x3d, 0x36, 0x3d, 0x73, // runtime up to PUSH20
+];
+/// Runtime after the implementation address: DELEGATECALL and bubble the result.
+const CLONE_RUNTIME_SUFFIX: [u8; 15] = [
+    0x5a, 0xf4, 0x3d, 0x82, 0x80, 0x3e, 0x90, 0x3d, 0x91, 0x60, 0x2b, 0x57, 0xfd, 0x5b, 0xf3,
+];
+
+/// Init code of a minimal proxy of `implementation`.
+pub fn clone_init_code(implementation: Address) -> Vec<u8> {
+    let mut code = Vec::with_capacity(55);
+    code.extend_from_slice(&CLONE_INIT_PREFIX);
+    code.extend_from_slice(implementation.as_slice());
+    code.extend_from_slice(&CLONE_RUNTIME_SUFFIX);
+    code
+}
+
+/// Runtime code of a minimal proxy of `implementation`, what `EXTCODECOPY` returns for it.
+pub fn clone_runtime_code(implementation: Address) -> Vec<u8> {
+    clone_init_code(implementation).split_off(10)
+}
+
+/// Deploys a minimal proxy of `implementation` with CREATE2 and `salt`.
+pub fn clone_deterministic(implementation: Address, salt: B256) -> Result<Address, Create2Error> {
+    create2::deploy(U256::ZERO, salt, &clone_init_code(implementation))
+}
+
+/// Address `clone_deterministic` deploys to from `deployer`.
+pub fn predict_deterministic_address(implementation: Address, salt: B256, deployer: Address) -> Address {
+    create2::compute_address_from(salt, keccak(clone_init_code(implementation)), deployer)
+}
@@ -0,0 +1,97 @@
+//! Deterministic deployments with CREATE2, port of OpenZeppelin `Create2`.
+//!
+//! `deploy` creates a contract from its init code with the deploy hostio, at the address
+//! `compute_address` (or `addresses::create2_address`) predicts from the deployer, the salt and
+//! the init code hash, the same address as a Solidity factory would get. A Stylus program is
+//! deployed from the EVM init code returning its compressed WASM (what `cargo stylus deploy`
+//! sends), it must then be activated before it can be called unless the same code is already
+//! activated on the chain. See `examples/create2_factory.rs` for a factory.
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{Address, B256, U256},
+    alloy_sol_types::{sol, SolError},
+    contract,
+    crypto::keccak,
+    deploy::RawDeploy,
+};
+
+use crate::utils::addresses::create2_address;
+
+sol! {
+    /// Indicates a deployment with empty init code.
+    error Create2EmptyBytecode();
+
+    /// Indicates a deployment sending more value than the deployer balance.
+    error Create2InsufficientBalance(uint256 balance, uint256 needed);
+
+    /// Indicates a deployment failing without revert data, e.g. an address already used.
+    error FailedDeployment();
+}
+
+pub enum Create2Error {
+    Create2EmptyBytecode(Create2EmptyBytecode),
+    Create2InsufficientBalance(Create2InsufficientBalance),
+    FailedDeployment(FailedDeployment),
+    /// revert data of the init code, passed through
+    Reverted(Vec<u8>),
+}
+
+impl From<Create2Error> for Vec<u8> {
+    fn from(e: Create2Error) -> Vec<u8> {
+        match e {
+            Create2Error::Create2EmptyBytecode(e) => e.encode(),
+            Create2Error::Create2InsufficientBalance(e) => e.encode(),
+            Create2Error::FailedDeployment(e) => e.encode(),
+            Create2Error::Reverted(data) => data,
+        }
+    }
+}
+
+crate::errors::error_helpers!(Create2Error {
+    Create2EmptyBytecode,
+    Create2InsufficientBalance,
+    FailedDeployment,
+} passthrough { Reverted });
+
+/// Deploys `init_code` with CREATE2 and `salt`, sending `value` from the program balance.
+/// Returns the address of the new contract, `compute_address(salt, keccak256(init_code))`.
+pub fn deploy(value: U256, salt: B256, init_code: &[u8]) -> Result<Address, Create2Error> {
+    if init_code.is_empty() {
+        return Err(Create2Error::Create2EmptyBytecode(Create2EmptyBytecode {}));
+    }
+    let balance = contract::balance();
+    if balance < value {
+        return Err(Create2Error::Create2InsufficientBalance(Create2InsufficientBalance {
+            balance,
+            needed: value,
+        }));
+    }
+    // SAFETY: the init code may call back into this program, callers deploy after their own
+    // storage writes, as for any external call.
+    let deployed = unsafe { RawDeploy::new().salt(salt).deploy(init_code, value) };
+    match deployed {
+        Ok(address) if address != Address::ZERO => Ok(address),
+        Ok(_) => Err(Create2Error::FailedDeployment(FailedDeployment {})),
+        Err(data) if data.is_empty() => Err(Create2Error::FailedDeployment(FailedDeployment {})),
+        Err(data) => Err(Create2Error::Reverted(data)),
+    }
+}
+
+/// Address `deploy` creates from this program for `salt` and `init_code_hash`.
+pub fn compute_address(salt: B256, init_code_hash: B256) -> Address {
+    compute_address_from(salt, init_code_hash, contract::address())
+}
+
+/// Address of a CREATE2 deployment of `deployer`, for factories other than this program.
+pub fn compute_address_from(salt: B256, init_code_hash: B256, deployer: Address) -> Address {
+    create2_address(deployer, salt, init_code_hash)
+}
+
+/// Salt bound to `account`: `keccak256(account ‖ salt)`. Factories deploying on behalf of
+/// anyone derive the salt from the caller so nobody can take the address of another caller.
+pub fn account_salt(account: Address, salt: B256) -> B256 {
+    let mut buf = Vec::with_capacity(52);
+    buf.extend_from_slice(account.as_slice());
+    buf.extend_from_slice(salt.as_slice());
+    keccak(buf)
+}
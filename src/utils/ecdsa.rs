@@ -0,0 +1,98 @@
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{address, Address, B256, U256},
+    alloy_sol_types::{sol, SolError},
+    call::{self, Call},
+};
+
+/// ecrecover precompile.
+const ECRECOVER: Address = address!("0000000000000000000000000000000000000001");
+
+/// Upper bound of `s` accepted in a signature (secp256k1n / 2), see EIP-2.
+pub const MAX_S: U256 = U256::from_limbs([
+    0xdfe92f46681b20a0,
+    0x5d576e7357a4501d,
+    0xffffffffffffffff,
+    0x7fffffffffffffff,
+]);
+
+sol! {
+    /// The signature derives the zero address.
+    error ECDSAInvalidSignature();
+
+    /// The signature has an invalid length.
+    /// * `length` - length of the given signature in bytes.
+    error ECDSAInvalidSignatureLength(uint256 length);
+
+    /// The signature has an `s` value in the upper half order.
+    /// * `s` - rejected `s` value.
+    error ECDSAInvalidSignatureS(bytes32 s);
+}
+
+pub enum EcdsaError {
+    ECDSAInvalidSignature(ECDSAInvalidSignature),
+    ECDSAInvalidSignatureLength(ECDSAInvalidSignatureLength),
+    ECDSAInvalidSignatureS(ECDSAInvalidSignatureS),
+}
+
+impl From<EcdsaError> for Vec<u8> {
+    fn from(e: EcdsaError) -> Vec<u8> {
+        match e {
+            EcdsaError::ECDSAInvalidSignature(e) => e.encode(),
+            EcdsaError::ECDSAInvalidSignatureLength(e) => e.encode(),
+            EcdsaError::ECDSAInvalidSignatureS(e) => e.encode(),
+        }
+    }
+}
+
+crate::errors::error_helpers!(EcdsaError {
+    ECDSAInvalidSignature,
+    ECDSAInvalidSignatureLength,
+    ECDSAInvalidSignatureS,
+});
+
+/// Recovers the signer of `hash` from a `(v, r, s)` signature using the ecrecover precompile.
+/// Rejects malleable (high `s`) signatures and signatures recovering to the zero address.
+pub fn recover(hash: B256, v: u8, r: B256, s: B256) -> Result<Address, EcdsaError> {
+    if U256::from_be_bytes(s.0) > MAX_S {
+        return Err(EcdsaError::ECDSAInvalidSignatureS(ECDSAInvalidSignatureS { s }));
+    }
+
+    let mut input = Vec::with_capacity(128);
+    input.extend_from_slice(hash.as_slice());
+    input.extend_from_slice(&[0u8; 31]);
+    input.push(v);
+    input.extend_from_slice(r.as_slice());
+    input.extend_from_slice(s.as_slice());
+
+    // the precompile returns no data for an invalid signature
+    let output = call::static_call(Call::new(), ECRECOVER, &input).unwrap_or_default();
+    signer_from_output(&output)
+}
+
+/// Decodes the signer returned by the ecrecover precompile, rejecting an empty output (invalid
+/// signature) and the zero address.
+pub fn signer_from_output(output: &[u8]) -> Result<Address, EcdsaError> {
+    if output.len() != 32 {
+        return Err(EcdsaError::ECDSAInvalidSignature(ECDSAInvalidSignature {}));
+    }
+    let signer = Address::from_slice(&output[12..]);
+    if signer == Address::ZERO {
+        return Err(EcdsaError::ECDSAInvalidSignature(ECDSAInvalidSignature {}));
+    }
+    Ok(signer)
+}
+
+/// Same as {recover} for a 65 bytes `r ‖ s ‖ v` signature.
+pub fn recover_bytes(hash: B256, signature: &[u8]) -> Result<Address, EcdsaError> {
+    if signature.len() != 65 {
+        return Err(EcdsaError::ECDSAInvalidSignatureLength(
+            ECDSAInvalidSignatureLength {
+                length: U256::from(signature.len()),
+            },
+        ));
+    }
+    let r = B256::from_slice(&signature[..32]);
+    let s = B256::from_slice(&signature[32..64]);
+    recover(hash, signature[64], r, s)
+}
@@ -0,0 +1,141 @@
+//! EIP-712 hashing and the ERC-5267 domain shared by the signature extensions.
+//!
+//! A typed signature is checked in three steps: `hash_struct(TYPE, &[fields...])` with every
+//! field ABI encoded as a word (dynamic types hashed first), the digest
+//! `to_typed_data_hash(domain_separator, struct_hash)`, then the signer with
+//! `recover_typed_signature` (EOA only) or `signature_checker` (EOA or EIP-1271 contract).
+//!
+//! Every extension verifying typed signatures of an `Erc20` (`erc20_approve_by_sig`,
+//! `erc20_signature_transfer`, `erc20_mint_authorization`, `delegateBySig` of `erc20_votes`)
+//! signs under the same domain: the token name, `DOMAIN_VERSION`, the current chain id and the
+//! token address. The chain id is read on every call, never cached, so signatures of a fork are
+//! rejected. The composed token exposes the domain once for wallets:
+//!
+//! ```ignore
+//! #[selector(name = "eip712Domain")]
+//! pub fn eip712_domain(&self) -> Result<Eip712Domain, Vec<u8>> {
+//!     Ok(eip712::erc20_domain::<P>())
+//! }
+//! ```
+use alloc::{string::String, vec::Vec};
+use stylus_sdk::{
+    alloy_primitives::{Address, FixedBytes, B256, U256},
+    block, contract,
+    crypto::keccak,
+};
+
+use crate::tokens::erc20::Erc20Params;
+use crate::tokens::erc721::Erc721Params;
+use crate::utils::ecdsa::{self, EcdsaError};
+
+/// `keccak256("EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)")` input.
+pub const DOMAIN_TYPE: &str =
+    "EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)";
+
+/// Version of the domain of the `Erc20` signature extensions.
+pub const DOMAIN_VERSION: &str = "1";
+
+/// ERC-5267 `fields` of `DOMAIN_TYPE`: name, version, chainId and verifyingContract, no salt.
+pub const DOMAIN_FIELDS: u8 = 0x0f;
+
+/// ERC-5267 `eip712Domain()` return values: `(fields, name, version, chainId,
+/// verifyingContract, salt, extensions)`.
+pub type Eip712Domain = (FixedBytes<1>, String, String, U256, Address, B256, Vec<U256>);
+
+/// Builds the EIP-712 domain separator.
+pub fn domain_separator(name: &str, version: &str, chain_id: u64, verifying_contract: Address) -> B256 {
+    hash_words(&[
+        keccak(DOMAIN_TYPE.as_bytes()),
+        keccak(name.as_bytes()),
+        keccak(version.as_bytes()),
+        u256_word(U256::from(chain_id)),
+        address_word(verifying_contract),
+    ])
+}
+
+/// ERC-5267 description of the domain hashed by {domain_separator} with the same arguments.
+pub fn domain(name: &str, version: &str, chain_id: u64, verifying_contract: Address) -> Eip712Domain {
+    (
+        FixedBytes([DOMAIN_FIELDS]),
+        String::from(name),
+        String::from(version),
+        U256::from(chain_id),
+        verifying_contract,
+        B256::ZERO,
+        Vec::new(),
+    )
+}
+
+/// Domain separator of the `Erc20` signature extensions of the current token.
+pub fn erc20_domain_separator<T: Erc20Params>() -> B256 {
+    domain_separator(T::NAME, DOMAIN_VERSION, block::chainid(), contract::address())
+}
+
+/// ERC-5267 domain of the `Erc20` signature extensions of the current token, for the
+/// `eip712Domain()` external of the composed token.
+pub fn erc20_domain<T: Erc20Params>() -> Eip712Domain {
+    domain(T::NAME, DOMAIN_VERSION, block::chainid(), contract::address())
+}
+
+/// Domain separator of the `Erc721` signature extensions (`erc721_permit`) of the current
+/// collection: same fields as the `Erc20` domain, with the collection name.
+pub fn erc721_domain_separator<T: Erc721Params>() -> B256 {
+    domain_separator(T::NAME, DOMAIN_VERSION, block::chainid(), contract::address())
+}
+
+/// ERC-5267 domain of the `Erc721` signature extensions of the current collection.
+pub fn erc721_domain<T: Erc721Params>() -> Eip712Domain {
+    domain(T::NAME, DOMAIN_VERSION, block::chainid(), contract::address())
+}
+
+/// Returns the digest to be signed for a struct: `keccak256("\x19\x01" ‖ domainSeparator ‖ structHash)`.
+pub fn to_typed_data_hash(domain_separator: B256, struct_hash: B256) -> B256 {
+    let mut buf = Vec::with_capacity(66);
+    buf.extend_from_slice(&[0x19, 0x01]);
+    buf.extend_from_slice(domain_separator.as_slice());
+    buf.extend_from_slice(struct_hash.as_slice());
+    keccak(buf)
+}
+
+/// `hashStruct(s) = keccak256(typeHash ‖ encodeData(s))` of a struct with the given type string
+/// (e.g. `"Delegation(address delegatee,uint256 nonce,uint256 expiry)"`) and encoded fields.
+pub fn hash_struct(type_string: &str, fields: &[B256]) -> B256 {
+    let mut words = Vec::with_capacity(fields.len() + 1);
+    words.push(keccak(type_string.as_bytes()));
+    words.extend_from_slice(fields);
+    hash_words(&words)
+}
+
+/// Recovers the EOA that signed `struct_hash` under `domain_separator` with the ecrecover
+/// precompile, rejecting malleable signatures and the zero address like {ecdsa::recover}.
+pub fn recover_typed_signature(
+    domain_separator: B256,
+    struct_hash: B256,
+    v: u8,
+    r: B256,
+    s: B256,
+) -> Result<Address, EcdsaError> {
+    ecdsa::recover(to_typed_data_hash(domain_separator, struct_hash), v, r, s)
+}
+
+/// Hashes the concatenation of ABI encoded static words, i.e. `keccak256(abi.encode(words...))`
+/// when every member is a 32 bytes value (uint, address, bytes32, hashed dynamic type).
+pub fn hash_words(words: &[B256]) -> B256 {
+    let mut buf = Vec::with_capacity(words.len() * 32);
+    for word in words {
+        buf.extend_from_slice(word.as_slice());
+    }
+    keccak(buf)
+}
+
+/// ABI encoding of an address, left padded to 32 bytes.
+pub fn address_word(address: Address) -> B256 {
+    let mut word = [0u8; 32];
+    word[12..].copy_from_slice(address.as_slice());
+    B256::from(word)
+}
+
+/// ABI encoding of an uint256.
+pub fn u256_word(value: U256) -> B256 {
+    B256::from(value.to_be_bytes::<32>())
+}
@@ -0,0 +1,92 @@
+//! ERC-165 interface detection. Token bases export `supportsInterface` for their own
+//! interfaces; an entrypoint composing extensions with interfaces of their own (`Erc2981`,
+//! `Erc721Rental`...) overrides it to combine the `Erc165` impls of its fields:
+//!
+//! ```ignore
+//! #[selector(name = "supportsInterface")]
+//! pub fn supports_interface(&self, interface_id: FixedBytes<4>) -> Result<bool, Vec<u8>> {
+//!     Ok(<Erc721<P> as Erc165>::supports_interface(interface_id)
+//!         || <Erc2981 as Erc165>::supports_interface(interface_id))
+//! }
+//! ```
+//!
+//! `supports_interface_of` asks another contract, as OpenZeppelin `ERC165Checker`.
+use stylus_sdk::{
+    alloy_primitives::{Address, FixedBytes, U256},
+    call::{self, Call},
+    types::AddressVM,
+};
+
+/// `type(IERC165).interfaceId`
+pub const IERC165: [u8; 4] = [0x01, 0xff, 0xc9, 0xa7];
+/// `type(IERC20).interfaceId`
+pub const IERC20: [u8; 4] = [0x36, 0x37, 0x2b, 0x07];
+/// `type(IERC20Metadata).interfaceId`, without the IERC20 functions
+pub const IERC20_METADATA: [u8; 4] = [0xa2, 0x19, 0xa0, 0x25];
+/// `type(IERC721).interfaceId`
+pub const IERC721: [u8; 4] = [0x80, 0xac, 0x58, 0xcd];
+/// `type(IERC721Metadata).interfaceId`, without the IERC721 functions
+pub const IERC721_METADATA: [u8; 4] = [0x5b, 0x5e, 0x13, 0x9f];
+/// `type(IERC721Enumerable).interfaceId`, without the IERC721 functions
+pub const IERC721_ENUMERABLE: [u8; 4] = [0x78, 0x0e, 0x9d, 0x63];
+/// `type(IERC1155).interfaceId`
+pub const IERC1155: [u8; 4] = [0xd9, 0xb6, 0x7a, 0x26];
+/// `type(IERC1155MetadataURI).interfaceId`, without the IERC1155 functions
+pub const IERC1155_METADATA_URI: [u8; 4] = [0x0e, 0x89, 0x34, 0x1c];
+/// `type(IERC2981).interfaceId`
+pub const IERC2981: [u8; 4] = [0x2a, 0x55, 0x20, 0x5a];
+/// `type(IERC4907).interfaceId`
+pub const IERC4907: [u8; 4] = [0xad, 0x09, 0x2b, 0x5c];
+/// `type(IERC4494).interfaceId`
+pub const IERC4494: [u8; 4] = [0x56, 0x04, 0xe2, 0x25];
+/// `type(IERC7802).interfaceId`, crosschain mint and burn
+pub const IERC7802: [u8; 4] = [0x33, 0x33, 0x19, 0x94];
+/// `type(IERC777Sender).interfaceId`, the selector of `tokensToSend`
+pub const IERC777_SENDER: [u8; 4] = [0x75, 0xab, 0x97, 0x82];
+/// `type(IERC777Recipient).interfaceId`, the selector of `tokensReceived`
+pub const IERC777_RECIPIENT: [u8; 4] = [0x00, 0x23, 0xde, 0x29];
+/// Interface id no contract may support.
+pub const INVALID_INTERFACE_ID: [u8; 4] = [0xff; 4];
+
+/// `bytes4(keccak256("supportsInterface(bytes4)"))`
+const SUPPORTS_INTERFACE_SELECTOR: [u8; 4] = [0x01, 0xff, 0xc9, 0xa7];
+
+/// Interfaces implemented by a token base or an extension, ERC-165 itself included.
+pub trait Erc165 {
+    fn supports_interface(interface_id: FixedBytes<4>) -> bool;
+}
+
+/// Whether `interface_id` is ERC-165 or one of `supported`. `0xffffffff` is never supported,
+/// as required by the ERC.
+pub fn supports(interface_id: FixedBytes<4>, supported: &[[u8; 4]]) -> bool {
+    interface_id.0 == IERC165 || supported.contains(&interface_id.0)
+}
+
+/// Interface id of the functions with the given signatures: xor of their selectors.
+pub fn interface_id(signatures: &[&str]) -> [u8; 4] {
+    signatures.iter().fold([0u8; 4], |id, signature| {
+        let hash = stylus_sdk::crypto::keccak(signature.as_bytes());
+        core::array::from_fn(|i| id[i] ^ hash[i])
+    })
+}
+
+/// Whether `account` implements ERC-165 and reports `interface_id` as supported. Accounts
+/// without code, reverting or answering `true` for `0xffffffff` support nothing.
+pub fn supports_interface_of(account: Address, interface_id: [u8; 4]) -> bool {
+    account.has_code()
+        && query_supports_interface(account, IERC165)
+        && !query_supports_interface(account, INVALID_INTERFACE_ID)
+        && query_supports_interface(account, interface_id)
+}
+
+/// `supportsInterface(interface_id)` of `account`, `false` when the call fails or returns
+/// anything else than an ABI encoded `true`.
+fn query_supports_interface(account: Address, interface_id: [u8; 4]) -> bool {
+    let mut data = SUPPORTS_INTERFACE_SELECTOR.to_vec();
+    data.extend_from_slice(&interface_id);
+    data.resize(4 + 32, 0);
+    match call::static_call(Call::new(), account, &data) {
+        Ok(output) => output.len() >= 32 && U256::from_be_slice(&output[..32]) == U256::from(1),
+        Err(_) => false,
+    }
+}
@@ -0,0 +1,124 @@
+//! ERC-2771 meta-transactions: a trusted forwarder (relayer contract) verifies the signed request
+//! of a user, pays the gas and calls the token with the user's address appended to the
+//! calldata. For such calls the token must use the appended address instead of `msg::sender()`.
+//!
+//! Stylus passes the calldata length to the entrypoint only, so the context reads the calldata
+//! of externals whose ABI encoded length is known: `4 + 32 * words` for static arguments. The
+//! composing token passes that length to `msg_sender`/`msg_data`; externals with dynamic
+//! arguments are not forwarder aware.
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::Address,
+    alloy_sol_types::{sol, SolError},
+    contract, evm, msg,
+    prelude::*,
+};
+
+/// Length of the sender appended by the forwarder.
+pub const FORWARDED_SENDER_LEN: usize = 20;
+
+sol_storage! {
+    /// Trusted forwarder of the token. Compose with `#[borrow] Erc2771Context context;`, set the
+    /// forwarder in `init` with `set_trusted_forwarder` and use
+    /// `self.context.msg_sender(args_len)` instead of `msg::sender()` in forwarder aware externals.
+    /// See `examples/compose_erc2771.rs` for a complete composition.
+    pub struct Erc2771Context {
+        address trusted_forwarder;
+    }
+}
+
+sol! {
+    event TrustedForwarderUpdated(address indexed previous_forwarder, address indexed new_forwarder);
+
+    /// Indicates the token itself as forwarder, any account could then impersonate any other.
+    error Erc2771InvalidForwarder(address forwarder);
+}
+
+pub enum Erc2771ContextError {
+    Erc2771InvalidForwarder(Erc2771InvalidForwarder),
+}
+
+impl From<Erc2771ContextError> for Vec<u8> {
+    fn from(e: Erc2771ContextError) -> Vec<u8> {
+        match e {
+            Erc2771ContextError::Erc2771InvalidForwarder(e) => e.encode(),
+        }
+    }
+}
+
+crate::errors::error_helpers!(Erc2771ContextError {
+    Erc2771InvalidForwarder,
+});
+
+impl Erc2771Context {
+    /// Sets the trusted forwarder, the zero address disables meta-transactions.
+    ///
+    /// Emits a {TrustedForwarderUpdated} event.
+    pub fn set_trusted_forwarder(&mut self, new_forwarder: Address) -> Result<(), Erc2771ContextError> {
+        if new_forwarder == contract::address() {
+            return Err(Erc2771ContextError::Erc2771InvalidForwarder(Erc2771InvalidForwarder {
+                forwarder: new_forwarder,
+            }));
+        }
+        let previous_forwarder = self.trusted_forwarder.get();
+        self.trusted_forwarder.set(new_forwarder);
+        evm::log(TrustedForwarderUpdated {
+            previous_forwarder,
+            new_forwarder,
+        });
+        Ok(())
+    }
+
+    /// Sender of the current call: the address appended by the trusted forwarder for a
+    /// forwarded call, `msg::sender()` otherwise. `args_len` is the calldata length of the
+    /// external without the appended sender.
+    pub fn msg_sender(&self, args_len: usize) -> Address {
+        match self.forwarded_calldata(args_len) {
+            Some(calldata) => split_forwarded(&calldata, args_len).map_or(msg::sender(), |(sender, _)| sender),
+            None => msg::sender(),
+        }
+    }
+
+    /// Calldata of a forwarded call without the appended sender. `None` for direct calls: the
+    /// external already has its decoded arguments and the calldata is not read, its length is
+    /// not checked and extra bytes would not fit the buffer.
+    pub fn msg_data(&self, args_len: usize) -> Option<Vec<u8>> {
+        let mut calldata = self.forwarded_calldata(args_len)?;
+        calldata.truncate(args_len);
+        Some(calldata)
+    }
+
+    /// Calldata including the appended sender, only read for calls of the trusted forwarder,
+    /// which is trusted to append exactly `FORWARDED_SENDER_LEN` bytes.
+    fn forwarded_calldata(&self, args_len: usize) -> Option<Vec<u8>> {
+        let forwarder = self.trusted_forwarder.get();
+        if forwarder == Address::ZERO || msg::sender() != forwarder {
+            return None;
+        }
+        Some(contract::args(args_len + FORWARDED_SENDER_LEN))
+    }
+}
+
+#[external]
+impl Erc2771Context {
+    #[selector(name = "trustedForwarder")]
+    pub fn trusted_forwarder(&self) -> Result<Address, Erc2771ContextError> {
+        Ok(self.trusted_forwarder.get())
+    }
+
+    /// Returns whether `forwarder` is the trusted forwarder, as expected by relayers.
+    #[selector(name = "isTrustedForwarder")]
+    pub fn is_trusted_forwarder(&self, forwarder: Address) -> Result<bool, Erc2771ContextError> {
+        Ok(forwarder != Address::ZERO && forwarder == self.trusted_forwarder.get())
+    }
+}
+
+/// Splits forwarded `calldata` into the appended sender and the original calldata of
+/// `args_len` bytes, `None` if the calldata is not exactly `args_len` plus a sender.
+pub fn split_forwarded(calldata: &[u8], args_len: usize) -> Option<(Address, &[u8])> {
+    if calldata.len() != args_len + FORWARDED_SENDER_LEN {
+        return None;
+    }
+    let (data, sender) = calldata.split_at(args_len);
+    Some((Address::from_slice(sender), data))
+}
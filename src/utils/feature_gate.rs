@@ -0,0 +1,44 @@
+//! Disabling inherited externals of a composed component.
+//!
+//! `#[inherit]` exports every external of a component, which is all-or-nothing. The router of the
+//! entrypoint tries its own methods before the inherited ones, so an external of a component is
+//! disabled by overriding it in the entrypoint with the same Solidity name and returning
+//! `disabled(...)`:
+//!
+//! ```ignore
+//! #[selector(name = "revokeAllAllowances")]
+//! pub fn revoke_all_allowances(&mut self) -> Result<(), Vec<u8>> {
+//!     Err(feature_gate::disabled("revokeAllAllowances()"))
+//! }
+//! ```
+//!
+//! The method stays in the exported ABI and reverts with {FeatureDisabled}. To remove it from the
+//! ABI as well, borrow the component without inheriting it and re-export the wanted externals by
+//! delegating to it. Internals of the component are usable in both cases.
+//! See `examples/compose_feature_gate.rs`.
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::FixedBytes,
+    alloy_sol_types::{sol, SolError},
+    crypto::keccak,
+};
+
+sol! {
+    /// Indicates a call to an external disabled by the composing contract.
+    /// * `selector` - selector of the disabled function.
+    error FeatureDisabled(bytes4 selector);
+}
+
+/// `bytes4(keccak256(signature))`, e.g. `selector("transfer(address,uint256)")`.
+pub fn selector(signature: &str) -> [u8; 4] {
+    let hash = keccak(signature.as_bytes());
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+/// Revert data of a call to the disabled function `signature` (canonical form, e.g. `burn(uint256)`).
+pub fn disabled(signature: &str) -> Vec<u8> {
+    FeatureDisabled {
+        selector: FixedBytes(selector(signature)),
+    }
+    .encode()
+}
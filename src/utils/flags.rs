@@ -0,0 +1,52 @@
+//! Up to 8 booleans packed in one byte of storage.
+//!
+//! Every `bool` of `sol_storage!` takes a byte, a component with several booleans keeps them in
+//! one byte instead, e.g. `Pausable` stores `paused` in bit 0. Packing only saves slots within a
+//! component: one composed with `#[borrow]` still starts a new slot.
+use stylus_sdk::{alloy_primitives::U8, prelude::*};
+
+/// Number of flags of a `Flags`.
+pub const FLAG_COUNT: u8 = 8;
+
+sol_storage! {
+    /// One byte of boolean flags, a `bool paused;` field is bit 0 of a `Flags` at the same place,
+    /// so replacing it keeps the storage layout.
+    pub struct Flags {
+        uint8 bits;
+    }
+}
+
+impl Flags {
+    /// Whether `flag` (`0..FLAG_COUNT`) is set.
+    pub fn get(&self, flag: u8) -> bool {
+        is_set(self.bits.get().to::<u8>(), flag)
+    }
+
+    /// Sets or clears `flag`, returns whether it changed.
+    pub fn set(&mut self, flag: u8, value: bool) -> bool {
+        let bits = self.bits.get().to::<u8>();
+        let updated = with_flag(bits, flag, value);
+        if updated == bits {
+            return false;
+        }
+        self.bits.set(U8::from(updated));
+        true
+    }
+}
+
+/// Whether `flag` is set in `bits`, false for flags out of range.
+pub fn is_set(bits: u8, flag: u8) -> bool {
+    flag < FLAG_COUNT && bits & (1 << flag) != 0
+}
+
+/// `bits` with `flag` set to `value`, unchanged for flags out of range.
+pub fn with_flag(bits: u8, flag: u8, value: bool) -> u8 {
+    if flag >= FLAG_COUNT {
+        return bits;
+    }
+    if value {
+        bits | (1 << flag)
+    } else {
+        bits & !(1 << flag)
+    }
+}
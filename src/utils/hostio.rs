@@ -0,0 +1,162 @@
+//! Host I/O accounting for extension authors. Storage slots, logs and calls are the expensive
+//! host operations of a Stylus program, every hook called from `update` adds its own to every
+//! transfer. Wrap the storage accesses of a hook with {read}/{write} and its logs and calls
+//! with {log}/{call}, run it inside {with_budget} and build with the `debug` feature: hooks
+//! exceeding their budget are reported on the console, and `HostioStats` keeps the counters of
+//! the last measured call readable with the `lastHostioCounters` view. `Erc20` wraps every
+//! storage access and log of its internal paths, so a measured hook is compared with the base.
+//!
+//! Counters live in the program memory, a fresh instance per call, so they always count the
+//! current external call only. Without `debug` the wrappers only run their closure, counting
+//! compiles to nothing and {counters} stays zero.
+//!
+//! A wrapper counts an access made through the SDK storage types, which cache slots within a
+//! call: a slot read twice is counted twice but costs one host read. Batching storage accesses
+//! is out of scope of this module, it measures, it does not change how slots are accessed.
+use alloc::vec::Vec;
+use core::sync::atomic::{AtomicU32, Ordering};
+use stylus_sdk::{alloy_primitives::U256, prelude::*};
+
+static STORAGE_READS: AtomicU32 = AtomicU32::new(0);
+static STORAGE_WRITES: AtomicU32 = AtomicU32::new(0);
+static LOGS: AtomicU32 = AtomicU32::new(0);
+static CALLS: AtomicU32 = AtomicU32::new(0);
+
+/// Host operations counted since the start of the call.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct HostioCounters {
+    pub storage_reads: u32,
+    pub storage_writes: u32,
+    pub logs: u32,
+    pub calls: u32,
+}
+
+impl HostioCounters {
+    /// Operations done since `earlier` was taken.
+    pub fn since(self, earlier: HostioCounters) -> HostioCounters {
+        HostioCounters {
+            storage_reads: self.storage_reads - earlier.storage_reads,
+            storage_writes: self.storage_writes - earlier.storage_writes,
+            logs: self.logs - earlier.logs,
+            calls: self.calls - earlier.calls,
+        }
+    }
+
+    /// Whether any counter is above the one of `budget`.
+    pub fn exceeds(self, budget: HostioCounters) -> bool {
+        self.storage_reads > budget.storage_reads
+            || self.storage_writes > budget.storage_writes
+            || self.logs > budget.logs
+            || self.calls > budget.calls
+    }
+}
+
+/// Host operations an extension hook should stay within when called on every transfer: one
+/// packed config read, one read and one write of per account state.
+pub const TRANSFER_HOOK_BUDGET: HostioCounters = HostioCounters {
+    storage_reads: 2,
+    storage_writes: 1,
+    logs: 0,
+    calls: 0,
+};
+
+/// Counts a storage read, e.g. `hostio::read(|| self.window.get())`.
+pub fn read<R>(f: impl FnOnce() -> R) -> R {
+    count(&STORAGE_READS);
+    f()
+}
+
+/// Counts a storage write, e.g. `hostio::write(|| self.window.set(window))`.
+pub fn write<R>(f: impl FnOnce() -> R) -> R {
+    count(&STORAGE_WRITES);
+    f()
+}
+
+/// Counts an emitted log.
+pub fn log<R>(f: impl FnOnce() -> R) -> R {
+    count(&LOGS);
+    f()
+}
+
+/// Counts an external call (including precompiles and static calls).
+pub fn call<R>(f: impl FnOnce() -> R) -> R {
+    count(&CALLS);
+    f()
+}
+
+#[cfg(feature = "debug")]
+fn count(counter: &AtomicU32) {
+    counter.fetch_add(1, Ordering::Relaxed);
+}
+
+#[cfg(not(feature = "debug"))]
+fn count(_counter: &AtomicU32) {}
+
+/// Host operations counted since the start of the call, zero without the `debug` feature.
+pub fn counters() -> HostioCounters {
+    HostioCounters {
+        storage_reads: STORAGE_READS.load(Ordering::Relaxed),
+        storage_writes: STORAGE_WRITES.load(Ordering::Relaxed),
+        logs: LOGS.load(Ordering::Relaxed),
+        calls: CALLS.load(Ordering::Relaxed),
+    }
+}
+
+/// Runs `f` and, with the `debug` feature, reports on the console when its host operations
+/// exceed `budget`. Returns the result of `f` whatever the cost, budgets are guidance.
+pub fn with_budget<R>(label: &str, budget: HostioCounters, f: impl FnOnce() -> R) -> R {
+    let before = counters();
+    let result = f();
+    let spent = counters().since(before);
+    if spent.exceeds(budget) {
+        report(label, spent, budget);
+    }
+    result
+}
+
+#[cfg(feature = "debug")]
+fn report(label: &str, spent: HostioCounters, budget: HostioCounters) {
+    stylus_sdk::console!("hostio budget exceeded by {}: spent {:?}, budget {:?}", label, spent, budget);
+}
+
+#[cfg(not(feature = "debug"))]
+fn report(_label: &str, _spent: HostioCounters, _budget: HostioCounters) {}
+
+sol_storage! {
+    /// Counters of the last call measured with `snapshot`, for the `lastHostioCounters` view.
+    /// `snapshot` only writes with the `debug` feature, compose it in debug builds of a token and
+    /// call `self.hostio_stats.snapshot()` at the end of the externals to measure.
+    pub struct HostioStats {
+        uint256 storage_reads;
+        uint256 storage_writes;
+        uint256 logs;
+        uint256 calls;
+    }
+}
+
+impl HostioStats {
+    /// Stores the counters of the current call (debug builds only, the writes are not counted).
+    pub fn snapshot(&mut self) {
+        if cfg!(feature = "debug") {
+            let counted = counters();
+            self.storage_reads.set(U256::from(counted.storage_reads));
+            self.storage_writes.set(U256::from(counted.storage_writes));
+            self.logs.set(U256::from(counted.logs));
+            self.calls.set(U256::from(counted.calls));
+        }
+    }
+}
+
+#[external]
+impl HostioStats {
+    /// Returns `(storage_reads, storage_writes, logs, calls)` of the last measured call.
+    #[selector(name = "lastHostioCounters")]
+    pub fn last_hostio_counters(&self) -> Result<(U256, U256, U256, U256), Vec<u8>> {
+        Ok((
+            self.storage_reads.get(),
+            self.storage_writes.get(),
+            self.logs.get(),
+            self.calls.get(),
+        ))
+    }
+}
@@ -0,0 +1,110 @@
+//! Initialization guards for programs without constructors, the Stylus counterpart of
+//! OpenZeppelin `Initializable`.
+//!
+//! A Stylus program is deployed first and initialized by a later call, as are proxies and
+//! EIP-1167 clones (`utils::clones`) pointing at it: anyone calling `init` first owns the
+//! contract. The composing contract calls `initializer()` at the start of its `init`, and
+//! `reinitializer(version)` at the start of the migration of each later version (e.g. after a
+//! program upgrade adding storage). An implementation only used through clones or proxies calls
+//! `disable_initializers` so its own storage can never be initialized.
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::U64,
+    alloy_sol_types::{sol, SolError},
+    evm,
+    prelude::*,
+};
+
+sol_storage! {
+    /// Last initialized version, 0 before `initializer()` and `u64::MAX` once disabled.
+    /// See `examples/compose_access_control.rs` for a composition.
+    pub struct Initializable {
+        uint64 initialized_version;
+    }
+}
+
+sol! {
+    /// Triggered when the contract has been initialized or reinitialized.
+    event Initialized(uint64 version);
+
+    /// The contract is already initialized, at `version` or a later one.
+    error InvalidInitialization();
+}
+
+pub enum InitializableError {
+    InvalidInitialization(InvalidInitialization),
+}
+
+impl From<InitializableError> for Vec<u8> {
+    fn from(e: InitializableError) -> Vec<u8> {
+        match e {
+            InitializableError::InvalidInitialization(e) => e.encode(),
+        }
+    }
+}
+
+crate::errors::error_helpers!(InitializableError {
+    InvalidInitialization,
+});
+
+impl Initializable {
+    /// Marks the contract initialized at version 1, reverts with `InvalidInitialization` if it
+    /// already is (or initializers are disabled).
+    ///
+    /// Emits an {Initialized} event.
+    pub fn initializer(&mut self) -> Result<(), InitializableError> {
+        self.reinitializer(1)
+    }
+
+    /// Marks the contract initialized at `version`, reverts with `InvalidInitialization` unless
+    /// every initialized version is below it. Versions can be skipped, each runs at most once.
+    ///
+    /// Emits an {Initialized} event.
+    pub fn reinitializer(&mut self, version: u64) -> Result<(), InitializableError> {
+        check_version(self.initialized_version(), version)?;
+        self.set_version(version);
+        Ok(())
+    }
+
+    /// Locks the contract against any future (re)initialization.
+    ///
+    /// Emits an {Initialized} event the first time.
+    pub fn disable_initializers(&mut self) {
+        if self.initialized_version() != u64::MAX {
+            self.set_version(u64::MAX);
+        }
+    }
+
+    /// Highest version initialized, `u64::MAX` once disabled.
+    pub fn initialized_version(&self) -> u64 {
+        self.initialized_version.get().to::<u64>()
+    }
+
+    /// Whether `initializer()` already ran.
+    pub fn is_initialized(&self) -> bool {
+        self.initialized_version() > 0
+    }
+
+    fn set_version(&mut self, version: u64) {
+        self.initialized_version.set(U64::from(version));
+        evm::log(Initialized { version });
+    }
+}
+
+#[external]
+impl Initializable {
+    /// Returns the highest initialized version, `type(uint64).max` once initializers are disabled.
+    #[selector(name = "getInitializedVersion")]
+    pub fn get_initialized_version(&self) -> Result<u64, InitializableError> {
+        Ok(self.initialized_version())
+    }
+}
+
+/// Check of {Initializable::reinitializer} without storage: `InvalidInitialization` unless
+/// `initialized_version` is below `version`.
+pub fn check_version(initialized_version: u64, version: u64) -> Result<(), InitializableError> {
+    if initialized_version >= version {
+        return Err(InitializableError::InvalidInitialization(InvalidInitialization {}));
+    }
+    Ok(())
+}
@@ -0,0 +1,128 @@
+//! Checked `U256` arithmetic returning typed errors. Plain `+`, `-`, `*` and `/` on `U256`
+//! panic (or wrap in release builds) when they overflow or divide by zero, and a panic in WASM
+//! traps with empty revert data; these helpers revert with an error the caller can decode.
+//!
+//! `mul_div` computes `x * y / denominator` on the 512 bits product, exact whenever the result
+//! fits in 256 bits, with the rounding direction chosen by the caller (vault conversions round
+//! against the user, fees up, streamed amounts down).
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{U256, U512},
+    alloy_sol_types::{sol, SolError},
+};
+
+/// Rounding direction of a division.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rounding {
+    Down,
+    Up,
+}
+
+sol! {
+    /// Indicates a result above `type(uint256).max`.
+    error MathOverflow();
+
+    /// Indicates a subtraction below zero.
+    error MathUnderflow();
+
+    /// Indicates a division by zero.
+    error MathDivisionByZero();
+}
+
+pub enum MathError {
+    MathOverflow(MathOverflow),
+    MathUnderflow(MathUnderflow),
+    MathDivisionByZero(MathDivisionByZero),
+}
+
+impl From<MathError> for Vec<u8> {
+    fn from(e: MathError) -> Vec<u8> {
+        match e {
+            MathError::MathOverflow(e) => e.encode(),
+            MathError::MathUnderflow(e) => e.encode(),
+            MathError::MathDivisionByZero(e) => e.encode(),
+        }
+    }
+}
+
+crate::errors::error_helpers!(MathError {
+    MathOverflow,
+    MathUnderflow,
+    MathDivisionByZero,
+});
+
+pub fn checked_add(a: U256, b: U256) -> Result<U256, MathError> {
+    a.checked_add(b).ok_or(MathError::MathOverflow(MathOverflow {}))
+}
+
+pub fn checked_sub(a: U256, b: U256) -> Result<U256, MathError> {
+    a.checked_sub(b).ok_or(MathError::MathUnderflow(MathUnderflow {}))
+}
+
+pub fn checked_mul(a: U256, b: U256) -> Result<U256, MathError> {
+    a.checked_mul(b).ok_or(MathError::MathOverflow(MathOverflow {}))
+}
+
+/// `a / b` rounded down.
+pub fn checked_div(a: U256, b: U256) -> Result<U256, MathError> {
+    a.checked_div(b).ok_or(MathError::MathDivisionByZero(MathDivisionByZero {}))
+}
+
+/// `a / b` rounded up.
+pub fn ceil_div(a: U256, b: U256) -> Result<U256, MathError> {
+    if b == U256::ZERO {
+        return Err(MathError::MathDivisionByZero(MathDivisionByZero {}));
+    }
+    if a == U256::ZERO {
+        return Ok(U256::ZERO);
+    }
+    // a - 1 can not underflow and the quotient is at most a - 1, so + 1 can not overflow
+    Ok((a - U256::from(1)) / b + U256::from(1))
+}
+
+/// `(a + b) / 2` rounded down, without overflowing.
+pub fn average(a: U256, b: U256) -> U256 {
+    (a & b) + ((a ^ b) >> 1)
+}
+
+/// `x * y / denominator` rounded in the `rounding` direction, with the product on 512 bits so
+/// it does not overflow when only the product exceeds 256 bits. Reverts when the result does.
+pub fn mul_div(x: U256, y: U256, denominator: U256, rounding: Rounding) -> Result<U256, MathError> {
+    if denominator == U256::ZERO {
+        return Err(MathError::MathDivisionByZero(MathDivisionByZero {}));
+    }
+    let product: U512 = x.widening_mul(y);
+    let (mut quotient, remainder) = product.div_rem(U512::from(denominator));
+    if rounding == Rounding::Up && remainder != U512::ZERO {
+        // can not overflow 512 bits: the quotient is at most the product
+        quotient += U512::from(1);
+    }
+    let limbs = quotient.as_limbs();
+    if limbs[4..].iter().any(|limb| *limb != 0) {
+        return Err(MathError::MathOverflow(MathOverflow {}));
+    }
+    Ok(U256::from_limbs_slice(&limbs[..4]))
+}
+
+/// Square root of `a` rounded in the `rounding` direction.
+pub fn sqrt(a: U256, rounding: Rounding) -> U256 {
+    if a <= U256::from(1) {
+        return a;
+    }
+    // Newton's iterations decrease from any start above the root down to its floor; the start
+    // 2^ceil(bits / 2) is above it and keeps `x + a / x` below 2^129
+    let mut x = U256::from(1) << ((a.bit_len() + 1) / 2);
+    loop {
+        let next = (x + a / x) >> 1;
+        if next >= x {
+            break;
+        }
+        x = next;
+    }
+    // x < 2^128, x * x fits
+    if rounding == Rounding::Up && x * x < a {
+        x + U256::from(1)
+    } else {
+        x
+    }
+}
@@ -0,0 +1,111 @@
+//! Inclusion proofs of merkle trees, port of OpenZeppelin `MerkleProof`: trees hash their pairs
+//! sorted (`commutative keccak256`), as built by `StandardMerkleTree` of `@openzeppelin/merkle-tree`
+//! and by `tests/util/balances_root.rs`, so proofs generated for Solidity verify unchanged.
+//!
+//! Leaves should be the double hash of the leaf data, `keccak256(keccak256(abi.encode(...)))`,
+//! otherwise a 64 bytes leaf can be passed off as an inner node. Pairs are hashed from a stack
+//! buffer, verifying a proof of depth `n` costs `n` keccak calls and no allocation.
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::B256,
+    alloy_sol_types::{sol, SolError},
+    crypto::keccak,
+};
+
+sol! {
+    /// Indicates a multiproof whose lengths do not add up or which reads past its proof.
+    error MerkleProofInvalidMultiproof();
+}
+
+pub enum MerkleProofError {
+    MerkleProofInvalidMultiproof(MerkleProofInvalidMultiproof),
+}
+
+impl From<MerkleProofError> for Vec<u8> {
+    fn from(e: MerkleProofError) -> Vec<u8> {
+        match e {
+            MerkleProofError::MerkleProofInvalidMultiproof(e) => e.encode(),
+        }
+    }
+}
+
+crate::errors::error_helpers!(MerkleProofError {
+    MerkleProofInvalidMultiproof,
+});
+
+/// Whether `leaf` is in the tree of `root`, `proof` holding the sibling hashes from the leaf up.
+pub fn verify(proof: &[B256], root: B256, leaf: B256) -> bool {
+    process_proof(proof, leaf) == root
+}
+
+/// Root rebuilt from `leaf` and its `proof`.
+pub fn process_proof(proof: &[B256], leaf: B256) -> B256 {
+    proof.iter().fold(leaf, |hash, sibling| hash_pair(hash, *sibling))
+}
+
+/// Whether all `leaves` are in the tree of `root`, proven together by `proof` and `proof_flags`
+/// as produced by `StandardMerkleTree.getMultiProof`. Leaves must be in the order of the
+/// multiproof. Reverts with `MerkleProofInvalidMultiproof` on malformed multiproofs.
+///
+/// NOTE: as in OpenZeppelin, a multiproof with no flags and no leaves verifies `proof[0]` as the
+/// root, callers must not accept empty `leaves`.
+pub fn multi_proof_verify(
+    proof: &[B256],
+    proof_flags: &[bool],
+    root: B256,
+    leaves: &[B256],
+) -> Result<bool, MerkleProofError> {
+    Ok(process_multi_proof(proof, proof_flags, leaves)? == root)
+}
+
+/// Root rebuilt from `leaves` and their multiproof: each flag hashes the next leaf (or hash
+/// computed so far) with either another leaf or hash (`true`) or the next proof element (`false`).
+pub fn process_multi_proof(proof: &[B256], proof_flags: &[bool], leaves: &[B256]) -> Result<B256, MerkleProofError> {
+    if leaves.len() + proof.len() != proof_flags.len() + 1 {
+        return Err(invalid_multiproof());
+    }
+    let mut hashes: Vec<B256> = Vec::with_capacity(proof_flags.len());
+    let (mut leaf_pos, mut hash_pos, mut proof_pos) = (0, 0, 0);
+    for flag in proof_flags {
+        let a = next_node(leaves, &hashes, &mut leaf_pos, &mut hash_pos)?;
+        let b = if *flag {
+            next_node(leaves, &hashes, &mut leaf_pos, &mut hash_pos)?
+        } else {
+            let sibling = *proof.get(proof_pos).ok_or_else(invalid_multiproof)?;
+            proof_pos += 1;
+            sibling
+        };
+        hashes.push(hash_pair(a, b));
+    }
+    if let Some(root) = hashes.last() {
+        if proof_pos != proof.len() {
+            return Err(invalid_multiproof());
+        }
+        return Ok(*root);
+    }
+    leaves.first().or(proof.first()).copied().ok_or_else(invalid_multiproof)
+}
+
+/// `keccak256` of the two hashes concatenated in ascending order.
+pub fn hash_pair(a: B256, b: B256) -> B256 {
+    let (low, high) = if a < b { (a, b) } else { (b, a) };
+    let mut buf = [0u8; 64];
+    buf[..32].copy_from_slice(low.as_slice());
+    buf[32..].copy_from_slice(high.as_slice());
+    keccak(buf)
+}
+
+/// Next unconsumed leaf, then the hashes computed so far once the leaves are consumed.
+fn next_node(leaves: &[B256], hashes: &[B256], leaf_pos: &mut usize, hash_pos: &mut usize) -> Result<B256, MerkleProofError> {
+    if let Some(leaf) = leaves.get(*leaf_pos) {
+        *leaf_pos += 1;
+        return Ok(*leaf);
+    }
+    let hash = *hashes.get(*hash_pos).ok_or_else(invalid_multiproof)?;
+    *hash_pos += 1;
+    Ok(hash)
+}
+
+fn invalid_multiproof() -> MerkleProofError {
+    MerkleProofError::MerkleProofInvalidMultiproof(MerkleProofInvalidMultiproof {})
+}
@@ -0,0 +1,30 @@
+pub mod abi_export;
+pub mod addresses;
+pub mod bitmaps;
+pub mod bounded_string;
+pub mod checkpoints;
+pub mod clones;
+pub mod create2;
+pub mod ecdsa;
+pub mod eip712;
+pub mod erc165;
+pub mod erc2771_context;
+pub mod feature_gate;
+pub mod flags;
+pub mod hostio;
+pub mod initializable;
+pub mod math;
+pub mod merkle_proof;
+pub mod multicall;
+pub mod nonces;
+pub mod pausable;
+pub mod payable;
+pub mod reentrancy_guard;
+pub mod replay_guard;
+pub mod rescue;
+pub mod revert_stats;
+pub mod safe_erc20;
+pub mod signature_checker;
+pub mod storage_slots;
+pub mod strings;
+pub mod transfer_checks;
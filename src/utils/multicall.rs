@@ -0,0 +1,70 @@
+//! Batching calls to the program itself, port of OpenZeppelin `Multicall`.
+//!
+//! Solidity runs every payload with a `delegatecall` to `address(this)`. Stylus programs built
+//! without the `stylus-sdk/reentrant` feature revert on reentrant calls, so `multicall` instead
+//! dispatches each payload through the router of the entrypoint, in the same call frame: the
+//! caller and the storage are shared, exactly as with a delegatecall. Expose it from the
+//! entrypoint, passing itself:
+//!
+//! ```ignore
+//! #[selector(name = "multicall")]
+//! pub fn multicall(&mut self, data: Vec<Bytes>) -> Result<Vec<Bytes>, Vec<u8>> {
+//!     multicall::multicall(self, data)
+//! }
+//! ```
+//!
+//! The first failing payload reverts the batch with its revert data. `multicall` is not payable,
+//! so payable externals only run with a zero value inside a batch.
+//! See `examples/compose_multicall.rs`.
+use alloc::vec::Vec;
+use core::borrow::BorrowMut;
+use stylus_sdk::{
+    abi::{Bytes, Router},
+    alloy_primitives::FixedBytes,
+    alloy_sol_types::{sol, SolError},
+    storage::TopLevelStorage,
+};
+
+sol! {
+    /// Indicates a payload matching no external of the program.
+    /// * `selector` - first four bytes of the payload, zero padded when shorter.
+    error MulticallUnknownFunction(bytes4 selector);
+}
+
+/// Runs every payload of `data` against the externals of `storage` (the entrypoint), returns
+/// their return data in order.
+pub fn multicall<S>(storage: &mut S, data: Vec<Bytes>) -> Result<Vec<Bytes>, Vec<u8>>
+where
+    S: TopLevelStorage + Router<S> + BorrowMut<<S as Router<S>>::Storage>,
+{
+    let mut results = Vec::with_capacity(data.len());
+    for payload in data {
+        let Some((selector, input)) = split_selector(&payload) else {
+            return Err(unknown_function(&payload));
+        };
+        match S::route(storage, u32::from_be_bytes(selector), input) {
+            Some(result) => results.push(Bytes::from(result?)),
+            None => return Err(unknown_function(&payload)),
+        }
+    }
+    Ok(results)
+}
+
+/// Splits a payload into its selector and ABI encoded arguments.
+pub fn split_selector(payload: &[u8]) -> Option<([u8; 4], &[u8])> {
+    if payload.len() < 4 {
+        return None;
+    }
+    let (selector, input) = payload.split_at(4);
+    Some(([selector[0], selector[1], selector[2], selector[3]], input))
+}
+
+fn unknown_function(payload: &[u8]) -> Vec<u8> {
+    let mut selector = [0u8; 4];
+    let len = payload.len().min(4);
+    selector[..len].copy_from_slice(&payload[..len]);
+    MulticallUnknownFunction {
+        selector: FixedBytes(selector),
+    }
+    .encode()
+}
@@ -0,0 +1,73 @@
+//! Sequential nonces of accounts, port of OpenZeppelin `Nonces`: a signature signs the current
+//! nonce of its signer, consuming it makes every older signature invalid.
+//!
+//! `ReplayGuard` embeds a `Nonces` for its sequential mode, so extensions taking the shared
+//! `ReplayGuard` (permits, delegation by signature, meta transactions) consume the same nonces.
+//! Contracts without unordered nonces compose `Nonces` alone.
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    alloy_sol_types::{sol, SolError},
+    prelude::*,
+};
+
+sol_storage! {
+    /// Next nonce of every account.
+    pub struct Nonces {
+        mapping(address => uint256) nonces;
+    }
+}
+
+sol! {
+    /// Indicates that the sequential `nonce` used by `account` is not its current nonce.
+    /// * `account` - account whose nonce was used.
+    /// * `current_nonce` - nonce expected for the account.
+    error InvalidAccountNonce(address account, uint256 current_nonce);
+}
+
+pub enum NoncesError {
+    InvalidAccountNonce(InvalidAccountNonce),
+}
+
+impl From<NoncesError> for Vec<u8> {
+    fn from(e: NoncesError) -> Vec<u8> {
+        match e {
+            NoncesError::InvalidAccountNonce(e) => e.encode(),
+        }
+    }
+}
+
+crate::errors::error_helpers!(NoncesError {
+    InvalidAccountNonce,
+});
+
+impl Nonces {
+    /// Consumes the next nonce of `account` and returns it.
+    pub fn use_nonce(&mut self, account: Address) -> U256 {
+        let mut nonce_ref = self.nonces.setter(account);
+        let nonce = nonce_ref.get();
+        nonce_ref.set(nonce + U256::from(1));
+        nonce
+    }
+
+    /// Same as {use_nonce} but reverts unless `nonce` is the current nonce of `account`.
+    pub fn use_checked_nonce(&mut self, account: Address, nonce: U256) -> Result<(), NoncesError> {
+        let current_nonce = self.use_nonce(account);
+        if nonce != current_nonce {
+            return Err(NoncesError::InvalidAccountNonce(InvalidAccountNonce {
+                account,
+                current_nonce,
+            }));
+        }
+        Ok(())
+    }
+}
+
+#[external]
+impl Nonces {
+    /// Returns the next nonce of `owner`.
+    #[selector(name = "nonces")]
+    pub fn nonces(&self, owner: Address) -> Result<U256, NoncesError> {
+        Ok(self.nonces.get(owner))
+    }
+}
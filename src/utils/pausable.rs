@@ -0,0 +1,116 @@
+//! Emergency stop shared by every token kind: nothing in the paused state is specific to a
+//! standard, ERC20 tokens, collections and vaults compose the same `Pausable` (re-exported as
+//! `Erc20Pausable` by `extensions::erc20_pausable`).
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_sol_types::{sol, SolError},
+    evm, msg,
+    prelude::*,
+};
+
+use super::flags::Flags;
+
+/// Flag of the paused state.
+pub const PAUSED_FLAG: u8 = 0;
+
+sol_storage! {
+    /// Emergency stop of token movements. The composing token calls `when_not_paused()` at the
+    /// start of every external moving balances (transfers, mints, burns, deposits...).
+    ///
+    /// `pause`/`unpause` are internal: the composing token exposes them behind its own
+    /// authorization, e.g. `Ownable::only_owner` or a pauser role of `AccessControl`, so the
+    /// extension is never callable by anyone.
+    ///
+    /// The paused state is bit 0 of a `Flags` byte, same layout as the former `bool paused`.
+    /// See `examples/compose_pausable.rs` for a complete composition.
+    pub struct Pausable {
+        Flags flags;
+    }
+}
+
+sol! {
+    event Paused(address account);
+    event Unpaused(address account);
+
+    /// The operation failed because the contract is paused.
+    error EnforcedPause();
+
+    /// The operation failed because the contract is not paused.
+    error ExpectedPause();
+}
+
+pub enum PausableError {
+    EnforcedPause(EnforcedPause),
+    ExpectedPause(ExpectedPause),
+}
+
+impl From<PausableError> for Vec<u8> {
+    fn from(e: PausableError) -> Vec<u8> {
+        match e {
+            PausableError::EnforcedPause(e) => e.encode(),
+            PausableError::ExpectedPause(e) => e.encode(),
+        }
+    }
+}
+
+crate::errors::error_helpers!(PausableError {
+    EnforcedPause,
+    ExpectedPause,
+});
+
+impl Pausable {
+    /// Reverts with `EnforcedPause` while paused.
+    pub fn when_not_paused(&self) -> Result<(), PausableError> {
+        require_not_paused(self.flags.get(PAUSED_FLAG))
+    }
+
+    /// Reverts with `ExpectedPause` while not paused.
+    pub fn when_paused(&self) -> Result<(), PausableError> {
+        require_paused(self.flags.get(PAUSED_FLAG))
+    }
+
+    /// Pauses the token, reverts with `EnforcedPause` if already paused.
+    ///
+    /// Emits a {Paused} event.
+    pub fn pause(&mut self) -> Result<(), PausableError> {
+        self.when_not_paused()?;
+        self.flags.set(PAUSED_FLAG, true);
+        evm::log(Paused { account: msg::sender() });
+        Ok(())
+    }
+
+    /// Unpauses the token, reverts with `ExpectedPause` if not paused.
+    ///
+    /// Emits an {Unpaused} event.
+    pub fn unpause(&mut self) -> Result<(), PausableError> {
+        self.when_paused()?;
+        self.flags.set(PAUSED_FLAG, false);
+        evm::log(Unpaused { account: msg::sender() });
+        Ok(())
+    }
+}
+
+#[external]
+impl Pausable {
+    /// Returns true if the token is paused.
+    #[selector(name = "paused")]
+    pub fn paused(&self) -> Result<bool, PausableError> {
+        Ok(self.flags.get(PAUSED_FLAG))
+    }
+}
+
+/// Check of {Pausable::when_not_paused} without storage: `EnforcedPause` when `paused`.
+pub fn require_not_paused(paused: bool) -> Result<(), PausableError> {
+    if paused {
+        return Err(PausableError::EnforcedPause(EnforcedPause {}));
+    }
+    Ok(())
+}
+
+/// Check of {Pausable::when_paused} without storage: `ExpectedPause` unless `paused`.
+pub fn require_paused(paused: bool) -> Result<(), PausableError> {
+    if !paused {
+        return Err(PausableError::ExpectedPause(ExpectedPause {}));
+    }
+    Ok(())
+}
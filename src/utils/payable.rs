@@ -0,0 +1,112 @@
+//! Receiving plain ETH transfers, the `receive()` and `fallback()` of Solidity contracts (WETH,
+//! payment splitters, crowdsales).
+//!
+//! The router generated by `#[entrypoint]` on a storage struct reverts on calldata without a
+//! selector, so a program sent ETH without calldata always rejects it. Programs receiving ETH
+//! instead implement `Receive` and replace `#[entrypoint]` with `payable_entrypoint!`, which
+//! routes calldata with a known selector to the externals as before, empty calldata to
+//! `Receive::receive` and anything else to `Receive::fallback`:
+//!
+//! ```ignore
+//! sol_storage! {
+//!     pub struct Weth {
+//!         #[borrow]
+//!         Erc20<WethParams> erc20;
+//!     }
+//! }
+//!
+//! oz_stylus_erc::payable_entrypoint!(Weth);
+//!
+//! impl Receive for Weth {
+//!     fn receive(&mut self) -> Result<(), Vec<u8>> {
+//!         Ok(self.erc20.mint(msg::sender(), msg::value())?)
+//!     }
+//! }
+//! ```
+//!
+//! Externals taking ETH are marked `#[payable]`, the others still revert when sent value.
+//! See `examples/weth.rs`.
+use alloc::vec::Vec;
+use core::borrow::BorrowMut;
+use stylus_sdk::{
+    abi::Router,
+    alloy_primitives::U256,
+    storage::{StorageType, TopLevelStorage},
+    ArbResult,
+};
+
+use crate::utils::multicall::split_selector;
+
+/// Handlers of the calls matching no external, implemented by the entrypoint of
+/// `payable_entrypoint!`.
+pub trait Receive {
+    /// Plain ETH transfer, the calldata is empty and `msg::value()` is the amount received.
+    fn receive(&mut self) -> Result<(), Vec<u8>>;
+
+    /// Calldata matching no external (or shorter than a selector), returns the return data.
+    /// Reverts without data by default, as the `#[entrypoint]` router.
+    fn fallback(&mut self, _input: &[u8]) -> ArbResult {
+        Err(Vec::new())
+    }
+}
+
+/// Handler of a calldata, before the selector is looked up in the router.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Dispatch<'a> {
+    /// empty calldata, to `Receive::receive`
+    Receive,
+    /// calldata shorter than a selector, to `Receive::fallback`
+    Fallback(&'a [u8]),
+    /// selector and ABI encoded arguments, to the router (then `Receive::fallback` if unknown)
+    Method(u32, &'a [u8]),
+}
+
+/// Classifies `input` like the entry point of a Solidity contract.
+pub fn dispatch(input: &[u8]) -> Dispatch<'_> {
+    if input.is_empty() {
+        return Dispatch::Receive;
+    }
+    match split_selector(input) {
+        Some((selector, args)) => Dispatch::Method(u32::from_be_bytes(selector), args),
+        None => Dispatch::Fallback(input),
+    }
+}
+
+/// Entry point of the programs declared with `payable_entrypoint!`: routes `input` to the
+/// externals of `S`, `Receive::receive` or `Receive::fallback`.
+pub fn route<S>(input: Vec<u8>) -> ArbResult
+where
+    S: StorageType + TopLevelStorage + Router<S> + BorrowMut<<S as Router<S>>::Storage> + Receive,
+{
+    // the storage of the entrypoint starts at slot 0, as with `#[entrypoint]`
+    let mut storage = unsafe { S::new(U256::ZERO, 0) };
+    match dispatch(&input) {
+        Dispatch::Receive => storage.receive().map(|()| Vec::new()),
+        Dispatch::Fallback(input) => storage.fallback(input),
+        Dispatch::Method(selector, args) => match S::route(&mut storage, selector, args) {
+            Some(result) => result,
+            None => storage.fallback(&input),
+        },
+    }
+}
+
+/// Makes `$contract`, a `sol_storage!` struct without `#[entrypoint]` implementing `Receive`,
+/// the entrypoint of the program: routes the calls with `utils::payable::route` and, with
+/// `export-abi`, defines the `print_abi` that `#[entrypoint]` would.
+#[macro_export]
+macro_rules! payable_entrypoint {
+    ($contract:ty) => {
+        unsafe impl stylus_sdk::storage::TopLevelStorage for $contract {}
+
+        #[cfg(not(feature = "export-abi"))]
+        #[stylus_sdk::prelude::entrypoint]
+        fn user_main(input: alloc::vec::Vec<u8>) -> stylus_sdk::ArbResult {
+            $crate::utils::payable::route::<$contract>(input)
+        }
+
+        #[cfg(feature = "export-abi")]
+        pub fn print_abi(license: &str, pragma: &str) {
+            stylus_sdk::abi::export::print_abi::<$contract>(license, pragma);
+        }
+    };
+}
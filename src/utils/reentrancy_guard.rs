@@ -0,0 +1,76 @@
+//! Read-only reentrancy protection: a vault's views price shares from its asset balance and
+//! share supply, which disagree while one of its asset transfers is in flight (assets pulled but
+//! shares not minted yet, shares burned but assets not paid yet). `Erc4626` and `Erc20Wrapper`
+//! engage the guard around those transfers and their views revert meanwhile, so a callback of
+//! the asset can not feed an integrator a manipulated price.
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_sol_types::{sol, SolError},
+    prelude::*,
+};
+
+sol_storage! {
+    /// Reentrancy guard for vault-style extensions (4626 vaults, wrappers): external calls run
+    /// inside `enter`/`exit`, and the views integrators use as oracles (`total_assets`,
+    /// `convert_to_assets`...) start with `non_reentrant_view()`.
+    ///
+    /// NOTE: programs built without the `stylus-sdk/reentrant` feature (the default of this crate)
+    /// already revert on any reentrant call, the guard keeps the views safe when it is enabled,
+    /// and costs two storage writes per guarded call.
+    pub struct ReentrancyGuard {
+        bool entered;
+    }
+}
+
+sol! {
+    /// Indicates a reentrant call, or a guarded view read during a guarded call.
+    error ReentrancyGuardReentrantCall();
+}
+
+pub enum ReentrancyGuardError {
+    ReentrancyGuardReentrantCall(ReentrancyGuardReentrantCall),
+}
+
+impl From<ReentrancyGuardError> for Vec<u8> {
+    fn from(e: ReentrancyGuardError) -> Vec<u8> {
+        match e {
+            ReentrancyGuardError::ReentrancyGuardReentrantCall(e) => e.encode(),
+        }
+    }
+}
+
+crate::errors::error_helpers!(ReentrancyGuardError {
+    ReentrancyGuardReentrantCall,
+});
+
+impl ReentrancyGuard {
+    /// Engages the guard, reverting if it already is. Pair with {exit} on every success path, a
+    /// revert in between reverts the whole call so the guard can not stay engaged.
+    pub fn enter(&mut self) -> Result<(), ReentrancyGuardError> {
+        self.non_reentrant_view()?;
+        self.entered.set(true);
+        Ok(())
+    }
+
+    pub fn exit(&mut self) {
+        self.entered.set(false);
+    }
+
+    /// Guard for views: reverts while a guarded call is in progress.
+    pub fn non_reentrant_view(&self) -> Result<(), ReentrancyGuardError> {
+        if self.entered.get() {
+            return Err(ReentrancyGuardError::ReentrancyGuardReentrantCall(
+                ReentrancyGuardReentrantCall {},
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[external]
+impl ReentrancyGuard {
+    /// Whether a guarded call is in progress, lets integrators check before reading prices.
+    pub fn reentrancy_guard_entered(&self) -> Result<bool, ReentrancyGuardError> {
+        Ok(self.entered.get())
+    }
+}
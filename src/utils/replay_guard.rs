@@ -0,0 +1,135 @@
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    alloy_sol_types::{sol, SolError},
+    evm, msg,
+    prelude::*,
+};
+
+use crate::utils::bitmaps::Bitmap;
+use crate::utils::nonces::{Nonces, NoncesError};
+
+pub use crate::utils::nonces::InvalidAccountNonce;
+
+/// Replay protection scheme used by a signature extension.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum NonceMode {
+    /// EIP-2612 style, signatures of an account must be used in order: `0, 1, 2...`.
+    Sequential,
+    /// Permit2 style, any unused nonce is valid, signatures can be used in any order.
+    Unordered,
+}
+
+sol_storage! {
+    /// Shared replay protection for signature based extensions (permit, signature transfers,
+    /// transfer with authorization...), supporting sequential nonces and unordered nonce bitmaps.
+    ///
+    /// Compose once in the token with `#[borrow] ReplayGuard replay_guard;` and inherit it, then
+    /// pass `&mut self.replay_guard` to the extensions consuming signatures, so every extension
+    /// shares the same nonces and `invalidate_unordered_nonces` cancels signatures of all of them.
+    ///
+    /// Sequential nonces are a `utils::nonces::Nonces` in the first slot, the layout of a plain
+    /// `mapping(address => uint256)`.
+    pub struct ReplayGuard {
+        /// next sequential nonce of an account
+        #[borrow]
+        Nonces nonces;
+        /// account => used unordered nonces, words of 256 nonces keyed by word position; the
+        /// slots of a `mapping(address => mapping(uint256 => uint256))`
+        mapping(address => Bitmap) nonce_bitmap;
+    }
+}
+
+sol! {
+    event UnorderedNonceInvalidation(address indexed owner, uint256 word, uint256 mask);
+
+    /// Indicates that the unordered `nonce` of `account` was already used or invalidated.
+    error InvalidUnorderedNonce(address account, uint256 nonce);
+}
+
+pub enum ReplayGuardError {
+    InvalidAccountNonce(InvalidAccountNonce),
+    InvalidUnorderedNonce(InvalidUnorderedNonce),
+}
+
+impl From<ReplayGuardError> for Vec<u8> {
+    fn from(e: ReplayGuardError) -> Vec<u8> {
+        match e {
+            ReplayGuardError::InvalidAccountNonce(e) => e.encode(),
+            ReplayGuardError::InvalidUnorderedNonce(e) => e.encode(),
+        }
+    }
+}
+
+crate::errors::error_helpers!(ReplayGuardError {
+    InvalidAccountNonce,
+    InvalidUnorderedNonce,
+});
+
+impl ReplayGuard {
+    /// Consumes `nonce` of `account` with the scheme selected by the calling extension.
+    pub fn consume(&mut self, mode: NonceMode, account: Address, nonce: U256) -> Result<(), ReplayGuardError> {
+        match mode {
+            NonceMode::Sequential => self.use_checked_nonce(account, nonce),
+            NonceMode::Unordered => self.use_unordered_nonce(account, nonce),
+        }
+    }
+
+    /// Consumes the next sequential nonce of `account` and returns it.
+    pub fn use_nonce(&mut self, account: Address) -> U256 {
+        self.nonces.use_nonce(account)
+    }
+
+    /// Same as {use_nonce} but reverts unless `nonce` is the current nonce of `account`.
+    pub fn use_checked_nonce(&mut self, account: Address, nonce: U256) -> Result<(), ReplayGuardError> {
+        self.nonces.use_checked_nonce(account, nonce).map_err(|e| match e {
+            NoncesError::InvalidAccountNonce(e) => ReplayGuardError::InvalidAccountNonce(e),
+        })
+    }
+
+    /// Marks the unordered `nonce` of `account` as used, reverting if it already was.
+    pub fn use_unordered_nonce(&mut self, account: Address, nonce: U256) -> Result<(), ReplayGuardError> {
+        let mut used = self.nonce_bitmap.setter(account);
+        if used.get(nonce) {
+            return Err(ReplayGuardError::InvalidUnorderedNonce(InvalidUnorderedNonce {
+                account,
+                nonce,
+            }));
+        }
+        used.set(nonce);
+        Ok(())
+    }
+
+    pub fn is_unordered_nonce_used(&self, account: Address, nonce: U256) -> bool {
+        self.nonce_bitmap.get(account).get(nonce)
+    }
+}
+
+/// `nonces(owner)` is the external of the inherited `Nonces`.
+#[external]
+#[inherit(Nonces)]
+impl ReplayGuard {
+    /// Returns the bitmap of used unordered nonces of `owner` at `word_pos`
+    /// (nonces `word_pos * 256 .. word_pos * 256 + 255`).
+    #[selector(name = "nonceBitmap")]
+    pub fn nonce_bitmap(&self, owner: Address, word_pos: U256) -> Result<U256, ReplayGuardError> {
+        Ok(self.nonce_bitmap.get(owner).word_at(word_pos))
+    }
+
+    /// Invalidates the unordered nonces of the caller set in `mask` at word position `word_pos`,
+    /// cancelling signatures not executed yet.
+    ///
+    /// Emits an {UnorderedNonceInvalidation} event.
+    #[selector(name = "invalidateUnorderedNonces")]
+    pub fn invalidate_unordered_nonces(&mut self, word_pos: U256, mask: U256) -> Result<(), ReplayGuardError> {
+        let owner = msg::sender();
+        self.nonce_bitmap.setter(owner).set_word_bits(word_pos, mask);
+
+        evm::log(UnorderedNonceInvalidation {
+            owner,
+            word: word_pos,
+            mask,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,94 @@
+//! Recovery of assets sent to a contract by mistake: ERC20 tokens (through `safe_erc20`, so
+//! tokens returning nothing are supported), ether, and the contract's own balance of the token
+//! it implements.
+//!
+//! The functions move whatever the contract holds, they are internal and the composing
+//! contract exposes them behind its own authorization (e.g. `Ownable::only_owner`). Contracts
+//! holding assets on behalf of users (vaults, wrappers, sales) must refuse to rescue those,
+//! e.g. a wrapper rejects its underlying. See `examples/compose_rescue.rs`.
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    alloy_sol_types::{sol, SolError},
+    call, contract, evm,
+};
+
+use crate::tokens::erc20::{Erc20, Erc20Error, Erc20Params};
+use crate::utils::safe_erc20::{self, SafeErc20Error};
+
+sol! {
+    event Erc20Rescued(address indexed token, address indexed to, uint256 amount);
+    event EthRescued(address indexed to, uint256 amount);
+
+    /// Indicates that the contract holds less ether than a rescue sends.
+    /// * `balance` - ether held by the contract.
+    /// * `needed` - ether to send.
+    error InsufficientBalance(uint256 balance, uint256 needed);
+
+    /// Indicates that the receiver of rescued ether reverted without a reason.
+    error FailedCall();
+}
+
+pub enum RescueError {
+    InsufficientBalance(InsufficientBalance),
+    FailedCall(FailedCall),
+    /// revert data of the ether receiver, passed through
+    Reverted(Vec<u8>),
+}
+
+impl From<RescueError> for Vec<u8> {
+    fn from(e: RescueError) -> Vec<u8> {
+        match e {
+            RescueError::InsufficientBalance(e) => e.encode(),
+            RescueError::FailedCall(e) => e.encode(),
+            RescueError::Reverted(data) => data,
+        }
+    }
+}
+
+crate::errors::error_helpers!(RescueError {
+    InsufficientBalance,
+    FailedCall,
+} passthrough { Reverted });
+
+/// Sends `amount` of the ERC20 `token` held by this contract to `to`. For the token the
+/// contract implements itself use {rescue_own_tokens}, calling itself would revert.
+///
+/// Emits an {Erc20Rescued} event.
+pub fn rescue_erc20(token: Address, to: Address, amount: U256) -> Result<(), SafeErc20Error> {
+    safe_erc20::safe_transfer(token, to, amount)?;
+    evm::log(Erc20Rescued { token, to, amount });
+    Ok(())
+}
+
+/// Sends `amount` wei held by this contract to `to`, the receiver runs with all the gas left.
+///
+/// Emits an {EthRescued} event.
+pub fn rescue_eth(to: Address, amount: U256) -> Result<(), RescueError> {
+    let balance = contract::balance();
+    if balance < amount {
+        return Err(RescueError::InsufficientBalance(InsufficientBalance {
+            balance,
+            needed: amount,
+        }));
+    }
+    call::transfer_eth(to, amount).map_err(|data| {
+        if data.is_empty() {
+            RescueError::FailedCall(FailedCall {})
+        } else {
+            RescueError::Reverted(data)
+        }
+    })?;
+    evm::log(EthRescued { to, amount });
+    Ok(())
+}
+
+/// Sends `amount` of the tokens credited to the contract's own address in `erc20` to `to`.
+///
+/// Emits a {Transfer} and an {Erc20Rescued} event.
+pub fn rescue_own_tokens<T: Erc20Params>(erc20: &mut Erc20<T>, to: Address, amount: U256) -> Result<(), Erc20Error> {
+    let token = contract::address();
+    erc20.transfer_internal(token, to, amount)?;
+    evm::log(Erc20Rescued { token, to, amount });
+    Ok(())
+}
@@ -0,0 +1,71 @@
+//! Revert statistics for testnets: how often users hit each error (insufficient balance,
+//! cooldown, caps...), readable with a view instead of indexing failed transactions.
+//!
+//! Storage writes of a reverted call are rolled back, so a failure can only be counted if the
+//! external does not revert: with the `revert-stats` feature {RevertStats::soft_fail} records the
+//! error and turns it into a `false` return, as allowed by ERC20 for `transfer`/`transferFrom`.
+//! Only wrap operations failing before their first storage write, otherwise the writes done
+//! before the failure are kept. Without the feature `soft_fail` returns the result unchanged
+//! and the struct is never written.
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{FixedBytes, U256},
+    prelude::*,
+};
+
+sol_storage! {
+    /// Failure counters keyed by the selector of the error, compose it in testnet builds of a
+    /// token and wrap the externals with `soft_fail`.
+    /// See `examples/compose_revert_stats.rs` for a complete composition.
+    pub struct RevertStats {
+        /// error selector => failures
+        mapping(bytes4 => uint256) counts;
+        /// failures of all errors
+        uint256 total;
+    }
+}
+
+impl RevertStats {
+    /// Counts `error` under its first 4 bytes (`revert-stats` builds only), raw string errors
+    /// are counted under their first 4 characters.
+    pub fn record(&mut self, error: &[u8]) {
+        if cfg!(feature = "revert-stats") {
+            let mut selector = [0u8; 4];
+            let len = error.len().min(4);
+            selector[..len].copy_from_slice(&error[..len]);
+
+            let mut count_ref = self.counts.setter(FixedBytes(selector));
+            let count = count_ref.get() + U256::from(1);
+            count_ref.set(count);
+            let total = self.total.get() + U256::from(1);
+            self.total.set(total);
+        }
+    }
+
+    /// With `revert-stats`, records a failed `result` and returns `Ok(false)` instead of
+    /// reverting; otherwise returns `result` as is.
+    pub fn soft_fail(&mut self, result: Result<bool, Vec<u8>>) -> Result<bool, Vec<u8>> {
+        match result {
+            Err(error) if cfg!(feature = "revert-stats") => {
+                self.record(&error);
+                Ok(false)
+            }
+            result => result,
+        }
+    }
+}
+
+#[external]
+impl RevertStats {
+    /// Returns the number of failures with the error of `selector`.
+    #[selector(name = "revertCount")]
+    pub fn revert_count(&self, selector: FixedBytes<4>) -> Result<U256, Vec<u8>> {
+        Ok(self.counts.get(selector))
+    }
+
+    /// Returns the number of failures of all errors.
+    #[selector(name = "totalReverts")]
+    pub fn total_reverts(&self) -> Result<U256, Vec<u8>> {
+        Ok(self.total.get())
+    }
+}
@@ -0,0 +1,177 @@
+//! Calls to external ERC20 tokens tolerating non compliant return values, plus `try_permit` for
+//! integrators accepting EIP-2612 permits.
+//!
+//! A permit submitted with a deposit can be read from the mempool and executed first by anyone:
+//! the allowance is set, but the deposit calling `permit` again then reverts on the used nonce.
+//! Call `try_permit` instead of `permit`, it only fails if the allowance is not there:
+//!
+//! ```ignore
+//! safe_erc20::try_permit(token, owner, contract::address(), amount, deadline, v, r, s)?;
+//! safe_erc20::safe_transfer_from(token, owner, contract::address(), amount)?;
+//! ```
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{Address, B256, U256},
+    alloy_sol_types::{sol, SolError},
+    call::{self, Call},
+    contract,
+    types::AddressVM,
+};
+
+use crate::interfaces::ierc20::IERC20;
+use crate::utils::eip712::{address_word, u256_word};
+
+/// `bytes4(keccak256("transfer(address,uint256)"))`
+const TRANSFER_SELECTOR: [u8; 4] = [0xa9, 0x05, 0x9c, 0xbb];
+/// `bytes4(keccak256("transferFrom(address,address,uint256)"))`
+const TRANSFER_FROM_SELECTOR: [u8; 4] = [0x23, 0xb8, 0x72, 0xdd];
+/// `bytes4(keccak256("approve(address,uint256)"))`
+const APPROVE_SELECTOR: [u8; 4] = [0x09, 0x5e, 0xa7, 0xb3];
+/// `bytes4(keccak256("permit(address,address,uint256,uint256,uint8,bytes32,bytes32)"))`
+const PERMIT_SELECTOR: [u8; 4] = [0xd5, 0x05, 0xac, 0xcf];
+
+sol! {
+    /// An operation with an ERC20 token failed.
+    /// * `token` - address of the called token.
+    error SafeErc20FailedOperation(address token);
+}
+
+pub enum SafeErc20Error {
+    SafeErc20FailedOperation(SafeErc20FailedOperation),
+}
+
+impl From<SafeErc20Error> for Vec<u8> {
+    fn from(e: SafeErc20Error) -> Vec<u8> {
+        match e {
+            SafeErc20Error::SafeErc20FailedOperation(e) => e.encode(),
+        }
+    }
+}
+
+crate::errors::error_helpers!(SafeErc20Error {
+    SafeErc20FailedOperation,
+});
+
+/// Transfers `value` tokens of this contract to `to`, reverting if the token reverts or returns `false`.
+/// Tokens returning nothing (e.g. USDT) are supported.
+pub fn safe_transfer(token: Address, to: Address, value: U256) -> Result<(), SafeErc20Error> {
+    call_optional_return(token, &transfer_calldata(to, value))
+}
+
+/// Transfers `value` tokens from `from` to `to` using this contract's allowance, reverting if the
+/// token reverts or returns `false`.
+pub fn safe_transfer_from(token: Address, from: Address, to: Address, value: U256) -> Result<(), SafeErc20Error> {
+    call_optional_return(token, &transfer_from_calldata(from, to, value))
+}
+
+/// Sets the allowance of `spender` over this contract's tokens to `value`, resetting it to 0
+/// first for tokens requiring it (e.g. USDT).
+pub fn force_approve(token: Address, spender: Address, value: U256) -> Result<(), SafeErc20Error> {
+    if call_optional_return(token, &approve_calldata(spender, value)).is_err() {
+        call_optional_return(token, &approve_calldata(spender, U256::ZERO))?;
+        call_optional_return(token, &approve_calldata(spender, value))?;
+    }
+    Ok(())
+}
+
+/// ABI encoded `transfer(to, value)` call.
+pub fn transfer_calldata(to: Address, value: U256) -> Vec<u8> {
+    let mut data = TRANSFER_SELECTOR.to_vec();
+    data.extend_from_slice(address_word(to).as_slice());
+    data.extend_from_slice(u256_word(value).as_slice());
+    data
+}
+
+/// ABI encoded `transferFrom(from, to, value)` call.
+pub fn transfer_from_calldata(from: Address, to: Address, value: U256) -> Vec<u8> {
+    let mut data = TRANSFER_FROM_SELECTOR.to_vec();
+    data.extend_from_slice(address_word(from).as_slice());
+    data.extend_from_slice(address_word(to).as_slice());
+    data.extend_from_slice(u256_word(value).as_slice());
+    data
+}
+
+/// ABI encoded `approve(spender, value)` call.
+pub fn approve_calldata(spender: Address, value: U256) -> Vec<u8> {
+    let mut data = APPROVE_SELECTOR.to_vec();
+    data.extend_from_slice(address_word(spender).as_slice());
+    data.extend_from_slice(u256_word(value).as_slice());
+    data
+}
+
+/// Balance of `account` in `token`.
+pub fn balance_of(token: Address, account: Address) -> Result<U256, SafeErc20Error> {
+    IERC20::new(token).balance_of(Call::new(), account).map_err(|_| failed(token))
+}
+
+/// Allowance of `spender` over `owner`'s tokens in `token`.
+pub fn allowance(token: Address, owner: Address, spender: Address) -> Result<U256, SafeErc20Error> {
+    IERC20::new(token).allowance(Call::new(), owner, spender).map_err(|_| failed(token))
+}
+
+/// Calls the EIP-2612 `permit` of `token`, ignoring its failure if the allowance of `spender`
+/// over `owner`'s tokens already covers `value` (e.g. the permit was frontrun). Reverts only
+/// when the allowance is missing, with the token's own error lost.
+#[allow(clippy::too_many_arguments)]
+pub fn try_permit(
+    token: Address,
+    owner: Address,
+    spender: Address,
+    value: U256,
+    deadline: U256,
+    v: u8,
+    r: B256,
+    s: B256,
+) -> Result<(), SafeErc20Error> {
+    let mut data = PERMIT_SELECTOR.to_vec();
+    data.extend_from_slice(address_word(owner).as_slice());
+    data.extend_from_slice(address_word(spender).as_slice());
+    data.extend_from_slice(u256_word(value).as_slice());
+    data.extend_from_slice(u256_word(deadline).as_slice());
+    data.extend_from_slice(u256_word(U256::from(v)).as_slice());
+    data.extend_from_slice(r.as_slice());
+    data.extend_from_slice(s.as_slice());
+    let permitted = call::call(Call::new(), token, &data).is_ok();
+    permit_outcome(token, permitted, value, || allowance(token, owner, spender))
+}
+
+/// Decision of {try_permit} once `permit` was called: a successful permit is enough, a failed
+/// one is accepted if `current_allowance` (read only in that case) covers `value`.
+pub fn permit_outcome(
+    token: Address,
+    permitted: bool,
+    value: U256,
+    current_allowance: impl FnOnce() -> Result<U256, SafeErc20Error>,
+) -> Result<(), SafeErc20Error> {
+    if permitted || current_allowance()? >= value {
+        return Ok(());
+    }
+    Err(failed(token))
+}
+
+/// Balance of this contract in `token`.
+pub fn self_balance(token: Address) -> Result<U256, SafeErc20Error> {
+    balance_of(token, contract::address())
+}
+
+/// Calls `token`, accepting either no return data (only if `token` has code) or an abi encoded `true`.
+fn call_optional_return(token: Address, data: &[u8]) -> Result<(), SafeErc20Error> {
+    let output = call::call(Call::new(), token, data).map_err(|_| failed(token))?;
+    if !optional_return_succeeded(&output, || token.has_code()) {
+        return Err(failed(token));
+    }
+    Ok(())
+}
+
+/// Whether a call which did not revert succeeded: an abi encoded `true`, or no return data from
+/// a token with code (`has_code`, read only in that case, tells an EOA which can not revert).
+pub fn optional_return_succeeded(output: &[u8], has_code: impl FnOnce() -> bool) -> bool {
+    if output.is_empty() {
+        return has_code();
+    }
+    output.len() >= 32 && U256::from_be_slice(&output[..32]) == U256::from(1)
+}
+
+fn failed(token: Address) -> SafeErc20Error {
+    SafeErc20Error::SafeErc20FailedOperation(SafeErc20FailedOperation { token })
+}
@@ -0,0 +1,37 @@
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{Address, B256, U256},
+    call::{self, Call},
+    types::AddressVM,
+};
+
+use crate::utils::ecdsa;
+use crate::utils::eip712::u256_word;
+
+/// `bytes4(keccak256("isValidSignature(bytes32,bytes)"))`, also the EIP-1271 magic value.
+pub const IS_VALID_SIGNATURE_SELECTOR: [u8; 4] = [0x16, 0x26, 0xba, 0x7e];
+
+/// Whether `signature` of `hash` is valid for `signer`: an ECDSA signature for EOAs, an EIP-1271
+/// `isValidSignature` check for contracts (smart wallets, multisigs). Never reverts.
+pub fn is_valid_signature_now(signer: Address, hash: B256, signature: &[u8]) -> bool {
+    if signer.has_code() {
+        return is_valid_erc1271_signature_now(signer, hash, signature);
+    }
+    matches!(ecdsa::recover_bytes(hash, signature), Ok(recovered) if recovered == signer)
+}
+
+/// Whether `signer.isValidSignature(hash, signature)` returns the EIP-1271 magic value.
+pub fn is_valid_erc1271_signature_now(signer: Address, hash: B256, signature: &[u8]) -> bool {
+    let mut data: Vec<u8> = IS_VALID_SIGNATURE_SELECTOR.to_vec();
+    data.extend_from_slice(hash.as_slice());
+    // offset of the `bytes signature` tail, after the 2 head words
+    data.extend_from_slice(u256_word(U256::from(2 * 32)).as_slice());
+    data.extend_from_slice(u256_word(U256::from(signature.len())).as_slice());
+    data.extend_from_slice(signature);
+    data.resize(data.len() + (32 - signature.len() % 32) % 32, 0);
+
+    match call::static_call(Call::new(), signer, &data) {
+        Ok(output) => output.len() >= 32 && output[..4] == IS_VALID_SIGNATURE_SELECTOR,
+        Err(_) => false,
+    }
+}
@@ -0,0 +1,52 @@
+use stylus_sdk::{
+    alloy_primitives::{Address, B256, U256},
+    crypto::keccak,
+};
+
+use crate::utils::eip712::{address_word, u256_word};
+
+/// Offset of `Erc20::balances` from the first slot of the `Erc20`.
+pub const ERC20_BALANCES_OFFSET: u64 = 0;
+/// Offset of `Erc20::allowances` (owner => epoch => spender => value).
+pub const ERC20_ALLOWANCES_OFFSET: u64 = 1;
+/// Offset of `Erc20::allowance_epochs`.
+pub const ERC20_ALLOWANCE_EPOCHS_OFFSET: u64 = 2;
+/// Offset of `Erc20::total_supply`.
+pub const ERC20_TOTAL_SUPPLY_OFFSET: u64 = 3;
+/// Offset of `Erc20::allowance_spend_ids` (owner => spender => last spend id).
+pub const ERC20_ALLOWANCE_SPEND_IDS_OFFSET: u64 = 4;
+
+/// Slot of the value at `key` in a mapping rooted at `slot`, as laid out by Solidity and
+/// `sol_storage!`: `keccak256(key ‖ slot)`, `key` being its 32 bytes ABI encoding.
+pub fn mapping_slot(key: B256, slot: U256) -> U256 {
+    let mut buf = [0u8; 64];
+    buf[..32].copy_from_slice(key.as_slice());
+    buf[32..].copy_from_slice(u256_word(slot).as_slice());
+    U256::from_be_bytes(keccak(buf).0)
+}
+
+/// Storage key of the balance of `account` in a token whose `Erc20` starts at `base_slot`,
+/// to be passed to `eth_getProof`/`eth_getStorageAt`.
+pub fn erc20_balance_slot(base_slot: U256, account: Address) -> B256 {
+    let slot = mapping_slot(address_word(account), base_slot + U256::from(ERC20_BALANCES_OFFSET));
+    B256::from(slot.to_be_bytes::<32>())
+}
+
+/// Storage key of the allowance of `spender` over `owner`'s tokens in allowance `epoch`, only
+/// the current epoch of `owner` (see `allowanceEpoch`) holds valid allowances.
+pub fn erc20_allowance_slot(base_slot: U256, owner: Address, epoch: U256, spender: Address) -> B256 {
+    let owner_slot = mapping_slot(address_word(owner), base_slot + U256::from(ERC20_ALLOWANCES_OFFSET));
+    let epoch_slot = mapping_slot(u256_word(epoch), owner_slot);
+    B256::from(mapping_slot(address_word(spender), epoch_slot).to_be_bytes::<32>())
+}
+
+/// Storage key of the allowance epoch of `owner`.
+pub fn erc20_allowance_epoch_slot(base_slot: U256, owner: Address) -> B256 {
+    let slot = mapping_slot(address_word(owner), base_slot + U256::from(ERC20_ALLOWANCE_EPOCHS_OFFSET));
+    B256::from(slot.to_be_bytes::<32>())
+}
+
+/// Storage key of the total supply.
+pub fn erc20_total_supply_slot(base_slot: U256) -> B256 {
+    B256::from((base_slot + U256::from(ERC20_TOTAL_SUPPLY_OFFSET)).to_be_bytes::<32>())
+}
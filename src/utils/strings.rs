@@ -0,0 +1,34 @@
+//! String building without `format!`: the formatting machinery adds several KB to a Stylus
+//! program, these helpers size the buffer once and push bytes, which MiniAlloc (a bump
+//! allocator never freeing) handles with a single allocation.
+use alloc::{string::String, vec::Vec};
+use stylus_sdk::alloy_primitives::U256;
+
+/// Digits of `type(uint256).max` in base 10.
+const MAX_DECIMAL_DIGITS: usize = 78;
+
+/// Base 10 representation of `value`, e.g. `"42"`.
+pub fn to_decimal(value: U256) -> String {
+    if value == U256::ZERO {
+        return String::from("0");
+    }
+    let ten = U256::from(10);
+    let mut digits = Vec::with_capacity(MAX_DECIMAL_DIGITS);
+    let mut rest = value;
+    while rest != U256::ZERO {
+        digits.push(b'0' + (rest % ten).to::<u8>());
+        rest /= ten;
+    }
+    digits.reverse();
+    // only ASCII digits were pushed
+    String::from_utf8(digits).unwrap_or_default()
+}
+
+/// `parts` joined without separator, allocated once.
+pub fn concat(parts: &[&str]) -> String {
+    let mut joined = String::with_capacity(parts.iter().map(|part| part.len()).sum());
+    for part in parts {
+        joined.push_str(part);
+    }
+    joined
+}
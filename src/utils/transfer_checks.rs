@@ -0,0 +1,164 @@
+//! Receiver checks of the safe transfers of `Erc721` and `Erc1155`: when the receiver is a
+//! contract it is called with `onERC721Received` / `onERC1155Received` /
+//! `onERC1155BatchReceived` and must return the selector of the function, as in OpenZeppelin
+//! `ERC721Utils` / `ERC1155Utils`. A receiver reverting with data bubbles its reason, any other
+//! failure (empty revert, wrong or missing return value) reverts with the `InvalidReceiver`
+//! error of the token. Extensions transferring NFTs on their own reuse the same checks.
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    call::{self, Call},
+    types::AddressVM,
+};
+
+use crate::tokens::erc1155::{Erc1155Error, Erc1155InvalidReceiver};
+use crate::tokens::erc721::{Erc721Error, Erc721InvalidReceiver};
+use crate::utils::eip712::{address_word, u256_word};
+
+/// `bytes4(keccak256("onERC721Received(address,address,uint256,bytes)"))`
+pub const ON_ERC721_RECEIVED_SELECTOR: [u8; 4] = [0x15, 0x0b, 0x7a, 0x02];
+/// `bytes4(keccak256("onERC1155Received(address,address,uint256,uint256,bytes)"))`
+pub const ON_ERC1155_RECEIVED_SELECTOR: [u8; 4] = [0xf2, 0x3a, 0x6e, 0x61];
+/// `bytes4(keccak256("onERC1155BatchReceived(address,address,uint256[],uint256[],bytes)"))`
+pub const ON_ERC1155_BATCH_RECEIVED_SELECTOR: [u8; 4] = [0xbc, 0x19, 0x7c, 0x81];
+
+/// Calls `onERC721Received` on `to` if it is a contract, reverting unless it returns the
+/// function selector.
+pub fn check_on_erc721_received(
+    operator: Address,
+    from: Address,
+    to: Address,
+    token_id: U256,
+    data: &[u8],
+) -> Result<(), Vec<u8>> {
+    if !to.has_code() {
+        return Ok(());
+    }
+    call_receiver(
+        to,
+        &erc721_received_calldata(operator, from, token_id, data),
+        ON_ERC721_RECEIVED_SELECTOR,
+        Erc721Error::Erc721InvalidReceiver(Erc721InvalidReceiver { receiver: to }).into(),
+    )
+}
+
+/// Calls `onERC1155Received` on `to` if it is a contract, reverting unless it returns the
+/// function selector.
+pub fn check_on_erc1155_received(
+    operator: Address,
+    from: Address,
+    to: Address,
+    id: U256,
+    value: U256,
+    data: &[u8],
+) -> Result<(), Vec<u8>> {
+    if !to.has_code() {
+        return Ok(());
+    }
+    call_receiver(
+        to,
+        &erc1155_received_calldata(operator, from, id, value, data),
+        ON_ERC1155_RECEIVED_SELECTOR,
+        erc1155_invalid_receiver(to),
+    )
+}
+
+/// Batched version of {check_on_erc1155_received} calling `onERC1155BatchReceived`.
+pub fn check_on_erc1155_batch_received(
+    operator: Address,
+    from: Address,
+    to: Address,
+    ids: &[U256],
+    values: &[U256],
+    data: &[u8],
+) -> Result<(), Vec<u8>> {
+    if !to.has_code() {
+        return Ok(());
+    }
+    call_receiver(
+        to,
+        &erc1155_batch_received_calldata(operator, from, ids, values, data),
+        ON_ERC1155_BATCH_RECEIVED_SELECTOR,
+        erc1155_invalid_receiver(to),
+    )
+}
+
+/// Calldata of `onERC721Received(operator, from, token_id, data)`.
+pub fn erc721_received_calldata(operator: Address, from: Address, token_id: U256, data: &[u8]) -> Vec<u8> {
+    let mut calldata = ON_ERC721_RECEIVED_SELECTOR.to_vec();
+    calldata.extend_from_slice(address_word(operator).as_slice());
+    calldata.extend_from_slice(address_word(from).as_slice());
+    calldata.extend_from_slice(u256_word(token_id).as_slice());
+    // offset of the `bytes data` tail, after the 4 head words
+    calldata.extend_from_slice(u256_word(U256::from(4 * 32)).as_slice());
+    encode_bytes(&mut calldata, data);
+    calldata
+}
+
+/// Calldata of `onERC1155Received(operator, from, id, value, data)`.
+pub fn erc1155_received_calldata(operator: Address, from: Address, id: U256, value: U256, data: &[u8]) -> Vec<u8> {
+    let mut calldata = ON_ERC1155_RECEIVED_SELECTOR.to_vec();
+    calldata.extend_from_slice(address_word(operator).as_slice());
+    calldata.extend_from_slice(address_word(from).as_slice());
+    calldata.extend_from_slice(u256_word(id).as_slice());
+    calldata.extend_from_slice(u256_word(value).as_slice());
+    // offset of the `bytes data` tail, after the 5 head words
+    calldata.extend_from_slice(u256_word(U256::from(5 * 32)).as_slice());
+    encode_bytes(&mut calldata, data);
+    calldata
+}
+
+/// Calldata of `onERC1155BatchReceived(operator, from, ids, values, data)`.
+pub fn erc1155_batch_received_calldata(
+    operator: Address,
+    from: Address,
+    ids: &[U256],
+    values: &[U256],
+    data: &[u8],
+) -> Vec<u8> {
+    // tails follow the 5 head words: ids, values (length word plus elements each), then data
+    let ids_offset = 5 * 32;
+    let values_offset = ids_offset + 32 * (1 + ids.len());
+    let data_offset = values_offset + 32 * (1 + values.len());
+
+    let mut calldata = ON_ERC1155_BATCH_RECEIVED_SELECTOR.to_vec();
+    calldata.extend_from_slice(address_word(operator).as_slice());
+    calldata.extend_from_slice(address_word(from).as_slice());
+    for offset in [ids_offset, values_offset, data_offset] {
+        calldata.extend_from_slice(u256_word(U256::from(offset)).as_slice());
+    }
+    for array in [ids, values] {
+        calldata.extend_from_slice(u256_word(U256::from(array.len())).as_slice());
+        for element in array {
+            calldata.extend_from_slice(u256_word(*element).as_slice());
+        }
+    }
+    encode_bytes(&mut calldata, data);
+    calldata
+}
+
+/// Whether the return data of a receiver hook accepts the transfer: an ABI encoded `bytes4`
+/// equal to `selector`.
+pub fn is_accepted(output: &[u8], selector: [u8; 4]) -> bool {
+    output.len() >= 4 && output[..4] == selector
+}
+
+/// Appends the ABI tail of `bytes data`: its length then the data right padded to 32 bytes.
+pub(crate) fn encode_bytes(calldata: &mut Vec<u8>, data: &[u8]) {
+    calldata.extend_from_slice(u256_word(U256::from(data.len())).as_slice());
+    calldata.extend_from_slice(data);
+    calldata.resize(calldata.len() + (32 - data.len() % 32) % 32, 0);
+}
+
+fn erc1155_invalid_receiver(to: Address) -> Vec<u8> {
+    Erc1155Error::Erc1155InvalidReceiver(Erc1155InvalidReceiver { receiver: to }).into()
+}
+
+fn call_receiver(to: Address, calldata: &[u8], selector: [u8; 4], invalid_receiver: Vec<u8>) -> Result<(), Vec<u8>> {
+    match call::call(Call::new(), to, calldata) {
+        Ok(output) if is_accepted(&output, selector) => Ok(()),
+        Ok(_) => Err(invalid_receiver),
+        Err(call::Error::Revert(reason)) if !reason.is_empty() => Err(reason),
+        Err(_) => Err(invalid_receiver),
+    }
+}
@@ -0,0 +1,85 @@
+//! Event and error declarations exported with `utils::abi_export`, checked against their `sol!`
+//! types and parsed the way ethers bindings read them.
+use ethers::abi::{self, Abi};
+use oz_stylus_erc::tokens::erc1155::Erc1155;
+use oz_stylus_erc::tokens::erc20::{Erc20, Erc20InsufficientBalance, Erc20Params, EventMode, NoTransferHook};
+use oz_stylus_erc::tokens::erc721::Erc721;
+use oz_stylus_erc::tokens::my_token::MyToken;
+use oz_stylus_erc::utils::abi_export::{canonical_signature, check_abi_items, human_readable_abi, AbiItems};
+use stylus_sdk::alloy_sol_types::SolError;
+
+struct Nft;
+
+impl oz_stylus_erc::tokens::erc721::Erc721Params for Nft {
+    const NAME: &'static str = "Nft";
+    const SYMBOL: &'static str = "NFT";
+}
+
+struct Items;
+
+impl oz_stylus_erc::tokens::erc1155::Erc1155Params for Items {
+    const URI: &'static str = "https://example.com/{id}.json";
+}
+
+struct Private;
+
+impl Erc20Params for Private {
+    const NAME: &'static str = "Private";
+    const SYMBOL: &'static str = "PRV";
+    const DECIMALS: u8 = 18;
+    const EVENT_MODE: EventMode = EventMode::Unindexed;
+    const ALLOWANCE_SPEND_IDS: bool = true;
+    type Hook = NoTransferHook;
+}
+
+fn parse(lines: &[String]) -> Abi {
+    let lines: Vec<&str> = lines.iter().map(String::as_str).collect();
+    abi::parse_abi(&lines).unwrap()
+}
+
+#[test]
+fn declarations_match_their_sol_types() {
+    check_abi_items(&MyToken::abi_items()).unwrap();
+    check_abi_items(&Erc20::<Private>::abi_items()).unwrap();
+    check_abi_items(&Erc721::<Nft>::abi_items()).unwrap();
+    check_abi_items(&Erc1155::<Items>::abi_items()).unwrap();
+}
+
+#[test]
+fn canonical_signature_drops_names_and_modifiers() {
+    assert_eq!(
+        canonical_signature("event URI(string value, uint256 indexed id)"),
+        "URI(string,uint256)"
+    );
+    assert_eq!(canonical_signature("error FailedDeployment()"), "FailedDeployment()");
+    assert_eq!(
+        canonical_signature("function transfer(address to, uint256 value) external returns (bool);"),
+        "transfer(address,uint256)"
+    );
+}
+
+#[test]
+fn a_stale_declaration_is_reported() {
+    let mut items = MyToken::abi_items();
+    items[0].declaration = "event Transfer(address indexed from, address indexed to)";
+    assert!(check_abi_items(&items).is_err());
+}
+
+#[test]
+fn exported_abi_decodes_events_and_errors() {
+    let interface = "interface IMyToken {\n    function balanceOf(address account) external view returns (uint256);\n}";
+    let abi = parse(&human_readable_abi(interface, &MyToken::abi_items()));
+
+    assert_eq!(abi.function("balanceOf").unwrap().inputs.len(), 1);
+    let transfer = abi.event("Transfer").unwrap();
+    assert!(transfer.inputs[0].indexed && !transfer.inputs[2].indexed);
+    let error = &abi.errors["Erc20InsufficientBalance"][0];
+    assert_eq!(error.selector(), Erc20InsufficientBalance::SELECTOR);
+}
+
+#[test]
+fn unindexed_event_mode_declares_unindexed_accounts() {
+    let abi = parse(&human_readable_abi("", &Erc20::<Private>::abi_items()));
+    assert!(abi.event("Transfer").unwrap().inputs.iter().all(|input| !input.indexed));
+    assert!(abi.event("AllowanceSpent").is_ok());
+}
@@ -0,0 +1,82 @@
+//! Golden file check of the function selectors exported by the entrypoint token, so renaming a
+//! method in Rust can not silently change the ABI.
+//!
+//! Run with `cargo test --features export-abi --test abi_selectors`, after an intended ABI change
+//! regenerate the golden file with `UPDATE_GOLDEN=1`.
+#![cfg(feature = "export-abi")]
+
+use ethers::utils::keccak256;
+use std::process::Command;
+
+const GOLDEN_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/my_token.selectors");
+
+/// Set to regenerate the golden file instead of checking it.
+const UPDATE_GOLDEN: &str = "UPDATE_GOLDEN";
+
+#[test]
+fn exported_selectors_match_golden_file() {
+    let abi = export_abi();
+    let actual = selectors(&abi);
+
+    if std::env::var(UPDATE_GOLDEN).is_ok() {
+        std::fs::write(GOLDEN_PATH, &actual).unwrap();
+        return;
+    }
+    let expected = std::fs::read_to_string(GOLDEN_PATH).unwrap();
+    assert_eq!(
+        actual, expected,
+        "exported selectors differ from {}, rerun with {}=1 if the ABI change is intended",
+        GOLDEN_PATH, UPDATE_GOLDEN
+    );
+}
+
+/// Solidity interface printed by the `erc20` binary with `export-abi`.
+fn export_abi() -> String {
+    let manifest_dir = env!("CARGO_MANIFEST_DIR");
+    // separate target dir, the one of `cargo test` is locked while tests run
+    let target_dir = format!("{}/target/abi-export", manifest_dir);
+    let output = Command::new(env!("CARGO"))
+        .current_dir(manifest_dir)
+        .args(["run", "--quiet", "--features", "export-abi", "--bin", "erc20"])
+        .args(["--target-dir", &target_dir])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "export-abi failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).unwrap()
+}
+
+/// One `0x<selector> <signature>` line per exported function, sorted by signature.
+fn selectors(abi: &str) -> String {
+    let mut signatures: Vec<String> = abi
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("function "))
+        .map(canonical_signature)
+        .collect();
+    signatures.sort();
+    signatures.dedup();
+
+    signatures
+        .iter()
+        .map(|signature| {
+            let hash = keccak256(signature.as_bytes());
+            format!("0x{} {}\n", hex::encode(&hash[..4]), signature)
+        })
+        .collect()
+}
+
+/// `transferFrom(address from, address to, uint256 value) external returns (bool);`
+/// -> `transferFrom(address,address,uint256)`
+fn canonical_signature(declaration: &str) -> String {
+    let open = declaration.find('(').unwrap();
+    let close = declaration[open..].find(')').unwrap() + open;
+    let name = &declaration[..open];
+    let types: Vec<&str> = declaration[open + 1..close]
+        .split(',')
+        .filter_map(|param| param.split_whitespace().next())
+        .collect();
+    format!("{}({})", name, types.join(","))
+}
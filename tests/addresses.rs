@@ -0,0 +1,84 @@
+//! Known vectors for `utils::addresses`: EIP-55 and EIP-1014 examples, the usual CREATE
+//! addresses of `0x6ac7ea33f8831ea9dcc53393aaa88b25a785dbf0` and Arbitrum aliasing.
+use oz_stylus_erc::utils::addresses::{
+    apply_l1_to_l2_alias, create2_address, create_address, to_checksum, undo_l1_to_l2_alias,
+};
+use stylus_sdk::{
+    alloy_primitives::{Address, B256},
+    crypto::keccak,
+};
+use std::str::FromStr;
+
+fn address(s: &str) -> Address {
+    Address::from_str(s).unwrap()
+}
+
+#[test]
+fn checksum_matches_eip55_examples() {
+    for expected in [
+        "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+        "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+        "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+        "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+    ] {
+        assert_eq!(to_checksum(address(&expected.to_lowercase())), expected);
+    }
+}
+
+#[test]
+fn create_address_matches_known_deployments() {
+    let deployer = address("0x6ac7ea33f8831ea9dcc53393aaa88b25a785dbf0");
+    for (nonce, expected) in [
+        (0, "0xcd234a471b72ba2f1ccf0a70fcaba648a5eecd8d"),
+        (1, "0x343c43a37d37dff08ae8c4a11544c718abb4fcf8"),
+        (2, "0xf778b86fa74e846c4f0a1fbd1335fe81c00a0c91"),
+        (3, "0xfffd933a0bc612844eaf0c6fe3e5b8e9b6c1d19c"),
+        // nonces of 0x80 and more are encoded as a length prefixed byte string
+        (0x80, "0x08e190dcb7b73f5fcdabb43e102215c83659a76d"),
+        (0x10000, "0xf666a819b370d38f44f2573464da3fba8479b917"),
+    ] {
+        assert_eq!(create_address(deployer, nonce), address(expected), "nonce {nonce}");
+    }
+}
+
+#[test]
+fn create2_address_matches_eip1014_examples() {
+    let zero_salt = B256::ZERO;
+    assert_eq!(
+        create2_address(Address::ZERO, zero_salt, keccak([0x00])),
+        address("0x4D1A2e2bB4F88F0250f26Ffff098B0b30B26BF38")
+    );
+    assert_eq!(
+        create2_address(address("0xdeadbeef00000000000000000000000000000000"), zero_salt, keccak([0x00])),
+        address("0xB928f69Bb1D91Cd65274e3c79d8986362984fDA3")
+    );
+    assert_eq!(
+        create2_address(
+            address("0x00000000000000000000000000000000deadbeef"),
+            B256::from_str("0x00000000000000000000000000000000000000000000000000000000cafebabe").unwrap(),
+            keccak([0xde, 0xad, 0xbe, 0xef])
+        ),
+        address("0x60f3f640a8508fc6a86d45df051962668e1e8ac7")
+    );
+}
+
+#[test]
+fn alias_adds_arbitrum_offset() {
+    assert_eq!(
+        apply_l1_to_l2_alias(Address::ZERO),
+        address("0x1111000000000000000000000000000000001111")
+    );
+    assert_eq!(
+        apply_l1_to_l2_alias(address("0x0000000000000000000000000000000000000001")),
+        address("0x1111000000000000000000000000000000001112")
+    );
+}
+
+#[test]
+fn alias_wraps_and_round_trips() {
+    let max = address("0xffffffffffffffffffffffffffffffffffffffff");
+    assert_eq!(apply_l1_to_l2_alias(max), address("0x1111000000000000000000000000000000001110"));
+    for l1 in [Address::ZERO, max, address("0x6ac7ea33f8831ea9dcc53393aaa88b25a785dbf0")] {
+        assert_eq!(undo_l1_to_l2_alias(apply_l1_to_l2_alias(l1)), l1);
+    }
+}
@@ -0,0 +1,71 @@
+//! Checks of the off-chain balances root helper (`tests/util/balances_root.rs`) used to produce
+//! `Erc20BalancesRoot` commitments, plus its run against the deployed token.
+use ethers::{
+    providers::Middleware,
+    types::{Address, U256},
+};
+use std::sync::Arc;
+
+mod util;
+use util::balances_root::{balance_leaf, balances_at, merkle_proof, merkle_root, verify_proof};
+use util::fixture_init;
+
+fn sample_balances(count: u64) -> Vec<(Address, U256)> {
+    (1..=count)
+        .map(|i| (Address::from_low_u64_be(i), U256::from(i * 1_000)))
+        .collect()
+}
+
+#[test]
+fn single_balance_root_is_its_leaf() {
+    let balances = sample_balances(1);
+    let (account, balance) = balances[0];
+    assert_eq!(merkle_root(&balances), balance_leaf(account, balance));
+}
+
+#[test]
+fn root_does_not_depend_on_balances_order() {
+    let balances = sample_balances(7);
+    let mut reversed = balances.clone();
+    reversed.reverse();
+    assert_eq!(merkle_root(&balances), merkle_root(&reversed));
+}
+
+#[test]
+fn root_changes_with_any_balance() {
+    let balances = sample_balances(5);
+    let mut changed = balances.clone();
+    changed[3].1 += U256::one();
+    assert_ne!(merkle_root(&balances), merkle_root(&changed));
+}
+
+#[test]
+fn every_balance_has_a_valid_proof() {
+    for count in 1..=9 {
+        let balances = sample_balances(count);
+        let root = merkle_root(&balances);
+        for (account, balance) in &balances {
+            let proof = merkle_proof(&balances, *account, *balance).unwrap();
+            assert!(verify_proof(&proof, root, balance_leaf(*account, *balance)));
+        }
+        // a wrong balance does not verify with the proof of the right one
+        let (account, balance) = balances[0];
+        let proof = merkle_proof(&balances, account, balance).unwrap();
+        assert!(!verify_proof(&proof, root, balance_leaf(account, balance + U256::one())));
+    }
+}
+
+#[tokio::test]
+async fn deployed_token_balances_match_total_supply() {
+    let devnet = fixture_init::devnet().await.unwrap();
+    let token = devnet.my_token().await.unwrap();
+    let provider = Arc::new(devnet.provider.clone());
+    let block = provider.get_block_number().await.unwrap().as_u64();
+
+    let balances = balances_at(provider.clone(), token, block).await.unwrap();
+    let sum = balances.iter().fold(U256::zero(), |sum, (_, balance)| sum + balance);
+
+    let contract = util::balances_root::BalanceOfToken::new(token, provider);
+    let total_supply = contract.total_supply().block(block).call().await.unwrap();
+    assert_eq!(sum, total_supply);
+}
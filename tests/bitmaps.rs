@@ -0,0 +1,99 @@
+//! Index packing of `utils::bitmaps`, same words as OpenZeppelin `BitMaps`, the bit updates of
+//! `Bitmap::get`, `set` and `unset`, and the unordered nonces of `ReplayGuard` kept in a `Bitmap`.
+//!
+//! The devnet test needs `examples/compose_signature_transfer.rs` in
+//! `STYLUS_PROGRAM_ADDRESS_SIGNATURE_TRANSFER`, then `cargo test --test bitmaps -- --ignored`.
+use ethers::{
+    middleware::SignerMiddleware,
+    prelude::abigen,
+    providers::{Http, Provider},
+    signers::{LocalWallet, Signer},
+    types::U256 as EthersU256,
+};
+use oz_stylus_erc::utils::bitmaps::{bucket_and_mask, is_set_in, with_bit};
+use stylus_sdk::alloy_primitives::U256;
+
+mod util;
+use util::fixture_init;
+
+abigen!(
+    SignatureToken,
+    r#"[
+        function nonceBitmap(address owner, uint256 word_pos) external view returns (uint256)
+        function invalidateUnorderedNonces(uint256 word_pos, uint256 mask) external
+    ]"#
+);
+
+#[test]
+fn indexes_of_a_word_share_its_bucket() {
+    assert_eq!(bucket_and_mask(U256::ZERO), (U256::ZERO, U256::from(1)));
+    assert_eq!(bucket_and_mask(U256::from(255)), (U256::ZERO, U256::from(1) << 255));
+    assert_eq!(bucket_and_mask(U256::from(256)), (U256::from(1), U256::from(1)));
+    assert_eq!(bucket_and_mask(U256::from(515)), (U256::from(2), U256::from(8)));
+}
+
+#[test]
+fn largest_index_is_the_top_bit_of_the_last_bucket() {
+    assert_eq!(bucket_and_mask(U256::MAX), (U256::MAX >> 8, U256::from(1) << 255));
+}
+
+#[test]
+fn every_index_of_a_word_has_its_own_bit() {
+    let mut word = U256::ZERO;
+    for index in 512..768u64 {
+        let (bucket, mask) = bucket_and_mask(U256::from(index));
+        assert_eq!(bucket, U256::from(2));
+        assert_eq!(word & mask, U256::ZERO);
+        word |= mask;
+    }
+    assert_eq!(word, U256::MAX);
+}
+
+#[test]
+fn set_bits_are_read_back() {
+    let index = U256::from(515);
+    let word = with_bit(U256::ZERO, index, true);
+
+    assert_eq!(word, U256::from(8));
+    assert!(is_set_in(word, index));
+    // same bit of another bucket
+    assert!(is_set_in(word, U256::from(3)));
+    assert!(!is_set_in(word, U256::from(514)));
+    assert!(!is_set_in(U256::ZERO, index));
+}
+
+#[test]
+fn set_and_unset_only_touch_their_bit() {
+    let neighbours = U256::from(0b1010);
+    let index = U256::from(256);
+
+    let set = with_bit(neighbours, index, true);
+    assert_eq!(set, U256::from(0b1011));
+    // setting twice is a no-op
+    assert_eq!(with_bit(set, index, true), set);
+
+    let unset = with_bit(set, index, false);
+    assert_eq!(unset, neighbours);
+    assert_eq!(with_bit(unset, index, false), neighbours);
+    assert_eq!(with_bit(U256::MAX, U256::MAX, false), U256::MAX >> 1);
+}
+
+#[tokio::test]
+#[ignore = "needs examples/compose_signature_transfer.rs in STYLUS_PROGRAM_ADDRESS_SIGNATURE_TRANSFER"]
+async fn invalidated_unordered_nonces_are_set_in_the_bitmap() -> eyre::Result<()> {
+    let devnet = fixture_init::devnet().await?;
+    let address = devnet.program("signature_transfer").await?;
+    let token: SignatureToken<SignerMiddleware<Provider<Http>, LocalWallet>> =
+        SignatureToken::new(address, devnet.alice_client.clone());
+    let alice = devnet.alice_client.address();
+    // a word no other test uses
+    let word_pos = EthersU256::from(0xb17);
+
+    let before = token.nonce_bitmap(alice, word_pos).call().await?;
+    token.invalidate_unordered_nonces(word_pos, EthersU256::from(0b101)).send().await?.await?;
+    token.invalidate_unordered_nonces(word_pos, EthersU256::from(0b10)).send().await?.await?;
+
+    assert_eq!(token.nonce_bitmap(alice, word_pos).call().await?, before | EthersU256::from(0b111));
+    assert_eq!(token.nonce_bitmap(alice, word_pos + 1).call().await?, EthersU256::zero());
+    Ok(())
+}
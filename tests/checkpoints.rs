@@ -0,0 +1,55 @@
+//! Binary searches of `utils::checkpoints`, run over in-memory keys.
+use oz_stylus_erc::utils::checkpoints::{lower_bound, upper_bound, ClockMode};
+use stylus_sdk::alloy_primitives::U256;
+
+fn search(keys: &[u64], key: u64, bound: fn(U256, U256, &dyn Fn(U256) -> U256) -> U256) -> usize {
+    let key_at = |position: U256| U256::from(keys[position.to::<usize>()]);
+    bound(U256::from(keys.len()), U256::from(key), &key_at).to::<usize>()
+}
+
+fn upper(keys: &[u64], key: u64) -> usize {
+    search(keys, key, |length, key, key_at| upper_bound(length, key, key_at))
+}
+
+fn lower(keys: &[u64], key: u64) -> usize {
+    search(keys, key, |length, key, key_at| lower_bound(length, key, key_at))
+}
+
+#[test]
+fn upper_bound_finds_the_first_later_checkpoint() {
+    let keys = [10, 20, 30];
+
+    assert_eq!(upper(&keys, 5), 0);
+    assert_eq!(upper(&keys, 10), 1);
+    assert_eq!(upper(&keys, 25), 2);
+    assert_eq!(upper(&keys, 30), 3);
+    assert_eq!(upper(&keys, 99), 3);
+    assert_eq!(upper(&[], 10), 0);
+}
+
+#[test]
+fn lower_bound_finds_the_first_checkpoint_at_or_after_the_key() {
+    let keys = [10, 20, 30];
+
+    assert_eq!(lower(&keys, 5), 0);
+    assert_eq!(lower(&keys, 10), 0);
+    assert_eq!(lower(&keys, 25), 2);
+    assert_eq!(lower(&keys, 30), 2);
+    assert_eq!(lower(&keys, 31), 3);
+}
+
+#[test]
+fn searches_agree_with_a_linear_scan() {
+    let keys: Vec<u64> = (0..37).map(|i| i * 3 + 1).collect();
+
+    for key in 0..120 {
+        assert_eq!(upper(&keys, key), keys.iter().filter(|k| **k <= key).count());
+        assert_eq!(lower(&keys, key), keys.iter().filter(|k| **k < key).count());
+    }
+}
+
+#[test]
+fn clock_modes_describe_themselves_as_in_erc6372() {
+    assert_eq!(ClockMode::BlockNumber.description(), "mode=blocknumber&from=default");
+    assert_eq!(ClockMode::Timestamp.description(), "mode=timestamp");
+}
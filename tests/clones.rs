@@ -0,0 +1,41 @@
+//! Minimal proxy code built by `utils::clones`, checked against the EIP-1167 bytecode.
+use oz_stylus_erc::utils::addresses::create2_address;
+use oz_stylus_erc::utils::clones::{clone_init_code, clone_runtime_code, predict_deterministic_address};
+use stylus_sdk::{
+    alloy_primitives::{Address, B256},
+    crypto::keccak,
+};
+
+const IMPLEMENTATION: Address = Address::repeat_byte(0xbe);
+
+#[test]
+fn runtime_code_is_the_eip1167_proxy() {
+    let expected = hex::decode(
+        "363d3d373d3d3d363d73bebebebebebebebebebebebebebebebebebebebe5af43d82803e903d91602b57fd5bf3",
+    )
+    .unwrap();
+    assert_eq!(clone_runtime_code(IMPLEMENTATION), expected);
+    assert_eq!(expected.len(), 45);
+}
+
+#[test]
+fn init_code_returns_the_runtime_code() {
+    let init_code = clone_init_code(IMPLEMENTATION);
+    // PUSH1 0x2d (runtime length) ... RETURN, then the runtime
+    assert_eq!(init_code[..10], hex::decode("3d602d80600a3d3981f3").unwrap()[..]);
+    assert_eq!(init_code[10..], clone_runtime_code(IMPLEMENTATION)[..]);
+}
+
+#[test]
+fn predicted_address_is_the_create2_address_of_the_init_code() {
+    let factory = Address::repeat_byte(0xfa);
+    let salt = B256::repeat_byte(0x05);
+    assert_eq!(
+        predict_deterministic_address(IMPLEMENTATION, salt, factory),
+        create2_address(factory, salt, keccak(clone_init_code(IMPLEMENTATION)))
+    );
+    assert_ne!(
+        predict_deterministic_address(Address::repeat_byte(0xbf), salt, factory),
+        predict_deterministic_address(IMPLEMENTATION, salt, factory)
+    );
+}
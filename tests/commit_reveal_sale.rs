@@ -0,0 +1,116 @@
+//! Commitments, costs and pro-rata allocations of `finance::commit_reveal_sale`, and the one-shot
+//! configuration and commitment checks of the deployed `examples/compose_commit_reveal_sale.rs`.
+//!
+//! The devnet test needs a fresh sale token, deploy it without calling `init` and set
+//! `STYLUS_PROGRAM_ADDRESS_COMMIT_REVEAL_SALE`, then `cargo test --test commit_reveal_sale -- --ignored`.
+use ethers::{
+    abi::{self, Token},
+    middleware::SignerMiddleware,
+    prelude::abigen,
+    providers::{Http, Middleware, Provider},
+    signers::LocalWallet,
+    types::{BlockNumber, U256 as EthersU256},
+};
+use oz_stylus_erc::finance::commit_reveal_sale::{commitment_hash, pro_rata_allocation, sale_cost, PRICE_PRECISION};
+use stylus_sdk::{
+    alloy_primitives::{Address, B256, U256},
+    crypto::keccak,
+};
+
+mod util;
+use util::fixture_init;
+
+abigen!(
+    SaleToken,
+    r#"[
+        function init() external
+        function configureSale(address payment_token, uint256 price, uint256 sale_supply, uint256 commit_start, uint256 commit_end, uint256 reveal_end) external
+        function commit(bytes32 commitment, uint256 deposit) external
+    ]"#
+);
+
+fn precision() -> U256 {
+    U256::from(PRICE_PRECISION)
+}
+
+#[test]
+fn commitment_is_the_hash_of_the_encoded_request() {
+    let buyer = Address::repeat_byte(0x42);
+    let salt = B256::repeat_byte(0x07);
+    let encoded = abi::encode(&[
+        Token::Address(buyer.0 .0.into()),
+        Token::Uint(1_000u64.into()),
+        Token::FixedBytes(salt.to_vec()),
+    ]);
+
+    assert_eq!(commitment_hash(buyer, U256::from(1_000), salt), keccak(&encoded));
+    // bound to the buyer, a copied commitment can not be revealed by someone else
+    assert_ne!(commitment_hash(Address::repeat_byte(0x43), U256::from(1_000), salt), keccak(&encoded));
+}
+
+#[test]
+fn cost_is_rounded_up_in_favour_of_the_sale() {
+    // 0.5 payment token per sale token
+    let price = precision() / U256::from(2);
+
+    assert_eq!(sale_cost(U256::from(10), price), U256::from(5));
+    assert_eq!(sale_cost(U256::from(11), price), U256::from(6));
+    assert_eq!(sale_cost(U256::from(1), U256::from(1)), U256::from(1));
+    assert_eq!(sale_cost(U256::ZERO, price), U256::ZERO);
+}
+
+#[test]
+fn cost_does_not_overflow_before_the_division() {
+    // amount * price exceeds 256 bits, the cost does not
+    assert_eq!(sale_cost(U256::MAX, precision()), U256::MAX);
+    assert_eq!(sale_cost(U256::MAX / U256::from(2), U256::from(2) * precision()), U256::MAX - U256::from(1));
+    // no deposit covers a cost above uint256
+    assert_eq!(sale_cost(U256::MAX, U256::from(2) * precision()), U256::MAX);
+}
+
+#[test]
+fn allocation_is_scaled_down_when_oversubscribed() {
+    let supply = U256::from(1_000);
+
+    assert_eq!(pro_rata_allocation(U256::from(300), U256::from(1_000), supply), U256::from(300));
+    assert_eq!(pro_rata_allocation(U256::from(300), U256::from(600), supply), U256::from(300));
+    // rounded down, allocations never add up to more than the supply
+    assert_eq!(pro_rata_allocation(U256::from(1), U256::from(3), U256::from(2)), U256::ZERO);
+    assert_eq!(pro_rata_allocation(U256::from(2), U256::from(3), U256::from(2)), U256::from(1));
+    assert_eq!(pro_rata_allocation(U256::MAX, U256::MAX, supply), supply);
+}
+
+#[tokio::test]
+#[ignore = "needs a fresh examples/compose_commit_reveal_sale.rs in STYLUS_PROGRAM_ADDRESS_COMMIT_REVEAL_SALE"]
+async fn sale_is_configured_once_and_rejects_zero_commitments() -> eyre::Result<()> {
+    let devnet = fixture_init::devnet().await?;
+    let address = devnet.program("commit_reveal_sale").await?;
+    let payment_token = devnet.my_token().await?;
+    let sale: SaleToken<SignerMiddleware<Provider<Http>, LocalWallet>> =
+        SaleToken::new(address, devnet.alice_client.clone());
+    let now = devnet
+        .provider
+        .get_block(BlockNumber::Latest)
+        .await?
+        .ok_or_else(|| eyre::eyre!("no latest block"))?
+        .timestamp;
+    let price = EthersU256::from(PRICE_PRECISION);
+
+    sale.init().send().await?.await?;
+    sale.configure_sale(payment_token, price, 1_000.into(), now, now + 3_600, now + 7_200)
+        .send()
+        .await?
+        .await?;
+    // terms are fixed once buyers can commit
+    assert!(sale
+        .configure_sale(payment_token, price * 2, 1_000.into(), now, now + 3_600, now + 7_200)
+        .send()
+        .await
+        .is_err());
+
+    // a zero deposit needs no payment token allowance
+    assert!(sale.commit([0; 32], EthersU256::zero()).send().await.is_err());
+    sale.commit([1; 32], EthersU256::zero()).send().await?.await?;
+    assert!(sale.commit([2; 32], EthersU256::zero()).send().await.is_err());
+    Ok(())
+}
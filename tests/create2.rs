@@ -0,0 +1,31 @@
+//! Addresses predicted by `utils::create2`, checked against the EIP-1014 formula.
+use oz_stylus_erc::utils::addresses::create2_address;
+use oz_stylus_erc::utils::create2::{account_salt, compute_address_from};
+use stylus_sdk::{
+    alloy_primitives::{Address, B256},
+    crypto::keccak,
+};
+
+const FACTORY: Address = Address::repeat_byte(0xfa);
+
+#[test]
+fn computed_address_is_the_eip1014_address() {
+    let salt = B256::repeat_byte(0x01);
+    let init_code_hash = keccak([0x60, 0x00]);
+
+    assert_eq!(
+        compute_address_from(salt, init_code_hash, FACTORY),
+        create2_address(FACTORY, salt, init_code_hash)
+    );
+}
+
+#[test]
+fn account_salt_binds_the_salt_to_the_account() {
+    let (alice, bob) = (Address::repeat_byte(0xa1), Address::repeat_byte(0xb0));
+    let salt = B256::repeat_byte(0x07);
+
+    let mut preimage = alice.to_vec();
+    preimage.extend_from_slice(salt.as_slice());
+    assert_eq!(account_salt(alice, salt), keccak(preimage));
+    assert_ne!(account_salt(alice, salt), account_salt(bob, salt));
+}
@@ -0,0 +1,80 @@
+//! Signature checks of `utils::ecdsa` done before and after the ecrecover precompile: malleable
+//! `s` values, signature lengths and signatures recovering to the zero address.
+use oz_stylus_erc::utils::ecdsa::{recover, recover_bytes, signer_from_output, EcdsaError, MAX_S};
+use stylus_sdk::alloy_primitives::{b256, Address, B256, U256};
+
+fn hash() -> B256 {
+    B256::repeat_byte(0x11)
+}
+
+fn invalid_s(result: Result<Address, EcdsaError>) -> Option<B256> {
+    match result {
+        Err(EcdsaError::ECDSAInvalidSignatureS(e)) => Some(e.s),
+        _ => None,
+    }
+}
+
+fn invalid_length(result: Result<Address, EcdsaError>) -> Option<U256> {
+    match result {
+        Err(EcdsaError::ECDSAInvalidSignatureLength(e)) => Some(e.length),
+        _ => None,
+    }
+}
+
+fn invalid_signature(result: Result<Address, EcdsaError>) -> bool {
+    matches!(result, Err(EcdsaError::ECDSAInvalidSignature(_)))
+}
+
+#[test]
+fn max_s_is_half_the_curve_order() {
+    // secp256k1n / 2
+    assert_eq!(
+        B256::from(MAX_S.to_be_bytes()),
+        b256!("7fffffffffffffffffffffffffffffff5d576e7357a4501ddfe92f46681b20a0")
+    );
+}
+
+#[test]
+fn high_s_is_rejected() {
+    let s = B256::from((MAX_S + U256::from(1)).to_be_bytes());
+    assert_eq!(invalid_s(recover(hash(), 27, B256::repeat_byte(0x22), s)), Some(s));
+
+    let s = B256::repeat_byte(0xff);
+    assert_eq!(invalid_s(recover(hash(), 28, B256::repeat_byte(0x22), s)), Some(s));
+}
+
+#[test]
+fn high_s_is_rejected_in_packed_signatures() {
+    let mut signature = [0x22u8; 65];
+    signature[32..64].copy_from_slice(&(MAX_S + U256::from(1)).to_be_bytes::<32>());
+    signature[64] = 27;
+
+    assert!(invalid_s(recover_bytes(hash(), &signature)).is_some());
+}
+
+#[test]
+fn packed_signatures_are_65_bytes() {
+    assert_eq!(invalid_length(recover_bytes(hash(), &[0u8; 64])), Some(U256::from(64)));
+    assert_eq!(invalid_length(recover_bytes(hash(), &[0u8; 66])), Some(U256::from(66)));
+    assert_eq!(invalid_length(recover_bytes(hash(), &[])), Some(U256::ZERO));
+}
+
+#[test]
+fn zero_address_is_not_a_signer() {
+    // the precompile returns the zero address, left padded, for some invalid signatures
+    assert!(invalid_signature(signer_from_output(&[0u8; 32])));
+}
+
+#[test]
+fn empty_precompile_output_is_an_invalid_signature() {
+    assert!(invalid_signature(signer_from_output(&[])));
+    assert!(invalid_signature(signer_from_output(&[0x42u8; 20])));
+}
+
+#[test]
+fn signer_is_the_low_20_bytes_of_the_output() {
+    let mut output = [0u8; 32];
+    output[12..].copy_from_slice(Address::repeat_byte(0x42).as_slice());
+
+    assert_eq!(signer_from_output(&output).ok(), Some(Address::repeat_byte(0x42)));
+}
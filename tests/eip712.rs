@@ -0,0 +1,74 @@
+//! ERC-5267 domain returned by `eip712Domain()`: wallets rebuild the domain separator from it, so
+//! it has to describe exactly the domain hashed by the signature extensions.
+use oz_stylus_erc::utils::eip712::{self, address_word, hash_words, u256_word, DOMAIN_FIELDS, DOMAIN_VERSION};
+use stylus_sdk::{
+    alloy_primitives::{b256, Address, FixedBytes, B256, U256},
+    crypto::keccak,
+};
+
+const TOKEN: Address = Address::repeat_byte(0x70);
+const CHAIN_ID: u64 = 412346;
+
+#[test]
+fn domain_describes_name_version_chain_id_and_contract() {
+    let (fields, name, version, chain_id, verifying_contract, salt, extensions) =
+        eip712::domain("My token", DOMAIN_VERSION, CHAIN_ID, TOKEN);
+
+    assert_eq!(fields, FixedBytes([0x0f]));
+    assert_eq!(fields.0[0], DOMAIN_FIELDS);
+    assert_eq!(name, "My token");
+    assert_eq!(version, "1");
+    assert_eq!(chain_id.to::<u64>(), CHAIN_ID);
+    assert_eq!(verifying_contract, TOKEN);
+    assert_eq!(salt, B256::ZERO);
+    assert!(extensions.is_empty());
+}
+
+#[test]
+fn domain_separator_is_rebuilt_from_the_domain() {
+    let (_, name, version, chain_id, verifying_contract, _, _) =
+        eip712::domain("My token", DOMAIN_VERSION, CHAIN_ID, TOKEN);
+
+    let rebuilt = hash_words(&[
+        keccak(eip712::DOMAIN_TYPE.as_bytes()),
+        keccak(name.as_bytes()),
+        keccak(version.as_bytes()),
+        u256_word(chain_id),
+        address_word(verifying_contract),
+    ]);
+    assert_eq!(rebuilt, eip712::domain_separator("My token", DOMAIN_VERSION, CHAIN_ID, TOKEN));
+}
+
+#[test]
+fn domain_separator_depends_on_the_chain_id() {
+    assert_ne!(
+        eip712::domain_separator("My token", DOMAIN_VERSION, CHAIN_ID, TOKEN),
+        eip712::domain_separator("My token", DOMAIN_VERSION, CHAIN_ID + 1, TOKEN),
+    );
+}
+
+#[test]
+fn hash_struct_prefixes_the_type_hash() {
+    const DELEGATION_TYPE: &str = "Delegation(address delegatee,uint256 nonce,uint256 expiry)";
+    let fields = [address_word(TOKEN), u256_word(U256::from(1)), u256_word(U256::from(2))];
+
+    let expected = hash_words(&[keccak(DELEGATION_TYPE.as_bytes()), fields[0], fields[1], fields[2]]);
+    assert_eq!(eip712::hash_struct(DELEGATION_TYPE, &fields), expected);
+}
+
+#[test]
+fn domain_type_hash_matches_eip712() {
+    assert_eq!(
+        keccak(eip712::DOMAIN_TYPE.as_bytes()),
+        b256!("8b73c3c69bb8fe3d512ecc4cf759cc79239f7b179b0ffacaa9a75d522b39400f")
+    );
+}
+
+#[test]
+fn domain_separator_matches_a_known_vector() {
+    // computed off-chain for ("My token", "1", 412346, 0x7070…70)
+    assert_eq!(
+        eip712::domain_separator("My token", DOMAIN_VERSION, CHAIN_ID, TOKEN),
+        b256!("4177692ee95b03a5cf7d67921f623d292a4eea49b26bfd638c03f921f3579d59")
+    );
+}
@@ -0,0 +1,74 @@
+//! Interface ids of `utils::erc165` recomputed from the function signatures of each interface.
+use oz_stylus_erc::utils::erc165::{self, interface_id, supports};
+use stylus_sdk::alloy_primitives::FixedBytes;
+
+#[test]
+fn interface_ids_match_function_signatures() {
+    let cases: [([u8; 4], &[&str]); 12] = [
+        (erc165::IERC165, &["supportsInterface(bytes4)"]),
+        (
+            erc165::IERC20,
+            &[
+                "totalSupply()",
+                "balanceOf(address)",
+                "transfer(address,uint256)",
+                "allowance(address,address)",
+                "approve(address,uint256)",
+                "transferFrom(address,address,uint256)",
+            ],
+        ),
+        (erc165::IERC20_METADATA, &["name()", "symbol()", "decimals()"]),
+        (
+            erc165::IERC721,
+            &[
+                "balanceOf(address)",
+                "ownerOf(uint256)",
+                "safeTransferFrom(address,address,uint256,bytes)",
+                "safeTransferFrom(address,address,uint256)",
+                "transferFrom(address,address,uint256)",
+                "approve(address,uint256)",
+                "setApprovalForAll(address,bool)",
+                "getApproved(uint256)",
+                "isApprovedForAll(address,address)",
+            ],
+        ),
+        (erc165::IERC721_METADATA, &["name()", "symbol()", "tokenURI(uint256)"]),
+        (
+            erc165::IERC721_ENUMERABLE,
+            &["totalSupply()", "tokenOfOwnerByIndex(address,uint256)", "tokenByIndex(uint256)"],
+        ),
+        (
+            erc165::IERC1155,
+            &[
+                "balanceOf(address,uint256)",
+                "balanceOfBatch(address[],uint256[])",
+                "setApprovalForAll(address,bool)",
+                "isApprovedForAll(address,address)",
+                "safeTransferFrom(address,address,uint256,uint256,bytes)",
+                "safeBatchTransferFrom(address,address,uint256[],uint256[],bytes)",
+            ],
+        ),
+        (erc165::IERC1155_METADATA_URI, &["uri(uint256)"]),
+        (erc165::IERC2981, &["royaltyInfo(uint256,uint256)"]),
+        (
+            erc165::IERC4907,
+            &["setUser(uint256,address,uint64)", "userOf(uint256)", "userExpires(uint256)"],
+        ),
+        (
+            erc165::IERC4494,
+            &["permit(address,uint256,uint256,bytes)", "nonces(uint256)", "DOMAIN_SEPARATOR()"],
+        ),
+        (erc165::IERC7802, &["crosschainMint(address,uint256)", "crosschainBurn(address,uint256)"]),
+    ];
+    for (expected, signatures) in cases {
+        assert_eq!(interface_id(signatures), expected, "{:?}", signatures);
+    }
+}
+
+#[test]
+fn supports_erc165_but_never_the_invalid_id() {
+    assert!(supports(FixedBytes(erc165::IERC165), &[]));
+    assert!(supports(FixedBytes(erc165::IERC20), &[erc165::IERC20]));
+    assert!(!supports(FixedBytes(erc165::IERC721), &[erc165::IERC20]));
+    assert!(!supports(FixedBytes([0xff; 4]), &[erc165::IERC20]));
+}
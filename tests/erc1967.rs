@@ -0,0 +1,24 @@
+//! EIP-1967 slot and UUPS selector constants of `proxy`, checked against their definitions.
+use oz_stylus_erc::proxy::erc1967::{erc1967_slot, IMPLEMENTATION_SLOT};
+use oz_stylus_erc::proxy::uups::PROXIABLE_UUID_SELECTOR;
+use stylus_sdk::{alloy_primitives::B256, crypto::keccak};
+
+#[test]
+fn implementation_slot_is_eip1967_slot() {
+    assert_eq!(erc1967_slot("eip1967.proxy.implementation"), IMPLEMENTATION_SLOT);
+    let expected: B256 = "0x360894a13ba1a3210667c828492db98dca3e2076cc3735a920a3ca505d382bbc".parse().unwrap();
+    assert_eq!(IMPLEMENTATION_SLOT, expected);
+}
+
+#[test]
+fn admin_and_beacon_slots() {
+    let admin: B256 = "0xb53127684a568b3173ae13b9f8a6016e243e63b6e8ee1178d6a717850b5d6103".parse().unwrap();
+    let beacon: B256 = "0xa3f0ad74e5423aebfd80d3ef4346578335a9a72aeaee59ff6cb3582b35133d50".parse().unwrap();
+    assert_eq!(erc1967_slot("eip1967.proxy.admin"), admin);
+    assert_eq!(erc1967_slot("eip1967.proxy.beacon"), beacon);
+}
+
+#[test]
+fn proxiable_uuid_selector() {
+    assert_eq!(PROXIABLE_UUID_SELECTOR, keccak(b"proxiableUUID()")[..4]);
+}
@@ -1,32 +1,21 @@
-use dotenv::dotenv;
 use ethers::{
     middleware::SignerMiddleware,
     prelude::abigen,
-    providers::{Http, Middleware, Provider},
+    providers::{Http, Provider},
     signers::{LocalWallet, Signer},
     types::{Address, TransactionReceipt, U256},
 };
-use eyre::{eyre, Report};
+use eyre::Report;
+use oz_stylus_erc::errors::{selector_hex, selectors};
 use oz_stylus_erc::tokens::erc20::Erc20Params;
-use std::str::FromStr;
-use std::sync::Arc;
 use tokio::sync::Mutex;
 use tokio::sync::OnceCell;
 
 extern crate oz_stylus_erc;
 use crate::oz_stylus_erc::tokens::my_token::MyTokenParams;
 
-/// deployer private key file path.
-const ALICE_PRIV_KEY_PATH: &str = "ALICE_PRIV_KEY_PATH";
-
-/// deployer private key file path.
-const BOB_PRIV_KEY_PATH: &str = "BOB_PRIV_KEY_PATH";
-
-/// Stylus RPC endpoint url.
-const RPC_URL: &str = "RPC_URL";
-
-/// Deployed program address.
-const MY_TOKEN_PROGRAM_ADDRESS: &str = "STYLUS_PROGRAM_ADDRESS";
+mod util;
+use util::fixture_init;
 
 abigen!(
     MyToken,
@@ -39,9 +28,15 @@ abigen!(
         function transfer(address recipient, uint256 amount) external returns (bool)
         function allowance(address owner, address spender) external view returns (uint256)
         function approve(address spender, uint256 amount) external returns (bool)
+        function increaseAllowance(address spender, uint256 added_value) external returns (bool)
+        function decreaseAllowance(address spender, uint256 requested_decrease) external returns (bool)
         function transferFrom(address sender, address recipient, uint256 amount) external returns (bool)
         function mint(address account, uint256 amount) external
         function burn(address account, uint256 amount) external
+        function revokeAllAllowances() external
+        function allowanceEpoch(address owner) external view returns (uint256)
+        function dangerousAllowances(address owner, address[] spenders) external view returns (uint8[])
+        function revoke(address[] spenders) external
     ]"#
 );
 
@@ -54,18 +49,6 @@ struct Fixtures {
     token_signer_bob: MyTokenType,
 }
 
-/// Errors signatures
-/// you can obtain them by calculating the Error selector same as for function
-/// eg: selector for Erc20InvalidSpender(address) =>
-///  -> bytes4(keccak256(bytes("Erc20InvalidSpender(address)"))) == 0xf886f534
-pub mod erc20_error_selector {
-    pub const INVALID_SPENDER: &str = "0xf886f534";
-    pub const INVALID_RECEIVER: &str = "0x5d908336";
-    pub const INVALID_APPROVER: &str = "0xd15b3125";
-    pub const INSUFFICIENT_ALLOWANCE: &str = "0xa7718e26";
-    pub const INSUFFICIENT_BALANCE: &str = "0x59eca5e6";
-}
-
 static FIXTURES: OnceCell<Mutex<Fixtures>> = OnceCell::const_new();
 
 #[tokio::test]
@@ -220,6 +203,233 @@ async fn approve_test() {
     assert_eq!(allowance_after, amount);
 }
 
+#[tokio::test]
+async fn revoke_all_allowances_test() {
+    let fixtures_mutex = init_fixtures().await.unwrap();
+    let fixtures = fixtures_mutex.lock().await;
+
+    let alice_address = fixtures.alice_wallet.address();
+    let bob_address = fixtures.bob_wallet.address();
+    let token_signer_alice = &fixtures.token_signer_alice;
+    let amount: U256 = 100.into();
+
+    approve(token_signer_alice, bob_address, amount)
+        .await
+        .unwrap();
+    let epoch_before = token_signer_alice
+        .allowance_epoch(alice_address)
+        .call()
+        .await
+        .unwrap();
+
+    token_signer_alice
+        .revoke_all_allowances()
+        .send()
+        .await
+        .unwrap()
+        .await
+        .unwrap();
+    let epoch_after = token_signer_alice
+        .allowance_epoch(alice_address)
+        .call()
+        .await
+        .unwrap();
+    let allowance_after_revoke = token_signer_alice
+        .allowance(alice_address, bob_address)
+        .call()
+        .await
+        .unwrap();
+
+    assert_eq!(epoch_after, epoch_before + 1);
+    assert_eq!(allowance_after_revoke, 0.into());
+
+    // approvals given after the revoke are valid again
+    approve(token_signer_alice, bob_address, amount)
+        .await
+        .unwrap();
+    let allowance_after_approve = token_signer_alice
+        .allowance(alice_address, bob_address)
+        .call()
+        .await
+        .unwrap();
+    assert_eq!(allowance_after_approve, amount);
+}
+
+#[tokio::test]
+async fn dangerous_allowances_and_revoke_test() {
+    let fixtures_mutex = init_fixtures().await.unwrap();
+    let fixtures = fixtures_mutex.lock().await;
+
+    let alice_address = fixtures.alice_wallet.address();
+    let bob_address = fixtures.bob_wallet.address();
+    let token_signer_alice = &fixtures.token_signer_alice;
+    // the token itself is a spender with code
+    let contract_spender = token_signer_alice.address();
+
+    approve(token_signer_alice, bob_address, U256::MAX)
+        .await
+        .unwrap();
+    approve(token_signer_alice, contract_spender, 100.into())
+        .await
+        .unwrap();
+    let flags = token_signer_alice
+        .dangerous_allowances(alice_address, vec![bob_address, contract_spender])
+        .call()
+        .await
+        .unwrap();
+    // bob: EOA | unlimited, token: bounded allowance to a contract
+    assert_eq!(flags, vec![3, 0]);
+
+    token_signer_alice
+        .revoke(vec![bob_address, contract_spender])
+        .send()
+        .await
+        .unwrap()
+        .await
+        .unwrap();
+    for spender in [bob_address, contract_spender] {
+        let allowance = token_signer_alice
+            .allowance(alice_address, spender)
+            .call()
+            .await
+            .unwrap();
+        assert_eq!(allowance, 0.into());
+    }
+    let flags = token_signer_alice
+        .dangerous_allowances(alice_address, vec![bob_address, contract_spender])
+        .call()
+        .await
+        .unwrap();
+    assert_eq!(flags, vec![0, 0]);
+}
+
+#[tokio::test]
+async fn zero_value_operations_test() {
+    let fixtures_mutex = init_fixtures().await.unwrap();
+    let fixtures = fixtures_mutex.lock().await;
+
+    let alice_address = fixtures.alice_wallet.address();
+    let bob_address = fixtures.bob_wallet.address();
+    let token_signer_alice = &fixtures.token_signer_alice;
+    let zero = U256::zero();
+
+    let alice_balance_before = balance_of(token_signer_alice, alice_address).await.unwrap();
+    let bob_balance_before = balance_of(token_signer_alice, bob_address).await.unwrap();
+    let total_supply_before = token_signer_alice.total_supply().call().await.unwrap();
+
+    // zero value operations are normal operations, even with an empty balance or no allowance
+    mint(token_signer_alice, alice_address, zero).await.unwrap();
+    burn(token_signer_alice, alice_address, zero).await.unwrap();
+    transfer(token_signer_alice, bob_address, zero).await.unwrap();
+    transfer_from(token_signer_alice, bob_address, alice_address, zero)
+        .await
+        .unwrap();
+
+    assert_eq!(balance_of(token_signer_alice, alice_address).await.unwrap(), alice_balance_before);
+    assert_eq!(balance_of(token_signer_alice, bob_address).await.unwrap(), bob_balance_before);
+    assert_eq!(token_signer_alice.total_supply().call().await.unwrap(), total_supply_before);
+}
+
+#[tokio::test]
+async fn self_transfer_test() {
+    let fixtures_mutex = init_fixtures().await.unwrap();
+    let fixtures = fixtures_mutex.lock().await;
+
+    let alice_address = fixtures.alice_wallet.address();
+    let token_signer_alice = &fixtures.token_signer_alice;
+    let amount: U256 = 100.into();
+
+    mint(token_signer_alice, alice_address, amount).await.unwrap();
+    let alice_balance_before = balance_of(token_signer_alice, alice_address).await.unwrap();
+    transfer(token_signer_alice, alice_address, amount).await.unwrap();
+    let alice_balance_after = balance_of(token_signer_alice, alice_address).await.unwrap();
+    assert_eq!(alice_balance_after, alice_balance_before);
+
+    // a self transfer still needs the balance
+    let tx = transfer(token_signer_alice, alice_address, alice_balance_after + 1).await;
+    match tx {
+        Ok(_) => panic!("self transfer above the balance should fail"),
+        Err(report) => {
+            assert!(report
+                .to_string()
+                .contains(&selector_hex(selectors::ERC20_INSUFFICIENT_BALANCE)));
+        }
+    }
+}
+
+#[tokio::test]
+async fn self_approve_test() {
+    let fixtures_mutex = init_fixtures().await.unwrap();
+    let fixtures = fixtures_mutex.lock().await;
+
+    let alice_address = fixtures.alice_wallet.address();
+    let bob_address = fixtures.bob_wallet.address();
+    let token_signer_alice = &fixtures.token_signer_alice;
+    let amount: U256 = 30.into();
+
+    mint(token_signer_alice, alice_address, amount).await.unwrap();
+    approve(token_signer_alice, alice_address, amount).await.unwrap();
+    let bob_balance_before = balance_of(token_signer_alice, bob_address).await.unwrap();
+
+    // as in OpenZeppelin, an owner using transferFrom on its own tokens spends its self allowance
+    transfer_from(token_signer_alice, alice_address, bob_address, amount)
+        .await
+        .unwrap();
+
+    let self_allowance = token_signer_alice
+        .allowance(alice_address, alice_address)
+        .call()
+        .await
+        .unwrap();
+    let bob_balance_after = balance_of(token_signer_alice, bob_address).await.unwrap();
+    assert_eq!(self_allowance, 0.into());
+    assert_eq!(bob_balance_after - bob_balance_before, amount);
+}
+
+#[tokio::test]
+async fn increase_and_decrease_allowance_test() {
+    let fixtures_mutex = init_fixtures().await.unwrap();
+    let fixtures = fixtures_mutex.lock().await;
+
+    let alice_address = fixtures.alice_wallet.address();
+    let bob_address = fixtures.bob_wallet.address();
+    let token_signer_alice = &fixtures.token_signer_alice;
+
+    approve(token_signer_alice, bob_address, 100.into()).await.unwrap();
+    token_signer_alice
+        .increase_allowance(bob_address, 50.into())
+        .send()
+        .await
+        .unwrap()
+        .await
+        .unwrap();
+    let allowance = token_signer_alice.allowance(alice_address, bob_address).await.unwrap();
+    assert_eq!(allowance, 150.into());
+
+    token_signer_alice
+        .decrease_allowance(bob_address, 120.into())
+        .send()
+        .await
+        .unwrap()
+        .await
+        .unwrap();
+    let allowance = token_signer_alice.allowance(alice_address, bob_address).await.unwrap();
+    assert_eq!(allowance, 30.into());
+
+    // below zero reverts, the allowance is left as is
+    let error = token_signer_alice
+        .decrease_allowance(bob_address, 31.into())
+        .send()
+        .await
+        .err()
+        .expect("decrease below zero should fail");
+    assert!(error
+        .to_string()
+        .contains(&selector_hex(selectors::ERC20_FAILED_DECREASE_ALLOWANCE)));
+    let allowance = token_signer_alice.allowance(alice_address, bob_address).await.unwrap();
+    assert_eq!(allowance, 30.into());
+}
+
 #[tokio::test]
 async fn approve_account_address_0_error_test() {
     let fixtures_mutex = init_fixtures().await.unwrap();
@@ -234,7 +444,7 @@ async fn approve_account_address_0_error_test() {
         Err(report) => {
             assert!(report
                 .to_string()
-                .contains(erc20_error_selector::INVALID_SPENDER));
+                .contains(&selector_hex(selectors::ERC20_INVALID_SPENDER)));
         }
     }
 }
@@ -267,7 +477,7 @@ async fn transfer_balance_too_small_error_test() {
         Err(report) => {
             assert!(report
                 .to_string()
-                .contains(erc20_error_selector::INSUFFICIENT_BALANCE));
+                .contains(&selector_hex(selectors::ERC20_INSUFFICIENT_BALANCE)));
         }
     }
 }
@@ -290,7 +500,7 @@ async fn transfer_receiver_address_0_error_test() {
         Err(report) => {
             assert!(report
                 .to_string()
-                .contains(erc20_error_selector::INVALID_RECEIVER));
+                .contains(&selector_hex(selectors::ERC20_INVALID_RECEIVER)));
         }
     }
 }
@@ -329,7 +539,7 @@ async fn transfer_from_amount_bigger_than_allowance_error_test() {
         Err(report) => {
             assert!(report
                 .to_string()
-                .contains(erc20_error_selector::INSUFFICIENT_ALLOWANCE));
+                .contains(&selector_hex(selectors::ERC20_INSUFFICIENT_ALLOWANCE)));
         }
     }
 }
@@ -419,45 +629,16 @@ async fn init_fixtures() -> eyre::Result<&'static Mutex<Fixtures>> {
 }
 
 async fn fill_fixtures() -> eyre::Result<Fixtures> {
-    dotenv().ok();
-
-    let program_address = std::env::var(MY_TOKEN_PROGRAM_ADDRESS)
-        .map_err(|_| eyre!("No {} env var set", MY_TOKEN_PROGRAM_ADDRESS))?;
-    let alice_key_path = std::env::var(ALICE_PRIV_KEY_PATH)
-        .map_err(|_| eyre!("No {} env var set", ALICE_PRIV_KEY_PATH))?;
-    let rpc_url = std::env::var(RPC_URL).map_err(|_| eyre!("No {} env var set", RPC_URL))?;
-    let bob_key_path = std::env::var(BOB_PRIV_KEY_PATH)
-        .map_err(|_| eyre!("No {} env var set", BOB_PRIV_KEY_PATH))?;
-
-    let provider = Provider::<Http>::try_from(rpc_url)?;
-    let my_token_address: Address = program_address.parse()?;
-
-    let alice_private_key = read_secret_from_file(&alice_key_path)?;
-    let alice_wallet = LocalWallet::from_str(&alice_private_key)?;
-    let chain_id = provider.get_chainid().await?.as_u64();
-    let alice_client = Arc::new(SignerMiddleware::new(
-        provider.clone(),
-        alice_wallet.clone().with_chain_id(chain_id),
-    ));
-
-    let bob_private_key = read_secret_from_file(&bob_key_path)?;
-    let bob_wallet = LocalWallet::from_str(&bob_private_key)?;
-    let bob_client = Arc::new(SignerMiddleware::new(
-        provider.clone(),
-        bob_wallet.clone().with_chain_id(chain_id),
-    ));
-
-    let token_signer_alice = MyToken::new(my_token_address, alice_client.clone());
-    let token_signer_bob = MyToken::new(my_token_address, bob_client.clone());
+    let devnet = fixture_init::devnet().await?;
+    let my_token_address = devnet.my_token().await?;
+
+    let token_signer_alice = MyToken::new(my_token_address, devnet.alice_client.clone());
+    let token_signer_bob = MyToken::new(my_token_address, devnet.bob_client.clone());
 
     Ok(Fixtures {
-        alice_wallet,
-        bob_wallet,
+        alice_wallet: devnet.alice_wallet.clone(),
+        bob_wallet: devnet.bob_wallet.clone(),
         token_signer_alice,
         token_signer_bob,
     })
 }
-
-fn read_secret_from_file(fpath: &str) -> eyre::Result<String> {
-    Ok(std::fs::read_to_string(fpath)?)
-}
@@ -0,0 +1,30 @@
+//! Log gas of the `erc20_batch_transfer` event modes and the batch total.
+use oz_stylus_erc::extensions::erc20_batch_transfer::{batch_events_gas, batch_total, log_gas, BatchEvents};
+use stylus_sdk::alloy_primitives::U256;
+
+#[test]
+fn transfer_event_costs_1756_gas() {
+    assert_eq!(log_gas(3, 32), 1756);
+    assert_eq!(batch_events_gas(BatchEvents::PerTransfer, 100), 175_600);
+}
+
+#[test]
+fn aggregated_event_pays_from_two_recipients() {
+    assert_eq!(batch_events_gas(BatchEvents::Aggregated, 1), 2661);
+    assert!(batch_events_gas(BatchEvents::Aggregated, 1) > batch_events_gas(BatchEvents::PerTransfer, 1));
+    assert!(batch_events_gas(BatchEvents::Aggregated, 2) < batch_events_gas(BatchEvents::PerTransfer, 2));
+}
+
+#[test]
+fn aggregated_event_saves_1244_gas_per_recipient() {
+    let saved = |n| batch_events_gas(BatchEvents::PerTransfer, n) - batch_events_gas(BatchEvents::Aggregated, n);
+    assert_eq!(saved(101) - saved(100), 1244);
+    assert_eq!(saved(100), 122_251);
+}
+
+#[test]
+fn batch_total_detects_overflow() {
+    assert_eq!(batch_total(&[U256::from(1), U256::from(2)]), Some(U256::from(3)));
+    assert_eq!(batch_total(&[]), Some(U256::ZERO));
+    assert_eq!(batch_total(&[U256::MAX, U256::from(1)]), None);
+}
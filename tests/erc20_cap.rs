@@ -0,0 +1,35 @@
+//! `Erc20Cap` validation done before the mint: the cap is inclusive and an overflowing supply is
+//! reported as exceeding it.
+use oz_stylus_erc::extensions::erc20_cap::{check_cap, Erc20CapError};
+use stylus_sdk::alloy_primitives::U256;
+
+fn exceeded(result: Result<(), Erc20CapError>) -> Option<(U256, U256)> {
+    match result {
+        Err(Erc20CapError::Erc20ExceededCap(e)) => Some((e.increased_supply, e.cap)),
+        _ => None,
+    }
+}
+
+#[test]
+fn mint_up_to_the_cap_is_allowed() {
+    let cap = U256::from(1000);
+    assert!(check_cap(cap, U256::from(400), U256::from(600)).is_ok());
+    assert!(check_cap(cap, cap, U256::ZERO).is_ok());
+}
+
+#[test]
+fn mint_over_the_cap_reports_the_increased_supply() {
+    let cap = U256::from(1000);
+    assert_eq!(
+        exceeded(check_cap(cap, U256::from(400), U256::from(601))),
+        Some((U256::from(1001), cap))
+    );
+}
+
+#[test]
+fn overflowing_supply_exceeds_the_cap() {
+    assert_eq!(
+        exceeded(check_cap(U256::MAX, U256::MAX, U256::from(1))),
+        Some((U256::MAX, U256::MAX))
+    );
+}
@@ -0,0 +1,92 @@
+//! ERC-7802 interface of `extensions::erc20_crosschain`, and the bridge-only mint and burn of the
+//! deployed `examples/compose_crosschain.rs`.
+//!
+//! The devnet test needs a fresh bridged token, deploy it without calling `init` and set
+//! `STYLUS_PROGRAM_ADDRESS_CROSSCHAIN`, then `cargo test --test erc20_crosschain -- --ignored`.
+use ethers::{
+    middleware::SignerMiddleware,
+    prelude::abigen,
+    providers::{Http, Provider},
+    signers::{LocalWallet, Signer},
+    types::U256,
+};
+use oz_stylus_erc::extensions::erc20_crosschain::Erc20Crosschain;
+use oz_stylus_erc::utils::erc165::{self, Erc165};
+use stylus_sdk::alloy_primitives::FixedBytes;
+
+mod util;
+use util::fixture_init;
+
+abigen!(
+    BridgedToken,
+    r#"[
+        function init(address bridge) external
+        function crosschainBridge() external view returns (address)
+        function crosschainMint(address to, uint256 amount) external
+        function crosschainBurn(address from, uint256 amount) external
+        function balanceOf(address account) external view returns (uint256)
+        function totalSupply() external view returns (uint256)
+        function supportsInterface(bytes4 interface_id) external view returns (bool)
+        event CrosschainMint(address indexed to, uint256 amount, address indexed sender)
+        event CrosschainBurn(address indexed from, uint256 amount, address indexed sender)
+    ]"#
+);
+
+type BridgedTokenType = BridgedToken<SignerMiddleware<Provider<Http>, LocalWallet>>;
+
+#[test]
+fn crosschain_reports_erc7802_only() {
+    assert!(<Erc20Crosschain as Erc165>::supports_interface(FixedBytes(erc165::IERC7802)));
+    assert!(<Erc20Crosschain as Erc165>::supports_interface(FixedBytes(erc165::IERC165)));
+    assert!(!<Erc20Crosschain as Erc165>::supports_interface(FixedBytes(erc165::IERC20)));
+    assert!(!<Erc20Crosschain as Erc165>::supports_interface(FixedBytes(erc165::INVALID_INTERFACE_ID)));
+}
+
+#[tokio::test]
+#[ignore = "needs a fresh examples/compose_crosschain.rs in STYLUS_PROGRAM_ADDRESS_CROSSCHAIN"]
+async fn only_the_bridge_mints_and_burns() -> eyre::Result<()> {
+    let devnet = fixture_init::devnet().await?;
+    let address = devnet.program("crosschain").await?;
+    let as_alice: BridgedTokenType = BridgedToken::new(address, devnet.alice_client.clone());
+    let as_bridge: BridgedTokenType = BridgedToken::new(address, devnet.bob_client.clone());
+    let alice = devnet.alice_client.address();
+    let bridge = devnet.bob_client.address();
+
+    as_alice.init(bridge).send().await?.await?;
+    assert_eq!(as_alice.crosschain_bridge().call().await?, bridge);
+    assert!(as_alice.init(alice).send().await.is_err());
+
+    // holders can not mint nor burn crosschain
+    assert!(as_alice.crosschain_mint(alice, U256::from(1_000)).send().await.is_err());
+
+    as_bridge.crosschain_mint(alice, U256::from(1_000)).send().await?.await?;
+    let minted = as_alice.crosschain_mint_filter().from_block(0u64).query().await?;
+    assert_eq!(minted.len(), 1);
+    assert_eq!((minted[0].to, minted[0].amount, minted[0].sender), (alice, U256::from(1_000), bridge));
+    assert_eq!(as_alice.balance_of(alice).call().await?, U256::from(1_000));
+
+    assert!(as_alice.crosschain_burn(alice, U256::from(400)).send().await.is_err());
+    // the bridge burns without an allowance
+    as_bridge.crosschain_burn(alice, U256::from(400)).send().await?.await?;
+    let burned = as_alice.crosschain_burn_filter().from_block(0u64).query().await?;
+    assert_eq!(burned.len(), 1);
+    assert_eq!((burned[0].from, burned[0].amount, burned[0].sender), (alice, U256::from(400), bridge));
+    assert_eq!(as_alice.balance_of(alice).call().await?, U256::from(600));
+    assert_eq!(as_alice.total_supply().call().await?, U256::from(600));
+    assert!(as_bridge.crosschain_burn(alice, U256::from(601)).send().await.is_err());
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore = "needs a fresh examples/compose_crosschain.rs in STYLUS_PROGRAM_ADDRESS_CROSSCHAIN"]
+async fn bridged_token_reports_erc20_and_erc7802() -> eyre::Result<()> {
+    let devnet = fixture_init::devnet().await?;
+    let address = devnet.program("crosschain").await?;
+    let token: BridgedTokenType = BridgedToken::new(address, devnet.alice_client.clone());
+
+    assert!(token.supports_interface(erc165::IERC7802).call().await?);
+    assert!(token.supports_interface(erc165::IERC20).call().await?);
+    assert!(token.supports_interface(erc165::IERC165).call().await?);
+    assert!(!token.supports_interface(erc165::INVALID_INTERFACE_ID).call().await?);
+    Ok(())
+}
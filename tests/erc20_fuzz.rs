@@ -0,0 +1,559 @@
+//! Long running randomized invariant checks against a deployed token.
+//!
+//! Run with `cargo test --features devnet-fuzz --test erc20_fuzz -- --nocapture`.
+//! Each run prints its seed, set `FUZZ_SEED` to replay a failing sequence and
+//! `FUZZ_ITERATIONS` to control how many operations are fired (default 200).
+//!
+//! The state machine of pause, cap and roles runs against `examples/compose_pausable_cap_roles.rs`
+//! deployed without calling `init`, in `STYLUS_PROGRAM_ADDRESS_MANAGED`; it fails when the
+//! variable is not set. `tests/erc20_state_machine.rs` runs the same state machine on the host.
+#![cfg(feature = "devnet-fuzz")]
+
+use ethers::{
+    middleware::SignerMiddleware,
+    prelude::abigen,
+    providers::{Http, Provider},
+    signers::{LocalWallet, Signer},
+    types::{Address, U256},
+};
+use eyre::{eyre, Report};
+use oz_stylus_erc::access::access_control::role_id;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::collections::HashMap;
+
+mod util;
+use util::fixture_init;
+
+/// Seed of the random operations generator.
+const FUZZ_SEED: &str = "FUZZ_SEED";
+
+/// Number of random operations fired in one run.
+const FUZZ_ITERATIONS: &str = "FUZZ_ITERATIONS";
+
+const DEFAULT_ITERATIONS: u64 = 200;
+
+/// Upper bound of a single random amount, big enough to regularly exceed balances and allowances.
+const MAX_AMOUNT: u64 = 2_000;
+
+/// Room under the cap of the managed token at `init`, reached after a few dozen mints.
+const CAP_ROOM: u64 = 20 * MAX_AMOUNT;
+
+/// Roles of the managed token, all administered by `DEFAULT_ADMIN_ROLE`.
+const MANAGED_ROLES: [&str; 3] = ["PAUSER_ROLE", "MINTER_ROLE", "BURNER_ROLE"];
+
+const DEFAULT_ADMIN_ROLE: [u8; 32] = [0; 32];
+
+// `MyToken` plus the pause, cap and role externals of the managed token
+abigen!(
+    FuzzToken,
+    r#"[
+        function totalSupply() external view returns (uint256)
+        function balanceOf(address account) external view returns (uint256)
+        function transfer(address recipient, uint256 amount) external returns (bool)
+        function allowance(address owner, address spender) external view returns (uint256)
+        function approve(address spender, uint256 amount) external returns (bool)
+        function transferFrom(address sender, address recipient, uint256 amount) external returns (bool)
+        function mint(address account, uint256 amount) external
+        function burn(address account, uint256 amount) external
+        function init(uint256 cap) external
+        function cap() external view returns (uint256)
+        function paused() external view returns (bool)
+        function pause() external
+        function unpause() external
+        function hasRole(bytes32 role, address account) external view returns (bool)
+        function grantRole(bytes32 role, address account) external
+        function revokeRole(bytes32 role, address account) external
+    ]"#
+);
+
+type FuzzTokenType = FuzzToken<SignerMiddleware<Provider<Http>, LocalWallet>>;
+
+/// Wallet able to sign transactions against the token.
+struct Actor {
+    address: Address,
+    token: FuzzTokenType,
+}
+
+#[derive(Debug, Clone)]
+enum Operation {
+    Mint { signer: usize, account: Address, amount: U256 },
+    Burn { signer: usize, account: Address, amount: U256 },
+    Transfer { signer: usize, to: Address, amount: U256 },
+    Approve { signer: usize, spender: Address, amount: U256 },
+    TransferFrom { signer: usize, from: Address, to: Address, amount: U256 },
+    Pause { signer: usize },
+    Unpause { signer: usize },
+    /// second `init`, the cap is set once
+    SetCap { signer: usize, cap: U256 },
+    GrantRole { signer: usize, role: [u8; 32], account: Address },
+    RevokeRole { signer: usize, role: [u8; 32], account: Address },
+}
+
+/// Pause, cap and roles of the managed token.
+struct Managed {
+    paused: bool,
+    cap: U256,
+    /// (role, actor) => has the role, for `MANAGED_ROLES` and `DEFAULT_ADMIN_ROLE`
+    roles: HashMap<([u8; 32], Address), bool>,
+}
+
+impl Managed {
+    fn has_role(&self, role: [u8; 32], account: Address) -> bool {
+        self.roles.get(&(role, account)).copied().unwrap_or_default()
+    }
+}
+
+/// Reference model of the token state restricted to the tracked accounts.
+struct Model {
+    total_supply: U256,
+    balances: HashMap<Address, U256>,
+    allowances: HashMap<(Address, Address), U256>,
+    /// part of total supply held by accounts not tracked by the fuzzer, must never change
+    untracked_supply: U256,
+    /// `None` for `MyToken`, where anybody mints and burns
+    managed: Option<Managed>,
+}
+
+impl Model {
+    fn balance(&self, account: Address) -> U256 {
+        self.balances.get(&account).copied().unwrap_or_default()
+    }
+
+    fn allowance(&self, owner: Address, spender: Address) -> U256 {
+        self.allowances.get(&(owner, spender)).copied().unwrap_or_default()
+    }
+
+    /// Whether the managed token lets `signer` run an operation needing `role` (`None` for
+    /// balance moves, only checked against the pause).
+    fn allows(&self, signer: Address, role: Option<[u8; 32]>) -> bool {
+        let Some(managed) = &self.managed else {
+            return true;
+        };
+        if managed.paused {
+            return false;
+        }
+        match role {
+            Some(role) => managed.has_role(role, signer),
+            None => true,
+        }
+    }
+
+    /// Applies the operation when it is expected to succeed on chain, returns whether it should succeed.
+    fn apply(&mut self, op: &Operation, actors: &[Actor]) -> bool {
+        match *op {
+            Operation::Pause { .. }
+            | Operation::Unpause { .. }
+            | Operation::SetCap { .. }
+            | Operation::GrantRole { .. }
+            | Operation::RevokeRole { .. } => return self.apply_managed(op, actors),
+            Operation::Mint { signer, .. } => {
+                if !self.allows(actors[signer].address, Some(role("MINTER_ROLE"))) {
+                    return false;
+                }
+            }
+            Operation::Burn { signer, .. } => {
+                if !self.allows(actors[signer].address, Some(role("BURNER_ROLE"))) {
+                    return false;
+                }
+            }
+            Operation::Transfer { signer, .. } | Operation::TransferFrom { signer, .. } => {
+                if !self.allows(actors[signer].address, None) {
+                    return false;
+                }
+            }
+            Operation::Approve { .. } => {}
+        }
+        match *op {
+            Operation::Mint { account, amount, .. } => {
+                if account == Address::zero() {
+                    return false;
+                }
+                if let Some(managed) = &self.managed {
+                    if self.total_supply + amount > managed.cap {
+                        return false;
+                    }
+                }
+                self.total_supply += amount;
+                *self.balances.entry(account).or_default() += amount;
+                true
+            }
+            Operation::Burn { account, amount, .. } => {
+                if account == Address::zero() || self.balance(account) < amount {
+                    return false;
+                }
+                self.total_supply -= amount;
+                *self.balances.entry(account).or_default() -= amount;
+                true
+            }
+            Operation::Transfer { signer, to, amount } => {
+                let from = actors[signer].address;
+                if to == Address::zero() || self.balance(from) < amount {
+                    return false;
+                }
+                *self.balances.entry(from).or_default() -= amount;
+                *self.balances.entry(to).or_default() += amount;
+                true
+            }
+            Operation::Approve { signer, spender, amount } => {
+                if spender == Address::zero() {
+                    return false;
+                }
+                self.allowances.insert((actors[signer].address, spender), amount);
+                true
+            }
+            Operation::TransferFrom { signer, from, to, amount } => {
+                let spender = actors[signer].address;
+                let allowance = self.allowance(from, spender);
+                if allowance < amount || to == Address::zero() || self.balance(from) < amount {
+                    return false;
+                }
+                if allowance != U256::MAX {
+                    self.allowances.insert((from, spender), allowance - amount);
+                }
+                *self.balances.entry(from).or_default() -= amount;
+                *self.balances.entry(to).or_default() += amount;
+                true
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    fn apply_managed(&mut self, op: &Operation, actors: &[Actor]) -> bool {
+        let Some(managed) = &mut self.managed else {
+            return false;
+        };
+        match *op {
+            Operation::Pause { signer } => {
+                if managed.paused || !managed.has_role(role("PAUSER_ROLE"), actors[signer].address) {
+                    return false;
+                }
+                managed.paused = true;
+                true
+            }
+            Operation::Unpause { signer } => {
+                if !managed.paused || !managed.has_role(role("PAUSER_ROLE"), actors[signer].address) {
+                    return false;
+                }
+                managed.paused = false;
+                true
+            }
+            Operation::SetCap { .. } => false,
+            Operation::GrantRole { signer, role, account } => {
+                if !managed.has_role(DEFAULT_ADMIN_ROLE, actors[signer].address) {
+                    return false;
+                }
+                managed.roles.insert((role, account), true);
+                true
+            }
+            Operation::RevokeRole { signer, role, account } => {
+                if !managed.has_role(DEFAULT_ADMIN_ROLE, actors[signer].address) {
+                    return false;
+                }
+                managed.roles.insert((role, account), false);
+                true
+            }
+            _ => unreachable!(),
+        }
+    }
+}
+
+fn role(name: &str) -> [u8; 32] {
+    role_id(name).0
+}
+
+#[tokio::test]
+async fn erc20_random_operations_keep_invariants() {
+    let devnet = fixture_init::devnet().await.unwrap();
+    let actors = init_actors(devnet.my_token().await.unwrap()).await.unwrap();
+    run(&actors, false).await;
+}
+
+/// pause/unpause/set_cap/mint/burn/role changes against the reference model
+#[tokio::test]
+async fn managed_token_random_operations_keep_invariants() {
+    let devnet = fixture_init::devnet().await.unwrap();
+    let address = devnet
+        .program("managed")
+        .await
+        .unwrap_or_else(|e| panic!("erc20 fuzz: deploy examples/compose_pausable_cap_roles.rs: {}", e));
+    let actors = init_actors(address).await.unwrap();
+    let token = &actors[0].token;
+    if token.cap().call().await.unwrap().is_zero() {
+        let cap = token.total_supply().call().await.unwrap() + U256::from(CAP_ROOM);
+        token.init(cap).send().await.unwrap().await.unwrap();
+    }
+    // alice starts with every role when admin, so pauses and cap-bound mints happen from the
+    // first steps; revocations and grants then move them around
+    if token.has_role(DEFAULT_ADMIN_ROLE, actors[0].address).call().await.unwrap() {
+        for name in MANAGED_ROLES {
+            token.grant_role(role(name), actors[0].address).send().await.unwrap().await.unwrap();
+        }
+    }
+    run(&actors, true).await;
+}
+
+async fn run(actors: &[Actor], managed: bool) {
+
+    let seed = std::env::var(FUZZ_SEED)
+        .ok()
+        .and_then(|s| s.parse::<u64>().ok())
+        .unwrap_or_else(rand::random);
+    let iterations = std::env::var(FUZZ_ITERATIONS)
+        .ok()
+        .and_then(|s| s.parse::<u64>().ok())
+        .unwrap_or(DEFAULT_ITERATIONS);
+    println!("erc20 fuzz: seed {}, iterations {}", seed, iterations);
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    // account without a key, only ever receives tokens
+    let carol = Address::random();
+    let mut accounts: Vec<Address> = actors.iter().map(|a| a.address).collect();
+    accounts.push(carol);
+
+    let mut model = snapshot_model(actors, &accounts, managed).await.unwrap();
+
+    for step in 0..iterations {
+        let op = random_operation(&mut rng, actors, &accounts, &model);
+        let expected_success = model.apply(&op, actors);
+        let result = execute(actors, &op).await;
+
+        match (expected_success, &result) {
+            (true, Err(report)) => panic!(
+                "step {} (seed {}): {:?} should succeed, failed with {}",
+                step, seed, op, report
+            ),
+            (false, Ok(_)) => panic!(
+                "step {} (seed {}): {:?} should revert but succeeded",
+                step, seed, op
+            ),
+            _ => {}
+        }
+
+        check_invariants(actors, &accounts, &model)
+            .await
+            .unwrap_or_else(|e| panic!("step {} (seed {}) after {:?}: {}", step, seed, op, e));
+    }
+}
+
+fn random_operation(rng: &mut StdRng, actors: &[Actor], accounts: &[Address], model: &Model) -> Operation {
+    let signer = rng.gen_range(0..actors.len());
+    let amount = U256::from(rng.gen_range(0..=MAX_AMOUNT));
+    // now and then target the zero address to exercise the invalid receiver/spender paths
+    let pick_account = |rng: &mut StdRng| {
+        if rng.gen_ratio(1, 20) {
+            Address::zero()
+        } else {
+            accounts[rng.gen_range(0..accounts.len())]
+        }
+    };
+
+    // role changes only for the actors, the model tracks their roles
+    let pick_actor = |rng: &mut StdRng| actors[rng.gen_range(0..actors.len())].address;
+    let pick_role = |rng: &mut StdRng| role(MANAGED_ROLES[rng.gen_range(0..MANAGED_ROLES.len())]);
+
+    match rng.gen_range(0..if model.managed.is_some() { 10 } else { 5 }) {
+        0 => {
+            // under a cap, half of the mints fill the room left exactly or overshoot it by one
+            let amount = match &model.managed {
+                Some(managed) if rng.gen_ratio(1, 2) => {
+                    let room = managed.cap.saturating_sub(model.total_supply);
+                    if rng.gen_ratio(1, 2) {
+                        room
+                    } else {
+                        room + 1
+                    }
+                }
+                _ => amount,
+            };
+            Operation::Mint { signer, account: pick_account(rng), amount }
+        }
+        1 => Operation::Burn { signer, account: pick_account(rng), amount },
+        2 => Operation::Transfer { signer, to: pick_account(rng), amount },
+        3 => {
+            // infinite approvals exercise the branch where allowance is not decreased
+            let amount = if rng.gen_ratio(1, 10) { U256::MAX } else { amount };
+            Operation::Approve { signer, spender: pick_account(rng), amount }
+        }
+        4 => {
+            let from = actors[rng.gen_range(0..actors.len())].address;
+            Operation::TransferFrom { signer, from, to: pick_account(rng), amount }
+        }
+        5 => Operation::Pause { signer },
+        6 => Operation::Unpause { signer },
+        7 => Operation::SetCap { signer, cap: amount },
+        8 => Operation::GrantRole { signer, role: pick_role(rng), account: pick_actor(rng) },
+        _ => Operation::RevokeRole { signer, role: pick_role(rng), account: pick_actor(rng) },
+    }
+}
+
+async fn execute(actors: &[Actor], op: &Operation) -> eyre::Result<()> {
+    let receipt = match *op {
+        Operation::Mint { signer, account, amount } => {
+            actors[signer].token.mint(account, amount).send().await?.await?
+        }
+        Operation::Burn { signer, account, amount } => {
+            actors[signer].token.burn(account, amount).send().await?.await?
+        }
+        Operation::Transfer { signer, to, amount } => {
+            actors[signer].token.transfer(to, amount).send().await?.await?
+        }
+        Operation::Approve { signer, spender, amount } => {
+            actors[signer].token.approve(spender, amount).send().await?.await?
+        }
+        Operation::TransferFrom { signer, from, to, amount } => {
+            actors[signer]
+                .token
+                .transfer_from(from, to, amount)
+                .send()
+                .await?
+                .await?
+        }
+        Operation::Pause { signer } => actors[signer].token.pause().send().await?.await?,
+        Operation::Unpause { signer } => actors[signer].token.unpause().send().await?.await?,
+        Operation::SetCap { signer, cap } => actors[signer].token.init(cap).send().await?.await?,
+        Operation::GrantRole { signer, role, account } => {
+            actors[signer].token.grant_role(role, account).send().await?.await?
+        }
+        Operation::RevokeRole { signer, role, account } => {
+            actors[signer].token.revoke_role(role, account).send().await?.await?
+        }
+    };
+    receipt.ok_or(Report::msg("tx dropped"))?;
+    Ok(())
+}
+
+async fn snapshot_model(actors: &[Actor], accounts: &[Address], managed: bool) -> eyre::Result<Model> {
+    let token = &actors[0].token;
+    let total_supply = token.total_supply().call().await?;
+
+    let mut balances = HashMap::new();
+    for account in accounts {
+        balances.insert(*account, token.balance_of(*account).call().await?);
+    }
+    let mut allowances = HashMap::new();
+    for owner in actors.iter().map(|a| a.address) {
+        for spender in accounts {
+            allowances.insert((owner, *spender), token.allowance(owner, *spender).call().await?);
+        }
+    }
+    let tracked_supply = balances.values().fold(U256::zero(), |acc, b| acc + b);
+
+    let managed = if managed {
+        let mut roles = HashMap::new();
+        for role in managed_role_ids() {
+            for actor in actors {
+                roles.insert((role, actor.address), token.has_role(role, actor.address).call().await?);
+            }
+        }
+        Some(Managed {
+            paused: token.paused().call().await?,
+            cap: token.cap().call().await?,
+            roles,
+        })
+    } else {
+        None
+    };
+
+    Ok(Model {
+        total_supply,
+        balances,
+        allowances,
+        untracked_supply: total_supply - tracked_supply,
+        managed,
+    })
+}
+
+async fn check_invariants(actors: &[Actor], accounts: &[Address], model: &Model) -> eyre::Result<()> {
+    let token = &actors[0].token;
+
+    let total_supply = token.total_supply().call().await?;
+    if total_supply != model.total_supply {
+        return Err(eyre!("total supply {} != model {}", total_supply, model.total_supply));
+    }
+
+    let mut tracked_supply = U256::zero();
+    for account in accounts {
+        let balance = token.balance_of(*account).call().await?;
+        if balance != model.balance(*account) {
+            return Err(eyre!(
+                "balance of {:?} {} != model {}",
+                account,
+                balance,
+                model.balance(*account)
+            ));
+        }
+        tracked_supply += balance;
+    }
+    // nobody else can gain or lose tokens through operations of the tracked accounts
+    if total_supply != tracked_supply + model.untracked_supply {
+        return Err(eyre!(
+            "total supply {} != tracked balances {} + untracked {}",
+            total_supply,
+            tracked_supply,
+            model.untracked_supply
+        ));
+    }
+
+    for owner in actors.iter().map(|a| a.address) {
+        for spender in accounts {
+            let allowance = token.allowance(owner, *spender).call().await?;
+            if allowance != model.allowance(owner, *spender) {
+                return Err(eyre!(
+                    "allowance {:?} -> {:?} {} != model {}",
+                    owner,
+                    spender,
+                    allowance,
+                    model.allowance(owner, *spender)
+                ));
+            }
+        }
+    }
+
+    if let Some(managed) = &model.managed {
+        let paused = token.paused().call().await?;
+        if paused != managed.paused {
+            return Err(eyre!("paused {} != model {}", paused, managed.paused));
+        }
+        let cap = token.cap().call().await?;
+        if cap != managed.cap || total_supply > cap {
+            return Err(eyre!("cap {} (model {}) with total supply {}", cap, managed.cap, total_supply));
+        }
+        for role in managed_role_ids() {
+            for actor in actors {
+                let has_role = token.has_role(role, actor.address).call().await?;
+                if has_role != managed.has_role(role, actor.address) {
+                    return Err(eyre!(
+                        "role {:?} of {:?} {} != model {}",
+                        role,
+                        actor.address,
+                        has_role,
+                        managed.has_role(role, actor.address)
+                    ));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn managed_role_ids() -> impl Iterator<Item = [u8; 32]> {
+    MANAGED_ROLES.into_iter().map(role).chain([DEFAULT_ADMIN_ROLE])
+}
+
+/*** Fixtures helper functions  ***/
+
+async fn init_actors(token_address: Address) -> eyre::Result<Vec<Actor>> {
+    let devnet = fixture_init::devnet().await?;
+
+    let mut actors = Vec::new();
+    for (wallet, client) in [
+        (&devnet.alice_wallet, &devnet.alice_client),
+        (&devnet.bob_wallet, &devnet.bob_client),
+    ] {
+        actors.push(Actor {
+            address: wallet.address(),
+            token: FuzzToken::new(token_address, client.clone()),
+        });
+    }
+    Ok(actors)
+}
@@ -0,0 +1,134 @@
+//! Claims of `extensions::erc20_global_settlement` at the settlement rate, and the settlement of
+//! the deployed `examples/compose_global_settlement.rs`.
+//!
+//! The devnet test needs a fresh settlement token, deploy it without calling `init` and set
+//! `STYLUS_PROGRAM_ADDRESS_GLOBAL_SETTLEMENT`, then
+//! `cargo test --test erc20_global_settlement -- --ignored`. `MyToken` is the reserve asset.
+use ethers::{
+    middleware::SignerMiddleware,
+    prelude::abigen,
+    providers::{Http, Provider},
+    signers::{LocalWallet, Signer},
+    types::U256 as EthersU256,
+};
+use oz_stylus_erc::extensions::erc20_global_settlement::{settlement_claim, Erc20GlobalSettlementError};
+use stylus_sdk::alloy_primitives::U256;
+
+mod util;
+use util::fixture_init;
+
+abigen!(
+    SettledToken,
+    r#"[
+        function init(address reserve_asset) external
+        function mint(address account, uint256 value) external
+        function transfer(address to, uint256 value) external returns (bool)
+        function triggerSettlement() external
+        function redeem(uint256 amount) external returns (uint256)
+        function redeemable(uint256 amount) external view returns (uint256)
+        function isSettled() external view returns (bool)
+        function settlementRate() external view returns (uint256, uint256)
+        function balanceOf(address account) external view returns (uint256)
+    ]"#
+);
+
+abigen!(
+    ReserveToken,
+    r#"[
+        function mint(address account, uint256 amount) external
+        function balanceOf(address account) external view returns (uint256)
+    ]"#
+);
+
+type Client = SignerMiddleware<Provider<Http>, LocalWallet>;
+
+#[test]
+fn claim_is_the_share_of_the_reserve() {
+    let (reserve, supply) = (U256::from(2_000), U256::from(4_000));
+
+    assert_eq!(settlement_claim(U256::from(1_000), reserve, supply).ok(), Some(U256::from(500)));
+    assert_eq!(settlement_claim(supply, reserve, supply).ok(), Some(reserve));
+    assert_eq!(settlement_claim(U256::ZERO, reserve, supply).ok(), Some(U256::ZERO));
+    // over-collateralized
+    assert_eq!(settlement_claim(U256::from(10), U256::from(30), U256::from(10)).ok(), Some(U256::from(30)));
+}
+
+#[test]
+fn claims_are_rounded_down_and_never_exceed_the_reserve() {
+    let (reserve, supply) = (U256::from(100), U256::from(3));
+
+    let claim = settlement_claim(U256::from(1), reserve, supply).ok();
+    assert_eq!(claim, Some(U256::from(33)));
+    // three holders of one token each, one wei stays in the contract
+    assert_eq!(claim.map(|claim| claim * U256::from(3)), Some(U256::from(99)));
+    assert_eq!(settlement_claim(U256::from(1), U256::from(1), U256::from(2)).ok(), Some(U256::ZERO));
+}
+
+#[test]
+fn nothing_is_owed_without_supply_or_reserve() {
+    assert_eq!(settlement_claim(U256::from(100), U256::from(1_000), U256::ZERO).ok(), Some(U256::ZERO));
+    assert_eq!(settlement_claim(U256::from(100), U256::ZERO, U256::from(1_000)).ok(), Some(U256::ZERO));
+}
+
+#[test]
+fn large_reserves_do_not_overflow_the_product() {
+    // amount * reserve exceeds 256 bits, the claim does not
+    let reserve = U256::MAX / U256::from(2);
+    assert_eq!(settlement_claim(U256::from(4), reserve, U256::from(8)).ok(), Some(reserve / U256::from(2)));
+    assert_eq!(settlement_claim(U256::MAX, reserve, U256::MAX).ok(), Some(reserve));
+}
+
+#[test]
+fn overflowing_claim_is_rejected() {
+    assert!(matches!(
+        settlement_claim(U256::MAX, U256::from(2), U256::from(1)),
+        Err(Erc20GlobalSettlementError::Erc20SettlementClaimOverflow(e)) if e.amount == U256::MAX
+    ));
+}
+
+#[tokio::test]
+#[ignore = "needs a fresh examples/compose_global_settlement.rs in STYLUS_PROGRAM_ADDRESS_GLOBAL_SETTLEMENT"]
+async fn settled_token_is_frozen_and_redeemed_pro_rata() -> eyre::Result<()> {
+    let devnet = fixture_init::devnet().await?;
+    let address = devnet.program("global_settlement").await?;
+    let reserve_address = devnet.my_token().await?;
+    let as_alice: SettledToken<Client> = SettledToken::new(address, devnet.alice_client.clone());
+    let as_bob: SettledToken<Client> = SettledToken::new(address, devnet.bob_client.clone());
+    let reserve: ReserveToken<Client> = ReserveToken::new(reserve_address, devnet.alice_client.clone());
+    let alice = devnet.alice_client.address();
+    let bob = devnet.bob_client.address();
+
+    // alice owns the token, 2 000 reserve tokens back 4 000 tokens
+    as_alice.init(reserve_address).send().await?.await?;
+    as_alice.mint(alice, EthersU256::from(1_000)).send().await?.await?;
+    as_alice.mint(bob, EthersU256::from(3_000)).send().await?.await?;
+    reserve.mint(address, EthersU256::from(2_000)).send().await?.await?;
+
+    assert!(as_alice.redeem(EthersU256::from(100)).send().await.is_err());
+    assert!(as_bob.trigger_settlement().send().await.is_err());
+    as_alice.trigger_settlement().send().await?.await?;
+    assert!(as_alice.is_settled().call().await?);
+    assert_eq!(
+        as_alice.settlement_rate().call().await?,
+        (EthersU256::from(2_000), EthersU256::from(4_000))
+    );
+
+    // frozen for good
+    assert!(as_alice.transfer(bob, EthersU256::from(1)).send().await.is_err());
+    assert!(as_alice.mint(alice, EthersU256::from(1)).send().await.is_err());
+    assert!(as_alice.trigger_settlement().send().await.is_err());
+
+    // the rate does not depend on the order of redemptions
+    assert_eq!(as_alice.redeemable(EthersU256::from(1_000)).call().await?, EthersU256::from(500));
+    let bob_before = reserve.balance_of(bob).call().await?;
+    as_bob.redeem(EthersU256::from(3_000)).send().await?.await?;
+    assert_eq!(reserve.balance_of(bob).call().await? - bob_before, EthersU256::from(1_500));
+
+    let alice_before = reserve.balance_of(alice).call().await?;
+    as_alice.redeem(EthersU256::from(1_000)).send().await?.await?;
+    assert_eq!(reserve.balance_of(alice).call().await? - alice_before, EthersU256::from(500));
+    assert_eq!(reserve.balance_of(address).call().await?, EthersU256::zero());
+    assert_eq!(as_alice.balance_of(alice).call().await?, EthersU256::zero());
+    assert!(as_alice.redeem(EthersU256::from(1)).send().await.is_err());
+    Ok(())
+}
@@ -0,0 +1,111 @@
+//! Index math of `Erc20InterestBearing`: conversions between token and scaled units, and the
+//! scaled debit of transfers and burns.
+use oz_stylus_erc::extensions::erc20_interest_bearing::{
+    check_accrued_index, from_scaled, scaled_debit, to_scaled, Erc20InterestBearingError, RAY,
+};
+use oz_stylus_erc::tokens::erc20::Erc20InsufficientBalance;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    alloy_sol_types::SolError,
+};
+
+fn u(value: u64) -> U256 {
+    U256::from(value)
+}
+
+/// `RAY * numerator / denominator`
+fn index(numerator: u64, denominator: u64) -> U256 {
+    RAY * u(numerator) / u(denominator)
+}
+
+fn overflowed<T>(result: Result<T, Erc20InterestBearingError>) -> Option<U256> {
+    match result {
+        Err(Erc20InterestBearingError::Erc20InterestAmountOverflow(e)) => Some(e.amount),
+        _ => None,
+    }
+}
+
+#[test]
+fn initial_index_converts_one_to_one() {
+    assert_eq!(to_scaled(u(1234), RAY, false).ok(), Some(u(1234)));
+    assert_eq!(to_scaled(u(1234), RAY, true).ok(), Some(u(1234)));
+    assert_eq!(from_scaled(u(1234), RAY).ok(), Some(u(1234)));
+}
+
+#[test]
+fn conversions_round_in_favor_of_the_token() {
+    let index = index(3, 2);
+    // 10 / 1.5 = 6.67
+    assert_eq!(to_scaled(u(10), index, false).ok(), Some(u(6)));
+    assert_eq!(to_scaled(u(10), index, true).ok(), Some(u(7)));
+    // 7 * 1.5 = 10.5
+    assert_eq!(from_scaled(u(7), index).ok(), Some(u(10)));
+    // exact conversions do not round up
+    assert_eq!(to_scaled(u(9), index, true).ok(), Some(u(6)));
+}
+
+#[test]
+fn conversions_report_overflows() {
+    assert_eq!(overflowed(to_scaled(U256::MAX, RAY, false)), Some(U256::MAX));
+    assert_eq!(overflowed(from_scaled(U256::MAX / u(2), index(3, 1))), Some(U256::MAX / u(2)));
+}
+
+#[test]
+fn whole_balance_debits_the_whole_scaled_balance() {
+    let account = Address::repeat_byte(0x11);
+    for (numerator, denominator) in [(1, 1), (11, 10), (3, 2), (7, 3), (1_000_001, 1_000_000)] {
+        let index = index(numerator, denominator);
+        for scaled_balance in [1u64, 7, 19, 1_000, 123_456_789] {
+            let balance = from_scaled(u(scaled_balance), index).unwrap();
+            assert_eq!(scaled_debit(account, balance, u(scaled_balance), index), Ok(u(scaled_balance)));
+        }
+    }
+}
+
+#[test]
+fn partial_debits_round_up_within_the_balance() {
+    let account = Address::repeat_byte(0x11);
+    let index = index(3, 2);
+    // balance 10 (7 scaled), moving 4 debits ceil(2.67) = 3
+    assert_eq!(scaled_debit(account, u(4), u(7), index), Ok(u(3)));
+    assert_eq!(scaled_debit(account, U256::ZERO, u(7), index), Ok(U256::ZERO));
+}
+
+#[test]
+fn insufficient_balance_is_reported_in_token_units() {
+    let account = Address::repeat_byte(0x11);
+    let expected = Erc20InsufficientBalance {
+        sender: account,
+        balance: u(10),
+        needed: u(11),
+    }
+    .encode();
+    assert_eq!(scaled_debit(account, u(11), u(7), index(3, 2)), Err(expected));
+}
+
+#[test]
+fn accrued_index_never_decreases() {
+    assert!(check_accrued_index(index(3, 2), index(3, 2), u(1_000)).is_ok());
+    assert!(check_accrued_index(index(3, 2), index(2, 1), u(1_000)).is_ok());
+    assert!(matches!(
+        check_accrued_index(index(3, 2), RAY, u(1_000)),
+        Err(Erc20InterestBearingError::Erc20InterestInvalidIndex(e)) if e.new_index == RAY
+    ));
+}
+
+#[test]
+fn accrued_index_keeps_the_total_supply_convertible() {
+    let scaled_total_supply = U256::MAX / index(2, 1);
+    // balances double, the total supply still fits
+    assert!(check_accrued_index(RAY, index(2, 1), scaled_total_supply).is_ok());
+    assert!(from_scaled(scaled_total_supply, index(2, 1)).is_ok());
+    // one more wei of index and `totalSupply` would revert for every holder
+    let new_index = index(2, 1) + u(1);
+    assert!(matches!(
+        check_accrued_index(RAY, new_index, scaled_total_supply),
+        Err(Erc20InterestBearingError::Erc20InterestIndexOverflow(e))
+            if e.new_index == new_index && e.scaled_total_supply == scaled_total_supply
+    ));
+    // any index is fine without supply
+    assert!(check_accrued_index(RAY, U256::MAX, U256::ZERO).is_ok());
+}
@@ -0,0 +1,22 @@
+//! Per call bound of the `erc20_mintable` extension.
+use oz_stylus_erc::extensions::erc20_mintable::{check_max_per_tx, Erc20MintableError};
+use stylus_sdk::alloy_primitives::U256;
+
+#[test]
+fn zero_max_per_tx_is_unbounded() {
+    assert!(check_max_per_tx(U256::ZERO, U256::MAX).is_ok());
+}
+
+#[test]
+fn max_per_tx_is_inclusive() {
+    let max = U256::from(1000);
+    assert!(check_max_per_tx(max, U256::ZERO).is_ok());
+    assert!(check_max_per_tx(max, max).is_ok());
+    match check_max_per_tx(max, max + U256::from(1)) {
+        Err(Erc20MintableError::Erc20MintAboveMaxPerTx(e)) => {
+            assert_eq!(e.value, U256::from(1001));
+            assert_eq!(e.max_per_tx, max);
+        }
+        Ok(()) => panic!("mint above max_per_tx accepted"),
+    }
+}
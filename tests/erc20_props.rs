@@ -0,0 +1,224 @@
+//! Property tests of the ERC20 accounting on the host: random mint/burn/transfer/approve/
+//! transferFrom sequences replayed on an in-memory ledger through the transitions
+//! `Erc20::update_balances` and `Erc20::spend_allowance` compute with (`balances_after`,
+//! `remaining_allowance`) and the supply check of `Erc20Cap` (`check_cap`).
+//!
+//! The ledger only stands in for the storage the transitions are read from and written to, the
+//! storage accesses and events themselves only run on a node, see `tests/erc20_fuzz.rs` for the
+//! same operations against a deployed token.
+use oz_stylus_erc::extensions::erc20_cap::check_cap;
+use oz_stylus_erc::tokens::erc20::{balances_after, credit, debit_balance, remaining_allowance};
+use proptest::prelude::*;
+use std::collections::BTreeMap;
+use stylus_sdk::alloy_primitives::{Address, U256};
+
+/// Accounts of the sequences, few enough that operations hit the same balances and allowances.
+const ACCOUNTS: u8 = 4;
+
+#[derive(Debug, Clone)]
+enum Operation {
+    Mint { to: u8, value: U256 },
+    Burn { from: u8, value: U256 },
+    Transfer { from: u8, to: u8, value: U256 },
+    Approve { owner: u8, spender: u8, value: U256 },
+    TransferFrom { spender: u8, from: u8, to: u8, value: U256 },
+}
+
+/// Balances, allowances and supply of a capped token, updated only by successful operations.
+#[derive(Clone, PartialEq, Eq, Debug)]
+struct Ledger {
+    cap: U256,
+    total_supply: U256,
+    balances: BTreeMap<Address, U256>,
+    allowances: BTreeMap<(Address, Address), U256>,
+}
+
+impl Ledger {
+    fn new(cap: U256) -> Self {
+        Ledger {
+            cap,
+            total_supply: U256::ZERO,
+            balances: BTreeMap::new(),
+            allowances: BTreeMap::new(),
+        }
+    }
+
+    fn balance(&self, account: Address) -> U256 {
+        self.balances.get(&account).copied().unwrap_or_default()
+    }
+
+    fn allowance(&self, owner: Address, spender: Address) -> U256 {
+        self.allowances.get(&(owner, spender)).copied().unwrap_or_default()
+    }
+
+    /// `Erc20::update_balances` of a capped token, the zero address mints and burns.
+    fn update(&mut self, from: Address, to: Address, value: U256) -> Result<(), Vec<u8>> {
+        if from == Address::ZERO {
+            check_cap(self.cap, self.total_supply, value)?;
+        }
+        let (from_balance, to_balance, total_supply) =
+            balances_after(from, to, value, self.balance(from), self.balance(to), self.total_supply)?;
+        if from != Address::ZERO {
+            self.balances.insert(from, from_balance);
+        }
+        if to != Address::ZERO {
+            self.balances.insert(to, to_balance);
+        }
+        self.total_supply = total_supply;
+        Ok(())
+    }
+
+    /// `Erc20::spend_allowance`, an infinite allowance is left as is.
+    fn spend_allowance(&mut self, owner: Address, spender: Address, value: U256) -> Result<(), Vec<u8>> {
+        if let Some(remaining) = remaining_allowance(owner, self.allowance(owner, spender), value)? {
+            self.allowances.insert((owner, spender), remaining);
+        }
+        Ok(())
+    }
+
+    /// Applies `operation` on a copy, kept only if it succeeds: a reverted call changes nothing.
+    fn apply(&mut self, operation: &Operation) -> Result<(), Vec<u8>> {
+        let mut next = self.clone();
+        match *operation {
+            Operation::Mint { to, value } => next.update(Address::ZERO, account(to), value)?,
+            Operation::Burn { from, value } => next.update(account(from), Address::ZERO, value)?,
+            Operation::Transfer { from, to, value } => next.update(account(from), account(to), value)?,
+            Operation::Approve { owner, spender, value } => {
+                next.allowances.insert((account(owner), account(spender)), value);
+            }
+            Operation::TransferFrom { spender, from, to, value } => {
+                next.spend_allowance(account(from), account(spender), value)?;
+                next.update(account(from), account(to), value)?;
+            }
+        }
+        *self = next;
+        Ok(())
+    }
+
+    fn sum_of_balances(&self) -> U256 {
+        self.balances.values().fold(U256::ZERO, |sum, balance| sum + *balance)
+    }
+}
+
+/// Non zero test account `index`.
+fn account(index: u8) -> Address {
+    Address::with_last_byte(index + 1)
+}
+
+/// Mostly small amounts, so sequences build up balances, plus the edges of `uint256`.
+fn value() -> impl Strategy<Value = U256> {
+    prop_oneof![
+        6 => (0u64..1_000).prop_map(U256::from),
+        1 => Just(U256::MAX),
+        1 => any::<[u8; 32]>().prop_map(U256::from_be_bytes),
+    ]
+}
+
+fn index() -> impl Strategy<Value = u8> {
+    0..ACCOUNTS
+}
+
+fn operation() -> impl Strategy<Value = Operation> {
+    prop_oneof![
+        (index(), value()).prop_map(|(to, value)| Operation::Mint { to, value }),
+        (index(), value()).prop_map(|(from, value)| Operation::Burn { from, value }),
+        (index(), index(), value()).prop_map(|(from, to, value)| Operation::Transfer { from, to, value }),
+        (index(), index(), value()).prop_map(|(owner, spender, value)| Operation::Approve { owner, spender, value }),
+        (index(), index(), index(), value())
+            .prop_map(|(spender, from, to, value)| Operation::TransferFrom { spender, from, to, value }),
+    ]
+}
+
+fn cap() -> impl Strategy<Value = U256> {
+    prop_oneof![(1u64..5_000).prop_map(U256::from), Just(U256::MAX)]
+}
+
+proptest! {
+    #[test]
+    fn sum_of_balances_is_total_supply_within_cap(cap in cap(), operations in prop::collection::vec(operation(), 1..64)) {
+        let mut ledger = Ledger::new(cap);
+        for operation in &operations {
+            let _ = ledger.apply(operation);
+            prop_assert_eq!(ledger.sum_of_balances(), ledger.total_supply);
+            prop_assert!(ledger.total_supply <= ledger.cap);
+        }
+    }
+
+    #[test]
+    fn transfers_preserve_balances(operations in prop::collection::vec(operation(), 1..64)) {
+        let mut ledger = Ledger::new(U256::MAX);
+        for operation in &operations {
+            let before = ledger.clone();
+            let moved = match *operation {
+                Operation::Transfer { from, to, .. } | Operation::TransferFrom { from, to, .. } => Some((from, to)),
+                _ => None,
+            };
+            if ledger.apply(operation).is_err() {
+                prop_assert_eq!(&ledger, &before);
+                continue;
+            }
+            if let Some((from, to)) = moved {
+                let (from, to) = (account(from), account(to));
+                prop_assert_eq!(
+                    ledger.balance(from).checked_add(ledger.balance(to)),
+                    before.balance(from).checked_add(before.balance(to))
+                );
+                prop_assert_eq!(ledger.total_supply, before.total_supply);
+            }
+        }
+    }
+
+    #[test]
+    fn allowances_never_underflow(operations in prop::collection::vec(operation(), 1..64)) {
+        let mut ledger = Ledger::new(U256::MAX);
+        for operation in &operations {
+            let before = ledger.clone();
+            let result = ledger.apply(operation);
+            if let Operation::TransferFrom { spender, from, value, .. } = *operation {
+                let (owner, spender) = (account(from), account(spender));
+                let allowance = before.allowance(owner, spender);
+                if allowance < value {
+                    prop_assert!(result.is_err());
+                } else if result.is_ok() {
+                    let expected = if allowance == U256::MAX { allowance } else { allowance - value };
+                    prop_assert_eq!(ledger.allowance(owner, spender), expected);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn debit_reports_the_missing_balance() {
+    assert!(debit_balance(account(0), U256::from(5), U256::from(6)).is_err());
+    assert_eq!(debit_balance(account(0), U256::from(5), U256::from(5)).ok(), Some(U256::ZERO));
+}
+
+#[test]
+fn infinite_allowance_is_not_spent() {
+    assert_eq!(remaining_allowance(account(0), U256::MAX, U256::from(7)).ok(), Some(None));
+    assert_eq!(remaining_allowance(account(0), U256::from(7), U256::from(7)).ok(), Some(Some(U256::ZERO)));
+}
+
+#[test]
+fn credit_reports_the_overflow() {
+    assert_eq!(credit(U256::from(2), U256::from(3)).ok(), Some(U256::from(5)));
+    assert!(credit(U256::MAX, U256::from(1)).is_err());
+}
+
+#[test]
+fn self_transfer_keeps_the_balance() {
+    let (from_balance, to_balance, total_supply) =
+        balances_after(account(0), account(0), U256::from(3), U256::from(5), U256::from(9), U256::from(5)).unwrap();
+    assert_eq!((from_balance, to_balance, total_supply), (U256::from(2), U256::from(5), U256::from(5)));
+    assert!(balances_after(account(0), account(0), U256::from(6), U256::from(5), U256::ZERO, U256::from(5)).is_err());
+}
+
+#[test]
+fn mint_and_burn_only_change_the_supply_side() {
+    let minted = balances_after(Address::ZERO, account(1), U256::from(4), U256::ZERO, U256::from(1), U256::from(10));
+    assert_eq!(minted.ok(), Some((U256::ZERO, U256::from(5), U256::from(14))));
+    let burned = balances_after(account(1), Address::ZERO, U256::from(4), U256::from(5), U256::ZERO, U256::from(14));
+    assert_eq!(burned.ok(), Some((U256::from(1), U256::ZERO, U256::from(10))));
+    assert!(balances_after(Address::ZERO, account(1), U256::from(1), U256::ZERO, U256::ZERO, U256::MAX).is_err());
+}
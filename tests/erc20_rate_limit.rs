@@ -0,0 +1,72 @@
+//! Window and limit arithmetic of `extensions::erc20_rate_limit`, saturating instead of
+//! overflowing near the largest `uint256`.
+use oz_stylus_erc::extensions::erc20_rate_limit::{check_outbound, window_elapsed, Erc20RateLimitError};
+use stylus_sdk::alloy_primitives::{Address, U256};
+
+fn account() -> Address {
+    Address::repeat_byte(0x42)
+}
+
+fn amount_exceeded(result: Result<(U256, U256), Erc20RateLimitError>) -> Option<(U256, U256)> {
+    match result {
+        Err(Erc20RateLimitError::Erc20RateLimitAmountExceeded(e)) => Some((e.sent, e.limit)),
+        _ => None,
+    }
+}
+
+fn count_exceeded(result: Result<(U256, U256), Erc20RateLimitError>) -> bool {
+    matches!(result, Err(Erc20RateLimitError::Erc20RateLimitCountExceeded(_)))
+}
+
+#[test]
+fn window_elapses_at_its_end() {
+    let start = U256::from(1_000);
+    let window = U256::from(60);
+
+    assert!(!window_elapsed(U256::from(1_059), start, window));
+    assert!(window_elapsed(U256::from(1_060), start, window));
+    // accounts which never sent have a window started at 0
+    assert!(window_elapsed(U256::from(1_000), U256::ZERO, window));
+}
+
+#[test]
+fn window_ending_past_uint256_never_elapses() {
+    assert!(!window_elapsed(U256::MAX - U256::from(1), U256::from(10), U256::MAX));
+    assert!(window_elapsed(U256::MAX, U256::ZERO, U256::MAX));
+}
+
+#[test]
+fn usage_is_counted_up_to_the_limits() {
+    let limit = U256::from(100);
+
+    assert_eq!(
+        check_outbound(account(), U256::from(40), U256::from(1), U256::from(60), limit, U256::from(2)).ok(),
+        Some((limit, U256::from(2)))
+    );
+    assert_eq!(
+        amount_exceeded(check_outbound(account(), U256::from(40), U256::ZERO, U256::from(61), limit, U256::ZERO)),
+        Some((U256::from(101), limit))
+    );
+    assert!(count_exceeded(check_outbound(
+        account(),
+        U256::ZERO,
+        U256::from(2),
+        U256::from(1),
+        U256::ZERO,
+        U256::from(2)
+    )));
+}
+
+#[test]
+fn amounts_saturate_instead_of_overflowing() {
+    // above any amount limit
+    assert_eq!(
+        amount_exceeded(check_outbound(account(), U256::MAX, U256::ZERO, U256::from(1), U256::MAX - U256::from(1), U256::ZERO)),
+        Some((U256::MAX, U256::MAX - U256::from(1)))
+    );
+    // without limits usage is still recorded, capped
+    assert_eq!(
+        check_outbound(account(), U256::MAX, U256::MAX, U256::MAX, U256::ZERO, U256::ZERO).ok(),
+        Some((U256::MAX, U256::MAX))
+    );
+}
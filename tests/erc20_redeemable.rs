@@ -0,0 +1,118 @@
+//! Reserve amounts of `extensions::erc20_redeemable` at a rate, and redemptions of the deployed
+//! `examples/compose_redeemable.rs` at the rate set by a holder of `RATE_SETTER_ROLE`.
+//!
+//! The devnet test needs a fresh backed token, deploy it without calling `init` and set
+//! `STYLUS_PROGRAM_ADDRESS_REDEEMABLE`, then `cargo test --test erc20_redeemable -- --ignored`.
+//! `MyToken` is the reserve token.
+use ethers::{
+    middleware::SignerMiddleware,
+    prelude::abigen,
+    providers::{Http, Provider},
+    signers::{LocalWallet, Signer},
+    types::U256 as EthersU256,
+};
+use oz_stylus_erc::access::access_control::role_id;
+use oz_stylus_erc::extensions::erc20_redeemable::{reserve_amount_at, Erc20RedeemableError, RATE_PRECISION};
+use stylus_sdk::alloy_primitives::U256;
+
+mod util;
+use util::fixture_init;
+
+abigen!(
+    BackedToken,
+    r#"[
+        function init(address reserve_token, address rate_setter) external
+        function grantRole(bytes32 role, address account) external
+        function mint(address account, uint256 value) external
+        function setRedemptionRate(uint256 new_rate) external
+        function previewRedeem(uint256 amount) external view returns (uint256)
+        function redeem(uint256 amount) external returns (uint256)
+        function balanceOf(address account) external view returns (uint256)
+    ]"#
+);
+
+abigen!(
+    ReserveToken,
+    r#"[
+        function mint(address account, uint256 amount) external
+        function balanceOf(address account) external view returns (uint256)
+    ]"#
+);
+
+type Client = SignerMiddleware<Provider<Http>, LocalWallet>;
+
+#[test]
+fn reserve_amount_follows_the_rate() {
+    let half = RATE_PRECISION / U256::from(2);
+
+    assert_eq!(reserve_amount_at(U256::from(100), RATE_PRECISION).ok(), Some(U256::from(100)));
+    assert_eq!(reserve_amount_at(U256::from(100), half).ok(), Some(U256::from(50)));
+    assert_eq!(reserve_amount_at(U256::from(100), RATE_PRECISION * U256::from(3)).ok(), Some(U256::from(300)));
+    assert_eq!(reserve_amount_at(U256::from(100), U256::ZERO).ok(), Some(U256::ZERO));
+}
+
+#[test]
+fn reserve_amount_is_rounded_down() {
+    let half = RATE_PRECISION / U256::from(2);
+
+    assert_eq!(reserve_amount_at(U256::from(101), half).ok(), Some(U256::from(50)));
+    assert_eq!(reserve_amount_at(U256::from(1), half).ok(), Some(U256::ZERO));
+}
+
+#[test]
+fn large_amounts_do_not_overflow_the_product() {
+    // amount * rate exceeds 256 bits, the reserve amount does not
+    assert_eq!(reserve_amount_at(U256::MAX, RATE_PRECISION).ok(), Some(U256::MAX));
+    assert_eq!(
+        reserve_amount_at(U256::MAX, RATE_PRECISION / U256::from(2)).ok(),
+        Some(U256::MAX / U256::from(2))
+    );
+}
+
+#[test]
+fn overflowing_reserve_amount_is_rejected() {
+    assert!(matches!(
+        reserve_amount_at(U256::MAX, RATE_PRECISION * U256::from(2)),
+        Err(Erc20RedeemableError::Erc20RedemptionOverflow(e)) if e.amount == U256::MAX
+    ));
+}
+
+#[tokio::test]
+#[ignore = "needs a fresh examples/compose_redeemable.rs in STYLUS_PROGRAM_ADDRESS_REDEEMABLE"]
+async fn redemptions_pay_the_rate_set_by_a_rate_setter() -> eyre::Result<()> {
+    let devnet = fixture_init::devnet().await?;
+    let address = devnet.program("redeemable").await?;
+    let reserve_address = devnet.my_token().await?;
+    let as_alice: BackedToken<Client> = BackedToken::new(address, devnet.alice_client.clone());
+    let as_bob: BackedToken<Client> = BackedToken::new(address, devnet.bob_client.clone());
+    let reserve: ReserveToken<Client> = ReserveToken::new(reserve_address, devnet.alice_client.clone());
+    let alice = devnet.alice_client.address();
+    let bob = devnet.bob_client.address();
+
+    // alice is admin, bob sets the rate
+    as_alice.init(reserve_address, bob).send().await?.await?;
+    as_alice.grant_role(role_id("MINTER_ROLE").0, alice).send().await?.await?;
+    as_alice.mint(alice, EthersU256::from(1_000)).send().await?.await?;
+    reserve.mint(address, EthersU256::from(1_000)).send().await?.await?;
+
+    // no rate yet, and the admin is not a rate setter
+    assert!(as_alice.redeem(EthersU256::from(100)).send().await.is_err());
+    assert!(as_alice
+        .set_redemption_rate(EthersU256::from(RATE_PRECISION.to::<u128>()))
+        .send()
+        .await
+        .is_err());
+
+    // 0.5 reserve token per backed token
+    let half = EthersU256::from((RATE_PRECISION / U256::from(2)).to::<u128>());
+    as_bob.set_redemption_rate(half).send().await?.await?;
+    assert_eq!(as_alice.preview_redeem(EthersU256::from(101)).call().await?, EthersU256::from(50));
+
+    let reserve_before = reserve.balance_of(alice).call().await?;
+    let held_before = reserve.balance_of(address).call().await?;
+    as_alice.redeem(EthersU256::from(100)).send().await?.await?;
+    assert_eq!(as_alice.balance_of(alice).call().await?, EthersU256::from(900));
+    assert_eq!(reserve.balance_of(alice).call().await? - reserve_before, EthersU256::from(50));
+    assert_eq!(held_before - reserve.balance_of(address).call().await?, EthersU256::from(50));
+    Ok(())
+}
@@ -0,0 +1,62 @@
+//! Calldata of the ERC777 hooks called by `Erc20SendHooks`, checked against the ABI encoding of
+//! ethers, and the interface ids used to detect them.
+use ethers::abi::{encode, Token};
+use oz_stylus_erc::extensions::erc20_send_hooks::{hook_calldata, TOKENS_RECEIVED_SELECTOR, TOKENS_TO_SEND_SELECTOR};
+use oz_stylus_erc::utils::erc165::{interface_id, IERC777_RECIPIENT, IERC777_SENDER};
+use stylus_sdk::alloy_primitives::{Address, U256};
+
+fn expected(selector: [u8; 4], amount: u64, data: &[u8], operator_data: &[u8]) -> Vec<u8> {
+    let mut calldata = selector.to_vec();
+    calldata.extend(encode(&[
+        Token::Address([1; 20].into()),
+        Token::Address([2; 20].into()),
+        Token::Address([3; 20].into()),
+        Token::Uint(amount.into()),
+        Token::Bytes(data.to_vec()),
+        Token::Bytes(operator_data.to_vec()),
+    ]));
+    calldata
+}
+
+fn calldata(selector: [u8; 4], amount: u64, data: &[u8], operator_data: &[u8]) -> Vec<u8> {
+    hook_calldata(
+        selector,
+        Address::repeat_byte(1),
+        Address::repeat_byte(2),
+        Address::repeat_byte(3),
+        U256::from(amount),
+        data,
+        operator_data,
+    )
+}
+
+#[test]
+fn hook_calldata_is_abi_encoded() {
+    for (data, operator_data) in [
+        (&b""[..], &b""[..]),
+        (&b"memo"[..], &b""[..]),
+        (&[7u8; 32][..], &[9u8; 33][..]),
+        (&[7u8; 65][..], &b"operator"[..]),
+    ] {
+        assert_eq!(
+            calldata(TOKENS_RECEIVED_SELECTOR, 42, data, operator_data),
+            expected(TOKENS_RECEIVED_SELECTOR, 42, data, operator_data)
+        );
+        assert_eq!(
+            calldata(TOKENS_TO_SEND_SELECTOR, 42, data, operator_data),
+            expected(TOKENS_TO_SEND_SELECTOR, 42, data, operator_data)
+        );
+    }
+}
+
+#[test]
+fn hook_interfaces_are_their_selectors() {
+    assert_eq!(
+        interface_id(&["tokensToSend(address,address,address,uint256,bytes,bytes)"]),
+        IERC777_SENDER
+    );
+    assert_eq!(
+        interface_id(&["tokensReceived(address,address,address,uint256,bytes,bytes)"]),
+        IERC777_RECIPIENT
+    );
+}
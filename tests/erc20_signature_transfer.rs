@@ -0,0 +1,82 @@
+//! Permits of `extensions::erc20_signature_transfer` must hash like Uniswap Permit2
+//! `SignatureTransfer`, so transfers signed by Permit2 tooling verify on Stylus tokens.
+use oz_stylus_erc::extensions::erc20_signature_transfer::{
+    permit_transfer_from_hash, PermitTransferFrom, PERMIT_TRANSFER_FROM_TYPE, SIGNATURE_TRANSFER_VERSION,
+    TOKEN_PERMISSIONS_TYPE,
+};
+use oz_stylus_erc::utils::eip712::{self, address_word, hash_words, u256_word};
+use stylus_sdk::{
+    alloy_primitives::{b256, Address, U256},
+    crypto::keccak,
+};
+
+const TOKEN: Address = Address::repeat_byte(0x70);
+const SPENDER: Address = Address::repeat_byte(0x5b);
+const CHAIN_ID: u64 = 412346;
+
+fn permit() -> PermitTransferFrom {
+    PermitTransferFrom {
+        amount: U256::from(1_000),
+        nonce: U256::from(7),
+        deadline: U256::from(1_700_000_000u64),
+    }
+}
+
+#[test]
+fn type_hashes_match_permit2() {
+    // `PermitHash._TOKEN_PERMISSIONS_TYPEHASH`
+    assert_eq!(
+        keccak(TOKEN_PERMISSIONS_TYPE.as_bytes()),
+        b256!("618358ac3db8dc274f0cd8829da7e234bd48cd73c4a740aede1adec9846d06a1")
+    );
+    // `PermitHash._PERMIT_TRANSFER_FROM_TYPEHASH`
+    assert_eq!(
+        keccak(PERMIT_TRANSFER_FROM_TYPE.as_bytes()),
+        b256!("939c21a48a8dbe3a9a2404a1d46691e4d39f6583d6ec6b35714604c986d80106")
+    );
+}
+
+#[test]
+fn permit_hash_nests_the_token_permissions() {
+    let permitted = hash_words(&[
+        keccak(TOKEN_PERMISSIONS_TYPE.as_bytes()),
+        address_word(TOKEN),
+        u256_word(U256::from(1_000)),
+    ]);
+    let expected = hash_words(&[
+        keccak(PERMIT_TRANSFER_FROM_TYPE.as_bytes()),
+        permitted,
+        address_word(SPENDER),
+        u256_word(U256::from(7)),
+        u256_word(U256::from(1_700_000_000u64)),
+    ]);
+
+    assert_eq!(permit_transfer_from_hash(TOKEN, SPENDER, &permit()), expected);
+}
+
+#[test]
+fn permit_hash_matches_a_known_vector() {
+    assert_eq!(
+        permit_transfer_from_hash(TOKEN, SPENDER, &permit()),
+        b256!("378631079c5a3ad809223554edee09214a691789d83ae68b9d40c7b2b3cbc3c8")
+    );
+}
+
+#[test]
+fn permit_hash_is_bound_to_the_spender() {
+    assert_ne!(
+        permit_transfer_from_hash(TOKEN, SPENDER, &permit()),
+        permit_transfer_from_hash(TOKEN, Address::repeat_byte(0x5c), &permit())
+    );
+}
+
+#[test]
+fn signed_digest_matches_a_known_vector() {
+    let domain_separator = eip712::domain_separator("My token", SIGNATURE_TRANSFER_VERSION, CHAIN_ID, TOKEN);
+    let struct_hash = permit_transfer_from_hash(TOKEN, SPENDER, &permit());
+
+    assert_eq!(
+        eip712::to_typed_data_hash(domain_separator, struct_hash),
+        b256!("1bf8ce0a99775cd6ccda0a061381a666107eed4090f4623c9071184708969cf7")
+    );
+}
@@ -0,0 +1,443 @@
+//! Model-based test of the pause, cap and role state machine of
+//! `examples/compose_pausable_cap_roles.rs` on the host: random sequences of init, pause, unpause,
+//! mints, burns, transfers and role changes run on a replica of the example's externals and on a
+//! reference model, which must agree on the outcome of every operation and on the state it
+//! leaves.
+//!
+//! The replica chains the checks the extensions run on storage, in the order of the example:
+//! `Initializable` (`check_version`), `Erc20Cap` (`check_new_cap`, `check_cap`), `Pausable`
+//! (`require_not_paused`, `require_paused`), `AccessControl` (`check_membership`) and `Erc20`
+//! (`balances_after`, `remaining_allowance`), so a change in one extension breaking the
+//! composition shows here. Only the zero address checks of `Erc20` are repeated in the replica.
+//! `tests/erc20_fuzz.rs` runs the same operations against the deployed example.
+use oz_stylus_erc::access::access_control::{check_membership, role_id, DEFAULT_ADMIN_ROLE};
+use oz_stylus_erc::extensions::erc20_cap::{check_cap, check_new_cap};
+use oz_stylus_erc::tokens::erc20::{balances_after, remaining_allowance};
+use oz_stylus_erc::utils::initializable::check_version;
+use oz_stylus_erc::utils::pausable::{require_not_paused, require_paused};
+use proptest::prelude::*;
+use std::collections::{BTreeMap, BTreeSet};
+use stylus_sdk::alloy_primitives::{Address, B256, U256};
+
+/// Signers of the sequences, accounts `0..ACTORS`, the index `ACTORS` is the zero address.
+const ACTORS: u8 = 3;
+
+/// Roles of the example, administered by `DEFAULT_ADMIN_ROLE`.
+const MANAGED_ROLES: [&str; 3] = ["PAUSER_ROLE", "MINTER_ROLE", "BURNER_ROLE"];
+
+#[derive(Debug, Clone)]
+enum Operation {
+    Init { cap: U256 },
+    Pause,
+    Unpause,
+    Mint { to: u8, value: U256 },
+    Burn { from: u8, value: U256 },
+    Transfer { to: u8, value: U256 },
+    Approve { spender: u8, value: U256 },
+    TransferFrom { from: u8, to: u8, value: U256 },
+    GrantRole { role: u8, account: u8 },
+    RevokeRole { role: u8, account: u8 },
+    RenounceRole { role: u8, confirmation: u8 },
+}
+
+/// Storage of the example, compared after every operation.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+struct Token {
+    initialized_version: u64,
+    cap: U256,
+    paused: bool,
+    total_supply: U256,
+    /// non zero balances
+    balances: BTreeMap<Address, U256>,
+    /// non zero allowances
+    allowances: BTreeMap<(Address, Address), U256>,
+    /// (role, member)
+    roles: BTreeSet<(B256, Address)>,
+}
+
+impl Token {
+    fn balance(&self, account: Address) -> U256 {
+        self.balances.get(&account).copied().unwrap_or_default()
+    }
+
+    fn allowance(&self, owner: Address, spender: Address) -> U256 {
+        self.allowances.get(&(owner, spender)).copied().unwrap_or_default()
+    }
+
+    fn has_role(&self, role: B256, account: Address) -> bool {
+        self.roles.contains(&(role, account))
+    }
+
+    fn set_balance(&mut self, account: Address, balance: U256) {
+        if balance == U256::ZERO {
+            self.balances.remove(&account);
+        } else {
+            self.balances.insert(account, balance);
+        }
+    }
+
+    fn set_allowance(&mut self, owner: Address, spender: Address, value: U256) {
+        if value == U256::ZERO {
+            self.allowances.remove(&(owner, spender));
+        } else {
+            self.allowances.insert((owner, spender), value);
+        }
+    }
+
+    /// Runs `operation` signed by `signer` on the replica, on a copy kept only if it succeeds: a
+    /// reverted call changes nothing.
+    fn execute(&mut self, signer: Address, operation: &Operation) -> Result<(), Vec<u8>> {
+        let mut next = self.clone();
+        next.run(signer, operation)?;
+        *self = next;
+        Ok(())
+    }
+
+    /// The externals of the example, each check where the example and its extensions run it.
+    fn run(&mut self, signer: Address, operation: &Operation) -> Result<(), Vec<u8>> {
+        match *operation {
+            Operation::Init { cap } => {
+                check_version(self.initialized_version, 1)?;
+                self.initialized_version = 1;
+                check_new_cap(self.cap, cap)?;
+                self.cap = cap;
+                self.roles.insert((DEFAULT_ADMIN_ROLE, signer));
+            }
+            Operation::Pause => {
+                self.only_role(role(1), signer)?;
+                require_not_paused(self.paused)?;
+                self.paused = true;
+            }
+            Operation::Unpause => {
+                self.only_role(role(1), signer)?;
+                require_paused(self.paused)?;
+                self.paused = false;
+            }
+            Operation::Mint { to, value } => {
+                self.only_role(role(2), signer)?;
+                require_not_paused(self.paused)?;
+                check_cap(self.cap, self.total_supply, value)?;
+                non_zero(account(to))?;
+                self.update(Address::ZERO, account(to), value)?;
+            }
+            Operation::Burn { from, value } => {
+                self.only_role(role(3), signer)?;
+                require_not_paused(self.paused)?;
+                non_zero(account(from))?;
+                self.update(account(from), Address::ZERO, value)?;
+            }
+            Operation::Transfer { to, value } => {
+                require_not_paused(self.paused)?;
+                non_zero(account(to))?;
+                self.update(signer, account(to), value)?;
+            }
+            Operation::Approve { spender, value } => {
+                non_zero(account(spender))?;
+                self.set_allowance(signer, account(spender), value);
+            }
+            Operation::TransferFrom { from, to, value } => {
+                require_not_paused(self.paused)?;
+                let (from, to) = (account(from), account(to));
+                if let Some(remaining) = remaining_allowance(from, self.allowance(from, signer), value)? {
+                    non_zero(from)?;
+                    self.set_allowance(from, signer, remaining);
+                }
+                non_zero(from)?;
+                non_zero(to)?;
+                self.update(from, to, value)?;
+            }
+            Operation::GrantRole { role: index, account: member } => {
+                // the admin of every role of the example is `DEFAULT_ADMIN_ROLE`
+                self.only_role(DEFAULT_ADMIN_ROLE, signer)?;
+                self.roles.insert((role(index), account(member)));
+            }
+            Operation::RevokeRole { role: index, account: member } => {
+                self.only_role(DEFAULT_ADMIN_ROLE, signer)?;
+                self.roles.remove(&(role(index), account(member)));
+            }
+            Operation::RenounceRole { role: index, confirmation } => {
+                if account(confirmation) != signer {
+                    return Err(b"AccessControlBadConfirmation".to_vec());
+                }
+                self.roles.remove(&(role(index), signer));
+            }
+        }
+        Ok(())
+    }
+
+    fn only_role(&self, role: B256, signer: Address) -> Result<(), Vec<u8>> {
+        Ok(check_membership(self.has_role(role, signer), role, signer)?)
+    }
+
+    /// `Erc20::update_balances`, the zero address mints and burns.
+    fn update(&mut self, from: Address, to: Address, value: U256) -> Result<(), Vec<u8>> {
+        let (from_balance, to_balance, total_supply) =
+            balances_after(from, to, value, self.balance(from), self.balance(to), self.total_supply)?;
+        if from != Address::ZERO {
+            self.set_balance(from, from_balance);
+        }
+        if to != Address::ZERO {
+            self.set_balance(to, to_balance);
+        }
+        self.total_supply = total_supply;
+        Ok(())
+    }
+
+    /// Reference model: whether `operation` signed by `signer` succeeds on the example, applied
+    /// when it does. Written from the documented behavior, without the crate's checks.
+    fn expect(&mut self, signer: Address, operation: &Operation) -> bool {
+        match *operation {
+            Operation::Init { cap } => {
+                if self.initialized_version != 0 || cap == U256::ZERO {
+                    return false;
+                }
+                self.initialized_version = 1;
+                self.cap = cap;
+                self.roles.insert((DEFAULT_ADMIN_ROLE, signer));
+            }
+            Operation::Pause | Operation::Unpause => {
+                let pause = matches!(operation, Operation::Pause);
+                if !self.has_role(role(1), signer) || self.paused == pause {
+                    return false;
+                }
+                self.paused = pause;
+            }
+            Operation::Mint { to, value } => {
+                let to = account(to);
+                let within_cap = self.total_supply.checked_add(value).is_some_and(|supply| supply <= self.cap);
+                if !self.has_role(role(2), signer) || self.paused || !within_cap || to == Address::ZERO {
+                    return false;
+                }
+                self.total_supply += value;
+                self.set_balance(to, self.balance(to) + value);
+            }
+            Operation::Burn { from, value } => {
+                let from = account(from);
+                if !self.has_role(role(3), signer) || self.paused || from == Address::ZERO || self.balance(from) < value {
+                    return false;
+                }
+                self.total_supply -= value;
+                self.set_balance(from, self.balance(from) - value);
+            }
+            Operation::Transfer { to, value } => {
+                let to = account(to);
+                if self.paused || to == Address::ZERO || self.balance(signer) < value {
+                    return false;
+                }
+                self.move_balance(signer, to, value);
+            }
+            Operation::Approve { spender, value } => {
+                if account(spender) == Address::ZERO {
+                    return false;
+                }
+                self.set_allowance(signer, account(spender), value);
+            }
+            Operation::TransferFrom { from, to, value } => {
+                let (from, to) = (account(from), account(to));
+                let allowance = self.allowance(from, signer);
+                if self.paused || from == Address::ZERO || to == Address::ZERO {
+                    return false;
+                }
+                if allowance < value || self.balance(from) < value {
+                    return false;
+                }
+                if allowance != U256::MAX {
+                    self.set_allowance(from, signer, allowance - value);
+                }
+                self.move_balance(from, to, value);
+            }
+            Operation::GrantRole { role: index, account: member } => {
+                if !self.has_role(DEFAULT_ADMIN_ROLE, signer) {
+                    return false;
+                }
+                self.roles.insert((role(index), account(member)));
+            }
+            Operation::RevokeRole { role: index, account: member } => {
+                if !self.has_role(DEFAULT_ADMIN_ROLE, signer) {
+                    return false;
+                }
+                self.roles.remove(&(role(index), account(member)));
+            }
+            Operation::RenounceRole { role: index, confirmation } => {
+                if account(confirmation) != signer {
+                    return false;
+                }
+                self.roles.remove(&(role(index), signer));
+            }
+        }
+        true
+    }
+
+    fn move_balance(&mut self, from: Address, to: Address, value: U256) {
+        self.set_balance(from, self.balance(from) - value);
+        self.set_balance(to, self.balance(to) + value);
+    }
+
+    fn sum_of_balances(&self) -> U256 {
+        self.balances.values().fold(U256::ZERO, |sum, balance| sum + *balance)
+    }
+}
+
+/// Test account `index`, the zero address for `ACTORS`.
+fn account(index: u8) -> Address {
+    if index >= ACTORS {
+        return Address::ZERO;
+    }
+    Address::with_last_byte(index + 1)
+}
+
+/// `DEFAULT_ADMIN_ROLE` for 0, then `MANAGED_ROLES`.
+fn role(index: u8) -> B256 {
+    match index {
+        0 => DEFAULT_ADMIN_ROLE,
+        index => role_id(MANAGED_ROLES[usize::from(index - 1) % MANAGED_ROLES.len()]),
+    }
+}
+
+fn non_zero(account: Address) -> Result<(), Vec<u8>> {
+    if account == Address::ZERO {
+        return Err(b"zero address".to_vec());
+    }
+    Ok(())
+}
+
+fn signer() -> impl Strategy<Value = u8> {
+    0..ACTORS
+}
+
+/// Mostly actors, now and then the zero address.
+fn target() -> impl Strategy<Value = u8> {
+    prop_oneof![9 => 0..ACTORS, 1 => Just(ACTORS)]
+}
+
+/// Mostly small amounts, so mints reach the cap and sequences build up balances, plus the edges
+/// of `uint256`.
+fn value() -> impl Strategy<Value = U256> {
+    prop_oneof![
+        8 => (0u64..2_000).prop_map(U256::from),
+        1 => Just(U256::MAX),
+        1 => any::<[u8; 32]>().prop_map(U256::from_be_bytes),
+    ]
+}
+
+fn cap() -> impl Strategy<Value = U256> {
+    prop_oneof![4 => (1u64..10_000).prop_map(U256::from), 1 => Just(U256::ZERO), 1 => Just(U256::MAX)]
+}
+
+fn role_index() -> impl Strategy<Value = u8> {
+    0..=MANAGED_ROLES.len() as u8
+}
+
+fn operation() -> impl Strategy<Value = Operation> {
+    prop_oneof![
+        1 => cap().prop_map(|cap| Operation::Init { cap }),
+        2 => Just(Operation::Pause),
+        2 => Just(Operation::Unpause),
+        4 => (target(), value()).prop_map(|(to, value)| Operation::Mint { to, value }),
+        3 => (target(), value()).prop_map(|(from, value)| Operation::Burn { from, value }),
+        3 => (target(), value()).prop_map(|(to, value)| Operation::Transfer { to, value }),
+        2 => (target(), value()).prop_map(|(spender, value)| Operation::Approve { spender, value }),
+        3 => (target(), target(), value()).prop_map(|(from, to, value)| Operation::TransferFrom { from, to, value }),
+        2 => (role_index(), target()).prop_map(|(role, account)| Operation::GrantRole { role, account }),
+        2 => (role_index(), target()).prop_map(|(role, account)| Operation::RevokeRole { role, account }),
+        1 => (role_index(), target()).prop_map(|(role, confirmation)| Operation::RenounceRole { role, confirmation }),
+    ]
+}
+
+/// Token initialized by actor 0 under `cap`, which then holds every role as in the devnet fuzz.
+fn initialized(cap: U256) -> Token {
+    let mut token = Token::default();
+    let admin = account(0);
+    token.execute(admin, &Operation::Init { cap }).unwrap();
+    for index in 1..=MANAGED_ROLES.len() as u8 {
+        token.execute(admin, &Operation::GrantRole { role: index, account: 0 }).unwrap();
+    }
+    token
+}
+
+proptest! {
+    #[test]
+    fn replica_follows_the_model(
+        cap in (1u64..10_000).prop_map(U256::from),
+        steps in prop::collection::vec((signer(), operation()), 1..96),
+    ) {
+        let mut replica = initialized(cap);
+        let mut model = replica.clone();
+        for (step, (signer, operation)) in steps.iter().enumerate() {
+            let signer = account(*signer);
+            let executed = replica.execute(signer, operation);
+            let expected = model.expect(signer, operation);
+            prop_assert_eq!(executed.is_ok(), expected, "step {}: {:?} by {:?}", step, operation, signer);
+            prop_assert_eq!(&replica, &model, "step {}: {:?} by {:?}", step, operation, signer);
+            prop_assert_eq!(replica.sum_of_balances(), replica.total_supply);
+            prop_assert!(replica.total_supply <= replica.cap);
+        }
+    }
+
+    #[test]
+    fn nothing_is_minted_before_init(steps in prop::collection::vec((signer(), operation()), 1..32)) {
+        let mut replica = Token::default();
+        let mut model = Token::default();
+        for (signer, operation) in &steps {
+            let signer = account(*signer);
+            prop_assert_eq!(replica.execute(signer, operation).is_ok(), model.expect(signer, operation));
+            prop_assert_eq!(&replica, &model);
+            if replica.initialized_version == 0 {
+                prop_assert_eq!(replica.total_supply, U256::ZERO);
+                prop_assert!(replica.roles.is_empty());
+            }
+        }
+    }
+}
+
+#[test]
+fn paused_token_only_unpauses_and_manages_roles() {
+    let mut token = initialized(U256::from(1_000));
+    let (admin, other) = (account(0), account(1));
+    token.execute(admin, &Operation::Mint { to: 1, value: U256::from(10) }).unwrap();
+    token.execute(admin, &Operation::Pause).unwrap();
+
+    let before = token.clone();
+    assert!(token.execute(admin, &Operation::Mint { to: 1, value: U256::from(1) }).is_err());
+    assert!(token.execute(admin, &Operation::Burn { from: 1, value: U256::from(1) }).is_err());
+    assert!(token.execute(other, &Operation::Transfer { to: 0, value: U256::from(1) }).is_err());
+    assert!(token.execute(admin, &Operation::Pause).is_err());
+    assert_eq!(token, before);
+
+    token.execute(admin, &Operation::GrantRole { role: 1, account: 1 }).unwrap();
+    token.execute(other, &Operation::Unpause).unwrap();
+    token.execute(other, &Operation::Transfer { to: 0, value: U256::from(1) }).unwrap();
+    assert_eq!(token.balance(admin), U256::from(1));
+}
+
+#[test]
+fn cap_is_set_once_and_bounds_mints() {
+    let mut token = initialized(U256::from(100));
+    let admin = account(0);
+    assert!(token.execute(admin, &Operation::Init { cap: U256::from(1_000) }).is_err());
+    token.execute(admin, &Operation::Mint { to: 1, value: U256::from(100) }).unwrap();
+    assert!(token.execute(admin, &Operation::Mint { to: 1, value: U256::from(1) }).is_err());
+
+    // burning makes room again
+    token.execute(admin, &Operation::Burn { from: 1, value: U256::from(1) }).unwrap();
+    token.execute(admin, &Operation::Mint { to: 2, value: U256::from(1) }).unwrap();
+    assert_eq!(token.total_supply, U256::from(100));
+
+    assert!(Token::default().execute(admin, &Operation::Init { cap: U256::ZERO }).is_err());
+}
+
+#[test]
+fn revoked_minter_can_not_mint() {
+    let mut token = initialized(U256::from(100));
+    let admin = account(0);
+    token.execute(admin, &Operation::RevokeRole { role: 2, account: 0 }).unwrap();
+    assert!(token.execute(admin, &Operation::Mint { to: 1, value: U256::from(1) }).is_err());
+    // the admin keeps granting, itself included
+    token.execute(admin, &Operation::GrantRole { role: 2, account: 0 }).unwrap();
+    token.execute(admin, &Operation::Mint { to: 1, value: U256::from(1) }).unwrap();
+
+    // renouncing needs the caller's own address as confirmation
+    assert!(token.execute(admin, &Operation::RenounceRole { role: 0, confirmation: 1 }).is_err());
+    token.execute(admin, &Operation::RenounceRole { role: 0, confirmation: 0 }).unwrap();
+    assert!(token.execute(admin, &Operation::GrantRole { role: 2, account: 1 }).is_err());
+}
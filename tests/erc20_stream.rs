@@ -0,0 +1,32 @@
+//! Linear release of `Erc20Stream` deposits.
+use oz_stylus_erc::extensions::erc20_stream::streamed_amount;
+use stylus_sdk::alloy_primitives::U256;
+
+fn streamed(deposit: U256, now: u64) -> U256 {
+    streamed_amount(deposit, U256::from(1_000), U256::from(1_100), U256::from(now))
+}
+
+#[test]
+fn nothing_is_earned_before_the_start_and_everything_after_the_stop() {
+    let deposit = U256::from(1_000_000);
+
+    assert_eq!(streamed(deposit, 0), U256::ZERO);
+    assert_eq!(streamed(deposit, 1_000), U256::ZERO);
+    assert_eq!(streamed(deposit, 1_100), deposit);
+    assert_eq!(streamed(deposit, 5_000), deposit);
+}
+
+#[test]
+fn deposit_is_released_every_second_rounding_down() {
+    // not a multiple of the 100 seconds duration
+    let deposit = U256::from(1_050);
+
+    assert_eq!(streamed(deposit, 1_001), U256::from(10));
+    assert_eq!(streamed(deposit, 1_050), U256::from(525));
+    assert_eq!(streamed(deposit, 1_099), U256::from(1_039));
+}
+
+#[test]
+fn huge_deposits_do_not_overflow() {
+    assert_eq!(streamed(U256::MAX, 1_050), U256::MAX / U256::from(2));
+}
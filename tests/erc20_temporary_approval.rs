@@ -0,0 +1,16 @@
+//! Deadline check of the `erc20_temporary_approval` extension.
+use oz_stylus_erc::extensions::erc20_temporary_approval::is_expired;
+use stylus_sdk::alloy_primitives::U256;
+
+#[test]
+fn zero_deadline_never_expires() {
+    assert!(!is_expired(U256::ZERO, U256::MAX));
+}
+
+#[test]
+fn allowance_is_spendable_at_its_deadline() {
+    let deadline = U256::from(1_700_000_000u64);
+    assert!(!is_expired(deadline, deadline - U256::from(1)));
+    assert!(!is_expired(deadline, deadline));
+    assert!(is_expired(deadline, deadline + U256::from(1)));
+}
@@ -0,0 +1,31 @@
+//! Signed delegations of `Erc20Votes` must hash like OpenZeppelin `Votes`, so delegations signed
+//! by governance tooling verify on Stylus tokens.
+use oz_stylus_erc::extensions::erc20_votes::{delegation_hash, DELEGATION_TYPE};
+use oz_stylus_erc::utils::eip712::{address_word, hash_words, u256_word};
+use stylus_sdk::{
+    alloy_primitives::{b256, Address, U256},
+    crypto::keccak,
+};
+
+#[test]
+fn delegation_type_hash_matches_openzeppelin() {
+    // `Votes.DELEGATION_TYPEHASH`
+    assert_eq!(
+        keccak(DELEGATION_TYPE.as_bytes()),
+        b256!("e48329057bfd03d55e49b547132e39cffd9c1820ad7b9d4c5307691425d15adf")
+    );
+}
+
+#[test]
+fn delegation_hash_encodes_delegatee_nonce_and_expiry() {
+    let delegatee = Address::repeat_byte(0xde);
+    let (nonce, expiry) = (U256::from(3), U256::from(1_700_000_000u64));
+
+    let expected = hash_words(&[
+        keccak(DELEGATION_TYPE.as_bytes()),
+        address_word(delegatee),
+        u256_word(nonce),
+        u256_word(expiry),
+    ]);
+    assert_eq!(delegation_hash(delegatee, nonce, expiry), expected);
+}
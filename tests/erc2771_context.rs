@@ -0,0 +1,23 @@
+//! Splitting of forwarded calldata by `utils::erc2771_context`.
+use oz_stylus_erc::utils::erc2771_context::{split_forwarded, FORWARDED_SENDER_LEN};
+use stylus_sdk::alloy_primitives::Address;
+
+/// `transfer(address,uint256)` calldata length.
+const ARGS_LEN: usize = 4 + 2 * 32;
+
+#[test]
+fn appended_sender_is_split_from_the_calldata() {
+    let user = Address::repeat_byte(0xa1);
+    let mut calldata = vec![0x11u8; ARGS_LEN];
+    calldata.extend_from_slice(user.as_slice());
+
+    let (sender, data) = split_forwarded(&calldata, ARGS_LEN).unwrap();
+    assert_eq!(sender, user);
+    assert_eq!(data, &calldata[..ARGS_LEN]);
+}
+
+#[test]
+fn calldata_without_exactly_one_sender_is_rejected() {
+    assert!(split_forwarded(&[0u8; ARGS_LEN], ARGS_LEN).is_none());
+    assert!(split_forwarded(&vec![0u8; ARGS_LEN + FORWARDED_SENDER_LEN + 1], ARGS_LEN).is_none());
+}
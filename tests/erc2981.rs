@@ -0,0 +1,24 @@
+//! Royalty amounts of `Erc2981::royaltyInfo`.
+use oz_stylus_erc::extensions::erc2981::{royalty_amount, FEE_DENOMINATOR};
+use stylus_sdk::alloy_primitives::U256;
+
+#[test]
+fn royalty_is_the_basis_points_of_the_sale_price() {
+    assert_eq!(royalty_amount(U256::from(10_000), U256::from(250)), U256::from(250));
+    assert_eq!(royalty_amount(U256::from(1_000_000), U256::from(500)), U256::from(50_000));
+    assert_eq!(royalty_amount(U256::from(123), U256::from(FEE_DENOMINATOR)), U256::from(123));
+    assert_eq!(royalty_amount(U256::from(123), U256::ZERO), U256::ZERO);
+}
+
+#[test]
+fn royalty_rounds_down() {
+    // 2.5% of 99 wei is 2.475 wei
+    assert_eq!(royalty_amount(U256::from(99), U256::from(250)), U256::from(2));
+    assert_eq!(royalty_amount(U256::from(1), U256::from(9_999)), U256::ZERO);
+}
+
+#[test]
+fn royalty_saturates_near_the_max_price() {
+    let royalty = royalty_amount(U256::MAX, U256::from(FEE_DENOMINATOR));
+    assert_eq!(royalty, U256::MAX / U256::from(FEE_DENOMINATOR));
+}
@@ -0,0 +1,128 @@
+//! Share conversions and fees of the `Erc4626` vault, and its read-only reentrancy guard.
+//!
+//! The devnet test needs a fresh `examples/compose_erc4626.rs` in
+//! `STYLUS_PROGRAM_ADDRESS_ERC4626` (deployed without calling `init`) and an
+//! `examples/reentrant_asset.rs` in `STYLUS_PROGRAM_ADDRESS_REENTRANT_ASSET`, then
+//! `cargo test --test erc4626 -- --ignored`.
+use ethers::{
+    middleware::SignerMiddleware,
+    prelude::abigen,
+    providers::{Http, Provider},
+    signers::{LocalWallet, Signer},
+    types::U256 as EthersU256,
+};
+use oz_stylus_erc::extensions::erc20_wrapper::Rounding;
+use oz_stylus_erc::extensions::erc4626::{assets_to_shares, fee_on_raw, fee_on_total, shares_to_assets};
+use stylus_sdk::alloy_primitives::U256;
+
+mod util;
+use util::fixture_init;
+
+abigen!(
+    FeeVault,
+    r#"[
+        function init(address asset, address fee_recipient) external
+        function deposit(uint256 assets, address receiver) external returns (uint256)
+        function redeem(uint256 shares, address receiver, address owner) external returns (uint256)
+        function convertToAssets(uint256 shares) external view returns (uint256)
+        function balanceOf(address account) external view returns (uint256)
+    ]"#
+);
+
+abigen!(
+    ReentrantAsset,
+    r#"[
+        function mint(address account, uint256 value) external
+        function approve(address spender, uint256 value) external returns (bool)
+        function setCallback(bool enabled) external
+        function balanceOf(address account) external view returns (uint256)
+    ]"#
+);
+
+type Client = SignerMiddleware<Provider<Http>, LocalWallet>;
+
+fn u(value: u64) -> U256 {
+    U256::from(value)
+}
+
+#[test]
+fn first_deposit_mints_one_share_per_asset() {
+    assert_eq!(assets_to_shares(u(1_000), U256::ZERO, U256::ZERO, Rounding::Down).ok(), Some(u(1_000)));
+    assert_eq!(shares_to_assets(u(1_000), U256::ZERO, U256::ZERO, Rounding::Down).ok(), Some(u(1_000)));
+}
+
+#[test]
+fn conversions_round_in_favor_of_the_vault() {
+    // 100 shares backed by 200 assets (plus the virtual share and asset): 201 / 101 per share
+    assert_eq!(assets_to_shares(u(10), u(100), u(200), Rounding::Down).ok(), Some(u(5)));
+    assert_eq!(assets_to_shares(u(10), u(100), u(200), Rounding::Up).ok(), Some(u(6)));
+    assert_eq!(shares_to_assets(u(5), u(100), u(200), Rounding::Down).ok(), Some(u(9)));
+    assert_eq!(shares_to_assets(u(5), u(100), u(200), Rounding::Up).ok(), Some(u(10)));
+}
+
+#[test]
+fn donations_do_not_zero_the_next_deposit() {
+    // a first depositor holding 1 share donates 1e18 assets: a deposit of 1e18 still gets shares
+    let donated = U256::from(1_000_000_000_000_000_000u64);
+    let shares = assets_to_shares(donated, u(1), donated + u(1), Rounding::Down).ok();
+    assert_eq!(shares, Some(u(1)));
+}
+
+#[test]
+fn conversion_overflow_is_an_error() {
+    assert!(assets_to_shares(U256::MAX, u(2), u(0), Rounding::Down).is_err());
+}
+
+#[test]
+fn fee_on_raw_is_added_on_top() {
+    assert_eq!(fee_on_raw(u(10_000), 50), u(50));
+    // rounded up
+    assert_eq!(fee_on_raw(u(1), 50), u(1));
+    assert_eq!(fee_on_raw(u(10_000), 0), U256::ZERO);
+}
+
+#[test]
+fn fee_on_total_is_the_fee_part_of_an_amount() {
+    // 10_050 assets = 10_000 converted + 50 fee at 0.5%
+    assert_eq!(fee_on_total(u(10_050), 50), u(50));
+    assert_eq!(fee_on_total(u(10_100), 100), u(100));
+    assert_eq!(fee_on_total(u(10_000), 0), U256::ZERO);
+    for assets in [1u64, 99, 12_345, 1_000_000_007] {
+        let fee = fee_on_total(u(assets), 50);
+        assert!(fee_on_raw(u(assets) - fee, 50) <= fee);
+    }
+}
+
+#[tokio::test]
+#[ignore = "needs a fresh examples/compose_erc4626.rs in STYLUS_PROGRAM_ADDRESS_ERC4626 and examples/reentrant_asset.rs in STYLUS_PROGRAM_ADDRESS_REENTRANT_ASSET"]
+async fn asset_reading_the_share_price_mid_transfer_reverts() -> eyre::Result<()> {
+    let devnet = fixture_init::devnet().await?;
+    let vault_address = devnet.program("erc4626").await?;
+    let asset_address = devnet.program("reentrant_asset").await?;
+    let vault: FeeVault<Client> = FeeVault::new(vault_address, devnet.alice_client.clone());
+    let asset: ReentrantAsset<Client> = ReentrantAsset::new(asset_address, devnet.alice_client.clone());
+    let alice = devnet.alice_client.address();
+
+    vault.init(asset_address, alice).send().await?.await?;
+    asset.mint(alice, EthersU256::from(10_000)).send().await?.await?;
+    asset.approve(vault_address, EthersU256::MAX).send().await?.await?;
+
+    // the asset reads convertToAssets after pulling the assets, before the shares are minted
+    asset.set_callback(true).send().await?.await?;
+    assert!(vault.deposit(EthersU256::from(1_000), alice).send().await.is_err());
+    assert_eq!(vault.balance_of(alice).call().await?, EthersU256::zero());
+    assert_eq!(asset.balance_of(vault_address).call().await?, EthersU256::zero());
+
+    // the same deposit goes through without the callback
+    asset.set_callback(false).send().await?.await?;
+    vault.deposit(EthersU256::from(1_000), alice).send().await?.await?;
+    let shares = vault.balance_of(alice).call().await?;
+    assert!(shares > EthersU256::zero());
+
+    // and a redemption reading the price after the shares are burned, before the assets are paid
+    asset.set_callback(true).send().await?.await?;
+    assert!(vault.redeem(shares, alice, alice).send().await.is_err());
+    assert_eq!(vault.balance_of(alice).call().await?, shares);
+    assert!(vault.convert_to_assets(shares).call().await? > EthersU256::zero());
+    Ok(())
+}
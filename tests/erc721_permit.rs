@@ -0,0 +1,31 @@
+//! ERC-4494 permits of `Erc721Permit` must hash like the ERC reference, so permits signed by
+//! marketplaces verify on Stylus collections.
+use oz_stylus_erc::extensions::erc721_permit::{permit_hash, PERMIT_TYPE};
+use oz_stylus_erc::utils::eip712::{address_word, hash_words, u256_word};
+use stylus_sdk::{
+    alloy_primitives::{b256, Address, U256},
+    crypto::keccak,
+};
+
+#[test]
+fn permit_type_hash_matches_erc4494() {
+    assert_eq!(
+        keccak(PERMIT_TYPE.as_bytes()),
+        b256!("49ecf333e5b8c95c40fdafc95c1ad136e8914a8fb55e9dc8bb01eaa83a2df9ad")
+    );
+}
+
+#[test]
+fn permit_hash_encodes_spender_token_nonce_and_deadline() {
+    let spender = Address::repeat_byte(0x5e);
+    let (token_id, nonce, deadline) = (U256::from(42), U256::from(1), U256::from(1_700_000_000u64));
+
+    let expected = hash_words(&[
+        keccak(PERMIT_TYPE.as_bytes()),
+        address_word(spender),
+        u256_word(token_id),
+        u256_word(nonce),
+        u256_word(deadline),
+    ]);
+    assert_eq!(permit_hash(spender, token_id, nonce, deadline), expected);
+}
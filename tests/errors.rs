@@ -0,0 +1,72 @@
+//! Selectors and formatting added to the error enums by `errors::error_helpers!`.
+use oz_stylus_erc::access::ownable::{OwnableError, OwnableUnauthorizedAccount};
+use oz_stylus_erc::errors::{data_selector, selector_hex, selectors, OzError};
+use oz_stylus_erc::tokens::erc20::{Erc20Error, Erc20InsufficientBalance, Erc20InvalidSpender};
+use oz_stylus_erc::utils::create2::Create2Error;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    crypto::keccak,
+};
+
+#[test]
+fn selector_constants_match_the_error_signatures() {
+    for (selector, signature) in [
+        (selectors::ERC20_INSUFFICIENT_BALANCE, "Erc20InsufficientBalance(address,uint256,uint256)"),
+        (selectors::ERC20_INSUFFICIENT_ALLOWANCE, "Erc20InsufficientAllowance(address,uint256,uint256)"),
+        (selectors::ERC20_INVALID_SPENDER, "Erc20InvalidSpender(address)"),
+        (selectors::ERC20_ARITHMETIC_OVERFLOW, "Erc20ArithmeticOverflow(uint256,uint256)"),
+        (selectors::ERC721_NONEXISTENT_TOKEN, "Erc721NonexistentToken(uint256)"),
+        (selectors::OWNABLE_UNAUTHORIZED_ACCOUNT, "OwnableUnauthorizedAccount(address)"),
+    ] {
+        assert_eq!(selector, keccak(signature)[..4], "{}", signature);
+    }
+    // values the integration tests used to hardcode
+    assert_eq!(selector_hex(selectors::ERC20_INSUFFICIENT_ALLOWANCE), "0xa7718e26");
+    assert_eq!(selector_hex(selectors::ERC20_INVALID_SPENDER), "0xf886f534");
+}
+
+#[test]
+fn error_enums_report_selector_name_and_revert_data() {
+    let error = Erc20Error::Erc20InsufficientBalance(Erc20InsufficientBalance {
+        sender: Address::repeat_byte(0x11),
+        balance: U256::from(1),
+        needed: U256::from(2),
+    });
+    assert_eq!(error.selector(), selectors::ERC20_INSUFFICIENT_BALANCE);
+    assert_eq!(error.name(), "Erc20InsufficientBalance");
+    assert_eq!(error.to_string(), "Erc20InsufficientBalance (0x59eca5e6)");
+
+    let debug = format!("{:?}", error);
+    assert!(debug.starts_with("Erc20InsufficientBalance(0x59eca5e6"), "{}", debug);
+    // selector and three words
+    assert_eq!(debug.len(), "Erc20InsufficientBalance(0x)".len() + 2 * (4 + 3 * 32));
+
+    let revert_data: Vec<u8> = error.into();
+    assert_eq!(revert_data[..4], selectors::ERC20_INSUFFICIENT_BALANCE);
+}
+
+#[test]
+fn passed_through_reverts_report_the_selector_of_their_data() {
+    let reason = Vec::from(Erc20Error::Erc20InvalidSpender(Erc20InvalidSpender { spender: Address::ZERO }));
+    let error = Erc20Error::TransferHookRejected(reason);
+    assert_eq!(error.selector(), selectors::ERC20_INVALID_SPENDER);
+    assert_eq!(error.name(), "TransferHookRejected");
+
+    assert_eq!(Create2Error::Reverted(Vec::new()).selector(), [0; 4]);
+    assert_eq!(data_selector(&[0xab, 0xcd]), [0xab, 0xcd, 0, 0]);
+}
+
+#[test]
+fn oz_error_reverts_with_the_wrapped_error() {
+    let account = Address::repeat_byte(0x22);
+    let ownable = || OwnableError::OwnableUnauthorizedAccount(OwnableUnauthorizedAccount { account });
+
+    let error = OzError::from(ownable());
+    assert_eq!(error.selector(), selectors::OWNABLE_UNAUTHORIZED_ACCOUNT);
+    assert_eq!(error.name(), "OwnableUnauthorizedAccount");
+    assert_eq!(Vec::<u8>::from(error), Vec::<u8>::from(ownable()));
+
+    let reason = OzError::from("already initialized");
+    assert_eq!(reason.name(), "Revert");
+    assert_eq!(Vec::<u8>::from(reason), b"already initialized".to_vec());
+}
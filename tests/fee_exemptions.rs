@@ -0,0 +1,82 @@
+//! Exemptions of `extensions::fee_exemptions` managed through `FEE_EXEMPTION_MANAGER_ROLE` of the
+//! token's `AccessControl`, on the deployed `examples/compose_fee.rs`.
+//!
+//! The devnet test needs a fresh fee token, deploy it without calling `init` and set
+//! `STYLUS_PROGRAM_ADDRESS_FEE_EXEMPTIONS`, then `cargo test --test fee_exemptions -- --ignored`.
+use ethers::{
+    middleware::SignerMiddleware,
+    prelude::abigen,
+    providers::{Http, Provider},
+    signers::{LocalWallet, Signer},
+    types::{Address, U256},
+};
+use oz_stylus_erc::access::access_control::role_id;
+use oz_stylus_erc::extensions::erc20_fee::MAX_FEE_BPS;
+use oz_stylus_erc::extensions::fee_exemptions::FEE_EXEMPTION_MANAGER_ROLE;
+use stylus_sdk::crypto::keccak;
+
+mod util;
+use util::fixture_init;
+
+abigen!(
+    FeeToken,
+    r#"[
+        function init() external
+        function mint(address account, uint256 value) external
+        function transfer(address to, uint256 value) external returns (bool)
+        function setFee(uint256 fee_bps) external
+        function setFeeCollector(address collector) external
+        function balanceOf(address account) external view returns (uint256)
+        function grantRole(bytes32 role, address account) external
+        function hasRole(bytes32 role, address account) external view returns (bool)
+        function isFeeExempt(address account) external view returns (bool)
+        function addFeeExemption(address account) external
+        function removeFeeExemption(address account) external
+    ]"#
+);
+
+type FeeTokenType = FeeToken<SignerMiddleware<Provider<Http>, LocalWallet>>;
+
+#[test]
+fn manager_role_is_the_hash_of_its_name() {
+    assert_eq!(role_id(FEE_EXEMPTION_MANAGER_ROLE), keccak(b"FEE_EXEMPTION_MANAGER_ROLE"));
+}
+
+#[tokio::test]
+#[ignore = "needs a fresh examples/compose_fee.rs in STYLUS_PROGRAM_ADDRESS_FEE_EXEMPTIONS"]
+async fn exemptions_are_managed_by_the_manager_role() -> eyre::Result<()> {
+    let devnet = fixture_init::devnet().await?;
+    let address = devnet.program("fee_exemptions").await?;
+    let as_alice: FeeTokenType = FeeToken::new(address, devnet.alice_client.clone());
+    let as_bob: FeeTokenType = FeeToken::new(address, devnet.bob_client.clone());
+    let alice = devnet.alice_client.address();
+    let bob = devnet.bob_client.address();
+    let collector = Address::repeat_byte(0xfe);
+    let manager_role = role_id(FEE_EXEMPTION_MANAGER_ROLE).0;
+
+    // alice is admin and manager, 10% fee
+    as_alice.init().send().await?.await?;
+    assert!(as_alice.has_role(manager_role, alice).call().await?);
+    as_alice.set_fee(U256::from(MAX_FEE_BPS)).send().await?.await?;
+    as_alice.set_fee_collector(collector).send().await?.await?;
+    as_alice.mint(alice, U256::from(1_000)).send().await?.await?;
+
+    // without the role bob can not exempt himself
+    assert!(as_bob.add_fee_exemption(bob).send().await.is_err());
+    assert!(!as_alice.is_fee_exempt(bob).call().await?);
+
+    // transfers to an exempt account pay no fee
+    as_alice.add_fee_exemption(bob).send().await?.await?;
+    assert!(as_alice.is_fee_exempt(bob).call().await?);
+    as_alice.transfer(bob, U256::from(100)).send().await?.await?;
+    assert_eq!(as_alice.balance_of(bob).call().await?, U256::from(100));
+    assert_eq!(as_alice.balance_of(collector).call().await?, U256::zero());
+
+    // once granted the role, bob manages the list
+    as_alice.grant_role(manager_role, bob).send().await?.await?;
+    as_bob.remove_fee_exemption(bob).send().await?.await?;
+    assert!(!as_alice.is_fee_exempt(bob).call().await?);
+    as_alice.transfer(bob, U256::from(100)).send().await?.await?;
+    assert_eq!(as_alice.balance_of(collector).call().await?, U256::from(10));
+    Ok(())
+}
@@ -0,0 +1,26 @@
+//! Bit packing of `utils::flags`.
+use oz_stylus_erc::utils::flags::{is_set, with_flag, FLAG_COUNT};
+
+#[test]
+fn flags_are_set_and_cleared_independently() {
+    let bits = with_flag(with_flag(0, 0, true), 3, true);
+
+    assert_eq!(bits, 0b1001);
+    assert!(is_set(bits, 0) && is_set(bits, 3));
+    assert!(!is_set(bits, 1));
+    assert_eq!(with_flag(bits, 0, false), 0b1000);
+    assert_eq!(with_flag(bits, 3, true), bits);
+}
+
+#[test]
+fn paused_flag_has_the_layout_of_a_bool() {
+    // a `bool paused` stored as true is the byte 0x01
+    assert!(is_set(0x01, 0));
+    assert_eq!(with_flag(0, 0, true), 0x01);
+}
+
+#[test]
+fn flags_out_of_range_are_ignored() {
+    assert_eq!(with_flag(0, FLAG_COUNT, true), 0);
+    assert!(!is_set(0xff, FLAG_COUNT));
+}
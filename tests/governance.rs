@@ -0,0 +1,193 @@
+//! Proposal ids, operation ids and timelock calldata are built by hand in
+//! `governance::operations`, they must match Solidity `abi.encode` or proposals created by
+//! governance tooling get other ids.
+use ethers::abi::{self, Token};
+use oz_stylus_erc::governance::governor::{
+    add_votes, decode_clock_mode, hash_proposal, proposal_state, quorum_of, GovernorError, ProposalState,
+    ProposalStatus, VOTE_FOR,
+};
+use oz_stylus_erc::governance::operations::{encode_batch, encode_call, hash_batch};
+use oz_stylus_erc::governance::timelock::{
+    hash_operation, hash_operation_batch, operation_state, OperationState, DONE_TIMESTAMP,
+};
+use oz_stylus_erc::utils::checkpoints::ClockMode;
+use stylus_sdk::{
+    abi::Bytes,
+    alloy_primitives::{Address, B256, U256},
+    crypto::keccak,
+};
+
+fn batch() -> (Vec<Address>, Vec<U256>, Vec<Bytes>) {
+    (
+        vec![Address::repeat_byte(0x11), Address::repeat_byte(0x22), Address::repeat_byte(0x33)],
+        vec![U256::ZERO, U256::from(7), U256::from(1_000_000u64)],
+        vec![Bytes::from(vec![0xab; 4]), Bytes::from(Vec::new()), Bytes::from(vec![0xcd; 68])],
+    )
+}
+
+fn ethers_encode(targets: &[Address], values: &[U256], payloads: &[Bytes], words: &[B256]) -> Vec<u8> {
+    let mut tokens = vec![
+        Token::Array(targets.iter().map(|t| Token::Address(t.0 .0.into())).collect()),
+        Token::Array(values.iter().map(|v| Token::Uint(v.to_be_bytes::<32>().into())).collect()),
+        Token::Array(payloads.iter().map(|p| Token::Bytes(p.to_vec())).collect()),
+    ];
+    tokens.extend(words.iter().map(|w| Token::FixedBytes(w.to_vec())));
+    abi::encode(&tokens)
+}
+
+#[test]
+fn encode_batch_matches_abi_encode() {
+    let (targets, values, payloads) = batch();
+    let words = [B256::repeat_byte(0x01), B256::ZERO, B256::repeat_byte(0xff)];
+
+    assert_eq!(
+        encode_batch(&targets, &values, &payloads, &words),
+        ethers_encode(&targets, &values, &payloads, &words)
+    );
+    assert_eq!(encode_batch(&[], &[], &[], &[]), ethers_encode(&[], &[], &[], &[]));
+}
+
+#[test]
+fn proposal_id_is_the_hash_of_the_encoded_proposal() {
+    let (targets, values, payloads) = batch();
+    let description_hash = keccak(b"mint 100 GOV to bob");
+
+    let expected = keccak(ethers_encode(&targets, &values, &payloads, &[description_hash]));
+    assert_eq!(hash_batch(&targets, &values, &payloads, &[description_hash]), expected);
+    assert_eq!(
+        hash_proposal(&targets, &values, &payloads, description_hash),
+        U256::from_be_bytes(expected.0)
+    );
+}
+
+#[test]
+fn operation_ids_match_abi_encode() {
+    let (targets, values, payloads) = batch();
+    let (predecessor, salt) = (B256::repeat_byte(0x0a), B256::repeat_byte(0x5a));
+
+    let single = abi::encode(&[
+        Token::Address(targets[2].0 .0.into()),
+        Token::Uint(values[2].to_be_bytes::<32>().into()),
+        Token::Bytes(payloads[2].to_vec()),
+        Token::FixedBytes(predecessor.to_vec()),
+        Token::FixedBytes(salt.to_vec()),
+    ]);
+    assert_eq!(encode_call(targets[2], values[2], &payloads[2], &[predecessor, salt]), single);
+    assert_eq!(
+        hash_operation(targets[2], values[2], &payloads[2], predecessor, salt),
+        keccak(single)
+    );
+    assert_eq!(
+        hash_operation_batch(&targets, &values, &payloads, predecessor, salt),
+        keccak(ethers_encode(&targets, &values, &payloads, &[predecessor, salt]))
+    );
+}
+
+#[test]
+fn operation_is_ready_from_its_timestamp_until_executed() {
+    let timestamp = U256::from(1_700_000_000u64);
+
+    assert_eq!(operation_state(U256::ZERO, 1_700_000_000), OperationState::Unset);
+    assert_eq!(operation_state(timestamp, 1_699_999_999), OperationState::Waiting);
+    assert_eq!(operation_state(timestamp, 1_700_000_000), OperationState::Ready);
+    assert_eq!(operation_state(U256::from(DONE_TIMESTAMP), 1_700_000_000), OperationState::Done);
+}
+
+fn status(vote_start: u64, vote_end: u64) -> ProposalStatus {
+    ProposalStatus {
+        vote_start: U256::from(vote_start),
+        vote_end: U256::from(vote_end),
+        executed: false,
+        canceled: false,
+        eta_seconds: U256::ZERO,
+        quorum_reached: true,
+        vote_succeeded: true,
+    }
+}
+
+#[test]
+fn vote_is_active_after_the_snapshot_until_the_deadline() {
+    let proposal = status(10, 20);
+
+    assert_eq!(proposal_state(&proposal, 10), ProposalState::Pending);
+    assert_eq!(proposal_state(&proposal, 11), ProposalState::Active);
+    assert_eq!(proposal_state(&proposal, 20), ProposalState::Active);
+    assert_eq!(proposal_state(&proposal, 21), ProposalState::Succeeded);
+}
+
+#[test]
+fn ended_vote_needs_quorum_and_more_for_than_against() {
+    let no_quorum = ProposalStatus {
+        quorum_reached: false,
+        ..status(10, 20)
+    };
+    let rejected = ProposalStatus {
+        vote_succeeded: false,
+        ..status(10, 20)
+    };
+
+    assert_eq!(proposal_state(&no_quorum, 21), ProposalState::Defeated);
+    assert_eq!(proposal_state(&rejected, 21), ProposalState::Defeated);
+}
+
+#[test]
+fn queued_executed_and_canceled_override_the_vote() {
+    let queued = ProposalStatus {
+        eta_seconds: U256::from(1_700_000_000u64),
+        ..status(10, 20)
+    };
+    let executed = ProposalStatus {
+        executed: true,
+        ..queued
+    };
+    let canceled = ProposalStatus {
+        canceled: true,
+        ..status(10, 20)
+    };
+
+    assert_eq!(proposal_state(&queued, 21), ProposalState::Queued);
+    assert_eq!(proposal_state(&executed, 21), ProposalState::Executed);
+    assert_eq!(proposal_state(&canceled, 5), ProposalState::Canceled);
+}
+
+#[test]
+fn unexpected_state_bitmap_sets_one_bit_per_state() {
+    let bitmap = ProposalState::bitmap(&[ProposalState::Pending, ProposalState::Queued]);
+
+    assert_eq!(U256::from_be_bytes(bitmap.0), U256::from(0b10_0001));
+}
+
+#[test]
+fn governor_reads_the_clock_mode_of_the_token() {
+    let encoded = |mode: &str| abi::encode(&[Token::String(mode.into())]);
+
+    assert_eq!(
+        decode_clock_mode(&encoded("mode=blocknumber&from=default")),
+        Some(ClockMode::BlockNumber)
+    );
+    assert_eq!(decode_clock_mode(&encoded("mode=timestamp")), Some(ClockMode::Timestamp));
+    // another chain's block numbers are not this clock
+    assert_eq!(decode_clock_mode(&encoded("mode=blocknumber&from=eip155:1")), None);
+    assert_eq!(decode_clock_mode(&encoded("mode=timestamp")[..70]), None);
+    assert_eq!(decode_clock_mode(&[]), None);
+}
+
+#[test]
+fn quorum_is_a_percentage_of_the_supply_rounded_down() {
+    assert_eq!(quorum_of(U256::from(1_000), U256::from(4)).ok(), Some(U256::from(40)));
+    assert_eq!(quorum_of(U256::from(99), U256::from(4)).ok(), Some(U256::from(3)));
+    // supply * numerator exceeds 256 bits, the quorum does not
+    assert_eq!(quorum_of(U256::MAX, U256::from(100)).ok(), Some(U256::MAX));
+    assert_eq!(quorum_of(U256::MAX, U256::from(50)).ok(), Some(U256::MAX / U256::from(2)));
+}
+
+#[test]
+fn vote_tallies_reject_overflows() {
+    let proposal_id = U256::from(7);
+
+    assert_eq!(add_votes(proposal_id, VOTE_FOR, U256::from(10), U256::from(5)).ok(), Some(U256::from(15)));
+    assert!(matches!(
+        add_votes(proposal_id, VOTE_FOR, U256::MAX, U256::from(1)),
+        Err(GovernorError::GovernorVoteOverflow(e)) if e.proposal_id == proposal_id && e.support == VOTE_FOR
+    ));
+}
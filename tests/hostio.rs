@@ -0,0 +1,55 @@
+//! Counting of `utils::hostio`: the wrappers count with the `debug` feature only, release builds
+//! run the closure alone. `cargo test --test hostio --features debug` checks the counting side.
+use oz_stylus_erc::utils::hostio::{self, HostioCounters, TRANSFER_HOOK_BUDGET};
+
+fn spent(f: impl FnOnce()) -> HostioCounters {
+    let before = hostio::counters();
+    f();
+    hostio::counters().since(before)
+}
+
+#[test]
+fn wrappers_return_the_closure_result() {
+    assert_eq!(hostio::read(|| 7), 7);
+    assert_eq!(hostio::write(|| "written"), "written");
+    assert_eq!(hostio::log(|| Some(1)), Some(1));
+    assert_eq!(hostio::call(|| Ok::<_, ()>(2)), Ok(2));
+}
+
+#[cfg(not(feature = "debug"))]
+#[test]
+fn nothing_is_counted_without_debug() {
+    let spent = spent(|| {
+        hostio::read(|| ());
+        hostio::write(|| ());
+        hostio::log(|| ());
+        hostio::call(|| ());
+    });
+    assert_eq!(spent, HostioCounters::default());
+    assert_eq!(hostio::counters(), HostioCounters::default());
+}
+
+#[cfg(feature = "debug")]
+#[test]
+fn every_wrapped_access_is_counted_with_debug() {
+    let spent = spent(|| {
+        hostio::read(|| ());
+        hostio::read(|| ());
+        hostio::write(|| ());
+        hostio::log(|| ());
+    });
+    // tests of this binary run in parallel, other counted accesses may add up
+    assert!(spent.storage_reads >= 2 && spent.storage_writes >= 1 && spent.logs >= 1);
+}
+
+#[test]
+fn budget_is_exceeded_by_any_counter() {
+    let within = HostioCounters {
+        storage_reads: 2,
+        storage_writes: 1,
+        ..Default::default()
+    };
+    assert!(!within.exceeds(TRANSFER_HOOK_BUDGET));
+    assert!(HostioCounters { logs: 1, ..within }.exceeds(TRANSFER_HOOK_BUDGET));
+    assert!(HostioCounters { storage_writes: 2, ..within }.exceeds(TRANSFER_HOOK_BUDGET));
+}
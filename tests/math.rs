@@ -0,0 +1,74 @@
+//! `utils::math`: checked operations, rounding of `mul_div`/`ceil_div`/`sqrt` and the full
+//! precision of `mul_div` when only the product exceeds 256 bits.
+use oz_stylus_erc::utils::math::{
+    average, ceil_div, checked_add, checked_div, checked_mul, checked_sub, mul_div, sqrt, MathError, Rounding,
+};
+use stylus_sdk::alloy_primitives::U256;
+
+fn u(value: u64) -> U256 {
+    U256::from(value)
+}
+
+fn overflowed(result: Result<U256, MathError>) -> bool {
+    matches!(result, Err(MathError::MathOverflow(_)))
+}
+
+#[test]
+fn checked_operations_report_their_error() {
+    assert_eq!(checked_add(u(2), u(3)).ok(), Some(u(5)));
+    assert!(overflowed(checked_add(U256::MAX, u(1))));
+    assert_eq!(checked_sub(u(3), u(2)).ok(), Some(u(1)));
+    assert!(matches!(checked_sub(u(2), u(3)), Err(MathError::MathUnderflow(_))));
+    assert_eq!(checked_mul(u(6), u(7)).ok(), Some(u(42)));
+    assert!(overflowed(checked_mul(U256::MAX, u(2))));
+    assert_eq!(checked_div(u(7), u(2)).ok(), Some(u(3)));
+    assert!(matches!(checked_div(u(7), U256::ZERO), Err(MathError::MathDivisionByZero(_))));
+}
+
+#[test]
+fn ceil_div_rounds_up() {
+    assert_eq!(ceil_div(u(7), u(2)).ok(), Some(u(4)));
+    assert_eq!(ceil_div(u(8), u(2)).ok(), Some(u(4)));
+    assert_eq!(ceil_div(U256::ZERO, u(3)).ok(), Some(U256::ZERO));
+    assert_eq!(ceil_div(U256::MAX, u(1)).ok(), Some(U256::MAX));
+    assert!(matches!(ceil_div(u(1), U256::ZERO), Err(MathError::MathDivisionByZero(_))));
+}
+
+#[test]
+fn average_does_not_overflow() {
+    assert_eq!(average(u(3), u(6)), u(4));
+    assert_eq!(average(U256::MAX, U256::MAX), U256::MAX);
+    assert_eq!(average(U256::MAX, U256::MAX - u(1)), U256::MAX - u(1));
+}
+
+#[test]
+fn mul_div_keeps_the_full_product() {
+    // MAX * MAX / MAX overflows 256 bits in the product only
+    assert_eq!(mul_div(U256::MAX, U256::MAX, U256::MAX, Rounding::Down).ok(), Some(U256::MAX));
+    let half = U256::MAX >> 1;
+    assert_eq!(mul_div(half, u(4), u(8), Rounding::Down).ok(), Some(half >> 1));
+    assert!(overflowed(mul_div(U256::MAX, u(2), u(1), Rounding::Down)));
+    assert!(matches!(mul_div(u(1), u(1), U256::ZERO, Rounding::Down), Err(MathError::MathDivisionByZero(_))));
+}
+
+#[test]
+fn mul_div_rounds_in_the_requested_direction() {
+    assert_eq!(mul_div(u(10), u(3), u(4), Rounding::Down).ok(), Some(u(7)));
+    assert_eq!(mul_div(u(10), u(3), u(4), Rounding::Up).ok(), Some(u(8)));
+    assert_eq!(mul_div(u(10), u(4), u(4), Rounding::Up).ok(), Some(u(10)));
+    // rounding up past the largest value
+    assert!(overflowed(mul_div(U256::MAX, u(3), u(2), Rounding::Up)));
+    assert!(overflowed(mul_div(U256::MAX, U256::MAX, U256::MAX - u(1), Rounding::Up)));
+}
+
+#[test]
+fn sqrt_rounds_in_the_requested_direction() {
+    for (a, down, up) in [(0u64, 0, 0), (1, 1, 1), (2, 1, 2), (4, 2, 2), (15, 3, 4), (16, 4, 4), (17, 4, 5)] {
+        assert_eq!(sqrt(u(a), Rounding::Down), u(down));
+        assert_eq!(sqrt(u(a), Rounding::Up), u(up));
+    }
+    let root_of_max = (U256::from(1) << 128) - u(1);
+    assert_eq!(sqrt(U256::MAX, Rounding::Down), root_of_max);
+    assert_eq!(sqrt(U256::MAX, Rounding::Up), U256::from(1) << 128);
+    assert_eq!(sqrt(root_of_max * root_of_max, Rounding::Up), root_of_max);
+}
@@ -0,0 +1,93 @@
+//! `utils::merkle_proof` against the trees built off-chain by `tests/util/balances_root.rs`, the
+//! same sorted pair hashing as OpenZeppelin `StandardMerkleTree`.
+use ethers::types::{Address, H256, U256};
+use oz_stylus_erc::utils::merkle_proof::{hash_pair, multi_proof_verify, process_multi_proof, verify};
+use stylus_sdk::alloy_primitives::B256;
+
+mod util;
+use util::balances_root::{balance_leaf, merkle_proof, merkle_root};
+
+fn b256(hash: H256) -> B256 {
+    B256::from(hash.0)
+}
+
+fn sample_balances(count: u64) -> Vec<(Address, U256)> {
+    (1..=count)
+        .map(|i| (Address::from_low_u64_be(i), U256::from(i * 1_000)))
+        .collect()
+}
+
+#[test]
+fn proofs_of_the_off_chain_tree_verify() {
+    for count in 1..=9 {
+        let balances = sample_balances(count);
+        let root = b256(merkle_root(&balances));
+        for (account, balance) in &balances {
+            let proof: Vec<B256> = merkle_proof(&balances, *account, *balance).unwrap().into_iter().map(b256).collect();
+            let leaf = b256(balance_leaf(*account, *balance));
+            assert!(verify(&proof, root, leaf));
+            assert!(!verify(&proof, root, b256(balance_leaf(*account, *balance + U256::one()))));
+        }
+    }
+}
+
+#[test]
+fn balances_root_leaves_match_the_off_chain_leaves() {
+    use oz_stylus_erc::extensions::erc20_balances_root;
+    use stylus_sdk::alloy_primitives::{Address as AlloyAddress, U256 as AlloyU256};
+    let account = Address::from_low_u64_be(42);
+    assert_eq!(
+        erc20_balances_root::balance_leaf(AlloyAddress::from(account.0), AlloyU256::from(1_000u64)),
+        b256(balance_leaf(account, U256::from(1_000u64)))
+    );
+}
+
+#[test]
+fn pair_hashing_is_commutative() {
+    let (a, b) = (B256::repeat_byte(0x01), B256::repeat_byte(0xfe));
+    assert_eq!(hash_pair(a, b), hash_pair(b, a));
+    assert_ne!(hash_pair(a, b), hash_pair(a, a));
+}
+
+/// Sorted leaves of a 4 leaves tree and its root.
+fn four_leaves() -> ([B256; 4], B256) {
+    let balances = sample_balances(4);
+    let mut leaves: Vec<B256> = balances.iter().map(|(a, b)| b256(balance_leaf(*a, *b))).collect();
+    leaves.sort();
+    (leaves.clone().try_into().unwrap(), b256(merkle_root(&balances)))
+}
+
+#[test]
+fn multiproof_of_siblings() {
+    let ([l0, l1, l2, l3], root) = four_leaves();
+    let proof = [hash_pair(l2, l3)];
+    assert!(matches!(multi_proof_verify(&proof, &[true, false], root, &[l0, l1]), Ok(true)));
+}
+
+#[test]
+fn multiproof_of_leaves_in_different_subtrees() {
+    let ([l0, l1, l2, l3], root) = four_leaves();
+    assert!(matches!(multi_proof_verify(&[l1, l3], &[false, false, true], root, &[l0, l2]), Ok(true)));
+    assert!(matches!(multi_proof_verify(&[l1, l3], &[false, false, true], root, &[l0, l1]), Ok(false)));
+}
+
+#[test]
+fn multiproof_of_a_single_leaf_is_a_proof() {
+    let ([l0, l1, l2, l3], root) = four_leaves();
+    let proof = [l1, hash_pair(l2, l3)];
+    assert!(matches!(multi_proof_verify(&proof, &[false, false], root, &[l0]), Ok(true)));
+    assert!(verify(&proof, root, l0));
+}
+
+#[test]
+fn malformed_multiproofs_are_rejected() {
+    let ([l0, l1, l2, l3], _) = four_leaves();
+    // lengths do not add up
+    assert!(process_multi_proof(&[l1], &[false, false], &[l0]).is_err());
+    // the second flag consumes a hash before it is computed
+    assert!(process_multi_proof(&[l2, l3], &[true, true, true], &[l0, l1]).is_err());
+    // every leaf given, no proof needed
+    assert!(process_multi_proof(&[], &[true, true, true], &[l0, l1, l2, l3]).is_ok());
+    // leaves only: the leaf is the root
+    assert_eq!(process_multi_proof(&[], &[], &[l0]).ok(), Some(l0));
+}
@@ -0,0 +1,20 @@
+//! Payload splitting of `utils::multicall`.
+use oz_stylus_erc::utils::multicall::split_selector;
+
+#[test]
+fn payload_is_split_after_the_selector() {
+    // transfer(address,uint256)
+    let mut payload = vec![0xa9, 0x05, 0x9c, 0xbb];
+    payload.extend_from_slice(&[0x11; 64]);
+
+    let (selector, input) = split_selector(&payload).unwrap();
+    assert_eq!(selector, [0xa9, 0x05, 0x9c, 0xbb]);
+    assert_eq!(input, &payload[4..]);
+    assert_eq!(split_selector(&payload[..4]).unwrap().1, &[] as &[u8]);
+}
+
+#[test]
+fn payload_shorter_than_a_selector_is_rejected() {
+    assert!(split_selector(&[]).is_none());
+    assert!(split_selector(&[0xa9, 0x05, 0x9c]).is_none());
+}
@@ -0,0 +1,151 @@
+//! Action hashes of `access::multisig_admin`, and confirmations of the deployed
+//! `examples/compose_multisig_admin.rs` following the current owner weights.
+//!
+//! The devnet test needs a fresh multisig token, deploy it without calling `init` and set
+//! `STYLUS_PROGRAM_ADDRESS_MULTISIG`, then `cargo test --test multisig_admin -- --ignored`.
+use ethers::{
+    abi::{self, Token},
+    middleware::SignerMiddleware,
+    prelude::abigen,
+    providers::{Http, Provider},
+    signers::{LocalWallet, Signer},
+    types::{Address, U256},
+};
+use oz_stylus_erc::access::multisig_admin::{action_hash, updated_total_weight, MultisigAdminError};
+use oz_stylus_erc::utils::eip712::{address_word, u256_word};
+use stylus_sdk::{alloy_primitives, crypto::keccak};
+
+mod util;
+use util::fixture_init;
+
+abigen!(
+    MultisigToken,
+    r#"[
+        function init(address[] owners, uint256[] weights, uint256 threshold) external
+        function mint(address account, uint256 value) external
+        function balanceOf(address account) external view returns (uint256)
+        function ownerWeight(address owner) external view returns (uint256)
+        function proposal(uint256 id) external view returns (bytes32, uint256, bool)
+        function isConfirmedBy(uint256 id, address owner) external view returns (bool)
+        function submitAction(bytes32 action) external returns (uint256)
+        function confirm(uint256 id) external
+        function revokeConfirmation(uint256 id) external
+        function setOwnerWeight(address owner, uint256 weight) external
+        function changeThreshold(uint256 threshold) external
+    ]"#
+);
+
+type MultisigTokenType = MultisigToken<SignerMiddleware<Provider<Http>, LocalWallet>>;
+
+fn to_alloy(address: Address) -> alloy_primitives::Address {
+    alloy_primitives::Address::from(address.0)
+}
+
+fn mint_action(account: Address, value: u64) -> [u8; 32] {
+    action_hash(
+        "mint(address,uint256)",
+        &[address_word(to_alloy(account)), u256_word(alloy_primitives::U256::from(value))],
+    )
+    .0
+}
+
+fn set_owner_weight_action(owner: Address, weight: u64) -> [u8; 32] {
+    action_hash(
+        "setOwnerWeight(address,uint256)",
+        &[address_word(to_alloy(owner)), u256_word(alloy_primitives::U256::from(weight))],
+    )
+    .0
+}
+
+#[test]
+fn action_hash_is_the_hash_of_the_calldata() {
+    let account = Address::repeat_byte(0x42);
+    let mut calldata = keccak(b"mint(address,uint256)")[..4].to_vec();
+    calldata.extend(abi::encode(&[Token::Address(account), Token::Uint(U256::from(1_000u64))]));
+
+    assert_eq!(mint_action(account, 1_000), keccak(calldata).0);
+    assert_ne!(mint_action(account, 1_000), mint_action(account, 1_001));
+}
+
+#[test]
+fn total_weight_follows_the_owner_weight() {
+    let owner = alloy_primitives::Address::repeat_byte(0x0a);
+    let weight = alloy_primitives::U256::from;
+
+    assert_eq!(updated_total_weight(weight(10), owner, weight(0), weight(5)).ok(), Some(weight(15)));
+    assert_eq!(updated_total_weight(weight(10), owner, weight(4), weight(1)).ok(), Some(weight(7)));
+    // removing an owner
+    assert_eq!(updated_total_weight(weight(10), owner, weight(4), weight(0)).ok(), Some(weight(6)));
+}
+
+#[test]
+fn total_weight_overflow_is_rejected() {
+    let owner = alloy_primitives::Address::repeat_byte(0x0a);
+    let max = alloy_primitives::U256::MAX;
+
+    assert!(matches!(
+        updated_total_weight(alloy_primitives::U256::from(1), owner, alloy_primitives::U256::ZERO, max),
+        Err(MultisigAdminError::MultisigWeightOverflow(e)) if e.owner == owner && e.weight == max
+    ));
+    // replacing the only weight by the max is fine
+    assert_eq!(
+        updated_total_weight(alloy_primitives::U256::from(1), owner, alloy_primitives::U256::from(1), max).ok(),
+        Some(max)
+    );
+}
+
+#[tokio::test]
+#[ignore = "needs a fresh examples/compose_multisig_admin.rs in STYLUS_PROGRAM_ADDRESS_MULTISIG"]
+async fn confirmations_follow_the_current_owner_weights() -> eyre::Result<()> {
+    let devnet = fixture_init::devnet().await?;
+    let address = devnet.program("multisig").await?;
+    let as_alice: MultisigTokenType = MultisigToken::new(address, devnet.alice_client.clone());
+    let as_bob: MultisigTokenType = MultisigToken::new(address, devnet.bob_client.clone());
+    let alice = devnet.alice_client.address();
+    let bob = devnet.bob_client.address();
+
+    // alice weighs 2, bob 1, threshold 2
+    as_alice
+        .init(vec![alice, bob], vec![U256::from(2), U256::from(1)], U256::from(2))
+        .send()
+        .await?
+        .await?;
+
+    // bob alone is below the threshold
+    let mint = mint_action(bob, 100);
+    as_bob.submit_action(mint).send().await?.await?;
+    let mint_id = U256::from(1);
+    assert_eq!(as_bob.proposal(mint_id).call().await?.1, U256::from(1));
+    assert!(as_bob.mint(bob, U256::from(100)).send().await.is_err());
+
+    // revoking removes bob's weight, confirming again restores it
+    as_bob.revoke_confirmation(mint_id).send().await?.await?;
+    assert_eq!(as_bob.proposal(mint_id).call().await?.1, U256::ZERO);
+    assert!(!as_bob.is_confirmed_by(mint_id, bob).call().await?);
+    assert!(as_bob.revoke_confirmation(mint_id).send().await.is_err());
+    as_bob.confirm(mint_id).send().await?.await?;
+    assert_eq!(as_bob.proposal(mint_id).call().await?.1, U256::from(1));
+
+    // alice removes bob on her own
+    as_alice.submit_action(set_owner_weight_action(bob, 0)).send().await?.await?;
+    as_alice.set_owner_weight(bob, U256::ZERO).send().await?.await?;
+    assert_eq!(as_alice.owner_weight(bob).call().await?, U256::ZERO);
+
+    // bob's confirmation no longer counts, and a removed owner can still revoke it
+    assert_eq!(as_bob.proposal(mint_id).call().await?.1, U256::ZERO);
+    assert!(as_bob.confirm(mint_id).send().await.is_err());
+    as_bob.revoke_confirmation(mint_id).send().await?.await?;
+
+    // the threshold cannot exceed the total weight left
+    let change_threshold = action_hash("changeThreshold(uint256)", &[u256_word(alloy_primitives::U256::from(3))]).0;
+    as_alice.submit_action(change_threshold).send().await?.await?;
+    assert!(as_alice.change_threshold(U256::from(3)).send().await.is_err());
+
+    // alice reaches the threshold, the action runs once
+    as_alice.confirm(mint_id).send().await?.await?;
+    assert_eq!(as_alice.proposal(mint_id).call().await?.1, U256::from(2));
+    as_alice.mint(bob, U256::from(100)).send().await?.await?;
+    assert_eq!(as_alice.balance_of(bob).call().await?, U256::from(100));
+    assert!(as_alice.mint(bob, U256::from(100)).send().await.is_err());
+    Ok(())
+}
@@ -0,0 +1,99 @@
+//! Calldata dispatch of `utils::payable`, and plain ETH sent to the deployed `examples/weth.rs`.
+//!
+//! The devnet tests need the WETH program, deploy it and set `STYLUS_PROGRAM_ADDRESS_WETH`, then
+//! `cargo test --test payable -- --ignored`.
+use ethers::{
+    middleware::SignerMiddleware,
+    prelude::abigen,
+    providers::{Http, Middleware, Provider},
+    signers::{LocalWallet, Signer},
+    types::{Address, TransactionRequest, U256},
+};
+use oz_stylus_erc::utils::payable::{dispatch, Dispatch};
+
+mod util;
+use util::fixture_init::{self, SignerClient};
+
+abigen!(
+    Weth,
+    r#"[
+        function balanceOf(address account) external view returns (uint256)
+        function totalSupply() external view returns (uint256)
+        function deposit() external payable
+        function withdraw(uint256 wad) external
+    ]"#
+);
+
+type WethType = Weth<SignerMiddleware<Provider<Http>, LocalWallet>>;
+
+#[test]
+fn empty_calldata_is_a_plain_transfer() {
+    assert_eq!(dispatch(&[]), Dispatch::Receive);
+}
+
+#[test]
+fn calldata_shorter_than_a_selector_goes_to_the_fallback() {
+    assert_eq!(dispatch(&[0xd0, 0xe3, 0x0d]), Dispatch::Fallback(&[0xd0, 0xe3, 0x0d]));
+}
+
+#[test]
+fn calldata_with_a_selector_goes_to_the_router() {
+    // `deposit()`
+    assert_eq!(dispatch(&[0xd0, 0xe3, 0x0d, 0xb0]), Dispatch::Method(0xd0e30db0, &[]));
+    assert_eq!(dispatch(&[0x2e, 0x1a, 0x7d, 0x4d, 0x01, 0x02]), Dispatch::Method(0x2e1a7d4d, &[0x01, 0x02]));
+}
+
+async fn weth() -> eyre::Result<(WethType, SignerClient, Address)> {
+    let devnet = fixture_init::devnet().await?;
+    let address = devnet.program("weth").await?;
+    Ok((Weth::new(address, devnet.alice_client.clone()), devnet.alice_client.clone(), address))
+}
+
+#[tokio::test]
+#[ignore = "needs the deployed examples/weth.rs in STYLUS_PROGRAM_ADDRESS_WETH"]
+async fn plain_eth_transfer_mints_weth() -> eyre::Result<()> {
+    let (weth, client, address) = weth().await?;
+    let alice = client.address();
+    let value = U256::from(1_000_000_000u64);
+    let balance_before = weth.balance_of(alice).call().await?;
+    let eth_before = client.get_balance(address, None).await?;
+
+    let tx = TransactionRequest::new().to(address).value(value);
+    let receipt = client.send_transaction(tx, None).await?.await?.expect("no receipt");
+
+    assert_eq!(receipt.status, Some(1.into()));
+    assert_eq!(weth.balance_of(alice).call().await?, balance_before + value);
+    assert_eq!(client.get_balance(address, None).await?, eth_before + value);
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore = "needs the deployed examples/weth.rs in STYLUS_PROGRAM_ADDRESS_WETH"]
+async fn eth_with_unknown_calldata_is_rejected() -> eyre::Result<()> {
+    let (_, client, address) = weth().await?;
+    let tx = TransactionRequest::new()
+        .to(address)
+        .value(U256::from(1_000u64))
+        .data(vec![0xde, 0xad, 0xbe, 0xef]);
+
+    assert!(client.send_transaction(tx, None).await.is_err());
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore = "needs the deployed examples/weth.rs in STYLUS_PROGRAM_ADDRESS_WETH"]
+async fn deposit_and_withdraw_round_trip() -> eyre::Result<()> {
+    let (weth, client, address) = weth().await?;
+    let alice = client.address();
+    let value = U256::from(5_000u64);
+    let balance_before = weth.balance_of(alice).call().await?;
+
+    weth.deposit().value(value).send().await?.await?;
+    assert_eq!(weth.balance_of(alice).call().await?, balance_before + value);
+
+    let eth_before = client.get_balance(address, None).await?;
+    weth.withdraw(value).send().await?.await?;
+    assert_eq!(weth.balance_of(alice).call().await?, balance_before);
+    assert_eq!(client.get_balance(address, None).await?, eth_before - value);
+    Ok(())
+}
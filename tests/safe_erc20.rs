@@ -0,0 +1,108 @@
+//! Calls built by `utils::safe_erc20` and their accepted return values, and the `try_permit`
+//! decisions: the frontrun case is a failed `permit` (nonce already used by the frontrunner) with
+//! the allowance already set by the frontrunner's copy of the permit.
+use ethers::{
+    abi::{self, Token},
+    types::U256 as EthersU256,
+};
+use oz_stylus_erc::utils::safe_erc20::{
+    approve_calldata, optional_return_succeeded, permit_outcome, transfer_calldata, transfer_from_calldata,
+    SafeErc20Error, SafeErc20FailedOperation,
+};
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    crypto::keccak,
+};
+
+const TOKEN: Address = Address::repeat_byte(0x70);
+
+fn read_fails() -> Result<U256, SafeErc20Error> {
+    Err(SafeErc20Error::SafeErc20FailedOperation(SafeErc20FailedOperation { token: TOKEN }))
+}
+
+#[test]
+fn successful_permit_does_not_read_the_allowance() {
+    let outcome = permit_outcome(TOKEN, true, U256::from(100), || panic!("allowance read"));
+    assert!(outcome.is_ok());
+}
+
+#[test]
+fn frontrun_permit_is_accepted_when_allowance_is_set() {
+    let value = U256::from(100);
+    // the frontrunner executed the same permit, the allowance is exactly `value`
+    assert!(permit_outcome(TOKEN, false, value, || Ok(value)).is_ok());
+    // or a previous approval already covers the deposit
+    assert!(permit_outcome(TOKEN, false, value, || Ok(U256::MAX)).is_ok());
+}
+
+#[test]
+fn failed_permit_without_allowance_reverts() {
+    let value = U256::from(100);
+    assert!(permit_outcome(TOKEN, false, value, || Ok(U256::ZERO)).is_err());
+    assert!(permit_outcome(TOKEN, false, value, || Ok(value - U256::from(1))).is_err());
+    assert!(permit_outcome(TOKEN, false, value, read_fails).is_err());
+}
+
+const FROM: Address = Address::repeat_byte(0xa1);
+const TO: Address = Address::repeat_byte(0xb0);
+
+fn encoded_call(signature: &str, args: &[Token]) -> Vec<u8> {
+    let mut data = keccak(signature.as_bytes())[..4].to_vec();
+    data.extend(abi::encode(args));
+    data
+}
+
+fn address_token(account: Address) -> Token {
+    Token::Address(account.0 .0.into())
+}
+
+fn true_word() -> Vec<u8> {
+    U256::from(1).to_be_bytes::<32>().to_vec()
+}
+
+#[test]
+fn calls_are_abi_encoded() {
+    let value = U256::from(1_000);
+    let value_token = Token::Uint(EthersU256::from(1_000));
+
+    assert_eq!(
+        transfer_calldata(TO, value),
+        encoded_call("transfer(address,uint256)", &[address_token(TO), value_token.clone()])
+    );
+    assert_eq!(
+        transfer_from_calldata(FROM, TO, value),
+        encoded_call(
+            "transferFrom(address,address,uint256)",
+            &[address_token(FROM), address_token(TO), value_token.clone()]
+        )
+    );
+    assert_eq!(
+        approve_calldata(TO, value),
+        encoded_call("approve(address,uint256)", &[address_token(TO), value_token])
+    );
+}
+
+#[test]
+fn returning_true_succeeds() {
+    assert!(optional_return_succeeded(&true_word(), || panic!("code read")));
+    // extra return data is ignored
+    let mut output = true_word();
+    output.extend_from_slice(&[0xff; 32]);
+    assert!(optional_return_succeeded(&output, || panic!("code read")));
+}
+
+#[test]
+fn returning_anything_else_fails() {
+    assert!(!optional_return_succeeded(&[0u8; 32], || true));
+    assert!(!optional_return_succeeded(&U256::from(2).to_be_bytes::<32>(), || true));
+    // a truncated `true`
+    assert!(!optional_return_succeeded(&true_word()[1..], || true));
+}
+
+#[test]
+fn returning_nothing_needs_code() {
+    // tokens returning nothing, e.g. USDT
+    assert!(optional_return_succeeded(&[], || true));
+    // an account without code never reverts
+    assert!(!optional_return_succeeded(&[], || false));
+}
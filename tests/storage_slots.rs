@@ -0,0 +1,77 @@
+//! Checks of the storage keys computed by `utils::storage_slots` against the off-chain helper
+//! (`tests/util/storage_slots.rs`), plus reads of the keys on the deployed token.
+use ethers::{
+    providers::Middleware,
+    signers::Signer,
+    types::{Address, H256, U256},
+};
+use oz_stylus_erc::utils::storage_slots::{erc20_allowance_slot, erc20_balance_slot};
+use std::str::FromStr;
+use std::sync::Arc;
+use stylus_sdk::alloy_primitives;
+
+mod util;
+use util::fixture_init;
+use util::storage_slots::{allowance_slot, balance_proof, balance_slot, BalanceSlotToken};
+
+fn to_alloy(address: Address) -> alloy_primitives::Address {
+    alloy_primitives::Address::from_slice(address.as_bytes())
+}
+
+#[test]
+fn balance_slot_is_solidity_mapping_slot() {
+    // `keccak256(abi.encode(0xbeef, slot))`, the key of `balanceOf(0xbeef)` in a Solidity token
+    // declaring its balances mapping at that slot
+    let account = Address::from_low_u64_be(0xbeef);
+    for (base_slot, expected) in [
+        (0u64, "0xf795696b84ec505a06e455ed35745d482b1c95debff7502f2dfa10a8a8820138"),
+        (5, "0x6bdfbd8dbb58bdf65a26c8acd4e9c78f79b986073466ecc3674aa1b325bf1951"),
+    ] {
+        let expected = H256::from_str(expected).unwrap();
+        let lib = erc20_balance_slot(alloy_primitives::U256::from(base_slot), to_alloy(account));
+        assert_eq!(lib.as_slice(), expected.as_bytes());
+        assert_eq!(balance_slot(U256::from(base_slot), account), expected);
+    }
+}
+
+#[test]
+fn allowance_slot_matches_helper() {
+    let owner = Address::from_low_u64_be(1);
+    let spender = Address::from_low_u64_be(2);
+    for epoch in [0u64, 1, 42] {
+        let lib = erc20_allowance_slot(
+            alloy_primitives::U256::ZERO,
+            to_alloy(owner),
+            alloy_primitives::U256::from(epoch),
+            to_alloy(spender),
+        );
+        let helper = allowance_slot(U256::zero(), owner, U256::from(epoch), spender);
+        assert_eq!(lib.as_slice(), helper.as_bytes());
+    }
+}
+
+#[tokio::test]
+async fn deployed_token_balance_slot_holds_balance() {
+    let devnet = fixture_init::devnet().await.unwrap();
+    let token = devnet.my_token().await.unwrap();
+    let provider = Arc::new(devnet.provider.clone());
+    let account = devnet.alice_wallet.address();
+
+    // give the account a balance, whatever ran before on the token
+    let minter = BalanceSlotToken::new(token, devnet.alice_client.clone());
+    minter.mint(account, U256::from(1000)).send().await.unwrap().await.unwrap();
+    let block = provider.get_block_number().await.unwrap().as_u64();
+
+    let contract = BalanceSlotToken::new(token, provider.clone());
+    let balance = contract.balance_of(account).block(block).call().await.unwrap();
+    assert!(!balance.is_zero());
+    let slot = contract.balance_slot(account).call().await.unwrap();
+    // MyToken composes its `Erc20` as first field
+    assert_eq!(H256::from(slot), balance_slot(U256::zero(), account));
+
+    let stored = provider.get_storage_at(token, H256::from(slot), Some(block.into())).await.unwrap();
+    assert_eq!(U256::from_big_endian(stored.as_bytes()), balance);
+
+    let proof = balance_proof(provider, token, U256::zero(), account, block).await.unwrap();
+    assert_eq!(proof.storage_proof[0].value, balance);
+}
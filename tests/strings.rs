@@ -0,0 +1,25 @@
+//! `utils::strings` and the token URIs of `Erc721Metadata` built with it.
+use oz_stylus_erc::extensions::erc721_metadata::token_uri;
+use oz_stylus_erc::utils::strings::{concat, to_decimal};
+use stylus_sdk::alloy_primitives::U256;
+
+#[test]
+fn decimal_matches_the_display_of_u256() {
+    for value in [U256::ZERO, U256::from(7), U256::from(10), U256::from(1_234_567_890u64), U256::MAX] {
+        assert_eq!(to_decimal(value), value.to_string());
+    }
+    assert_eq!(to_decimal(U256::MAX).len(), 78);
+}
+
+#[test]
+fn concat_joins_without_separator() {
+    assert_eq!(concat(&["ipfs://", "cid/", "42"]), "ipfs://cid/42");
+    assert_eq!(concat(&[]), "");
+}
+
+#[test]
+fn token_uri_appends_the_token_id_to_the_base_uri() {
+    assert_eq!(token_uri("ipfs://cid/", U256::from(42)), "ipfs://cid/42");
+    assert_eq!(token_uri("https://api.example/token/", U256::ZERO), "https://api.example/token/0");
+    assert_eq!(token_uri("", U256::from(42)), "");
+}
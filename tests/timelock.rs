@@ -0,0 +1,140 @@
+//! Operations of `governance::timelock` targeting the timelock itself, applied in place by
+//! `execute` since a Stylus program cannot call itself.
+//!
+//! The devnet test needs a fresh `examples/compose_timelock.rs`, deploy it without calling
+//! `init` and set `STYLUS_PROGRAM_ADDRESS_TIMELOCK_ADMIN`, then
+//! `cargo test --test timelock -- --ignored`.
+use ethers::{
+    abi::{self, Token},
+    middleware::SignerMiddleware,
+    prelude::abigen,
+    providers::{Http, Provider},
+    signers::{LocalWallet, Signer},
+    types::{Address as EthersAddress, Bytes as EthersBytes, U256 as EthersU256},
+};
+use oz_stylus_erc::access::access_control::role_id;
+use oz_stylus_erc::governance::timelock::{
+    decode_self_call, SelfCall, TimelockError, GRANT_ROLE_SELECTOR, PROPOSER_ROLE, RENOUNCE_ROLE_SELECTOR,
+    UPDATE_DELAY_SELECTOR,
+};
+use stylus_sdk::alloy_primitives::{Address, B256, U256};
+
+mod util;
+use util::fixture_init;
+
+abigen!(
+    Timelock,
+    r#"[
+        function init(uint256 min_delay, address[] proposers, address[] executors) external
+        function schedule(address target, uint256 value, bytes data, bytes32 predecessor, bytes32 salt, uint256 delay) external
+        function execute(address target, uint256 value, bytes payload, bytes32 predecessor, bytes32 salt) external payable
+        function getMinDelay() external view returns (uint256)
+        function hasRole(bytes32 role, address account) external view returns (bool)
+    ]"#
+);
+
+type TimelockType = Timelock<SignerMiddleware<Provider<Http>, LocalWallet>>;
+
+fn calldata(selector: [u8; 4], args: &[Token]) -> Vec<u8> {
+    let mut calldata = selector.to_vec();
+    calldata.extend(abi::encode(args));
+    calldata
+}
+
+fn is_unsupported(result: Result<SelfCall, TimelockError>) -> bool {
+    matches!(result, Err(TimelockError::TimelockUnsupportedSelfCall(_)))
+}
+
+#[test]
+fn update_delay_is_decoded() {
+    let payload = calldata(UPDATE_DELAY_SELECTOR, &[Token::Uint(EthersU256::from(3_600u64))]);
+
+    assert_eq!(decode_self_call(&payload).ok(), Some(SelfCall::UpdateDelay(U256::from(3_600u64))));
+}
+
+#[test]
+fn role_changes_are_decoded() {
+    let role = role_id(PROPOSER_ROLE);
+    let account = EthersAddress::repeat_byte(0x42);
+    let args = [Token::FixedBytes(role.to_vec()), Token::Address(account)];
+
+    assert_eq!(
+        decode_self_call(&calldata(GRANT_ROLE_SELECTOR, &args)).ok(),
+        Some(SelfCall::GrantRole(role, Address::repeat_byte(0x42)))
+    );
+    assert_eq!(
+        decode_self_call(&calldata(RENOUNCE_ROLE_SELECTOR, &args)).ok(),
+        Some(SelfCall::RenounceRole(role, Address::repeat_byte(0x42)))
+    );
+}
+
+#[test]
+fn other_methods_and_malformed_arguments_are_unsupported() {
+    // `schedule` on itself is not applied in place
+    assert!(is_unsupported(decode_self_call(&[0x01, 0xd5, 0x06, 0x2a])));
+    assert!(is_unsupported(decode_self_call(&[])));
+    assert!(is_unsupported(decode_self_call(&UPDATE_DELAY_SELECTOR)));
+
+    let mut too_long = calldata(UPDATE_DELAY_SELECTOR, &[Token::Uint(EthersU256::one())]);
+    too_long.push(0);
+    assert!(is_unsupported(decode_self_call(&too_long)));
+
+    // dirty high bytes of an address word
+    let mut dirty = calldata(
+        GRANT_ROLE_SELECTOR,
+        &[Token::FixedBytes(B256::ZERO.to_vec()), Token::Address(EthersAddress::zero())],
+    );
+    dirty[4 + 32] = 1;
+    assert!(is_unsupported(decode_self_call(&dirty)));
+}
+
+#[tokio::test]
+#[ignore = "needs a fresh examples/compose_timelock.rs in STYLUS_PROGRAM_ADDRESS_TIMELOCK_ADMIN"]
+async fn scheduled_update_delay_and_grant_role_are_executed() -> eyre::Result<()> {
+    let devnet = fixture_init::devnet().await?;
+    let address = devnet.program("timelock_admin").await?;
+    let timelock: TimelockType = Timelock::new(address, devnet.alice_client.clone());
+    let alice = devnet.alice_client.address();
+    let bob = devnet.bob_client.address();
+
+    // no delay, operations are ready once scheduled
+    timelock.init(EthersU256::zero(), vec![alice], vec![alice]).send().await?.await?;
+
+    let proposer = role_id(PROPOSER_ROLE).0;
+    let grant = EthersBytes::from(calldata(
+        GRANT_ROLE_SELECTOR,
+        &[Token::FixedBytes(proposer.to_vec()), Token::Address(bob)],
+    ));
+    timelock
+        .schedule(address, EthersU256::zero(), grant.clone(), [0; 32], [1; 32], EthersU256::zero())
+        .send()
+        .await?
+        .await?;
+    timelock
+        .execute(address, EthersU256::zero(), grant, [0; 32], [1; 32])
+        .send()
+        .await?
+        .await?;
+    assert!(timelock.has_role(proposer, bob).call().await?);
+
+    let update_delay = EthersBytes::from(calldata(UPDATE_DELAY_SELECTOR, &[Token::Uint(EthersU256::from(60u64))]));
+    timelock
+        .schedule(address, EthersU256::zero(), update_delay.clone(), [0; 32], [2; 32], EthersU256::zero())
+        .send()
+        .await?
+        .await?;
+    timelock
+        .execute(address, EthersU256::zero(), update_delay.clone(), [0; 32], [2; 32])
+        .send()
+        .await?
+        .await?;
+    assert_eq!(timelock.get_min_delay().call().await?, EthersU256::from(60u64));
+
+    // the new delay applies to the next operations
+    assert!(timelock
+        .schedule(address, EthersU256::zero(), update_delay, [0; 32], [3; 32], EthersU256::zero())
+        .send()
+        .await
+        .is_err());
+    Ok(())
+}
@@ -0,0 +1,25 @@
+//! Amounts minted by `finance::token_migrator` for the old tokens migrated.
+use oz_stylus_erc::finance::token_migrator::migrated_amount;
+use stylus_sdk::alloy_primitives::U256;
+
+#[test]
+fn equal_ratio_terms_migrate_one_to_one() {
+    let amount = U256::from(123_456_789u64);
+    assert_eq!(migrated_amount(amount, U256::from(1), U256::from(1)).ok(), Some(amount));
+    assert_eq!(migrated_amount(amount, U256::from(7), U256::from(7)).ok(), Some(amount));
+}
+
+#[test]
+fn ratio_rounds_down() {
+    // a 1:1000 redenomination, e.g. from 18 to 15 decimals
+    assert_eq!(migrated_amount(U256::from(2_999), U256::from(1), U256::from(1_000)).ok(), Some(U256::from(2)));
+    assert_eq!(migrated_amount(U256::from(999), U256::from(1), U256::from(1_000)).ok(), Some(U256::ZERO));
+    assert_eq!(migrated_amount(U256::from(5), U256::from(3), U256::from(2)).ok(), Some(U256::from(7)));
+}
+
+#[test]
+fn large_amounts_do_not_overflow_the_product() {
+    let amount = U256::MAX / U256::from(2);
+    assert_eq!(migrated_amount(amount, U256::from(2), U256::from(2)).ok(), Some(amount));
+    assert!(migrated_amount(U256::MAX, U256::from(2), U256::from(1)).is_err());
+}
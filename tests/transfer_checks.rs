@@ -0,0 +1,103 @@
+//! Receiver hook calldata built by hand in `utils::transfer_checks`, checked against
+//! `abi.encodeWithSelector` so receivers written in Solidity decode it.
+use ethers::abi::{self, Token};
+use oz_stylus_erc::utils::transfer_checks::{
+    erc1155_batch_received_calldata, erc1155_received_calldata, erc721_received_calldata, is_accepted,
+    ON_ERC1155_BATCH_RECEIVED_SELECTOR, ON_ERC1155_RECEIVED_SELECTOR, ON_ERC721_RECEIVED_SELECTOR,
+};
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    crypto::keccak,
+};
+
+const OPERATOR: Address = Address::repeat_byte(0x0a);
+const FROM: Address = Address::repeat_byte(0xf0);
+
+fn address(address: Address) -> Token {
+    Token::Address(address.0 .0.into())
+}
+
+fn uint(value: U256) -> Token {
+    Token::Uint(value.to_be_bytes::<32>().into())
+}
+
+fn with_selector(selector: [u8; 4], tokens: &[Token]) -> Vec<u8> {
+    let mut calldata = selector.to_vec();
+    calldata.extend(abi::encode(tokens));
+    calldata
+}
+
+#[test]
+fn selectors_match_the_receiver_signatures() {
+    for (selector, signature) in [
+        (ON_ERC721_RECEIVED_SELECTOR, "onERC721Received(address,address,uint256,bytes)"),
+        (ON_ERC1155_RECEIVED_SELECTOR, "onERC1155Received(address,address,uint256,uint256,bytes)"),
+        (
+            ON_ERC1155_BATCH_RECEIVED_SELECTOR,
+            "onERC1155BatchReceived(address,address,uint256[],uint256[],bytes)",
+        ),
+    ] {
+        assert_eq!(selector, keccak(signature)[..4], "{}", signature);
+    }
+}
+
+#[test]
+fn erc721_calldata_matches_abi_encode() {
+    for data in [vec![], vec![0x42; 5], vec![0x42; 64]] {
+        assert_eq!(
+            erc721_received_calldata(OPERATOR, FROM, U256::from(7), &data),
+            with_selector(
+                ON_ERC721_RECEIVED_SELECTOR,
+                &[address(OPERATOR), address(FROM), uint(U256::from(7)), Token::Bytes(data.clone())]
+            )
+        );
+    }
+}
+
+#[test]
+fn erc1155_calldata_matches_abi_encode() {
+    let data = vec![0x13; 33];
+    assert_eq!(
+        erc1155_received_calldata(OPERATOR, FROM, U256::from(1), U256::from(250), &data),
+        with_selector(
+            ON_ERC1155_RECEIVED_SELECTOR,
+            &[
+                address(OPERATOR),
+                address(FROM),
+                uint(U256::from(1)),
+                uint(U256::from(250)),
+                Token::Bytes(data)
+            ]
+        )
+    );
+}
+
+#[test]
+fn erc1155_batch_calldata_matches_abi_encode() {
+    let ids = [U256::from(1), U256::from(2), U256::from(3)];
+    let values = [U256::from(10), U256::ZERO, U256::MAX];
+    let data = vec![0x99; 3];
+    assert_eq!(
+        erc1155_batch_received_calldata(OPERATOR, FROM, &ids, &values, &data),
+        with_selector(
+            ON_ERC1155_BATCH_RECEIVED_SELECTOR,
+            &[
+                address(OPERATOR),
+                address(FROM),
+                Token::Array(ids.iter().copied().map(uint).collect()),
+                Token::Array(values.iter().copied().map(uint).collect()),
+                Token::Bytes(data)
+            ]
+        )
+    );
+}
+
+#[test]
+fn only_the_expected_selector_is_accepted() {
+    let mut output = ON_ERC721_RECEIVED_SELECTOR.to_vec();
+    output.resize(32, 0);
+    assert!(is_accepted(&output, ON_ERC721_RECEIVED_SELECTOR));
+    assert!(!is_accepted(&output, ON_ERC1155_RECEIVED_SELECTOR));
+    assert!(!is_accepted(&output[..3], ON_ERC721_RECEIVED_SELECTOR));
+    assert!(!is_accepted(&[], ON_ERC721_RECEIVED_SELECTOR));
+}
@@ -0,0 +1,136 @@
+//! Off-chain side of `Erc20BalancesRoot`: computes the merkle root of all balances of a token at
+//! a block from chain state. Leaves are `keccak256(keccak256(abi.encode(account, balance)))` sorted
+//! ascending, pairs are hashed sorted and an odd node is carried up a level.
+use ethers::{
+    abi::{encode, Token},
+    prelude::abigen,
+    providers::Middleware,
+    types::{Address, BlockId, BlockNumber, Filter, H256, U256},
+    utils::keccak256,
+};
+use eyre::Report;
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+abigen!(
+    BalanceOfToken,
+    r#"[
+        function totalSupply() external view returns (uint256)
+        function balanceOf(address account) external view returns (uint256)
+    ]"#
+);
+
+/// `keccak256("Transfer(address,address,uint256)")`
+const TRANSFER_TOPIC: &str = "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef";
+
+/// Balances of every account that ever received `token` up to `block`, zero balances skipped,
+/// sorted by account.
+pub async fn balances_at<M: Middleware + 'static>(
+    client: Arc<M>,
+    token: Address,
+    block: u64,
+) -> Result<Vec<(Address, U256)>, Report> {
+    let filter = Filter::new()
+        .address(token)
+        .topic0(TRANSFER_TOPIC.parse::<H256>()?)
+        .from_block(BlockNumber::Earliest)
+        .to_block(block);
+    let logs = client.get_logs(&filter).await.map_err(|e| eyre::eyre!("{e}"))?;
+    let holders: BTreeSet<Address> = logs
+        .iter()
+        .filter_map(|log| log.topics.get(2))
+        .map(|topic| Address::from(*topic))
+        .filter(|holder| *holder != Address::zero())
+        .collect();
+
+    let contract = BalanceOfToken::new(token, client);
+    let mut balances = Vec::new();
+    for holder in holders {
+        let balance = contract
+            .balance_of(holder)
+            .block(BlockId::Number(block.into()))
+            .call()
+            .await?;
+        if !balance.is_zero() {
+            balances.push((holder, balance));
+        }
+    }
+    Ok(balances)
+}
+
+/// Root to commit with `commit_balances_root(root, block)`.
+pub async fn balances_root<M: Middleware + 'static>(
+    client: Arc<M>,
+    token: Address,
+    block: u64,
+) -> Result<H256, Report> {
+    Ok(merkle_root(&balances_at(client, token, block).await?))
+}
+
+/// Same as `erc20_balances_root::balance_leaf`.
+pub fn balance_leaf(account: Address, balance: U256) -> H256 {
+    let encoded = encode(&[Token::Address(account), Token::Uint(balance)]);
+    H256(keccak256(keccak256(encoded)))
+}
+
+/// Merkle root of `balances`, zero for no balances.
+pub fn merkle_root(balances: &[(Address, U256)]) -> H256 {
+    let mut level: Vec<H256> = balances
+        .iter()
+        .map(|(account, balance)| balance_leaf(*account, *balance))
+        .collect();
+    if level.is_empty() {
+        return H256::zero();
+    }
+    level.sort();
+    while level.len() > 1 {
+        level = level
+            .chunks(2)
+            .map(|pair| match pair {
+                [a, b] => hash_pair(*a, *b),
+                [single] => *single,
+                _ => unreachable!(),
+            })
+            .collect();
+    }
+    level[0]
+}
+
+/// Proof of the leaf of `(account, balance)`, verified with sorted pair hashing.
+pub fn merkle_proof(balances: &[(Address, U256)], account: Address, balance: U256) -> Option<Vec<H256>> {
+    let mut level: Vec<H256> = balances
+        .iter()
+        .map(|(account, balance)| balance_leaf(*account, *balance))
+        .collect();
+    level.sort();
+    let mut index = level.iter().position(|leaf| *leaf == balance_leaf(account, balance))?;
+    let mut proof = Vec::new();
+    while level.len() > 1 {
+        let sibling = index ^ 1;
+        if sibling < level.len() {
+            proof.push(level[sibling]);
+        }
+        level = level
+            .chunks(2)
+            .map(|pair| match pair {
+                [a, b] => hash_pair(*a, *b),
+                [single] => *single,
+                _ => unreachable!(),
+            })
+            .collect();
+        index /= 2;
+    }
+    Some(proof)
+}
+
+pub fn verify_proof(proof: &[H256], root: H256, leaf: H256) -> bool {
+    proof.iter().fold(leaf, |hash, sibling| hash_pair(hash, *sibling)) == root
+}
+
+fn hash_pair(a: H256, b: H256) -> H256 {
+    let (low, high) = if a < b { (a, b) } else { (b, a) };
+    let mut buf = [0u8; 64];
+    buf[..32].copy_from_slice(low.as_bytes());
+    buf[32..].copy_from_slice(high.as_bytes());
+    H256(keccak256(buf))
+}
@@ -0,0 +1,157 @@
+//! Typed configuration of the devnet tests, read once from the environment (and `.env`).
+//!
+//! | variable | |
+//! |---|---|
+//! | `RPC_URL` | Stylus RPC endpoint, required |
+//! | `ALICE_PRIV_KEY_PATH`, `BOB_PRIV_KEY_PATH` | files holding the private keys of the test accounts |
+//! | `STYLUS_PROGRAM_ADDRESS` | deployed `MyToken`, the `my_token` preset |
+//! | `STYLUS_PROGRAM_ADDRESS_<PRESET>` | deployed program of another preset, e.g. `STYLUS_PROGRAM_ADDRESS_VOTES` for `votes` |
+//!
+//! Every variable is validated by `TestConfig::load`, errors name the variable and what is wrong
+//! with it. Keys and programs are only required by the tests asking for them.
+use dotenv::dotenv;
+use ethers::{
+    providers::{Http, Provider},
+    signers::LocalWallet,
+    types::Address,
+};
+use eyre::{eyre, Report};
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+/// Stylus RPC endpoint url.
+pub const RPC_URL: &str = "RPC_URL";
+
+/// Private key file path of the deployer, funded on the devnet.
+pub const ALICE_PRIV_KEY_PATH: &str = "ALICE_PRIV_KEY_PATH";
+
+/// Private key file path of the second test account.
+pub const BOB_PRIV_KEY_PATH: &str = "BOB_PRIV_KEY_PATH";
+
+/// Deployed program address, suffixed with `_<PRESET>` for presets other than `my_token`.
+pub const PROGRAM_ADDRESS_PREFIX: &str = "STYLUS_PROGRAM_ADDRESS";
+
+/// Preset of the `STYLUS_PROGRAM_ADDRESS` variable without suffix.
+pub const DEFAULT_PRESET: &str = "my_token";
+
+#[derive(Debug, Clone)]
+pub struct TestConfig {
+    pub rpc_url: String,
+    alice_key: Option<String>,
+    bob_key: Option<String>,
+    /// preset name (lowercase) => deployed program
+    programs: BTreeMap<String, Address>,
+}
+
+impl TestConfig {
+    /// Reads and validates the configuration from the environment, loading `.env` first.
+    pub fn load() -> Result<Self, Report> {
+        dotenv().ok();
+        Self::from_vars(std::env::vars())
+    }
+
+    /// Same as {load} from explicit variables, the environment is not read.
+    pub fn from_vars(vars: impl IntoIterator<Item = (String, String)>) -> Result<Self, Report> {
+        let vars: BTreeMap<String, String> = vars.into_iter().collect();
+
+        let rpc_url = vars
+            .get(RPC_URL)
+            .cloned()
+            .ok_or_else(|| eyre!("No {} env var set, add it to .env (e.g. http://localhost:8547)", RPC_URL))?;
+        if !rpc_url.starts_with("http://") && !rpc_url.starts_with("https://") {
+            return Err(eyre!("{} must be an http(s) url, got `{}`", RPC_URL, rpc_url));
+        }
+
+        let mut programs = BTreeMap::new();
+        for (name, value) in &vars {
+            let Some(suffix) = name.strip_prefix(PROGRAM_ADDRESS_PREFIX) else {
+                continue;
+            };
+            let preset = match suffix {
+                "" => DEFAULT_PRESET.to_string(),
+                suffix => match suffix.strip_prefix('_') {
+                    Some(preset) if !preset.is_empty() => preset.to_lowercase(),
+                    _ => continue,
+                },
+            };
+            let address = Address::from_str(value.trim())
+                .map_err(|e| eyre!("{} is not a valid address (`{}`): {}", name, value, e))?;
+            programs.insert(preset, address);
+        }
+
+        let alice_key = read_key(&vars, ALICE_PRIV_KEY_PATH)?;
+        let bob_key = read_key(&vars, BOB_PRIV_KEY_PATH)?;
+
+        Ok(Self {
+            rpc_url,
+            alice_key,
+            bob_key,
+            programs,
+        })
+    }
+
+    pub fn provider(&self) -> Result<Provider<Http>, Report> {
+        Ok(Provider::<Http>::try_from(self.rpc_url.as_str())?)
+    }
+
+    /// Deployed program of `preset`, `my_token` for `STYLUS_PROGRAM_ADDRESS`.
+    pub fn program(&self, preset: &str) -> Result<Address, Report> {
+        self.programs.get(&preset.to_lowercase()).copied().ok_or_else(|| {
+            eyre!(
+                "No program deployed for preset `{}`, set {}; configured presets: {:?}",
+                preset,
+                program_var(preset),
+                self.programs.keys().collect::<Vec<_>>()
+            )
+        })
+    }
+
+    /// Uses `alice_key` and `bob_key` for the accounts without key file, e.g. the prefunded
+    /// accounts of a dev node.
+    pub fn with_default_keys(mut self, alice_key: &str, bob_key: &str) -> Self {
+        self.alice_key.get_or_insert_with(|| alice_key.to_string());
+        self.bob_key.get_or_insert_with(|| bob_key.to_string());
+        self
+    }
+
+    /// Deployed `MyToken`.
+    pub fn my_token(&self) -> Result<Address, Report> {
+        self.program(DEFAULT_PRESET)
+    }
+
+    pub fn alice_wallet(&self) -> Result<LocalWallet, Report> {
+        wallet(&self.alice_key, ALICE_PRIV_KEY_PATH)
+    }
+
+    pub fn bob_wallet(&self) -> Result<LocalWallet, Report> {
+        wallet(&self.bob_key, BOB_PRIV_KEY_PATH)
+    }
+}
+
+/// Name of the variable holding the program of `preset`.
+pub fn program_var(preset: &str) -> String {
+    if preset.eq_ignore_ascii_case(DEFAULT_PRESET) {
+        return PROGRAM_ADDRESS_PREFIX.to_string();
+    }
+    format!("{}_{}", PROGRAM_ADDRESS_PREFIX, preset.to_uppercase())
+}
+
+/// Private key read from the file named by `var`, validated now so a bad key fails at load.
+fn read_key(vars: &BTreeMap<String, String>, var: &str) -> Result<Option<String>, Report> {
+    let Some(path) = vars.get(var) else {
+        return Ok(None);
+    };
+    let key = std::fs::read_to_string(path)
+        .map_err(|e| eyre!("{} points to `{}` which can not be read: {}", var, path, e))?
+        .trim()
+        .to_string();
+    LocalWallet::from_str(&key).map_err(|e| eyre!("{} file `{}` does not hold a private key: {}", var, path, e))?;
+    Ok(Some(key))
+}
+
+fn wallet(key: &Option<String>, var: &str) -> Result<LocalWallet, Report> {
+    let key = key
+        .as_deref()
+        .ok_or_else(|| eyre!("No {} env var set, it must point to a file holding a private key", var))?;
+    Ok(LocalWallet::from_str(key)?)
+}
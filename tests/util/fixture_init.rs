@@ -0,0 +1,297 @@
+//! Devnet fixture of the integration tests: attaches to a node or starts a local Stylus dev
+//! node, deploys the programs missing from the configuration and runs their `init()`, so the
+//! tests run from a clean checkout without hand deployed programs.
+//!
+//! | variable | |
+//! |---|---|
+//! | `STYLUS_FIXTURE` | `attach` (default): use `RPC_URL`; `dev-node`: start the nitro dev node in docker first |
+//! | `STYLUS_DEV_NODE_IMAGE` | docker image of the dev node, default `DEV_NODE_IMAGE` |
+//!
+//! Programs configured with `STYLUS_PROGRAM_ADDRESS[_<PRESET>]` are used as is. Other presets
+//! are compiled and deployed with `cargo stylus deploy` once per test binary, so each binary
+//! starts from a fresh token and tests do not depend on the state left by other binaries. On
+//! the dev node alice and bob default to fixed keys, bob is funded by alice.
+//!
+//! The dev node container is left running for the next test binaries, stop it with
+//! `docker stop stylus-dev-node`.
+use super::config::{TestConfig, BOB_PRIV_KEY_PATH, DEFAULT_PRESET, RPC_URL};
+use dotenv::dotenv;
+use ethers::{
+    middleware::SignerMiddleware,
+    providers::{Http, Middleware, Provider},
+    signers::{LocalWallet, Signer},
+    types::{Address, TransactionRequest, U256},
+};
+use eyre::{eyre, Report};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::process::Command;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{Mutex, OnceCell};
+
+/// Fixture mode variable.
+pub const STYLUS_FIXTURE: &str = "STYLUS_FIXTURE";
+
+/// Dev node image variable.
+pub const STYLUS_DEV_NODE_IMAGE: &str = "STYLUS_DEV_NODE_IMAGE";
+
+/// Nitro dev node image with Stylus enabled.
+pub const DEV_NODE_IMAGE: &str = "offchainlabs/nitro-node:v3.2.1-d81324d";
+
+/// Name of the dev node container, reused when already running.
+pub const DEV_NODE_CONTAINER: &str = "stylus-dev-node";
+
+/// RPC endpoint of the dev node.
+pub const DEV_NODE_RPC_URL: &str = "http://localhost:8547";
+
+/// Prefunded account of the nitro dev node, alice on the dev node.
+pub const DEV_NODE_ALICE_KEY: &str = "0xb6b15c8cb491557369f3c7d2c287b053eb229daa9c22138887752191c9520659";
+
+/// Fixed second account of the dev node, funded by alice.
+pub const DEV_NODE_BOB_KEY: &str = "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d";
+
+/// Ether sent to bob on the dev node when bob has less.
+const BOB_FUNDING_WEI: u128 = 1_000_000_000_000_000_000;
+
+/// Seconds waited for the dev node to answer after starting it.
+const DEV_NODE_STARTUP_SECS: u64 = 60;
+
+/// `bytes4(keccak256("init()"))`
+const INIT_SELECTOR: [u8; 4] = [0xe1, 0xc7, 0x39, 0x2a];
+
+pub type SignerClient = Arc<SignerMiddleware<Provider<Http>, LocalWallet>>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FixtureMode {
+    /// Node at `RPC_URL`, already running.
+    Attach,
+    /// Local nitro dev node in docker, started when not running.
+    DevNode,
+}
+
+impl FromStr for FixtureMode {
+    type Err = Report;
+
+    fn from_str(value: &str) -> Result<Self, Report> {
+        match value.trim() {
+            "" | "attach" => Ok(FixtureMode::Attach),
+            "dev-node" => Ok(FixtureMode::DevNode),
+            other => Err(eyre!("{} must be `attach` or `dev-node`, got `{}`", STYLUS_FIXTURE, other)),
+        }
+    }
+}
+
+/// How the program of a preset is obtained when not configured.
+struct Preset {
+    name: &'static str,
+    /// program built by `cargo stylus` from the crate root, the only deployable one: the
+    /// `compose_*` examples are not built as wasm libraries yet, configure their addresses
+    deployable: bool,
+    /// whether `init()` must be called once after the deployment
+    init: bool,
+}
+
+const PRESETS: &[Preset] = &[Preset {
+    name: DEFAULT_PRESET,
+    deployable: true,
+    init: false,
+}];
+
+/// Node, test accounts and programs shared by the tests of a binary.
+pub struct Devnet {
+    pub config: TestConfig,
+    pub provider: Provider<Http>,
+    pub chain_id: u64,
+    pub alice_wallet: LocalWallet,
+    pub bob_wallet: LocalWallet,
+    pub alice_client: SignerClient,
+    pub bob_client: SignerClient,
+    /// preset => program deployed by this fixture
+    deployed: Mutex<BTreeMap<String, Address>>,
+}
+
+static DEVNET: OnceCell<Devnet> = OnceCell::const_new();
+
+/// Devnet of the test binary, set up on first use.
+pub async fn devnet() -> Result<&'static Devnet, Report> {
+    DEVNET.get_or_try_init(setup).await
+}
+
+impl Devnet {
+    /// Program of `preset`: the configured address, or a program deployed (and initialized) by
+    /// this fixture on first request.
+    pub async fn program(&self, preset: &str) -> Result<Address, Report> {
+        if let Ok(address) = self.config.program(preset) {
+            return Ok(address);
+        }
+        let mut deployed = self.deployed.lock().await;
+        if let Some(address) = deployed.get(preset) {
+            return Ok(*address);
+        }
+        let Some(spec) = PRESETS.iter().find(|spec| spec.name == preset && spec.deployable) else {
+            // reports the variable to set
+            return self.config.program(preset);
+        };
+        let address = deploy(&self.config.rpc_url, &self.alice_wallet)?;
+        if spec.init {
+            self.init(address).await?;
+        }
+        deployed.insert(preset.to_string(), address);
+        Ok(address)
+    }
+
+    /// `MyToken`, deployed when not configured.
+    pub async fn my_token(&self) -> Result<Address, Report> {
+        self.program(DEFAULT_PRESET).await
+    }
+
+    /// Calls `init()` of `program` as alice.
+    async fn init(&self, program: Address) -> Result<(), Report> {
+        let tx = TransactionRequest::new().to(program).data(INIT_SELECTOR.to_vec());
+        let receipt = self
+            .alice_client
+            .send_transaction(tx, None)
+            .await?
+            .await?
+            .ok_or_else(|| eyre!("init() of {:?} returned no receipt", program))?;
+        if receipt.status != Some(1.into()) {
+            return Err(eyre!("init() of {:?} reverted in {:?}", program, receipt.transaction_hash));
+        }
+        Ok(())
+    }
+}
+
+async fn setup() -> Result<Devnet, Report> {
+    dotenv().ok();
+    let mut vars: BTreeMap<String, String> = std::env::vars().collect();
+    let mode = FixtureMode::from_str(vars.get(STYLUS_FIXTURE).map(String::as_str).unwrap_or(""))?;
+
+    if mode == FixtureMode::DevNode {
+        let image = vars.get(STYLUS_DEV_NODE_IMAGE).cloned().unwrap_or_else(|| DEV_NODE_IMAGE.to_string());
+        start_dev_node(&image)?;
+        vars.entry(RPC_URL.to_string()).or_insert_with(|| DEV_NODE_RPC_URL.to_string());
+    }
+    let mut config = TestConfig::from_vars(vars.clone())?;
+    if mode == FixtureMode::DevNode {
+        config = config.with_default_keys(DEV_NODE_ALICE_KEY, DEV_NODE_BOB_KEY);
+    }
+
+    let provider = config.provider()?;
+    let chain_id = wait_for_node(&provider, &config.rpc_url, mode).await?;
+
+    let alice_wallet = config.alice_wallet()?;
+    let bob_wallet = config.bob_wallet()?;
+    let alice_client = Arc::new(SignerMiddleware::new(
+        provider.clone(),
+        alice_wallet.clone().with_chain_id(chain_id),
+    ));
+    let bob_client = Arc::new(SignerMiddleware::new(
+        provider.clone(),
+        bob_wallet.clone().with_chain_id(chain_id),
+    ));
+
+    if mode == FixtureMode::DevNode && !vars.contains_key(BOB_PRIV_KEY_PATH) {
+        fund(&alice_client, bob_wallet.address()).await?;
+    }
+
+    Ok(Devnet {
+        config,
+        provider,
+        chain_id,
+        alice_wallet,
+        bob_wallet,
+        alice_client,
+        bob_client,
+        deployed: Mutex::new(BTreeMap::new()),
+    })
+}
+
+/// Starts the dev node container unless it is already running.
+fn start_dev_node(image: &str) -> Result<(), Report> {
+    let running = Command::new("docker")
+        .args(["ps", "--quiet", "--filter", &format!("name=^{}$", DEV_NODE_CONTAINER)])
+        .output()
+        .map_err(|e| eyre!("docker is required by {}=dev-node: {}", STYLUS_FIXTURE, e))?;
+    if !String::from_utf8_lossy(&running.stdout).trim().is_empty() {
+        return Ok(());
+    }
+    let output = Command::new("docker")
+        .args(["run", "--detach", "--rm", "--name", DEV_NODE_CONTAINER, "-p", "8547:8547", image])
+        .args(["--dev", "--http.addr", "0.0.0.0", "--http.api=net,web3,eth,debug"])
+        .output()?;
+    if !output.status.success() {
+        return Err(eyre!(
+            "starting the dev node ({}) failed: {}",
+            image,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(())
+}
+
+/// Chain id of the node, retried while a dev node starts.
+async fn wait_for_node(provider: &Provider<Http>, rpc_url: &str, mode: FixtureMode) -> Result<u64, Report> {
+    let attempts = match mode {
+        FixtureMode::Attach => 1,
+        FixtureMode::DevNode => DEV_NODE_STARTUP_SECS,
+    };
+    for _ in 1..attempts {
+        if let Ok(chain_id) = provider.get_chainid().await {
+            return Ok(chain_id.as_u64());
+        }
+        tokio::time::sleep(Duration::from_secs(1)).await;
+    }
+    let chain_id = provider
+        .get_chainid()
+        .await
+        .map_err(|e| eyre!("no node answers at {}: {}", rpc_url, e))?;
+    Ok(chain_id.as_u64())
+}
+
+async fn fund(alice_client: &SignerClient, bob: Address) -> Result<(), Report> {
+    let amount = U256::from(BOB_FUNDING_WEI);
+    if alice_client.get_balance(bob, None).await? >= amount {
+        return Ok(());
+    }
+    let tx = TransactionRequest::new().to(bob).value(amount);
+    alice_client.send_transaction(tx, None).await?.await?;
+    Ok(())
+}
+
+/// Compiles the crate program and deploys it with `cargo stylus deploy`, returns its address.
+fn deploy(rpc_url: &str, deployer: &LocalWallet) -> Result<Address, Report> {
+    let key = format!("0x{}", hex_string(&deployer.signer().to_bytes()));
+    let output = Command::new("cargo")
+        .current_dir(Path::new(env!("CARGO_MANIFEST_DIR")))
+        .args(["stylus", "deploy", "--endpoint", rpc_url, "--private-key", &key])
+        .output()
+        .map_err(|e| eyre!("`cargo stylus` is required to deploy test programs: {}", e))?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if !output.status.success() {
+        return Err(eyre!(
+            "cargo stylus deploy failed:\n{}\n{}",
+            stdout,
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+    deployed_address(&stdout).ok_or_else(|| eyre!("no deployed address in cargo stylus output:\n{}", stdout))
+}
+
+/// Address of the last line of `cargo stylus deploy` output mentioning a deployed address.
+pub fn deployed_address(output: &str) -> Option<Address> {
+    output
+        .lines()
+        .filter(|line| line.to_lowercase().contains("address"))
+        .filter_map(|line| {
+            let start = line.find("0x")?;
+            let candidate: String = line[start..].chars().take(42).collect();
+            Address::from_str(&candidate).ok()
+        })
+        .last()
+}
+
+fn hex_string(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
@@ -0,0 +1,8 @@
+//! Helpers shared by the integration tests, included with `mod util;`. Not every test uses
+//! every helper.
+#![allow(dead_code)]
+
+pub mod balances_root;
+pub mod config;
+pub mod fixture_init;
+pub mod storage_slots;
@@ -0,0 +1,58 @@
+//! Off-chain side of `utils::storage_slots`: storage keys of `Erc20` balances and allowances in a
+//! deployed token and the `eth_getProof` of a balance, for light clients proving balances against
+//! a state root.
+use ethers::{
+    abi::{encode, Token},
+    prelude::abigen,
+    providers::Middleware,
+    types::{Address, BlockId, EIP1186ProofResponse, H256, U256},
+    utils::keccak256,
+};
+use eyre::Report;
+use std::sync::Arc;
+
+abigen!(
+    BalanceSlotToken,
+    r#"[
+        function balanceOf(address account) external view returns (uint256)
+        function balanceSlot(address account) external view returns (bytes32)
+        function mint(address account, uint256 amount) external
+    ]"#
+);
+
+/// `keccak256(abi.encode(key, slot))`
+pub fn mapping_slot(key: Token, slot: U256) -> U256 {
+    U256::from_big_endian(&keccak256(encode(&[key, Token::Uint(slot)])))
+}
+
+/// Storage key of the balance of `account`, `base_slot` being the first slot of the `Erc20`.
+pub fn balance_slot(base_slot: U256, account: Address) -> H256 {
+    to_h256(mapping_slot(Token::Address(account), base_slot))
+}
+
+/// Storage key of the allowance of `spender` over `owner`'s tokens in allowance `epoch`.
+pub fn allowance_slot(base_slot: U256, owner: Address, epoch: U256, spender: Address) -> H256 {
+    let owner_slot = mapping_slot(Token::Address(owner), base_slot + 1);
+    let epoch_slot = mapping_slot(Token::Uint(epoch), owner_slot);
+    to_h256(mapping_slot(Token::Address(spender), epoch_slot))
+}
+
+/// Account and storage proof of the balance of `account` at `block`.
+pub async fn balance_proof<M: Middleware + 'static>(
+    client: Arc<M>,
+    token: Address,
+    base_slot: U256,
+    account: Address,
+    block: u64,
+) -> Result<EIP1186ProofResponse, Report> {
+    client
+        .get_proof(token, vec![balance_slot(base_slot, account)], Some(BlockId::from(block)))
+        .await
+        .map_err(|e| eyre::eyre!("{e}"))
+}
+
+fn to_h256(value: U256) -> H256 {
+    let mut bytes = [0u8; 32];
+    value.to_big_endian(&mut bytes);
+    H256::from(bytes)
+}
@@ -0,0 +1,213 @@
+//! Zero value and self transfers through the extensions hooking transfers: they move nothing out
+//! of the sender, so outbound limits neither reject nor count them and no fee is charged; a
+//! paused token still rejects them, a zero mint at the cap is accepted.
+//!
+//! The devnet tests need fresh tokens deployed without calling `init`:
+//! `examples/compose_rate_limit.rs` in `STYLUS_PROGRAM_ADDRESS_RATE_LIMIT`,
+//! `examples/compose_transfer_cooldown.rs` in `STYLUS_PROGRAM_ADDRESS_TRANSFER_COOLDOWN`,
+//! `examples/compose_fee.rs` in `STYLUS_PROGRAM_ADDRESS_FEE` and `examples/compose_pausable.rs`
+//! in `STYLUS_PROGRAM_ADDRESS_PAUSABLE`, then `cargo test --test zero_value_transfers -- --ignored`.
+use ethers::{
+    middleware::SignerMiddleware,
+    prelude::abigen,
+    providers::{Http, Provider},
+    signers::{LocalWallet, Signer},
+    types::{Address as EthersAddress, U256 as EthersU256},
+};
+use oz_stylus_erc::extensions::erc20_cap::check_cap;
+use oz_stylus_erc::extensions::erc20_fee::{fee_on, MAX_FEE_BPS};
+use oz_stylus_erc::tokens::erc20::moves_out;
+use stylus_sdk::alloy_primitives::{Address, U256};
+
+mod util;
+use util::fixture_init;
+
+abigen!(
+    RateLimitedToken,
+    r#"[
+        function init() external
+        function mint(address account, uint256 value) external
+        function transfer(address to, uint256 value) external returns (bool)
+        function transferFrom(address from, address to, uint256 value) external returns (bool)
+        function setRateLimit(uint256 max_amount, uint256 max_transfers, uint256 window) external
+        function remainingOutbound(address account) external view returns (uint256, uint256)
+    ]"#
+);
+
+abigen!(
+    LaunchToken,
+    r#"[
+        function init(uint256 cooldown) external
+        function mint(address account, uint256 value) external
+        function transfer(address to, uint256 value) external returns (bool)
+        function transferFrom(address from, address to, uint256 value) external returns (bool)
+        function cooldownRemaining(address account) external view returns (uint256)
+    ]"#
+);
+
+abigen!(
+    FeeToken,
+    r#"[
+        function init() external
+        function mint(address account, uint256 value) external
+        function transfer(address to, uint256 value) external returns (bool)
+        function setFee(uint256 fee_bps) external
+        function setFeeCollector(address collector) external
+        function balanceOf(address account) external view returns (uint256)
+    ]"#
+);
+
+abigen!(
+    PausableToken,
+    r#"[
+        function init() external
+        function mint(address account, uint256 value) external
+        function pause() external
+        function transfer(address to, uint256 value) external returns (bool)
+        function transferFrom(address from, address to, uint256 value) external returns (bool)
+    ]"#
+);
+
+type Client = SignerMiddleware<Provider<Http>, LocalWallet>;
+
+#[test]
+fn zero_value_and_self_transfers_move_nothing_out() {
+    let alice = Address::repeat_byte(0x11);
+    let bob = Address::repeat_byte(0x22);
+
+    assert!(moves_out(alice, bob, U256::from(1)));
+    assert!(!moves_out(alice, bob, U256::ZERO));
+    assert!(!moves_out(alice, alice, U256::from(1)));
+    assert!(!moves_out(alice, alice, U256::ZERO));
+}
+
+#[test]
+fn zero_value_transfers_pay_no_fee() {
+    let max_fee = U256::from(MAX_FEE_BPS);
+
+    assert_eq!(fee_on(U256::ZERO, max_fee), U256::ZERO);
+    // rounded down, dust transfers pay nothing either
+    assert_eq!(fee_on(U256::from(9), max_fee), U256::ZERO);
+    assert_eq!(fee_on(U256::from(10), max_fee), U256::from(1));
+    assert_eq!(fee_on(U256::MAX, max_fee), U256::MAX / U256::from(10));
+}
+
+#[test]
+fn zero_mint_at_the_cap_is_allowed() {
+    let cap = U256::from(1_000);
+
+    assert!(check_cap(cap, cap, U256::ZERO).is_ok());
+    assert!(check_cap(cap, cap, U256::from(1)).is_err());
+}
+
+#[tokio::test]
+#[ignore = "needs a fresh examples/compose_rate_limit.rs in STYLUS_PROGRAM_ADDRESS_RATE_LIMIT"]
+async fn rate_limit_does_not_count_zero_value_and_self_transfers() -> eyre::Result<()> {
+    let devnet = fixture_init::devnet().await?;
+    let address = devnet.program("rate_limit").await?;
+    let as_alice: RateLimitedToken<Client> = RateLimitedToken::new(address, devnet.alice_client.clone());
+    let as_bob: RateLimitedToken<Client> = RateLimitedToken::new(address, devnet.bob_client.clone());
+    let alice = devnet.alice_client.address();
+    let bob = devnet.bob_client.address();
+
+    // one transfer of at most 100 tokens per hour
+    as_alice.init().send().await?.await?;
+    as_alice.mint(alice, EthersU256::from(1_000)).send().await?.await?;
+    as_alice
+        .set_rate_limit(EthersU256::from(100), EthersU256::one(), EthersU256::from(3_600))
+        .send()
+        .await?
+        .await?;
+
+    as_alice.transfer(bob, EthersU256::zero()).send().await?.await?;
+    // above the amount limit, but nothing leaves alice
+    as_alice.transfer(alice, EthersU256::from(500)).send().await?.await?;
+    // a third party can not use up the transfer count of alice
+    as_bob.transfer_from(alice, bob, EthersU256::zero()).send().await?.await?;
+    assert_eq!(
+        as_alice.remaining_outbound(alice).call().await?,
+        (EthersU256::from(100), EthersU256::one())
+    );
+
+    as_alice.transfer(bob, EthersU256::from(100)).send().await?.await?;
+    assert_eq!(as_alice.remaining_outbound(alice).call().await?, (EthersU256::zero(), EthersU256::zero()));
+    as_alice.transfer(bob, EthersU256::zero()).send().await?.await?;
+    assert!(as_alice.transfer(bob, EthersU256::one()).send().await.is_err());
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore = "needs a fresh examples/compose_transfer_cooldown.rs in STYLUS_PROGRAM_ADDRESS_TRANSFER_COOLDOWN"]
+async fn cooldown_is_not_started_by_zero_value_and_self_transfers() -> eyre::Result<()> {
+    let devnet = fixture_init::devnet().await?;
+    let address = devnet.program("transfer_cooldown").await?;
+    let as_alice: LaunchToken<Client> = LaunchToken::new(address, devnet.alice_client.clone());
+    let as_bob: LaunchToken<Client> = LaunchToken::new(address, devnet.bob_client.clone());
+    let alice = devnet.alice_client.address();
+    let bob = devnet.bob_client.address();
+
+    as_alice.init(EthersU256::from(3_600)).send().await?.await?;
+    as_alice.mint(alice, EthersU256::from(1_000)).send().await?.await?;
+
+    as_alice.transfer(bob, EthersU256::zero()).send().await?.await?;
+    as_alice.transfer(alice, EthersU256::from(10)).send().await?.await?;
+    as_bob.transfer_from(alice, bob, EthersU256::zero()).send().await?.await?;
+    assert_eq!(as_alice.cooldown_remaining(alice).call().await?, EthersU256::zero());
+
+    as_alice.transfer(bob, EthersU256::from(10)).send().await?.await?;
+    assert!(as_alice.cooldown_remaining(alice).call().await? > EthersU256::zero());
+    // zero value and self transfers do not wait for the cooldown either
+    as_alice.transfer(bob, EthersU256::zero()).send().await?.await?;
+    as_alice.transfer(alice, EthersU256::from(10)).send().await?.await?;
+    assert!(as_alice.transfer(bob, EthersU256::one()).send().await.is_err());
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore = "needs a fresh examples/compose_fee.rs in STYLUS_PROGRAM_ADDRESS_FEE"]
+async fn fee_is_not_charged_on_zero_value_and_self_transfers() -> eyre::Result<()> {
+    let devnet = fixture_init::devnet().await?;
+    let address = devnet.program("fee").await?;
+    let as_alice: FeeToken<Client> = FeeToken::new(address, devnet.alice_client.clone());
+    let alice = devnet.alice_client.address();
+    let bob = devnet.bob_client.address();
+    let collector = EthersAddress::repeat_byte(0xfe);
+
+    // 10%
+    as_alice.init().send().await?.await?;
+    as_alice.set_fee(EthersU256::from(MAX_FEE_BPS)).send().await?.await?;
+    as_alice.set_fee_collector(collector).send().await?.await?;
+    as_alice.mint(alice, EthersU256::from(1_000)).send().await?.await?;
+
+    as_alice.transfer(bob, EthersU256::zero()).send().await?.await?;
+    as_alice.transfer(alice, EthersU256::from(500)).send().await?.await?;
+    assert_eq!(as_alice.balance_of(alice).call().await?, EthersU256::from(1_000));
+    assert_eq!(as_alice.balance_of(collector).call().await?, EthersU256::zero());
+
+    as_alice.transfer(bob, EthersU256::from(500)).send().await?.await?;
+    assert_eq!(as_alice.balance_of(bob).call().await?, EthersU256::from(450));
+    assert_eq!(as_alice.balance_of(collector).call().await?, EthersU256::from(50));
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore = "needs a fresh examples/compose_pausable.rs in STYLUS_PROGRAM_ADDRESS_PAUSABLE"]
+async fn paused_token_rejects_zero_value_and_self_transfers() -> eyre::Result<()> {
+    let devnet = fixture_init::devnet().await?;
+    let address = devnet.program("pausable").await?;
+    let as_alice: PausableToken<Client> = PausableToken::new(address, devnet.alice_client.clone());
+    let as_bob: PausableToken<Client> = PausableToken::new(address, devnet.bob_client.clone());
+    let alice = devnet.alice_client.address();
+    let bob = devnet.bob_client.address();
+
+    as_alice.init().send().await?.await?;
+    as_alice.mint(alice, EthersU256::from(1_000)).send().await?.await?;
+    as_alice.pause().send().await?.await?;
+
+    // as in OpenZeppelin, a paused token moves nothing, whatever the value
+    assert!(as_alice.transfer(bob, EthersU256::zero()).send().await.is_err());
+    assert!(as_alice.transfer(alice, EthersU256::from(10)).send().await.is_err());
+    assert!(as_bob.transfer_from(alice, bob, EthersU256::zero()).send().await.is_err());
+    assert!(as_alice.mint(alice, EthersU256::zero()).send().await.is_err());
+    Ok(())
+}